@@ -0,0 +1,42 @@
+---- MODULE example_protocol ----
+EXTENDS Naturals, Sequences, TLC
+
+CONSTANTS Nodes, MaxMessages
+
+VARIABLES 
+    messages,    \* Messages in transit
+    nodeState    \* State of each node
+
+TypeOK == 
+    /\ messages \in Seq(Nat)
+    /\ nodeState \in [Nodes -> {"init", "ready", "done"}]
+
+Init ==
+    /\ messages = <<>>
+    /\ nodeState = [n \in Nodes |-> "init"]
+
+SendMessage(sender, receiver) ==
+    /\ nodeState[sender] = "ready"
+    /\ messages' = Append(messages, receiver)
+    /\ UNCHANGED nodeState
+
+ReceiveMessage(receiver) ==
+    /\ Len(messages) > 0
+    /\ Head(messages) = receiver
+    /\ nodeState[receiver] = "ready"
+    /\ nodeState' = [nodeState EXCEPT ![receiver] = "done"]
+    /\ messages' = Tail(messages)
+
+Next ==
+    \/ \E sender, receiver \in Nodes : SendMessage(sender, receiver)
+    \/ \E receiver \in Nodes : ReceiveMessage(receiver)
+
+Spec == Init /\ [][Next]_<<messages, nodeState>>
+
+\* Safety property: No message is lost
+NoMessageLoss == Len(messages) <= MaxMessages
+
+\* Liveness property: All nodes eventually reach done state
+AllNodesComplete == <>(\A n \in Nodes : nodeState[n] = "done")
+
+====
@@ -0,0 +1,18 @@
+// Example session type definition
+// This would be expanded by FerrisProof macros
+
+use ferris_proof_macros::session_type;
+
+#[session_type]
+pub enum ProtocolState {
+    Init,
+    Connected { peer_id: String },
+    Authenticated { user_id: u64 },
+    Closed,
+}
+
+// Example usage:
+// let protocol = ProtocolState::Init;
+// let protocol = protocol.connect("peer123")?;
+// let protocol = protocol.authenticate(42)?;
+// protocol.close();
@@ -0,0 +1,283 @@
+use crate::commands::init::techniques_for_level;
+use crate::commands::upgrade::collect_files;
+use ferris_proof_config::module_tree::ModuleTree;
+use ferris_proof_config::{Config, ModuleConfig, ProfileConfig};
+use ferris_proof_core::{EnforcementMode, Technique, VerificationLevel};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const ASYNC_MARKERS: [&str; 2] = ["async fn", ".await"];
+const UNSAFE_MARKERS: [&str; 3] = ["unsafe {", "unsafe fn", "unsafe impl"];
+const CONCURRENCY_MARKERS: [&str; 8] = [
+    "Mutex<",
+    "RwLock<",
+    "Condvar",
+    "Atomic",
+    "mpsc::",
+    "thread::spawn",
+    "tokio::spawn",
+    "crossbeam",
+];
+const PUBLIC_ITEM_MARKERS: [&str; 4] = ["pub fn ", "pub struct ", "pub enum ", "pub trait "];
+
+/// Loop bound for Kani harnesses suggested for modules with `unsafe` code
+const KANI_UNWIND: u32 = 10;
+
+/// What a module's source says about which techniques pay off
+#[derive(Debug, Clone)]
+pub struct ModuleSignals {
+    pub module_path: String,
+    pub file: PathBuf,
+    pub is_async: bool,
+    pub unsafe_blocks: usize,
+    pub concurrency: bool,
+    pub public_items: usize,
+}
+
+impl ModuleSignals {
+    /// Concurrency testing is worth it for shared state, and for async code on tokio
+    fn wants_concurrency_testing(&self, uses_tokio: bool) -> bool {
+        self.concurrency || (self.is_async && uses_tokio)
+    }
+}
+
+/// Signals gathered from an existing crate, used by `init --analyze`
+#[derive(Debug, Clone, Default)]
+pub struct ProjectAnalysis {
+    pub uses_tokio: bool,
+    /// TLA+ or Alloy specs are already present
+    pub has_specs: bool,
+    pub modules: Vec<ModuleSignals>,
+}
+
+impl ProjectAnalysis {
+    /// Scan the modules reachable from the crate roots below `project_root`
+    pub fn analyze(project_root: &Path) -> Self {
+        let tree = ModuleTree::build(project_root);
+        let files = collect_files(project_root);
+        let extension_is =
+            |path: &Path, ext: &str| path.extension().and_then(|e| e.to_str()) == Some(ext);
+
+        let modules = files
+            .iter()
+            .filter(|path| extension_is(path, "rs"))
+            .filter_map(|path| {
+                let module_path = tree.module_path(path)?.to_string();
+                let content = fs::read_to_string(path).ok()?;
+                Some(scan_module(module_path, path, &content))
+            })
+            .collect();
+
+        Self {
+            uses_tokio: files
+                .iter()
+                .filter(|path| path.file_name().is_some_and(|name| name == "Cargo.toml"))
+                .any(|manifest| depends_on(manifest, "tokio")),
+            has_specs: files
+                .iter()
+                .any(|path| extension_is(path, "tla") || extension_is(path, "als")),
+            modules,
+        }
+    }
+
+    /// Level for the project as a whole; risky modules are raised by overrides
+    pub fn recommended_level(&self) -> VerificationLevel {
+        if self.has_specs {
+            VerificationLevel::Formal
+        } else if self.modules.iter().any(|m| m.public_items > 0) {
+            VerificationLevel::Standard
+        } else {
+            VerificationLevel::Minimal
+        }
+    }
+
+    /// Overrides for modules with unsafe code or concurrency, keyed by module path
+    pub fn module_overrides(&self, level: VerificationLevel) -> HashMap<String, ModuleConfig> {
+        self.modules
+            .iter()
+            .filter_map(|module| {
+                let concurrent = module.wants_concurrency_testing(self.uses_tokio);
+                if !concurrent && module.unsafe_blocks == 0 {
+                    return None;
+                }
+
+                let mut techniques = techniques_for_level(level);
+                if concurrent && !techniques.contains(&Technique::ConcurrencyTesting) {
+                    techniques.push(Technique::ConcurrencyTesting);
+                }
+                let override_config = ModuleConfig {
+                    level: (module.unsafe_blocks > 0 && level < VerificationLevel::Strict)
+                        .then_some(VerificationLevel::Strict),
+                    enforcement: (module.unsafe_blocks > 0).then_some(EnforcementMode::Error),
+                    enabled_techniques: Some(techniques),
+                    spec_file: None,
+                    mutation_threshold: None,
+                    enforcement_policy: None,
+                };
+                Some((module.module_path.clone(), override_config))
+            })
+            .collect()
+    }
+
+    /// Config for `level` with overrides for the modules that need more
+    pub fn tailored_config(&self, level: VerificationLevel) -> Config {
+        let mut config = Config {
+            profile: ProfileConfig {
+                level,
+                enforcement: EnforcementMode::Warning,
+                enabled_techniques: techniques_for_level(level),
+            },
+            ..Default::default()
+        };
+        config.modules = self.module_overrides(level);
+        if self.modules.iter().any(|m| m.unsafe_blocks > 0) {
+            config.tools.kani = Some(ferris_proof_config::config::KaniConfig {
+                cbmc_path: None,
+                unwind: Some(KANI_UNWIND),
+                retries: None,
+                backoff: None,
+                env: None,
+            });
+        }
+        config
+    }
+
+    /// One line per finding, explaining the overrides in the tailored config
+    pub fn recommendations(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for module in &self.modules {
+            if module.unsafe_blocks > 0 {
+                lines.push(format!(
+                    "{}: {} unsafe block(s) → strict level, error enforcement, Kani harnesses",
+                    module.module_path, module.unsafe_blocks
+                ));
+            }
+            if module.concurrency {
+                lines.push(format!(
+                    "{}: shared-state concurrency → ConcurrencyTesting",
+                    module.module_path
+                ));
+            } else if module.is_async && self.uses_tokio {
+                lines.push(format!(
+                    "{}: async code on tokio → ConcurrencyTesting",
+                    module.module_path
+                ));
+            }
+        }
+        if self.has_specs {
+            lines.push("existing TLA+/Alloy specs → formal level".to_string());
+        }
+        lines
+    }
+}
+
+fn scan_module(module_path: String, file: &Path, content: &str) -> ModuleSignals {
+    let code: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("//"))
+        .collect();
+    let count = |markers: &[&str]| {
+        code.iter()
+            .map(|line| {
+                markers
+                    .iter()
+                    .map(|m| line.matches(m).count())
+                    .sum::<usize>()
+            })
+            .sum::<usize>()
+    };
+
+    ModuleSignals {
+        module_path,
+        file: file.to_path_buf(),
+        is_async: count(&ASYNC_MARKERS) > 0,
+        unsafe_blocks: count(&UNSAFE_MARKERS),
+        concurrency: count(&CONCURRENCY_MARKERS) > 0,
+        public_items: code
+            .iter()
+            .filter(|line| PUBLIC_ITEM_MARKERS.iter().any(|m| line.starts_with(m)))
+            .count(),
+    }
+}
+
+/// Whether a manifest lists `name` under `[dependencies]` or `[workspace.dependencies]`
+fn depends_on(manifest: &Path, name: &str) -> bool {
+    let Some(manifest) = fs::read_to_string(manifest)
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+    else {
+        return false;
+    };
+
+    let listed = [
+        manifest.get("dependencies"),
+        manifest
+            .get("workspace")
+            .and_then(|w| w.get("dependencies")),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|deps| deps.get(name).is_some());
+    listed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_analysis_overrides_risky_modules() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+
+        write(
+            "Cargo.toml",
+            "[package]\nname = \"demo\"\n\n[dependencies]\ntokio = \"1\"\n",
+        );
+        write(
+            "src/lib.rs",
+            "pub mod ffi;\npub mod server;\npub mod util;\npub fn version() -> u32 { 1 }\n",
+        );
+        write(
+            "src/ffi.rs",
+            "pub unsafe fn raw(p: *const u8) -> u8 { unsafe { *p } }\n",
+        );
+        write(
+            "src/server.rs",
+            "pub async fn serve() { tokio::task::yield_now().await }\n",
+        );
+        write(
+            "src/util.rs",
+            "// unsafe { never mind }\npub fn add(a: u32, b: u32) -> u32 { a + b }\n",
+        );
+
+        let analysis = ProjectAnalysis::analyze(root);
+        assert!(analysis.uses_tokio);
+        assert_eq!(analysis.recommended_level(), VerificationLevel::Standard);
+
+        let config = analysis.tailored_config(VerificationLevel::Standard);
+        assert_eq!(config.modules.len(), 2);
+        assert!(config.tools.kani.is_some());
+
+        let ffi = &config.modules["ffi"];
+        assert_eq!(ffi.level, Some(VerificationLevel::Strict));
+        assert_eq!(ffi.enforcement, Some(EnforcementMode::Error));
+
+        let server = &config.modules["server"];
+        assert_eq!(server.level, None);
+        assert!(server
+            .enabled_techniques
+            .as_ref()
+            .unwrap()
+            .contains(&Technique::ConcurrencyTesting));
+        assert_eq!(analysis.recommendations().len(), 2);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::commands::{explain, init};
+use anyhow::Result;
+use ferris_proof_core::VerificationLevel;
+
+/// A default asset shipped inside the binary
+#[derive(Debug, Clone)]
+pub struct Asset {
+    /// Path relative to the dump directory
+    pub path: String,
+    pub contents: String,
+}
+
+/// Everything under `assets/`, embedded when built with `--features bundled-defaults`
+#[cfg(feature = "bundled-defaults")]
+static BUNDLED: include_dir::Dir<'static> = include_dir::include_dir!("$CARGO_MANIFEST_DIR/assets");
+
+/// All default assets: config presets, the error catalog and templates
+pub fn all() -> Result<Vec<Asset>> {
+    let mut assets = Vec::new();
+
+    for level in [
+        VerificationLevel::Minimal,
+        VerificationLevel::Standard,
+        VerificationLevel::Strict,
+        VerificationLevel::Formal,
+    ] {
+        let name = format!("{:?}", level).to_lowercase();
+        assets.push(Asset {
+            path: format!("presets/{}.toml", name),
+            contents: init::preset_toml(level)?,
+        });
+    }
+
+    assets.push(Asset {
+        path: "errors/catalog.json".to_string(),
+        contents: explain::catalog_json()?,
+    });
+
+    assets.extend(asset_files());
+    assets.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(assets)
+}
+
+/// Whether the full `assets/` tree is embedded in this binary
+pub fn is_bundled() -> bool {
+    cfg!(feature = "bundled-defaults")
+}
+
+#[cfg(feature = "bundled-defaults")]
+fn asset_files() -> Vec<Asset> {
+    fn visit(dir: &include_dir::Dir<'static>, assets: &mut Vec<Asset>) {
+        for file in dir.files() {
+            if let Some(contents) = file.contents_utf8() {
+                assets.push(Asset {
+                    path: file.path().to_string_lossy().replace('\\', "/"),
+                    contents: contents.to_string(),
+                });
+            }
+        }
+        for sub_dir in dir.dirs() {
+            visit(sub_dir, assets);
+        }
+    }
+
+    let mut assets = Vec::new();
+    visit(&BUNDLED, &mut assets);
+    assets
+}
+
+#[cfg(not(feature = "bundled-defaults"))]
+fn asset_files() -> Vec<Asset> {
+    [
+        (
+            "templates/example_properties.rs",
+            init::PROPERTY_TEST_TEMPLATE,
+        ),
+        (
+            "templates/example_protocol_session.rs",
+            init::SESSION_TYPE_TEMPLATE,
+        ),
+        ("templates/example_protocol.tla", init::TLA_SPEC_TEMPLATE),
+        ("templates/example_protocol.als", init::ALLOY_SPEC_TEMPLATE),
+    ]
+    .into_iter()
+    .map(|(path, contents)| Asset {
+        path: path.to_string(),
+        contents: contents.to_string(),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_assets_include_presets_catalog_and_templates() {
+        let assets = all().unwrap();
+        let paths: Vec<&str> = assets.iter().map(|a| a.path.as_str()).collect();
+
+        assert!(paths.contains(&"presets/standard.toml"));
+        assert!(paths.contains(&"errors/catalog.json"));
+        assert!(paths.contains(&"templates/example_protocol.tla"));
+
+        let catalog = assets
+            .iter()
+            .find(|a| a.path == "errors/catalog.json")
+            .unwrap();
+        assert!(catalog.contents.contains("FP-CF-001"));
+    }
+
+    #[test]
+    fn test_spec_templates_pass_spec_lint() {
+        for (path, template) in [
+            (
+                "specs/formal/tla/example_protocol.tla",
+                init::TLA_SPEC_TEMPLATE,
+            ),
+            (
+                "specs/formal/alloy/example_protocol.als",
+                init::ALLOY_SPEC_TEMPLATE,
+            ),
+        ] {
+            let violations =
+                ferris_proof_core::spec_lint::lint_spec(std::path::Path::new(path), template, None);
+            assert!(violations.is_empty(), "{}: {:?}", path, violations);
+        }
+    }
+}
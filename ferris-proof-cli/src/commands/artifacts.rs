@@ -0,0 +1,98 @@
+use crate::commands::cache::{format_bytes, get_cache_dir};
+use crate::ArtifactsAction;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use ferris_proof_config::ConfigManager;
+use ferris_proof_core::artifacts::{ArtifactStore, OutputStream};
+use ferris_proof_core::retention::{self, Pins, PrunePlan, RetentionPolicy};
+use std::path::Path;
+
+pub async fn run(action: ArtifactsAction) -> Result<i32> {
+    match action {
+        ArtifactsAction::Show { id, stderr } => {
+            let stream = if stderr {
+                OutputStream::Stderr
+            } else {
+                OutputStream::Stdout
+            };
+            let mut reader = ArtifactStore::new(&get_cache_dir()?).open(&id, stream)?;
+
+            let stdout = std::io::stdout();
+            let mut out = stdout.lock();
+            match std::io::copy(&mut reader, &mut out) {
+                Ok(_) => Ok(0),
+                // A closed pager is not an error
+                Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(0),
+                Err(e) => Err(e).context("Failed to stream artifact"),
+            }
+        }
+        ArtifactsAction::Prune {
+            retention_days,
+            max_total_size,
+            dry_run,
+        } => {
+            let project_root =
+                std::env::current_dir().context("Failed to get current directory")?;
+            let configured = retention_policy(&project_root);
+            let policy = RetentionPolicy {
+                retention_days: retention_days.or(configured.retention_days),
+                max_total_size: max_total_size.or(configured.max_total_size),
+            };
+            if policy.is_empty() {
+                println!(
+                    "No retention limits: set [artifacts] retention_days or max_total_size, or pass --retention-days or --max-total-size"
+                );
+                return Ok(0);
+            }
+
+            let plan = prune_plan(&project_root, policy)?;
+            let verb = if dry_run { "Would remove" } else { "Removing" };
+            for artifact in &plan.remove {
+                println!(
+                    "{} {} {} ({})",
+                    verb,
+                    artifact.kind,
+                    artifact
+                        .path
+                        .strip_prefix(&project_root)
+                        .unwrap_or(&artifact.path)
+                        .display(),
+                    format_bytes(artifact.size)
+                );
+            }
+            let removed = if dry_run {
+                plan.remove.len()
+            } else {
+                plan.execute()
+            };
+            println!(
+                "{} {} artifact(s), {} freed; kept {} ({}), {} pinned",
+                "✓".green(),
+                removed,
+                format_bytes(plan.freed_bytes()),
+                plan.kept,
+                format_bytes(plan.kept_bytes),
+                plan.pinned
+            );
+            Ok(0)
+        }
+    }
+}
+
+/// `[artifacts] retention_days` and `max_total_size` of the project
+pub(crate) fn retention_policy(project_root: &Path) -> RetentionPolicy {
+    let artifacts = ConfigManager::from_project_root(project_root)
+        .map(|manager| manager.root_config().artifacts.clone())
+        .unwrap_or_default();
+    RetentionPolicy {
+        retention_days: artifacts.retention_days,
+        max_total_size: artifacts.max_total_size,
+    }
+}
+
+/// Artifacts of the project and its cache that `policy` removes
+pub(crate) fn prune_plan(project_root: &Path, policy: RetentionPolicy) -> Result<PrunePlan> {
+    let pins = Pins::for_project(project_root)?;
+    let artifacts = retention::inventory(project_root, &get_cache_dir()?, &pins)?;
+    Ok(policy.plan(artifacts, chrono::Utc::now()))
+}
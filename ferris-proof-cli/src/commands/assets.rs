@@ -0,0 +1,57 @@
+use crate::assets;
+use crate::AssetsAction;
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+
+pub async fn run(action: AssetsAction) -> Result<i32> {
+    match action {
+        AssetsAction::List => {
+            for asset in assets::all()? {
+                println!("{}", asset.path);
+            }
+            Ok(0)
+        }
+        AssetsAction::Dump { output_dir, force } => dump(&output_dir, force),
+    }
+}
+
+fn dump(output_dir: &Path, force: bool) -> Result<i32> {
+    let assets = assets::all()?;
+
+    if !force {
+        if let Some(existing) = assets
+            .iter()
+            .map(|asset| output_dir.join(&asset.path))
+            .find(|path| path.exists())
+        {
+            return Err(anyhow!(
+                "{} already exists; use --force to overwrite",
+                existing.display()
+            ));
+        }
+    }
+
+    for asset in &assets {
+        let path = output_dir.join(&asset.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+        fs::write(&path, &asset.contents).with_context(|| format!("Failed to write {:?}", path))?;
+        println!("✓ Wrote {}", path.display().to_string().green());
+    }
+
+    println!(
+        "Dumped {} asset(s){}",
+        assets.len(),
+        if assets::is_bundled() {
+            " from the bundled defaults"
+        } else {
+            ""
+        }
+    );
+
+    Ok(0)
+}
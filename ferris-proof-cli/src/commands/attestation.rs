@@ -0,0 +1,72 @@
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use ferris_proof_config::{AttestationConfig, ConfigManager};
+use ferris_proof_core::attestation::{
+    load_signing_key, parse_verifying_key, verify_chain, AttestationLog, AttestationPayload,
+};
+use ferris_proof_core::verification::Target;
+use ferris_proof_core::{RendererRegistry, VerificationResult};
+use std::path::{Path, PathBuf};
+
+pub async fn run(file: PathBuf, public_key: Option<String>) -> Result<i32> {
+    let configured = std::env::current_dir()
+        .ok()
+        .and_then(|root| ConfigManager::from_project_root(&root).ok())
+        .and_then(|config| config.root_config().attestation.public_key.clone());
+    let trusted = public_key
+        .or(configured)
+        .map(|key| parse_verifying_key(&key))
+        .transpose()?;
+
+    let attestations = AttestationLog::with_path(file.clone()).load()?;
+    if attestations.is_empty() {
+        return Err(anyhow!("No attestations in {}", file.display()));
+    }
+
+    match verify_chain(&attestations, trusted.as_ref()) {
+        Ok(count) => {
+            println!(
+                "{} {} attestation(s) in {} verified",
+                "✓".green(),
+                count,
+                file.display()
+            );
+            if trusted.is_none() {
+                println!(
+                    "{} Signer not checked; pass --public-key or set [attestation] public_key",
+                    "•".yellow()
+                );
+            }
+            Ok(0)
+        }
+        Err(e) => {
+            println!("{} {:#}", "✗".red(), e);
+            Ok(1)
+        }
+    }
+}
+
+/// Sign the JSON report of a run into the project's attestation log
+///
+/// Returns the status line to print.
+pub(crate) fn attest(
+    project_root: &Path,
+    result: &VerificationResult,
+    targets: &[Target],
+    config: &AttestationConfig,
+) -> Result<String> {
+    let key_file = config
+        .signing_key_file
+        .as_ref()
+        .map(|path| project_root.join(path));
+    let key = load_signing_key(key_file.as_deref())?;
+
+    let report = RendererRegistry::new().render("json", result)?;
+    let log = AttestationLog::for_project(project_root);
+    let attestation = log.append(AttestationPayload::new(report, targets)?, &key)?;
+    Ok(format!(
+        "✓ Report {} attested in {}",
+        &attestation.payload.report_hash[..12],
+        log.path().display().to_string().green()
+    ))
+}
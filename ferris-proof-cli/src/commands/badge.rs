@@ -0,0 +1,50 @@
+use crate::commands::diff::load_report;
+use crate::render::badge::Badge;
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use ferris_proof_config::ConfigManager;
+use ferris_proof_core::history::HistoryStore;
+use std::path::PathBuf;
+
+const SVG_FILE: &str = "ferris-proof-badge.svg";
+const ENDPOINT_FILE: &str = "ferris-proof-badge.json";
+
+pub async fn run(report: Option<PathBuf>, output: PathBuf) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+
+    let record = match report {
+        Some(path) => load_report(&path)?,
+        None => HistoryStore::for_project(&project_root)
+            .load()?
+            .pop()
+            .ok_or_else(|| {
+                anyhow!("No verification history recorded yet; run 'ferris-proof check' or pass --report")
+            })?,
+    };
+    let level = ConfigManager::from_project_root(&project_root)
+        .ok()
+        .map(|manager| manager.root_config().profile.level);
+    let badge = Badge::from_record(&record, level);
+
+    std::fs::create_dir_all(&output)
+        .with_context(|| format!("Failed to create output directory {:?}", output))?;
+    let svg_path = output.join(SVG_FILE);
+    let endpoint_path = output.join(ENDPOINT_FILE);
+    std::fs::write(&svg_path, badge.to_svg())
+        .with_context(|| format!("Failed to write {:?}", svg_path))?;
+    std::fs::write(
+        &endpoint_path,
+        serde_json::to_string_pretty(&badge.to_endpoint_json())?,
+    )
+    .with_context(|| format!("Failed to write {:?}", endpoint_path))?;
+
+    println!("{} {}", "✓".green(), badge.message());
+    println!("  SVG badge:           {}", svg_path.display());
+    println!("  shields.io endpoint: {}", endpoint_path.display());
+    println!(
+        "  Embed with {}",
+        format!("![ferris-proof]({})", svg_path.display()).cyan()
+    );
+
+    Ok(0)
+}
@@ -1,14 +1,26 @@
 use crate::CacheAction;
-use anyhow::Result;
+use anyhow::{Context, Result};
+pub use ferris_proof_core::cache::parse_age;
+use ferris_proof_core::cache::CacheFilter;
+use ferris_proof_core::cache_index::{toolchain_id, CacheIndex};
 use ferris_proof_core::CacheManager;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub async fn run(action: CacheAction) -> Result<i32> {
+    match &action {
+        CacheAction::Info { all_projects: true } => return list_projects(),
+        CacheAction::Clear {
+            project: Some(project),
+            ..
+        } => return clear_project(project),
+        _ => {}
+    }
+
     let cache_dir = get_cache_dir()?;
     let mut cache_manager = CacheManager::with_cache_dir(cache_dir);
 
     match action {
-        CacheAction::Info => {
+        CacheAction::Info { .. } => {
             let info = cache_manager.info()?;
 
             println!("Cache Information:");
@@ -51,7 +63,29 @@ pub async fn run(action: CacheAction) -> Result<i32> {
             }
         }
 
-        CacheAction::Clear => {
+        CacheAction::Clear {
+            layer,
+            module,
+            older_than,
+            ..
+        } => {
+            let filter = CacheFilter {
+                layer,
+                module,
+                older_than,
+                config_hash: None,
+            };
+
+            if !filter.is_empty() {
+                println!("Clearing matching cache entries...");
+                let result = cache_manager.clear_matching(&filter)?;
+
+                println!("Clear completed:");
+                println!("  Entries removed: {}", result.entries_removed);
+                println!("  Size freed: {}", format_bytes(result.size_freed));
+                return Ok(0);
+            }
+
             println!("⚠️  This will remove ALL cache entries. Are you sure? (y/N)");
 
             let mut input = String::new();
@@ -146,20 +180,77 @@ pub async fn run(action: CacheAction) -> Result<i32> {
     Ok(0)
 }
 
-fn get_cache_dir() -> Result<PathBuf> {
+/// Cache directory of the project in the current directory, for the active toolchain
+pub(crate) fn get_cache_dir() -> Result<PathBuf> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    cache_dir_for(&project_root)
+}
+
+/// Cache namespace of `project_root` under the current toolchain
+pub(crate) fn cache_dir_for(project_root: &Path) -> Result<PathBuf> {
+    Ok(CacheIndex::load(&cache_root())?.open_namespace(project_root, &toolchain_id())?)
+}
+
+/// Root shared by all project caches
+fn cache_root() -> PathBuf {
     // Try to get cache directory from environment or use default
     if let Ok(cache_dir) = std::env::var("FERRIS_PROOF_CACHE_DIR") {
-        Ok(PathBuf::from(cache_dir))
+        PathBuf::from(cache_dir)
     } else {
-        // Use default cache directory
-        let cache_dir = dirs::cache_dir()
+        dirs::cache_dir()
             .unwrap_or_else(std::env::temp_dir)
-            .join("ferris-proof");
-        Ok(cache_dir)
+            .join("ferris-proof")
+    }
+}
+
+fn list_projects() -> Result<i32> {
+    let root = cache_root();
+    let index = CacheIndex::load(&root)?;
+
+    println!("Project caches in {}:", root.display());
+    if index.namespaces.is_empty() {
+        println!("\nNo project caches found.");
+        return Ok(0);
     }
+
+    let mut total = 0;
+    for (id, entry) in &index.namespaces {
+        let disk_size = CacheManager::with_cache_dir(index.namespace_dir(id))
+            .info()?
+            .disk_size_bytes;
+        total += disk_size;
+        println!();
+        println!("  {}", entry.project_root.display());
+        println!("    Namespace: {}", id);
+        println!("    Toolchain: {}", entry.toolchain);
+        println!(
+            "    Last used: {}",
+            entry.last_used.format("%Y-%m-%d %H:%M UTC")
+        );
+        println!("    Disk size: {}", format_bytes(disk_size));
+    }
+    println!("\nTotal disk size: {}", format_bytes(total));
+
+    Ok(0)
 }
 
-fn format_bytes(bytes: u64) -> String {
+fn clear_project(project: &Path) -> Result<i32> {
+    let mut index = CacheIndex::load(&cache_root())?;
+    let removed = index.remove_project(project)?;
+
+    if removed.is_empty() {
+        println!("No cache found for {}.", project.display());
+    } else {
+        println!(
+            "✅ Removed {} cache namespace(s) for {}.",
+            removed.len(),
+            project.display()
+        );
+    }
+    Ok(0)
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
 
     if bytes == 0 {
@@ -184,6 +275,7 @@ fn format_bytes(bytes: u64) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn test_format_bytes() {
@@ -194,4 +286,18 @@ mod tests {
         assert_eq!(format_bytes(1048576), "1.0 MB");
         assert_eq!(format_bytes(1073741824), "1.0 GB");
     }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30m").unwrap(), Duration::from_secs(30 * 60));
+        assert_eq!(parse_age("7d").unwrap(), Duration::from_secs(7 * 24 * 3600));
+        assert_eq!(
+            parse_age("2w").unwrap(),
+            Duration::from_secs(14 * 24 * 3600)
+        );
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("99999999999999999w").is_err());
+        assert!(parse_age("3y").is_err());
+    }
 }
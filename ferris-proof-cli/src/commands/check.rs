@@ -1,21 +1,1245 @@
-use anyhow::Result;
-use ferris_proof_core::Layer;
+use crate::commands::artifacts;
+use crate::commands::attestation;
+use crate::commands::cache::{cache_dir_for, get_cache_dir};
+use crate::commands::plugins;
+use crate::exit_codes::{self, ExitCodePolicy};
+use crate::render::view::ViolationView;
+use crate::render::{github, snippet};
+use crate::OutputFormat;
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use ferris_proof_config::module_tree::ModuleTree;
+use ferris_proof_config::{ArtifactsConfig, ConfigManager};
+use ferris_proof_core::api_surface::{api_break_violations, ApiSurface};
+use ferris_proof_core::artifacts::{ArtifactPolicy, ArtifactStore};
+use ferris_proof_core::baseline::Baseline;
+use ferris_proof_core::cache::{wildcard_match, CacheKey};
+use ferris_proof_core::dependency_graph::LayerGates;
+use ferris_proof_core::discovery::{discover_targets, DiscoveryOptions};
+use ferris_proof_core::environment::EnvironmentSnapshot;
+use ferris_proof_core::history::{self, HistoryStore, RunRecord};
+use ferris_proof_core::lockfile::{self, VerificationLock};
+use ferris_proof_core::metrics::MetricsStore;
+use ferris_proof_core::normalization::NormalizationPolicies;
+use ferris_proof_core::plugins::{CancellationToken, SelectionStrategy};
+use ferris_proof_core::seed;
+use ferris_proof_core::spec_diff::diff_against_rev;
+use ferris_proof_core::spec_drift::{stale_spec_violations, DriftBaseline};
+use ferris_proof_core::spec_lint;
+use ferris_proof_core::timings::{Phase, Timings};
+use ferris_proof_core::traceability::TraceMatrix;
+use ferris_proof_core::types::UnavailableLayer;
+use ferris_proof_core::verification::Target;
+use ferris_proof_core::{
+    Layer, LayerResult, PluginManager, RendererRegistry, Severity, Status, VerificationCache,
+    VerificationEngine, VerificationResult,
+};
+use ferris_proof_plugins::cargo_metadata::{self, MetadataPolicy};
+use ferris_proof_plugins::tlc_config::{self, TlcModelOverrides};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
 
-pub async fn run(module: Option<String>, layer: Option<Layer>, fix: bool) -> Result<i32> {
-    println!("Running verification checks");
+/// Written below `.ferris-proof/` when an interrupted run has no `--report-output`
+const PARTIAL_REPORT: &str = "partial-report.json";
 
-    if let Some(module) = module {
-        println!("Checking module: {}", module);
+/// Print a status line, on stderr when stdout carries the NDJSON event stream
+macro_rules! say {
+    ($stream:expr, $($arg:tt)*) => {
+        if $stream {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    modules: Vec<String>,
+    item: Option<String>,
+    layer: Option<Layer>,
+    fix: bool,
+    report: Option<String>,
+    report_output: Option<PathBuf>,
+    keep_going: bool,
+    strict_tools: bool,
+    plugin: Option<String>,
+    spec_diff: Option<String>,
+    model: Option<String>,
+    output_format: Option<OutputFormat>,
+    seed: Option<u64>,
+    timings_output: Option<Option<PathBuf>>,
+    update_lock: bool,
+    ignore_lock: bool,
+    attest: bool,
+    view: ViolationView,
+) -> Result<i32> {
+    if update_lock && layer.is_some() {
+        bail!("--update-lock records results of every layer and cannot be combined with --layer");
+    }
+    let annotate = matches!(output_format, Some(OutputFormat::Github));
+    let stream = matches!(output_format, Some(OutputFormat::Ndjson));
+    let seed = seed.unwrap_or_else(seed::random_seed);
+    let timings = if timings_output.is_some() {
+        Timings::enabled()
+    } else {
+        Timings::default()
+    };
+
+    say!(stream, "Running verification checks (seed {})", seed);
+
+    if let Some(ref item) = item {
+        say!(stream, "Checking item: {}", item);
     }
 
     if let Some(layer) = layer {
-        println!("Running layer: {:?}", layer);
+        say!(stream, "Running layer: {:?}", layer);
     }
 
     if fix {
-        println!("Auto-fix mode enabled");
+        say!(stream, "Auto-fix mode enabled");
+    }
+
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let targets: Vec<Target> = match item {
+        Some(item) => vec![item_target(&project_root, item)?],
+        None if !modules.is_empty() => {
+            let targets = module_targets(&project_root, &modules)?;
+            say!(
+                stream,
+                "Checking module(s): {}",
+                targets
+                    .iter()
+                    .map(Target::label)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            targets
+        }
+        None => timings.time(Phase::Discovery, "discover targets", || {
+            discover_project_targets(&project_root)
+        }),
+    };
+    prepare_tla_models(&targets, model.as_deref(), stream)?;
+
+    let config_manager = ConfigManager::from_project_root(&project_root).ok();
+    let config_hash = |target: &Target| {
+        let file = target.file().unwrap_or(project_root.as_path());
+        config_manager
+            .as_ref()
+            .map(|manager| manager.for_file(file).config_hash)
+            .unwrap_or_default()
+    };
+    let lock_path = VerificationLock::path_for_project(&project_root);
+    let lock = VerificationLock::load(&lock_path)?;
+    // Detecting tool versions spawns each tool, so only do it when the lock is used
+    let pinned_tools = if (lock.is_some() && !ignore_lock) || update_lock {
+        lockfile::pinned_tool_versions()
+    } else {
+        Default::default()
+    };
+    let (targets, up_to_date) = match &lock {
+        Some(lock) if !ignore_lock => {
+            let partition = lock.partition(&targets, &project_root, &pinned_tools, config_hash);
+            (partition.stale, partition.up_to_date)
+        }
+        _ => (targets, Default::default()),
+    };
+    if !up_to_date.is_empty() {
+        say!(
+            stream,
+            "{} {} target(s) unchanged since {} (use --ignore-lock to verify anyway)",
+            "•".yellow(),
+            up_to_date.len(),
+            lock_path.display()
+        );
+    }
+
+    let engine = if stream {
+        VerificationEngine::new().with_progress(|event| {
+            if let Ok(line) = serde_json::to_string(event) {
+                println!("{}", line);
+            }
+        })
+    } else {
+        VerificationEngine::new()
+    };
+    let (mut result, suppressed) = verify_with(
+        engine,
+        &project_root,
+        get_cache_dir().ok(),
+        &targets,
+        layer,
+        keep_going,
+        strict_tools,
+        plugin,
+        None,
+        Some(seed),
+        &timings,
+        cancel_on_ctrl_c(),
+    )
+    .await?;
+    // Unchanged targets were not run, so they report as cached whatever
+    // status the lock recorded
+    result
+        .target_statuses
+        .extend(up_to_date.into_keys().map(|label| (label, Status::Cached)));
+    result.link_template = config_manager
+        .as_ref()
+        .map(ConfigManager::link_template)
+        .transpose()?
+        .flatten();
+
+    if result.interrupted {
+        return finish_interrupted(&result, &project_root, report, report_output, stream);
+    }
+
+    if update_lock {
+        let mut lock = lock.unwrap_or_default();
+        lock.update(
+            &targets,
+            &project_root,
+            &pinned_tools,
+            &result.target_statuses,
+            config_hash,
+        );
+        lock.save(&lock_path)?;
+        say!(
+            stream,
+            "✓ Updated {} ({} locked target(s))",
+            lock_path.display().to_string().green(),
+            lock.targets.len()
+        );
+    }
+
+    for skipped in &result.skipped_targets {
+        say!(
+            stream,
+            "{} Skipped {} (dependency {} failed; use --keep-going to verify anyway)",
+            "•".yellow(),
+            skipped.target,
+            skipped.failed_dependency
+        );
+    }
+    for unavailable in &result.unavailable_layers {
+        say!(
+            stream,
+            "{} {} {:?}: {} ({})",
+            "•".yellow(),
+            if strict_tools { "Failed" } else { "Skipped" },
+            unavailable.layer,
+            unavailable.reason,
+            unavailable.code
+        );
+        say!(stream, "  {} {}", "help:".cyan(), unavailable.hint);
+    }
+    for gated in &result.gated_layers {
+        say!(
+            stream,
+            "{} Skipped {:?} for {} (gated: {:?} did not pass)",
+            "•".yellow(),
+            gated.layer,
+            gated.target,
+            gated.required
+        );
+    }
+
+    for breakdown in &result.plugin_breakdowns {
+        let outcomes: Vec<String> = breakdown
+            .outcomes
+            .iter()
+            .map(|outcome| match &outcome.error {
+                Some(error) => format!("{} {:?} ({})", outcome.plugin, outcome.status, error),
+                None => format!(
+                    "{} {:?} ({} violation(s))",
+                    outcome.plugin, outcome.status, outcome.violations
+                ),
+            })
+            .collect();
+        say!(
+            stream,
+            "{} {:?} for {}: {}{}",
+            "•".yellow(),
+            breakdown.technique,
+            breakdown.target,
+            outcomes.join(", "),
+            if breakdown.inconsistent {
+                " (plugins disagree)"
+            } else {
+                ""
+            }
+        );
+    }
+
+    let groups = view.groups(&result);
+    for (key, violations) in &groups {
+        if view.group_by.is_some() && !annotate {
+            say!(stream, "{} ({})", key.bold(), violations.len());
+        }
+        for violation in violations {
+            if annotate {
+                say!(stream, "{}", github::annotation(violation));
+            } else {
+                let mut rendered = snippet::render_for_project(&project_root, violation);
+                if let Some(link) = result.violation_link(violation) {
+                    rendered.push_str(&snippet::link_note(&link));
+                }
+                say!(stream, "{}", rendered);
+            }
+        }
+    }
+    if !view.filters.is_empty() {
+        let shown: usize = groups.iter().map(|(_, violations)| violations.len()).sum();
+        let total: usize = result
+            .layer_results
+            .values()
+            .map(|l| l.violations.len())
+            .sum();
+        say!(
+            stream,
+            "{} {} of {} violation(s) match --filter",
+            "•".yellow(),
+            shown,
+            total
+        );
+    }
+
+    if suppressed > 0 {
+        say!(
+            stream,
+            "{} {} violation(s) suppressed by the baseline",
+            "•".yellow(),
+            suppressed
+        );
+    }
+
+    let budget = config_manager
+        .as_ref()
+        .map(|manager| manager.root_config().thresholds.max_violations.clone())
+        .filter(|budget| !budget.is_empty());
+    if let Some(budget) = budget {
+        let report = budget.evaluate(&result);
+        let marker = if report.exceeded() {
+            "✗".red()
+        } else {
+            "✓".green()
+        };
+        say!(stream, "{} Violation budget: {}", marker, report);
+        result.budget = Some(report);
+    }
+
+    record_history(&result);
+    prune_artifacts();
+
+    if annotate {
+        write_job_summary(&result);
+    }
+
+    if let Some(rev) = spec_diff {
+        result.spec_changes = diff_against_rev(&project_root, &rev)?;
+    }
+
+    match TraceMatrix::build(&project_root) {
+        Ok(matrix) if !matrix.is_empty() => result.traceability = Some(matrix),
+        Ok(_) => {}
+        Err(e) => warn!("Skipping traceability matrix: {}", e),
+    }
+
+    if let Some(path) = timings_output {
+        print_timings(&timings, stream);
+        if let Some(path) = path {
+            timings.write_chrome_trace(&path)?;
+            say!(
+                stream,
+                "✓ Timings written to {}",
+                path.display().to_string().green()
+            );
+        }
+    }
+
+    if let Some(format) = report {
+        let registry = renderer_registry();
+        let rendered = registry.render(&format, &result)?;
+
+        match report_output {
+            Some(path) => {
+                std::fs::write(&path, rendered)
+                    .with_context(|| format!("Failed to write report to {:?}", path))?;
+                say!(
+                    stream,
+                    "✓ Report written to {}",
+                    path.display().to_string().green()
+                );
+            }
+            None => say!(stream, "{}", rendered),
+        }
+    }
+
+    let attestation_config = config_manager
+        .as_ref()
+        .map(|manager| manager.root_config().attestation.clone())
+        .unwrap_or_default();
+    if attest || attestation_config.enabled {
+        let attested = attestation::attest(&project_root, &result, &targets, &attestation_config)?;
+        say!(stream, "{}", attested);
+    }
+
+    let exit_code = ExitCodePolicy::for_current_project()?.exit_code(&result);
+    if exit_code != 0 {
+        say!(
+            stream,
+            "Reproduce with {}",
+            format!("--seed {}", seed).cyan()
+        );
+    }
+    Ok(exit_code)
+}
+
+/// `check --action-mode`: verify like a hermetic build action, e.g. a Bazel rule
+///
+/// Only the listed inputs are verified and only `report_output` is written.
+/// Nothing is cached unless `cache_dir` is given, no history, lockfile or
+/// drift baseline is recorded, generated model configs go to a scratch
+/// directory, and the report is identical for identical inputs and seed.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_action(
+    inputs: Vec<PathBuf>,
+    layer: Option<Layer>,
+    keep_going: bool,
+    strict_tools: bool,
+    plugin: Option<String>,
+    model: Option<String>,
+    report: Option<String>,
+    report_output: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    seed: Option<u64>,
+) -> Result<i32> {
+    let Some(report_output) = report_output else {
+        bail!("--action-mode needs --report-output as its declared output");
+    };
+    let inputs = expand_param_files(&inputs)?;
+    if inputs.is_empty() {
+        bail!("--action-mode needs at least one --input");
+    }
+
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    // Other inputs, such as ferrisproof.toml, are read but not verified
+    let targets: Vec<Target> = inputs
+        .iter()
+        .filter_map(|path| match path.extension().and_then(|e| e.to_str()) {
+            Some("rs") => Some(Target::RustFile(project_root.join(path))),
+            Some("tla") | Some("als") => Some(Target::FormalSpec(project_root.join(path))),
+            _ => None,
+        })
+        .collect();
+    if let Some(model) = model.as_deref() {
+        let manager = ConfigManager::from_project_root(&project_root)?;
+        for target in &targets {
+            if let Target::FormalSpec(spec) = target {
+                if spec.extension().and_then(|e| e.to_str()) == Some("tla") {
+                    manager.tla_model_for(spec, Some(model))?;
+                }
+            }
+        }
+    }
+
+    // Generated files, such as default TLC and named model configs, must not
+    // land next to the inputs
+    let scratch_dir = tempfile::TempDir::new().context("Failed to create a scratch directory")?;
+    let engine = VerificationEngine::in_memory().with_scratch_dir(scratch_dir.path().to_path_buf());
+    let (mut result, _) = verify_with(
+        engine,
+        &project_root,
+        cache_dir,
+        &targets,
+        layer,
+        keep_going,
+        strict_tools,
+        plugin,
+        model.as_deref(),
+        Some(seed.unwrap_or(0)),
+        &Timings::default(),
+        CancellationToken::new(),
+    )
+    .await?;
+    result.make_reproducible(&project_root);
+
+    let rendered = renderer_registry().render(report.as_deref().unwrap_or("json"), &result)?;
+    if let Some(parent) = report_output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&report_output, rendered)
+        .with_context(|| format!("Failed to write report to {:?}", report_output))?;
+
+    Ok(ExitCodePolicy::for_current_project()?.exit_code(&result))
+}
+
+/// Inputs with `@FILE` params files expanded to the paths they list, one per line
+fn expand_param_files(inputs: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut expanded = Vec::new();
+    for input in inputs {
+        match input.to_str().and_then(|s| s.strip_prefix('@')) {
+            Some(params) => {
+                let content = std::fs::read_to_string(params)
+                    .with_context(|| format!("Failed to read params file {}", params))?;
+                expanded.extend(
+                    content
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .map(PathBuf::from),
+                );
+            }
+            None => expanded.push(input.clone()),
+        }
+    }
+    Ok(expanded)
+}
+
+/// Write a partial report of an interrupted run
+///
+/// Unless given, the format defaults to JSON and the path to
+/// `.ferris-proof/partial-report.json`, each on its own. The layers it
+/// completed were cached by the engine as they finished. The lockfile and
+/// history are left untouched, since the run is incomplete.
+fn finish_interrupted(
+    result: &VerificationResult,
+    project_root: &Path,
+    report: Option<String>,
+    report_output: Option<PathBuf>,
+    stream: bool,
+) -> Result<i32> {
+    say!(
+        stream,
+        "{} Verification interrupted after {} target(s)",
+        "✗".red(),
+        result.target_statuses.len()
+    );
+
+    let format = report.unwrap_or_else(|| "json".to_string());
+    let path =
+        report_output.unwrap_or_else(|| project_root.join(".ferris-proof").join(PARTIAL_REPORT));
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let rendered = renderer_registry().render(&format, result)?;
+    std::fs::write(&path, rendered)
+        .with_context(|| format!("Failed to write partial report to {:?}", path))?;
+    say!(
+        stream,
+        "✓ Partial report written to {}",
+        path.display().to_string().green()
+    );
+
+    Ok(exit_codes::INTERRUPTED)
+}
+
+/// Run verification, metadata checks and baseline suppression
+///
+/// Returns the result together with the number of baselined violations.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn verify_project(
+    project_root: &Path,
+    targets: &[Target],
+    layer: Option<Layer>,
+    keep_going: bool,
+    plugin: Option<String>,
+    seed: Option<u64>,
+    timings: &Timings,
+    cancellation: CancellationToken,
+) -> Result<(VerificationResult, usize)> {
+    verify_with(
+        VerificationEngine::new(),
+        project_root,
+        cache_dir_for(project_root).ok(),
+        targets,
+        layer,
+        keep_going,
+        false,
+        plugin,
+        None,
+        seed,
+        timings,
+        cancellation,
+    )
+    .await
+}
+
+/// [`verify_project`] on a given engine, keeping plugin stats and spilled
+/// artifacts in `cache_dir` if any, and checking TLA+ specs with `model`
+/// instead of their configured model if given
+#[allow(clippy::too_many_arguments)]
+async fn verify_with(
+    engine: VerificationEngine,
+    project_root: &Path,
+    cache_dir: Option<PathBuf>,
+    targets: &[Target],
+    layer: Option<Layer>,
+    keep_going: bool,
+    strict_tools: bool,
+    plugin: Option<String>,
+    model: Option<&str>,
+    seed: Option<u64>,
+    timings: &Timings,
+    cancellation: CancellationToken,
+) -> Result<(VerificationResult, usize)> {
+    let mut engine = engine
+        .with_timings(timings.clone())
+        .with_cancellation(cancellation)
+        .with_environment(capture_environment(project_root, timings));
+    if let Some(seed) = seed {
+        engine = engine.with_seed(seed);
+    }
+    let strategy = timings.time(Phase::ConfigResolution, "selection strategy", || {
+        selection_strategy(project_root, plugin)
+    });
+    let unavailable = timings.time(Phase::ConfigResolution, "tool preflight", || {
+        tool_preflight(project_root, &strategy, layer)
+    })?;
+    let engine = configure_engine(engine, project_root, cache_dir.as_deref(), timings)?
+        .with_keep_going(keep_going)
+        .with_layer(layer)
+        .with_unavailable_layers(unavailable)
+        .with_strict_tools(strict_tools)
+        .with_selection_strategy(strategy);
+    let engine = match model {
+        Some(model) => with_tla_model(engine, model),
+        None => engine,
+    };
+
+    verify_on(&engine, project_root, targets, layer, timings).await
+}
+
+/// Have the TLA+ plugin render and check the named model, which it writes to
+/// the engine's scratch directory in hermetic runs
+fn with_tla_model(engine: VerificationEngine, model: &str) -> VerificationEngine {
+    let Some(mut config) = engine.config().cloned() else {
+        return engine;
+    };
+    let tla_plus = config
+        .plugin_tools
+        .entry("tla-plus".to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    if let Some(tla_plus) = tla_plus.as_object_mut() {
+        tla_plus.insert("model".to_string(), model.into());
+    }
+    engine.with_config(config)
+}
+
+/// Toolchain and configuration recorded with results and cache entries
+pub(crate) fn capture_environment(project_root: &Path, timings: &Timings) -> EnvironmentSnapshot {
+    timings.time(Phase::ConfigResolution, "environment", || {
+        let config_hash = ConfigManager::from_project_root(project_root)
+            .ok()
+            .map(|manager| manager.for_file(project_root).config_hash);
+        EnvironmentSnapshot::capture(project_root, config_hash)
+    })
+}
+
+/// Give the engine the built-in and discovered plugins and the project's
+/// techniques, and apply its `[artifacts]`, `[layers]` and normalization
+/// settings, keeping cached results, plugin stats and spilled artifacts in
+/// `cache_dir` if any
+pub(crate) fn configure_engine(
+    engine: VerificationEngine,
+    project_root: &Path,
+    cache_dir: Option<&Path>,
+    timings: &Timings,
+) -> Result<VerificationEngine> {
+    let (plugin_manager, policy, gates, normalization) =
+        timings.time(Phase::ConfigResolution, "engine options", || {
+            Ok::<_, anyhow::Error>((
+                plugins::plugin_manager()?,
+                artifact_policy(project_root)?,
+                layer_gates(project_root)?,
+                normalization_policies(project_root)?,
+            ))
+        })?;
+    let mut engine = engine.with_plugin_manager(plugin_manager);
+    if let Ok(manager) = ConfigManager::from_project_root(project_root) {
+        engine = engine
+            .with_config(manager.for_file(project_root).plugin_config())
+            .with_cache_ttls(manager.cache_ttls()?);
+        let root = project_root.to_path_buf();
+        engine = engine.with_config_hash(move |target| {
+            manager
+                .for_file(target.file().unwrap_or(root.as_path()))
+                .config_hash
+        });
+    }
+    let engine = engine
+        .with_project_root(project_root)
+        .with_artifact_policy(policy)
+        .with_layer_gates(gates)
+        .with_normalization(normalization);
+    Ok(match cache_dir {
+        Some(cache_dir) => engine
+            .with_cache(VerificationCache::with_cache_dir(cache_dir.to_path_buf()))
+            .with_plugin_stats(cache_dir.join("plugin-stats.json"))
+            .with_artifact_store(artifact_store(project_root, cache_dir)),
+        None => engine,
+    })
+}
+
+/// Run a configured engine, then metadata checks and baseline suppression
+///
+/// Returns the result together with the number of baselined violations.
+pub(crate) async fn verify_on(
+    engine: &VerificationEngine,
+    project_root: &Path,
+    targets: &[Target],
+    layer: Option<Layer>,
+    timings: &Timings,
+) -> Result<(VerificationResult, usize)> {
+    let mut result = engine.verify(targets).await?;
+
+    timings.time(Phase::Parsing, "cargo metadata", || {
+        check_metadata_policy(project_root, &mut result, layer)
+    });
+    timings.time(Phase::Parsing, "spec drift", || {
+        check_spec_drift(project_root, &mut result, layer, !engine.is_hermetic())
+    });
+    timings.time(Phase::Parsing, "spec lint", || {
+        check_spec_lint(project_root, &mut result, layer)
+    });
+    timings.time(Phase::Parsing, "api surface", || {
+        check_api_surface(project_root, &mut result, layer)
+    });
+    result.relativize(project_root);
+
+    let baseline = Baseline::load(&Baseline::path_for_project(project_root))?;
+    let suppressed = baseline.apply(&mut result);
+
+    Ok((result, suppressed))
+}
+
+/// Token cancelled on Ctrl-C, so in-flight tools are killed rather than orphaned
+fn cancel_on_ctrl_c() -> CancellationToken {
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            warn!("Interrupted, cancelling verification");
+            cancel.cancel();
+        }
+    });
+    token
+}
+
+/// `--plugin` wins over the `[plugins] prefer` list and disables fan-out,
+/// but cannot select a plugin listed in `[plugins] disabled`
+pub(crate) fn selection_strategy(project_root: &Path, forced: Option<String>) -> SelectionStrategy {
+    let plugins = ConfigManager::from_project_root(project_root)
+        .ok()
+        .map(|manager| manager.root_config().plugins.clone())
+        .unwrap_or_default();
+
+    SelectionStrategy {
+        forced,
+        prefer: plugins.prefer,
+        fan_out: plugins.fan_out,
+        disabled: plugins.disabled,
+    }
+}
+
+/// Layers of the configured techniques that no installed tool can verify
+pub(crate) fn tool_preflight(
+    project_root: &Path,
+    strategy: &SelectionStrategy,
+    layer: Option<Layer>,
+) -> Result<Vec<UnavailableLayer>> {
+    let techniques: Vec<_> = ConfigManager::from_project_root(project_root)
+        .ok()
+        .map(|manager| manager.root_config().profile.enabled_techniques.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|technique| layer.is_none_or(|layer| technique.layer() == layer))
+        .collect();
+    if techniques.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut plugin_manager = plugins::plugin_manager()?;
+    plugin_manager.set_selection_strategy(strategy.clone());
+    Ok(plugin_manager.preflight(&techniques))
+}
+
+fn artifacts_config(project_root: &Path) -> ArtifactsConfig {
+    ConfigManager::from_project_root(project_root)
+        .ok()
+        .map(|manager| manager.root_config().artifacts.clone())
+        .unwrap_or_default()
+}
+
+/// Size caps and redaction rules from `[artifacts]`
+fn artifact_policy(project_root: &Path) -> Result<ArtifactPolicy> {
+    let artifacts = artifacts_config(project_root);
+    ArtifactPolicy::new(artifacts.max_bytes, &artifacts.redact)
+}
+
+/// Store under the cache dir for tool outputs over `[artifacts] spill_bytes`
+fn artifact_store(project_root: &Path, cache_dir: &Path) -> ArtifactStore {
+    let store = ArtifactStore::new(cache_dir);
+    match artifacts_config(project_root).spill_bytes {
+        Some(spill_bytes) => store.with_spill_bytes(spill_bytes as usize),
+        None => store,
+    }
+}
+
+/// Render the selected named TLC model for each TLA+ spec being checked
+///
+/// Specs without a selected model keep their default `.cfg`.
+fn prepare_tla_models(targets: &[Target], requested: Option<&str>, stream: bool) -> Result<()> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let Ok(manager) = ConfigManager::from_project_root(&project_root) else {
+        return Ok(());
+    };
+
+    for target in targets {
+        let Target::FormalSpec(spec) = target else {
+            continue;
+        };
+        if spec.extension().and_then(|e| e.to_str()) != Some("tla") {
+            continue;
+        }
+        let Some((name, model)) = manager.tla_model_for(spec, requested)? else {
+            continue;
+        };
+
+        let overrides = TlcModelOverrides {
+            specification: model.specification,
+            init: model.init,
+            next: model.next,
+            constants: model.constants,
+            invariants: model.invariants,
+            properties: model.properties,
+        };
+        let cfg_path = tlc_config::write_model_cfg(&project_root.join(spec), &name, &overrides)?;
+        say!(
+            stream,
+            "Using TLC model '{}' for {} ({})",
+            name,
+            spec.display(),
+            cfg_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Layer gating from `[layers]`
+fn layer_gates(project_root: &Path) -> Result<LayerGates> {
+    match ConfigManager::from_project_root(project_root).ok() {
+        Some(manager) => Ok(manager.layer_gates()?),
+        None => Ok(LayerGates::default()),
+    }
+}
+
+fn normalization_policies(project_root: &Path) -> Result<NormalizationPolicies> {
+    match ConfigManager::from_project_root(project_root).ok() {
+        Some(manager) => Ok(manager.normalization_policies()?),
+        None => Ok(NormalizationPolicies::default()),
+    }
+}
+
+/// Targets under `project_root`, honouring `[targets]` for the profile level
+pub(crate) fn discover_project_targets(project_root: &Path) -> Vec<Target> {
+    let options = ConfigManager::from_project_root(project_root)
+        .map(|manager| {
+            let config = manager.root_config();
+            DiscoveryOptions {
+                include_examples: config.targets.include_examples(config.profile.level),
+                include_benches: config.targets.include_benches(config.profile.level),
+            }
+        })
+        .unwrap_or_default();
+
+    discover_targets(project_root, &options)
+}
+
+/// Apply the Cargo.toml metadata policy as part of the type-level layer
+fn check_metadata_policy(
+    project_root: &Path,
+    result: &mut VerificationResult,
+    layer: Option<Layer>,
+) {
+    if layer.is_some_and(|l| l != Layer::TypeLevel) {
+        return;
+    }
+    let manifest_path = project_root.join("Cargo.toml");
+    if !manifest_path.exists() {
+        return;
+    }
+
+    let policy = ConfigManager::from_project_root(project_root)
+        .map(|manager| manager.root_config().metadata.clone())
+        .unwrap_or_default();
+    let policy = MetadataPolicy {
+        require_rust_version: policy.require_rust_version,
+        require_license: policy.require_license,
+        forbid_wildcard_dependencies: policy.forbid_wildcard_dependencies,
+    };
+    if policy.is_empty() {
+        return;
+    }
+
+    let start = std::time::Instant::now();
+    let violations = match cargo_metadata::check_manifest(&manifest_path, &policy) {
+        Ok(violations) => violations,
+        Err(e) => {
+            warn!("Skipping metadata policy checks: {}", e);
+            return;
+        }
+    };
+    if violations.is_empty() {
+        return;
+    }
+
+    let layer_result = result
+        .layer_results
+        .entry(Layer::TypeLevel)
+        .or_insert_with(|| LayerResult {
+            layer: Layer::TypeLevel,
+            status: Status::Success,
+            violations: Vec::new(),
+            execution_time: std::time::Duration::ZERO,
+            tool_outputs: Vec::new(),
+        });
+    layer_result.violations.extend(violations);
+    layer_result.execution_time += start.elapsed();
+    if layer_result.status == Status::Success {
+        layer_result.status = Status::Warning;
+    }
+    if result.overall_status == Status::Success {
+        result.overall_status = Status::Warning;
+    }
+}
+
+/// Report problems the spec linter finds in TLA+ and Alloy files, as part of the formal layer
+fn check_spec_lint(project_root: &Path, result: &mut VerificationResult, layer: Option<Layer>) {
+    if layer.is_some_and(|l| l != Layer::Formal) {
+        return;
+    }
+    let start = std::time::Instant::now();
+    let violations = spec_lint::lint_project(project_root);
+    if violations.is_empty() {
+        return;
+    }
+    let status = if violations
+        .iter()
+        .any(|v| matches!(v.severity, Severity::Critical | Severity::Error))
+    {
+        Status::Error
+    } else {
+        Status::Warning
+    };
+    add_layer_violations(result, Layer::Formal, violations, status, start.elapsed());
+}
+
+/// Report specs whose linked Rust definitions changed, as part of the formal layer
+///
+/// The drift baseline is updated only with `save_baseline`, never in action mode.
+fn check_spec_drift(
+    project_root: &Path,
+    result: &mut VerificationResult,
+    layer: Option<Layer>,
+    save_baseline: bool,
+) {
+    if layer.is_some_and(|l| l != Layer::Formal) {
+        return;
+    }
+    let config = ConfigManager::from_project_root(project_root)
+        .map(|manager| manager.root_config().spec_drift.clone())
+        .unwrap_or_default();
+    if !config.enabled() {
+        return;
+    }
+
+    let start = std::time::Instant::now();
+    let stale = TraceMatrix::build(project_root).and_then(|matrix| {
+        let mut baseline = DriftBaseline::for_project(project_root)?;
+        let stale = baseline.check(project_root, &matrix)?;
+        if save_baseline {
+            baseline.save()?;
+        }
+        Ok(stale_spec_violations(&stale, config.severity()))
+    });
+    let violations = match stale {
+        Ok(violations) => violations,
+        Err(e) => {
+            warn!("Skipping spec drift checks: {}", e);
+            return;
+        }
+    };
+    if violations.is_empty() {
+        return;
+    }
+
+    let status = match config.severity() {
+        Severity::Critical | Severity::Error => Status::Error,
+        Severity::Warning | Severity::Info => Status::Warning,
+    };
+    add_layer_violations(result, Layer::Formal, violations, status, start.elapsed());
+}
+
+/// Compare the public API with the snapshot of the last run, as the API-surface layer
+///
+/// Runs when `[api_surface] enabled` is set or the layer is selected with
+/// `--layer`; the snapshot is recorded in the run history for the next run.
+fn check_api_surface(project_root: &Path, result: &mut VerificationResult, layer: Option<Layer>) {
+    if layer.is_some_and(|l| l != Layer::ApiSurface) {
+        return;
+    }
+    let config = ConfigManager::from_project_root(project_root)
+        .map(|manager| manager.root_config().api_surface.clone())
+        .unwrap_or_default();
+    if layer.is_none() && !config.enabled() {
+        return;
+    }
+
+    let start = std::time::Instant::now();
+    let surface = match ApiSurface::snapshot(project_root) {
+        Ok(surface) => surface,
+        Err(e) => {
+            warn!("Skipping API surface checks: {}", e);
+            return;
+        }
+    };
+    let previous = match HistoryStore::for_project(project_root).latest_api_surface() {
+        Ok(previous) => previous,
+        Err(e) => {
+            warn!("Not comparing the API surface with history: {}", e);
+            None
+        }
+    };
+    let breaks = previous
+        .map(|previous| surface.breaks(&previous, &config.acknowledge))
+        .unwrap_or_default();
+    result.api_surface = Some(surface);
+
+    let status = if breaks.is_empty() {
+        Status::Success
+    } else {
+        match config.severity() {
+            Severity::Critical | Severity::Error => Status::Error,
+            Severity::Warning | Severity::Info => Status::Warning,
+        }
+    };
+    let violations = api_break_violations(&breaks, config.severity());
+    add_layer_violations(
+        result,
+        Layer::ApiSurface,
+        violations,
+        status,
+        start.elapsed(),
+    );
+}
+
+/// Merge findings into a layer, raising its status and the overall status to `status`
+fn add_layer_violations(
+    result: &mut VerificationResult,
+    layer: Layer,
+    violations: Vec<ferris_proof_core::types::Violation>,
+    status: Status,
+    elapsed: std::time::Duration,
+) {
+    let layer_result = result
+        .layer_results
+        .entry(layer)
+        .or_insert_with(|| LayerResult {
+            layer,
+            status: Status::Success,
+            violations: Vec::new(),
+            execution_time: std::time::Duration::ZERO,
+            tool_outputs: Vec::new(),
+        });
+    layer_result.violations.extend(violations);
+    layer_result.execution_time += elapsed;
+    if matches!(layer_result.status, Status::Success | Status::Warning) {
+        layer_result.status = status;
+    }
+    if matches!(result.overall_status, Status::Success | Status::Warning) {
+        result.overall_status = status;
+    }
+}
+
+/// Per-phase totals of a `--timings` run, slowest first
+fn print_timings(timings: &Timings, stream: bool) {
+    let breakdown = timings.breakdown();
+    let total: f64 = breakdown.iter().map(|p| p.total.as_secs_f64()).sum();
+
+    say!(
+        stream,
+        "\n{:<18} {:>9} {:>6} {:>6}",
+        "Phase",
+        "Time",
+        "Share",
+        "Spans"
+    );
+    for phase in &breakdown {
+        let seconds = phase.total.as_secs_f64();
+        say!(
+            stream,
+            "{:<18} {:>8.3}s {:>5.0}% {:>6}",
+            phase.phase.name(),
+            seconds,
+            if total > 0.0 {
+                seconds / total * 100.0
+            } else {
+                0.0
+            },
+            phase.spans
+        );
+    }
+
+    let slowest = timings
+        .spans()
+        .into_iter()
+        .filter(|span| span.phase == Phase::PluginExecution)
+        .max_by_key(|span| span.duration);
+    if let Some(span) = slowest {
+        say!(
+            stream,
+            "Slowest layer run: {} ({:.3}s)",
+            span.label,
+            span.duration.as_secs_f64()
+        );
+    }
+}
+
+/// Append the markdown summary to `$GITHUB_STEP_SUMMARY` when running in Actions
+fn write_job_summary(result: &VerificationResult) {
+    let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY") else {
+        warn!("GITHUB_STEP_SUMMARY is not set; skipping job summary");
+        return;
+    };
+
+    let appended = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| {
+            use std::io::Write;
+            file.write_all(github::job_summary(result).as_bytes())
+        });
+    if let Err(e) = appended {
+        warn!("Failed to write job summary to {:?}: {}", path, e);
+    }
+}
+
+/// Resolve `--module` patterns against the project's module tree
+///
+/// `*` matches any part of a module path, so `crypto::*` selects every module
+/// below `crypto`. Patterns matching no module are reported together.
+pub fn module_targets(project_root: &Path, patterns: &[String]) -> Result<Vec<Target>> {
+    let tree = ModuleTree::build(project_root);
+    let modules = tree.modules();
+    let mut selected = BTreeSet::new();
+    let mut unknown = Vec::new();
+    for pattern in patterns {
+        let pattern = pattern.strip_prefix("crate::").unwrap_or(pattern);
+        let matches: Vec<&str> = modules
+            .iter()
+            .map(|(module, _)| *module)
+            .filter(|module| wildcard_match(pattern, module))
+            .collect();
+        if matches.is_empty() {
+            unknown.push(format!("`{}`", pattern));
+        }
+        selected.extend(matches.into_iter().map(str::to_string));
+    }
+    if !unknown.is_empty() {
+        bail!(
+            "No module matches {} (known modules: {})",
+            unknown.join(", "),
+            modules
+                .iter()
+                .map(|(module, _)| *module)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    Ok(selected.into_iter().map(Target::Module).collect())
+}
+
+/// Resolve `--item` to the file defining it, failing early if it does not exist
+fn item_target(project_root: &Path, item_path: String) -> Result<Target> {
+    let tree = ModuleTree::build(project_root);
+    let file = tree
+        .item_file(&item_path)
+        .with_context(|| format!("No source file defines a module containing `{}`", item_path))?;
+    let target = Target::Item {
+        file: file.to_path_buf(),
+        item_path,
+    };
+    CacheKey::compute_content_hash(&target)?;
+    Ok(target)
+}
+
+/// Append this run to the project's verification history, and to the local
+/// usage metrics when `[metrics] enabled` is set
+fn record_history(result: &VerificationResult) {
+    let Ok(project_root) = std::env::current_dir() else {
+        return;
+    };
+
+    let (level, collect_metrics) = ConfigManager::from_project_root(&project_root)
+        .ok()
+        .map(|manager| {
+            let config = manager.root_config();
+            (Some(config.profile.level), config.metrics.enabled)
+        })
+        .unwrap_or_default();
+    let mut record =
+        RunRecord::from_result(result, level, history::current_git_commit(&project_root));
+    match Baseline::load(&Baseline::path_for_project(&project_root)) {
+        Ok(baseline) => record.suppressions = baseline.active_fingerprints(chrono::Utc::now()),
+        Err(e) => warn!("Not recording suppressions: {}", e),
+    }
+
+    if let Err(e) = HistoryStore::for_project(&project_root).record(&record) {
+        warn!("Failed to record verification history: {}", e);
+    }
+
+    if collect_metrics {
+        if let Err(e) = MetricsStore::for_project(&project_root).record(&record) {
+            warn!("Failed to record usage metrics: {}", e);
+        }
+    }
+}
+
+/// Apply `[artifacts] retention_days` and `max_total_size` after a run
+fn prune_artifacts() {
+    let Ok(project_root) = std::env::current_dir() else {
+        return;
+    };
+    let policy = artifacts::retention_policy(&project_root);
+    if policy.is_empty() {
+        return;
+    }
+    match artifacts::prune_plan(&project_root, policy) {
+        Ok(plan) => {
+            let removed = plan.execute();
+            if removed > 0 {
+                debug!("Pruned {} artifact(s) past the retention limits", removed);
+            }
+        }
+        Err(e) => warn!("Not pruning artifacts: {}", e),
+    }
+}
+
+/// Build the renderer registry, including renderers contributed by plugins
+fn renderer_registry() -> RendererRegistry {
+    let mut registry = RendererRegistry::new();
+    let mut plugin_manager = PluginManager::new();
+
+    if let Err(e) = plugin_manager.discover_plugins() {
+        warn!(
+            "Plugin discovery failed, using built-in renderers only: {}",
+            e
+        );
     }
+    registry.register_manifests(&plugin_manager.report_renderers());
 
-    // TODO: Implement verification checks
-    Ok(0)
+    registry
 }
@@ -1,8 +1,46 @@
-use anyhow::{Context, Result};
+use crate::commands::cache::format_bytes;
+use crate::commands::init::techniques_for_level;
+use crate::SchemaFormat;
+use anyhow::{anyhow, Context, Result};
 use colored::Colorize;
-use ferris_proof_config::{Config, ConfigManager};
+use console::Term;
+use ferris_proof_config::schema::ValidationResult;
+use ferris_proof_config::{Config, ConfigManager, SchemaValidator};
+use ferris_proof_core::{EnforcementMode, Technique, VerificationLevel};
+use serde::Serialize;
+use std::io::{self, Write};
 use std::path::Path;
 use std::path::PathBuf;
+use toml_edit::{Array, DocumentMut, Item, Table, Value};
+
+/// Techniques offered by `config edit`, in menu order
+const TECHNIQUES: [Technique; 10] = [
+    Technique::TypeSafety,
+    Technique::PropertyTests,
+    Technique::SessionTypes,
+    Technique::RefinementTypes,
+    Technique::ConcurrencyTesting,
+    Technique::FormalSpecs,
+    Technique::ModelChecking,
+    Technique::MutationTesting,
+    Technique::UndefinedBehaviorChecks,
+    Technique::DeductiveProofs,
+];
+
+/// `[thresholds]` keys offered by `config edit`, with their unit
+const THRESHOLDS: [(&str, &str); 3] = [
+    ("max_verification_time", "seconds"),
+    ("max_memory_usage", "bytes"),
+    ("cache_ttl", "seconds"),
+];
+
+/// Tool path settings offered by `config edit`, as `[tools.<table>]` and key
+const TOOL_PATHS: [(&str, &str); 4] = [
+    ("tla_plus", "tlc_path"),
+    ("alloy", "analyzer_path"),
+    ("kani", "cbmc_path"),
+    ("mutants", "cargo_mutants_path"),
+];
 
 pub async fn run(file: Option<PathBuf>, validate: bool) -> Result<i32> {
     if validate {
@@ -21,6 +59,26 @@ async fn validate_configuration() -> Result<i32> {
 
     let current_dir = std::env::current_dir().context("Failed to get current directory")?;
 
+    // Check the root file on its own first for field-level errors
+    let config_file = current_dir.join("ferrisproof.toml");
+    if config_file.exists() {
+        let content =
+            std::fs::read_to_string(&config_file).context("Failed to read ferrisproof.toml")?;
+        let document: toml::Value = match toml::from_str(&content) {
+            Ok(document) => document,
+            Err(e) => {
+                println!("✗ {}", format!("Invalid TOML syntax: {}", e).red());
+                return Ok(1);
+            }
+        };
+        let details =
+            SchemaValidator::new()?.validate_with_details(&serde_json::to_value(document)?);
+        print_validation_details(&details);
+        if !details.is_valid {
+            return Ok(1);
+        }
+    }
+
     match ConfigManager::from_project_root(&current_dir) {
         Ok(config_manager) => match config_manager.validate() {
             Ok(()) => {
@@ -40,6 +98,32 @@ async fn validate_configuration() -> Result<i32> {
     }
 }
 
+fn print_validation_details(details: &ValidationResult) {
+    if !details.errors.is_empty() {
+        println!("✗ {}", "Configuration validation failed:".red());
+    }
+    for error in &details.errors {
+        println!("  • {}: {}", error.field.bold(), error.message.red());
+        if let Some(expected) = &error.expected_value {
+            println!("    expected: {}", expected);
+        }
+        if let Some(suggestion) = &error.suggestion {
+            println!("    {} {}", "hint:".cyan(), suggestion);
+        }
+    }
+    for warning in &details.warnings {
+        println!(
+            "  {} {}: {}",
+            "⚠".yellow(),
+            warning.field.bold(),
+            warning.message
+        );
+        if let Some(suggestion) = &warning.suggestion {
+            println!("    {} {}", "hint:".cyan(), suggestion);
+        }
+    }
+}
+
 async fn show_file_configuration(file_path: &Path) -> Result<i32> {
     println!(
         "{}",
@@ -121,6 +205,9 @@ fn display_config(config: &Config) {
                 max_shrink_iters.to_string().green()
             );
         }
+        if let Some(runner) = proptest_config.runner {
+            println!("    Runner: {}", format!("{:?}", runner).green());
+        }
     }
 
     if let Some(ref tla_config) = config.tools.tla_plus {
@@ -134,6 +221,9 @@ fn display_config(config: &Config) {
         if let Some(workers) = tla_config.workers {
             println!("    Workers: {}", workers.to_string().green());
         }
+        if let Some(heap) = tla_config.heap {
+            println!("    Heap: {}", format_bytes(heap).green());
+        }
     }
 
     if let Some(ref alloy_config) = config.tools.alloy {
@@ -183,6 +273,9 @@ fn display_config(config: &Config) {
         "  Cache TTL: {}s",
         config.thresholds.cache_ttl.to_string().green()
     );
+    for (layer, age) in &config.cache.ttl {
+        println!("  Cache TTL ({}): {}", layer, age.green());
+    }
 
     println!("\n{}", "CI Configuration:".yellow());
     println!(
@@ -211,3 +304,618 @@ fn display_effective_config(config: &ferris_proof_config::manager::EffectiveConf
         println!("    • {}", format!("{:?}", technique).green());
     }
 }
+
+/// Print the configuration schema the validator checks against
+pub fn schema(format: SchemaFormat) -> Result<i32> {
+    match format {
+        SchemaFormat::JsonSchema => println!(
+            "{}",
+            serde_json::to_string_pretty(&SchemaValidator::config_schema())?
+        ),
+        SchemaFormat::TomlDoc => print!("{}", SchemaValidator::toml_doc()),
+    }
+    Ok(0)
+}
+
+/// Interactively edit `ferrisproof.toml` in the current directory
+pub async fn edit() -> Result<i32> {
+    let current_dir = std::env::current_dir().context("Failed to get current directory")?;
+    let config_file = current_dir.join("ferrisproof.toml");
+    if !config_file.exists() {
+        return Err(anyhow!(
+            "No ferrisproof.toml found. Run 'ferris-proof init' first."
+        ));
+    }
+
+    let term = Term::stdout();
+    if !term.is_term() {
+        return Err(anyhow!("config edit requires an interactive terminal"));
+    }
+
+    let content =
+        std::fs::read_to_string(&config_file).context("Failed to read ferrisproof.toml")?;
+    let mut editor = ConfigEditor::parse(&content)?;
+
+    loop {
+        let config = editor.config()?;
+        println!("\n{}", "Edit ferrisproof.toml".bold().cyan());
+        println!(
+            "  1. Profile level    [{}]",
+            serde_name(&config.profile.level).green()
+        );
+        println!(
+            "  2. Enforcement      [{}]",
+            serde_name(&config.profile.enforcement).green()
+        );
+        println!(
+            "  3. Techniques       [{}]",
+            config.profile.enabled_techniques.len().to_string().green()
+        );
+        println!("  4. Thresholds");
+        println!("  5. Tool paths");
+        println!("  s. Save and exit");
+        println!("  q. Quit without saving");
+        print!("Select: ");
+        io::stdout().flush()?;
+
+        let outcome = match term.read_line()?.trim() {
+            "1" => edit_level(&term, &mut editor, &config)?,
+            "2" => edit_enforcement(&term, &mut editor)?,
+            "3" => edit_techniques(&term, &mut editor, &config)?,
+            "4" => edit_thresholds(&term, &mut editor)?,
+            "5" => edit_tool_paths(&term, &mut editor)?,
+            "s" => {
+                if editor.to_string() == content {
+                    println!("{}", "No changes to save.".yellow());
+                } else {
+                    std::fs::write(&config_file, editor.to_string())
+                        .context("Failed to write ferrisproof.toml")?;
+                    println!("✓ Saved {}", "ferrisproof.toml".green());
+                }
+                return Ok(0);
+            }
+            "q" => {
+                println!("{}", "Configuration left unchanged.".yellow());
+                return Ok(0);
+            }
+            _ => {
+                println!("{}", "Invalid choice.".red());
+                continue;
+            }
+        };
+        report(outcome);
+    }
+}
+
+fn edit_level(term: &Term, editor: &mut ConfigEditor, config: &Config) -> Result<EditOutcome> {
+    let levels = [
+        VerificationLevel::Minimal,
+        VerificationLevel::Standard,
+        VerificationLevel::Strict,
+        VerificationLevel::Formal,
+    ];
+    let Some(level) = choose(term, "Verification level", &levels)? else {
+        return Ok(EditOutcome::Unchanged);
+    };
+
+    // Levels require certain techniques, so offer the level's defaults along
+    // with it; the validator rejects the change if the current set falls short
+    let defaults = techniques_for_level(level);
+    let techniques = (config.profile.enabled_techniques != defaults
+        && confirm(term, "Enable exactly the techniques of this level?")?)
+    .then_some(defaults.as_slice());
+    editor.set_level(level, techniques)
+}
+
+fn edit_enforcement(term: &Term, editor: &mut ConfigEditor) -> Result<EditOutcome> {
+    let modes = [
+        EnforcementMode::Advisory,
+        EnforcementMode::Warning,
+        EnforcementMode::Error,
+    ];
+    match choose(term, "Enforcement mode", &modes)? {
+        Some(mode) => editor.set_enforcement(mode),
+        None => Ok(EditOutcome::Unchanged),
+    }
+}
+
+fn edit_techniques(term: &Term, editor: &mut ConfigEditor, config: &Config) -> Result<EditOutcome> {
+    let mut enabled = config.profile.enabled_techniques.clone();
+    loop {
+        println!("\nTechniques (number toggles, empty line applies):");
+        for (index, technique) in TECHNIQUES.iter().enumerate() {
+            let mark = if enabled.contains(technique) {
+                "x"
+            } else {
+                " "
+            };
+            println!("  {}. [{}] {}", index + 1, mark, serde_name(technique));
+        }
+        print!("Toggle: ");
+        io::stdout().flush()?;
+
+        let input = term.read_line()?;
+        let input = input.trim();
+        if input.is_empty() {
+            break;
+        }
+        match input
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| TECHNIQUES.get(n.wrapping_sub(1)))
+        {
+            Some(technique) => match enabled.iter().position(|t| t == technique) {
+                Some(position) => {
+                    enabled.remove(position);
+                }
+                None => enabled.push(technique.clone()),
+            },
+            None => println!("{}", "Invalid choice.".red()),
+        }
+    }
+
+    if enabled == config.profile.enabled_techniques {
+        return Ok(EditOutcome::Unchanged);
+    }
+    editor.set_techniques(&enabled)
+}
+
+fn edit_thresholds(term: &Term, editor: &mut ConfigEditor) -> Result<EditOutcome> {
+    let mut outcome = EditOutcome::Unchanged;
+    for (key, unit) in THRESHOLDS {
+        let current = editor.get(&["thresholds", key]).unwrap_or_default();
+        print!("{} in {} [{}]: ", key, unit, current);
+        io::stdout().flush()?;
+
+        let input = term.read_line()?;
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+        let Ok(number) = input.parse::<u64>() else {
+            println!("{}", "Please enter a whole number.".red());
+            continue;
+        };
+        report(std::mem::replace(
+            &mut outcome,
+            editor.set_threshold(key, number)?,
+        ));
+    }
+    Ok(outcome)
+}
+
+fn edit_tool_paths(term: &Term, editor: &mut ConfigEditor) -> Result<EditOutcome> {
+    println!("Empty keeps the current path, '-' removes it.");
+    let mut outcome = EditOutcome::Unchanged;
+    for (tool, key) in TOOL_PATHS {
+        let current = editor.get(&["tools", tool, key]).unwrap_or_default();
+        print!("tools.{}.{} [{}]: ", tool, key, current);
+        io::stdout().flush()?;
+
+        let input = term.read_line()?;
+        let path = match input.trim() {
+            "" => continue,
+            "-" => None,
+            path => Some(Path::new(path)),
+        };
+        report(std::mem::replace(
+            &mut outcome,
+            editor.set_tool_path(tool, key, path)?,
+        ));
+    }
+    Ok(outcome)
+}
+
+/// Numbered menu over `options`; `None` when the user keeps the current value
+fn choose<T: Serialize + Clone>(term: &Term, title: &str, options: &[T]) -> Result<Option<T>> {
+    println!("\n{}:", title);
+    for (index, option) in options.iter().enumerate() {
+        println!("  {}. {}", index + 1, serde_name(option));
+    }
+    loop {
+        print!("Select (1-{}) [keep current]: ", options.len());
+        io::stdout().flush()?;
+
+        let input = term.read_line()?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        match input
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| options.get(n.wrapping_sub(1)))
+        {
+            Some(option) => return Ok(Some(option.clone())),
+            None => println!("{}", "Invalid choice.".red()),
+        }
+    }
+}
+
+fn confirm(term: &Term, question: &str) -> Result<bool> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+    Ok(matches!(
+        term.read_line()?.trim().to_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+fn report(outcome: EditOutcome) {
+    match outcome {
+        EditOutcome::Unchanged => {}
+        EditOutcome::Applied { warnings } => {
+            println!("✓ {}", "Updated".green());
+            for warning in warnings {
+                println!("  {} {}", "⚠".yellow(), warning);
+            }
+        }
+        EditOutcome::Rejected { errors } => {
+            println!(
+                "✗ {}",
+                "Change rejected, the configuration would be invalid:".red()
+            );
+            for error in errors {
+                println!("  • {}", error.red());
+            }
+        }
+    }
+}
+
+/// Name a value has in ferrisproof.toml, e.g. `standard` or `PropertyTests`
+fn serde_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+/// Result of applying one edit
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditOutcome {
+    Unchanged,
+    /// Applied; the configuration is valid but may have warnings
+    Applied {
+        warnings: Vec<String>,
+    },
+    /// Left unapplied because the configuration would be invalid
+    Rejected {
+        errors: Vec<String>,
+    },
+}
+
+/// `ferrisproof.toml` under edit, keeping comments and formatting
+///
+/// Every edit is validated with [`SchemaValidator`] before it is applied, so
+/// the document is always a valid configuration.
+pub struct ConfigEditor {
+    document: DocumentMut,
+    validator: SchemaValidator,
+}
+
+impl ConfigEditor {
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(Self {
+            document: content
+                .parse()
+                .context("Failed to parse ferrisproof.toml")?,
+            validator: SchemaValidator::new()?,
+        })
+    }
+
+    pub fn config(&self) -> Result<Config> {
+        toml::from_str(&self.document.to_string()).context("Failed to parse ferrisproof.toml")
+    }
+
+    /// Display form of the value at `path`, if set
+    pub fn get(&self, path: &[&str]) -> Option<String> {
+        let mut item = self.document.as_item();
+        for key in path {
+            item = item.get(key)?;
+        }
+        Some(match item.as_str() {
+            Some(text) => text.to_string(),
+            None => item.as_value()?.to_string().trim().to_string(),
+        })
+    }
+
+    /// Set the level, replacing the enabled techniques too when given
+    pub fn set_level(
+        &mut self,
+        level: VerificationLevel,
+        techniques: Option<&[Technique]>,
+    ) -> Result<EditOutcome> {
+        let techniques: Option<Array> =
+            techniques.map(|techniques| techniques.iter().map(serde_name).collect());
+        self.apply(|document| {
+            let profile = table(document, &["profile"]);
+            set(profile, "level", serde_name(&level).into());
+            if let Some(techniques) = techniques {
+                set(profile, "enabled_techniques", techniques.into());
+            }
+        })
+    }
+
+    pub fn set_enforcement(&mut self, mode: EnforcementMode) -> Result<EditOutcome> {
+        self.apply(|document| {
+            set(
+                table(document, &["profile"]),
+                "enforcement",
+                serde_name(&mode).into(),
+            );
+        })
+    }
+
+    pub fn set_techniques(&mut self, techniques: &[Technique]) -> Result<EditOutcome> {
+        let array: Array = techniques.iter().map(serde_name).collect();
+        self.apply(|document| {
+            set(
+                table(document, &["profile"]),
+                "enabled_techniques",
+                array.into(),
+            );
+        })
+    }
+
+    pub fn set_threshold(&mut self, key: &str, number: u64) -> Result<EditOutcome> {
+        let number = i64::try_from(number).map_err(|_| anyhow!("{} is too large", number))?;
+        self.apply(|document| {
+            set(table(document, &["thresholds"]), key, number.into());
+        })
+    }
+
+    /// Set `[tools.<tool>] <key>`, or remove it when `path` is `None`
+    pub fn set_tool_path(
+        &mut self,
+        tool: &str,
+        key: &str,
+        path: Option<&Path>,
+    ) -> Result<EditOutcome> {
+        self.apply(|document| {
+            let tool_table = table(document, &["tools", tool]);
+            match path {
+                Some(path) => {
+                    set(tool_table, key, path.display().to_string().into());
+                }
+                None => {
+                    tool_table.remove(key);
+                }
+            }
+        })
+    }
+
+    /// Add the tables and keys of `defaults` that the document lacks,
+    /// leaving every value already set untouched
+    ///
+    /// Returns the dotted paths that were added, empty unless applied.
+    pub fn add_missing(&mut self, defaults: &str) -> Result<(EditOutcome, Vec<String>)> {
+        let defaults: DocumentMut = defaults
+            .parse()
+            .context("Failed to parse default configuration")?;
+        let mut added = Vec::new();
+        let outcome = self.apply(|document| {
+            merge_missing(document.as_table_mut(), defaults.as_table(), "", &mut added)
+        })?;
+        if !matches!(outcome, EditOutcome::Applied { .. }) {
+            added.clear();
+        }
+        Ok((outcome, added))
+    }
+
+    /// Apply `change` to a copy and keep it only if the result validates
+    fn apply(&mut self, change: impl FnOnce(&mut DocumentMut)) -> Result<EditOutcome> {
+        let mut candidate = self.document.clone();
+        change(&mut candidate);
+        let rendered = candidate.to_string();
+        if rendered == self.document.to_string() {
+            return Ok(EditOutcome::Unchanged);
+        }
+
+        let parsed: toml::Value = toml::from_str(&rendered)?;
+        let details = self
+            .validator
+            .validate_with_details(&serde_json::to_value(parsed)?);
+        let mut errors: Vec<String> = details.errors.iter().map(ToString::to_string).collect();
+        if errors.is_empty() {
+            if let Err(e) = toml::from_str::<Config>(&rendered) {
+                errors.push(e.message().to_string());
+            }
+        }
+        if !errors.is_empty() {
+            return Ok(EditOutcome::Rejected { errors });
+        }
+
+        self.document = candidate;
+        Ok(EditOutcome::Applied {
+            warnings: details
+                .warnings
+                .iter()
+                .map(|w| format!("{}: {}", w.field, w.message))
+                .collect(),
+        })
+    }
+}
+
+impl std::fmt::Display for ConfigEditor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.document.fmt(f)
+    }
+}
+
+/// Set `key` in `table`, keeping any comment attached to the old value
+fn set(table: &mut Table, key: &str, mut new_value: Value) {
+    if let Some(old) = table.get(key).and_then(Item::as_value) {
+        *new_value.decor_mut() = old.decor().clone();
+    }
+    table[key] = Item::Value(new_value);
+}
+
+/// Table at `path`, created as needed; parents created here stay implicit so
+/// no empty `[tools]` header is written
+fn table<'d>(document: &'d mut DocumentMut, path: &[&str]) -> &'d mut Table {
+    let mut current = document.as_table_mut();
+    for (depth, key) in path.iter().enumerate() {
+        let item = current.entry(key).or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(depth + 1 < path.len());
+            Item::Table(table)
+        });
+        if !item.is_table() {
+            *item = Item::Table(Table::new());
+        }
+        current = item.as_table_mut().expect("replaced with a table above");
+    }
+    current
+}
+
+/// Copy entries of `defaults` missing from `target`, descending into tables
+/// both sides have
+fn merge_missing(target: &mut Table, defaults: &Table, prefix: &str, added: &mut Vec<String>) {
+    for (key, default) in defaults.iter() {
+        let path = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match (target.get_mut(key), default) {
+            // An empty table adds nothing but a header
+            (None, Item::Table(default)) if default.is_empty() => {}
+            (None, _) => {
+                let mut item = default.clone();
+                if let Item::Table(table) = &mut item {
+                    detach(table);
+                }
+                target.insert(key, item);
+                added.push(path);
+            }
+            (Some(Item::Table(existing)), Item::Table(default)) => {
+                merge_missing(existing, default, &path, added);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Detach a table copied from another document: its position and decor
+/// there would interleave it with existing tables or repeat that file's
+/// header comment, so it is written after everything else instead
+fn detach(table: &mut Table) {
+    table.set_position(usize::MAX);
+    table.decor_mut().clear();
+    for (_, item) in table.iter_mut() {
+        if let Item::Table(child) = item {
+            detach(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"# Project verification settings
+[profile]
+level = "standard" # raised once the specs exist
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+
+# Keep runs short in CI
+[thresholds]
+max_verification_time = 300
+max_memory_usage = 2147483648
+cache_ttl = 86400
+"#;
+
+    #[test]
+    fn test_edits_preserve_comments_and_formatting() {
+        let mut editor = ConfigEditor::parse(CONFIG).unwrap();
+        let strict = techniques_for_level(VerificationLevel::Strict);
+        assert!(matches!(
+            editor
+                .set_level(VerificationLevel::Strict, Some(&strict))
+                .unwrap(),
+            EditOutcome::Applied { .. }
+        ));
+        assert!(matches!(
+            editor
+                .set_tool_path(
+                    "tla_plus",
+                    "tlc_path",
+                    Some(Path::new("/opt/tla/tla2tools.jar"))
+                )
+                .unwrap(),
+            EditOutcome::Applied { .. }
+        ));
+
+        let edited = editor.to_string();
+        assert!(edited.starts_with("# Project verification settings\n"));
+        assert!(edited.contains("level = \"strict\" # raised once the specs exist"));
+        assert!(edited.contains("# Keep runs short in CI\n[thresholds]"));
+        assert!(edited.contains("[tools.tla_plus]\ntlc_path = \"/opt/tla/tla2tools.jar\""));
+        assert!(!edited.contains("[tools]\n"));
+        assert_eq!(
+            editor.config().unwrap().profile.level,
+            VerificationLevel::Strict
+        );
+
+        editor.set_tool_path("tla_plus", "tlc_path", None).unwrap();
+        assert_eq!(editor.get(&["tools", "tla_plus", "tlc_path"]), None);
+    }
+
+    #[test]
+    fn test_invalid_edits_are_rejected() {
+        let mut editor = ConfigEditor::parse(CONFIG).unwrap();
+        let EditOutcome::Rejected { errors } =
+            editor.set_threshold("max_verification_time", 0).unwrap()
+        else {
+            panic!("zero timeout accepted");
+        };
+        assert!(errors.iter().any(|e| e.contains("max_verification_time")));
+        assert_eq!(editor.to_string(), CONFIG);
+
+        // Strict needs more techniques than the file enables
+        assert!(matches!(
+            editor.set_level(VerificationLevel::Strict, None).unwrap(),
+            EditOutcome::Rejected { .. }
+        ));
+        assert_eq!(editor.to_string(), CONFIG);
+
+        assert_eq!(
+            editor.set_threshold("cache_ttl", 86400).unwrap(),
+            EditOutcome::Unchanged
+        );
+        let EditOutcome::Applied { warnings } =
+            editor.set_threshold("max_verification_time", 7200).unwrap()
+        else {
+            panic!("long timeout rejected");
+        };
+        assert!(!warnings.is_empty());
+        assert_eq!(
+            editor
+                .get(&["thresholds", "max_verification_time"])
+                .as_deref(),
+            Some("7200")
+        );
+    }
+
+    #[test]
+    fn test_set_techniques() {
+        let mut editor = ConfigEditor::parse(CONFIG).unwrap();
+        let techniques = [
+            Technique::TypeSafety,
+            Technique::PropertyTests,
+            Technique::MutationTesting,
+        ];
+        editor.set_techniques(&techniques).unwrap();
+        assert_eq!(
+            editor.config().unwrap().profile.enabled_techniques,
+            techniques
+        );
+
+        // Standard level requires PropertyTests
+        assert!(matches!(
+            editor.set_techniques(&[Technique::TypeSafety]).unwrap(),
+            EditOutcome::Rejected { .. }
+        ));
+    }
+}
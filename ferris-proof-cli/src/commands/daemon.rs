@@ -0,0 +1,753 @@
+use crate::commands::cache::{cache_dir_for, get_cache_dir};
+use crate::commands::check;
+use anyhow::{anyhow, bail, Context, Result};
+use ferris_proof_config::ConfigManager;
+use ferris_proof_core::plugins::{CancellationToken, SelectionStrategy};
+use ferris_proof_core::seed;
+use ferris_proof_core::timings::Timings;
+use ferris_proof_core::types::{UnavailableLayer, Violation};
+use ferris_proof_core::verification::Target;
+use ferris_proof_core::{CacheManager, Layer, VerificationEngine, VerificationResult};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::{debug, info, warn};
+
+/// Socket below `.ferris-proof/` when `--socket` is not given
+pub const SOCKET_FILE: &str = "daemon.sock";
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Serve JSON-RPC on a local socket until `shutdown` or Ctrl-C
+///
+/// Requests and responses are single-line JSON-RPC 2.0 messages. Requests on
+/// one connection are handled concurrently, so a client can `cancel` its own
+/// running `verify`.
+pub async fn run(socket: Option<PathBuf>) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let daemon = Arc::new(Daemon::new(project_root.clone()));
+
+    #[cfg(unix)]
+    {
+        let socket = socket.unwrap_or_else(|| project_root.join(".ferris-proof").join(SOCKET_FILE));
+        serve_unix(daemon, socket).await?;
+    }
+    #[cfg(windows)]
+    {
+        let pipe = socket
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| default_pipe_name(&project_root));
+        serve_pipe(daemon, &pipe).await?;
+    }
+
+    Ok(0)
+}
+
+#[cfg(unix)]
+async fn serve_unix(daemon: Arc<Daemon>, socket: PathBuf) -> Result<()> {
+    use tokio::net::{UnixListener, UnixStream};
+
+    if socket.exists() {
+        if UnixStream::connect(&socket).await.is_ok() {
+            bail!("A daemon is already listening on {}", socket.display());
+        }
+        // Left behind by a daemon that did not shut down cleanly
+        std::fs::remove_file(&socket)
+            .with_context(|| format!("Failed to remove stale socket {:?}", socket))?;
+    }
+    if let Some(parent) = socket.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(&socket)
+        .with_context(|| format!("Failed to bind daemon socket {:?}", socket))?;
+    println!("Serving JSON-RPC on {}", socket.display());
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                tokio::spawn(serve_connection(stream, Arc::clone(&daemon)));
+            }
+            _ = daemon.shutdown.cancelled() => break,
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    info!("Shutting down");
+    daemon.cancel(None);
+    let _ = std::fs::remove_file(&socket);
+    Ok(())
+}
+
+/// `\\.\pipe\ferris-proof-<hash>`, unique per project root
+#[cfg(windows)]
+fn default_pipe_name(project_root: &Path) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    project_root.hash(&mut hasher);
+    format!(r"\\.\pipe\ferris-proof-{:016x}", hasher.finish())
+}
+
+#[cfg(windows)]
+async fn serve_pipe(daemon: Arc<Daemon>, pipe: &str) -> Result<()> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(pipe)
+        .with_context(|| {
+            format!(
+                "Failed to create pipe {} (is a daemon already running?)",
+                pipe
+            )
+        })?;
+    println!("Serving JSON-RPC on {}", pipe);
+
+    loop {
+        tokio::select! {
+            connected = server.connect() => {
+                connected?;
+                // A new instance must exist before the next client connects
+                let stream = std::mem::replace(&mut server, ServerOptions::new().create(pipe)?);
+                tokio::spawn(serve_connection(stream, Arc::clone(&daemon)));
+            }
+            _ = daemon.shutdown.cancelled() => break,
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    info!("Shutting down");
+    daemon.cancel(None);
+    Ok(())
+}
+
+async fn serve_connection<S>(stream: S, daemon: Arc<Daemon>)
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (reader, writer) = tokio::io::split(stream);
+    let writer = Arc::new(tokio::sync::Mutex::new(writer));
+    let mut lines = BufReader::new(reader).lines();
+
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                debug!("Daemon connection closed: {}", e);
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let daemon = Arc::clone(&daemon);
+        let writer = Arc::clone(&writer);
+        tokio::spawn(async move {
+            let Some(response) = daemon.handle_message(&line).await else {
+                return;
+            };
+            let mut writer = writer.lock().await;
+            let written = writer
+                .write_all(format!("{}\n", response).as_bytes())
+                .await
+                .and(writer.flush().await);
+            if let Err(e) = written {
+                debug!("Failed to write daemon response: {}", e);
+            }
+            // Stop only once the client has its answer
+            if daemon.stopping.load(Ordering::SeqCst) {
+                daemon.shutdown.cancel();
+            }
+        });
+    }
+}
+
+/// Verification still in progress
+struct Job {
+    targets: Vec<String>,
+    started: Instant,
+    cancellation: CancellationToken,
+}
+
+/// Outcome of the latest finished `verify`
+#[derive(Debug, Clone)]
+struct RunSummary {
+    job: u64,
+    status: String,
+    violations: usize,
+    duration_ms: u128,
+}
+
+/// Engine reused by every `verify`, so its cache and plugin stats stay warm
+struct WarmEngine {
+    engine: VerificationEngine,
+    /// [`Daemon::config_generation`] the engine was configured for
+    generation: u64,
+    strategy: SelectionStrategy,
+    /// Layers without a usable tool, for every configured technique
+    unavailable: Vec<UnavailableLayer>,
+}
+
+/// State shared by every connection for the daemon's lifetime
+pub struct Daemon {
+    project_root: PathBuf,
+    started: Instant,
+    /// Loaded on first use and reloaded when a ferrisproof.toml changes
+    config: Mutex<Option<ConfigManager>>,
+    /// Bumped whenever `config` is loaded or reloaded
+    config_generation: AtomicU64,
+    /// Built on the first `verify`; jobs take turns on it
+    engine: tokio::sync::Mutex<Option<WarmEngine>>,
+    jobs: Mutex<BTreeMap<u64, Job>>,
+    next_job: AtomicU64,
+    last_run: Mutex<Option<RunSummary>>,
+    /// Set by `shutdown`, which takes effect after its response is sent
+    stopping: AtomicBool,
+    shutdown: CancellationToken,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct VerifyParams {
+    /// Files to verify, relative to the project root; every target when empty
+    #[serde(default)]
+    targets: Vec<PathBuf>,
+    /// Module path pattern as for `check --module`, e.g. `crypto::*`
+    module: Option<String>,
+    layer: Option<Layer>,
+    #[serde(default)]
+    keep_going: bool,
+    plugin: Option<String>,
+    seed: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CancelParams {
+    /// Job to cancel, every running job when omitted
+    job: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ConfigParams {
+    /// File whose effective config is resolved, the project root when omitted
+    file: Option<PathBuf>,
+}
+
+/// Error returned as a JSON-RPC error object
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl Daemon {
+    pub fn new(project_root: PathBuf) -> Self {
+        Self {
+            project_root,
+            started: Instant::now(),
+            config: Mutex::new(None),
+            config_generation: AtomicU64::new(0),
+            engine: tokio::sync::Mutex::new(None),
+            jobs: Mutex::new(BTreeMap::new()),
+            next_job: AtomicU64::new(1),
+            last_run: Mutex::new(None),
+            stopping: AtomicBool::new(false),
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    /// Response line for one request line, `None` for notifications
+    pub async fn handle_message(&self, line: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, e.to_string())),
+        };
+        let id = request.get("id").cloned();
+        let Some(method) = request.get("method").and_then(|m| m.as_str()) else {
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "Missing method",
+            ));
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        debug!("Daemon request: {}", method);
+        let result = self.dispatch(method, params).await;
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_response(id, e.code, e.message),
+        })
+    }
+
+    async fn dispatch(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "verify" => self.verify(parse_params(params)?).await,
+            "status" => Ok(self.status()),
+            "cancel" => {
+                let params: CancelParams = parse_params(params)?;
+                Ok(json!({ "cancelled": self.cancel(params.job) }))
+            }
+            "config" => self.config(parse_params(params)?),
+            "cache/stats" => cache_stats(),
+            "shutdown" => {
+                self.stopping.store(true, Ordering::SeqCst);
+                self.cancel(None);
+                Ok(Value::Null)
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method '{}'", method),
+            )),
+        }
+    }
+
+    async fn verify(&self, params: VerifyParams) -> Result<Value, RpcError> {
+        let targets: Vec<Target> = match (&params.module, params.targets.is_empty()) {
+            (Some(module), _) => {
+                check::module_targets(&self.project_root, std::slice::from_ref(module))
+                    .map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))?
+            }
+            (None, false) => params
+                .targets
+                .iter()
+                .map(|path| self.target(path))
+                .collect::<Result<_, _>>()?,
+            (None, true) => check::discover_project_targets(&self.project_root),
+        };
+
+        let job = self.next_job.fetch_add(1, Ordering::SeqCst);
+        let cancellation = self.shutdown.child_token();
+        self.jobs.lock().unwrap().insert(
+            job,
+            Job {
+                targets: targets.iter().map(Target::label).collect(),
+                started: Instant::now(),
+                cancellation: cancellation.clone(),
+            },
+        );
+        info!("Verify job {} started for {} target(s)", job, targets.len());
+
+        let verified = self.run(&targets, &params, cancellation).await;
+        let started = self
+            .jobs
+            .lock()
+            .unwrap()
+            .remove(&job)
+            .map_or_else(Instant::now, |job| job.started);
+        let (result, suppressed) =
+            verified.map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+
+        let violations: Vec<&Violation> = result
+            .layer_results
+            .values()
+            .flat_map(|layer| &layer.violations)
+            .collect();
+        let summary = RunSummary {
+            job,
+            status: format!("{:?}", result.overall_status),
+            violations: violations.len(),
+            duration_ms: started.elapsed().as_millis(),
+        };
+        *self.last_run.lock().unwrap() = Some(summary.clone());
+
+        Ok(json!({
+            "job": job,
+            "status": result.overall_status,
+            "interrupted": result.interrupted,
+            "violations": violations,
+            "suppressed": suppressed,
+            "seed": result.seed,
+            "duration_ms": summary.duration_ms,
+        }))
+    }
+
+    /// Verify on the warm engine, reconfiguring it first if the configuration changed
+    async fn run(
+        &self,
+        targets: &[Target],
+        params: &VerifyParams,
+        cancellation: CancellationToken,
+    ) -> Result<(VerificationResult, usize)> {
+        let generation = self.config_generation()?;
+        let mut slot = tokio::select! {
+            slot = self.engine.lock() => slot,
+            _ = cancellation.cancelled() => return Err(anyhow!("Cancelled before it started")),
+        };
+        let warm = match slot.take() {
+            Some(warm) if warm.generation == generation => warm,
+            previous => self.warm_up(previous.map(|warm| warm.engine), generation)?,
+        };
+
+        let strategy = SelectionStrategy {
+            forced: params.plugin.clone(),
+            ..warm.strategy.clone()
+        };
+        // A forced plugin may have tools of its own, so only it is checked again
+        let unavailable = match &params.plugin {
+            Some(_) => check::tool_preflight(&self.project_root, &strategy, params.layer)?,
+            None => warm
+                .unavailable
+                .iter()
+                .filter(|unavailable| params.layer.is_none_or(|layer| unavailable.layer == layer))
+                .cloned()
+                .collect(),
+        };
+        let timings = Timings::default();
+        let engine = warm
+            .engine
+            .with_timings(timings.clone())
+            .with_cancellation(cancellation)
+            .with_environment(check::capture_environment(&self.project_root, &timings))
+            .with_seed(params.seed.unwrap_or_else(seed::random_seed))
+            .with_keep_going(params.keep_going)
+            .with_layer(params.layer)
+            .with_selection_strategy(strategy)
+            .with_unavailable_layers(unavailable);
+
+        let verified =
+            check::verify_on(&engine, &self.project_root, targets, params.layer, &timings).await;
+        *slot = Some(WarmEngine { engine, ..warm });
+        verified
+    }
+
+    /// Configure `engine`, or a new one, from the current configuration
+    fn warm_up(&self, engine: Option<VerificationEngine>, generation: u64) -> Result<WarmEngine> {
+        let reused = engine.is_some();
+        let engine = check::configure_engine(
+            engine.unwrap_or_default(),
+            &self.project_root,
+            cache_dir_for(&self.project_root).ok().as_deref(),
+            &Timings::default(),
+        )?;
+        let strategy = check::selection_strategy(&self.project_root, None);
+        let unavailable = check::tool_preflight(&self.project_root, &strategy, None)?;
+        info!(
+            "{} verification engine",
+            if reused { "Reconfigured" } else { "Started" }
+        );
+        Ok(WarmEngine {
+            engine,
+            generation,
+            strategy,
+            unavailable,
+        })
+    }
+
+    /// Generation of the configuration, reloading it first if a ferrisproof.toml changed
+    fn config_generation(&self) -> Result<u64> {
+        self.with_config(&self.project_root, |_| ())?;
+        Ok(self.config_generation.load(Ordering::SeqCst))
+    }
+
+    /// Run `f` on the configuration, loading it on first use and reloading it
+    /// when a ferrisproof.toml affecting `file` changed
+    fn with_config<T>(&self, file: &Path, f: impl FnOnce(&ConfigManager) -> T) -> Result<T> {
+        let mut guard = self.config.lock().unwrap();
+        match guard.as_mut() {
+            Some(manager) => match manager.reload_if_changed(&[file.to_path_buf()]) {
+                Ok(Some(_)) => {
+                    self.config_generation.fetch_add(1, Ordering::SeqCst);
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Keeping previous configuration: {}", e),
+            },
+            None => {
+                *guard = Some(ConfigManager::from_project_root(&self.project_root)?);
+                self.config_generation.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        Ok(f(guard.as_ref().unwrap()))
+    }
+
+    /// Target for a file, by extension as the language server does
+    fn target(&self, path: &Path) -> Result<Target, RpcError> {
+        let path = self.project_root.join(path);
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("rs") => Ok(Target::RustFile(path)),
+            Some("tla") | Some("als") => Ok(Target::FormalSpec(path)),
+            _ => Err(RpcError::new(
+                INVALID_PARAMS,
+                format!(
+                    "Cannot verify {}: expected a .rs, .tla or .als file",
+                    path.display()
+                ),
+            )),
+        }
+    }
+
+    fn status(&self) -> Value {
+        let now = Instant::now();
+        let running: Vec<Value> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, job)| {
+                json!({
+                    "job": id,
+                    "targets": job.targets,
+                    "running_ms": now.duration_since(job.started).as_millis(),
+                })
+            })
+            .collect();
+        let last_run = self.last_run.lock().unwrap().clone().map(|run| {
+            json!({
+                "job": run.job,
+                "status": run.status,
+                "violations": run.violations,
+                "duration_ms": run.duration_ms,
+            })
+        });
+
+        json!({
+            "pid": std::process::id(),
+            "version": env!("CARGO_PKG_VERSION"),
+            "project_root": self.project_root,
+            "uptime_secs": self.started.elapsed().as_secs(),
+            "running": running,
+            "last_run": last_run,
+        })
+    }
+
+    /// Cancel one job or all of them, returning the ids cancelled
+    fn cancel(&self, job: Option<u64>) -> Vec<u64> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.iter()
+            .filter(|(id, _)| job.is_none_or(|job| job == **id))
+            .map(|(id, job)| {
+                warn!("Cancelling verify job {}", id);
+                job.cancellation.cancel();
+                *id
+            })
+            .collect()
+    }
+
+    fn config(&self, params: ConfigParams) -> Result<Value, RpcError> {
+        let file = params
+            .file
+            .map_or_else(|| self.project_root.clone(), |f| self.project_root.join(f));
+
+        let config = self
+            .with_config(&file, |manager| manager.for_file(&file))
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+
+        Ok(json!({
+            "file": file,
+            "level": config.level,
+            "enforcement": config.enforcement,
+            "enabled_techniques": config.enabled_techniques,
+            "config_hash": config.config_hash,
+        }))
+    }
+}
+
+fn cache_stats() -> Result<Value, RpcError> {
+    let cache_dir = get_cache_dir().map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+    let info = CacheManager::with_cache_dir(cache_dir)
+        .info()
+        .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+
+    Ok(json!({
+        "cache_dir": info.cache_dir,
+        "total_entries": info.total_entries,
+        "valid_entries": info.valid_entries,
+        "expired_entries": info.expired_entries,
+        "total_size_bytes": info.total_size_bytes,
+        "disk_size_bytes": info.disk_size_bytes,
+    }))
+}
+
+/// Params of a method, where omitted params mean the defaults
+fn parse_params<T: Default + for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcError> {
+    if params.is_null() {
+        return Ok(T::default());
+    }
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn error_response(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_answers_status_and_reports_errors() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let daemon = Daemon::new(temp_dir.path().to_path_buf());
+
+        let status = daemon
+            .handle_message(r#"{"jsonrpc":"2.0","id":1,"method":"status"}"#)
+            .await
+            .unwrap();
+        assert_eq!(status["id"], 1);
+        assert_eq!(status["result"]["pid"], std::process::id());
+        assert_eq!(status["result"]["running"], json!([]));
+
+        let unknown = daemon
+            .handle_message(r#"{"jsonrpc":"2.0","id":2,"method":"frobnicate"}"#)
+            .await
+            .unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+
+        let invalid = daemon
+            .handle_message(
+                r#"{"jsonrpc":"2.0","id":3,"method":"verify","params":{"targets":["README.md"]}}"#,
+            )
+            .await
+            .unwrap();
+        assert_eq!(invalid["error"]["code"], INVALID_PARAMS);
+
+        let unknown_module = daemon
+            .handle_message(
+                r#"{"jsonrpc":"2.0","id":4,"method":"verify","params":{"module":"nowhere::*"}}"#,
+            )
+            .await
+            .unwrap();
+        assert_eq!(unknown_module["error"]["code"], INVALID_PARAMS);
+        assert!(unknown_module["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("No module matches `nowhere::*`"));
+
+        let garbled = daemon.handle_message("{not json").await.unwrap();
+        assert_eq!(garbled["error"]["code"], PARSE_ERROR);
+
+        // Notifications get no response
+        assert!(daemon
+            .handle_message(r#"{"jsonrpc":"2.0","method":"status"}"#)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_verify_reuses_the_engine_until_config_changes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config = "[profile]\nlevel = \"minimal\"\nenforcement = \"advisory\"\n\
+                      enabled_techniques = [\"TypeSafety\"]\n";
+        std::fs::write(root.join("ferrisproof.toml"), config).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+        let daemon = Daemon::new(root.to_path_buf());
+        let verify =
+            r#"{"jsonrpc":"2.0","id":1,"method":"verify","params":{"targets":["src/lib.rs"]}}"#;
+
+        let first = daemon.handle_message(verify).await.unwrap();
+        assert!(first.get("result").is_some(), "{}", first);
+        let mut events = {
+            let slot = daemon.engine.lock().await;
+            assert_eq!(slot.as_ref().unwrap().generation, 1);
+            slot.as_ref().unwrap().engine.subscribe()
+        };
+
+        // Events of the next run reach a subscriber of the warm engine
+        daemon.handle_message(verify).await.unwrap();
+        assert!(events.try_recv().is_ok());
+
+        std::fs::write(
+            root.join("ferrisproof.toml"),
+            config.replace("advisory", "warning"),
+        )
+        .unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(root.join("ferrisproof.toml"))
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+        daemon.handle_message(verify).await.unwrap();
+        let slot = daemon.engine.lock().await;
+        assert_eq!(slot.as_ref().unwrap().generation, 2);
+    }
+
+    #[test]
+    fn test_cancel_targets_running_jobs() {
+        let daemon = Daemon::new(PathBuf::from("."));
+        let tokens: Vec<CancellationToken> = (1..=2)
+            .map(|id| {
+                let token = daemon.shutdown.child_token();
+                daemon.jobs.lock().unwrap().insert(
+                    id,
+                    Job {
+                        targets: Vec::new(),
+                        started: Instant::now(),
+                        cancellation: token.clone(),
+                    },
+                );
+                token
+            })
+            .collect();
+
+        assert_eq!(daemon.cancel(Some(2)), vec![2]);
+        assert!(!tokens[0].is_cancelled());
+        assert!(tokens[1].is_cancelled());
+        assert_eq!(daemon.cancel(None), vec![1, 2]);
+        assert!(tokens[0].is_cancelled());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_serves_requests_over_unix_socket() {
+        use tokio::net::UnixStream;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let socket = temp_dir.path().join(SOCKET_FILE);
+        let daemon = Arc::new(Daemon::new(temp_dir.path().to_path_buf()));
+        let server = tokio::spawn(serve_unix(Arc::clone(&daemon), socket.clone()));
+
+        let stream = loop {
+            match UnixStream::connect(&socket).await {
+                Ok(stream) => break stream,
+                Err(_) => tokio::time::sleep(std::time::Duration::from_millis(10)).await,
+            }
+        };
+        let (reader, mut writer) = stream.into_split();
+        writer
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":7,\"method\":\"shutdown\"}\n")
+            .await
+            .unwrap();
+        let line = BufReader::new(reader)
+            .lines()
+            .next_line()
+            .await
+            .unwrap()
+            .unwrap();
+        let response: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(
+            response,
+            json!({ "jsonrpc": "2.0", "id": 7, "result": null })
+        );
+
+        server.await.unwrap().unwrap();
+        assert!(!socket.exists());
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use ferris_proof_core::history::{HistoryStore, RunRecord, ViolationDiff, ViolationRecord};
+use ferris_proof_core::VerificationResult;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub async fn run(
+    old: Option<PathBuf>,
+    new: Option<PathBuf>,
+    against: Option<String>,
+) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let store = HistoryStore::for_project(&project_root);
+
+    let (old_record, new_report) = match against {
+        Some(git_ref) => {
+            if new.is_some() {
+                return Err(anyhow!("Pass at most one report together with --against"));
+            }
+            let commit = resolve_commit(&git_ref)?;
+            let record = store.latest_for_commit(&commit)?.ok_or_else(|| {
+                anyhow!(
+                    "No verification run recorded for {} ({}); run 'ferris-proof check' on that commit first",
+                    git_ref,
+                    &commit[..commit.len().min(12)]
+                )
+            })?;
+            (record, old)
+        }
+        None => {
+            let old =
+                old.ok_or_else(|| anyhow!("Provide two report files or --against <git-ref>"))?;
+            (load_report(&old)?, new)
+        }
+    };
+
+    let new_record = match new_report {
+        Some(path) => load_report(&path)?,
+        None => store
+            .load()?
+            .pop()
+            .ok_or_else(|| anyhow!("No verification history recorded yet; pass a report file"))?,
+    };
+
+    let diff = ViolationDiff::between(&old_record, &new_record);
+    print_diff(&diff);
+
+    Ok(if diff.added.is_empty() { 0 } else { 1 })
+}
+
+/// Load a JSON report produced by `check --report json`
+pub(crate) fn load_report(path: &Path) -> Result<RunRecord> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read report {:?}", path))?;
+    let result: VerificationResult = serde_json::from_str(&content)
+        .with_context(|| format!("{:?} is not a JSON verification report", path))?;
+
+    Ok(RunRecord::from_result(&result, None, None))
+}
+
+fn resolve_commit(git_ref: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", &format!("{}^{{commit}}", git_ref)])
+        .output()
+        .context("Failed to execute git")?;
+
+    if !output.status.success() {
+        return Err(anyhow!("Unknown git ref '{}'", git_ref));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn print_diff(diff: &ViolationDiff) {
+    print_section("Added", &diff.added, "+".red());
+    print_section("Fixed", &diff.fixed, "-".green());
+
+    println!(
+        "{} added, {} fixed, {} unchanged",
+        diff.added.len().to_string().red(),
+        diff.fixed.len().to_string().green(),
+        diff.unchanged.len()
+    );
+}
+
+fn print_section(title: &str, violations: &[ViolationRecord], marker: colored::ColoredString) {
+    if violations.is_empty() {
+        return;
+    }
+
+    println!("{}", title.bold());
+    for violation in violations {
+        println!(
+            "  {} [{}] {}: {}",
+            marker,
+            violation.rule,
+            violation.file.display(),
+            violation.message
+        );
+    }
+    println!();
+}
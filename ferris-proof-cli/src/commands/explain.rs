@@ -1,30 +1,254 @@
-use anyhow::Result;
+use crate::commands::cache::get_cache_dir;
+use crate::commands::plugins::plugin_manager;
+use crate::render::snippet;
+use anyhow::{anyhow, Context, Result};
 use colored::Colorize;
-use std::collections::HashMap;
+use ferris_proof_core::error_catalog::{self, ErrorExplanation};
+use ferris_proof_core::history::{HistoryStore, ViolationRecord};
+use ferris_proof_core::types::{LayerResult, Location, ToolOutput, Violation};
+use ferris_proof_core::{CacheManager, VerificationResult};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use tracing::warn;
 
-pub async fn run(error_code: String) -> Result<i32> {
-    let error_catalog = create_error_catalog();
+/// Tool output lines shown with a live violation
+const EXCERPT_LINES: usize = 12;
 
-    match error_catalog.get(&error_code) {
-        Some(explanation) => {
-            display_error_explanation(&error_code, explanation);
-            Ok(0)
+/// Explain an error code, or violations of the latest run
+///
+/// `error_code` is looked up in the catalog first; otherwise it selects
+/// violations of the latest run by fingerprint or violation id. `last`
+/// explains every violation of that run.
+pub async fn run(error_code: Option<String>, last: bool, report: Option<&Path>) -> Result<i32> {
+    let error_catalog = error_catalog_with_plugins();
+
+    let code = match error_code {
+        Some(code) if !last => {
+            if let Some(explanation) = error_catalog.get(&code) {
+                display_error_explanation(&code, explanation);
+                return Ok(0);
+            }
+            Some(code)
         }
-        None => {
-            display_unknown_error(&error_code, &error_catalog);
-            Ok(1)
+        _ => None,
+    };
+
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let mut live = match live_violations(&project_root, report) {
+        // Without any recorded run an unknown code is just unknown
+        Err(_) if code.is_some() && report.is_none() => Vec::new(),
+        live => live?,
+    };
+    if let Some(code) = &code {
+        live.retain(|live| live.matches(code));
+        if live.is_empty() {
+            display_unknown_error(code, &error_catalog);
+            return Ok(1);
+        }
+    } else if live.is_empty() {
+        println!("{} The latest run reported no violations", "✓".green());
+        return Ok(0);
+    }
+
+    for (i, violation) in live.iter().enumerate() {
+        if i > 0 {
+            println!("{}", "─".repeat(60).dimmed());
+        }
+        display_live_violation(&project_root, violation, &error_catalog);
+    }
+    Ok(0)
+}
+
+/// Violation of a recorded run with the tool output that produced it
+#[derive(Debug)]
+struct LiveViolation {
+    violation: Violation,
+    fingerprint: String,
+    tool_output: Option<ToolOutput>,
+}
+
+impl LiveViolation {
+    fn new(violation: Violation, layer: Option<&LayerResult>) -> Self {
+        Self {
+            fingerprint: ViolationRecord::from_violation(&violation).fingerprint,
+            violation,
+            // A failing tool is the one that explains the violation
+            tool_output: layer.and_then(|layer| {
+                layer
+                    .tool_outputs
+                    .iter()
+                    .find(|output| output.exit_code != 0)
+                    .or_else(|| layer.tool_outputs.first())
+                    .cloned()
+            }),
         }
     }
+
+    fn matches(&self, code: &str) -> bool {
+        self.fingerprint == code || self.violation.id == code
+    }
 }
 
-#[derive(Debug, Clone)]
-struct ErrorExplanation {
-    title: String,
-    description: String,
-    causes: Vec<String>,
-    solutions: Vec<String>,
-    examples: Vec<String>,
-    related_codes: Vec<String>,
+/// Violations of `report`, or of the latest recorded run
+///
+/// History keeps only a summary of each violation; line numbers and tool
+/// output are recovered from cached results with the same fingerprint.
+fn live_violations(project_root: &Path, report: Option<&Path>) -> Result<Vec<LiveViolation>> {
+    if let Some(path) = report {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read report {:?}", path))?;
+        let result: VerificationResult = serde_json::from_str(&content)
+            .with_context(|| format!("{:?} is not a JSON verification report", path))?;
+        let mut layers: Vec<&LayerResult> = result.layer_results.values().collect();
+        layers.sort_by_key(|layer| format!("{:?}", layer.layer));
+        return Ok(layers
+            .into_iter()
+            .flat_map(|layer| {
+                layer
+                    .violations
+                    .iter()
+                    .map(move |v| LiveViolation::new(v.clone(), Some(layer)))
+            })
+            .collect());
+    }
+
+    let record = HistoryStore::for_project(project_root)
+        .load()?
+        .pop()
+        .ok_or_else(|| {
+            anyhow!(
+                "No verification history recorded yet; run 'ferris-proof check' or pass --report"
+            )
+        })?;
+    let cache = get_cache_dir().ok().and_then(|dir| {
+        let mut manager = CacheManager::with_cache_dir(dir);
+        manager.load().ok().map(|_| manager)
+    });
+
+    Ok(record
+        .violations
+        .into_iter()
+        .map(|recorded| {
+            let is_recorded = |v: &Violation| {
+                ViolationRecord::from_violation(v).fingerprint == recorded.fingerprint
+            };
+            let cached = cache.as_ref().and_then(|cache| {
+                let entry = cache.cache().entries_matching(is_recorded).pop()?;
+                let violation = entry.result.violations.iter().find(|v| is_recorded(v))?;
+                Some(LiveViolation::new(violation.clone(), Some(&entry.result)))
+            });
+            match cached {
+                Some(live) => live,
+                None => LiveViolation::new(
+                    Violation {
+                        id: recorded.rule.clone(),
+                        severity: recorded.severity,
+                        location: Location {
+                            file: recorded.file,
+                            line: None,
+                            column: None,
+                            span: None,
+                        },
+                        message: recorded.message,
+                        suggestion: None,
+                        rule: recorded.rule,
+                    },
+                    None,
+                ),
+            }
+        })
+        .collect())
+}
+
+fn display_live_violation(
+    project_root: &Path,
+    live: &LiveViolation,
+    catalog: &HashMap<String, ErrorExplanation>,
+) {
+    print!(
+        "{}",
+        snippet::render_for_project(project_root, &live.violation)
+    );
+    println!(
+        "  {} {}",
+        "fingerprint:".dimmed(),
+        live.fingerprint.dimmed()
+    );
+    println!();
+
+    if let Some(output) = &live.tool_output {
+        println!(
+            "{} {} (exit code {})",
+            "Tool Output:".bold().cyan(),
+            output.tool,
+            output.exit_code
+        );
+        for line in excerpt(output) {
+            println!("  {}", line.dimmed());
+        }
+        if let Some(id) = &output.artifact_id {
+            println!("  full output: ferris-proof artifacts show {}", id);
+        }
+        println!();
+    }
+
+    match catalog.get(&live.violation.id) {
+        Some(explanation) => {
+            println!(
+                "{} {}",
+                "Guidance:".bold().yellow(),
+                explanation.title.bold()
+            );
+            println!("  {}", explanation.description);
+            for (i, solution) in explanation.solutions.iter().enumerate() {
+                println!("  {}. {}", i + 1, solution);
+            }
+            println!();
+        }
+        None => println!(
+            "{} No catalog entry for {}",
+            "•".yellow(),
+            live.violation.id
+        ),
+    }
+}
+
+/// Last lines of a tool's output, stderr first as that is where tools report failures
+fn excerpt(output: &ToolOutput) -> Vec<&str> {
+    let source = if output.stderr.trim().is_empty() {
+        &output.stdout
+    } else {
+        &output.stderr
+    };
+    let lines: Vec<&str> = source.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(EXCERPT_LINES)..].to_vec()
+}
+
+/// Error catalog serialized as JSON, ordered by code
+pub(crate) fn catalog_json() -> Result<String> {
+    let catalog: BTreeMap<String, ErrorExplanation> = create_error_catalog().into_iter().collect();
+    Ok(serde_json::to_string_pretty(&catalog)?)
+}
+
+/// Built-in codes plus those registered by loaded and discovered plugins
+///
+/// A plugin code outside its namespace or clashing with a registered code is
+/// left out with a warning; the rest of the catalog stays usable.
+fn error_catalog_with_plugins() -> HashMap<String, ErrorExplanation> {
+    let mut catalog = create_error_catalog();
+    let plugin_codes = match plugin_manager() {
+        Ok(manager) => manager.error_codes(),
+        Err(e) => {
+            warn!("Failed to load plugin error codes: {}", e);
+            Vec::new()
+        }
+    };
+    for (plugin, codes) in plugin_codes {
+        for issue in error_catalog::register_plugin_codes(&mut catalog, &plugin, &codes) {
+            warn!("Ignoring plugin error code: {}", issue);
+        }
+    }
+    catalog
 }
 
 fn create_error_catalog() -> HashMap<String, ErrorExplanation> {
@@ -99,6 +323,25 @@ fn create_error_catalog() -> HashMap<String, ErrorExplanation> {
         },
     );
 
+    catalog.insert(
+        "FP-CF-004".to_string(),
+        ErrorExplanation {
+            title: "Unknown Configuration Section".to_string(),
+            description: "ferrisproof.toml contains a top-level section FerrisProof does not know."
+                .to_string(),
+            causes: vec![
+                "Typo in a section name".to_string(),
+                "Section from a newer FerrisProof version".to_string(),
+            ],
+            solutions: vec![
+                "Check the section name against the documentation".to_string(),
+                "Run 'ferris-proof upgrade' to migrate older configurations".to_string(),
+            ],
+            examples: vec!["[tool]  # should be [tools]".to_string()],
+            related_codes: vec!["FP-CF-001".to_string()],
+        },
+    );
+
     // Verification Errors (FP-VR-xxx)
     catalog.insert(
         "FP-VR-001".to_string(),
@@ -146,6 +389,58 @@ fn create_error_catalog() -> HashMap<String, ErrorExplanation> {
         related_codes: vec!["FP-VR-001".to_string()],
     });
 
+    catalog.insert("FP-VR-003".to_string(), ErrorExplanation {
+        title: "Specification Possibly Stale".to_string(),
+        description: "A Rust definition linked to a TLA+ or Alloy definition with #[verification(spec = \"...\")] changed since the last check, but the specification did not.".to_string(),
+        causes: vec![
+            "A constant or type was changed without updating the model".to_string(),
+            "The specification was updated in a separate commit that has not landed".to_string(),
+        ],
+        solutions: vec![
+            "Update the linked specification definition to match the code".to_string(),
+            "Run `ferris-proof trace --accept-drift` if the specification still holds".to_string(),
+            "Set [spec_drift] severity = \"Error\" to block on drift".to_string(),
+        ],
+        examples: vec![
+            "#[verification(spec = \"specs/queue.tla#MaxSize\")]\npub const MAX_SIZE: usize = 16; // spec still says MaxSize == 8".to_string(),
+        ],
+        related_codes: vec!["FP-VR-002".to_string()],
+    });
+
+    catalog.insert("FP-VR-004".to_string(), ErrorExplanation {
+        title: "Public API Invariant Broken".to_string(),
+        description: "A public item dropped or changed a guarantee (ensures, invariant or refine) recorded by the last API-surface run, or gained a precondition its callers may not meet.".to_string(),
+        causes: vec![
+            "An `ensures` clause or field refinement was loosened or removed".to_string(),
+            "A `requires` clause was added or tightened".to_string(),
+        ],
+        solutions: vec![
+            "Restore the previous contract so dependent crates keep their assumptions".to_string(),
+            "Add the item path to [api_surface] acknowledge if the break is intended".to_string(),
+        ],
+        examples: vec![
+            "// before: #[contract(requires = amount > 0)]\n#[contract(requires = amount > 10)]\npub fn charge(amount: u32) -> u32".to_string(),
+        ],
+        related_codes: vec!["FP-VR-005".to_string()],
+    });
+
+    catalog.insert("FP-VR-005".to_string(), ErrorExplanation {
+        title: "Verified Public Item Removed".to_string(),
+        description: "A public item that carried #[verification], #[contract], #[derive(Verified)] or declared invariants in the last API-surface run was removed or lost its verification.".to_string(),
+        causes: vec![
+            "The item was deleted, renamed or made private".to_string(),
+            "Its verification attributes were removed".to_string(),
+        ],
+        solutions: vec![
+            "Keep the item, or re-export it under its previous path".to_string(),
+            "Add the item path to [api_surface] acknowledge if the removal is intended".to_string(),
+        ],
+        examples: vec![
+            "[api_surface]\nacknowledge = [\"billing::refund\"]".to_string(),
+        ],
+        related_codes: vec!["FP-VR-004".to_string()],
+    });
+
     // Tool Errors (FP-TL-xxx)
     catalog.insert(
         "FP-TL-001".to_string(),
@@ -193,6 +488,82 @@ fn create_error_catalog() -> HashMap<String, ErrorExplanation> {
         },
     );
 
+    catalog.insert(
+        "FP-TL-003".to_string(),
+        ErrorExplanation {
+            title: "Tool Execution Failed".to_string(),
+            description: "An external verification tool was found but failed while running."
+                .to_string(),
+            causes: vec![
+                "Tool exited with a non-zero status".to_string(),
+                "Tool output could not be understood".to_string(),
+            ],
+            solutions: vec![
+                "Run the command again with -vv to see the tool output".to_string(),
+                "Run the tool manually to check its installation".to_string(),
+            ],
+            examples: vec!["Tool error: rustc - Returned non-zero exit code".to_string()],
+            related_codes: vec!["FP-TL-001".to_string(), "FP-TL-002".to_string()],
+        },
+    );
+
+    // Metadata Policy Violations (FP-MD-xxx)
+    catalog.insert(
+        "FP-MD-001".to_string(),
+        ErrorExplanation {
+            title: "Missing rust-version".to_string(),
+            description: "The crate does not declare a minimum supported Rust version.".to_string(),
+            causes: vec![
+                "`rust-version` not set in [package]".to_string(),
+                "Workspace sets rust-version but the crate does not inherit it".to_string(),
+            ],
+            solutions: vec![
+                "Add `rust-version` to the crate's [package] section".to_string(),
+                "Use `rust-version.workspace = true` to inherit from the workspace".to_string(),
+                "Set `require_rust_version = false` under [metadata] to disable the rule"
+                    .to_string(),
+            ],
+            examples: vec!["[package]\nname = \"my-crate\"\nrust-version = \"1.75\"".to_string()],
+            related_codes: vec!["FP-MD-002".to_string(), "FP-MD-003".to_string()],
+        },
+    );
+
+    catalog.insert(
+        "FP-MD-002".to_string(),
+        ErrorExplanation {
+            title: "Wildcard Dependency Version".to_string(),
+            description: "A dependency uses `*` as its version requirement, accepting any release including breaking ones."
+                .to_string(),
+            causes: vec![
+                "Dependency declared as `name = \"*\"`".to_string(),
+                "Dependency table with `version = \"*\"`".to_string(),
+            ],
+            solutions: vec![
+                "Pin a semver-compatible requirement such as \"1.2\"".to_string(),
+                "Move the dependency to [workspace.dependencies] with a pinned version"
+                    .to_string(),
+            ],
+            examples: vec!["# Instead of:\nrand = \"*\"\n# use:\nrand = \"0.8\"".to_string()],
+            related_codes: vec!["FP-MD-001".to_string()],
+        },
+    );
+
+    catalog.insert(
+        "FP-MD-003".to_string(),
+        ErrorExplanation {
+            title: "Missing License".to_string(),
+            description: "The crate does not declare a license or license file.".to_string(),
+            causes: vec!["Neither `license` nor `license-file` set in [package]".to_string()],
+            solutions: vec![
+                "Add an SPDX expression with `license = \"MIT OR Apache-2.0\"`".to_string(),
+                "Point to a custom license with `license-file = \"LICENSE\"`".to_string(),
+                "Use `license.workspace = true` to inherit from the workspace".to_string(),
+            ],
+            examples: vec!["[package]\nlicense = \"MIT OR Apache-2.0\"".to_string()],
+            related_codes: vec!["FP-MD-001".to_string()],
+        },
+    );
+
     // I/O Errors (FP-IO-xxx)
     catalog.insert(
         "FP-IO-001".to_string(),
@@ -216,6 +587,147 @@ fn create_error_catalog() -> HashMap<String, ErrorExplanation> {
         },
     );
 
+    // Parse Errors (FP-PS-xxx)
+    catalog.insert(
+        "FP-PS-001".to_string(),
+        ErrorExplanation {
+            title: "Cannot Parse Input File".to_string(),
+            description: "A Rust source, specification or manifest file has invalid syntax."
+                .to_string(),
+            causes: vec![
+                "Syntax error in the file".to_string(),
+                "File is not in the expected format".to_string(),
+            ],
+            solutions: vec![
+                "Fix the syntax error at the reported location".to_string(),
+                "Run 'cargo check' for Rust sources".to_string(),
+            ],
+            examples: vec!["Parse error: expected `;`".to_string()],
+            related_codes: vec!["FP-IO-001".to_string()],
+        },
+    );
+
+    // Cache Errors (FP-CH-xxx)
+    catalog.insert(
+        "FP-CH-001".to_string(),
+        ErrorExplanation {
+            title: "Cache Unreadable".to_string(),
+            description: "The verification cache or its index could not be read or written."
+                .to_string(),
+            causes: vec![
+                "Cache files written by an incompatible version".to_string(),
+                "Interrupted write left a corrupt file".to_string(),
+            ],
+            solutions: vec![
+                "Run 'ferris-proof cache repair'".to_string(),
+                "Run 'ferris-proof cache clear' to start from an empty cache".to_string(),
+            ],
+            examples: vec!["Cache error: Invalid cache index: EOF while parsing".to_string()],
+            related_codes: vec!["FP-IO-001".to_string()],
+        },
+    );
+
+    catalog.insert(
+        "FP-CH-002".to_string(),
+        ErrorExplanation {
+            title: "Cache Locked".to_string(),
+            description: "The cache lock could not be taken before the retry timeout.".to_string(),
+            causes: vec![
+                "Another run is clearing or compacting the same cache".to_string(),
+                "A stalled process still holds the cache lock".to_string(),
+            ],
+            solutions: vec![
+                "Retry once the other run finishes".to_string(),
+                "Stop stalled ferris-proof processes using the cache".to_string(),
+            ],
+            examples: vec![
+                "Cache at .ferris-proof/cache is locked by another ferris-proof run".to_string(),
+            ],
+            related_codes: vec!["FP-CH-001".to_string()],
+        },
+    );
+
+    // Plugin Errors (FP-PL-xxx)
+    catalog.insert(
+        "FP-PL-001".to_string(),
+        ErrorExplanation {
+            title: "Plugin Failed".to_string(),
+            description: "A verification plugin could not be loaded, selected or run.".to_string(),
+            causes: vec![
+                "Invalid plugin manifest".to_string(),
+                "Plugin disabled or not registered".to_string(),
+                "Plugin timed out".to_string(),
+            ],
+            solutions: vec![
+                "Run 'ferris-proof plugins validate' on the manifest".to_string(),
+                "Check [plugins] in ferrisproof.toml".to_string(),
+            ],
+            examples: vec!["Plugin error: kani - disabled in [plugins] disabled".to_string()],
+            related_codes: vec!["FP-PL-002".to_string(), "FP-PL-004".to_string()],
+        },
+    );
+
+    catalog.insert(
+        "FP-PL-002".to_string(),
+        ErrorExplanation {
+            title: "No Plugin for Technique".to_string(),
+            description: "No enabled plugin supports the requested verification technique."
+                .to_string(),
+            causes: vec![
+                "Every supporting plugin is disabled".to_string(),
+                "The technique needs an external plugin that is not installed".to_string(),
+            ],
+            solutions: vec![
+                "Remove the plugin from [plugins] disabled".to_string(),
+                "Install a plugin supporting the technique".to_string(),
+            ],
+            examples: vec!["No plugin available for ModelChecking".to_string()],
+            related_codes: vec!["FP-PL-001".to_string()],
+        },
+    );
+
+    catalog.insert(
+        "FP-PL-003".to_string(),
+        ErrorExplanation {
+            title: "Plugins Disagree".to_string(),
+            description: "Plugins fanned out on one technique reached different verdicts."
+                .to_string(),
+            causes: vec![
+                "One tool explores a bounded scope that misses the failing case".to_string(),
+                "Plugins check different properties under the same technique".to_string(),
+            ],
+            solutions: vec![
+                "Check whether the passing plugin covers the failing case".to_string(),
+                "Raise the scope or depth of the passing tool under [tools]".to_string(),
+            ],
+            examples: vec![
+                "Plugins disagree on ModelChecking: alloy passed but tla-plus found violations"
+                    .to_string(),
+            ],
+            related_codes: vec!["FP-PL-001".to_string()],
+        },
+    );
+
+    catalog.insert(
+        "FP-PL-004".to_string(),
+        ErrorExplanation {
+            title: "Verification Cancelled".to_string(),
+            description: "A tool or plugin was stopped before it finished, so its results are \
+                          incomplete."
+                .to_string(),
+            causes: vec![
+                "The run was interrupted with Ctrl-C".to_string(),
+                "The run or daemon job was cancelled".to_string(),
+            ],
+            solutions: vec![
+                "Run the verification again".to_string(),
+                "Use the partial report to see which targets finished".to_string(),
+            ],
+            examples: vec!["Cancelled: tlc".to_string()],
+            related_codes: vec!["FP-PL-001".to_string()],
+        },
+    );
+
     catalog
 }
 
@@ -290,6 +802,12 @@ fn display_unknown_error(code: &str, catalog: &HashMap<String, ErrorExplanation>
     println!("  • {} - Tool errors", "FP-TL-xxx".green());
     println!("  • {} - I/O and file system errors", "FP-IO-xxx".green());
     println!("  • {} - Parse errors", "FP-PS-xxx".green());
+    println!("  • {} - Cache errors", "FP-CH-xxx".green());
+    println!("  • {} - Plugin errors", "FP-PL-xxx".green());
+    println!(
+        "  • {} - Errors registered by plugins",
+        "FP-PL-<plugin>-xxx".green()
+    );
     println!();
 
     println!("{}", "To see all available error codes:".bold());
@@ -321,3 +839,58 @@ fn find_similar_codes(input: &str, catalog: &HashMap<String, ErrorExplanation>)
     similar.truncate(5); // Limit to 5 suggestions
     similar
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferris_proof_core::FerrisProofError;
+
+    #[test]
+    fn test_live_violation_matches_fingerprint_or_id() {
+        let violation = Violation {
+            id: "FP-VR-001".to_string(),
+            severity: ferris_proof_core::types::Severity::Error,
+            location: Location {
+                file: "src/lib.rs".into(),
+                line: Some(4),
+                column: None,
+                span: None,
+            },
+            message: "property failed".to_string(),
+            suggestion: None,
+            rule: "property".to_string(),
+        };
+        let layer = LayerResult {
+            layer: ferris_proof_core::types::Layer::PropertyBased,
+            status: ferris_proof_core::types::Status::Error,
+            violations: vec![violation.clone()],
+            execution_time: std::time::Duration::ZERO,
+            tool_outputs: vec![ToolOutput {
+                tool: "proptest".to_string(),
+                stdout: (1..=20).map(|i| format!("line {}\n", i)).collect(),
+                stderr: String::new(),
+                exit_code: 101,
+                execution_time: std::time::Duration::ZERO,
+                artifact_id: None,
+                retries: 0,
+            }],
+        };
+
+        let live = LiveViolation::new(violation, Some(&layer));
+        assert!(live.matches("property|src/lib.rs|property failed"));
+        assert!(live.matches("FP-VR-001"));
+        assert!(!live.matches("FP-VR-002"));
+
+        let lines = excerpt(live.tool_output.as_ref().unwrap());
+        assert_eq!(lines.len(), EXCERPT_LINES);
+        assert_eq!(lines.last(), Some(&"line 20"));
+    }
+
+    #[test]
+    fn test_catalog_covers_error_codes() {
+        let catalog = create_error_catalog();
+        for code in FerrisProofError::CODES {
+            assert!(catalog.contains_key(*code), "{} missing from catalog", code);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use ferris_proof_config::ConfigManager;
+use ferris_proof_core::history::HistoryStore;
+
+pub async fn run() -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let gates = ConfigManager::from_project_root(&project_root)?.release_gates()?;
+    if gates.is_empty() {
+        println!("No release gates declared under [gates]");
+        return Ok(0);
+    }
+
+    let history = HistoryStore::for_project(&project_root).load()?;
+    let now = chrono::Utc::now();
+    let mut failed = 0;
+    for gate in &gates {
+        let outcome = gate.evaluate(&history, now);
+        if outcome.passed() {
+            println!("{} {} ({})", "✓".green(), outcome.gate, gate.modules);
+            continue;
+        }
+        failed += 1;
+        println!("{} {} ({})", "✗".red(), outcome.gate.bold(), gate.modules);
+        for failure in &outcome.failures {
+            println!("    {}", failure);
+        }
+    }
+
+    if failed > 0 {
+        println!("{} of {} gate(s) failed", failed, gates.len());
+        return Ok(1);
+    }
+    println!("All {} gate(s) passed", gates.len());
+    Ok(0)
+}
@@ -1,14 +1,404 @@
 use crate::GenerateTarget;
-use anyhow::Result;
-use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use ferris_proof_config::{ConfigManager, TlaModelConfig};
+use ferris_proof_core::types::Technique;
+use ferris_proof_plugins::doc_contracts::{self, DocContracts};
+use ferris_proof_plugins::serde_roundtrip::{RoundtripPlan, SerdeFormat};
+use ferris_proof_plugins::session_types::{self, Protocol};
+use ferris_proof_plugins::snapshot_tests::SnapshotPlan;
+use ferris_proof_plugins::tla_skeleton::{self, StateMachine};
+use ferris_proof_plugins::tlc_config::{self, TlcModel, TlcModelOverrides};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-pub async fn run(target: GenerateTarget, output_dir: Option<PathBuf>) -> Result<i32> {
+pub async fn run(
+    target: GenerateTarget,
+    output_dir: Option<PathBuf>,
+    spec: Option<PathBuf>,
+) -> Result<i32> {
     println!("Generating artifacts: {:?}", target);
 
-    if let Some(output_dir) = output_dir {
+    if let Some(ref output_dir) = output_dir {
         println!("Output directory: {:?}", output_dir);
     }
 
-    // TODO: Implement artifact generation
+    match target {
+        GenerateTarget::TlcConfig => generate_tlc_configs(output_dir.as_deref()),
+        GenerateTarget::SerdeRoundtrip => generate_serde_roundtrips(output_dir.as_deref()),
+        GenerateTarget::SnapshotTests => generate_snapshot_tests(output_dir.as_deref()),
+        GenerateTarget::SessionTypes => {
+            let Some(spec) = spec else {
+                bail!("--target session-types needs --spec <FILE> with a protocol definition");
+            };
+            generate_session_types(&spec, output_dir.as_deref())
+        }
+        GenerateTarget::FormalSpecs => generate_formal_specs(output_dir.as_deref()),
+        GenerateTarget::DocContracts => generate_doc_contracts(output_dir.as_deref()),
+        _ => {
+            // TODO: Implement artifact generation
+            Ok(0)
+        }
+    }
+}
+
+/// Generate TLC `.cfg` files for configured models and annotated specs
+fn generate_tlc_configs(output_dir: Option<&Path>) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let config_manager = ConfigManager::from_project_root(&project_root)
+        .context("Failed to load configuration manager")?;
+
+    let models = config_manager
+        .root_config()
+        .tools
+        .tla_plus
+        .as_ref()
+        .map(|tla| tla.models.clone())
+        .unwrap_or_default();
+
+    let mut covered_specs = HashSet::new();
+    let mut generated = 0;
+
+    // Named models from [tools.tla_plus.models]
+    let mut model_names: Vec<&String> = models.keys().collect();
+    model_names.sort();
+    for name in model_names {
+        let model_config = &models[name];
+        let spec_path = model_config.spec.as_ref().map(|p| project_root.join(p));
+
+        let base_model = match &spec_path {
+            Some(path) => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read spec for model '{}'", name))?;
+                covered_specs.insert(path.clone());
+                TlcModel::from_spec(&content)
+            }
+            None => TlcModel::default(),
+        };
+        let model = base_model.with_overrides(&to_overrides(model_config));
+
+        let cfg_dir = output_dir
+            .map(Path::to_path_buf)
+            .or_else(|| {
+                spec_path
+                    .as_ref()
+                    .and_then(|p| p.parent().map(Path::to_path_buf))
+            })
+            .unwrap_or_else(|| project_root.clone());
+        let cfg_path = cfg_dir.join(format!("{}.cfg", name));
+
+        generated += write_and_report(&cfg_path, &model.render())?;
+    }
+
+    // Default configs for specs not covered by a named model
+    for spec_path in find_tla_specs(&project_root) {
+        if covered_specs.contains(&spec_path) {
+            continue;
+        }
+
+        let content = std::fs::read_to_string(&spec_path)
+            .with_context(|| format!("Failed to read spec {:?}", spec_path))?;
+        let model = TlcModel::from_spec(&content);
+
+        let cfg_path = match output_dir {
+            Some(dir) => dir.join(tlc_config::default_cfg_path(Path::new(
+                spec_path.file_name().unwrap_or_default(),
+            ))),
+            None => tlc_config::default_cfg_path(&spec_path),
+        };
+
+        generated += write_and_report(&cfg_path, &model.render())?;
+    }
+
+    if generated == 0 {
+        println!("{}", "No TLC configuration files generated.".yellow());
+    } else {
+        println!("✓ Generated {} TLC configuration file(s)", generated);
+    }
+
+    Ok(0)
+}
+
+/// Generate round-trip properties for serde types in modules with property tests
+///
+/// `output_dir` selects the package to scan and defaults to the current directory.
+fn generate_serde_roundtrips(output_dir: Option<&Path>) -> Result<i32> {
+    let package_root = match output_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => std::env::current_dir().context("Failed to get current directory")?,
+    };
+    let config_manager = ConfigManager::from_project_root(&package_root).ok();
+    let strategies = config_manager
+        .as_ref()
+        .map(|manager| manager.root_config().generate.proptest.strategies.clone())
+        .unwrap_or_default();
+
+    let plan = RoundtripPlan::build(&package_root, &SerdeFormat::ALL, &strategies, |file| {
+        config_manager.as_ref().is_none_or(|manager| {
+            manager
+                .for_file(file)
+                .enabled_techniques
+                .contains(&Technique::PropertyTests)
+        })
+    })?;
+
+    for gap in plan.coverage_gaps() {
+        println!(
+            "{} {}:{} {}",
+            "•".yellow(),
+            gap.location.file.display(),
+            gap.location.line.unwrap_or(1),
+            gap.message
+        );
+    }
+
+    let properties = plan.covered_types().count() * plan.formats.len();
+    if properties == 0 {
+        println!("{}", "No round-trip properties generated.".yellow());
+        return Ok(0);
+    }
+
+    let test_path = RoundtripPlan::test_path(&package_root);
+    if plan.write(&package_root)? {
+        println!(
+            "✓ Generated {} round-trip propert{} in {}",
+            properties,
+            if properties == 1 { "y" } else { "ies" },
+            test_path.display().to_string().green()
+        );
+    } else {
+        println!(
+            "• Skipped {} (hand-written tests)",
+            test_path.display().to_string().yellow()
+        );
+    }
+
+    Ok(0)
+}
+
+/// Generate insta snapshot tests for functions returning serde types
+///
+/// `output_dir` selects the package to scan and defaults to the current directory.
+fn generate_snapshot_tests(output_dir: Option<&Path>) -> Result<i32> {
+    let package_root = match output_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => std::env::current_dir().context("Failed to get current directory")?,
+    };
+    let snapshots = ConfigManager::from_project_root(&package_root)
+        .map(|manager| manager.root_config().generate.snapshots.clone())
+        .unwrap_or_default();
+
+    let plan = SnapshotPlan::build(&package_root, &snapshots.modules, &snapshots.inputs)?;
+    for gap in plan.coverage_gaps() {
+        println!(
+            "{} {}:{} {}",
+            "•".yellow(),
+            gap.location.file.display(),
+            gap.location.line.unwrap_or(1),
+            gap.message
+        );
+    }
+
+    let tests = plan.covered_functions().count();
+    if tests == 0 || !plan.has_insta {
+        println!("{}", "No snapshot tests generated.".yellow());
+        return Ok(0);
+    }
+
+    let test_path = SnapshotPlan::test_path(&package_root);
+    if plan.write(&package_root)? {
+        println!(
+            "✓ Generated {} snapshot test(s) in {}; record them with `cargo insta test --accept`",
+            tests,
+            test_path.display().to_string().green()
+        );
+    } else {
+        println!(
+            "• Skipped {} (hand-written tests)",
+            test_path.display().to_string().yellow()
+        );
+    }
+
     Ok(0)
 }
+
+/// Extract doc-comment contracts, report unchecked ones and write test skeletons
+///
+/// Stubs go to `.ferris-proof/` and skeletons to `tests/`, or both to `output_dir`.
+fn generate_doc_contracts(output_dir: Option<&Path>) -> Result<i32> {
+    let package_root = std::env::current_dir().context("Failed to get current directory")?;
+    let contracts = DocContracts::build(&package_root)?;
+
+    for gap in contracts.gaps() {
+        println!(
+            "{} {}:{} {}",
+            "•".yellow(),
+            gap.location.file.display(),
+            gap.location.line.unwrap_or(1),
+            gap.message
+        );
+    }
+
+    if contracts.contracts.is_empty() {
+        println!(
+            "{}",
+            "No doc contracts found. Document functions with # Panics, # Safety, # Errors or # Invariants sections."
+                .yellow()
+        );
+        return Ok(0);
+    }
+
+    let stubs_path = output_dir
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| package_root.join(".ferris-proof"))
+        .join(doc_contracts::STUBS_FILE);
+    if let Some(parent) = stubs_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&stubs_path, contracts.render_stubs()?)
+        .with_context(|| format!("Failed to write {:?}", stubs_path))?;
+    println!(
+        "✓ Extracted {} contract(s) to {}",
+        contracts.contracts.len(),
+        stubs_path.display().to_string().green()
+    );
+
+    let skeletons = contracts.skeletons();
+    if skeletons == 0 {
+        return Ok(0);
+    }
+    let test_path = output_dir
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| package_root.join("tests"))
+        .join(format!("{}.rs", doc_contracts::TEST_TARGET));
+    if contracts.write_tests(&test_path)? {
+        println!(
+            "✓ Generated {} property-test skeleton(s) in {}",
+            skeletons,
+            test_path.display().to_string().green()
+        );
+    } else {
+        println!(
+            "• Skipped {} (hand-written tests)",
+            test_path.display().to_string().yellow()
+        );
+    }
+
+    Ok(0)
+}
+
+/// Generate typestate code and a graphviz diagram from a protocol definition
+fn generate_session_types(spec: &Path, output_dir: Option<&Path>) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let protocol = Protocol::load(spec)?;
+    let (code_path, diagram_path) =
+        session_types::output_paths(&protocol, spec, &project_root, output_dir);
+
+    for (path, content) in [
+        (&code_path, protocol.render_rust()),
+        (&diagram_path, protocol.render_dot()),
+    ] {
+        if session_types::write_generated(path, &content)? {
+            println!("✓ Created {}", path.display().to_string().green());
+        } else {
+            println!(
+                "• Skipped {} (hand-written file)",
+                path.display().to_string().yellow()
+            );
+        }
+    }
+
+    println!(
+        "✓ Protocol '{}': {} state(s), {} transition(s)",
+        protocol.name,
+        protocol.states.len(),
+        protocol.transitions.len()
+    );
+    Ok(0)
+}
+
+/// Generate skeleton TLA+ modules for the state machines found in `src/`
+fn generate_formal_specs(output_dir: Option<&Path>) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let machines = StateMachine::discover(&project_root)?;
+    if machines.is_empty() {
+        println!(
+            "{}",
+            "No state machines found. Annotate an enum with #[verification(state_machine)]."
+                .yellow()
+        );
+        return Ok(0);
+    }
+
+    for machine in &machines {
+        let path = machine.spec_path(&project_root, output_dir);
+        if tla_skeleton::write_generated(&path, &machine.render_tla())? {
+            println!(
+                "✓ Created {} from {} ({} state(s), {} transition(s))",
+                path.display().to_string().green(),
+                machine.name,
+                machine.states.len(),
+                machine.transitions.len()
+            );
+        } else {
+            println!(
+                "• Skipped {} (hand-written spec)",
+                path.display().to_string().yellow()
+            );
+        }
+    }
+
+    Ok(0)
+}
+
+fn write_and_report(cfg_path: &Path, content: &str) -> Result<usize> {
+    if tlc_config::write_cfg(cfg_path, content)? {
+        println!("✓ Created {}", cfg_path.display().to_string().green());
+        Ok(1)
+    } else {
+        println!(
+            "• Skipped {} (hand-written configuration)",
+            cfg_path.display().to_string().yellow()
+        );
+        Ok(0)
+    }
+}
+
+fn to_overrides(model: &TlaModelConfig) -> TlcModelOverrides {
+    TlcModelOverrides {
+        specification: model.specification.clone(),
+        init: model.init.clone(),
+        next: model.next.clone(),
+        constants: model.constants.clone(),
+        invariants: model.invariants.clone(),
+        properties: model.properties.clone(),
+    }
+}
+
+/// Find all `.tla` files below `root`, skipping hidden directories and target/
+fn find_tla_specs(root: &Path) -> Vec<PathBuf> {
+    fn visit(dir: &Path, specs: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            if path.is_dir() {
+                if !name.starts_with('.') && name != "target" {
+                    visit(&path, specs);
+                }
+            } else if path.extension().and_then(|s| s.to_str()) == Some("tla") {
+                specs.push(path);
+            }
+        }
+    }
+
+    let mut specs = Vec::new();
+    visit(root, &mut specs);
+    specs.sort();
+    specs
+}
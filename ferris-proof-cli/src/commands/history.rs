@@ -0,0 +1,109 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use ferris_proof_core::history::{HistoryStore, HistoryTrend, RunRecord, TimingTrend};
+use ferris_proof_core::Status;
+use serde_json::json;
+
+pub async fn run(last: usize, threshold: f64, fail_on_regression: bool, json: bool) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let store = HistoryStore::for_project(&project_root);
+    let records = store.last(last.max(1))?;
+    let trend = HistoryTrend::from_records(&records, threshold / 100.0);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({ "runs": records, "trend": trend }))?
+        );
+    } else if records.is_empty() {
+        println!("{}", "No verification history recorded yet".yellow());
+        println!(
+            "  Runs of {} are recorded in {}",
+            "ferris-proof check".cyan(),
+            store.path().display()
+        );
+    } else {
+        print_runs(&records);
+        if let Some(trend) = &trend {
+            print_trend(trend, threshold);
+        }
+    }
+
+    let regressed = trend.as_ref().is_some_and(HistoryTrend::has_regression);
+    Ok(if regressed && fail_on_regression {
+        1
+    } else {
+        0
+    })
+}
+
+fn print_runs(records: &[RunRecord]) {
+    println!(
+        "{:<17} {:<8} {:<8} {:>9} {:>10} {:>6}",
+        "Date", "Commit", "Status", "Time", "Violations", "Cache"
+    );
+    for record in records {
+        let status = match record.overall_status {
+            Status::Error => "error".red(),
+            Status::Warning => "warning".yellow(),
+            Status::Success => "ok".green(),
+            Status::Skipped => "skipped".dimmed(),
+            Status::Cached => "cached".green(),
+        };
+        println!(
+            "{:<17} {:<8} {:<8} {:>8.2}s {:>10} {:>5.0}%",
+            record.timestamp.format("%Y-%m-%d %H:%M"),
+            record
+                .git_commit
+                .as_deref()
+                .map(|c| &c[..c.len().min(7)])
+                .unwrap_or("-"),
+            status,
+            record.total_time.as_secs_f64(),
+            record.violations.len(),
+            record.cache_hit_rate * 100.0
+        );
+    }
+}
+
+fn print_trend(trend: &HistoryTrend, threshold: f64) {
+    println!("\nTrend over {} run(s):", trend.runs);
+    if let Some(total) = &trend.total {
+        println!("  {:<14} {}", "total", describe(total));
+    }
+    for layer in &trend.layers {
+        println!(
+            "  {:<14} {}",
+            format!("{:?}", layer.layer),
+            describe(&layer.timing)
+        );
+    }
+    if trend.violation_delta != 0 {
+        println!(
+            "  {:<14} {:+} since the previous run",
+            "violations", trend.violation_delta
+        );
+    }
+
+    if trend.has_regression() {
+        println!(
+            "\n{} Verification time regressed by more than {}% against the median",
+            "✗".red(),
+            threshold
+        );
+    }
+}
+
+fn describe(timing: &TimingTrend) -> String {
+    let summary = format!(
+        "{:.2}s vs median {:.2}s ({:+.0}%)",
+        timing.latest.as_secs_f64(),
+        timing.median.as_secs_f64(),
+        timing.change * 100.0
+    );
+    if timing.regression {
+        summary.red().to_string()
+    } else {
+        summary
+    }
+}
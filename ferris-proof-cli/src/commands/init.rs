@@ -1,3 +1,5 @@
+use crate::analysis::ProjectAnalysis;
+use crate::commands::config::{ConfigEditor, EditOutcome};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use console::Term;
@@ -11,6 +13,7 @@ pub async fn run(
     level: VerificationLevel,
     interactive: bool,
     template: Option<String>,
+    force: bool,
 ) -> Result<i32> {
     let term = Term::stdout();
 
@@ -21,12 +24,51 @@ pub async fn run(
         let level = prompt_verification_level(&term)?;
         let template = prompt_template(&term)?;
 
-        initialize_project(level, template.as_deref(), &term).await
+        initialize_project(
+            create_config_for_level(level),
+            template.as_deref(),
+            force,
+            &term,
+        )
+        .await
     } else {
-        initialize_project(level, template.as_deref(), &Term::stdout()).await
+        initialize_project(
+            create_config_for_level(level),
+            template.as_deref(),
+            force,
+            &Term::stdout(),
+        )
+        .await
     }
 }
 
+/// Initialize with a config tailored to the crate in the current directory
+///
+/// `level` overrides the recommended project level; module overrides for
+/// unsafe and concurrent code are kept either way.
+pub async fn run_analyzed(
+    level: Option<VerificationLevel>,
+    template: Option<String>,
+    force: bool,
+) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let analysis = ProjectAnalysis::analyze(&project_root);
+    let recommended = analysis.recommended_level();
+
+    println!(
+        "Analyzed {} module(s); recommended level: {}",
+        analysis.modules.len(),
+        format!("{:?}", recommended).green()
+    );
+    for line in analysis.recommendations() {
+        println!("  • {}", line);
+    }
+    println!();
+
+    let config = analysis.tailored_config(level.unwrap_or(recommended));
+    initialize_project(config, template.as_deref(), force, &Term::stdout()).await
+}
+
 fn prompt_verification_level(term: &Term) -> Result<VerificationLevel> {
     println!("Select verification level:");
     println!("  1. {} - Type safety only", "Minimal".yellow());
@@ -92,30 +134,32 @@ fn prompt_template(term: &Term) -> Result<Option<String>> {
 }
 
 async fn initialize_project(
-    level: VerificationLevel,
+    config: Config,
     _template: Option<&str>,
+    force: bool,
     _term: &Term,
 ) -> Result<i32> {
+    let level = config.profile.level;
     println!(
         "Initializing FerrisProof project with level: {}",
         format!("{:?}", level).green()
     );
 
-    // Check if ferrisproof.toml already exists
-    if Path::new("ferrisproof.toml").exists() {
+    // Merge into an existing ferrisproof.toml unless asked to overwrite it
+    if !Path::new("ferrisproof.toml").exists() {
+        write_config_file(&config)?;
+        println!("✓ Created {}", "ferrisproof.toml".green());
+    } else if force {
         println!(
             "{}",
             "Warning: ferrisproof.toml already exists. Overwriting...".yellow()
         );
+        write_config_file(&config)?;
+        println!("✓ Created {}", "ferrisproof.toml".green());
+    } else {
+        merge_config_file(&config)?;
     }
 
-    // Create configuration based on verification level
-    let config = create_config_for_level(level);
-
-    // Write configuration file
-    write_config_file(&config)?;
-    println!("✓ Created {}", "ferrisproof.toml".green());
-
     // Create directory structure
     create_directory_structure(level, _template).await?;
 
@@ -168,7 +212,19 @@ async fn initialize_project(
 }
 
 fn create_config_for_level(level: VerificationLevel) -> Config {
-    let techniques = match level {
+    Config {
+        profile: ProfileConfig {
+            level,
+            enforcement: EnforcementMode::Warning,
+            enabled_techniques: techniques_for_level(level),
+        },
+        ..Default::default()
+    }
+}
+
+/// Techniques enabled by default at a verification level
+pub(crate) fn techniques_for_level(level: VerificationLevel) -> Vec<Technique> {
+    match level {
         VerificationLevel::Minimal => vec![Technique::TypeSafety],
         VerificationLevel::Standard => vec![Technique::TypeSafety, Technique::PropertyTests],
         VerificationLevel::Strict => vec![
@@ -187,30 +243,75 @@ fn create_config_for_level(level: VerificationLevel) -> Config {
             Technique::FormalSpecs,
             Technique::ModelChecking,
         ],
-    };
-
-    Config {
-        profile: ProfileConfig {
-            level,
-            enforcement: EnforcementMode::Warning,
-            enabled_techniques: techniques,
-        },
-        ..Default::default()
     }
 }
 
 fn write_config_file(config: &Config) -> Result<()> {
+    let content = render_config(config)?;
+
+    fs::write("ferrisproof.toml", content).context("Failed to write ferrisproof.toml")?;
+
+    Ok(())
+}
+
+/// Add the sections of `config` missing from the existing ferrisproof.toml,
+/// keeping its comments and every value the user already set
+fn merge_config_file(config: &Config) -> Result<()> {
+    let existing =
+        fs::read_to_string("ferrisproof.toml").context("Failed to read ferrisproof.toml")?;
+    let mut editor = ConfigEditor::parse(&existing)?;
+    let (outcome, added) = editor.add_missing(&render_config(config)?)?;
+
+    match outcome {
+        EditOutcome::Unchanged => {
+            println!("✓ {} is already complete", "ferrisproof.toml".green());
+        }
+        EditOutcome::Applied { .. } => {
+            fs::write("ferrisproof.toml", editor.to_string())
+                .context("Failed to write ferrisproof.toml")?;
+            println!(
+                "✓ Merged into existing {}: added {}",
+                "ferrisproof.toml".green(),
+                added.join(", ")
+            );
+        }
+        EditOutcome::Rejected { errors } => {
+            anyhow::bail!(
+                "Cannot merge into ferrisproof.toml ({}); rerun with --force to overwrite it",
+                errors.join("; ")
+            );
+        }
+    }
+
+    let existing_level = editor.config()?.profile.level;
+    if existing_level < config.profile.level {
+        println!(
+            "{}",
+            format!(
+                "Note: kept level {:?} from ferrisproof.toml; run `ferris-proof upgrade --to {}` to raise it",
+                existing_level,
+                format!("{:?}", config.profile.level).to_lowercase()
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+fn render_config(config: &Config) -> Result<String> {
     let toml_content =
         toml::to_string_pretty(config).context("Failed to serialize configuration to TOML")?;
 
-    let content = format!(
+    Ok(format!(
         "# FerrisProof Configuration\n# Generated by ferris-proof init\n\n{}\n",
         toml_content
-    );
-
-    fs::write("ferrisproof.toml", content).context("Failed to write ferrisproof.toml")?;
+    ))
+}
 
-    Ok(())
+/// Default `ferrisproof.toml` written by `init` for a verification level
+pub(crate) fn preset_toml(level: VerificationLevel) -> Result<String> {
+    render_config(&create_config_for_level(level))
 }
 
 async fn create_directory_structure(
@@ -346,34 +447,9 @@ See `ferrisproof.toml` for current configuration.
     println!("✓ Created {}", "README.md".green());
 
     // Create example property test
-    let property_test_content = r#"use proptest::prelude::*;
-
-// Example property test
-proptest! {
-    #[test]
-    /// **Feature: example, Property 1: Addition is commutative**
-    fn addition_is_commutative(a in any::<i32>(), b in any::<i32>()) {
-        // Avoid overflow by using saturating arithmetic
-        let result1 = a.saturating_add(b);
-        let result2 = b.saturating_add(a);
-        prop_assert_eq!(result1, result2);
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    
-    #[test]
-    fn basic_addition_test() {
-        assert_eq!(2 + 2, 4);
-    }
-}
-"#;
-
     fs::write(
         "tests/property/example_properties.rs",
-        property_test_content,
+        PROPERTY_TEST_TEMPLATE,
     )?;
     println!(
         "✓ Created {}",
@@ -387,29 +463,9 @@ async fn create_strict_template() -> Result<()> {
     create_standard_template().await?;
 
     // Create session type example
-    let session_type_content = r#"// Example session type definition
-// This would be expanded by FerrisProof macros
-
-use ferris_proof_macros::session_type;
-
-#[session_type]
-pub enum ProtocolState {
-    Init,
-    Connected { peer_id: String },
-    Authenticated { user_id: u64 },
-    Closed,
-}
-
-// Example usage:
-// let protocol = ProtocolState::Init;
-// let protocol = protocol.connect("peer123")?;
-// let protocol = protocol.authenticate(42)?;
-// protocol.close();
-"#;
-
     fs::write(
         "specs/session-types/example_protocol.rs",
-        session_type_content,
+        SESSION_TYPE_TEMPLATE,
     )?;
     println!(
         "✓ Created {}",
@@ -423,109 +479,16 @@ async fn create_formal_template() -> Result<()> {
     create_strict_template().await?;
 
     // Create TLA+ specification example
-    let tla_spec_content = r#"---- MODULE ExampleProtocol ----
-EXTENDS Naturals, Sequences, TLC
-
-CONSTANTS Nodes, MaxMessages
-
-VARIABLES 
-    messages,    \* Messages in transit
-    nodeState    \* State of each node
-
-TypeOK == 
-    /\ messages \in Seq(Nat)
-    /\ nodeState \in [Nodes -> {"init", "ready", "done"}]
-
-Init ==
-    /\ messages = <<>>
-    /\ nodeState = [n \in Nodes |-> "init"]
-
-SendMessage(sender, receiver) ==
-    /\ nodeState[sender] = "ready"
-    /\ messages' = Append(messages, receiver)
-    /\ UNCHANGED nodeState
-
-ReceiveMessage(receiver) ==
-    /\ Len(messages) > 0
-    /\ Head(messages) = receiver
-    /\ nodeState[receiver] = "ready"
-    /\ nodeState' = [nodeState EXCEPT ![receiver] = "done"]
-    /\ messages' = Tail(messages)
-
-Next ==
-    \/ \E sender, receiver \in Nodes : SendMessage(sender, receiver)
-    \/ \E receiver \in Nodes : ReceiveMessage(receiver)
-
-Spec == Init /\ [][Next]_<<messages, nodeState>>
-
-\* Safety property: No message is lost
-NoMessageLoss == Len(messages) <= MaxMessages
-
-\* Liveness property: All nodes eventually reach done state
-AllNodesComplete == <>(\A n \in Nodes : nodeState[n] = "done")
-
-====
-"#;
-
-    fs::write("specs/formal/tla/example_protocol.tla", tla_spec_content)?;
+    fs::write("specs/formal/tla/example_protocol.tla", TLA_SPEC_TEMPLATE)?;
     println!(
         "✓ Created {}",
         "specs/formal/tla/example_protocol.tla".green()
     );
 
     // Create Alloy specification example
-    let alloy_spec_content = r#"// Example Alloy specification
-module ExampleProtocol
-
-// Basic signatures
-sig Node {
-    state: one State
-}
-
-abstract sig State {}
-one sig Init, Ready, Done extends State {}
-
-sig Message {
-    sender: one Node,
-    receiver: one Node
-}
-
-// Predicates
-pred validTransition[n: Node, s, s': State] {
-    (s = Init and s' = Ready) or
-    (s = Ready and s' = Done) or
-    (s = s')  // No change
-}
-
-pred sendMessage[m: Message] {
-    m.sender.state = Ready
-}
-
-pred receiveMessage[m: Message] {
-    m.receiver.state = Ready
-    // After receiving, node transitions to Done
-}
-
-// Facts
-fact NoSelfMessages {
-    no m: Message | m.sender = m.receiver
-}
-
-fact StateTransitions {
-    all n: Node | validTransition[n, Init, n.state]
-}
-
-// Assertions
-assert NoOrphanMessages {
-    all m: Message | some n: Node | n = m.sender or n = m.receiver
-}
-
-check NoOrphanMessages for 5
-"#;
-
     fs::write(
         "specs/formal/alloy/example_protocol.als",
-        alloy_spec_content,
+        ALLOY_SPEC_TEMPLATE,
     )?;
     println!(
         "✓ Created {}",
@@ -534,3 +497,19 @@ check NoOrphanMessages for 5
 
     Ok(())
 }
+
+/// Example property test scaffold
+pub(crate) const PROPERTY_TEST_TEMPLATE: &str =
+    include_str!("../../assets/templates/example_properties.rs");
+
+/// Example session type scaffold
+pub(crate) const SESSION_TYPE_TEMPLATE: &str =
+    include_str!("../../assets/templates/example_protocol_session.rs");
+
+/// Example TLA+ specification scaffold
+pub(crate) const TLA_SPEC_TEMPLATE: &str =
+    include_str!("../../assets/templates/example_protocol.tla");
+
+/// Example Alloy specification scaffold
+pub(crate) const ALLOY_SPEC_TEMPLATE: &str =
+    include_str!("../../assets/templates/example_protocol.als");
@@ -0,0 +1,529 @@
+use crate::commands::cache::cache_dir_for;
+use crate::commands::check;
+use crate::fixes::{quick_fix, Fix};
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use ferris_proof_config::ConfigManager;
+use ferris_proof_core::baseline::{AuditAction, AuditLog, Baseline};
+use ferris_proof_core::cache::{CacheFilter, VerificationCache};
+use ferris_proof_core::history::ViolationRecord;
+use ferris_proof_core::plugins::CancellationToken;
+use ferris_proof_core::timings::Timings;
+use ferris_proof_core::types::{Severity, Violation};
+use ferris_proof_core::verification::Target;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tower_lsp::jsonrpc::{Error as RpcError, Result as RpcResult};
+use tower_lsp::lsp_types as lsp;
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+use tracing::{debug, warn};
+
+/// Custom request returning the effective config for a document
+pub const EFFECTIVE_CONFIG_METHOD: &str = "ferrisProof/effectiveConfig";
+
+const BASELINE_COMMAND: &str = "ferrisProof.baseline";
+const SNOOZE_COMMAND: &str = "ferrisProof.snooze";
+const SNOOZE_DAYS: i64 = 7;
+const DIAGNOSTIC_SOURCE: &str = "ferris-proof";
+const CONFIG_FILE: &str = "ferrisproof.toml";
+
+/// Serve LSP over stdin/stdout until the client disconnects
+pub async fn run() -> Result<i32> {
+    let (service, socket) = LspService::build(Backend::new)
+        .custom_method(EFFECTIVE_CONFIG_METHOD, Backend::effective_config)
+        .finish();
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
+        .serve(service)
+        .await;
+    Ok(0)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveConfigParams {
+    pub text_document: lsp::TextDocumentIdentifier,
+}
+
+pub struct Backend {
+    client: Client,
+    root: OnceLock<PathBuf>,
+    /// Violations last published per document, for code actions
+    published: Mutex<HashMap<lsp::Url, Vec<Violation>>>,
+    /// Loaded lazily and reloaded when a ferrisproof.toml changes
+    config: Mutex<Option<ConfigManager>>,
+}
+
+impl Backend {
+    fn new(client: Client) -> Self {
+        Self {
+            client,
+            root: OnceLock::new(),
+            published: Mutex::new(HashMap::new()),
+            config: Mutex::new(None),
+        }
+    }
+
+    fn project_root(&self) -> PathBuf {
+        self.root
+            .get()
+            .cloned()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default()
+    }
+
+    async fn effective_config(&self, params: EffectiveConfigParams) -> RpcResult<Value> {
+        let path = file_path(&params.text_document.uri)?;
+        let mut guard = self.config.lock().unwrap();
+        if guard.is_none() {
+            let manager = ConfigManager::from_project_root(&self.project_root())
+                .map_err(|e| internal_error(e.to_string()))?;
+            *guard = Some(manager);
+        }
+        let config = guard.as_ref().unwrap().for_file(&path);
+
+        Ok(json!({
+            "level": config.level,
+            "enforcement": config.enforcement,
+            "enabledTechniques": config.enabled_techniques,
+        }))
+    }
+
+    /// Verify a saved document and publish diagnostics for every affected file
+    async fn verify_document(&self, uri: &lsp::Url) {
+        let Ok(path) = file_path(uri) else {
+            return;
+        };
+        let targets = match path.extension().and_then(|e| e.to_str()) {
+            Some("rs") => vec![Target::RustFile(path.clone())],
+            Some("tla") | Some("als") => vec![Target::FormalSpec(path.clone())],
+            // Cargo.toml metadata rules run on every verification
+            Some("toml") => Vec::new(),
+            _ => return,
+        };
+
+        let verified = check::verify_project(
+            &self.project_root(),
+            &targets,
+            None,
+            false,
+            None,
+            None,
+            &Timings::default(),
+            CancellationToken::new(),
+        )
+        .await;
+        let result = match verified {
+            Ok((result, _)) => result,
+            Err(e) => {
+                self.client
+                    .log_message(
+                        lsp::MessageType::ERROR,
+                        format!("Verification failed: {}", e),
+                    )
+                    .await;
+                return;
+            }
+        };
+
+        let root = self.project_root();
+        let mut by_file: HashMap<lsp::Url, Vec<Violation>> = HashMap::new();
+        by_file.entry(uri.clone()).or_default();
+        for violation in result
+            .layer_results
+            .into_values()
+            .flat_map(|l| l.violations)
+        {
+            let file = root.join(&violation.location.file);
+            match lsp::Url::from_file_path(&file) {
+                Ok(url) => by_file.entry(url).or_default().push(violation),
+                Err(()) => warn!("Cannot publish diagnostics for {:?}", file),
+            }
+        }
+
+        for (url, violations) in by_file {
+            self.publish(url, violations).await;
+        }
+    }
+
+    /// Reload changed config files and re-verify documents whose config moved
+    async fn reload_config(&self) {
+        let documents: Vec<lsp::Url> = self.published.lock().unwrap().keys().cloned().collect();
+        let files: Vec<PathBuf> = documents
+            .iter()
+            .filter_map(|uri| uri.to_file_path().ok())
+            .collect();
+
+        let reload = match self.config.lock().unwrap().as_mut() {
+            Some(manager) => manager.reload_if_changed(&files),
+            // Nothing resolved yet, so the next request loads fresh config
+            None => return,
+        };
+        let reload = match reload {
+            Ok(Some(reload)) => reload,
+            Ok(None) => return,
+            Err(e) => {
+                self.client
+                    .log_message(
+                        lsp::MessageType::ERROR,
+                        format!("Keeping previous configuration: {}", e),
+                    )
+                    .await;
+                return;
+            }
+        };
+        debug!("Reloaded configuration after {:?}", reload.changed_files);
+
+        // Clear and persist the cache verification reads from
+        match cache_dir_for(&self.project_root()) {
+            Ok(cache_dir) => {
+                let mut cache = VerificationCache::with_cache_dir(cache_dir);
+                let cleared = cache.load_from_disk_async().await.and_then(|()| {
+                    for hash in &reload.stale_config_hashes {
+                        cache.clear_matching(&CacheFilter {
+                            config_hash: Some(hash.clone()),
+                            ..CacheFilter::default()
+                        });
+                    }
+                    cache.save_to_disk()
+                });
+                if let Err(e) = cleared {
+                    warn!("Skipping cache invalidation: {}", e);
+                }
+            }
+            Err(e) => warn!("Skipping cache invalidation: {}", e),
+        }
+
+        for file in &reload.affected_files {
+            if let Ok(uri) = lsp::Url::from_file_path(file) {
+                self.verify_document(&uri).await;
+            }
+        }
+    }
+
+    async fn publish(&self, uri: lsp::Url, violations: Vec<Violation>) {
+        let diagnostics = violations.iter().map(to_diagnostic).collect();
+        self.published
+            .lock()
+            .unwrap()
+            .insert(uri.clone(), violations);
+        self.client
+            .publish_diagnostics(uri, diagnostics, None)
+            .await;
+    }
+
+    /// Record a baseline or snooze decision and clear the diagnostic
+    async fn suppress(&self, command: &str, violation: Violation) -> Result<()> {
+        let root = self.project_root();
+        let baseline_path = Baseline::path_for_project(&root);
+        let mut baseline = Baseline::load(&baseline_path)?;
+        let record = ViolationRecord::from_violation(&violation);
+
+        if command == SNOOZE_COMMAND {
+            let until = Utc::now() + Duration::days(SNOOZE_DAYS);
+            baseline.snooze(&record, until);
+            baseline.save(&baseline_path)?;
+            AuditLog::for_project(&root).record(
+                AuditAction::Snoozed,
+                &record,
+                Some(format!("until {}", until.format("%Y-%m-%d"))),
+            )?;
+        } else {
+            baseline.add(&record, Some("suppressed from editor".to_string()));
+            baseline.save(&baseline_path)?;
+            AuditLog::for_project(&root).record(AuditAction::Baselined, &record, None)?;
+        }
+
+        if let Ok(uri) = lsp::Url::from_file_path(root.join(&violation.location.file)) {
+            let remaining: Vec<Violation> = self
+                .published
+                .lock()
+                .unwrap()
+                .get(&uri)
+                .map(|published| {
+                    published
+                        .iter()
+                        .filter(|v| ViolationRecord::from_violation(v) != record)
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default();
+            self.publish(uri, remaining).await;
+        }
+        Ok(())
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, params: lsp::InitializeParams) -> RpcResult<lsp::InitializeResult> {
+        #[allow(deprecated)]
+        let root = params
+            .workspace_folders
+            .as_ref()
+            .and_then(|folders| folders.first())
+            .map(|folder| folder.uri.clone())
+            .or(params.root_uri)
+            .and_then(|uri| uri.to_file_path().ok());
+        if let Some(root) = root {
+            let _ = self.root.set(root);
+        }
+
+        Ok(lsp::InitializeResult {
+            capabilities: lsp::ServerCapabilities {
+                text_document_sync: Some(lsp::TextDocumentSyncCapability::Options(
+                    lsp::TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(lsp::TextDocumentSyncKind::NONE),
+                        save: Some(lsp::TextDocumentSyncSaveOptions::Supported(true)),
+                        ..Default::default()
+                    },
+                )),
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
+                execute_command_provider: Some(lsp::ExecuteCommandOptions {
+                    commands: vec![BASELINE_COMMAND.to_string(), SNOOZE_COMMAND.to_string()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            server_info: Some(lsp::ServerInfo {
+                name: "ferris-proof".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn initialized(&self, _: lsp::InitializedParams) {
+        let watcher = lsp::FileSystemWatcher {
+            glob_pattern: lsp::GlobPattern::String(format!("**/{}", CONFIG_FILE)),
+            kind: None,
+        };
+        let registration = lsp::Registration {
+            id: "ferris-proof-config".to_string(),
+            method: "workspace/didChangeWatchedFiles".to_string(),
+            register_options: serde_json::to_value(lsp::DidChangeWatchedFilesRegistrationOptions {
+                watchers: vec![watcher],
+            })
+            .ok(),
+        };
+        // Clients without dynamic registration still reload on save
+        if let Err(e) = self.client.register_capability(vec![registration]).await {
+            debug!("Config file watching unavailable: {}", e);
+        }
+    }
+
+    async fn shutdown(&self) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: lsp::DidOpenTextDocumentParams) {
+        self.verify_document(&params.text_document.uri).await;
+    }
+
+    async fn did_save(&self, params: lsp::DidSaveTextDocumentParams) {
+        let uri = &params.text_document.uri;
+        if uri.path().ends_with(CONFIG_FILE) {
+            self.reload_config().await;
+            return;
+        }
+        debug!("Verifying {} on save", uri);
+        self.verify_document(uri).await;
+    }
+
+    async fn did_change_watched_files(&self, _: lsp::DidChangeWatchedFilesParams) {
+        self.reload_config().await;
+    }
+
+    async fn did_close(&self, params: lsp::DidCloseTextDocumentParams) {
+        self.published
+            .lock()
+            .unwrap()
+            .remove(&params.text_document.uri);
+    }
+
+    async fn code_action(
+        &self,
+        params: lsp::CodeActionParams,
+    ) -> RpcResult<Option<lsp::CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let published = self
+            .published
+            .lock()
+            .unwrap()
+            .get(&uri)
+            .cloned()
+            .unwrap_or_default();
+
+        let mut actions = Vec::new();
+        for diagnostic in &params.context.diagnostics {
+            if diagnostic.source.as_deref() != Some(DIAGNOSTIC_SOURCE) {
+                continue;
+            }
+            let Some(violation) = published.iter().find(|v| {
+                to_diagnostic(v).range == diagnostic.range && v.id == code_of(diagnostic)
+            }) else {
+                continue;
+            };
+
+            if let Some(edit) = quick_fix(violation).map(text_edit) {
+                actions.push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                    title: format!(
+                        "Fix: {}",
+                        violation
+                            .suggestion
+                            .as_deref()
+                            .unwrap_or(&violation.message)
+                    ),
+                    kind: Some(lsp::CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(lsp::WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                }));
+            }
+
+            let argument =
+                serde_json::to_value(violation).map_err(|e| internal_error(e.to_string()))?;
+            for (command, title) in [
+                (BASELINE_COMMAND, "Add to FerrisProof baseline".to_string()),
+                (SNOOZE_COMMAND, format!("Snooze for {} days", SNOOZE_DAYS)),
+            ] {
+                actions.push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                    title,
+                    kind: Some(lsp::CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    command: Some(lsp::Command {
+                        title: command.to_string(),
+                        command: command.to_string(),
+                        arguments: Some(vec![argument.clone()]),
+                    }),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(Some(actions))
+    }
+
+    async fn execute_command(&self, params: lsp::ExecuteCommandParams) -> RpcResult<Option<Value>> {
+        if params.command != BASELINE_COMMAND && params.command != SNOOZE_COMMAND {
+            return Err(RpcError::method_not_found());
+        }
+        let violation: Violation = params
+            .arguments
+            .into_iter()
+            .next()
+            .and_then(|arg| serde_json::from_value(arg).ok())
+            .ok_or_else(RpcError::invalid_request)?;
+
+        self.suppress(&params.command, violation)
+            .await
+            .map_err(|e| internal_error(e.to_string()))?;
+        Ok(None)
+    }
+}
+
+fn file_path(uri: &lsp::Url) -> RpcResult<PathBuf> {
+    uri.to_file_path()
+        .map_err(|()| internal_error(format!("Not a file URI: {}", uri)))
+}
+
+fn internal_error(message: String) -> RpcError {
+    RpcError {
+        message: message.into(),
+        ..RpcError::internal_error()
+    }
+}
+
+fn code_of(diagnostic: &lsp::Diagnostic) -> String {
+    match &diagnostic.code {
+        Some(lsp::NumberOrString::String(code)) => code.clone(),
+        Some(lsp::NumberOrString::Number(code)) => code.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Convert a violation to an LSP diagnostic
+///
+/// FerrisProof positions are 1-based with an exclusive span end; LSP's are
+/// 0-based. Whole-file violations are pinned to the first line.
+pub fn to_diagnostic(violation: &Violation) -> lsp::Diagnostic {
+    let location = &violation.location;
+    let start = lsp::Position::new(
+        location.line.unwrap_or(1).saturating_sub(1),
+        location.column.unwrap_or(1).saturating_sub(1),
+    );
+    let end = match &location.span {
+        Some(span) => lsp::Position::new(
+            span.end.line.saturating_sub(1),
+            span.end.column.saturating_sub(1),
+        ),
+        None if location.column.is_some() => lsp::Position::new(start.line, start.character + 1),
+        // Highlight the whole line when only the line is known
+        None => lsp::Position::new(start.line + 1, 0),
+    };
+
+    let message = match &violation.suggestion {
+        Some(suggestion) => format!("{}\nhelp: {}", violation.message, suggestion),
+        None => violation.message.clone(),
+    };
+
+    lsp::Diagnostic {
+        range: lsp::Range::new(start, end),
+        severity: Some(match violation.severity {
+            Severity::Critical | Severity::Error => lsp::DiagnosticSeverity::ERROR,
+            Severity::Warning => lsp::DiagnosticSeverity::WARNING,
+            Severity::Info => lsp::DiagnosticSeverity::INFORMATION,
+        }),
+        code: Some(lsp::NumberOrString::String(violation.id.clone())),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// A fix as an edit inserting its text
+fn text_edit(fix: Fix) -> lsp::TextEdit {
+    let insert_at = lsp::Position::new(fix.line, 0);
+    lsp::TextEdit {
+        range: lsp::Range::new(insert_at, insert_at),
+        new_text: fix.text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferris_proof_core::types::Location;
+    use ferris_proof_plugins::cargo_metadata::MISSING_LICENSE;
+
+    fn violation(id: &str, location: Location) -> Violation {
+        Violation {
+            id: id.to_string(),
+            severity: Severity::Warning,
+            location,
+            message: "Crate does not declare a license".to_string(),
+            suggestion: Some("Add a license".to_string()),
+            rule: "metadata.license".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diagnostic_positions_are_zero_based() {
+        let spanned = violation(MISSING_LICENSE, Location::span("Cargo.toml", 1, 1, 1, 10));
+        let diagnostic = to_diagnostic(&spanned);
+        assert_eq!(diagnostic.range.start, lsp::Position::new(0, 0));
+        assert_eq!(diagnostic.range.end, lsp::Position::new(0, 9));
+        assert_eq!(diagnostic.severity, Some(lsp::DiagnosticSeverity::WARNING));
+        assert!(diagnostic.message.ends_with("help: Add a license"));
+
+        let whole_file = to_diagnostic(&violation("FP-VR-001", Location::file("src/lib.rs")));
+        assert_eq!(whole_file.range.start, lsp::Position::new(0, 0));
+        assert_eq!(whole_file.range.end, lsp::Position::new(1, 0));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::MetricsExport;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use ferris_proof_config::ConfigManager;
+use ferris_proof_core::metrics::{MetricsStore, UsageMetrics};
+
+pub async fn run(export: Option<MetricsExport>, reset: bool) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let store = MetricsStore::for_project(&project_root);
+
+    if reset {
+        store.reset()?;
+        println!("{} Cleared usage metrics", "✓".green());
+        return Ok(0);
+    }
+
+    let metrics = store.load()?;
+    match export {
+        Some(MetricsExport::Csv) => print!("{}", metrics.to_csv()),
+        Some(MetricsExport::Json) => {
+            println!("{}", serde_json::to_string_pretty(&metrics.to_json())?)
+        }
+        None if metrics.runs == 0 => {
+            let enabled = ConfigManager::from_project_root(&project_root)
+                .map(|manager| manager.root_config().metrics.enabled)
+                .unwrap_or(false);
+            println!("{}", "No usage metrics collected yet".yellow());
+            if enabled {
+                println!(
+                    "  Runs of {} are aggregated in {}",
+                    "ferris-proof check".cyan(),
+                    store.path().display()
+                );
+            } else {
+                println!(
+                    "  Collection is opt-in: set {} in ferris-proof.toml",
+                    "[metrics] enabled = true".cyan()
+                );
+            }
+        }
+        None => print_summary(&metrics),
+    }
+
+    Ok(0)
+}
+
+fn print_summary(metrics: &UsageMetrics) {
+    println!("Usage metrics over {} run(s)", metrics.runs);
+    if let (Some(first), Some(last)) = (metrics.first_run, metrics.last_run) {
+        println!(
+            "  {} to {}",
+            first.format("%Y-%m-%d %H:%M"),
+            last.format("%Y-%m-%d %H:%M")
+        );
+    }
+    println!(
+        "  Average cache hit rate: {:.0}%",
+        metrics.average_cache_hit_rate() * 100.0
+    );
+
+    if !metrics.layers.is_empty() {
+        println!(
+            "\n{:<16} {:>6} {:>12} {:>10}",
+            "Layer", "Runs", "Avg time", "Violations"
+        );
+        for (name, usage) in &metrics.layers {
+            println!(
+                "{:<16} {:>6} {:>11.2}s {:>10}",
+                name,
+                usage.runs,
+                usage.average_duration().as_secs_f64(),
+                usage.violations
+            );
+        }
+    }
+
+    if !metrics.rules.is_empty() {
+        println!(
+            "\n{:<32} {:>10} {:>6} {:>6}",
+            "Rule", "Violations", "Runs", "Rate"
+        );
+        let mut rules: Vec<_> = metrics.rules.iter().collect();
+        rules.sort_by(|a, b| b.1.runs.cmp(&a.1.runs).then(a.0.cmp(b.0)));
+        for (rule, usage) in rules {
+            println!(
+                "{:<32} {:>10} {:>6} {:>5.0}%",
+                rule,
+                usage.violations,
+                usage.runs,
+                metrics.violation_rate(usage) * 100.0
+            );
+        }
+    }
+}
@@ -1,7 +1,25 @@
+pub mod artifacts;
+pub mod assets;
+pub mod attestation;
+pub mod badge;
 pub mod cache;
 pub mod check;
 pub mod config;
+pub mod daemon;
+pub mod diff;
 pub mod explain;
+pub mod gate;
 pub mod generate;
+pub mod history;
 pub mod init;
+pub mod lsp;
+pub mod metrics;
+pub mod plugins;
+pub mod quick;
+pub mod release_report;
+pub mod replay;
+pub mod serve;
+pub mod spec_diff;
+pub mod trace;
+pub mod triage;
 pub mod upgrade;
@@ -0,0 +1,258 @@
+use crate::PluginsAction;
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use ferris_proof_config::ConfigManager;
+use ferris_proof_core::plugin_manifest;
+use ferris_proof_core::plugins::{PluginMetadata, SelectionStrategy};
+use ferris_proof_core::PluginManager;
+use ferris_proof_plugins::{
+    AlloyPlugin, CargoMutantsPlugin, ClippyPlugin, DeductivePlugin, MiriPlugin, ProptestPlugin,
+    SerdeRoundtripPlugin, SmtPlugin, TlaPlusPlugin,
+};
+use std::fs;
+use std::path::Path;
+use tracing::warn;
+
+const CONFIG_FILE: &str = "ferrisproof.toml";
+
+pub async fn run(action: PluginsAction) -> Result<i32> {
+    match action {
+        PluginsAction::List => list(),
+        PluginsAction::Info { name } => info(&name),
+        PluginsAction::Enable { name } => set_enabled(&name, true),
+        PluginsAction::Disable { name } => set_enabled(&name, false),
+        PluginsAction::Validate { manifest } => validate(&manifest),
+    }
+}
+
+/// Built-in plugins plus those discovered from manifests, with `[plugins] disabled` applied
+pub(crate) fn plugin_manager() -> Result<PluginManager> {
+    let mut plugin_manager = PluginManager::new();
+    plugin_manager.register_plugin(Box::new(TlaPlusPlugin::new()))?;
+    plugin_manager.register_plugin(Box::new(AlloyPlugin::new()))?;
+    plugin_manager.register_plugin(Box::new(ProptestPlugin::new()))?;
+    plugin_manager.register_plugin(Box::new(CargoMutantsPlugin::new()))?;
+    plugin_manager.register_plugin(Box::new(MiriPlugin::new()))?;
+    plugin_manager.register_plugin(Box::new(ClippyPlugin::new()))?;
+    plugin_manager.register_plugin(Box::new(SerdeRoundtripPlugin::new()))?;
+    plugin_manager.register_plugin(Box::new(SmtPlugin::new()))?;
+    plugin_manager.register_plugin(Box::new(DeductivePlugin::new()))?;
+
+    if let Err(e) = plugin_manager.discover_plugins() {
+        warn!(
+            "Plugin discovery failed, listing built-in plugins only: {}",
+            e
+        );
+    }
+
+    let disabled = std::env::current_dir()
+        .ok()
+        .and_then(|root| ConfigManager::from_project_root(&root).ok())
+        .map(|manager| manager.root_config().plugins.disabled.clone())
+        .unwrap_or_default();
+    plugin_manager.set_selection_strategy(SelectionStrategy {
+        disabled,
+        ..Default::default()
+    });
+
+    Ok(plugin_manager)
+}
+
+fn list() -> Result<i32> {
+    let plugin_manager = plugin_manager()?;
+
+    for metadata in plugin_manager.list_plugins() {
+        let status = if plugin_manager.is_enabled(&metadata.name) {
+            "enabled".green()
+        } else {
+            "disabled".yellow()
+        };
+        let source = match plugin_manager.manifest_path(&metadata.name) {
+            Some(path) => path.display().to_string(),
+            None => "built-in".to_string(),
+        };
+
+        println!(
+            "{:<20} {:<10} {:<9} {}",
+            metadata.name.bold(),
+            metadata.version,
+            status,
+            source.dimmed()
+        );
+    }
+
+    Ok(0)
+}
+
+fn info(name: &str) -> Result<i32> {
+    let plugin_manager = plugin_manager()?;
+    let metadata = find_plugin(&plugin_manager, name)?;
+
+    println!("{} v{}", metadata.name.bold(), metadata.version);
+    println!("  {}", metadata.description);
+    println!(
+        "  Status: {}",
+        if plugin_manager.is_enabled(name) {
+            "enabled".green()
+        } else {
+            "disabled".yellow()
+        }
+    );
+    println!("  Author: {}", metadata.author);
+    println!("  License: {}", metadata.license);
+    if let Some(homepage) = &metadata.homepage {
+        println!("  Homepage: {}", homepage);
+    }
+    match plugin_manager.manifest_path(name) {
+        Some(path) => println!("  Manifest: {}", path.display()),
+        None => println!("  Manifest: built-in"),
+    }
+    println!("  Techniques: {:?}", metadata.techniques);
+    if !metadata.supported_platforms.is_empty() {
+        println!("  Platforms: {}", metadata.supported_platforms.join(", "));
+    }
+    if !metadata.dependencies.is_empty() {
+        println!("  Dependencies: {}", metadata.dependencies.join(", "));
+    }
+    for renderer in &metadata.renderers {
+        println!(
+            "  Renderer: {} (.{}) via {}",
+            renderer.format,
+            renderer.extension,
+            renderer.command.display()
+        );
+    }
+    for entry in &metadata.error_codes {
+        println!("  Error code: {} {}", entry.code, entry.explanation.title);
+    }
+
+    Ok(0)
+}
+
+fn set_enabled(name: &str, enabled: bool) -> Result<i32> {
+    // Re-enabling a plugin that has since been removed only cleans up the config
+    if !enabled {
+        find_plugin(&plugin_manager()?, name)?;
+    }
+
+    let config_path = std::env::current_dir()
+        .context("Failed to get current directory")?
+        .join(CONFIG_FILE);
+    let content = fs::read_to_string(&config_path)
+        .with_context(|| format!("No {} found; run `ferris-proof init` first", CONFIG_FILE))?;
+
+    match update_disabled(&content, name, enabled)? {
+        Some(updated) => {
+            fs::write(&config_path, updated).context("Failed to write ferrisproof.toml")?;
+            println!(
+                "✓ {} {}",
+                if enabled { "Enabled" } else { "Disabled" },
+                name.bold()
+            );
+        }
+        None => println!(
+            "• {} is already {}",
+            name.bold(),
+            if enabled { "enabled" } else { "disabled" }
+        ),
+    }
+
+    Ok(0)
+}
+
+fn validate(manifest: &Path) -> Result<i32> {
+    match plugin_manifest::load_manifest(manifest) {
+        Ok(value) => {
+            println!(
+                "{} {} is a valid manifest for {}",
+                "✓".green(),
+                manifest.display(),
+                value["name"].as_str().unwrap_or_default().bold()
+            );
+            Ok(0)
+        }
+        Err(e) => {
+            println!("{} {}", "✗".red(), manifest.display());
+            println!("{:#}", e);
+            Ok(1)
+        }
+    }
+}
+
+fn find_plugin(plugin_manager: &PluginManager, name: &str) -> Result<PluginMetadata> {
+    plugin_manager.plugin_metadata(name).ok_or_else(|| {
+        let known: Vec<String> = plugin_manager
+            .list_plugins()
+            .into_iter()
+            .map(|metadata| metadata.name)
+            .collect();
+        anyhow!(
+            "Unknown plugin '{}'; available plugins: {}",
+            name,
+            known.join(", ")
+        )
+    })
+}
+
+/// Rewrite `[plugins] disabled`, or `None` when the plugin is already in the requested state
+fn update_disabled(content: &str, name: &str, enabled: bool) -> Result<Option<String>> {
+    let mut document: toml::Value =
+        toml::from_str(content).context("Failed to parse ferrisproof.toml")?;
+    let root = document
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("ferrisproof.toml is not a table"))?;
+    let plugins = root
+        .entry("plugins")
+        .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("[plugins] in ferrisproof.toml is not a table"))?;
+    let disabled = plugins
+        .entry("disabled")
+        .or_insert_with(|| toml::Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| anyhow!("[plugins] disabled in ferrisproof.toml is not an array"))?;
+
+    let entry = toml::Value::String(name.to_string());
+    let changed = if enabled {
+        let before = disabled.len();
+        disabled.retain(|value| value != &entry);
+        disabled.len() != before
+    } else if disabled.contains(&entry) {
+        false
+    } else {
+        disabled.push(entry);
+        true
+    };
+
+    if !changed {
+        return Ok(None);
+    }
+    toml::to_string_pretty(&document)
+        .map(Some)
+        .context("Failed to serialize configuration to TOML")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_disabled() {
+        let content = "[profile]\nlevel = \"standard\"\n";
+
+        let disabled = update_disabled(content, "kani", false).unwrap().unwrap();
+        let config: toml::Value = toml::from_str(&disabled).unwrap();
+        assert_eq!(
+            config["plugins"]["disabled"].as_array().unwrap(),
+            &vec![toml::Value::String("kani".to_string())]
+        );
+        assert_eq!(config["profile"]["level"].as_str(), Some("standard"));
+
+        assert!(update_disabled(&disabled, "kani", false).unwrap().is_none());
+        assert!(update_disabled(content, "kani", true).unwrap().is_none());
+
+        let enabled = update_disabled(&disabled, "kani", true).unwrap().unwrap();
+        let config: toml::Value = toml::from_str(&enabled).unwrap();
+        assert!(config["plugins"]["disabled"].as_array().unwrap().is_empty());
+    }
+}
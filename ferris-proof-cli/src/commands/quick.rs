@@ -0,0 +1,116 @@
+use crate::commands::cache::get_cache_dir;
+use crate::commands::plugins;
+use crate::commands::upgrade::{collect_files, find_evidence};
+use crate::exit_codes::ExitCodePolicy;
+use crate::render::snippet;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use ferris_proof_config::Config;
+use ferris_proof_core::cache::VerificationCache;
+use ferris_proof_core::discovery::{discover_targets, DiscoveryOptions};
+use ferris_proof_core::plugins::EffectiveConfig;
+use ferris_proof_core::seed;
+use ferris_proof_core::verification::Target;
+use ferris_proof_core::{Status, Technique, VerificationEngine};
+
+/// Verify the current crate with built-in defaults, ignoring any config
+///
+/// Nothing is written to disk unless `persist_cache` is set.
+pub async fn run(persist_cache: bool, seed: Option<u64>) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+
+    if project_root.join("ferrisproof.toml").exists() {
+        println!(
+            "{} ferrisproof.toml found; quick mode ignores it (use `ferris-proof check`)",
+            "•".yellow()
+        );
+    }
+
+    let mut techniques = vec![Technique::TypeSafety];
+    let property_tests = find_evidence(&Technique::PropertyTests, &collect_files(&project_root));
+    if !property_tests.is_empty() {
+        techniques.push(Technique::PropertyTests);
+    }
+    println!(
+        "Quick check with {:?} ({} property test file(s) found)",
+        techniques,
+        property_tests.len()
+    );
+
+    let targets: Vec<Target> = discover_targets(&project_root, &DiscoveryOptions::default())
+        .into_iter()
+        .filter(|target| matches!(target, Target::RustFile(_)))
+        .collect();
+
+    let seed = seed.unwrap_or_else(seed::random_seed);
+    let result = engine(&techniques, persist_cache, seed)?
+        .verify(&targets)
+        .await?;
+
+    let violations: Vec<_> = result
+        .layer_results
+        .values()
+        .flat_map(|l| l.violations.iter())
+        .collect();
+    for violation in &violations {
+        println!("{}", snippet::render_for_project(&project_root, violation));
+    }
+
+    let summary = format!(
+        "{} target(s) checked, {} violation(s)",
+        targets.len(),
+        violations.len()
+    );
+    match result.overall_status {
+        Status::Error => println!("{} {}", "✗".red(), summary),
+        Status::Warning => println!("{} {}", "!".yellow(), summary),
+        Status::Success | Status::Skipped | Status::Cached => {
+            println!("{} {}", "✓".green(), summary)
+        }
+    }
+    if result.overall_status == Status::Error {
+        println!("Reproduce with {}", format!("--seed {}", seed).cyan());
+    }
+
+    println!(
+        "\nRun {} to save these defaults to ferrisproof.toml and enable caching, history and stricter levels",
+        "ferris-proof init".cyan()
+    );
+
+    // Quick mode ignores ferrisproof.toml, so exit with the default policy
+    Ok(ExitCodePolicy::default().exit_code(&result))
+}
+
+/// Engine verifying `techniques` with the built-in default settings
+///
+/// The cache stays in memory unless `persist_cache` is set.
+pub fn engine(
+    techniques: &[Technique],
+    persist_cache: bool,
+    seed: u64,
+) -> Result<VerificationEngine> {
+    let defaults = Config::default();
+    let config = EffectiveConfig {
+        level: defaults.profile.level,
+        enforcement: defaults.profile.enforcement,
+        enforcement_policy: defaults.enforcement.resolve(),
+        enabled_techniques: techniques.to_vec(),
+        tool_config: serde_json::Value::Null,
+        plugin_tools: defaults.tools.by_plugin(),
+        memory_budget: Some(defaults.thresholds.max_memory_usage),
+    };
+
+    let builder = VerificationEngine::builder()
+        .config(config)
+        .plugin_manager(plugins::plugin_manager()?)
+        .seed(seed);
+    let builder = if persist_cache {
+        let cache_dir = get_cache_dir()?;
+        builder
+            .cache(VerificationCache::with_cache_dir(cache_dir.clone()))
+            .plugin_stats(cache_dir.join("plugin-stats.json"))
+    } else {
+        builder.cache(VerificationCache::in_memory())
+    };
+    Ok(builder.build())
+}
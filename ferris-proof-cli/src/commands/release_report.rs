@@ -0,0 +1,75 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use ferris_proof_core::history::{HistoryStore, ReleaseSummary};
+use std::path::PathBuf;
+use std::process::Command;
+
+pub async fn run(since: String, until: Option<String>, output: Option<PathBuf>) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let until_label = until.unwrap_or_else(|| "HEAD".to_string());
+
+    let since_time = git_ref_timestamp(&since)?;
+    let until_time = if until_label == "HEAD" {
+        Utc::now()
+    } else {
+        git_ref_timestamp(&until_label)?
+    };
+
+    let store = HistoryStore::for_project(&project_root);
+    let records = store.between(since_time, until_time)?;
+
+    if records.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "No verification history recorded between {} and {}",
+                since, until_label
+            )
+            .yellow()
+        );
+        println!(
+            "  Runs of {} are recorded in {}",
+            "ferris-proof check".cyan(),
+            store.path().display()
+        );
+    }
+
+    let summary = ReleaseSummary::from_records(&records, since_time, until_time);
+    let document = summary.to_markdown(&since, &until_label);
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, document)
+                .with_context(|| format!("Failed to write release report to {:?}", path))?;
+            println!(
+                "✓ Release report written to {}",
+                path.display().to_string().green()
+            );
+        }
+        None => println!("{}", document),
+    }
+
+    Ok(0)
+}
+
+/// Resolve the commit timestamp of a git ref (tag, branch or commit)
+fn git_ref_timestamp(git_ref: &str) -> Result<DateTime<Utc>> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%cI", git_ref])
+        .output()
+        .context("Failed to execute git")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Unknown git ref '{}': {}",
+            git_ref,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    DateTime::parse_from_rfc3339(stdout.trim())
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| anyhow!("Failed to parse commit date for '{}': {}", git_ref, e))
+}
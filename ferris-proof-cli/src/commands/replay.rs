@@ -0,0 +1,126 @@
+use crate::commands::cache::get_cache_dir;
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use ferris_proof_core::types::{Artifact, ArtifactType};
+use ferris_proof_core::CacheManager;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const REGRESSIONS_DIR: &str = "proptest-regressions";
+
+pub async fn run(violation_id: String) -> Result<i32> {
+    let mut cache_manager = CacheManager::with_cache_dir(get_cache_dir()?);
+    cache_manager.load()?;
+
+    let entries = cache_manager.cache().entries_with_violation(&violation_id);
+    let regressions: Vec<&Artifact> = entries
+        .iter()
+        .flat_map(|entry| entry.artifacts.iter())
+        .filter(|artifact| matches!(artifact.artifact_type, ArtifactType::Regression))
+        .collect();
+
+    if regressions.is_empty() {
+        return Err(anyhow!(
+            "No cached regression for violation '{}'; run `ferris-proof check` to record one",
+            violation_id
+        ));
+    }
+
+    let mut runs = BTreeSet::new();
+    for artifact in regressions {
+        restore(artifact)?;
+        runs.insert(test_target(&artifact.path)?);
+    }
+
+    let mut reproduced = false;
+    for (package_root, test) in runs {
+        println!(
+            "{} {} in {}",
+            "Replaying".bold(),
+            test.join(" "),
+            package_root.display()
+        );
+
+        // Persisted seeds run before generated cases, so zero cases replays only them
+        let status = Command::new("cargo")
+            .current_dir(&package_root)
+            .arg("test")
+            .args(&test)
+            .env("PROPTEST_CASES", "0")
+            .status()
+            .context("Failed to run cargo test")?;
+        reproduced |= !status.success();
+    }
+
+    if reproduced {
+        println!("{} Failure reproduced", "✗".red());
+        Ok(1)
+    } else {
+        println!("{} Recorded failure no longer reproduces", "✓".green());
+        Ok(0)
+    }
+}
+
+/// Write a cached regression file back where proptest looks for it
+fn restore(artifact: &Artifact) -> Result<()> {
+    if std::fs::read_to_string(&artifact.path).is_ok_and(|current| current == artifact.content) {
+        return Ok(());
+    }
+    if let Some(parent) = artifact.path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&artifact.path, &artifact.content)
+        .with_context(|| format!("Failed to restore {}", artifact.path.display()))
+}
+
+/// Package root and `cargo test` target selecting the test a regression file belongs to
+///
+/// `proptest-regressions/tests/prop.txt` belongs to `--test prop`; seeds for
+/// sources under `src/` belong to the library's unit tests.
+fn test_target(regression: &Path) -> Result<(PathBuf, Vec<String>)> {
+    let regressions_dir = regression
+        .ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == REGRESSIONS_DIR))
+        .ok_or_else(|| {
+            anyhow!(
+                "{} is not under a {} directory",
+                regression.display(),
+                REGRESSIONS_DIR
+            )
+        })?;
+    let package_root = regressions_dir
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf();
+
+    let relative = regression.strip_prefix(regressions_dir)?.with_extension("");
+    let mut components = relative.iter().map(|c| c.to_string_lossy().to_string());
+    let test = match (components.next().as_deref(), components.next()) {
+        (Some("tests"), Some(name)) => vec!["--test".to_string(), name],
+        _ => vec!["--lib".to_string()],
+    };
+
+    Ok((package_root, test))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_target() {
+        let (root, test) = test_target(Path::new(
+            "/work/codec/proptest-regressions/tests/roundtrip.txt",
+        ))
+        .unwrap();
+        assert_eq!(root, PathBuf::from("/work/codec"));
+        assert_eq!(test, vec!["--test", "roundtrip"]);
+
+        let (_, test) =
+            test_target(Path::new("/work/codec/proptest-regressions/src/lib.txt")).unwrap();
+        assert_eq!(test, vec!["--lib"]);
+
+        assert!(test_target(Path::new("/work/codec/tests/roundtrip.txt")).is_err());
+    }
+}
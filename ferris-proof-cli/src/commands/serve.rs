@@ -0,0 +1,294 @@
+use crate::commands::cache::get_cache_dir;
+use crate::commands::plugins;
+use anyhow::{Context, Result};
+use ferris_proof_core::plugins::ValidationStatus;
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info, warn};
+
+/// Artifacts need at least this much free space for readiness
+const MIN_FREE_DISK_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Plugins that take longer than this to validate are considered hung
+const PLUGIN_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pause after a failed accept before trying again
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Outcome of a single self-check
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub healthy: bool,
+    pub detail: String,
+}
+
+/// Readiness shared between the self-check loop and the HTTP listener
+#[derive(Debug, Default)]
+pub struct HealthState {
+    ready: AtomicBool,
+    checks: Mutex<Vec<CheckResult>>,
+}
+
+impl HealthState {
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    /// Store new check results, returning whether readiness changed
+    pub fn update(&self, checks: Vec<CheckResult>) -> bool {
+        let ready = checks.iter().all(|c| c.healthy);
+        *self.checks.lock().unwrap() = checks;
+        self.ready.swap(ready, Ordering::SeqCst) != ready
+    }
+
+    fn checks(&self) -> Vec<CheckResult> {
+        self.checks.lock().unwrap().clone()
+    }
+}
+
+pub async fn run(addr: SocketAddr, check_interval: u64) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let cache_dir = get_cache_dir()?;
+    let state = Arc::new(HealthState::default());
+
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind health endpoint on {}", addr))?;
+    println!("Serving /healthz and /readyz on http://{}", addr);
+
+    let checker = tokio::spawn(self_check_loop(
+        Arc::clone(&state),
+        project_root,
+        cache_dir,
+        Duration::from_secs(check_interval.max(1)),
+    ));
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, peer) = match accepted {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        // Usually transient, e.g. out of file descriptors;
+                        // back off so open connections can finish
+                        warn!("Failed to accept health connection: {}", e);
+                        tokio::time::sleep(ACCEPT_BACKOFF).await;
+                        continue;
+                    }
+                };
+                let state = Arc::clone(&state);
+                tokio::spawn(async move {
+                    if let Err(e) = handle_connection(stream, &state).await {
+                        debug!("Health request from {} failed: {}", peer, e);
+                    }
+                });
+            }
+            _ = tokio::signal::ctrl_c() => {
+                info!("Shutting down");
+                break;
+            }
+        }
+    }
+
+    checker.abort();
+    Ok(0)
+}
+
+async fn self_check_loop(
+    state: Arc<HealthState>,
+    project_root: PathBuf,
+    cache_dir: PathBuf,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        ticker.tick().await;
+
+        let mut checks = vec![
+            check_cache_writable(&cache_dir),
+            check_disk_space(&project_root.join(".ferris-proof")),
+        ];
+        checks.push(check_plugins().await);
+
+        for check in checks.iter().filter(|c| !c.healthy) {
+            warn!("Self-check '{}' failed: {}", check.name, check.detail);
+        }
+        if state.update(checks) {
+            if state.is_ready() {
+                info!("All self-checks passing; marking ready");
+            } else {
+                warn!("Marking not ready until self-checks pass");
+            }
+        }
+    }
+}
+
+fn check_cache_writable(cache_dir: &Path) -> CheckResult {
+    let probe = cache_dir.join(format!(".healthz-{}", std::process::id()));
+    let result = std::fs::create_dir_all(cache_dir)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+
+    CheckResult {
+        name: "cache_writable",
+        healthy: result.is_ok(),
+        detail: match result {
+            Ok(()) => cache_dir.display().to_string(),
+            Err(e) => format!("{}: {}", cache_dir.display(), e),
+        },
+    }
+}
+
+fn check_disk_space(artifacts_dir: &Path) -> CheckResult {
+    // Measure the nearest existing ancestor; the directory may not exist yet
+    let existing = artifacts_dir
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or(artifacts_dir);
+
+    match free_disk_bytes(existing) {
+        Some(free) => CheckResult {
+            name: "disk_space",
+            healthy: free >= MIN_FREE_DISK_BYTES,
+            detail: format!("{} MiB free", free / (1024 * 1024)),
+        },
+        None => CheckResult {
+            name: "disk_space",
+            healthy: true,
+            detail: "not measured on this platform".to_string(),
+        },
+    }
+}
+
+#[cfg(unix)]
+fn free_disk_bytes(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+    Some(stats.f_bavail as u64 * stats.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn free_disk_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Plugins are responsive when the availability checks of the built-in and
+/// discovered plugins finish in time without lock errors; a missing tool is
+/// reported by `check`, not here
+async fn check_plugins() -> CheckResult {
+    let validation = tokio::time::timeout(
+        PLUGIN_CHECK_TIMEOUT,
+        tokio::task::spawn_blocking(|| -> Result<_> {
+            Ok(plugins::plugin_manager()?.validate_tools()?)
+        }),
+    )
+    .await;
+
+    let (healthy, detail) = match validation {
+        Ok(Ok(Ok(results))) => {
+            let broken: Vec<String> = results
+                .iter()
+                .filter(|r| r.status == ValidationStatus::Error)
+                .map(|r| r.plugin_name.clone())
+                .collect();
+            if broken.is_empty() {
+                (true, format!("{} plugin(s) responsive", results.len()))
+            } else {
+                (false, format!("unresponsive: {}", broken.join(", ")))
+            }
+        }
+        Ok(Ok(Err(e))) => (false, e.to_string()),
+        Ok(Err(e)) => (false, format!("plugin check panicked: {}", e)),
+        Err(_) => (
+            false,
+            format!("validation timed out after {:?}", PLUGIN_CHECK_TIMEOUT),
+        ),
+    };
+
+    CheckResult {
+        name: "plugins",
+        healthy,
+        detail,
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, state: &HealthState) -> Result<()> {
+    let mut buffer = [0u8; 1024];
+    let read = stream.read(&mut buffer).await?;
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+    let (status, body) = respond(path, state);
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Status line and JSON body for a request path
+fn respond(path: &str, state: &HealthState) -> (&'static str, String) {
+    match path {
+        // Liveness only says the process can answer requests
+        "/healthz" => ("200 OK", r#"{"status":"ok"}"#.to_string()),
+        "/readyz" => {
+            let ready = state.is_ready();
+            let body = serde_json::json!({
+                "status": if ready { "ready" } else { "not_ready" },
+                "checks": state.checks(),
+            });
+            let status = if ready {
+                "200 OK"
+            } else {
+                "503 Service Unavailable"
+            };
+            (status, body.to_string())
+        }
+        _ => ("404 Not Found", r#"{"status":"not_found"}"#.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readiness_follows_self_checks() {
+        let state = HealthState::default();
+        assert_eq!(respond("/healthz", &state).0, "200 OK");
+        assert_eq!(respond("/readyz", &state).0, "503 Service Unavailable");
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = check_cache_writable(&temp_dir.path().join("cache"));
+        assert!(cache.healthy);
+
+        assert!(state.update(vec![cache]));
+        assert_eq!(respond("/readyz", &state).0, "200 OK");
+
+        assert!(state.update(vec![CheckResult {
+            name: "disk_space",
+            healthy: false,
+            detail: "0 MiB free".to_string(),
+        }]));
+        let (status, body) = respond("/readyz", &state);
+        assert_eq!(status, "503 Service Unavailable");
+        assert!(body.contains("disk_space"));
+    }
+}
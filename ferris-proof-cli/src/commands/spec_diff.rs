@@ -0,0 +1,55 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use ferris_proof_core::spec_diff::{self, ChangeKind, SpecDiff};
+
+pub async fn run(rev: String, markdown: bool) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let diffs = spec_diff::diff_against_rev(&project_root, &rev)?;
+
+    if diffs.is_empty() {
+        println!("No semantic specification changes since {}", rev);
+        return Ok(0);
+    }
+
+    if markdown {
+        print!("{}", render_markdown(&rev, &diffs));
+    } else {
+        for diff in &diffs {
+            print_diff(diff);
+        }
+    }
+
+    Ok(0)
+}
+
+fn print_diff(diff: &SpecDiff) {
+    println!("{}", diff.path.display().to_string().bold());
+    for change in &diff.changes {
+        let marker = match change.change {
+            ChangeKind::Added => "+".green(),
+            ChangeKind::Removed => "-".red(),
+            ChangeKind::Modified => "~".yellow(),
+        };
+        println!("  {} {} {}", marker, change.kind.label(), change.name);
+    }
+    println!();
+}
+
+/// Summary table for pasting into a PR description
+fn render_markdown(rev: &str, diffs: &[SpecDiff]) -> String {
+    let mut out = format!("## Specification changes since `{}`\n\n", rev);
+    out.push_str("| Spec | Change | Kind | Name |\n");
+    out.push_str("|------|--------|------|------|\n");
+    for diff in diffs {
+        for change in &diff.changes {
+            out.push_str(&format!(
+                "| `{}` | {:?} | {} | `{}` |\n",
+                diff.path.display(),
+                change.change,
+                change.kind.label(),
+                change.name
+            ));
+        }
+    }
+    out
+}
@@ -0,0 +1,80 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use ferris_proof_core::spec_drift::DriftBaseline;
+use ferris_proof_core::traceability::TraceMatrix;
+
+pub async fn run(json: bool, accept_drift: bool) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let matrix = TraceMatrix::build(&project_root)?;
+
+    if accept_drift {
+        let mut baseline = DriftBaseline::for_project(&project_root)?;
+        let accepted = baseline.accept(&project_root, &matrix)?;
+        baseline.save()?;
+        println!(
+            "{} Recorded {} spec link(s) as up to date",
+            "✓".green(),
+            accepted
+        );
+        return Ok(0);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&matrix)?);
+        return Ok(0);
+    }
+
+    if matrix.is_empty() && matrix.spec_actions.is_empty() {
+        println!("No specifications or #[verification(spec = \"...\")] links found");
+        return Ok(0);
+    }
+
+    for (spec, actions) in &matrix.spec_actions {
+        println!("{}", spec.display().to_string().bold());
+        for action in actions {
+            let links = matrix.implementations(spec, action);
+            if links.is_empty() {
+                println!("  {} {} (no implementing code)", "✗".red(), action);
+                continue;
+            }
+            println!("  {} {}", "✓".green(), action);
+            for link in links {
+                println!(
+                    "      {} ({}:{})",
+                    link.item,
+                    link.file.display(),
+                    link.line
+                );
+            }
+        }
+        println!();
+    }
+
+    let dangling = matrix.dangling_links();
+    if !dangling.is_empty() {
+        println!("{}", "Broken links".bold());
+        for link in &dangling {
+            println!(
+                "  {} {} -> {}#{} ({}:{})",
+                "!".yellow(),
+                link.item,
+                link.spec.display(),
+                link.action,
+                link.file.display(),
+                link.line
+            );
+        }
+        println!();
+    }
+
+    let unimplemented = matrix.unimplemented_actions().len();
+    let total: usize = matrix.spec_actions.values().map(Vec::len).sum();
+    println!(
+        "{}/{} spec actions have implementing code, {} broken link(s)",
+        total - unimplemented,
+        total,
+        dangling.len()
+    );
+
+    Ok(0)
+}
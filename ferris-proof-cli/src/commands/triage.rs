@@ -0,0 +1,214 @@
+use crate::commands::check;
+use crate::fixes;
+use crate::render::snippet;
+use anyhow::{anyhow, Context, Result};
+use chrono::{Duration, Utc};
+use colored::Colorize;
+use console::{Key, Term};
+use ferris_proof_core::baseline::{AuditAction, AuditLog, Baseline};
+use ferris_proof_core::history::ViolationRecord;
+use ferris_proof_core::plugins::CancellationToken;
+use ferris_proof_core::timings::Timings;
+use ferris_proof_core::types::Violation;
+use ferris_proof_core::VerificationResult;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const DEFAULT_SNOOZE_DAYS: i64 = 7;
+
+pub async fn run(report: Option<PathBuf>) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let baseline_path = Baseline::path_for_project(&project_root);
+    let mut baseline = Baseline::load(&baseline_path)?;
+    let audit = AuditLog::for_project(&project_root);
+
+    let mut result = match report {
+        Some(path) => load_report(&path)?,
+        None => {
+            check::verify_project(
+                &project_root,
+                &[],
+                None,
+                false,
+                None,
+                None,
+                &Timings::default(),
+                CancellationToken::new(),
+            )
+            .await?
+            .0
+        }
+    };
+    baseline.apply(&mut result);
+
+    let mut violations: Vec<Violation> = result
+        .layer_results
+        .into_values()
+        .flat_map(|l| l.violations)
+        .collect();
+    violations.sort_by(|a, b| {
+        (&a.location.file, a.location.line).cmp(&(&b.location.file, b.location.line))
+    });
+
+    if violations.is_empty() {
+        println!("{}", "✓ No violations to triage".green());
+        return Ok(0);
+    }
+
+    let term = Term::stdout();
+    if !term.is_term() {
+        return Err(anyhow!("triage requires an interactive terminal"));
+    }
+
+    let mut index = 0;
+    let mut status_line = String::new();
+
+    while !violations.is_empty() {
+        index = index.min(violations.len() - 1);
+        let violation = &violations[index];
+        let record = ViolationRecord::from_violation(violation);
+
+        term.clear_screen()?;
+        render(&project_root, violation, index, violations.len());
+        if !status_line.is_empty() {
+            println!("{}", status_line);
+            status_line.clear();
+        }
+
+        match term.read_key()? {
+            Key::Char('n') | Key::ArrowRight | Key::ArrowDown | Key::Enter => {
+                index = (index + 1) % violations.len();
+            }
+            Key::Char('p') | Key::ArrowLeft | Key::ArrowUp => {
+                index = (index + violations.len() - 1) % violations.len();
+            }
+            Key::Char('b') => {
+                term.write_str("Reason (optional): ")?;
+                let reason = term.read_line()?.trim().to_string();
+                let reason = (!reason.is_empty()).then_some(reason);
+
+                baseline.add(&record, reason.clone());
+                baseline.save(&baseline_path)?;
+                audit.record(AuditAction::Baselined, &record, reason)?;
+
+                violations.remove(index);
+                status_line = format!("{} Added to baseline", "✓".green());
+            }
+            Key::Char('s') => {
+                term.write_str(&format!(
+                    "Snooze for how many days? [{}]: ",
+                    DEFAULT_SNOOZE_DAYS
+                ))?;
+                let input = term.read_line()?;
+                let days = input
+                    .trim()
+                    .parse::<i64>()
+                    .unwrap_or(DEFAULT_SNOOZE_DAYS)
+                    .max(1);
+                let until = Utc::now() + Duration::days(days);
+
+                baseline.snooze(&record, until);
+                baseline.save(&baseline_path)?;
+                audit.record(
+                    AuditAction::Snoozed,
+                    &record,
+                    Some(format!("until {}", until.format("%Y-%m-%d"))),
+                )?;
+
+                violations.remove(index);
+                status_line = format!("{} Snoozed for {} day(s)", "✓".green(), days);
+            }
+            Key::Char('e') => {
+                open_in_editor(&project_root, violation)?;
+                audit.record(AuditAction::Opened, &record, None)?;
+            }
+            Key::Char('f') => match fixes::quick_fix(violation) {
+                Some(fix) => {
+                    fix.apply(&resolve(&project_root, &violation.location.file))?;
+                    audit.record(
+                        AuditAction::Fixed,
+                        &record,
+                        Some(fix.text.trim().to_string()),
+                    )?;
+
+                    violations.remove(index);
+                    status_line = format!("{} Applied fix: {}", "✓".green(), fix.text.trim());
+                }
+                None => {
+                    status_line = match &violation.suggestion {
+                        Some(suggestion) => format!(
+                            "{} No automatic fix available. Suggested: {}",
+                            "•".yellow(),
+                            suggestion
+                        ),
+                        None => format!("{} No automatic fix available", "•".yellow()),
+                    };
+                }
+            },
+            Key::Char('q') | Key::Escape => break,
+            _ => {}
+        }
+    }
+
+    term.clear_screen()?;
+    println!(
+        "Triage finished: {} violation(s) remaining",
+        violations.len()
+    );
+    Ok(0)
+}
+
+fn render(project_root: &Path, violation: &Violation, index: usize, total: usize) {
+    println!(
+        "{} [{}/{}]",
+        "FerrisProof triage".bold().cyan(),
+        index + 1,
+        total
+    );
+    println!();
+    println!("{}", snippet::render_for_project(project_root, violation));
+    println!(
+        "{}",
+        "[n]ext  [p]rev  [f]ix  [b]aseline  [s]nooze  [e]dit  [q]uit".dimmed()
+    );
+}
+
+fn open_in_editor(project_root: &Path, violation: &Violation) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let path = resolve(project_root, &violation.location.file);
+
+    // Editors are often configured with arguments, e.g. `code --wait`
+    let words = shell_words::split(&editor)
+        .with_context(|| format!("Failed to parse editor command '{}'", editor))?;
+    let Some((program, args)) = words.split_first() else {
+        return Err(anyhow!("Editor command is empty"));
+    };
+    let mut command = Command::new(program);
+    command.args(args);
+    if let Some(line) = violation.location.line {
+        command.arg(format!("+{}", line));
+    }
+    command
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{}'", editor))?;
+
+    Ok(())
+}
+
+fn resolve(project_root: &Path, file: &Path) -> PathBuf {
+    if file.is_absolute() {
+        file.to_path_buf()
+    } else {
+        project_root.join(file)
+    }
+}
+
+fn load_report(path: &Path) -> Result<VerificationResult> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read report {:?}", path))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("{:?} is not a JSON verification report", path))
+}
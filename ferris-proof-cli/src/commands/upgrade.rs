@@ -1,17 +1,490 @@
-use anyhow::Result;
-use ferris_proof_core::VerificationLevel;
+use crate::commands::config::{ConfigEditor, EditOutcome};
+use crate::commands::init::{
+    techniques_for_level, ALLOY_SPEC_TEMPLATE, PROPERTY_TEST_TEMPLATE, SESSION_TYPE_TEMPLATE,
+    TLA_SPEC_TEMPLATE,
+};
+use crate::render::unified;
+use crate::OutputFormat;
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use console::Term;
+use ferris_proof_config::Config;
+use ferris_proof_core::{Layer, Technique, VerificationLevel};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
-pub async fn run(to: VerificationLevel, dry_run: bool, interactive: bool) -> Result<i32> {
-    println!("Upgrading to verification level: {:?}", to);
+/// Rough effort needed to close a gap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Effort {
+    /// Artifacts already exist; the technique only needs enabling
+    Low,
+    Medium,
+    High,
+}
+
+/// A technique required by the target level that the project does not yet use
+#[derive(Debug, Clone)]
+pub struct Gap {
+    pub technique: Technique,
+    /// Files found in the project that already use the technique
+    pub evidence: Vec<PathBuf>,
+    pub effort: Effort,
+    /// Scaffolding files to create, relative to the project root
+    pub scaffolding: Vec<(PathBuf, &'static str)>,
+}
+
+/// A file an upgrade writes, with its unified diff
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    /// Path relative to the project root
+    pub path: PathBuf,
+    pub diff: String,
+}
+
+/// Everything an upgrade would change, as shown by `--dry-run`
+#[derive(Debug, Clone, Serialize)]
+pub struct Changeset {
+    pub from: VerificationLevel,
+    pub to: VerificationLevel,
+    pub config: FileChange,
+    /// Directories created for scaffolding, parents first
+    pub directories: Vec<PathBuf>,
+    /// Scaffolding files created from templates
+    pub files: Vec<FileChange>,
+    /// Techniques enabled by the upgrade
+    pub techniques: Vec<Technique>,
+    /// Layers no previously enabled technique belongs to
+    pub layers: Vec<Layer>,
+}
+
+impl Changeset {
+    /// Changes of upgrading a project with config `content` to `to`
+    pub fn new(
+        project_root: &Path,
+        content: &str,
+        config: &Config,
+        to: VerificationLevel,
+        gaps: &[Gap],
+    ) -> Result<Self> {
+        let config_change = FileChange {
+            path: PathBuf::from("ferrisproof.toml"),
+            diff: unified::unified_diff(
+                content,
+                &upgraded_config(content, to, gaps)?,
+                "ferrisproof.toml",
+                "ferrisproof.toml",
+            ),
+        };
+
+        let scaffolding = missing_scaffolding(project_root, gaps);
+        let directories: BTreeSet<PathBuf> = scaffolding
+            .iter()
+            .flat_map(|(path, _)| path.ancestors().skip(1))
+            .filter(|dir| !dir.as_os_str().is_empty() && !project_root.join(dir).exists())
+            .map(Path::to_path_buf)
+            .collect();
+        let files = scaffolding
+            .into_iter()
+            .map(|(path, template)| {
+                let label = path.display().to_string();
+                FileChange {
+                    path: path.clone(),
+                    diff: unified::unified_diff("", template, &label, &label),
+                }
+            })
+            .collect();
+
+        let running: HashSet<Layer> = config
+            .profile
+            .enabled_techniques
+            .iter()
+            .map(Technique::layer)
+            .collect();
+        let mut layers: Vec<Layer> = Vec::new();
+        for layer in gaps.iter().map(|gap| gap.technique.layer()) {
+            if !running.contains(&layer) && !layers.contains(&layer) {
+                layers.push(layer);
+            }
+        }
+
+        Ok(Self {
+            from: config.profile.level,
+            to,
+            config: config_change,
+            directories: directories.into_iter().collect(),
+            files,
+            techniques: gaps.iter().map(|gap| gap.technique.clone()).collect(),
+            layers,
+        })
+    }
+
+    /// Changeset of a project already at `level`
+    pub fn unchanged(level: VerificationLevel) -> Self {
+        Self {
+            from: level,
+            to: level,
+            config: FileChange {
+                path: PathBuf::from("ferrisproof.toml"),
+                diff: String::new(),
+            },
+            directories: Vec::new(),
+            files: Vec::new(),
+            techniques: Vec::new(),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Print unified diffs of every written file, created directories and new layers
+    fn print(&self) {
+        println!("{}", "Changes".bold());
+        if self.config.diff.is_empty() {
+            println!("  {} unchanged", self.config.path.display());
+        } else {
+            print!("{}", unified::colorize(&self.config.diff));
+        }
+        for dir in &self.directories {
+            println!("{} {}/", "new directory".green(), dir.display());
+        }
+        for file in &self.files {
+            print!("{}", unified::colorize(&file.diff));
+        }
+
+        if !self.techniques.is_empty() {
+            let techniques: Vec<String> =
+                self.techniques.iter().map(|t| format!("{:?}", t)).collect();
+            println!("Techniques enabled: {}", techniques.join(", ").green());
+        }
+        if !self.layers.is_empty() {
+            let layers: Vec<String> = self.layers.iter().map(|l| format!("{:?}", l)).collect();
+            println!("Layers that start running: {}", layers.join(", ").green());
+        }
+    }
+}
+
+pub async fn run(
+    to: VerificationLevel,
+    dry_run: bool,
+    interactive: bool,
+    output_format: Option<OutputFormat>,
+) -> Result<i32> {
+    let project_root = std::env::current_dir().context("Failed to get current directory")?;
+    let config_path = project_root.join("ferrisproof.toml");
+
+    if !config_path.exists() {
+        return Err(anyhow!(
+            "No ferrisproof.toml found. Run 'ferris-proof init' first."
+        ));
+    }
+
+    let content = fs::read_to_string(&config_path).context("Failed to read ferrisproof.toml")?;
+    let config: Config = toml::from_str(&content).context("Failed to parse ferrisproof.toml")?;
+
+    let current = config.profile.level;
+    let json = matches!(output_format, Some(OutputFormat::Json)) && dry_run;
+    if to <= current {
+        if json {
+            let unchanged = Changeset::unchanged(current);
+            println!("{}", serde_json::to_string_pretty(&unchanged)?);
+        } else {
+            println!(
+                "Project is already at level {}; nothing to upgrade",
+                format!("{:?}", current).green()
+            );
+        }
+        return Ok(0);
+    }
+
+    let gaps = analyze_gaps(&project_root, &config, to);
+    if json {
+        let changeset = Changeset::new(&project_root, &content, &config, to, &gaps)?;
+        println!("{}", serde_json::to_string_pretty(&changeset)?);
+        return Ok(0);
+    }
+
+    println!(
+        "Upgrading verification level: {} → {}",
+        format!("{:?}", current).yellow(),
+        format!("{:?}", to).green()
+    );
+    println!();
+    print_gap_report(&gaps);
 
     if dry_run {
-        println!("Dry run mode - showing changes only");
+        println!();
+        Changeset::new(&project_root, &content, &config, to, &gaps)?.print();
+        println!();
+        println!("Dry run mode - no changes applied");
+        return Ok(0);
     }
 
-    if interactive {
-        println!("Interactive mode enabled");
+    let term = Term::stdout();
+    let scaffolding = missing_scaffolding(&project_root, &gaps);
+
+    if !scaffolding.is_empty()
+        && (!interactive || confirm(&term, "Generate missing scaffolding?", true)?)
+    {
+        for (path, content) in scaffolding {
+            let full_path = project_root.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&full_path, content)
+                .with_context(|| format!("Failed to write {:?}", full_path))?;
+            println!("✓ Created {}", path.display().to_string().green());
+        }
+    }
+
+    if interactive && !confirm(&term, "Update ferrisproof.toml with the new level?", false)? {
+        println!("{}", "Configuration left unchanged.".yellow());
+        return Ok(0);
     }
 
-    // TODO: Implement verification level upgrade
+    update_config_file(&config_path, &content, to, &gaps)?;
+    println!(
+        "✓ Updated {} to level {}",
+        "ferrisproof.toml".green(),
+        format!("{:?}", to).green()
+    );
+
     Ok(0)
 }
+
+/// Compare the project against what the target level requires
+pub fn analyze_gaps(project_root: &Path, config: &Config, to: VerificationLevel) -> Vec<Gap> {
+    let sources = collect_files(project_root);
+
+    techniques_for_level(to)
+        .into_iter()
+        .filter(|technique| !config.profile.enabled_techniques.contains(technique))
+        .map(|technique| {
+            let evidence = find_evidence(&technique, &sources);
+            let effort = if evidence.is_empty() {
+                base_effort(&technique)
+            } else {
+                Effort::Low
+            };
+            let scaffolding = if evidence.is_empty() {
+                scaffolding_for(&technique)
+            } else {
+                Vec::new()
+            };
+
+            Gap {
+                technique,
+                evidence,
+                effort,
+                scaffolding,
+            }
+        })
+        .collect()
+}
+
+fn print_gap_report(gaps: &[Gap]) {
+    if gaps.is_empty() {
+        println!(
+            "{}",
+            "✓ All required techniques are already enabled".green()
+        );
+        return;
+    }
+
+    println!("{}", "Gap analysis".bold());
+    for gap in gaps {
+        let effort = match gap.effort {
+            Effort::Low => "low".green(),
+            Effort::Medium => "medium".yellow(),
+            Effort::High => "high".red(),
+        };
+
+        if gap.evidence.is_empty() {
+            println!(
+                "  {} {:?}: not used yet (effort: {})",
+                "✗".red(),
+                gap.technique,
+                effort
+            );
+        } else {
+            println!(
+                "  {} {:?}: found in {} file(s), needs enabling (effort: {})",
+                "•".yellow(),
+                gap.technique,
+                gap.evidence.len(),
+                effort
+            );
+        }
+
+        for (path, _) in &gap.scaffolding {
+            println!("      scaffold: {}", path.display().to_string().cyan());
+        }
+    }
+}
+
+fn base_effort(technique: &Technique) -> Effort {
+    match technique {
+        Technique::TypeSafety => Effort::Low,
+        Technique::PropertyTests
+        | Technique::RefinementTypes
+        | Technique::ConcurrencyTesting
+        | Technique::ModelChecking
+        | Technique::MutationTesting
+        | Technique::UndefinedBehaviorChecks => Effort::Medium,
+        Technique::SessionTypes | Technique::FormalSpecs | Technique::DeductiveProofs => {
+            Effort::High
+        }
+    }
+}
+
+fn scaffolding_for(technique: &Technique) -> Vec<(PathBuf, &'static str)> {
+    match technique {
+        Technique::PropertyTests => vec![(
+            PathBuf::from("tests/property/example_properties.rs"),
+            PROPERTY_TEST_TEMPLATE,
+        )],
+        Technique::SessionTypes => vec![(
+            PathBuf::from("specs/session-types/example_protocol.rs"),
+            SESSION_TYPE_TEMPLATE,
+        )],
+        Technique::FormalSpecs => vec![
+            (
+                PathBuf::from("specs/formal/tla/example_protocol.tla"),
+                TLA_SPEC_TEMPLATE,
+            ),
+            (
+                PathBuf::from("specs/formal/alloy/example_protocol.als"),
+                ALLOY_SPEC_TEMPLATE,
+            ),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+/// Files in the project that already use a technique
+pub(crate) fn find_evidence(technique: &Technique, sources: &[PathBuf]) -> Vec<PathBuf> {
+    let extension_is =
+        |path: &Path, ext: &str| path.extension().and_then(|e| e.to_str()) == Some(ext);
+
+    let markers: &[&str] = match technique {
+        Technique::TypeSafety => return Vec::new(),
+        Technique::PropertyTests => &["proptest!", "#[quickcheck]"],
+        Technique::SessionTypes => &["#[session_type]"],
+        Technique::RefinementTypes => &["#[refinement", "#[refine("],
+        Technique::ConcurrencyTesting => &["loom::model", "#[loom"],
+        Technique::MutationTesting => &["#[mutants::skip]", "#[cfg_attr(test, mutants::skip)]"],
+        Technique::UndefinedBehaviorChecks => &["cfg(miri)", "cfg_attr(miri"],
+        Technique::DeductiveProofs => &["#[requires(", "#[ensures(", "#[contract("],
+        Technique::FormalSpecs => {
+            return sources
+                .iter()
+                .filter(|p| extension_is(p, "tla") || extension_is(p, "als"))
+                .cloned()
+                .collect();
+        }
+        Technique::ModelChecking => {
+            return sources
+                .iter()
+                .filter(|p| extension_is(p, "cfg") && p.with_extension("tla").exists())
+                .cloned()
+                .collect();
+        }
+    };
+
+    sources
+        .iter()
+        .filter(|p| extension_is(p, "rs"))
+        .filter(|p| {
+            fs::read_to_string(p)
+                .map(|content| markers.iter().any(|m| content.contains(m)))
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect()
+}
+
+/// All files below `root`, skipping hidden directories and target/
+pub(crate) fn collect_files(root: &Path) -> Vec<PathBuf> {
+    fn visit(dir: &Path, files: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            if path.is_dir() {
+                if !name.starts_with('.') && name != "target" {
+                    visit(&path, files);
+                }
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    visit(root, &mut files);
+    files.sort();
+    files
+}
+
+/// Scaffolding files of `gaps` that do not exist yet
+fn missing_scaffolding<'g>(
+    project_root: &Path,
+    gaps: &'g [Gap],
+) -> Vec<&'g (PathBuf, &'static str)> {
+    gaps.iter()
+        .flat_map(|gap| gap.scaffolding.iter())
+        .filter(|(path, _)| !project_root.join(path).exists())
+        .collect()
+}
+
+/// Rewrite the profile section with the new level and techniques
+fn update_config_file(
+    config_path: &Path,
+    content: &str,
+    to: VerificationLevel,
+    gaps: &[Gap],
+) -> Result<()> {
+    let updated = upgraded_config(content, to, gaps)?;
+    fs::write(config_path, updated).context("Failed to write ferrisproof.toml")?;
+    Ok(())
+}
+
+/// `ferrisproof.toml` as written by the upgrade, keeping comments and formatting
+fn upgraded_config(content: &str, to: VerificationLevel, gaps: &[Gap]) -> Result<String> {
+    let mut editor = ConfigEditor::parse(content)?;
+    let mut techniques = editor.config()?.profile.enabled_techniques;
+    for gap in gaps {
+        if !techniques.contains(&gap.technique) {
+            techniques.push(gap.technique.clone());
+        }
+    }
+
+    if let EditOutcome::Rejected { errors } = editor.set_level(to, Some(&techniques))? {
+        return Err(anyhow!(
+            "Upgraded ferrisproof.toml would be invalid: {}",
+            errors.join("; ")
+        ));
+    }
+    Ok(editor.to_string())
+}
+
+fn confirm(term: &Term, question: &str, default: bool) -> Result<bool> {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+
+    loop {
+        print!("{} {} ", question, hint);
+        io::stdout().flush()?;
+
+        match term.read_line()?.trim().to_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("{}", "Please answer y or n.".red()),
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use anyhow::Result;
+use ferris_proof_config::{ConfigManager, ExitCodesConfig};
+use ferris_proof_core::types::{SkipReason, Violation};
+use ferris_proof_core::{
+    EnforcementMode, EnforcementPolicy, Layer, Severity, Status, VerificationResult,
+};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Exit code of a run interrupted by Ctrl-C, following the shell's 128 + SIGINT
+pub const INTERRUPTED: i32 = 130;
+
+/// Layers in pipeline order, so the first failing layer picks the exit code
+const LAYER_ORDER: [Layer; 5] = [
+    Layer::Formal,
+    Layer::TypeLevel,
+    Layer::PropertyBased,
+    Layer::Monitoring,
+    Layer::ApiSurface,
+];
+
+/// Converts a `VerificationResult` into a process exit code
+///
+/// Built from `[ci] fail_on_violations` and `[ci.exit_codes]`; each
+/// violation is graded by its module's `[enforcement]` policy and mode.
+pub struct ExitCodePolicy {
+    fail_on_violations: bool,
+    codes: ExitCodesConfig,
+    layer_codes: HashMap<Layer, i32>,
+    config: Option<ConfigManager>,
+}
+
+impl Default for ExitCodePolicy {
+    fn default() -> Self {
+        Self {
+            fail_on_violations: true,
+            codes: ExitCodesConfig::default(),
+            layer_codes: HashMap::new(),
+            config: None,
+        }
+    }
+}
+
+impl ExitCodePolicy {
+    pub fn from_config(config: ConfigManager) -> Result<Self> {
+        let ci = config.root_config().ci.clone();
+        let layer_codes = ci
+            .exit_codes
+            .layers
+            .iter()
+            .map(|(layer, code)| {
+                layer
+                    .parse::<Layer>()
+                    .map(|layer| (layer, *code))
+                    .map_err(|e| anyhow::anyhow!("ci.exit_codes.layers: {}", e))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            fail_on_violations: ci.fail_on_violations,
+            codes: ci.exit_codes,
+            layer_codes,
+            config: Some(config),
+        })
+    }
+
+    /// Policy for the project in the current directory, or the defaults
+    pub fn for_current_project() -> Result<Self> {
+        match std::env::current_dir()
+            .ok()
+            .and_then(|root| ConfigManager::from_project_root(&root).ok())
+        {
+            Some(config) => Self::from_config(config),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn exit_code(&self, result: &VerificationResult) -> i32 {
+        if !self.fail_on_violations {
+            return 0;
+        }
+
+        // Within `thresholds.max_violations` violations are tolerated
+        if let Some(budget) = &result.budget {
+            let violations = result.layer_results.values().map(|l| l.violations.len());
+            let tool_failure =
+                result.overall_status == Status::Error && violations.sum::<usize>() == 0;
+            return if budget.exceeded() || tool_failure {
+                self.codes.error
+            } else {
+                0
+            };
+        }
+
+        let mut worst: Option<Severity> = None;
+        for layer in LAYER_ORDER {
+            let Some(layer_result) = result.layer_results.get(&layer) else {
+                continue;
+            };
+            let failing = layer_result
+                .violations
+                .iter()
+                .filter_map(|v| self.failing_severity(v))
+                .min_by_key(severity_rank);
+
+            if let Some(severity) = failing {
+                if let Some(code) = self.layer_codes.get(&layer) {
+                    return *code;
+                }
+                if worst
+                    .as_ref()
+                    .is_none_or(|w| severity_rank(&severity) < severity_rank(w))
+                {
+                    worst = Some(severity);
+                }
+            }
+        }
+
+        match worst {
+            Some(Severity::Error) => self.codes.error,
+            Some(Severity::Warning) => self.codes.warning,
+            // A tool failure without violations still fails the run
+            _ if result.overall_status == Status::Error => self.codes.error,
+            _ if self.codes.skipped_fail && skipped_work(result) => self.codes.warning,
+            _ => 0,
+        }
+    }
+
+    /// Severity the violation fails the run with, or `None` if it does not
+    fn failing_severity(&self, violation: &Violation) -> Option<Severity> {
+        let (mode, policy) = self.enforcement_for(&violation.location.file);
+        let mode = match mode {
+            EnforcementMode::Advisory if self.codes.advisory_fail => EnforcementMode::Error,
+            mode => mode,
+        };
+
+        match policy.outcome(&violation.severity, mode) {
+            EnforcementMode::Error => Some(Severity::Error),
+            EnforcementMode::Warning if self.codes.warnings_fail => Some(Severity::Warning),
+            _ => None,
+        }
+    }
+
+    fn enforcement_for(&self, file: &Path) -> (EnforcementMode, EnforcementPolicy) {
+        self.config.as_ref().map_or(
+            (EnforcementMode::Error, EnforcementPolicy::default()),
+            |config| {
+                let effective = config.for_file(file);
+                (effective.enforcement, effective.enforcement_policy)
+            },
+        )
+    }
+}
+
+/// A layer or target was skipped for a reason other than the baseline
+fn skipped_work(result: &VerificationResult) -> bool {
+    let skipped_layer = result.layer_results.values().any(|layer| {
+        layer.status == Status::Skipped
+            && result.skip_reasons.get(&layer.layer) != Some(&SkipReason::Suppressed)
+    });
+    skipped_layer
+        || result
+            .target_statuses
+            .values()
+            .any(|status| *status == Status::Skipped)
+}
+
+fn severity_rank(severity: &Severity) -> u8 {
+    match severity {
+        Severity::Critical => 0,
+        Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Info => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferris_proof_core::budget::ViolationBudget;
+    use ferris_proof_core::types::{Location, VerificationMetrics};
+    use ferris_proof_core::LayerResult;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    fn result(violations: Vec<(Layer, Severity)>) -> VerificationResult {
+        let mut layer_results = HashMap::new();
+        for (layer, severity) in violations {
+            layer_results
+                .entry(layer)
+                .or_insert_with(|| LayerResult {
+                    layer,
+                    status: Status::Error,
+                    violations: vec![],
+                    execution_time: Duration::ZERO,
+                    tool_outputs: vec![],
+                })
+                .violations
+                .push(Violation {
+                    id: "FP-TEST".to_string(),
+                    severity,
+                    location: Location::file("src/lib.rs"),
+                    message: "test".to_string(),
+                    suggestion: None,
+                    rule: "test".to_string(),
+                });
+        }
+
+        VerificationResult {
+            overall_status: Status::Warning,
+            layer_results,
+            metrics: VerificationMetrics {
+                total_time: Duration::ZERO,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+            artifacts: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            skipped_targets: Vec::new(),
+            gated_layers: Vec::new(),
+            unavailable_layers: Vec::new(),
+            skip_reasons: HashMap::new(),
+            spec_changes: Vec::new(),
+            traceability: None,
+            budget: None,
+            seed: None,
+            environment: None,
+            target_statuses: BTreeMap::new(),
+            interrupted: false,
+            project_root: None,
+            link_template: None,
+            plugin_breakdowns: Vec::new(),
+            completed: Vec::new(),
+            api_surface: None,
+        }
+    }
+
+    #[test]
+    fn test_exit_codes_by_severity_and_layer() {
+        let mut policy = ExitCodePolicy::default();
+        assert_eq!(policy.exit_code(&result(vec![])), 0);
+        assert_eq!(
+            policy.exit_code(&result(vec![(Layer::TypeLevel, Severity::Warning)])),
+            0
+        );
+        assert_eq!(
+            policy.exit_code(&result(vec![(Layer::TypeLevel, Severity::Error)])),
+            1
+        );
+        assert_eq!(
+            policy.exit_code(&result(vec![(Layer::Formal, Severity::Critical)])),
+            1
+        );
+
+        policy.codes.warnings_fail = true;
+        assert_eq!(
+            policy.exit_code(&result(vec![(Layer::TypeLevel, Severity::Warning)])),
+            2
+        );
+
+        policy.layer_codes.insert(Layer::PropertyBased, 30);
+        assert_eq!(
+            policy.exit_code(&result(vec![
+                (Layer::TypeLevel, Severity::Info),
+                (Layer::PropertyBased, Severity::Warning),
+            ])),
+            30
+        );
+
+        policy.fail_on_violations = false;
+        assert_eq!(
+            policy.exit_code(&result(vec![(Layer::Formal, Severity::Error)])),
+            0
+        );
+    }
+
+    #[test]
+    fn test_skipped_layers_fail_only_when_configured() {
+        let mut policy = ExitCodePolicy::default();
+        let mut skipped = result(vec![]);
+        skipped.overall_status = Status::Success;
+        skipped.layer_results.insert(
+            Layer::Formal,
+            LayerResult {
+                layer: Layer::Formal,
+                status: Status::Skipped,
+                violations: vec![],
+                execution_time: Duration::ZERO,
+                tool_outputs: vec![],
+            },
+        );
+        skipped
+            .skip_reasons
+            .insert(Layer::Formal, SkipReason::ToolUnavailable);
+        skipped
+            .target_statuses
+            .insert("src/lib.rs".to_string(), Status::Cached);
+        assert_eq!(policy.exit_code(&skipped), 0);
+
+        policy.codes.skipped_fail = true;
+        assert_eq!(policy.exit_code(&skipped), 2);
+
+        // Accepting every finding in the baseline is not skipped work
+        skipped
+            .skip_reasons
+            .insert(Layer::Formal, SkipReason::Suppressed);
+        assert_eq!(policy.exit_code(&skipped), 0);
+    }
+
+    #[test]
+    fn test_violation_budget_decides_the_exit_code() {
+        let policy = ExitCodePolicy::default();
+        let budget = ViolationBudget {
+            total: Some(2),
+            ..Default::default()
+        };
+        let with_budget = |violations| {
+            let mut result = result(violations);
+            result.budget = Some(budget.evaluate(&result));
+            result
+        };
+
+        assert_eq!(
+            policy.exit_code(&with_budget(vec![
+                (Layer::TypeLevel, Severity::Error),
+                (Layer::Formal, Severity::Critical),
+            ])),
+            0
+        );
+        assert_eq!(
+            policy.exit_code(&with_budget(vec![
+                (Layer::TypeLevel, Severity::Warning),
+                (Layer::TypeLevel, Severity::Warning),
+                (Layer::TypeLevel, Severity::Warning),
+            ])),
+            1
+        );
+    }
+}
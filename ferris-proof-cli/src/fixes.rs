@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use ferris_proof_core::types::Violation;
+use ferris_proof_plugins::cargo_metadata::MISSING_RUST_VERSION;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Text inserted at the start of a 0-based line of the violation's file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub line: u32,
+    pub text: String,
+}
+
+impl Fix {
+    /// `content` with the fix inserted
+    pub fn apply_to(&self, content: &str) -> String {
+        let offset: usize = content
+            .split_inclusive('\n')
+            .take(self.line as usize)
+            .map(str::len)
+            .sum();
+        let mut fixed = content.to_string();
+        // A header on the last line may have no newline to insert after
+        let text = if offset == content.len() && !content.is_empty() && !content.ends_with('\n') {
+            format!("\n{}", self.text)
+        } else {
+            self.text.clone()
+        };
+        fixed.insert_str(offset, &text);
+        fixed
+    }
+
+    /// Apply the fix to the file at `path`
+    pub fn apply(&self, path: &Path) -> Result<()> {
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+        std::fs::write(path, self.apply_to(&content))
+            .with_context(|| format!("Failed to write {:?}", path))
+    }
+}
+
+/// Machine-applicable fix for violations that have one
+///
+/// Only a missing `rust-version` has an unambiguous edit: the active
+/// toolchain's version inserted below the `[package]` header the violation
+/// points at. A missing license is a legal choice left to the user.
+pub fn quick_fix(violation: &Violation) -> Option<Fix> {
+    let text = match violation.id.as_str() {
+        MISSING_RUST_VERSION => format!("rust-version = \"{}\"\n", rustc_minor_version()?),
+        _ => return None,
+    };
+    Some(Fix {
+        line: violation.location.line?,
+        text,
+    })
+}
+
+/// `1.83` for the active toolchain
+fn rustc_minor_version() -> Option<String> {
+    static VERSION: OnceLock<Option<String>> = OnceLock::new();
+
+    VERSION
+        .get_or_init(|| {
+            let output = std::process::Command::new("rustc")
+                .arg("--version")
+                .output()
+                .ok()?;
+            let text = String::from_utf8_lossy(&output.stdout);
+            let version = text.split_whitespace().nth(1)?;
+            let mut parts = version.split('.');
+            Some(format!("{}.{}", parts.next()?, parts.next()?))
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferris_proof_core::types::{Location, Severity};
+    use ferris_proof_plugins::cargo_metadata::{MISSING_LICENSE, WILDCARD_DEPENDENCY};
+
+    fn violation(id: &str, location: Location) -> Violation {
+        Violation {
+            id: id.to_string(),
+            severity: Severity::Warning,
+            location,
+            message: "Crate does not declare a rust-version".to_string(),
+            suggestion: None,
+            rule: "metadata.rust_version".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_quick_fix_inserts_below_package_header() {
+        let missing = violation(
+            MISSING_RUST_VERSION,
+            Location::span("Cargo.toml", 3, 1, 3, 10),
+        );
+        let fix = quick_fix(&missing).unwrap();
+        assert_eq!(fix.line, 3);
+        assert!(fix.text.starts_with("rust-version = \"1."), "{}", fix.text);
+
+        let fix = Fix {
+            line: 3,
+            text: "rust-version = \"1.83\"\n".to_string(),
+        };
+        assert_eq!(
+            fix.apply_to("[workspace]\n\n[package]\nname = \"demo\"\n"),
+            "[workspace]\n\n[package]\nrust-version = \"1.83\"\nname = \"demo\"\n"
+        );
+        assert_eq!(
+            fix.apply_to("[workspace]\n\n[package]"),
+            "[workspace]\n\n[package]\nrust-version = \"1.83\"\n"
+        );
+
+        let license = violation(MISSING_LICENSE, Location::span("Cargo.toml", 3, 1, 3, 10));
+        assert!(quick_fix(&license).is_none());
+        assert!(quick_fix(&violation(
+            WILDCARD_DEPENDENCY,
+            Location::file("Cargo.toml")
+        ))
+        .is_none());
+    }
+}
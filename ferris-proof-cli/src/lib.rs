@@ -1,8 +1,14 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use ferris_proof_core::{Layer, VerificationLevel};
+use render::view::{GroupBy, ViolationFilter};
 use std::path::PathBuf;
 
+pub mod analysis;
+pub mod assets;
 pub mod commands;
+pub mod exit_codes;
+pub mod fixes;
+pub mod render;
 
 #[derive(Parser)]
 #[command(name = "ferris-proof")]
@@ -30,32 +36,168 @@ pub struct Cli {
     /// Disable colored output (respects NO_COLOR environment variable)
     #[arg(long, global = true)]
     pub no_color: bool,
+
+    /// Seed for target order and randomized tools (random and printed if omitted)
+    #[arg(long, global = true, value_name = "SEED")]
+    pub seed: Option<u64>,
 }
 
+// Parsed once per process, so the size of `Check` does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Initialize project with verification configuration
     Init {
-        #[arg(long, default_value = "standard", help = "Verification level")]
-        level: VerificationLevel,
+        #[arg(
+            long,
+            help = "Verification level [default: standard, or the recommended one with --analyze]"
+        )]
+        level: Option<VerificationLevel>,
         #[arg(long, help = "Use interactive mode")]
         interactive: bool,
         #[arg(long, help = "Project template to use")]
         template: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "interactive",
+            help = "Analyze the existing crate and add overrides for unsafe and concurrent modules"
+        )]
+        analyze: bool,
+        #[arg(
+            long,
+            help = "Overwrite an existing ferrisproof.toml instead of merging into it"
+        )]
+        force: bool,
     },
 
     /// Run verification checks
     Check {
-        #[arg(long, help = "Specific module to check")]
-        module: Option<String>,
+        #[arg(
+            long,
+            value_name = "PATTERN",
+            help = "Module to check, e.g. payment::ledger or 'crypto::*' (repeatable)"
+        )]
+        module: Vec<String>,
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with = "module",
+            help = "Single function or item to check, e.g. my_mod::parse_header"
+        )]
+        item: Option<String>,
         #[arg(long, help = "Specific layer to run")]
         layer: Option<Layer>,
         #[arg(long, help = "Automatically fix violations")]
         fix: bool,
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            help = "Render a report (json, markdown, html, sarif, or a plugin-provided format)"
+        )]
+        report: Option<String>,
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Write the report to a file instead of stdout"
+        )]
+        report_output: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Verify dependents of failed targets instead of skipping them"
+        )]
+        keep_going: bool,
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Use this plugin instead of ranking candidates"
+        )]
+        plugin: Option<String>,
+        #[arg(
+            long,
+            value_name = "REV",
+            help = "Include semantic spec changes since a git revision in the report"
+        )]
+        spec_diff: Option<String>,
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Check TLA+ specs with a named model from [tools.tla_plus.models]"
+        )]
+        model: Option<String>,
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Print a per-phase timing breakdown, optionally exporting a Chrome trace to FILE"
+        )]
+        timings: Option<Option<PathBuf>>,
+        #[arg(
+            long,
+            help = "Record passing targets in .ferrisproof.lock so unchanged ones are skipped"
+        )]
+        update_lock: bool,
+        #[arg(
+            long,
+            help = "Verify every target even if .ferrisproof.lock vouches for it"
+        )]
+        ignore_lock: bool,
+        #[arg(
+            long,
+            help = "Sign the JSON report into .ferris-proof/attestations.jsonl"
+        )]
+        attest: bool,
+        #[arg(
+            long,
+            requires = "report_output",
+            conflicts_with_all = [
+                "module", "item", "fix", "spec_diff", "timings", "update_lock", "ignore_lock",
+                "attest", "filters", "group_by", "output_format",
+            ],
+            help = "Run as a hermetic build action: verify only --input files, write only --report-output"
+        )]
+        action_mode: bool,
+        #[arg(
+            long = "input",
+            value_name = "FILE",
+            requires = "action_mode",
+            help = "Input file for --action-mode; @FILE reads one path per line (repeatable)"
+        )]
+        inputs: Vec<PathBuf>,
+        #[arg(
+            long,
+            value_name = "DIR",
+            requires = "action_mode",
+            help = "Cache directory for --action-mode, which caches nothing otherwise"
+        )]
+        cache_dir: Option<PathBuf>,
+        #[arg(
+            long = "filter",
+            value_name = "KEY=VALUE",
+            help = "Only print violations matching severity=, rule=, layer= or module= (repeatable)"
+        )]
+        filters: Vec<ViolationFilter>,
+        #[arg(
+            long,
+            value_enum,
+            help = "Print violations under module, layer or rule headings"
+        )]
+        group_by: Option<GroupBy>,
+        #[arg(
+            long,
+            help = "Fail layers whose tools are not installed instead of skipping them"
+        )]
+        strict_tools: bool,
+    },
+
+    /// Try FerrisProof on an existing crate without a ferrisproof.toml
+    Quick {
+        #[arg(long, help = "Write results to the persistent verification cache")]
+        persist_cache: bool,
     },
 
     /// Show effective configuration
     Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
         #[arg(long, help = "Show config for specific file")]
         file: Option<PathBuf>,
         #[arg(long, help = "Validate configuration")]
@@ -78,12 +220,203 @@ pub enum Commands {
         target: GenerateTarget,
         #[arg(long, help = "Output directory")]
         output_dir: Option<PathBuf>,
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Protocol definition (JSON or DSL) for --target session-types"
+        )]
+        spec: Option<PathBuf>,
     },
 
     /// Explain error codes and provide guidance
     Explain {
-        #[arg(help = "Error code to explain")]
-        error_code: String,
+        #[arg(
+            required_unless_present = "last",
+            help = "Error code, or fingerprint or id of a violation in the latest run"
+        )]
+        error_code: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "error_code",
+            help = "Explain every violation of the latest run"
+        )]
+        last: bool,
+        #[arg(
+            long,
+            value_name = "REPORT",
+            help = "JSON report to take violations from (default: latest recorded run)"
+        )]
+        report: Option<PathBuf>,
+    },
+
+    /// Aggregate verification history between two git tags for release sign-off
+    ReleaseReport {
+        #[arg(long, help = "Git tag or ref marking the start of the release window")]
+        since: String,
+        #[arg(
+            long,
+            help = "Git tag or ref marking the end of the window (default: HEAD)"
+        )]
+        until: Option<String>,
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Write the report to a file instead of stdout"
+        )]
+        output: Option<PathBuf>,
+    },
+
+    /// Compare violations between two runs
+    Diff {
+        #[arg(value_name = "OLD_REPORT", help = "Baseline JSON report")]
+        old: Option<PathBuf>,
+        #[arg(
+            value_name = "NEW_REPORT",
+            help = "JSON report to compare (default: latest recorded run)"
+        )]
+        new: Option<PathBuf>,
+        #[arg(
+            long,
+            value_name = "GIT_REF",
+            help = "Use the run recorded for a git ref as the baseline"
+        )]
+        against: Option<String>,
+    },
+
+    /// Semantically diff TLA+/Alloy specs against a git revision
+    SpecDiff {
+        #[arg(
+            value_name = "REV",
+            help = "Git revision to compare the working tree against"
+        )]
+        rev: String,
+        #[arg(long, help = "Print Markdown suitable for a PR description")]
+        markdown: bool,
+    },
+
+    /// Run a language server publishing verification diagnostics on save
+    Lsp,
+
+    /// Show recent runs and flag verification-time regressions
+    History {
+        #[arg(
+            long,
+            default_value_t = 20,
+            help = "Number of most recent runs to analyse"
+        )]
+        last: usize,
+        #[arg(
+            long,
+            value_name = "PERCENT",
+            default_value_t = 25.0,
+            help = "Slowdown against the median of earlier runs that counts as a regression"
+        )]
+        threshold: f64,
+        #[arg(long, help = "Exit with code 1 when a regression is detected")]
+        fail_on_regression: bool,
+        #[arg(long, help = "Print the runs and trend as JSON")]
+        json: bool,
+    },
+
+    /// Write an SVG badge and shields.io endpoint file for the latest run
+    Badge {
+        #[arg(
+            long,
+            value_name = "REPORT",
+            help = "JSON report to summarise (default: latest recorded run)"
+        )]
+        report: Option<PathBuf>,
+        #[arg(
+            short,
+            long,
+            value_name = "DIR",
+            default_value = ".",
+            help = "Directory to write ferris-proof-badge.svg and ferris-proof-badge.json to"
+        )]
+        output: PathBuf,
+    },
+
+    /// Summarise locally collected usage metrics; enable collection with `[metrics] enabled = true`
+    Metrics {
+        #[arg(long, value_enum, help = "Print the metrics as CSV or JSON instead")]
+        export: Option<MetricsExport>,
+        #[arg(long, help = "Discard all collected metrics")]
+        reset: bool,
+    },
+
+    /// Show which code items implement which spec actions, and which have none
+    Trace {
+        #[arg(long, help = "Print the traceability matrix as JSON")]
+        json: bool,
+        #[arg(
+            long,
+            help = "Record linked specs as up to date with their Rust definitions"
+        )]
+        accept_drift: bool,
+    },
+
+    /// Interactively review violations: baseline, snooze or open them in $EDITOR
+    Triage {
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Triage violations from a JSON report instead of running verification"
+        )]
+        report: Option<PathBuf>,
+    },
+
+    /// Re-run the cached failing property test case behind a violation
+    Replay {
+        #[arg(
+            value_name = "VIOLATION_ID",
+            help = "Violation id from a previous check, e.g. PROPTEST_FAILURE"
+        )]
+        violation_id: String,
+    },
+
+    /// Evaluate the release gates in [gates] against recorded runs
+    Gate,
+
+    /// Check the signatures and hash chain of an attestation log
+    VerifyAttestation {
+        #[arg(
+            value_name = "FILE",
+            help = "Attestation log written by `check --attest`"
+        )]
+        file: PathBuf,
+        #[arg(
+            long,
+            value_name = "HEX",
+            help = "Public key the attestations must be signed with (default: [attestation] public_key)"
+        )]
+        public_key: Option<String>,
+    },
+
+    /// Run in the background, exposing /healthz and /readyz for supervisors
+    Serve {
+        #[arg(
+            long,
+            default_value = "127.0.0.1:9464",
+            help = "Address for the health endpoints"
+        )]
+        addr: std::net::SocketAddr,
+        #[arg(
+            long,
+            default_value = "30",
+            value_name = "SECONDS",
+            help = "Interval between self-checks"
+        )]
+        check_interval: u64,
+    },
+
+    /// Keep a warm verification engine behind a local JSON-RPC socket
+    Daemon {
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Socket path [default: .ferris-proof/daemon.sock, or a per-project named pipe on Windows]"
+        )]
+        socket: Option<PathBuf>,
     },
 
     /// Manage verification cache
@@ -91,18 +424,160 @@ pub enum Commands {
         #[command(subcommand)]
         action: CacheAction,
     },
+
+    /// Inspect or extract the default assets shipped with this binary
+    Assets {
+        #[command(subcommand)]
+        action: AssetsAction,
+    },
+
+    /// Access tool outputs stored outside verification results
+    Artifacts {
+        #[command(subcommand)]
+        action: ArtifactsAction,
+    },
+
+    /// Inspect, enable or disable verification plugins
+    Plugins {
+        #[command(subcommand)]
+        action: PluginsAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Walk through ferrisproof.toml interactively, validating every change
+    Edit,
+    /// Print the configuration schema, e.g. for editor completion
+    Schema {
+        #[arg(long, value_enum, default_value = "json-schema")]
+        format: SchemaFormat,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum SchemaFormat {
+    /// JSON Schema (draft 7), for taplo, Even Better TOML and other validators
+    #[value(name = "json-schema")]
+    JsonSchema,
+    /// Commented TOML listing every table and key with its type
+    #[value(name = "toml-doc")]
+    TomlDoc,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ArtifactsAction {
+    /// Stream the full output of a tool run whose result holds only a preview
+    Show {
+        #[arg(value_name = "ID", help = "Artifact id from the truncated output")]
+        id: String,
+        #[arg(long, help = "Show stderr instead of stdout")]
+        stderr: bool,
+    },
+
+    /// Remove generated artifacts beyond the retention limits, keeping pinned ones
+    Prune {
+        #[arg(
+            long,
+            value_name = "DAYS",
+            help = "Override [artifacts] retention_days"
+        )]
+        retention_days: Option<u32>,
+        #[arg(
+            long,
+            value_name = "BYTES",
+            help = "Override [artifacts] max_total_size"
+        )]
+        max_total_size: Option<u64>,
+        #[arg(long, help = "List what would be removed without removing it")]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AssetsAction {
+    /// List default config presets, error catalog and templates
+    List,
+
+    /// Write the default assets to a directory
+    Dump {
+        #[arg(
+            long,
+            default_value = "ferris-proof-assets",
+            help = "Directory to write assets to"
+        )]
+        output_dir: PathBuf,
+        #[arg(long, help = "Overwrite existing files")]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PluginsAction {
+    /// List built-in and discovered plugins
+    List,
+
+    /// Show a plugin's metadata, manifest and renderers
+    Info {
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+
+    /// Remove a plugin from `[plugins] disabled` in ferrisproof.toml
+    Enable {
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+
+    /// Add a plugin to `[plugins] disabled` in ferrisproof.toml
+    Disable {
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+
+    /// Check a plugin manifest against the manifest schema
+    Validate {
+        #[arg(value_name = "MANIFEST")]
+        manifest: PathBuf,
+    },
 }
 
 #[derive(Subcommand, Debug)]
 pub enum CacheAction {
     /// Show cache information and statistics
-    Info,
+    Info {
+        #[arg(long, help = "List the cache of every project sharing the cache root")]
+        all_projects: bool,
+    },
 
     /// Clean up expired cache entries
     Cleanup,
 
-    /// Clear all cache entries
-    Clear,
+    /// Clear all cache entries, or only those matching the given filters
+    Clear {
+        #[arg(long, help = "Only clear results for this layer")]
+        layer: Option<Layer>,
+        #[arg(
+            long,
+            value_name = "PATTERN",
+            help = "Only clear results for matching modules, e.g. 'crypto::*'"
+        )]
+        module: Option<String>,
+        #[arg(
+            long,
+            value_name = "AGE",
+            value_parser = commands::cache::parse_age,
+            help = "Only clear results older than AGE, e.g. 12h or 7d"
+        )]
+        older_than: Option<std::time::Duration>,
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = ["layer", "module", "older_than"],
+            help = "Remove the caches of another project, for every toolchain"
+        )]
+        project: Option<PathBuf>,
+    },
 
     /// Compact cache by removing expired entries and optimizing storage
     Compact,
@@ -123,6 +598,18 @@ pub enum OutputFormat {
     Json,
     /// Compact single-line format for CI environments
     Compact,
+    /// GitHub Actions annotations and job summary
+    Github,
+    /// One JSON object per line for every event as verification progresses
+    Ndjson,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum MetricsExport {
+    /// One `section,name,metric,value` row per figure
+    Csv,
+    /// Layer, rule and cache figures as a JSON document
+    Json,
 }
 
 #[derive(Clone, Debug, ValueEnum)]
@@ -130,13 +617,25 @@ pub enum GenerateTarget {
     /// Generate property-based tests
     #[value(name = "property-tests")]
     PropertyTests,
-    /// Generate session type definitions
+    /// Generate typestate code and a graphviz diagram from a protocol definition
     #[value(name = "session-types")]
     SessionTypes,
     /// Generate refinement type definitions
     #[value(name = "refinement-types")]
     RefinementTypes,
-    /// Generate formal specification templates
+    /// Generate skeleton TLA+ specs from state machine enums
     #[value(name = "formal-specs")]
     FormalSpecs,
+    /// Generate TLC model configuration (.cfg) files
+    #[value(name = "tlc-config")]
+    TlcConfig,
+    /// Generate serialize/deserialize round-trip property tests for serde types
+    #[value(name = "serde-roundtrip")]
+    SerdeRoundtrip,
+    /// Generate insta snapshot tests for functions returning serde types
+    #[value(name = "snapshot-tests")]
+    SnapshotTests,
+    /// Extract contracts from `# Panics`/`# Safety`/`# Invariants` doc sections
+    #[value(name = "doc-contracts")]
+    DocContracts,
 }
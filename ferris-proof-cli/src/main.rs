@@ -1,5 +1,6 @@
 use clap::Parser;
-use ferris_proof_cli::{Cli, Commands};
+use ferris_proof_cli::render::view::ViolationView;
+use ferris_proof_cli::{Cli, Commands, ConfigAction};
 use std::process;
 use tracing::{error, info, Level};
 
@@ -15,43 +16,201 @@ async fn main() {
         _ => Level::TRACE,
     };
 
+    // Logs go to stderr so they never mix into machine-readable stdout, such
+    // as the language server's JSON-RPC or the NDJSON event stream
     tracing_subscriber::fmt()
         .with_max_level(log_level)
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
         .init();
 
     info!("FerrisProof starting with command: {:?}", cli.command);
 
     let result = match cli.command {
+        Commands::Init {
+            level,
+            template,
+            analyze: true,
+            force,
+            ..
+        } => ferris_proof_cli::commands::init::run_analyzed(level, template, force).await,
         Commands::Init {
             level,
             interactive,
             template,
-        } => ferris_proof_cli::commands::init::run(level, interactive, template).await,
-        Commands::Check { module, layer, fix } => {
-            ferris_proof_cli::commands::check::run(module, layer, fix).await
+            analyze: false,
+            force,
+        } => {
+            ferris_proof_cli::commands::init::run(
+                level.unwrap_or(ferris_proof_core::VerificationLevel::Standard),
+                interactive,
+                template,
+                force,
+            )
+            .await
         }
-        Commands::Config { file, validate } => {
-            ferris_proof_cli::commands::config::run(file, validate).await
+        Commands::Check {
+            action_mode: true,
+            inputs,
+            layer,
+            keep_going,
+            plugin,
+            model,
+            report,
+            report_output,
+            cache_dir,
+            strict_tools,
+            ..
+        } => {
+            ferris_proof_cli::commands::check::run_action(
+                inputs,
+                layer,
+                keep_going,
+                strict_tools,
+                plugin,
+                model,
+                report,
+                report_output,
+                cache_dir,
+                cli.seed,
+            )
+            .await
         }
+        Commands::Check {
+            module,
+            item,
+            layer,
+            fix,
+            report,
+            report_output,
+            keep_going,
+            plugin,
+            spec_diff,
+            model,
+            timings,
+            update_lock,
+            ignore_lock,
+            attest,
+            filters,
+            group_by,
+            strict_tools,
+            ..
+        } => {
+            ferris_proof_cli::commands::check::run(
+                module,
+                item,
+                layer,
+                fix,
+                report,
+                report_output,
+                keep_going,
+                strict_tools,
+                plugin,
+                spec_diff,
+                model,
+                cli.output_format,
+                cli.seed,
+                timings,
+                update_lock,
+                ignore_lock,
+                attest,
+                ViolationView { filters, group_by },
+            )
+            .await
+        }
+        Commands::Quick { persist_cache } => {
+            ferris_proof_cli::commands::quick::run(persist_cache, cli.seed).await
+        }
+        Commands::Config {
+            action: Some(ConfigAction::Edit),
+            ..
+        } => ferris_proof_cli::commands::config::edit().await,
+        Commands::Config {
+            action: Some(ConfigAction::Schema { format }),
+            ..
+        } => ferris_proof_cli::commands::config::schema(format),
+        Commands::Config {
+            action: None,
+            file,
+            validate,
+        } => ferris_proof_cli::commands::config::run(file, validate).await,
         Commands::Upgrade {
             to,
             dry_run,
             interactive,
-        } => ferris_proof_cli::commands::upgrade::run(to, dry_run, interactive).await,
-        Commands::Generate { target, output_dir } => {
-            ferris_proof_cli::commands::generate::run(target, output_dir).await
+        } => {
+            ferris_proof_cli::commands::upgrade::run(to, dry_run, interactive, cli.output_format)
+                .await
+        }
+        Commands::Generate {
+            target,
+            output_dir,
+            spec,
+        } => ferris_proof_cli::commands::generate::run(target, output_dir, spec).await,
+        Commands::Explain {
+            error_code,
+            last,
+            report,
+        } => ferris_proof_cli::commands::explain::run(error_code, last, report.as_deref()).await,
+        Commands::ReleaseReport {
+            since,
+            until,
+            output,
+        } => ferris_proof_cli::commands::release_report::run(since, until, output).await,
+        Commands::Diff { old, new, against } => {
+            ferris_proof_cli::commands::diff::run(old, new, against).await
+        }
+        Commands::SpecDiff { rev, markdown } => {
+            ferris_proof_cli::commands::spec_diff::run(rev, markdown).await
+        }
+        Commands::Lsp => ferris_proof_cli::commands::lsp::run().await,
+        Commands::History {
+            last,
+            threshold,
+            fail_on_regression,
+            json,
+        } => {
+            ferris_proof_cli::commands::history::run(last, threshold, fail_on_regression, json)
+                .await
+        }
+        Commands::Badge { report, output } => {
+            ferris_proof_cli::commands::badge::run(report, output).await
+        }
+        Commands::Metrics { export, reset } => {
+            ferris_proof_cli::commands::metrics::run(export, reset).await
+        }
+        Commands::Trace { json, accept_drift } => {
+            ferris_proof_cli::commands::trace::run(json, accept_drift).await
+        }
+        Commands::Triage { report } => ferris_proof_cli::commands::triage::run(report).await,
+        Commands::Replay { violation_id } => {
+            ferris_proof_cli::commands::replay::run(violation_id).await
         }
-        Commands::Explain { error_code } => {
-            ferris_proof_cli::commands::explain::run(error_code).await
+        Commands::Gate => ferris_proof_cli::commands::gate::run().await,
+        Commands::VerifyAttestation { file, public_key } => {
+            ferris_proof_cli::commands::attestation::run(file, public_key).await
         }
+        Commands::Serve {
+            addr,
+            check_interval,
+        } => ferris_proof_cli::commands::serve::run(addr, check_interval).await,
+        Commands::Daemon { socket } => ferris_proof_cli::commands::daemon::run(socket).await,
         Commands::Cache { action } => ferris_proof_cli::commands::cache::run(action).await,
+        Commands::Assets { action } => ferris_proof_cli::commands::assets::run(action).await,
+        Commands::Artifacts { action } => ferris_proof_cli::commands::artifacts::run(action).await,
+        Commands::Plugins { action } => ferris_proof_cli::commands::plugins::run(action).await,
     };
 
     match result {
         Ok(exit_code) => process::exit(exit_code),
         Err(e) => {
             error!("Error: {}", e);
+            if let Some(error) = e
+                .chain()
+                .find_map(|cause| cause.downcast_ref::<ferris_proof_core::FerrisProofError>())
+            {
+                error!("Run '{}' for details", error.explain_command());
+            }
             process::exit(1);
         }
     }
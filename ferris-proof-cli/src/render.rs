@@ -0,0 +1,5 @@
+pub mod badge;
+pub mod github;
+pub mod snippet;
+pub mod unified;
+pub mod view;
@@ -0,0 +1,295 @@
+use ferris_proof_core::history::RunRecord;
+use ferris_proof_core::{Layer, Status, VerificationLevel};
+use serde_json::json;
+use std::fmt::Write;
+
+const LABEL: &str = "ferris-proof";
+const LEVEL_COLOR: &str = "#007ec6";
+const LABEL_COLOR: &str = "#555";
+
+/// README badge summarising the verification level and per-layer status of a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Badge {
+    pub level: Option<VerificationLevel>,
+    pub overall: Status,
+    pub layers: Vec<(Layer, Status)>,
+}
+
+impl Badge {
+    /// Summarise a recorded run, taking each layer's worst status across targets
+    pub fn from_record(record: &RunRecord, level: Option<VerificationLevel>) -> Self {
+        let layers = [
+            Layer::Formal,
+            Layer::TypeLevel,
+            Layer::PropertyBased,
+            Layer::Monitoring,
+            Layer::ApiSurface,
+        ]
+        .into_iter()
+        .filter_map(|layer| {
+            let from_targets = record
+                .targets
+                .iter()
+                .filter(|target| target.layer == layer)
+                .map(|target| target.status)
+                .max_by_key(|status| severity_rank(*status));
+            let from_summary = record
+                .layers
+                .iter()
+                .find(|summary| summary.layer == layer)
+                .map(|summary| {
+                    if summary.violations > 0 {
+                        Status::Warning
+                    } else {
+                        Status::Success
+                    }
+                });
+            from_targets.or(from_summary).map(|status| (layer, status))
+        })
+        .collect();
+
+        Self {
+            level: record.level.or(level),
+            overall: record.overall_status,
+            layers,
+        }
+    }
+
+    /// Message shown on the right-hand side, e.g. `standard | formal ✓ | property-based ✗`
+    pub fn message(&self) -> String {
+        let mut parts: Vec<String> = self.level.iter().map(level_name).collect();
+        parts.extend(
+            self.layers
+                .iter()
+                .map(|(layer, status)| format!("{} {}", layer_name(*layer), status_mark(*status))),
+        );
+        if parts.is_empty() {
+            parts.push(status_word(self.overall).to_string());
+        }
+        parts.join(" | ")
+    }
+
+    /// shields.io endpoint file, for `https://img.shields.io/endpoint?url=...`
+    pub fn to_endpoint_json(&self) -> serde_json::Value {
+        json!({
+            "schemaVersion": 1,
+            "label": LABEL,
+            "message": self.message(),
+            "color": status_color(self.overall),
+        })
+    }
+
+    /// Flat-style SVG with one segment for the level and one per layer
+    pub fn to_svg(&self) -> String {
+        let mut segments = vec![(LABEL.to_string(), LABEL_COLOR)];
+        segments.extend(
+            self.level
+                .iter()
+                .map(|level| (level_name(level), LEVEL_COLOR)),
+        );
+        segments.extend(self.layers.iter().map(|(layer, status)| {
+            (
+                format!("{} {}", layer_name(*layer), status_mark(*status)),
+                status_color(*status),
+            )
+        }));
+        if segments.len() == 1 {
+            segments.push((
+                status_word(self.overall).to_string(),
+                status_color(self.overall),
+            ));
+        }
+
+        let widths: Vec<usize> = segments.iter().map(|(text, _)| text_width(text)).collect();
+        let total: usize = widths.iter().sum();
+        let title = format!("{}: {}", LABEL, self.message());
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{total}" height="20" role="img" aria-label="{title}">"#,
+            title = escape_xml(&title)
+        );
+        let _ = writeln!(svg, "  <title>{}</title>", escape_xml(&title));
+        let _ = writeln!(
+            svg,
+            r##"  <linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>"##
+        );
+        let _ = writeln!(
+            svg,
+            r##"  <clipPath id="r"><rect width="{total}" height="20" rx="3" fill="#fff"/></clipPath>"##
+        );
+        let _ = writeln!(svg, r#"  <g clip-path="url(#r)">"#);
+        let mut x = 0;
+        for ((_, color), width) in segments.iter().zip(&widths) {
+            let _ = writeln!(
+                svg,
+                r#"    <rect x="{x}" width="{width}" height="20" fill="{color}"/>"#
+            );
+            x += width;
+        }
+        let _ = writeln!(
+            svg,
+            r#"    <rect width="{total}" height="20" fill="url(#s)"/>"#
+        );
+        let _ = writeln!(svg, "  </g>");
+        let _ = writeln!(
+            svg,
+            r##"  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">"##
+        );
+        let mut x = 0;
+        for ((text, _), width) in segments.iter().zip(&widths) {
+            let center = x + width / 2;
+            let text = escape_xml(text);
+            let _ = writeln!(
+                svg,
+                r##"    <text x="{center}" y="15" fill="#010101" fill-opacity=".3">{text}</text>"##
+            );
+            let _ = writeln!(svg, r#"    <text x="{center}" y="14">{text}</text>"#);
+            x += width;
+        }
+        let _ = writeln!(svg, "  </g>");
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn level_name(level: &VerificationLevel) -> String {
+    format!("{:?}", level).to_lowercase()
+}
+
+fn layer_name(layer: Layer) -> &'static str {
+    match layer {
+        Layer::Formal => "formal",
+        Layer::TypeLevel => "type-level",
+        Layer::PropertyBased => "property-based",
+        Layer::Monitoring => "monitoring",
+        Layer::ApiSurface => "api-surface",
+    }
+}
+
+fn severity_rank(status: Status) -> u8 {
+    match status {
+        Status::Skipped => 0,
+        Status::Cached => 1,
+        Status::Success => 2,
+        Status::Warning => 3,
+        Status::Error => 4,
+    }
+}
+
+fn status_mark(status: Status) -> &'static str {
+    match status {
+        Status::Success => "✓",
+        Status::Warning => "!",
+        Status::Error => "✗",
+        Status::Skipped => "-",
+        Status::Cached => "✓",
+    }
+}
+
+fn status_word(status: Status) -> &'static str {
+    match status {
+        Status::Success => "passing",
+        Status::Warning => "warnings",
+        Status::Error => "failing",
+        Status::Skipped => "skipped",
+        Status::Cached => "cached",
+    }
+}
+
+fn status_color(status: Status) -> &'static str {
+    match status {
+        Status::Success => "#4c1",
+        Status::Warning => "#dfb317",
+        Status::Error => "#e05d44",
+        Status::Skipped => "#9f9f9f",
+        Status::Cached => "#97ca00",
+    }
+}
+
+/// Approximate rendered width of 11px Verdana plus padding
+fn text_width(text: &str) -> usize {
+    text.chars().count() * 7 + 10
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferris_proof_core::history::{LayerRecord, TargetRecord};
+    use std::time::Duration;
+
+    fn record() -> RunRecord {
+        RunRecord {
+            id: "run".to_string(),
+            timestamp: chrono::Utc::now(),
+            git_commit: None,
+            level: Some(VerificationLevel::Strict),
+            overall_status: Status::Error,
+            violations: Vec::new(),
+            total_time: Duration::ZERO,
+            cache_hit_rate: 0.0,
+            layers: vec![LayerRecord {
+                layer: Layer::TypeLevel,
+                duration: Duration::ZERO,
+                violations: 0,
+                retries: 0,
+                flaky_pass: false,
+            }],
+            targets: vec![
+                TargetRecord {
+                    module: "a".to_string(),
+                    layer: Layer::PropertyBased,
+                    status: Status::Success,
+                },
+                TargetRecord {
+                    module: "b".to_string(),
+                    layer: Layer::PropertyBased,
+                    status: Status::Error,
+                },
+            ],
+            api_surface: None,
+            suppressions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_layers_take_worst_target_status() {
+        let badge = Badge::from_record(&record(), None);
+
+        assert_eq!(
+            badge.layers,
+            vec![
+                (Layer::TypeLevel, Status::Success),
+                (Layer::PropertyBased, Status::Error)
+            ]
+        );
+        assert_eq!(badge.message(), "strict | type-level ✓ | property-based ✗");
+    }
+
+    #[test]
+    fn test_endpoint_json_follows_shields_schema() {
+        let endpoint = Badge::from_record(&record(), None).to_endpoint_json();
+
+        assert_eq!(endpoint["schemaVersion"], 1);
+        assert_eq!(endpoint["label"], "ferris-proof");
+        assert_eq!(endpoint["color"], "#e05d44");
+    }
+
+    #[test]
+    fn test_svg_has_a_segment_per_layer() {
+        let svg = Badge::from_record(&record(), None).to_svg();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">strict</text>"));
+        assert!(svg.contains(">property-based ✗</text>"));
+        assert_eq!(svg.matches(r##"fill="#e05d44""##).count(), 1);
+    }
+}
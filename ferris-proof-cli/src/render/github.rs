@@ -0,0 +1,173 @@
+use ferris_proof_core::types::{Severity, Violation};
+use ferris_proof_core::{Status, VerificationResult};
+use std::fmt::Write;
+
+/// GitHub Actions workflow command annotating a violation inline on the PR
+///
+/// e.g. `::error file=src/lib.rs,line=3,col=5,title=FP-MD-002::message`
+pub fn annotation(violation: &Violation) -> String {
+    let command = match violation.severity {
+        Severity::Critical | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "notice",
+    };
+
+    let location = &violation.location;
+    let mut properties = vec![format!(
+        "file={}",
+        escape_property(&location.file.display().to_string())
+    )];
+    match &location.span {
+        Some(span) => {
+            properties.push(format!("line={}", span.start.line));
+            properties.push(format!("endLine={}", span.end.line.max(span.start.line)));
+            // GitHub only honours columns on single-line annotations
+            if span.end.line <= span.start.line {
+                properties.push(format!("col={}", span.start.column));
+                properties.push(format!("endColumn={}", span.end.column));
+            }
+        }
+        None => {
+            if let Some(line) = location.line {
+                properties.push(format!("line={}", line));
+                if let Some(column) = location.column {
+                    properties.push(format!("col={}", column));
+                }
+            }
+        }
+    }
+    properties.push(format!("title={}", escape_property(&violation.id)));
+
+    let mut message = violation.message.clone();
+    if let Some(suggestion) = &violation.suggestion {
+        let _ = write!(message, "\nhelp: {}", suggestion);
+    }
+
+    format!(
+        "::{} {}::{}",
+        command,
+        properties.join(","),
+        escape_data(&message)
+    )
+}
+
+/// Markdown job summary with one table row per violation
+pub fn job_summary(result: &VerificationResult) -> String {
+    let violations: Vec<&Violation> = result
+        .layer_results
+        .values()
+        .flat_map(|l| l.violations.iter())
+        .collect();
+
+    let status = match result.overall_status {
+        Status::Success => "✅ Success",
+        Status::Warning => "⚠️ Warning",
+        Status::Error => "❌ Error",
+        Status::Skipped => "⏭️ Skipped",
+        Status::Cached => "♻️ Cached",
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "## FerrisProof verification\n");
+    let _ = writeln!(
+        out,
+        "**Status:** {} · {} violation(s) · {:.2}s\n",
+        status,
+        violations.len(),
+        result.metrics.total_time.as_secs_f64()
+    );
+    if let Some(targets) = result.target_summary() {
+        let _ = writeln!(out, "**Targets:** {}\n", targets);
+    }
+    if let Some(budget) = &result.budget {
+        let _ = writeln!(out, "**Violation budget:** {}\n", budget);
+    }
+
+    if violations.is_empty() {
+        let _ = writeln!(out, "No violations found.");
+        return out;
+    }
+
+    let _ = writeln!(out, "| Severity | ID | Location | Message |");
+    let _ = writeln!(out, "| --- | --- | --- | --- |");
+    for violation in violations {
+        let location = &violation.location;
+        let mut position = location.file.display().to_string();
+        if let Some(line) = location
+            .span
+            .as_ref()
+            .map(|span| span.start.line)
+            .or(location.line)
+        {
+            let _ = write!(position, ":{}", line);
+        }
+        let _ = writeln!(
+            out,
+            "| {:?} | `{}` | `{}` | {} |",
+            violation.severity,
+            violation.id,
+            position,
+            escape_cell(&violation.message)
+        );
+    }
+
+    out
+}
+
+/// Escape a workflow command message
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Keep a message on one table row
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferris_proof_core::types::Location;
+
+    fn violation(severity: Severity, location: Location) -> Violation {
+        Violation {
+            id: "FP-MD-002".to_string(),
+            severity,
+            location,
+            message: "Dependency `rand` uses a wildcard version: 100% | unpinned".to_string(),
+            suggestion: Some("Pin a semver requirement".to_string()),
+            rule: "metadata.wildcard_dependency".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_annotation_escapes_message_and_properties() {
+        assert_eq!(
+            annotation(&violation(
+                Severity::Warning,
+                Location::span("Cargo.toml", 5, 8, 5, 11)
+            )),
+            "::warning file=Cargo.toml,line=5,endLine=5,col=8,endColumn=11,title=FP-MD-002::\
+             Dependency `rand` uses a wildcard version: 100%25 | unpinned%0Ahelp: Pin a semver requirement"
+        );
+        let unspanned = Location {
+            line: Some(3),
+            column: Some(1),
+            ..Location::file("a,b.rs")
+        };
+        assert!(annotation(&violation(Severity::Error, unspanned))
+            .starts_with("::error file=a%2Cb.rs,line=3,col=1,title="));
+        assert!(
+            annotation(&violation(Severity::Info, Location::file("Cargo.toml")))
+                .starts_with("::notice file=Cargo.toml,title=FP-MD-002::")
+        );
+    }
+}
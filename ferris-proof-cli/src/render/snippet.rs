@@ -0,0 +1,205 @@
+use colored::Colorize;
+use ferris_proof_core::types::{Severity, Violation};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Source lines shown before and after the highlighted range
+const CONTEXT_LINES: u32 = 2;
+
+/// Render a violation like a rustc diagnostic, reading the source from disk
+///
+/// Relative violation paths are resolved against `project_root`.
+pub fn render_for_project(project_root: &Path, violation: &Violation) -> String {
+    let file = &violation.location.file;
+    let path = if file.is_absolute() {
+        file.to_path_buf()
+    } else {
+        project_root.join(file)
+    };
+    let source = std::fs::read_to_string(path).ok();
+
+    render(violation, source.as_deref())
+}
+
+/// Render a violation like a rustc diagnostic
+///
+/// Without `source`, or without a line number, only the header, location
+/// and suggestion are shown.
+pub fn render(violation: &Violation, source: Option<&str>) -> String {
+    let mut out = String::new();
+    let location = &violation.location;
+
+    let label = match violation.severity {
+        Severity::Critical => format!("critical[{}]", violation.id).red().bold(),
+        Severity::Error => format!("error[{}]", violation.id).red().bold(),
+        Severity::Warning => format!("warning[{}]", violation.id).yellow().bold(),
+        Severity::Info => format!("info[{}]", violation.id).blue().bold(),
+    };
+    let _ = writeln!(out, "{}: {}", label, violation.message.bold());
+
+    let mut position = location.file.display().to_string();
+    if let Some(line) = location.line {
+        let _ = write!(position, ":{}", line);
+        if let Some(column) = location.column {
+            let _ = write!(position, ":{}", column);
+        }
+    }
+
+    let lines: Vec<&str> = source.map(|s| s.lines().collect()).unwrap_or_default();
+    let range = highlighted_range(violation).filter(|_| !lines.is_empty());
+
+    let last_shown = range.map_or(0, |(_, _, end_line, _)| {
+        (end_line + CONTEXT_LINES).min(lines.len() as u32)
+    });
+    let width = last_shown.max(1).to_string().len();
+    let gutter = " ".repeat(width);
+    let bar = "|".blue().bold();
+
+    let _ = writeln!(out, "{}{} {}", gutter, "-->".blue().bold(), position);
+
+    if let Some((start_line, start_column, end_line, end_column)) = range {
+        let first_shown = start_line.saturating_sub(CONTEXT_LINES).max(1);
+        let _ = writeln!(out, "{} {}", gutter, bar);
+
+        for number in first_shown..=last_shown {
+            let text = lines[number as usize - 1];
+            let _ = writeln!(
+                out,
+                "{} {} {}",
+                format!("{:>width$}", number, width = width).blue().bold(),
+                bar,
+                text
+            );
+
+            if number < start_line || number > end_line {
+                continue;
+            }
+            let from = if number == start_line {
+                start_column
+            } else {
+                1
+            };
+            let to = if number == end_line {
+                end_column
+            } else {
+                text.chars().count() as u32 + 1
+            };
+            let _ = writeln!(
+                out,
+                "{} {} {}",
+                gutter,
+                bar,
+                carets(text, from, to, violation)
+            );
+        }
+
+        let _ = writeln!(out, "{} {}", gutter, bar);
+    }
+
+    if let Some(suggestion) = &violation.suggestion {
+        let _ = writeln!(out, "{} {} help: {}", gutter, "=".blue().bold(), suggestion);
+    }
+
+    out
+}
+
+/// Note line pointing at a violation's deep link, appended to its snippet
+pub fn link_note(link: &str) -> String {
+    format!("  {} link: {}\n", "=".blue().bold(), link)
+}
+
+/// Start line, start column, end line and exclusive end column to underline
+fn highlighted_range(violation: &Violation) -> Option<(u32, u32, u32, u32)> {
+    let location = &violation.location;
+
+    if let Some(span) = &location.span {
+        let end_line = span.end.line.max(span.start.line);
+        return Some((
+            span.start.line,
+            span.start.column,
+            end_line,
+            span.end.column,
+        ));
+    }
+
+    let line = location.line?;
+    Some(match location.column {
+        Some(column) => (line, column, line, column + 1),
+        // Underline the whole line when only the line is known
+        None => (line, 1, line, u32::MAX),
+    })
+}
+
+/// Caret marker line, preserving tabs so the carets stay aligned
+fn carets(text: &str, from: u32, to: u32, violation: &Violation) -> String {
+    let from = from.max(1) as usize;
+    let to = (to as usize).min(text.chars().count() + 1).max(from + 1);
+
+    let indent: String = text
+        .chars()
+        .chain(std::iter::repeat(' '))
+        .take(from - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let marker = "^".repeat(to - from);
+
+    let marker = match violation.severity {
+        Severity::Critical | Severity::Error => marker.red().bold(),
+        Severity::Warning => marker.yellow().bold(),
+        Severity::Info => marker.blue().bold(),
+    };
+    format!("{}{}", indent, marker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferris_proof_core::types::Location;
+
+    fn violation(location: Location) -> Violation {
+        Violation {
+            id: "FP-MD-002".to_string(),
+            severity: Severity::Warning,
+            location,
+            message: "Dependency `rand` uses a wildcard version".to_string(),
+            suggestion: Some("Pin a semver requirement".to_string()),
+            rule: "metadata.wildcard_dependency".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_renders_span_with_context() {
+        colored::control::set_override(false);
+        let source = "[package]\nname = \"demo\"\n\n[dependencies]\nrand = \"*\"\n";
+        let rendered = render(
+            &violation(Location::span("Cargo.toml", 5, 8, 5, 11)),
+            Some(source),
+        );
+
+        assert_eq!(
+            rendered,
+            "warning[FP-MD-002]: Dependency `rand` uses a wildcard version\n\
+             \x20--> Cargo.toml:5:8\n\
+             \x20 |\n\
+             3 | \n\
+             4 | [dependencies]\n\
+             5 | rand = \"*\"\n\
+             \x20 |        ^^^\n\
+             \x20 |\n\
+             \x20 = help: Pin a semver requirement\n"
+        );
+    }
+
+    #[test]
+    fn test_renders_without_source() {
+        colored::control::set_override(false);
+        let rendered = render(&violation(Location::file("Cargo.toml")), None);
+
+        assert_eq!(
+            rendered,
+            "warning[FP-MD-002]: Dependency `rand` uses a wildcard version\n\
+             \x20--> Cargo.toml\n\
+             \x20 = help: Pin a semver requirement\n"
+        );
+    }
+}
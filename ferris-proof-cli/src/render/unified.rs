@@ -0,0 +1,153 @@
+//! Line-based unified diffs, as printed by `upgrade --dry-run`
+
+use colored::Colorize;
+
+/// Unchanged lines shown around each change
+pub const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Unified diff from `old` to `new`, or an empty string if they are equal
+///
+/// An empty `old` is labelled `/dev/null`, as for a file being created.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = line_ops(&old_lines, &new_lines);
+    if ops.iter().all(|(op, _, _)| *op == Op::Equal) {
+        return String::new();
+    }
+
+    let old_label = if old.is_empty() {
+        "/dev/null".to_string()
+    } else {
+        format!("a/{}", old_label)
+    };
+    let mut out = format!("--- {}\n+++ b/{}\n", old_label, new_label);
+    for (start, end) in hunks(&ops) {
+        let hunk = &ops[start..end];
+        let old_len = hunk.iter().filter(|(op, _, _)| *op != Op::Insert).count();
+        let new_len = hunk.iter().filter(|(op, _, _)| *op != Op::Delete).count();
+        let (_, old_pos, new_pos) = hunk[0];
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_pos + usize::from(old_len > 0),
+            old_len,
+            new_pos + usize::from(new_len > 0),
+            new_len
+        ));
+        for (op, old_pos, new_pos) in hunk {
+            let line = match op {
+                Op::Equal => format!(" {}", old_lines[*old_pos]),
+                Op::Delete => format!("-{}", old_lines[*old_pos]),
+                Op::Insert => format!("+{}", new_lines[*new_pos]),
+            };
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Color added lines green, removed lines red and hunk headers cyan
+pub fn colorize(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            if line.starts_with("+++") || line.starts_with("---") {
+                line.bold().to_string()
+            } else if line.starts_with('+') {
+                line.green().to_string()
+            } else if line.starts_with('-') {
+                line.red().to_string()
+            } else if line.starts_with("@@") {
+                line.cyan().to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Edit script from a longest common subsequence, with the old and new
+/// line index each operation starts at
+fn line_ops(old: &[&str], new: &[&str]) -> Vec<(Op, usize, usize)> {
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((Op::Equal, i, j));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push((Op::Delete, i, j));
+            i += 1;
+        } else {
+            ops.push((Op::Insert, i, j));
+            j += 1;
+        }
+    }
+    ops
+}
+
+/// Ranges of `ops` to print, each change padded with context and
+/// overlapping ranges merged
+fn hunks(ops: &[(Op, usize, usize)]) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (index, _) in ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _, _))| *op != Op::Equal)
+    {
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + 1 + CONTEXT_LINES).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hunks_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\nb\nC\nd\ne\nf\ng\nh\ni\nj\nk\n";
+
+        assert_eq!(
+            unified_diff(old, new, "x.toml", "x.toml"),
+            "--- a/x.toml\n+++ b/x.toml\n\
+             @@ -1,6 +1,6 @@\n a\n b\n-c\n+C\n d\n e\n f\n\
+             @@ -8,3 +8,4 @@\n h\n i\n j\n+k\n"
+        );
+        assert_eq!(unified_diff(old, old, "x.toml", "x.toml"), "");
+    }
+
+    #[test]
+    fn test_created_file() {
+        assert_eq!(
+            unified_diff("", "one\ntwo\n", "new.rs", "new.rs"),
+            "--- /dev/null\n+++ b/new.rs\n@@ -0,0 +1,2 @@\n+one\n+two\n"
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use clap::ValueEnum;
+use ferris_proof_core::cache::{module_path_of, wildcard_match};
+use ferris_proof_core::types::{Severity, Violation};
+use ferris_proof_core::{Layer, VerificationResult};
+use std::collections::BTreeMap;
+use std::mem::discriminant;
+
+/// Violation filter from `--filter key=value`, e.g. `severity=error` or `rule=FP-VR-*`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationFilter {
+    Severity(Severity),
+    /// Pattern matched against the violation id and rule name
+    Rule(String),
+    Layer(Layer),
+    /// Module path pattern, e.g. `payment::*`
+    Module(String),
+}
+
+impl std::str::FromStr for ViolationFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected key=value, got '{}'", s))?;
+        match key.trim() {
+            "severity" => parse_severity(value.trim()).map(ViolationFilter::Severity),
+            "rule" => Ok(ViolationFilter::Rule(value.trim().to_string())),
+            "layer" => value.trim().parse().map(ViolationFilter::Layer),
+            "module" => Ok(ViolationFilter::Module(value.trim().to_string())),
+            other => Err(format!(
+                "Unknown filter '{}'. Valid filters: severity, rule, layer, module",
+                other
+            )),
+        }
+    }
+}
+
+impl ViolationFilter {
+    fn matches(&self, layer: Layer, violation: &Violation) -> bool {
+        match self {
+            ViolationFilter::Severity(severity) => violation.severity == *severity,
+            ViolationFilter::Rule(pattern) => {
+                wildcard_match(pattern, &violation.id) || wildcard_match(pattern, &violation.rule)
+            }
+            ViolationFilter::Layer(expected) => layer == *expected,
+            ViolationFilter::Module(pattern) => wildcard_match(pattern, &module_of(violation)),
+        }
+    }
+}
+
+/// How `check` groups the violations it prints
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    Module,
+    Layer,
+    Rule,
+}
+
+/// Which violations `check` prints, and under which headings
+///
+/// Filters of the same kind are alternatives, while filters of different
+/// kinds must all match. Reports and exit codes are unaffected.
+#[derive(Debug, Clone, Default)]
+pub struct ViolationView {
+    pub filters: Vec<ViolationFilter>,
+    pub group_by: Option<GroupBy>,
+}
+
+impl ViolationView {
+    pub fn matches(&self, layer: Layer, violation: &Violation) -> bool {
+        let mut kinds = Vec::new();
+        for filter in &self.filters {
+            if !kinds.contains(&discriminant(filter)) {
+                kinds.push(discriminant(filter));
+            }
+        }
+        kinds.iter().all(|kind| {
+            self.filters
+                .iter()
+                .filter(|filter| discriminant(*filter) == *kind)
+                .any(|filter| filter.matches(layer, violation))
+        })
+    }
+
+    /// Matching violations grouped by key, in key order
+    ///
+    /// Without `group_by` everything is in a single group with an empty key.
+    pub fn groups<'a>(&self, result: &'a VerificationResult) -> Vec<(String, Vec<&'a Violation>)> {
+        let mut groups: BTreeMap<String, Vec<&Violation>> = BTreeMap::new();
+        for (layer, layer_result) in &result.layer_results {
+            for violation in &layer_result.violations {
+                if !self.matches(*layer, violation) {
+                    continue;
+                }
+                let key = match self.group_by {
+                    None => String::new(),
+                    Some(GroupBy::Module) => module_of(violation),
+                    Some(GroupBy::Layer) => format!("{:?}", layer),
+                    Some(GroupBy::Rule) => violation.id.clone(),
+                };
+                groups.entry(key).or_default().push(violation);
+            }
+        }
+        groups.into_iter().collect()
+    }
+}
+
+fn module_of(violation: &Violation) -> String {
+    module_path_of(&violation.location.file.to_string_lossy())
+}
+
+fn parse_severity(value: &str) -> Result<Severity, String> {
+    match value.to_lowercase().as_str() {
+        "critical" => Ok(Severity::Critical),
+        "error" => Ok(Severity::Error),
+        "warning" => Ok(Severity::Warning),
+        "info" => Ok(Severity::Info),
+        _ => Err(format!(
+            "Invalid severity: {}. Valid options: critical, error, warning, info",
+            value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferris_proof_core::types::{Location, VerificationMetrics};
+    use ferris_proof_core::{LayerResult, Status};
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn violation(id: &str, severity: Severity, file: &str) -> Violation {
+        Violation {
+            id: id.to_string(),
+            severity,
+            location: Location::file(file),
+            message: String::new(),
+            suggestion: None,
+            rule: "rule".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_filters_and_groups_violations() {
+        let layer_results = HashMap::from([(
+            Layer::PropertyBased,
+            LayerResult {
+                layer: Layer::PropertyBased,
+                status: Status::Error,
+                violations: vec![
+                    violation("FP-VR-001", Severity::Error, "src/payment/refund.rs"),
+                    violation("FP-VR-001", Severity::Warning, "src/crypto/aes.rs"),
+                    violation("FP-MD-001", Severity::Critical, "src/payment/charge.rs"),
+                ],
+                execution_time: Duration::ZERO,
+                tool_outputs: Vec::new(),
+            },
+        )]);
+        let result = VerificationResult {
+            overall_status: Status::Error,
+            layer_results,
+            metrics: VerificationMetrics {
+                total_time: Duration::ZERO,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+            artifacts: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            skipped_targets: Vec::new(),
+            gated_layers: Vec::new(),
+            unavailable_layers: Vec::new(),
+            skip_reasons: HashMap::new(),
+            spec_changes: Vec::new(),
+            traceability: None,
+            budget: None,
+            seed: None,
+            environment: None,
+            target_statuses: BTreeMap::new(),
+            interrupted: false,
+            project_root: None,
+            link_template: None,
+            plugin_breakdowns: Vec::new(),
+            completed: Vec::new(),
+            api_surface: None,
+        };
+
+        let view = |filters: &[&str], group_by| ViolationView {
+            filters: filters.iter().map(|f| f.parse().unwrap()).collect(),
+            group_by,
+        };
+        let count = |view: ViolationView| {
+            view.groups(&result)
+                .iter()
+                .map(|(_, violations)| violations.len())
+                .sum::<usize>()
+        };
+
+        assert_eq!(count(view(&["rule=FP-VR-*"], None)), 2);
+        assert_eq!(
+            count(view(&["severity=error", "severity=critical"], None)),
+            2
+        );
+        assert_eq!(
+            count(view(&["severity=error", "module=crypto::*"], None)),
+            0
+        );
+
+        let groups = view(&[], Some(GroupBy::Module)).groups(&result);
+        let keys: Vec<_> = groups.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["crypto::aes", "payment::charge", "payment::refund"]
+        );
+
+        assert!("owner=me".parse::<ViolationFilter>().is_err());
+        assert!("severity=fatal".parse::<ViolationFilter>().is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use ferris_proof_cli::commands::{config, explain, init};
+use ferris_proof_cli::commands::{config, explain, init, quick, upgrade};
 use ferris_proof_core::VerificationLevel;
 use std::fs;
 use tempfile::TempDir;
@@ -15,7 +15,7 @@ mod init_command_tests {
 
         std::env::set_current_dir(&temp_path).unwrap();
 
-        let result = init::run(VerificationLevel::Standard, false, None).await;
+        let result = init::run(VerificationLevel::Standard, false, None, false).await;
 
         // Restore directory before checking result to avoid issues with temp dir cleanup
         std::env::set_current_dir(original_dir).unwrap();
@@ -40,7 +40,7 @@ mod init_command_tests {
 
         std::env::set_current_dir(&temp_path).unwrap();
 
-        let result = init::run(VerificationLevel::Minimal, false, None).await;
+        let result = init::run(VerificationLevel::Minimal, false, None, false).await;
 
         // Check basic directories exist
         assert!(temp_path.join("specs").exists());
@@ -65,7 +65,7 @@ mod init_command_tests {
 
         std::env::set_current_dir(&temp_path).unwrap();
 
-        let result = init::run(VerificationLevel::Standard, false, None).await;
+        let result = init::run(VerificationLevel::Standard, false, None, false).await;
 
         // Check basic directories exist
         assert!(temp_path.join("specs").exists());
@@ -90,7 +90,7 @@ mod init_command_tests {
 
         std::env::set_current_dir(&temp_path).unwrap();
 
-        let result = init::run(VerificationLevel::Formal, false, None).await;
+        let result = init::run(VerificationLevel::Formal, false, None, false).await;
 
         // Check all directories for formal level exist
         assert!(temp_path.join("specs").exists());
@@ -122,6 +122,7 @@ mod init_command_tests {
             VerificationLevel::Standard,
             false,
             Some("standard".to_string()),
+            false,
         )
         .await;
 
@@ -143,6 +144,46 @@ mod init_command_tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0);
     }
+
+    #[tokio::test]
+    async fn test_init_merges_into_existing_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().to_path_buf();
+        let original_dir = std::env::current_dir().unwrap();
+        let existing = r#"# Team settings
+[profile]
+level = "standard"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+
+[tools.tla_plus]
+tlc_path = "/opt/tla/tlc" # pinned release
+
+[modules."ffi::*"]
+level = "strict"
+"#;
+        fs::write(temp_path.join("ferrisproof.toml"), existing).unwrap();
+
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let merged = init::run(VerificationLevel::Formal, false, None, false).await;
+        let merged_content = fs::read_to_string(temp_path.join("ferrisproof.toml")).unwrap();
+        let forced = init::run(VerificationLevel::Formal, false, None, true).await;
+        let forced_content = fs::read_to_string(temp_path.join("ferrisproof.toml")).unwrap();
+
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(merged.unwrap(), 0);
+        assert!(merged_content.starts_with(existing));
+        assert!(merged_content.contains("[thresholds]"));
+        let config: ferris_proof_config::Config = toml::from_str(&merged_content).unwrap();
+        assert_eq!(config.profile.level, VerificationLevel::Standard);
+        assert_eq!(config.modules.len(), 1);
+
+        assert_eq!(forced.unwrap(), 0);
+        assert!(forced_content.contains("level = \"formal\""));
+        assert!(!forced_content.contains("/opt/tla/tlc"));
+    }
 }
 
 #[cfg(test)]
@@ -174,7 +215,7 @@ mod config_command_tests {
         std::env::set_current_dir(&temp_path).unwrap();
 
         // First create a config file
-        let init_result = init::run(VerificationLevel::Standard, false, None).await;
+        let init_result = init::run(VerificationLevel::Standard, false, None, false).await;
         assert!(init_result.is_ok());
         assert_eq!(init_result.unwrap(), 0);
 
@@ -198,7 +239,7 @@ mod config_command_tests {
         std::env::set_current_dir(&temp_path).unwrap();
 
         // First create a config file
-        let init_result = init::run(VerificationLevel::Standard, false, None).await;
+        let init_result = init::run(VerificationLevel::Standard, false, None, false).await;
         assert!(init_result.is_ok());
         assert_eq!(init_result.unwrap(), 0);
 
@@ -241,7 +282,7 @@ mod config_command_tests {
         std::env::set_current_dir(&temp_path).unwrap();
 
         // Create a config file
-        let init_result = init::run(VerificationLevel::Standard, false, None).await;
+        let init_result = init::run(VerificationLevel::Standard, false, None, false).await;
 
         // Create a test file
         let test_file = temp_path.join("src").join("main.rs");
@@ -268,7 +309,7 @@ mod explain_command_tests {
 
     #[tokio::test]
     async fn test_explain_command_with_known_error_code() {
-        let result = explain::run("FP-CF-001".to_string()).await;
+        let result = explain::run(Some("FP-CF-001".to_string()), false, None).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0);
@@ -276,7 +317,7 @@ mod explain_command_tests {
 
     #[tokio::test]
     async fn test_explain_command_with_unknown_error_code() {
-        let result = explain::run("FP-XX-999".to_string()).await;
+        let result = explain::run(Some("FP-XX-999".to_string()), false, None).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 1); // Should return 1 for unknown codes
@@ -284,7 +325,7 @@ mod explain_command_tests {
 
     #[tokio::test]
     async fn test_explain_command_with_configuration_error() {
-        let result = explain::run("FP-CF-002".to_string()).await;
+        let result = explain::run(Some("FP-CF-002".to_string()), false, None).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0);
@@ -292,7 +333,7 @@ mod explain_command_tests {
 
     #[tokio::test]
     async fn test_explain_command_with_verification_error() {
-        let result = explain::run("FP-VR-001".to_string()).await;
+        let result = explain::run(Some("FP-VR-001".to_string()), false, None).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0);
@@ -300,7 +341,7 @@ mod explain_command_tests {
 
     #[tokio::test]
     async fn test_explain_command_with_tool_error() {
-        let result = explain::run("FP-TL-001".to_string()).await;
+        let result = explain::run(Some("FP-TL-001".to_string()), false, None).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0);
@@ -308,7 +349,7 @@ mod explain_command_tests {
 
     #[tokio::test]
     async fn test_explain_command_with_io_error() {
-        let result = explain::run("FP-IO-001".to_string()).await;
+        let result = explain::run(Some("FP-IO-001".to_string()), false, None).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0);
@@ -316,7 +357,7 @@ mod explain_command_tests {
 
     #[tokio::test]
     async fn test_explain_command_with_empty_code() {
-        let result = explain::run("".to_string()).await;
+        let result = explain::run(Some("".to_string()), false, None).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 1); // Should return 1 for empty/invalid codes
@@ -324,7 +365,7 @@ mod explain_command_tests {
 
     #[tokio::test]
     async fn test_explain_command_with_partial_code() {
-        let result = explain::run("FP-CF".to_string()).await;
+        let result = explain::run(Some("FP-CF".to_string()), false, None).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 1); // Should return 1 for partial codes
@@ -344,7 +385,7 @@ mod integration_tests {
         std::env::set_current_dir(&temp_path).unwrap();
 
         // Initialize project
-        let init_result = init::run(VerificationLevel::Strict, false, None).await;
+        let init_result = init::run(VerificationLevel::Strict, false, None, false).await;
         assert!(init_result.is_ok());
         assert_eq!(init_result.unwrap(), 0);
 
@@ -379,7 +420,7 @@ mod integration_tests {
 
             std::env::set_current_dir(&temp_path).unwrap();
 
-            let result = init::run(level, false, None).await;
+            let result = init::run(level, false, None, false).await;
 
             // Verify config contains correct level while still in temp directory
             let config_path = temp_path.join("ferrisproof.toml");
@@ -398,3 +439,226 @@ mod integration_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod upgrade_command_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_upgrade_reports_gaps_and_updates_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().to_path_buf();
+        let original_dir = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let init_result = init::run(VerificationLevel::Minimal, false, None, false).await;
+        fs::create_dir_all(temp_path.join("tests")).unwrap();
+        fs::write(
+            temp_path.join("tests/roundtrip.rs"),
+            "proptest! { #[test] fn roundtrip(x in 0..10u8) {} }\n",
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(temp_path.join("ferrisproof.toml")).unwrap();
+        let config: ferris_proof_config::Config = toml::from_str(&content).unwrap();
+        let gaps = upgrade::analyze_gaps(&temp_path, &config, VerificationLevel::Strict);
+
+        let upgrade_result = upgrade::run(VerificationLevel::Strict, false, false, None).await;
+
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(init_result.is_ok());
+        assert_eq!(upgrade_result.unwrap(), 0);
+
+        let property_gap = gaps
+            .iter()
+            .find(|g| g.technique == ferris_proof_core::Technique::PropertyTests)
+            .unwrap();
+        assert_eq!(property_gap.effort, upgrade::Effort::Low);
+        assert!(property_gap.scaffolding.is_empty());
+        assert!(gaps
+            .iter()
+            .any(|g| g.technique == ferris_proof_core::Technique::SessionTypes));
+
+        let updated = fs::read_to_string(temp_path.join("ferrisproof.toml")).unwrap();
+        assert!(updated.contains("level = \"strict\""));
+        assert!(updated.contains("SessionTypes"));
+        assert!(temp_path
+            .join("specs/session-types/example_protocol.rs")
+            .exists());
+    }
+
+    #[test]
+    fn test_changeset_diffs_config_and_scaffolding() {
+        let temp_dir = TempDir::new().unwrap();
+        let content = "# Project policy\n[profile]\nlevel = \"minimal\" # start small\nenforcement = \"warning\"\nenabled_techniques = [\"TypeSafety\"]\n";
+        let config: ferris_proof_config::Config = toml::from_str(content).unwrap();
+        let gaps = upgrade::analyze_gaps(temp_dir.path(), &config, VerificationLevel::Standard);
+
+        let changeset = upgrade::Changeset::new(
+            temp_dir.path(),
+            content,
+            &config,
+            VerificationLevel::Standard,
+            &gaps,
+        )
+        .unwrap();
+
+        assert!(changeset.config.diff.contains(" # Project policy\n"));
+        assert!(changeset
+            .config
+            .diff
+            .contains("-level = \"minimal\" # start small\n+level = \"standard\" # start small\n"));
+        assert!(changeset
+            .config
+            .diff
+            .contains("+enabled_techniques = [\"TypeSafety\", \"PropertyTests\"]"));
+        assert_eq!(
+            changeset.directories,
+            vec![
+                std::path::PathBuf::from("tests"),
+                std::path::PathBuf::from("tests/property")
+            ]
+        );
+        assert!(changeset.files[0]
+            .diff
+            .starts_with("--- /dev/null\n+++ b/tests/property/example_properties.rs\n"));
+        assert_eq!(
+            changeset.layers,
+            vec![ferris_proof_core::Layer::PropertyBased]
+        );
+        assert!(!temp_dir.path().join("tests").exists());
+    }
+}
+
+#[cfg(test)]
+mod quick_command_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_quick_runs_without_config_and_writes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_path = temp_dir.path().to_path_buf();
+        let original_dir = std::env::current_dir().unwrap();
+
+        fs::create_dir_all(temp_path.join("src")).unwrap();
+        fs::write(
+            temp_path.join("src/lib.rs"),
+            "pub fn answer() -> u8 { 42 }\n",
+        )
+        .unwrap();
+
+        std::env::set_current_dir(&temp_path).unwrap();
+
+        let result = quick::run(false, None).await;
+
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(result.unwrap(), 0);
+        let entries: Vec<_> = fs::read_dir(&temp_path)
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(entries, vec![std::ffi::OsString::from("src")]);
+    }
+
+    #[tokio::test]
+    async fn test_quick_engine_runs_the_chosen_layers() {
+        use ferris_proof_core::verification::Target;
+        use ferris_proof_core::Technique;
+
+        let temp_dir = TempDir::new().unwrap();
+        let lib = temp_dir.path().join("lib.rs");
+        fs::write(&lib, "pub fn answer() -> u8 { 42 }\n").unwrap();
+
+        let techniques = [Technique::TypeSafety, Technique::PropertyTests];
+        let result = quick::engine(&techniques, false, 7)
+            .unwrap()
+            .verify(&[Target::RustFile(lib)])
+            .await
+            .unwrap();
+
+        for technique in &techniques {
+            assert!(
+                result.layer_results.contains_key(&technique.layer()),
+                "no {:?} result",
+                technique.layer()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod check_command_tests {
+    use super::*;
+    use ferris_proof_cli::commands::check;
+
+    #[test]
+    fn test_module_patterns_resolve_against_module_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/crypto")).unwrap();
+        fs::create_dir_all(root.join("src/payment")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"shop\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/lib.rs"), "mod crypto;\nmod payment;\n").unwrap();
+        fs::write(root.join("src/crypto/mod.rs"), "mod aes;\nmod rsa;\n").unwrap();
+        fs::write(root.join("src/crypto/aes.rs"), "").unwrap();
+        fs::write(root.join("src/crypto/rsa.rs"), "").unwrap();
+        fs::write(root.join("src/payment/mod.rs"), "mod ledger;\n").unwrap();
+        fs::write(root.join("src/payment/ledger.rs"), "").unwrap();
+
+        let targets = check::module_targets(
+            root,
+            &["crypto::*".to_string(), "payment::ledger".to_string()],
+        )
+        .unwrap();
+        let labels: Vec<String> = targets.iter().map(|t| t.label()).collect();
+        assert_eq!(labels, ["crypto::aes", "crypto::rsa", "payment::ledger"]);
+
+        let err = check::module_targets(
+            root,
+            &["payment::ledger".to_string(), "billing::*".to_string()],
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("`billing::*`"), "{}", err);
+        assert!(!err.contains("`payment::ledger`"), "{}", err);
+    }
+
+    #[test]
+    fn test_action_mode_rejects_inputs_it_would_ignore() {
+        use clap::Parser;
+        use ferris_proof_cli::Cli;
+
+        let parse = |extra: &[&str]| {
+            let args = [
+                "ferris-proof",
+                "check",
+                "--action-mode",
+                "--input",
+                "src/lib.rs",
+                "--report-output",
+                "report.json",
+            ];
+            Cli::try_parse_from(args.iter().chain(extra))
+        };
+
+        assert!(parse(&["--model", "small"]).is_ok());
+        for flag in [
+            &["--fix"][..],
+            &["--spec-diff", "main"],
+            &["--timings"],
+            &["--ignore-lock"],
+            &["--filter", "severity=error"],
+            &["--group-by", "rule"],
+            &["--output-format", "json"],
+        ] {
+            assert!(parse(flag).is_err(), "{:?} was accepted", flag);
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::config::Config;
-use anyhow::Result;
+use ferris_proof_core::errors::{FerrisProofError, Result};
 use ferris_proof_core::{EnforcementMode, Technique, VerificationLevel};
 use std::path::Path;
 use tracing::debug;
@@ -19,7 +19,8 @@ pub fn parse_verification_attributes(file_path: &Path) -> Result<Option<Config>>
     }
 
     // Read the file content
-    let content = std::fs::read_to_string(file_path)?;
+    let content =
+        std::fs::read_to_string(file_path).map_err(|e| FerrisProofError::io(file_path, e))?;
 
     // Look for verification attributes
     if let Some(config) = parse_verification_attribute_from_content(&content)? {
@@ -1,6 +1,10 @@
-use ferris_proof_core::{EnforcementMode, Technique, VerificationLevel};
+use ferris_proof_core::budget::ViolationBudget;
+use ferris_proof_core::plugins::ToolEnv;
+use ferris_proof_core::{
+    EnforcementMode, EnforcementPolicy, Severity, Technique, VerificationLevel,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +20,36 @@ pub struct Config {
     pub thresholds: Thresholds,
     #[serde(default)]
     pub ci: CiConfig,
+    #[serde(default)]
+    pub metadata: MetadataPolicyConfig,
+    #[serde(default)]
+    pub targets: TargetsConfig,
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+    #[serde(default)]
+    pub artifacts: ArtifactsConfig,
+    /// Layer gating keyed by layer name, e.g. `[layers] formal.requires = ["property_based"]`
+    #[serde(default)]
+    pub layers: BTreeMap<String, LayerGateConfig>,
+    #[serde(default)]
+    pub generate: GenerateConfig,
+    #[serde(default)]
+    pub enforcement: EnforcementPolicyConfig,
+    #[serde(default)]
+    pub attestation: AttestationConfig,
+    /// Release gates keyed by name, checked by `ferris-proof gate`
+    #[serde(default)]
+    pub gates: BTreeMap<String, GateConfig>,
+    #[serde(default)]
+    pub spec_drift: SpecDriftConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub api_surface: ApiSurfaceConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub report: ReportConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +65,43 @@ pub struct ToolConfig {
     pub alloy: Option<AlloyConfig>,
     pub proptest: Option<ProptestConfig>,
     pub kani: Option<KaniConfig>,
+    #[serde(default)]
+    pub mutants: Option<MutantsConfig>,
+    #[serde(default)]
+    pub miri: Option<MiriConfig>,
+    #[serde(default)]
+    pub clippy: Option<ClippyConfig>,
+    #[serde(default)]
+    pub smt: Option<SmtConfig>,
+    #[serde(default)]
+    pub deductive: Option<DeductiveConfig>,
+}
+
+impl ToolConfig {
+    /// Set sections keyed by the name of the plugin that reads them
+    pub fn by_plugin(&self) -> HashMap<String, serde_json::Value> {
+        fn section<T: Serialize>(
+            sections: &mut HashMap<String, serde_json::Value>,
+            plugin: &str,
+            config: &Option<T>,
+        ) {
+            if let Some(value) = config.as_ref().and_then(|c| serde_json::to_value(c).ok()) {
+                sections.insert(plugin.to_string(), value);
+            }
+        }
+
+        let mut sections = HashMap::new();
+        section(&mut sections, "tla-plus", &self.tla_plus);
+        section(&mut sections, "alloy", &self.alloy);
+        section(&mut sections, "proptest", &self.proptest);
+        section(&mut sections, "kani", &self.kani);
+        section(&mut sections, "cargo-mutants", &self.mutants);
+        section(&mut sections, "miri", &self.miri);
+        section(&mut sections, "clippy", &self.clippy);
+        section(&mut sections, "smt", &self.smt);
+        section(&mut sections, "deductive", &self.deductive);
+        sections
+    }
 }
 
 impl Default for ToolConfig {
@@ -41,35 +112,247 @@ impl Default for ToolConfig {
             proptest: Some(ProptestConfig {
                 cases: Some(1000),
                 max_shrink_iters: Some(10000),
+                runner: None,
+                retries: None,
+                backoff: None,
+                env: None,
             }),
             kani: None,
+            mutants: None,
+            miri: None,
+            clippy: None,
+            smt: None,
+            deductive: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TlaPlusConfig {
+    /// Model checker run on `.tla` specs, TLC unless set
+    pub backend: Option<TlaBackend>,
     pub tlc_path: Option<PathBuf>,
+    pub apalache_path: Option<PathBuf>,
     pub timeout: Option<u64>,
+    /// TLC `-workers`, chosen from the cores and heap unless set
     pub workers: Option<u32>,
+    /// JVM heap for TLC in bytes, passed as `-Xmx`; half the host memory
+    /// capped at `thresholds.max_memory_usage` unless set
+    pub heap: Option<u64>,
+    /// Number of steps Apalache explores, 10 unless set
+    pub length: Option<u32>,
+    /// Model checked when `check --model` is not given, e.g. `small` in a module config
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub models: HashMap<String, TlaModelConfig>,
+    /// Reruns of the tool when it fails spuriously, e.g. at JVM startup
+    pub retries: Option<u32>,
+    /// Wait before the first rerun, doubling after each, e.g. `"5s"`
+    pub backoff: Option<String>,
+    /// Variables the tool needs, e.g. `pass = ["JAVA_HOME"]`
+    pub env: Option<ToolEnv>,
+}
+
+/// Model checker for TLA+ specs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TlaBackend {
+    /// Explicit-state checking with TLC
+    #[default]
+    Tlc,
+    /// Bounded symbolic checking with Apalache, for large state spaces
+    Apalache,
+}
+
+/// Named TLC model, rendered to `<name>.cfg` by `generate --target tlc-config`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TlaModelConfig {
+    /// Spec the model checks, relative to the project root
+    pub spec: Option<PathBuf>,
+    pub specification: Option<String>,
+    pub init: Option<String>,
+    pub next: Option<String>,
+    /// Constant assignments as TLA+ expressions, e.g. `Nodes = "{n1, n2}"` or `Nodes = 3`
+    #[serde(default, deserialize_with = "tla_constants")]
+    pub constants: BTreeMap<String, String>,
+    #[serde(default)]
+    pub invariants: Vec<String>,
+    #[serde(default)]
+    pub properties: Vec<String>,
+}
+
+/// Accept TOML integers and booleans as well as TLA+ expression strings
+fn tla_constants<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Constant {
+        Expression(String),
+        Integer(i64),
+        Boolean(bool),
+    }
+
+    let constants = BTreeMap::<String, Constant>::deserialize(deserializer)?;
+    Ok(constants
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Constant::Expression(expression) => expression,
+                Constant::Integer(n) => n.to_string(),
+                Constant::Boolean(true) => "TRUE".to_string(),
+                Constant::Boolean(false) => "FALSE".to_string(),
+            };
+            (name, value)
+        })
+        .collect())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlloyConfig {
     pub analyzer_path: Option<PathBuf>,
     pub scope: Option<u32>,
+    /// Check increasing scopes instead of the single `scope`
+    #[serde(default)]
+    pub sweep: Option<AlloySweepConfig>,
+    /// Reruns when the tool fails spuriously, as for `[tools.tla_plus]`
+    pub retries: Option<u32>,
+    pub backoff: Option<String>,
+    /// Variables the tool needs, as for `[tools.tla_plus]`
+    pub env: Option<ToolEnv>,
+}
+
+/// Scope range for an Alloy sweep, e.g. `sweep = { from = 3, to = 8, budget = 300 }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlloySweepConfig {
+    #[serde(default = "default_sweep_from")]
+    pub from: u32,
+    pub to: u32,
+    /// Seconds after which no further scope is started
+    pub budget: Option<u64>,
+}
+
+fn default_sweep_from() -> u32 {
+    1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProptestConfig {
     pub cases: Option<u32>,
     pub max_shrink_iters: Option<u32>,
+    /// Test runner for property tests, `cargo test` unless set
+    pub runner: Option<ProptestRunner>,
+    /// Reruns of the test run when it fails spuriously; under nextest, also
+    /// the times a failing test is retried before it is reported
+    pub retries: Option<u32>,
+    /// Wait before the first rerun, doubling after each, e.g. `"5s"`
+    pub backoff: Option<String>,
+    /// Variables the tool needs, as for `[tools.tla_plus]`
+    pub env: Option<ToolEnv>,
+}
+
+/// How property tests are run; nextest falls back to `cargo test` when absent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProptestRunner {
+    #[default]
+    Cargo,
+    Nextest,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KaniConfig {
     pub cbmc_path: Option<PathBuf>,
     pub unwind: Option<u32>,
+    /// Reruns when the tool fails spuriously, as for `[tools.tla_plus]`
+    pub retries: Option<u32>,
+    pub backoff: Option<String>,
+    /// Variables the tool needs, as for `[tools.tla_plus]`
+    pub env: Option<ToolEnv>,
+}
+
+/// cargo-mutants settings for the mutation testing layer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MutantsConfig {
+    pub cargo_mutants_path: Option<PathBuf>,
+    /// Per-mutant test timeout in seconds
+    pub timeout: Option<u64>,
+    /// Score required for modules without a `mutation_threshold`
+    pub min_score: Option<f64>,
+    /// Reruns when the tool fails spuriously, as for `[tools.tla_plus]`
+    pub retries: Option<u32>,
+    pub backoff: Option<String>,
+    /// Variables the tool needs, as for `[tools.tla_plus]`
+    pub env: Option<ToolEnv>,
+}
+
+/// Miri settings for the undefined behavior checks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MiriConfig {
+    /// Toolchain with the Miri component, `nightly` unless set
+    pub toolchain: Option<String>,
+    /// Extra `MIRIFLAGS`, e.g. `["-Zmiri-strict-provenance"]`
+    pub flags: Option<Vec<String>>,
+    /// Reruns when the tool fails spuriously, as for `[tools.tla_plus]`
+    pub retries: Option<u32>,
+    pub backoff: Option<String>,
+    /// Variables the tool needs, as for `[tools.tla_plus]`
+    pub env: Option<ToolEnv>,
+}
+
+/// Lint set for the type-safety layer, passed to clippy as `-A`, `-W` and `-D`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClippyConfig {
+    pub allow: Option<Vec<String>>,
+    pub warn: Option<Vec<String>>,
+    pub deny: Option<Vec<String>>,
+    /// Reruns when the tool fails spuriously, as for `[tools.tla_plus]`
+    pub retries: Option<u32>,
+    pub backoff: Option<String>,
+    /// Variables the tool needs, as for `[tools.tla_plus]`
+    pub env: Option<ToolEnv>,
+}
+
+/// SMT solver proving `#[contract]` refinements
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmtConfig {
+    pub solver_path: Option<PathBuf>,
+    /// Seconds per solver query
+    pub timeout: Option<u64>,
+    /// Reruns when the tool fails spuriously, as for `[tools.tla_plus]`
+    pub retries: Option<u32>,
+    pub backoff: Option<String>,
+    /// Variables the tool needs, as for `[tools.tla_plus]`
+    pub env: Option<ToolEnv>,
+}
+
+/// Deductive verifier proving `#[requires]`/`#[ensures]` contracts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeductiveConfig {
+    pub verifier: Option<DeductiveVerifier>,
+    /// `cargo-prusti` or `cargo-creusot` executable, found in PATH otherwise
+    pub path: Option<PathBuf>,
+    /// Seconds before the verifier is stopped
+    pub timeout: Option<u64>,
+    /// Extra arguments passed to the verifier
+    pub args: Option<Vec<String>>,
+    /// Reruns when the tool fails spuriously, as for `[tools.tla_plus]`
+    pub retries: Option<u32>,
+    pub backoff: Option<String>,
+    /// Variables the tool needs, as for `[tools.tla_plus]`
+    pub env: Option<ToolEnv>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeductiveVerifier {
+    /// Prusti, built on the Viper verification infrastructure
+    #[default]
+    Prusti,
+    /// Creusot, discharging proof obligations with Why3
+    Creusot,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +361,39 @@ pub struct ModuleConfig {
     pub enforcement: Option<EnforcementMode>,
     pub enabled_techniques: Option<Vec<Technique>>,
     pub spec_file: Option<PathBuf>,
+    /// Minimum fraction of mutants in matching modules the tests must kill
+    pub mutation_threshold: Option<f64>,
+    /// Severity lists replacing the `[enforcement]` ones for matching modules
+    pub enforcement_policy: Option<EnforcementPolicyConfig>,
+}
+
+/// Severity escalation policy under `[enforcement]`; unset lists keep the defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnforcementPolicyConfig {
+    pub advisory: Option<Vec<Severity>>,
+    pub warning: Option<Vec<Severity>>,
+    pub error: Option<Vec<Severity>>,
+}
+
+impl EnforcementPolicyConfig {
+    /// Lists set in `other` replace the ones set here
+    pub fn merged_with(self, other: Self) -> Self {
+        Self {
+            advisory: other.advisory.or(self.advisory),
+            warning: other.warning.or(self.warning),
+            error: other.error.or(self.error),
+        }
+    }
+
+    pub fn resolve(&self) -> EnforcementPolicy {
+        let defaults = EnforcementPolicy::default();
+        EnforcementPolicy {
+            advisory: self.advisory.clone().unwrap_or(defaults.advisory),
+            warning: self.warning.clone().unwrap_or(defaults.warning),
+            error: self.error.clone().unwrap_or(defaults.error),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,6 +418,9 @@ pub struct Thresholds {
     pub max_verification_time: u64,
     pub max_memory_usage: u64,
     pub cache_ttl: u64,
+    /// Violations `check` tolerates before failing, for gradual adoption
+    #[serde(default, skip_serializing_if = "ViolationBudget::is_empty")]
+    pub max_violations: ViolationBudget,
 }
 
 impl Default for Thresholds {
@@ -110,15 +429,18 @@ impl Default for Thresholds {
             max_verification_time: 300,               // 5 minutes
             max_memory_usage: 2 * 1024 * 1024 * 1024, // 2GB
             cache_ttl: 24 * 60 * 60,                  // 24 hours
+            max_violations: ViolationBudget::default(),
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CiConfig {
     pub fail_on_violations: bool,
     pub generate_artifacts: bool,
     pub upload_reports: bool,
+    pub exit_codes: ExitCodesConfig,
 }
 
 impl Default for CiConfig {
@@ -127,10 +449,262 @@ impl Default for CiConfig {
             fail_on_violations: true,
             generate_artifacts: true,
             upload_reports: false,
+            exit_codes: ExitCodesConfig::default(),
         }
     }
 }
 
+/// How a verification result maps to a process exit code, under `[ci.exit_codes]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExitCodesConfig {
+    /// Warnings fail the run with the `warning` code
+    pub warnings_fail: bool,
+    /// Violations in modules with `advisory` enforcement fail the run
+    pub advisory_fail: bool,
+    /// Layers or targets skipped rather than run fail the run with the
+    /// `warning` code; cached passes and baseline-suppressed layers do not
+    pub skipped_fail: bool,
+    pub error: i32,
+    pub warning: i32,
+    /// Codes for failing violations by layer, e.g. `formal = 10`; take precedence over severity
+    pub layers: BTreeMap<String, i32>,
+}
+
+impl Default for ExitCodesConfig {
+    fn default() -> Self {
+        Self {
+            warnings_fail: false,
+            advisory_fail: false,
+            skipped_fail: false,
+            error: 1,
+            warning: 2,
+            layers: BTreeMap::new(),
+        }
+    }
+}
+
+/// Cargo.toml metadata rules checked by `ferris-proof check` (FP-MD-xxx)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataPolicyConfig {
+    #[serde(default)]
+    pub require_rust_version: bool,
+    #[serde(default = "default_true")]
+    pub require_license: bool,
+    #[serde(default = "default_true")]
+    pub forbid_wildcard_dependencies: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for MetadataPolicyConfig {
+    fn default() -> Self {
+        Self {
+            require_rust_version: false,
+            require_license: true,
+            forbid_wildcard_dependencies: true,
+        }
+    }
+}
+
+/// Optional Cargo targets to verify in addition to `src/`
+///
+/// Unset values follow the profile level: examples from `strict`, benches
+/// from `formal`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TargetsConfig {
+    pub include_examples: Option<bool>,
+    pub include_benches: Option<bool>,
+}
+
+impl TargetsConfig {
+    pub fn include_examples(&self, level: VerificationLevel) -> bool {
+        self.include_examples
+            .unwrap_or(level >= VerificationLevel::Strict)
+    }
+
+    pub fn include_benches(&self, level: VerificationLevel) -> bool {
+        self.include_benches
+            .unwrap_or(level >= VerificationLevel::Formal)
+    }
+}
+
+/// Plugin selection when several plugins support a technique
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginsConfig {
+    /// Plugin names in order of preference, e.g. `["kani", "proptest"]`
+    #[serde(default)]
+    pub prefer: Vec<String>,
+    /// Techniques verified by every compatible plugin instead of the best one
+    #[serde(default)]
+    pub fan_out: Vec<Technique>,
+    /// Plugins that are never selected, managed by `ferris-proof plugins enable/disable`
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
+/// Per-layer settings: layers that must pass for a target before this layer
+/// runs on it, and how sources are normalized for this layer's cache keys
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LayerGateConfig {
+    #[serde(default)]
+    pub requires: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<NormalizationConfig>,
+}
+
+/// Overrides of the default normalization policy; unset fields keep the default
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NormalizationConfig {
+    /// Drop `#[cfg(..)]` attributes of functions (default: true)
+    pub strip_cfg_attrs: Option<bool>,
+    /// Drop doc comments of functions and methods (default: false)
+    pub strip_doc_comments: Option<bool>,
+    /// Hash signatures only, ignoring function bodies (default: false)
+    pub ignore_bodies: Option<bool>,
+    /// Hash macro invocations verbatim rather than dropping them (default: true)
+    pub keep_macros: Option<bool>,
+}
+
+/// Settings for `ferris-proof generate`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GenerateConfig {
+    #[serde(default)]
+    pub proptest: ProptestGenerateConfig,
+    #[serde(default)]
+    pub snapshots: SnapshotsGenerateConfig,
+}
+
+/// Settings for generated property tests
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProptestGenerateConfig {
+    /// Strategy expressions keyed by type path, used instead of `any::<T>()`
+    #[serde(default)]
+    pub strategies: BTreeMap<String, String>,
+}
+
+/// Settings for generated insta snapshot tests
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotsGenerateConfig {
+    /// Module patterns, e.g. `crate::report::*`, whose functions get snapshots; all when empty
+    #[serde(default)]
+    pub modules: Vec<String>,
+    /// Argument lists keyed by function path, for functions that take arguments
+    #[serde(default)]
+    pub inputs: BTreeMap<String, String>,
+}
+
+/// Limits applied to tool outputs and artifacts before they are persisted
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArtifactsConfig {
+    /// Cap for each artifact and tool output stream; unset means unlimited
+    pub max_bytes: Option<u64>,
+    /// Regexes whose matches are replaced with `[REDACTED]`
+    #[serde(default)]
+    pub redact: Vec<String>,
+    /// Tool outputs larger than this are stored compressed under the cache dir
+    pub spill_bytes: Option<u64>,
+    /// Prune generated artifacts older than this after each run
+    pub retention_days: Option<u32>,
+    /// Prune the oldest generated artifacts after each run until the rest fit, in bytes
+    pub max_total_size: Option<u64>,
+}
+
+/// Release gate, e.g. `[gates.payments] modules = "payment::*"` with `deny = ["Error"]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GateConfig {
+    /// Module path pattern where `*` matches any sequence
+    pub modules: String,
+    /// Severities the latest run must have no violations of
+    #[serde(default)]
+    pub deny: Vec<Severity>,
+    /// Layer that must have run for a matching module, e.g. `formal`
+    pub require_layer: Option<String>,
+    /// How recently `require_layer` must have run
+    pub within_days: Option<u32>,
+}
+
+/// Signing of verification reports, checked by `verify-attestation`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttestationConfig {
+    /// Attest every `check` run, as if `--attest` were passed
+    #[serde(default)]
+    pub enabled: bool,
+    /// File holding the hex-encoded ed25519 secret key; `FERRIS_PROOF_SIGNING_KEY` otherwise
+    pub signing_key_file: Option<PathBuf>,
+    /// Hex-encoded public key attestations must be signed with
+    pub public_key: Option<String>,
+}
+
+/// Local usage metrics shown by `ferris-proof metrics`; nothing leaves the machine
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    /// Aggregate every `check` run into `.ferris-proof/metrics.json`
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Detection of specs left stale by changes to linked Rust definitions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpecDriftConfig {
+    /// Check for drift on every `check`; on unless set to false
+    pub enabled: Option<bool>,
+    /// Severity of stale spec violations, `Warning` unless set
+    pub severity: Option<Severity>,
+}
+
+impl SpecDriftConfig {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity.clone().unwrap_or(Severity::Warning)
+    }
+}
+
+/// Comparison of the public API with the snapshot recorded by the last run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiSurfaceConfig {
+    /// Snapshot the public API on every `check`; off unless set, though
+    /// `--layer api-surface` always runs it
+    pub enabled: Option<bool>,
+    /// Severity of broken invariants and removed verified items, `Error` unless set
+    pub severity: Option<Severity>,
+    /// Item paths whose breaking changes are intended, e.g. `billing::Invoice::*`
+    #[serde(default)]
+    pub acknowledge: Vec<String>,
+}
+
+impl ApiSurfaceConfig {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity.clone().unwrap_or(Severity::Error)
+    }
+}
+
+/// Cached result lifetimes under `[cache]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Age after which a layer's results are re-verified, keyed by layer
+    /// name, e.g. `formal = "30d"`; other layers use `thresholds.cache_ttl`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ttl: BTreeMap<String, String>,
+}
+
+/// Rendering of reports under `[report]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportConfig {
+    /// Deep link added to every violation, e.g.
+    /// `"https://github.com/org/repo/blob/{commit}/{path}#L{line}"`
+    pub link_template: Option<String>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -145,8 +719,17 @@ impl Default for Config {
                 proptest: Some(ProptestConfig {
                     cases: Some(1000),
                     max_shrink_iters: Some(10000),
+                    runner: None,
+                    retries: None,
+                    backoff: None,
+                    env: None,
                 }),
                 kani: None,
+                mutants: None,
+                miri: None,
+                clippy: None,
+                smt: None,
+                deductive: None,
             },
             modules: HashMap::new(),
             features: FeatureConfig {
@@ -154,16 +737,22 @@ impl Default for Config {
                 parallel_execution: true,
                 generate_reports: true,
             },
-            thresholds: Thresholds {
-                max_verification_time: 300,               // 5 minutes
-                max_memory_usage: 2 * 1024 * 1024 * 1024, // 2GB
-                cache_ttl: 24 * 60 * 60,                  // 24 hours
-            },
-            ci: CiConfig {
-                fail_on_violations: true,
-                generate_artifacts: true,
-                upload_reports: false,
-            },
+            thresholds: Thresholds::default(),
+            ci: CiConfig::default(),
+            metadata: MetadataPolicyConfig::default(),
+            targets: TargetsConfig::default(),
+            plugins: PluginsConfig::default(),
+            artifacts: ArtifactsConfig::default(),
+            layers: BTreeMap::new(),
+            generate: GenerateConfig::default(),
+            enforcement: EnforcementPolicyConfig::default(),
+            attestation: AttestationConfig::default(),
+            gates: BTreeMap::new(),
+            spec_drift: SpecDriftConfig::default(),
+            metrics: MetricsConfig::default(),
+            api_surface: ApiSurfaceConfig::default(),
+            cache: CacheConfig::default(),
+            report: ReportConfig::default(),
         }
     }
 }
@@ -1,8 +1,18 @@
+#![recursion_limit = "256"]
+
 pub mod attributes;
 pub mod config;
 pub mod manager;
+pub mod module_tree;
 pub mod schema;
 
-pub use config::{Config, ModuleConfig, ProfileConfig, ToolConfig};
-pub use manager::ConfigManager;
+pub use config::{
+    AlloySweepConfig, ApiSurfaceConfig, ArtifactsConfig, AttestationConfig, CacheConfig,
+    ClippyConfig, Config, DeductiveConfig, DeductiveVerifier, ExitCodesConfig, GateConfig,
+    GenerateConfig, LayerGateConfig, MetadataPolicyConfig, MetricsConfig, MiriConfig, ModuleConfig,
+    MutantsConfig, NormalizationConfig, PluginsConfig, ProfileConfig, ProptestGenerateConfig,
+    ProptestRunner, ReportConfig, SmtConfig, SnapshotsGenerateConfig, SpecDriftConfig,
+    TargetsConfig, TlaBackend, TlaModelConfig, ToolConfig,
+};
+pub use manager::{ConfigManager, ConfigReload};
 pub use schema::SchemaValidator;
@@ -1,10 +1,22 @@
 use crate::attributes::parse_verification_attributes;
-use crate::config::Config;
+use crate::config::{Config, TlaModelConfig, ToolConfig};
+use crate::module_tree::ModuleTree;
 use crate::schema::SchemaValidator;
-use anyhow::{anyhow, Result};
-use globset::{Glob, GlobSetBuilder};
-use std::collections::HashMap;
+use ferris_proof_core::artifacts::ArtifactPolicy;
+use ferris_proof_core::cache::{parse_age, CacheTtls};
+use ferris_proof_core::dependency_graph::LayerGates;
+use ferris_proof_core::errors::{FerrisProofError, Result};
+use ferris_proof_core::gates::{GateRule, ReleaseGate};
+use ferris_proof_core::links::LinkTemplate;
+use ferris_proof_core::normalization::{NormalizationPolicies, NormalizationPolicy};
+use ferris_proof_core::plugins::ToolEnv;
+use ferris_proof_core::types::Location;
+use ferris_proof_core::Layer;
+use globset::Glob;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
@@ -16,12 +28,16 @@ pub struct ConfigManager {
     cache: ConfigCache,
     project_root: PathBuf,
     schema_validator: SchemaValidator,
+    /// Built on first use, as resolving walks every `mod` declaration
+    module_tree: OnceLock<ModuleTree>,
 }
 
 #[derive(Debug, Default)]
 #[allow(dead_code)]
 struct ConfigCache {
     entries: HashMap<PathBuf, CachedConfig>,
+    /// Config files that failed to load, so they only count as changed once edited
+    failed: HashMap<PathBuf, std::time::SystemTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -40,21 +56,25 @@ impl ConfigManager {
 
         let config_path = root.join("ferrisproof.toml");
         let root_config = if config_path.exists() {
-            let content = std::fs::read_to_string(&config_path)?;
+            let content = std::fs::read_to_string(&config_path)
+                .map_err(|e| FerrisProofError::io(&config_path, e))?;
 
             // Simple validation for unknown top-level sections
             Self::validate_toml_structure(&content)?;
 
             // Parse TOML first
-            let config: Config = toml::from_str(&content)
-                .map_err(|e| anyhow!("Failed to parse root config at {:?}: {}", config_path, e))?;
-
-            // TODO: Re-enable schema validation after fixing schema for optional fields
-            // Convert to JSON for schema validation
-            // let json_value = serde_json::to_value(&config)
-            //     .map_err(|e| anyhow!("Failed to convert config to JSON for validation: {}", e))?;
-            // schema_validator.validate(&json_value)
-            //     .map_err(|e| anyhow!("Schema validation failed: {}", e))?;
+            let config: Config = toml::from_str(&content).map_err(|e| {
+                FerrisProofError::configuration_in(
+                    format!("Failed to parse root config at {:?}: {}", config_path, e),
+                    &config_path,
+                )
+            })?;
+
+            // Validate what was written rather than the parsed config, so
+            // misspelled keys serde would ignore are reported
+            schema_validator
+                .validate(&Self::toml_to_json(&content)?)
+                .map_err(|e| Self::schema_error_in(&config_path, e))?;
 
             config
         } else {
@@ -68,22 +88,74 @@ impl ConfigManager {
             cache: ConfigCache::default(),
             project_root: root.to_path_buf(),
             schema_validator,
+            module_tree: OnceLock::new(),
         };
 
+        if config_path.exists() {
+            let modified_time = std::fs::metadata(&config_path)?.modified()?;
+            manager.cache.entries.insert(
+                config_path,
+                CachedConfig {
+                    config: manager.root_config.clone(),
+                    timestamp: std::time::SystemTime::now(),
+                    modified_time,
+                },
+            );
+        }
+
         // Discover and load module configuration files
         manager.discover_module_configs()?;
 
         Ok(manager)
     }
 
+    /// Root configuration loaded from the project's ferrisproof.toml
+    pub fn root_config(&self) -> &Config {
+        &self.root_config
+    }
+
+    /// Project root this manager was loaded from
+    pub fn project_root(&self) -> &Path {
+        &self.project_root
+    }
+
     /// Recursively discover all ferrisproof.toml files in subdirectories
     fn discover_module_configs(&mut self) -> Result<()> {
         info!("Discovering module configuration files");
 
         let mut discovered_count = 0;
 
-        // Collect all config paths first to avoid borrowing issues
-        let config_paths: Vec<PathBuf> = WalkDir::new(&self.project_root)
+        // Now load each config
+        for config_path in self.module_config_paths() {
+            debug!("Found module config: {:?}", config_path);
+
+            match self.load_module_config(&config_path) {
+                Ok(_) => {
+                    discovered_count += 1;
+                    debug!("Successfully loaded module config: {:?}", config_path);
+                }
+                Err(e) => {
+                    warn!("Failed to load module config {:?}: {}", config_path, e);
+                    if let Ok(modified_time) =
+                        std::fs::metadata(&config_path).and_then(|m| m.modified())
+                    {
+                        self.cache.failed.insert(config_path, modified_time);
+                    }
+                    // Continue loading other configs rather than failing completely
+                }
+            }
+        }
+
+        info!(
+            "Discovered and loaded {} module configuration files",
+            discovered_count
+        );
+        Ok(())
+    }
+
+    /// Paths of every ferrisproof.toml below the project root, excluding the root one
+    fn module_config_paths(&self) -> Vec<PathBuf> {
+        WalkDir::new(&self.project_root)
             .into_iter()
             .filter_entry(|e| {
                 // Skip hidden directories and target/, but not the root itself
@@ -107,43 +179,86 @@ impl ConfigManager {
                     && e.path().parent() != Some(&self.project_root)
             })
             .map(|e| e.path().to_path_buf())
+            .collect()
+    }
+
+    /// Config files added, removed or modified since this manager was loaded
+    pub fn changed_config_files(&self) -> Vec<PathBuf> {
+        let known = self
+            .cache
+            .entries
+            .iter()
+            .map(|(path, cached)| (path, cached.modified_time))
+            .chain(self.cache.failed.iter().map(|(path, time)| (path, *time)));
+
+        let mut changed: Vec<PathBuf> = known
+            .filter(|(path, modified_time)| {
+                std::fs::metadata(path)
+                    .and_then(|m| m.modified())
+                    .map_or(true, |current| current != *modified_time)
+            })
+            .map(|(path, _)| path.clone())
             .collect();
 
-        // Now load each config
-        for config_path in config_paths {
-            debug!("Found module config: {:?}", config_path);
+        let root_config = self.project_root.join("ferrisproof.toml");
+        let on_disk = self
+            .module_config_paths()
+            .into_iter()
+            .chain(root_config.exists().then_some(root_config));
+        changed.extend(on_disk.filter(|path| {
+            !self.cache.entries.contains_key(path) && !self.cache.failed.contains_key(path)
+        }));
 
-            match self.load_module_config(&config_path) {
-                Ok(_) => {
-                    discovered_count += 1;
-                    debug!("Successfully loaded module config: {:?}", config_path);
-                }
-                Err(e) => {
-                    warn!("Failed to load module config {:?}: {}", config_path, e);
-                    // Continue loading other configs rather than failing completely
-                }
+        changed.sort();
+        changed
+    }
+
+    /// Reload from disk if any config file changed since this manager was loaded
+    ///
+    /// `files` are the files whose effective config the caller depends on, such
+    /// as open documents or watched targets. On a parse error the current
+    /// configuration is kept and the error returned.
+    pub fn reload_if_changed(&mut self, files: &[PathBuf]) -> Result<Option<ConfigReload>> {
+        let changed_files = self.changed_config_files();
+        if changed_files.is_empty() {
+            return Ok(None);
+        }
+        info!("Configuration changed, reloading: {:?}", changed_files);
+
+        let reloaded = Self::from_project_root(&self.project_root)?;
+
+        let mut affected_files = Vec::new();
+        let mut stale_config_hashes = BTreeSet::new();
+        for file in files {
+            let before = self.for_file(file).config_hash;
+            if reloaded.for_file(file).config_hash != before {
+                affected_files.push(file.clone());
+                stale_config_hashes.insert(before);
             }
         }
 
-        info!(
-            "Discovered and loaded {} module configuration files",
-            discovered_count
-        );
-        Ok(())
+        *self = reloaded;
+        Ok(Some(ConfigReload {
+            changed_files,
+            affected_files,
+            stale_config_hashes,
+        }))
     }
 
     /// Load a single module configuration file
     fn load_module_config(&mut self, config_path: &Path) -> Result<()> {
-        let content = std::fs::read_to_string(config_path)?;
-        let config: Config = toml::from_str(&content)
-            .map_err(|e| anyhow!("Failed to parse module config at {:?}: {}", config_path, e))?;
-
-        // TODO: Re-enable schema validation after fixing schema for optional fields
-        // Convert to JSON for schema validation
-        // let json_value = serde_json::to_value(&config)
-        //     .map_err(|e| anyhow!("Failed to convert module config to JSON for validation: {}", e))?;
-        // self.schema_validator.validate_module(&json_value)
-        //     .map_err(|e| anyhow!("Module schema validation failed: {}", e))?;
+        let content = std::fs::read_to_string(config_path)
+            .map_err(|e| FerrisProofError::io(config_path, e))?;
+        let config: Config = toml::from_str(&content).map_err(|e| {
+            FerrisProofError::configuration_in(
+                format!("Failed to parse module config at {:?}: {}", config_path, e),
+                config_path,
+            )
+        })?;
+
+        self.schema_validator
+            .validate_module(&Self::toml_to_json(&content)?)
+            .map_err(|e| Self::schema_error_in(config_path, e))?;
 
         let metadata = std::fs::metadata(config_path)?;
         let modified_time = metadata.modified()?;
@@ -151,7 +266,12 @@ impl ConfigManager {
         // Store the config with its directory as the key
         let config_dir = config_path
             .parent()
-            .ok_or_else(|| anyhow!("Config file has no parent directory: {:?}", config_path))?
+            .ok_or_else(|| {
+                FerrisProofError::configuration(format!(
+                    "Config file has no parent directory: {:?}",
+                    config_path
+                ))
+            })?
             .to_path_buf();
 
         // Clone config before moving it
@@ -186,8 +306,8 @@ impl ConfigManager {
         }
 
         // Apply glob pattern matches from modules (only the most specific one)
-        let module_path = self.file_to_module_path(file_path);
-        let matching_configs = self.find_matching_configs(&module_path, &config);
+        let module = self.module_location(file_path);
+        let matching_configs = self.find_matching_configs(&module, &config);
         if let Some((pattern, module_config)) = matching_configs.first() {
             debug!(
                 "Applying most specific glob pattern '{}' from config",
@@ -205,10 +325,74 @@ impl ConfigManager {
         EffectiveConfig {
             level: config.profile.level,
             enforcement: config.profile.enforcement,
+            enforcement_policy: config.enforcement.resolve(),
             enabled_techniques: config.profile.enabled_techniques.clone(),
+            config_hash: config_hash(&config),
+            max_memory_usage: config.thresholds.max_memory_usage,
+            tools: config.tools,
         }
     }
 
+    /// Tool settings for a file, with module directory configs applied
+    pub fn tools_for_file(&self, file_path: &Path) -> ToolConfig {
+        let file_path = self.project_root.join(file_path);
+        self.find_ancestor_configs(&file_path).into_iter().fold(
+            self.root_config.tools.clone(),
+            |tools, (_, module_config)| self.merge_tool_configs(&tools, &module_config.tools),
+        )
+    }
+
+    /// Named TLC model to check `spec` with, if any
+    ///
+    /// `requested` (from `check --model`) wins over the `model` set in the
+    /// closest config. Models bound to another spec via `spec = ...` do not
+    /// apply, so the spec falls back to its default `.cfg`.
+    pub fn tla_model_for(
+        &self,
+        spec: &Path,
+        requested: Option<&str>,
+    ) -> Result<Option<(String, TlaModelConfig)>> {
+        let tla = self.tools_for_file(spec).tla_plus.unwrap_or_default();
+        let Some(name) = requested.map(str::to_string).or(tla.model) else {
+            return Ok(None);
+        };
+
+        let Some(model) = tla.models.get(&name) else {
+            let mut known: Vec<&str> = tla.models.keys().map(String::as_str).collect();
+            known.sort();
+            return Err(FerrisProofError::configuration(format!(
+                "Unknown TLA+ model '{}' (configured: {})",
+                name,
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            )));
+        };
+
+        if let Some(model_spec) = &model.spec {
+            if self.project_root.join(model_spec) != self.project_root.join(spec) {
+                debug!("Model '{}' checks {:?}, not {:?}", name, model_spec, spec);
+                return Ok(None);
+            }
+        }
+        Ok(Some((name, model.clone())))
+    }
+
+    /// `[tools.tla_plus]` settings for `spec` as passed to the TLA+ plugin
+    ///
+    /// `model` is set to the resolved model name, or null for the default `.cfg`.
+    pub fn tla_tool_config(&self, spec: &Path, requested: Option<&str>) -> Result<Value> {
+        let tla = self.tools_for_file(spec).tla_plus.unwrap_or_default();
+        let mut value = serde_json::to_value(&tla)?;
+        value["model"] = match self.tla_model_for(spec, requested)? {
+            Some((name, _)) => Value::String(name),
+            None => Value::Null,
+        };
+        Ok(value)
+    }
+
     /// Find all ancestor module configurations for a file
     fn find_ancestor_configs(&self, file_path: &Path) -> Vec<(PathBuf, Config)> {
         let mut ancestors = Vec::new();
@@ -232,84 +416,88 @@ impl ConfigManager {
         ancestors
     }
 
-    /// Find all module configurations with glob patterns matching the module path
+    /// Module configurations whose `[modules]` pattern matches `module`
+    ///
+    /// Crate-qualified patterns (`my-crate::crypto::*`) come first, as they
+    /// name the module unambiguously; within each kind, more specific
+    /// patterns come first.
     fn find_matching_configs(
         &self,
-        module_path: &str,
+        module: &ModuleLocation,
         current_config: &Config,
     ) -> Vec<(String, Config)> {
         let mut matches = Vec::new();
 
-        // Check root config for glob patterns
-        for (pattern_str, module_override) in &self.root_config.modules {
-            // Try to compile the glob pattern
-            if let Ok(glob) = Glob::new(pattern_str) {
-                if let Ok(glob_set) = GlobSetBuilder::new().add(glob).build() {
-                    if glob_set.is_match(module_path) {
-                        debug!(
-                            "Root glob pattern '{}' matches module path '{}'",
-                            pattern_str, module_path
-                        );
-                        debug!(
-                            "Pattern specificity: {}",
-                            Self::calculate_pattern_specificity(pattern_str)
-                        );
-                        // Create a temporary config with just this module override
-                        let mut temp_config = current_config.clone();
-                        temp_config.profile.level =
-                            module_override.level.unwrap_or(temp_config.profile.level);
-                        temp_config.profile.enforcement = module_override
-                            .enforcement
-                            .unwrap_or(temp_config.profile.enforcement);
-                        if let Some(techniques) = &module_override.enabled_techniques {
-                            temp_config.profile.enabled_techniques = techniques.clone();
-                        }
-                        matches.push((pattern_str.clone(), temp_config));
-                    }
-                }
-            }
-        }
+        let overrides = self.root_config.modules.iter().chain(
+            self.module_overrides
+                .values()
+                .flat_map(|module_config| &module_config.modules),
+        );
+        for (pattern_str, module_override) in overrides {
+            let Some(qualified) = self.pattern_matches(pattern_str, module) else {
+                continue;
+            };
+            debug!(
+                "Glob pattern '{}' matches module path '{}' (specificity {}, crate-qualified: {})",
+                pattern_str,
+                module.path,
+                Self::calculate_pattern_specificity(pattern_str),
+                qualified
+            );
 
-        // Check module configs for glob patterns
-        for module_config in self.module_overrides.values() {
-            // Check if this module config has glob patterns
-            for (pattern_str, module_override) in &module_config.modules {
-                // Try to compile the glob pattern
-                if let Ok(glob) = Glob::new(pattern_str) {
-                    if let Ok(glob_set) = GlobSetBuilder::new().add(glob).build() {
-                        if glob_set.is_match(module_path) {
-                            debug!(
-                                "Module glob pattern '{}' matches module path '{}'",
-                                pattern_str, module_path
-                            );
-                            debug!(
-                                "Pattern specificity: {}",
-                                Self::calculate_pattern_specificity(pattern_str)
-                            );
-                            // Create a temporary config with just this module override
-                            let mut temp_config = current_config.clone();
-                            temp_config.profile.level =
-                                module_override.level.unwrap_or(temp_config.profile.level);
-                            temp_config.profile.enforcement = module_override
-                                .enforcement
-                                .unwrap_or(temp_config.profile.enforcement);
-                            if let Some(techniques) = &module_override.enabled_techniques {
-                                temp_config.profile.enabled_techniques = techniques.clone();
-                            }
-                            matches.push((pattern_str.clone(), temp_config));
-                        }
-                    }
-                }
+            // Create a temporary config with just this module override
+            let mut temp_config = current_config.clone();
+            temp_config.profile.level = module_override.level.unwrap_or(temp_config.profile.level);
+            temp_config.profile.enforcement = module_override
+                .enforcement
+                .unwrap_or(temp_config.profile.enforcement);
+            if let Some(techniques) = &module_override.enabled_techniques {
+                temp_config.profile.enabled_techniques = techniques.clone();
             }
+            if let Some(policy) = &module_override.enforcement_policy {
+                temp_config.enforcement = temp_config.enforcement.merged_with(policy.clone());
+            }
+            matches.push((qualified, pattern_str.clone(), temp_config));
         }
 
-        // Sort by specificity (more specific patterns first)
         matches.sort_by(|a, b| {
-            let specificity_a = Self::calculate_pattern_specificity(&a.0);
-            let specificity_b = Self::calculate_pattern_specificity(&b.0);
-            specificity_b.cmp(&specificity_a) // Higher specificity first
+            let specificity_a = Self::calculate_pattern_specificity(&a.1);
+            let specificity_b = Self::calculate_pattern_specificity(&b.1);
+            b.0.cmp(&a.0).then(specificity_b.cmp(&specificity_a))
         });
         matches
+            .into_iter()
+            .map(|(_, pattern, config)| (pattern, config))
+            .collect()
+    }
+
+    /// Whether `pattern` applies to `module`, and if so whether it was
+    /// crate-qualified
+    ///
+    /// A pattern is crate-qualified when its first segment names a workspace
+    /// package; the rest is matched against the module path within that
+    /// package. Unqualified patterns match the full module path or the path
+    /// within any package.
+    fn pattern_matches(&self, pattern: &str, module: &ModuleLocation) -> Option<bool> {
+        let glob_matches = |pattern: &str, path: &str| {
+            Glob::new(pattern).is_ok_and(|glob| glob.compile_matcher().is_match(path))
+        };
+
+        if let Some((package, rest)) = pattern
+            .split_once("::")
+            .filter(|(package, _)| self.module_tree().is_package(package))
+        {
+            let (krate, path) = module.in_crate.as_ref()?;
+            return (*krate == package.replace('-', "_") && glob_matches(rest, path))
+                .then_some(true);
+        }
+
+        let matches = glob_matches(pattern, &module.path)
+            || module
+                .in_crate
+                .as_ref()
+                .is_some_and(|(_, path)| glob_matches(pattern, path));
+        matches.then_some(false)
     }
 
     /// Calculate pattern specificity for sorting
@@ -334,11 +522,36 @@ impl ConfigManager {
         specificity
     }
 
+    /// Schema validation error naming the file it was found in
+    fn schema_error_in(file: &Path, error: FerrisProofError) -> FerrisProofError {
+        match error {
+            FerrisProofError::Configuration {
+                message,
+                suggestions,
+                ..
+            } => FerrisProofError::Configuration {
+                message: format!("{}: {}", file.display(), message),
+                location: Some(Location::file(file)),
+                suggestions,
+            },
+            other => other,
+        }
+    }
+
+    /// TOML document as JSON, the form the schema validates
+    fn toml_to_json(content: &str) -> Result<Value> {
+        let document: toml::Value = toml::from_str(content)
+            .map_err(|e| FerrisProofError::configuration(format!("Invalid TOML syntax: {}", e)))?;
+        serde_json::to_value(document).map_err(|e| {
+            FerrisProofError::configuration(format!("Failed to convert config to JSON: {}", e))
+        })
+    }
+
     /// Simple validation for TOML structure to reject unknown sections
     fn validate_toml_structure(content: &str) -> Result<()> {
         // Parse as generic TOML value to check structure
-        let toml_value: toml::Value =
-            toml::from_str(content).map_err(|e| anyhow!("Invalid TOML syntax: {}", e))?;
+        let toml_value: toml::Value = toml::from_str(content)
+            .map_err(|e| FerrisProofError::configuration(format!("Invalid TOML syntax: {}", e)))?;
 
         if let toml::Value::Table(table) = toml_value {
             let known_sections = [
@@ -348,11 +561,27 @@ impl ConfigManager {
                 "features",
                 "thresholds",
                 "ci",
+                "metadata",
+                "targets",
+                "plugins",
+                "artifacts",
+                "layers",
+                "generate",
+                "enforcement",
+                "attestation",
+                "gates",
+                "spec_drift",
+                "metrics",
+                "api_surface",
+                "cache",
+                "report",
             ];
 
             for key in table.keys() {
                 if !known_sections.contains(&key.as_str()) {
-                    return Err(anyhow!("Unknown configuration section: '{}'", key));
+                    return Err(FerrisProofError::UnknownSection {
+                        section: key.clone(),
+                    });
                 }
             }
         }
@@ -361,7 +590,37 @@ impl ConfigManager {
     }
 
     /// Convert file path to module path string
+    ///
+    /// Files reachable from a crate root get their real module path; others
+    /// (tests, examples, files not declared yet) fall back to their location.
+    fn module_tree(&self) -> &ModuleTree {
+        self.module_tree
+            .get_or_init(|| ModuleTree::build(&self.project_root))
+    }
+
+    fn module_location(&self, file_path: &Path) -> ModuleLocation {
+        let in_crate = self
+            .module_tree()
+            .crate_module(&self.project_root.join(file_path))
+            .map(|(krate, path)| (krate.to_string(), path.to_string()));
+        ModuleLocation {
+            path: self.file_to_module_path(file_path),
+            in_crate,
+        }
+    }
+
     fn file_to_module_path(&self, file_path: &Path) -> String {
+        if let Some(module_path) = self
+            .module_tree()
+            .module_path(&self.project_root.join(file_path))
+        {
+            debug!(
+                "Resolved file path {:?} to module path '{}'",
+                file_path, module_path
+            );
+            return module_path.to_string();
+        }
+
         // Get relative path from project root
         let relative_path = file_path
             .strip_prefix(&self.project_root)
@@ -371,9 +630,10 @@ impl ConfigManager {
         let path_str = relative_path.to_string_lossy();
 
         // Remove file extension and replace path separators with ::
+        let path_str = path_str.strip_suffix(".rs").unwrap_or(&path_str);
         let module_path = path_str
-            .strip_suffix(".rs")
-            .unwrap_or(&path_str)
+            .strip_suffix("/mod")
+            .unwrap_or(path_str)
             .replace(['/', '\\'], "::");
 
         // Strip src prefix if present (common Rust convention)
@@ -427,6 +687,161 @@ impl ConfigManager {
             features: base.features,     // Use base features for now
             thresholds: base.thresholds, // Use base thresholds for now
             ci: base.ci,                 // Use base ci for now
+            metadata: base.metadata,     // Metadata policy is project-wide
+            targets: crate::config::TargetsConfig {
+                include_examples: override_config
+                    .targets
+                    .include_examples
+                    .or(base.targets.include_examples),
+                include_benches: override_config
+                    .targets
+                    .include_benches
+                    .or(base.targets.include_benches),
+            },
+            plugins: crate::config::PluginsConfig {
+                prefer: if override_config.plugins.prefer.is_empty() {
+                    base.plugins.prefer
+                } else {
+                    override_config.plugins.prefer
+                },
+                fan_out: if override_config.plugins.fan_out.is_empty() {
+                    base.plugins.fan_out
+                } else {
+                    override_config.plugins.fan_out
+                },
+                // A module can disable further plugins but not re-enable one
+                disabled: base
+                    .plugins
+                    .disabled
+                    .into_iter()
+                    .chain(override_config.plugins.disabled)
+                    .fold(Vec::new(), |mut disabled, name| {
+                        if !disabled.contains(&name) {
+                            disabled.push(name);
+                        }
+                        disabled
+                    }),
+            },
+            // Redaction rules accumulate so a module can never un-redact
+            artifacts: crate::config::ArtifactsConfig {
+                max_bytes: override_config
+                    .artifacts
+                    .max_bytes
+                    .or(base.artifacts.max_bytes),
+                spill_bytes: override_config
+                    .artifacts
+                    .spill_bytes
+                    .or(base.artifacts.spill_bytes),
+                retention_days: override_config
+                    .artifacts
+                    .retention_days
+                    .or(base.artifacts.retention_days),
+                max_total_size: override_config
+                    .artifacts
+                    .max_total_size
+                    .or(base.artifacts.max_total_size),
+                redact: base
+                    .artifacts
+                    .redact
+                    .into_iter()
+                    .chain(override_config.artifacts.redact)
+                    .fold(Vec::new(), |mut rules, rule| {
+                        if !rules.contains(&rule) {
+                            rules.push(rule);
+                        }
+                        rules
+                    }),
+            },
+            layers: base
+                .layers
+                .into_iter()
+                .chain(override_config.layers)
+                .collect(),
+            generate: crate::config::GenerateConfig {
+                proptest: crate::config::ProptestGenerateConfig {
+                    strategies: base
+                        .generate
+                        .proptest
+                        .strategies
+                        .into_iter()
+                        .chain(override_config.generate.proptest.strategies)
+                        .collect(),
+                },
+                snapshots: crate::config::SnapshotsGenerateConfig {
+                    modules: if override_config.generate.snapshots.modules.is_empty() {
+                        base.generate.snapshots.modules
+                    } else {
+                        override_config.generate.snapshots.modules
+                    },
+                    inputs: base
+                        .generate
+                        .snapshots
+                        .inputs
+                        .into_iter()
+                        .chain(override_config.generate.snapshots.inputs)
+                        .collect(),
+                },
+            },
+            enforcement: base.enforcement.merged_with(override_config.enforcement),
+            attestation: crate::config::AttestationConfig {
+                enabled: base.attestation.enabled || override_config.attestation.enabled,
+                signing_key_file: override_config
+                    .attestation
+                    .signing_key_file
+                    .or(base.attestation.signing_key_file),
+                public_key: override_config
+                    .attestation
+                    .public_key
+                    .or(base.attestation.public_key),
+            },
+            gates: base
+                .gates
+                .into_iter()
+                .chain(override_config.gates)
+                .collect(),
+            spec_drift: crate::config::SpecDriftConfig {
+                enabled: override_config
+                    .spec_drift
+                    .enabled
+                    .or(base.spec_drift.enabled),
+                severity: override_config
+                    .spec_drift
+                    .severity
+                    .or(base.spec_drift.severity),
+            },
+            metrics: crate::config::MetricsConfig {
+                enabled: base.metrics.enabled || override_config.metrics.enabled,
+            },
+            api_surface: crate::config::ApiSurfaceConfig {
+                enabled: override_config
+                    .api_surface
+                    .enabled
+                    .or(base.api_surface.enabled),
+                severity: override_config
+                    .api_surface
+                    .severity
+                    .or(base.api_surface.severity),
+                acknowledge: base
+                    .api_surface
+                    .acknowledge
+                    .into_iter()
+                    .chain(override_config.api_surface.acknowledge)
+                    .collect(),
+            },
+            cache: crate::config::CacheConfig {
+                ttl: base
+                    .cache
+                    .ttl
+                    .into_iter()
+                    .chain(override_config.cache.ttl)
+                    .collect(),
+            },
+            report: crate::config::ReportConfig {
+                link_template: override_config
+                    .report
+                    .link_template
+                    .or(base.report.link_template),
+            },
         }
     }
 
@@ -436,10 +851,37 @@ impl ConfigManager {
         override_config: &crate::config::ToolConfig,
     ) -> crate::config::ToolConfig {
         crate::config::ToolConfig {
-            tla_plus: override_config.tla_plus.clone().or(base.tla_plus.clone()),
+            tla_plus: match (&base.tla_plus, &override_config.tla_plus) {
+                (Some(base), Some(over)) => Some(crate::config::TlaPlusConfig {
+                    backend: over.backend.or(base.backend),
+                    tlc_path: over.tlc_path.clone().or(base.tlc_path.clone()),
+                    apalache_path: over.apalache_path.clone().or(base.apalache_path.clone()),
+                    timeout: over.timeout.or(base.timeout),
+                    workers: over.workers.or(base.workers),
+                    heap: over.heap.or(base.heap),
+                    length: over.length.or(base.length),
+                    model: over.model.clone().or(base.model.clone()),
+                    retries: over.retries.or(base.retries),
+                    backoff: over.backoff.clone().or(base.backoff.clone()),
+                    env: over.env.clone().or(base.env.clone()),
+                    // Models accumulate so a module can select one defined at the root
+                    models: base
+                        .models
+                        .clone()
+                        .into_iter()
+                        .chain(over.models.clone())
+                        .collect(),
+                }),
+                (base, over) => over.clone().or(base.clone()),
+            },
             alloy: override_config.alloy.clone().or(base.alloy.clone()),
             proptest: override_config.proptest.clone().or(base.proptest.clone()),
             kani: override_config.kani.clone().or(base.kani.clone()),
+            mutants: override_config.mutants.clone().or(base.mutants.clone()),
+            miri: override_config.miri.clone().or(base.miri.clone()),
+            clippy: override_config.clippy.clone().or(base.clippy.clone()),
+            smt: override_config.smt.clone().or(base.smt.clone()),
+            deductive: override_config.deductive.clone().or(base.deductive.clone()),
         }
     }
 
@@ -458,6 +900,11 @@ impl ConfigManager {
     pub fn validate(&self) -> Result<()> {
         // Validate root config
         self.validate_config(&self.root_config, "root")?;
+        self.layer_gates()?;
+        self.normalization_policies()?;
+        self.cache_ttls()?;
+        self.tool_envs()?;
+        self.link_template()?;
 
         // Validate all module configs
         for (config_dir, module_config) in &self.module_overrides {
@@ -477,10 +924,10 @@ impl ConfigManager {
                     .iter()
                     .any(|t| matches!(t, ferris_proof_core::Technique::TypeSafety))
                 {
-                    return Err(anyhow!(
+                    return Err(FerrisProofError::configuration(format!(
                         "Minimal level must include TypeSafety technique in {}",
                         context
-                    ));
+                    )));
                 }
             }
             ferris_proof_core::VerificationLevel::Standard => {
@@ -490,10 +937,10 @@ impl ConfigManager {
                     .iter()
                     .any(|t| matches!(t, ferris_proof_core::Technique::PropertyTests))
                 {
-                    return Err(anyhow!(
+                    return Err(FerrisProofError::configuration(format!(
                         "Standard level must include PropertyTests technique in {}",
                         context
-                    ));
+                    )));
                 }
             }
             ferris_proof_core::VerificationLevel::Strict => {
@@ -503,10 +950,10 @@ impl ConfigManager {
                     .iter()
                     .any(|t| matches!(t, ferris_proof_core::Technique::SessionTypes))
                 {
-                    return Err(anyhow!(
+                    return Err(FerrisProofError::configuration(format!(
                         "Strict level must include SessionTypes technique in {}",
                         context
-                    ));
+                    )));
                 }
             }
             ferris_proof_core::VerificationLevel::Formal => {
@@ -516,41 +963,329 @@ impl ConfigManager {
                     .iter()
                     .any(|t| matches!(t, ferris_proof_core::Technique::FormalSpecs))
                 {
-                    return Err(anyhow!(
+                    return Err(FerrisProofError::configuration(format!(
                         "Formal level must include FormalSpecs technique in {}",
                         context
-                    ));
+                    )));
                 }
             }
         }
 
         // Validate thresholds
         if config.thresholds.max_verification_time == 0 {
-            return Err(anyhow!("max_verification_time must be > 0 in {}", context));
+            return Err(FerrisProofError::configuration(format!(
+                "max_verification_time must be > 0 in {}",
+                context
+            )));
         }
         if config.thresholds.max_memory_usage == 0 {
-            return Err(anyhow!("max_memory_usage must be > 0 in {}", context));
+            return Err(FerrisProofError::configuration(format!(
+                "max_memory_usage must be > 0 in {}",
+                context
+            )));
         }
         if config.thresholds.cache_ttl == 0 {
-            return Err(anyhow!("cache_ttl must be > 0 in {}", context));
+            return Err(FerrisProofError::configuration(format!(
+                "cache_ttl must be > 0 in {}",
+                context
+            )));
         }
 
         // Validate tool configurations
         if let Some(proptest_config) = &config.tools.proptest {
             if let Some(cases) = proptest_config.cases {
                 if cases == 0 {
-                    return Err(anyhow!("proptest.cases must be > 0 in {}", context));
+                    return Err(FerrisProofError::configuration(format!(
+                        "proptest.cases must be > 0 in {}",
+                        context
+                    )));
                 }
             }
         }
 
+        if let Some(sweep) = config.tools.alloy.as_ref().and_then(|a| a.sweep.as_ref()) {
+            if sweep.from == 0 || sweep.from > sweep.to {
+                return Err(FerrisProofError::configuration(format!(
+                    "alloy.sweep must satisfy 1 <= from <= to in {}",
+                    context
+                )));
+            }
+        }
+
+        // Validate exit codes
+        let exit_codes = &config.ci.exit_codes;
+        for (name, code) in [("error", exit_codes.error), ("warning", exit_codes.warning)]
+            .into_iter()
+            .chain(exit_codes.layers.iter().map(|(l, c)| (l.as_str(), *c)))
+        {
+            if !(1..=255).contains(&code) {
+                return Err(FerrisProofError::configuration(format!(
+                    "ci.exit_codes.{} must be between 1 and 255 in {}",
+                    name, context
+                )));
+            }
+        }
+        for layer in exit_codes.layers.keys() {
+            layer.parse::<ferris_proof_core::Layer>().map_err(|e| {
+                FerrisProofError::configuration(format!(
+                    "ci.exit_codes.layers: {} in {}",
+                    e, context
+                ))
+            })?;
+        }
+
+        // Validate artifact limits
+        if config.artifacts.max_bytes == Some(0) {
+            return Err(FerrisProofError::configuration(format!(
+                "artifacts.max_bytes must be > 0 in {}",
+                context
+            )));
+        }
+        if config.artifacts.spill_bytes == Some(0) {
+            return Err(FerrisProofError::configuration(format!(
+                "artifacts.spill_bytes must be > 0 in {}",
+                context
+            )));
+        }
+        ArtifactPolicy::new(config.artifacts.max_bytes, &config.artifacts.redact)
+            .map_err(|e| FerrisProofError::configuration(format!("{} in {}", e, context)))?;
+
+        // Validate mutation score thresholds
+        let min_score = config.tools.mutants.as_ref().and_then(|m| m.min_score);
+        if min_score.is_some_and(|score| !(0.0..=1.0).contains(&score)) {
+            return Err(FerrisProofError::configuration(format!(
+                "mutants.min_score must be between 0.0 and 1.0 in {}",
+                context
+            )));
+        }
+        for (pattern, module) in &config.modules {
+            if module
+                .mutation_threshold
+                .is_some_and(|score| !(0.0..=1.0).contains(&score))
+            {
+                return Err(FerrisProofError::configuration(format!(
+                    "modules.\"{}\".mutation_threshold must be between 0.0 and 1.0 in {}",
+                    pattern, context
+                )));
+            }
+        }
+
         Ok(())
     }
+
+    /// Layer gates declared in the root `[layers]` section
+    pub fn layer_gates(&self) -> Result<LayerGates> {
+        let mut requires = HashMap::new();
+        for (name, gate) in &self.root_config.layers {
+            let layer: Layer = name.parse().map_err(|e| {
+                FerrisProofError::configuration(format!("[layers] {}: {}", name, e))
+            })?;
+            let required = gate
+                .requires
+                .iter()
+                .map(|r| r.parse::<Layer>())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| {
+                    FerrisProofError::configuration(format!("[layers] {}.requires: {}", name, e))
+                })?;
+            requires.insert(layer, required);
+        }
+        LayerGates::new(requires)
+    }
+
+    /// Cache-key normalization policies from `[layers.<layer>.normalization]`
+    pub fn normalization_policies(&self) -> Result<NormalizationPolicies> {
+        let mut layers = HashMap::new();
+        for (name, settings) in &self.root_config.layers {
+            let Some(config) = &settings.normalization else {
+                continue;
+            };
+            let layer: Layer = name.parse().map_err(|e| {
+                FerrisProofError::configuration(format!("[layers] {}: {}", name, e))
+            })?;
+            let default = NormalizationPolicy::default();
+            layers.insert(
+                layer,
+                NormalizationPolicy {
+                    strip_cfg_attrs: config.strip_cfg_attrs.unwrap_or(default.strip_cfg_attrs),
+                    strip_doc_comments: config
+                        .strip_doc_comments
+                        .unwrap_or(default.strip_doc_comments),
+                    ignore_bodies: config.ignore_bodies.unwrap_or(default.ignore_bodies),
+                    keep_macros: config.keep_macros.unwrap_or(default.keep_macros),
+                },
+            );
+        }
+        Ok(NormalizationPolicies::new(layers))
+    }
+
+    /// Result lifetimes from `[cache.ttl]`, falling back to `thresholds.cache_ttl`
+    pub fn cache_ttls(&self) -> Result<CacheTtls> {
+        let mut layers = HashMap::new();
+        for (name, age) in &self.root_config.cache.ttl {
+            let layer: Layer = name.parse().map_err(|e| {
+                FerrisProofError::configuration(format!("[cache.ttl] {}: {}", name, e))
+            })?;
+            let ttl = parse_age(age).map_err(|e| {
+                FerrisProofError::configuration(format!("[cache.ttl] {}: {}", name, e))
+            })?;
+            layers.insert(layer, ttl);
+        }
+        Ok(CacheTtls::new(
+            std::time::Duration::from_secs(self.root_config.thresholds.cache_ttl),
+            layers,
+        ))
+    }
+
+    /// `[report] link_template` of the root config
+    pub fn link_template(&self) -> Result<Option<LinkTemplate>> {
+        self.root_config
+            .report
+            .link_template
+            .as_deref()
+            .map(|template| {
+                LinkTemplate::parse(template).map_err(|e| {
+                    FerrisProofError::configuration(format!("[report] link_template: {}", e))
+                })
+            })
+            .transpose()
+    }
+
+    /// `[tools.*.env]` of the root config, keyed by plugin name
+    pub fn tool_envs(&self) -> Result<BTreeMap<String, ToolEnv>> {
+        self.root_config
+            .tools
+            .by_plugin()
+            .iter()
+            .map(|(plugin, tool_config)| {
+                let env = ToolEnv::from_tool_config(tool_config);
+                env.validate().map_err(|e| match e {
+                    FerrisProofError::Configuration { message, .. } => {
+                        FerrisProofError::configuration(format!(
+                            "[tools] {} env: {}",
+                            plugin, message
+                        ))
+                    }
+                    e => e,
+                })?;
+                Ok((plugin.clone(), env))
+            })
+            .collect()
+    }
+
+    /// Release gates from `[gates]`, in name order
+    pub fn release_gates(&self) -> Result<Vec<ReleaseGate>> {
+        self.root_config
+            .gates
+            .iter()
+            .map(|(name, gate)| {
+                let mut rules = Vec::new();
+                if !gate.deny.is_empty() {
+                    rules.push(GateRule::Deny(gate.deny.clone()));
+                }
+                match (&gate.require_layer, gate.within_days) {
+                    (Some(layer), within_days) => rules.push(GateRule::RequireLayer {
+                        layer: layer.parse().map_err(|e| {
+                            FerrisProofError::configuration(format!(
+                                "[gates] {}.require_layer: {}",
+                                name, e
+                            ))
+                        })?,
+                        within: within_days
+                            .map(|days| std::time::Duration::from_secs(u64::from(days) * 86_400)),
+                    }),
+                    (None, Some(_)) => {
+                        return Err(FerrisProofError::configuration(format!(
+                            "[gates] {}: within_days needs require_layer",
+                            name
+                        )))
+                    }
+                    (None, None) => {}
+                }
+                if rules.is_empty() {
+                    return Err(FerrisProofError::configuration(format!(
+                        "[gates] {}: declare deny or require_layer",
+                        name
+                    )));
+                }
+
+                Ok(ReleaseGate {
+                    name: name.clone(),
+                    modules: gate.modules.clone(),
+                    rules,
+                })
+            })
+            .collect()
+    }
+
+    /// Mutation score thresholds keyed by module pattern
+    ///
+    /// Module directory configs override root patterns of the same name.
+    pub fn mutation_thresholds(&self) -> BTreeMap<String, f64> {
+        std::iter::once(&self.root_config)
+            .chain(self.module_overrides.values())
+            .flat_map(|config| &config.modules)
+            .filter_map(|(pattern, module)| Some((pattern.clone(), module.mutation_threshold?)))
+            .collect()
+    }
+}
+
+/// Where a file sits in the module tree, for matching `[modules]` patterns
+struct ModuleLocation {
+    /// Module path, prefixed with the crate name in workspace members
+    path: String,
+    /// Crate name and module path within the crate, if the package is known
+    in_crate: Option<(String, String)>,
 }
 
 #[derive(Debug, Clone)]
 pub struct EffectiveConfig {
     pub level: ferris_proof_core::VerificationLevel,
     pub enforcement: ferris_proof_core::EnforcementMode,
+    pub enforcement_policy: ferris_proof_core::EnforcementPolicy,
     pub enabled_techniques: Vec<ferris_proof_core::Technique>,
+    /// Hash of the fully merged config, used as the cache key's config hash
+    pub config_hash: String,
+    /// `thresholds.max_memory_usage` in bytes
+    pub max_memory_usage: u64,
+    /// `[tools]` with module overrides applied
+    pub tools: ToolConfig,
+}
+
+impl EffectiveConfig {
+    /// Configuration handed to plugins verifying this file
+    ///
+    /// Each plugin receives its own `[tools]` section as `tool_config`.
+    pub fn plugin_config(&self) -> ferris_proof_core::plugins::EffectiveConfig {
+        ferris_proof_core::plugins::EffectiveConfig {
+            level: self.level,
+            enforcement: self.enforcement,
+            enforcement_policy: self.enforcement_policy.clone(),
+            enabled_techniques: self.enabled_techniques.clone(),
+            tool_config: Value::Null,
+            plugin_tools: self.tools.by_plugin(),
+            memory_budget: Some(self.max_memory_usage),
+        }
+    }
+}
+
+/// Outcome of a configuration reload
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReload {
+    /// Config files that were added, removed or modified
+    pub changed_files: Vec<PathBuf>,
+    /// Files whose effective configuration changed
+    pub affected_files: Vec<PathBuf>,
+    /// Config hashes of cache entries that no longer apply
+    pub stale_config_hashes: BTreeSet<String>,
+}
+
+/// Stable hash of a merged config
+///
+/// Serialized through `serde_json::Value` so map keys are sorted.
+fn config_hash(config: &Config) -> String {
+    let value = serde_json::to_value(config).unwrap_or_default();
+    blake3::hash(value.to_string().as_bytes())
+        .to_hex()
+        .to_string()
 }
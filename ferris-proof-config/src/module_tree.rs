@@ -0,0 +1,417 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use syn::{Attribute, Expr, ExprLit, Item, Lit, Meta};
+use tracing::debug;
+use walkdir::WalkDir;
+
+/// Module paths of source files, found by walking `mod` declarations
+///
+/// The project's own package starts at `crate` (so `src/crypto/aes.rs` is
+/// `crypto::aes`), workspace members are prefixed with their crate name.
+/// `mod.rs` layouts, inline modules and `#[path = "..."]` are honoured.
+///
+/// Packages come from `cargo metadata` when the project root has a manifest,
+/// otherwise from every `Cargo.toml` below the root.
+#[derive(Debug, Default)]
+pub struct ModuleTree {
+    modules: HashMap<PathBuf, String>,
+    /// Package of each file and its module path within that package
+    crates: HashMap<PathBuf, (String, String)>,
+    /// Package names with `-` replaced by `_`
+    packages: BTreeSet<String>,
+}
+
+impl ModuleTree {
+    pub fn build(project_root: &Path) -> Self {
+        let mut tree = Self::default();
+        let packages =
+            workspace_packages(project_root).unwrap_or_else(|| manifest_packages(project_root));
+        let mut root_package = false;
+        for (name, package_dir) in packages {
+            let crate_name = name.replace('-', "_");
+            let prefix = if normalize(&package_dir) == normalize(project_root) {
+                root_package = true;
+                Vec::new()
+            } else {
+                vec![crate_name.clone()]
+            };
+            tree.packages.insert(crate_name.clone());
+            tree.add_package(&package_dir.join("src"), &prefix, &crate_name);
+        }
+
+        // `src/` of a root that is not itself a package still starts at `crate`
+        if !root_package {
+            tree.add_package(&project_root.join("src"), &[], "");
+        }
+
+        tree
+    }
+
+    /// Module path of a file reachable from a crate root
+    pub fn module_path(&self, file: &Path) -> Option<&str> {
+        self.modules.get(&normalize(file)).map(String::as_str)
+    }
+
+    /// Every module path with the file defining it, in path order
+    pub fn modules(&self) -> Vec<(&str, &Path)> {
+        let mut modules: Vec<(&str, &Path)> = self
+            .modules
+            .iter()
+            .map(|(file, module)| (module.as_str(), file.as_path()))
+            .collect();
+        modules.sort();
+        modules
+    }
+
+    /// Package of a file (as a crate name) and its module path in that crate
+    pub fn crate_module(&self, file: &Path) -> Option<(&str, &str)> {
+        self.crates
+            .get(&normalize(file))
+            .map(|(krate, path)| (krate.as_str(), path.as_str()))
+    }
+
+    /// File defining the item at `item_path`, e.g. `codec::parse_header`
+    ///
+    /// The file whose module is the longest prefix of the path wins; the
+    /// remaining segments name the item within it.
+    pub fn item_file(&self, item_path: &str) -> Option<&Path> {
+        let item_path = item_path.strip_prefix("crate::").unwrap_or(item_path);
+        self.modules
+            .iter()
+            .filter_map(|(file, module)| {
+                let prefix_len = match module.as_str() {
+                    "crate" => 0,
+                    module => {
+                        item_path.strip_prefix(module)?.strip_prefix("::")?;
+                        module.len()
+                    }
+                };
+                Some((prefix_len, file))
+            })
+            .max_by_key(|(prefix_len, file)| (*prefix_len, std::cmp::Reverse(*file)))
+            .map(|(_, file)| file.as_path())
+    }
+
+    /// Whether `name` names a workspace package, as `my-crate` or `my_crate`
+    pub fn is_package(&self, name: &str) -> bool {
+        self.packages.contains(&name.replace('-', "_"))
+    }
+
+    fn add_package(&mut self, src_dir: &Path, prefix: &[String], crate_name: &str) {
+        let package = Package {
+            name: crate_name,
+            depth: prefix.len(),
+        };
+        for root in ["lib.rs", "main.rs"] {
+            let root = src_dir.join(root);
+            if root.is_file() {
+                self.add_file(&root, src_dir, prefix, &package);
+            }
+        }
+    }
+
+    /// Record `file` and the file modules it declares, resolved against `dir`
+    fn add_file(&mut self, file: &Path, dir: &Path, segments: &[String], package: &Package) {
+        let file = normalize(file);
+        if self.modules.contains_key(&file) {
+            return;
+        }
+        self.modules.insert(file.clone(), join(segments));
+        if !package.name.is_empty() {
+            self.crates.insert(
+                file.clone(),
+                (package.name.to_string(), join(&segments[package.depth..])),
+            );
+        }
+
+        let parsed = match std::fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|content| syn::parse_file(&content).map_err(|e| e.to_string()))
+        {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                debug!("Not resolving modules declared in {:?}: {}", file, e);
+                return;
+            }
+        };
+        let file_dir = file.parent().unwrap_or(dir).to_path_buf();
+        self.add_items(&parsed.items, dir, &file_dir, segments, package);
+    }
+
+    /// `path_dir` is where `#[path]` is relative to: the file's directory at the
+    /// top level, the module directory inside inline modules
+    fn add_items(
+        &mut self,
+        items: &[Item],
+        dir: &Path,
+        path_dir: &Path,
+        segments: &[String],
+        package: &Package,
+    ) {
+        for item in items {
+            let Item::Mod(module) = item else {
+                continue;
+            };
+            let name = module.ident.to_string();
+            let name = name.strip_prefix("r#").unwrap_or(&name).to_string();
+            let mut child = segments.to_vec();
+            child.push(name.clone());
+            let path_attr = path_attribute(&module.attrs);
+
+            match &module.content {
+                Some((_, items)) => {
+                    let inline_dir = match &path_attr {
+                        Some(path) => path_dir.join(path),
+                        None => dir.join(&name),
+                    };
+                    self.add_items(items, &inline_dir, &inline_dir, &child, package);
+                }
+                None => {
+                    // Files loaded via `#[path]` own their directory like `mod.rs`
+                    let (file, child_dir) = match path_attr {
+                        Some(path) => {
+                            let file = path_dir.join(path);
+                            let child_dir = file.parent().unwrap_or(path_dir).to_path_buf();
+                            (file, child_dir)
+                        }
+                        None => {
+                            let flat = dir.join(format!("{}.rs", name));
+                            if flat.is_file() {
+                                (flat, dir.join(&name))
+                            } else {
+                                (dir.join(&name).join("mod.rs"), dir.join(&name))
+                            }
+                        }
+                    };
+                    if file.is_file() {
+                        self.add_file(&file, &child_dir, &child, package);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Package whose modules are being recorded
+struct Package<'n> {
+    /// Crate name, empty for a root without a manifest
+    name: &'n str,
+    /// Leading module path segments naming the package
+    depth: usize,
+}
+
+/// Workspace members as (package name, package directory) from `cargo metadata`
+fn workspace_packages(project_root: &Path) -> Option<Vec<(String, PathBuf)>> {
+    let manifest = project_root.join("Cargo.toml");
+    if !manifest.is_file() {
+        return None;
+    }
+
+    let output = Command::new("cargo")
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--no-deps",
+            "--offline",
+        ])
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        debug!(
+            "cargo metadata failed, reading manifests instead: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    let packages = metadata["packages"]
+        .as_array()?
+        .iter()
+        .filter_map(|package| {
+            let name = package["name"].as_str()?;
+            let manifest = Path::new(package["manifest_path"].as_str()?);
+            Some((name.to_string(), manifest.parent()?.to_path_buf()))
+        })
+        .collect();
+    Some(packages)
+}
+
+/// Packages of every `Cargo.toml` below the project root
+fn manifest_packages(project_root: &Path) -> Vec<(String, PathBuf)> {
+    WalkDir::new(project_root)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            e.depth() == 0 || (!name.starts_with('.') && name != "target")
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name() == "Cargo.toml")
+        .filter_map(|entry| {
+            let name = package_name(entry.path())?;
+            Some((name, entry.path().parent()?.to_path_buf()))
+        })
+        .collect()
+}
+
+fn path_attribute(attrs: &[Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        Meta::NameValue(meta) if meta.path.is_ident("path") => match &meta.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(path),
+                ..
+            }) => Some(path.value()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn package_name(manifest: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(manifest).ok()?;
+    let manifest: toml::Value = toml::from_str(&content).ok()?;
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn join(segments: &[String]) -> String {
+    if segments.is_empty() {
+        "crate".to_string()
+    } else {
+        segments.join("::")
+    }
+}
+
+/// Resolve `.` and `..` lexically so `#[path = "../x.rs"]` matches plain paths
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolves_mod_rs_path_attributes_and_members() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+
+        write(
+            "src/lib.rs",
+            "mod crypto;\n#[path = \"platform/unix.rs\"]\nmod sys;\nmod net { pub mod tcp; }\n",
+        );
+        write("src/crypto/mod.rs", "pub mod aes;\n");
+        write("src/crypto/aes.rs", "mod tables;\n");
+        write("src/crypto/aes/tables.rs", "");
+        write("src/platform/unix.rs", "mod fd;\n");
+        write("src/platform/fd.rs", "");
+        write("src/net/tcp.rs", "");
+        write("src/orphan.rs", "");
+        write(
+            "crates/wire-format/Cargo.toml",
+            "[package]\nname = \"wire-format\"\n",
+        );
+        write("crates/wire-format/src/lib.rs", "pub mod frame;\n");
+        write("crates/wire-format/src/frame.rs", "");
+
+        let tree = ModuleTree::build(root);
+        let path_of = |file: &str| tree.module_path(&root.join(file));
+        assert_eq!(path_of("src/lib.rs"), Some("crate"));
+        assert_eq!(path_of("src/crypto/mod.rs"), Some("crypto"));
+        assert_eq!(
+            path_of("src/crypto/aes/tables.rs"),
+            Some("crypto::aes::tables")
+        );
+        assert_eq!(path_of("src/platform/unix.rs"), Some("sys"));
+        assert_eq!(path_of("src/platform/fd.rs"), Some("sys::fd"));
+        assert_eq!(path_of("src/net/tcp.rs"), Some("net::tcp"));
+        assert_eq!(path_of("src/orphan.rs"), None);
+        assert_eq!(
+            path_of("crates/wire-format/src/frame.rs"),
+            Some("wire_format::frame")
+        );
+        assert_eq!(
+            tree.crate_module(&root.join("crates/wire-format/src/frame.rs")),
+            Some(("wire_format", "frame"))
+        );
+        assert_eq!(tree.crate_module(&root.join("src/net/tcp.rs")), None);
+        assert!(tree.is_package("wire-format"));
+
+        let item_file = |item: &str| tree.item_file(item).map(Path::to_path_buf);
+        assert_eq!(
+            item_file("crypto::aes::encrypt"),
+            Some(root.join("src/crypto/aes.rs"))
+        );
+        assert_eq!(
+            item_file("crate::net::tcp::Stream::connect"),
+            Some(root.join("src/net/tcp.rs"))
+        );
+        assert_eq!(item_file("net::helper"), Some(root.join("src/lib.rs")));
+        assert_eq!(
+            item_file("wire_format::frame::parse"),
+            Some(root.join("crates/wire-format/src/frame.rs"))
+        );
+    }
+
+    #[test]
+    fn test_packages_come_from_cargo_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        let manifest = |name: &str| {
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+                name
+            )
+        };
+
+        write(
+            "Cargo.toml",
+            &format!("{}\n[workspace]\nmembers = [\"codec\"]\n", manifest("app")),
+        );
+        write("src/main.rs", "mod cli;\n");
+        write("src/cli.rs", "");
+        write("codec/Cargo.toml", &manifest("codec"));
+        write("codec/src/lib.rs", "");
+        // Not a workspace member, so not a package of this project
+        write("tests/fixtures/sample/Cargo.toml", &manifest("sample"));
+        write("tests/fixtures/sample/src/lib.rs", "");
+
+        let tree = ModuleTree::build(root);
+        assert_eq!(
+            tree.crate_module(&root.join("src/cli.rs")),
+            Some(("app", "cli"))
+        );
+        assert_eq!(
+            tree.crate_module(&root.join("codec/src/lib.rs")),
+            Some(("codec", "crate"))
+        );
+        assert!(!tree.is_package("sample"));
+    }
+}
@@ -1,4 +1,7 @@
-use anyhow::{anyhow, bail, Result};
+use ferris_proof_core::artifacts::ArtifactPolicy;
+use ferris_proof_core::errors::{FerrisProofError, Result};
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::paths::{JSONPointer, PathChunk};
 use jsonschema::{JSONSchema, ValidationError};
 use serde_json::{json, Value};
 use tracing::debug;
@@ -31,85 +34,72 @@ impl SchemaValidator {
     /// Validate a root configuration file
     pub fn validate(&self, config: &Value) -> Result<()> {
         debug!("Validating configuration against schema");
-
-        match self.config_schema.validate(config) {
-            Ok(_) => {
-                debug!("Configuration schema validation passed");
-                Ok(())
-            }
-            Err(errors) => {
-                let error_messages: Vec<String> = errors
-                    .into_iter()
-                    .map(|e| format!("{:?} at {}", e.kind, e.instance_path))
-                    .collect();
-
-                bail!(
-                    "Configuration validation failed:\n{}",
-                    error_messages.join("\n")
-                );
-            }
-        }
+        Self::into_result(
+            "schema validation failed",
+            self.schema_errors(&self.config_schema, config),
+        )
     }
 
     /// Validate a module configuration (partial config)
     pub fn validate_module(&self, config: &Value) -> Result<()> {
         debug!("Validating module configuration against schema");
+        Self::into_result(
+            "module schema validation failed",
+            self.schema_errors(&self.module_schema, config),
+        )
+    }
 
-        match self.module_schema.validate(config) {
-            Ok(_) => {
-                debug!("Module configuration schema validation passed");
-                Ok(())
-            }
-            Err(errors) => {
-                let error_messages: Vec<String> = errors
-                    .into_iter()
-                    .map(|e| format!("{:?} at {}", e.kind, e.instance_path))
-                    .collect();
-
-                bail!(
-                    "Module configuration validation failed:\n{}",
-                    error_messages.join("\n")
-                );
-            }
+    fn into_result(title: &str, errors: Vec<ValidationErrorDetail>) -> Result<()> {
+        if errors.is_empty() {
+            debug!("Configuration schema validation passed");
+            return Ok(());
         }
+
+        let details: Vec<String> = errors.iter().map(|e| format!("  - {}", e)).collect();
+        Err(FerrisProofError::Configuration {
+            message: format!("{}:\n{}", title, details.join("\n")),
+            location: None,
+            suggestions: errors.into_iter().filter_map(|e| e.suggestion).collect(),
+        })
     }
 
     /// Get detailed validation errors with suggestions
     pub fn validate_with_details(&self, config: &Value) -> ValidationResult {
+        let errors = self.schema_errors(&self.config_schema, config);
         let mut result = ValidationResult {
-            is_valid: true,
-            errors: Vec::new(),
+            is_valid: errors.is_empty(),
+            errors,
             warnings: Vec::new(),
             suggestions: Vec::new(),
         };
 
-        // Validate against schema
-        if let Err(errors) = self.config_schema.validate(config) {
-            result.is_valid = false;
-            for error in errors {
-                let location = error.instance_path.to_string();
-                let message = format!("{:?}", error.kind);
+        // Additional business logic validation
+        self.validate_business_logic(config, &mut result);
+
+        result
+    }
+
+    fn schema_errors(&self, schema: &JSONSchema, config: &Value) -> Vec<ValidationErrorDetail> {
+        let Err(errors) = schema.validate(config) else {
+            return Vec::new();
+        };
 
-                let validation_error = ValidationErrorDetail {
+        errors
+            .map(|error| {
+                let location = dotted_path(&error.instance_path);
+                ValidationErrorDetail {
                     field: if location.is_empty() {
                         "root".to_string()
                     } else {
                         location.clone()
                     },
-                    message,
-                    location: location.clone(),
-                    expected_value: self.get_expected_value_for_field(&location),
+                    message: error.to_string(),
+                    expected_value: self.get_expected_value_for_field(&location, &error),
                     suggestion: self.get_suggestion_for_field(&location, &error),
-                };
-
-                result.errors.push(validation_error);
-            }
-        }
-
-        // Additional business logic validation
-        self.validate_business_logic(config, &mut result);
-
-        result
+                    location,
+                }
+            })
+            .collect()
     }
 
     /// Validate business logic beyond schema validation
@@ -137,6 +127,34 @@ impl SchemaValidator {
         if let Some(tools) = config.get("tools") {
             self.validate_tool_configs(tools, result);
         }
+
+        // Check redaction patterns compile
+        if let Some(patterns) = config
+            .get("artifacts")
+            .and_then(|a| a.get("redact"))
+            .and_then(|r| r.as_array())
+        {
+            self.validate_redaction_patterns(patterns, result);
+        }
+    }
+
+    fn validate_redaction_patterns(&self, patterns: &[Value], result: &mut ValidationResult) {
+        for (idx, pattern) in patterns.iter().enumerate() {
+            let Some(pattern) = pattern.as_str() else {
+                continue;
+            };
+            if let Err(e) = ArtifactPolicy::new(None, &[pattern.to_string()]) {
+                let location = format!("artifacts.redact[{}]", idx);
+                result.errors.push(ValidationErrorDetail {
+                    field: location.clone(),
+                    message: e.to_string(),
+                    location,
+                    expected_value: Some("valid regular expression".to_string()),
+                    suggestion: Some("Escape regex metacharacters such as ( and [".to_string()),
+                });
+                result.is_valid = false;
+            }
+        }
     }
 
     fn validate_level_techniques_consistency(
@@ -199,6 +217,8 @@ impl SchemaValidator {
                     result.is_valid = false;
                 }
             }
+            // Already reported by the schema's enum check
+            _ if result.errors.iter().any(|e| e.field == "profile.level") => {}
             _ => {
                 result.errors.push(ValidationErrorDetail {
                     field: "profile.level".to_string(),
@@ -286,28 +306,105 @@ impl SchemaValidator {
         }
     }
 
-    fn get_expected_value_for_field(&self, field: &str) -> Option<String> {
-        match field {
-            "profile.level" => Some("one of: minimal, standard, strict, formal".to_string()),
-            "profile.enforcement" => Some("one of: advisory, warning, error".to_string()),
-            "thresholds.max_verification_time" => Some("positive integer (seconds)".to_string()),
-            "thresholds.max_memory_usage" => Some("positive integer (bytes)".to_string()),
-            "tools.proptest.cases" => Some("positive integer".to_string()),
-            _ => None,
-        }
+    fn get_expected_value_for_field(&self, field: &str, error: &ValidationError) -> Option<String> {
+        let expected = match without_indices(field).as_str() {
+            "profile.level" => "one of: minimal, standard, strict, formal",
+            "profile.enforcement" => "one of: advisory, warning, error",
+            "thresholds.max_verification_time" => "positive integer (seconds)",
+            "thresholds.max_memory_usage" => "positive integer (bytes)",
+            "thresholds.cache_ttl" => "positive integer (seconds)",
+            "tools.proptest.cases" => "positive integer",
+            _ => {
+                return match &error.kind {
+                    ValidationErrorKind::Enum { options } => Some(format!(
+                        "one of: {}",
+                        options
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter(|option| !option.is_null())
+                            .map(|option| option
+                                .as_str()
+                                .map_or(option.to_string(), str::to_string))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )),
+                    _ => None,
+                };
+            }
+        };
+        Some(expected.to_string())
     }
 
-    fn get_suggestion_for_field(&self, field: &str, _error: &ValidationError) -> Option<String> {
-        match field {
+    fn get_suggestion_for_field(&self, field: &str, error: &ValidationError) -> Option<String> {
+        if let ValidationErrorKind::AdditionalProperties { unexpected } = &error.kind {
+            let known = known_keys(&Self::config_schema(), field);
+            let hints: Vec<String> = unexpected
+                .iter()
+                .map(|key| match closest(key, &known) {
+                    Some(candidate) => {
+                        format!("'{}' is unknown, did you mean '{}'?", key, candidate)
+                    }
+                    None => format!("Remove '{}', it is not a recognized setting", key),
+                })
+                .collect();
+            return Some(hints.join("; "));
+        }
+
+        match without_indices(field).as_str() {
             "profile.level" => Some("Use 'minimal' for basic checks, 'standard' for most projects, 'strict' for critical systems, or 'formal' for safety-critical systems".to_string()),
             "profile.enforcement" => Some("Use 'advisory' during development, 'warning' for CI, or 'error' for production builds".to_string()),
+            "profile.enabled_techniques" => Some("Technique names are PascalCase, e.g. TypeSafety or PropertyTests".to_string()),
             _ => None,
         }
     }
 
-    /// Build the JSON schema for root configuration
+    /// Compile the root configuration schema
     fn build_config_schema() -> Result<JSONSchema> {
-        let schema = json!({
+        JSONSchema::compile(&Self::config_schema()).map_err(|e| {
+            FerrisProofError::configuration(format!("Failed to compile config schema: {}", e))
+        })
+    }
+
+    /// Compile the module schema: the root schema with nothing required, as
+    /// module files only override what they set
+    fn build_module_schema() -> Result<JSONSchema> {
+        let mut schema = Self::config_schema();
+        schema["title"] = json!("FerrisProof Module Configuration");
+        schema["description"] = json!("Partial configuration for specific modules");
+        if let Some(schema) = schema.as_object_mut() {
+            schema.remove("required");
+        }
+        if let Some(profile) = schema["properties"]["profile"].as_object_mut() {
+            profile.remove("required");
+        }
+
+        JSONSchema::compile(&schema).map_err(|e| {
+            FerrisProofError::configuration(format!("Failed to compile module schema: {}", e))
+        })
+    }
+
+    /// TOML reference of every configuration key, with its type and
+    /// description as comments, as printed by `config schema --format toml-doc`
+    pub fn toml_doc() -> String {
+        let schema = Self::config_schema();
+        let mut out = String::new();
+        for key in ["title", "description"] {
+            if let Some(text) = schema[key].as_str() {
+                out.push_str(&format!("# {}\n", text));
+            }
+        }
+        document_table(&schema, &schema, "", None, &mut out);
+        out
+    }
+
+    /// JSON schema for the root configuration
+    ///
+    /// Optional fields also accept `null`, which is how serde serializes
+    /// unset `Option`s, so both raw TOML and serialized [`crate::Config`]s
+    /// validate. `config schema` exports it for editors and external tools.
+    pub fn config_schema() -> Value {
+        let mut schema = json!({
             "$schema": "http://json-schema.org/draft-07/schema#",
             "title": "FerrisProof Configuration",
             "description": "Configuration for FerrisProof multi-layer verification pipeline",
@@ -330,7 +427,7 @@ impl SchemaValidator {
                             "type": "array",
                             "items": {
                                 "type": "string",
-                                "enum": ["TypeSafety", "PropertyTests", "SessionTypes", "RefinementTypes", "ConcurrencyTesting", "FormalSpecs", "ModelChecking"]
+                                "enum": ["TypeSafety", "PropertyTests", "SessionTypes", "RefinementTypes", "ConcurrencyTesting", "FormalSpecs", "ModelChecking", "MutationTesting", "UndefinedBehaviorChecks", "DeductiveProofs"]
                             },
                             "description": "List of verification techniques to enable"
                         }
@@ -351,6 +448,21 @@ impl SchemaValidator {
                         },
                         "kani": {
                             "$ref": "#/definitions/KaniConfig"
+                        },
+                        "mutants": {
+                            "$ref": "#/definitions/MutantsConfig"
+                        },
+                        "miri": {
+                            "$ref": "#/definitions/MiriConfig"
+                        },
+                        "clippy": {
+                            "$ref": "#/definitions/ClippyConfig"
+                        },
+                        "smt": {
+                            "$ref": "#/definitions/SmtConfig"
+                        },
+                        "deductive": {
+                            "$ref": "#/definitions/DeductiveConfig"
                         }
                     },
                     "additionalProperties": false
@@ -372,18 +484,132 @@ impl SchemaValidator {
                 },
                 "ci": {
                     "$ref": "#/definitions/CiConfig"
+                },
+                "metadata": {
+                    "$ref": "#/definitions/MetadataPolicyConfig"
+                },
+                "targets": {
+                    "$ref": "#/definitions/TargetsConfig"
+                },
+                "plugins": {
+                    "$ref": "#/definitions/PluginsConfig"
+                },
+                "artifacts": {
+                    "$ref": "#/definitions/ArtifactsConfig"
+                },
+                "layers": {
+                    "type": "object",
+                    "additionalProperties": {
+                        "$ref": "#/definitions/LayerGateConfig"
+                    },
+                    "description": "Per-layer gating and cache-key normalization"
+                },
+                "generate": {
+                    "$ref": "#/definitions/GenerateConfig"
+                },
+                "enforcement": {
+                    "$ref": "#/definitions/EnforcementPolicyConfig"
+                },
+                "attestation": {
+                    "$ref": "#/definitions/AttestationConfig"
+                },
+                "gates": {
+                    "type": "object",
+                    "additionalProperties": {
+                        "$ref": "#/definitions/GateConfig"
+                    },
+                    "description": "Release gates checked by `ferris-proof gate`"
+                },
+                "spec_drift": {
+                    "$ref": "#/definitions/SpecDriftConfig"
+                },
+                "metrics": {
+                    "$ref": "#/definitions/MetricsConfig"
+                },
+                "api_surface": {
+                    "$ref": "#/definitions/ApiSurfaceConfig"
+                },
+                "cache": {
+                    "$ref": "#/definitions/CacheConfig"
+                },
+                "report": {
+                    "$ref": "#/definitions/ReportConfig"
                 }
             },
             "required": ["profile"],
             "additionalProperties": false,
             "definitions": {
+                "EnforcementPolicyConfig": {
+                    "type": "object",
+                    "description": "Severities that are informational, reported as warnings, or block",
+                    "properties": {
+                        "advisory": { "$ref": "#/definitions/SeverityList" },
+                        "warning": { "$ref": "#/definitions/SeverityList" },
+                        "error": { "$ref": "#/definitions/SeverityList" }
+                    },
+                    "additionalProperties": false
+                },
+                "SeverityList": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": ["Critical", "Error", "Warning", "Info"]
+                    }
+                },
+                "GenerateConfig": {
+                    "type": "object",
+                    "properties": {
+                        "proptest": {
+                            "type": "object",
+                            "properties": {
+                                "strategies": {
+                                    "type": "object",
+                                    "additionalProperties": { "type": "string" },
+                                    "description": "Strategy expressions keyed by type path, used instead of any::<T>()"
+                                }
+                            },
+                            "additionalProperties": false
+                        },
+                        "snapshots": {
+                            "type": "object",
+                            "properties": {
+                                "modules": {
+                                    "type": "array",
+                                    "items": { "type": "string" },
+                                    "description": "Module patterns whose functions get snapshot tests; all when empty"
+                                },
+                                "inputs": {
+                                    "type": "object",
+                                    "additionalProperties": { "type": "string" },
+                                    "description": "Argument lists keyed by function path, for functions that take arguments"
+                                }
+                            },
+                            "additionalProperties": false
+                        }
+                    },
+                    "additionalProperties": false
+                },
                 "TlaPlusConfig": {
                     "type": "object",
                     "properties": {
+                        "backend": {
+                            "type": "string",
+                            "enum": ["tlc", "apalache"],
+                            "description": "Model checker run on TLA+ specs"
+                        },
                         "tlc_path": {
                             "type": "string",
                             "description": "Path to TLC model checker executable"
                         },
+                        "apalache_path": {
+                            "type": "string",
+                            "description": "Path to the apalache-mc executable"
+                        },
+                        "length": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Number of steps Apalache explores"
+                        },
                         "timeout": {
                             "type": "integer",
                             "minimum": 1,
@@ -393,6 +619,81 @@ impl SchemaValidator {
                             "type": "integer",
                             "minimum": 1,
                             "description": "Number of parallel workers for model checking"
+                        },
+                        "heap": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "JVM heap for TLC in bytes, passed as -Xmx"
+                        },
+                        "model": {
+                            "type": "string",
+                            "description": "Model checked when `check --model` is not given"
+                        },
+                        "models": {
+                            "type": "object",
+                            "additionalProperties": {
+                                "$ref": "#/definitions/TlaModelConfig"
+                            },
+                            "description": "Named TLC models used to generate .cfg files"
+                        },
+                        "retries": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Reruns when the tool fails spuriously"
+                        },
+                        "backoff": {
+                            "type": "string",
+                            "pattern": "^[0-9]+(ms|s|m)?$",
+                            "description": "Wait before the first rerun, doubling after each, e.g. 5s"
+                        },
+                        "env": {
+                            "$ref": "#/definitions/ToolEnvConfig"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "ToolEnvConfig": {
+                    "type": "object",
+                    "properties": {
+                        "pass": {
+                            "type": "array",
+                            "items": {
+                                "type": "string"
+                            },
+                            "description": "Variables copied from the invoking environment, e.g. JAVA_HOME"
+                        },
+                        "set": {
+                            "type": "object",
+                            "additionalProperties": {
+                                "type": "string"
+                            },
+                            "description": "Variables set to fixed values"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "TlaModelConfig": {
+                    "type": "object",
+                    "properties": {
+                        "spec": {
+                            "type": "string",
+                            "description": "Path to the TLA+ spec checked by this model"
+                        },
+                        "specification": { "type": "string" },
+                        "init": { "type": "string" },
+                        "next": { "type": "string" },
+                        "constants": {
+                            "type": "object",
+                            "additionalProperties": { "type": ["string", "integer", "boolean"] },
+                            "description": "Constant assignments as TLA+ expressions or plain values"
+                        },
+                        "invariants": {
+                            "type": "array",
+                            "items": { "type": "string" }
+                        },
+                        "properties": {
+                            "type": "array",
+                            "items": { "type": "string" }
                         }
                     },
                     "additionalProperties": false
@@ -408,6 +709,41 @@ impl SchemaValidator {
                             "type": "integer",
                             "minimum": 1,
                             "description": "Scope for Alloy analysis"
+                        },
+                        "sweep": {
+                            "type": "object",
+                            "properties": {
+                                "from": {
+                                    "type": "integer",
+                                    "minimum": 1,
+                                    "description": "First scope to check"
+                                },
+                                "to": {
+                                    "type": "integer",
+                                    "minimum": 1,
+                                    "description": "Last scope to check"
+                                },
+                                "budget": {
+                                    "type": "integer",
+                                    "minimum": 1,
+                                    "description": "Seconds after which no further scope is started"
+                                }
+                            },
+                            "required": ["to"],
+                            "additionalProperties": false
+                        },
+                        "retries": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Reruns when the tool fails spuriously"
+                        },
+                        "backoff": {
+                            "type": "string",
+                            "pattern": "^[0-9]+(ms|s|m)?$",
+                            "description": "Wait before the first rerun, doubling after each, e.g. 5s"
+                        },
+                        "env": {
+                            "$ref": "#/definitions/ToolEnvConfig"
                         }
                     },
                     "additionalProperties": false
@@ -424,6 +760,24 @@ impl SchemaValidator {
                             "type": "integer",
                             "minimum": 1,
                             "description": "Maximum iterations for test case shrinking"
+                        },
+                        "runner": {
+                            "type": "string",
+                            "enum": ["cargo", "nextest"],
+                            "description": "Run property tests with cargo test or cargo-nextest"
+                        },
+                        "retries": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Reruns of a spuriously failing test run, and retries of a failing test under nextest"
+                        },
+                        "backoff": {
+                            "type": "string",
+                            "pattern": "^[0-9]+(ms|s|m)?$",
+                            "description": "Wait before the first rerun, doubling after each, e.g. 5s"
+                        },
+                        "env": {
+                            "$ref": "#/definitions/ToolEnvConfig"
                         }
                     },
                     "additionalProperties": false
@@ -439,6 +793,181 @@ impl SchemaValidator {
                             "type": "integer",
                             "minimum": 1,
                             "description": "Unwind depth for Kani verification"
+                        },
+                        "retries": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Reruns when the tool fails spuriously"
+                        },
+                        "backoff": {
+                            "type": "string",
+                            "pattern": "^[0-9]+(ms|s|m)?$",
+                            "description": "Wait before the first rerun, doubling after each, e.g. 5s"
+                        },
+                        "env": {
+                            "$ref": "#/definitions/ToolEnvConfig"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "MutantsConfig": {
+                    "type": "object",
+                    "properties": {
+                        "cargo_mutants_path": {
+                            "type": "string",
+                            "description": "Path to the cargo-mutants executable"
+                        },
+                        "timeout": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Per-mutant test timeout in seconds"
+                        },
+                        "min_score": {
+                            "type": "number",
+                            "minimum": 0,
+                            "maximum": 1,
+                            "description": "Mutation score required for modules without their own threshold"
+                        },
+                        "retries": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Reruns when the tool fails spuriously"
+                        },
+                        "backoff": {
+                            "type": "string",
+                            "pattern": "^[0-9]+(ms|s|m)?$",
+                            "description": "Wait before the first rerun, doubling after each, e.g. 5s"
+                        },
+                        "env": {
+                            "$ref": "#/definitions/ToolEnvConfig"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "ClippyConfig": {
+                    "type": "object",
+                    "properties": {
+                        "allow": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Lints passed to clippy with -A"
+                        },
+                        "warn": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Lints passed to clippy with -W"
+                        },
+                        "deny": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Lints passed to clippy with -D, reported as errors"
+                        },
+                        "retries": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Reruns when the tool fails spuriously"
+                        },
+                        "backoff": {
+                            "type": "string",
+                            "pattern": "^[0-9]+(ms|s|m)?$",
+                            "description": "Wait before the first rerun, doubling after each, e.g. 5s"
+                        },
+                        "env": {
+                            "$ref": "#/definitions/ToolEnvConfig"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "DeductiveConfig": {
+                    "type": "object",
+                    "properties": {
+                        "verifier": {
+                            "type": "string",
+                            "enum": ["prusti", "creusot"],
+                            "description": "Deductive verifier proving contracts"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Path to the cargo-prusti or cargo-creusot executable"
+                        },
+                        "timeout": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Seconds before the verifier is stopped"
+                        },
+                        "args": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Extra arguments passed to the verifier"
+                        },
+                        "retries": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Reruns when the tool fails spuriously"
+                        },
+                        "backoff": {
+                            "type": "string",
+                            "pattern": "^[0-9]+(ms|s|m)?$",
+                            "description": "Wait before the first rerun, doubling after each, e.g. 5s"
+                        },
+                        "env": {
+                            "$ref": "#/definitions/ToolEnvConfig"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "SmtConfig": {
+                    "type": "object",
+                    "properties": {
+                        "solver_path": {
+                            "type": "string",
+                            "description": "Path to the z3 executable"
+                        },
+                        "timeout": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Per-query solver timeout in seconds"
+                        },
+                        "retries": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Reruns when the tool fails spuriously"
+                        },
+                        "backoff": {
+                            "type": "string",
+                            "pattern": "^[0-9]+(ms|s|m)?$",
+                            "description": "Wait before the first rerun, doubling after each, e.g. 5s"
+                        },
+                        "env": {
+                            "$ref": "#/definitions/ToolEnvConfig"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "MiriConfig": {
+                    "type": "object",
+                    "properties": {
+                        "toolchain": {
+                            "type": "string",
+                            "description": "Toolchain with the Miri component, e.g. nightly"
+                        },
+                        "flags": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Extra MIRIFLAGS passed to Miri"
+                        },
+                        "retries": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Reruns when the tool fails spuriously"
+                        },
+                        "backoff": {
+                            "type": "string",
+                            "pattern": "^[0-9]+(ms|s|m)?$",
+                            "description": "Wait before the first rerun, doubling after each, e.g. 5s"
+                        },
+                        "env": {
+                            "$ref": "#/definitions/ToolEnvConfig"
                         }
                     },
                     "additionalProperties": false
@@ -463,6 +992,15 @@ impl SchemaValidator {
                         "spec_file": {
                             "type": "string",
                             "description": "Path to formal specification file"
+                        },
+                        "mutation_threshold": {
+                            "type": "number",
+                            "minimum": 0,
+                            "maximum": 1,
+                            "description": "Minimum fraction of mutants the module's tests must kill"
+                        },
+                        "enforcement_policy": {
+                            "$ref": "#/definitions/EnforcementPolicyConfig"
                         }
                     },
                     "additionalProperties": false
@@ -502,6 +1040,26 @@ impl SchemaValidator {
                             "type": "integer",
                             "minimum": 1,
                             "description": "Cache time-to-live in seconds"
+                        },
+                        "max_violations": {
+                            "oneOf": [
+                                {
+                                    "type": "integer",
+                                    "minimum": 0
+                                },
+                                {
+                                    "type": "object",
+                                    "properties": {
+                                        "total": { "type": "integer", "minimum": 0 },
+                                        "critical": { "type": "integer", "minimum": 0 },
+                                        "error": { "type": "integer", "minimum": 0 },
+                                        "warning": { "type": "integer", "minimum": 0 },
+                                        "info": { "type": "integer", "minimum": 0 }
+                                    },
+                                    "additionalProperties": false
+                                }
+                            ],
+                            "description": "Violations check tolerates before failing, in total or per severity"
                         }
                     },
                     "required": ["max_verification_time", "max_memory_usage", "cache_ttl"]
@@ -520,80 +1078,553 @@ impl SchemaValidator {
                         "upload_reports": {
                             "type": "boolean",
                             "description": "Upload verification reports"
+                        },
+                        "exit_codes": {
+                            "type": "object",
+                            "properties": {
+                                "warnings_fail": {
+                                    "type": "boolean",
+                                    "description": "Fail the run on warnings"
+                                },
+                                "advisory_fail": {
+                                    "type": "boolean",
+                                    "description": "Fail the run on violations in advisory modules"
+                                },
+                                "skipped_fail": {
+                                    "type": "boolean",
+                                    "description": "Fail the run with the warning code when layers or targets were skipped"
+                                },
+                                "error": {
+                                    "type": "integer",
+                                    "minimum": 1,
+                                    "maximum": 255,
+                                    "description": "Exit code for errors"
+                                },
+                                "warning": {
+                                    "type": "integer",
+                                    "minimum": 1,
+                                    "maximum": 255,
+                                    "description": "Exit code for warnings when warnings_fail is set"
+                                },
+                                "layers": {
+                                    "type": "object",
+                                    "additionalProperties": {
+                                        "type": "integer",
+                                        "minimum": 1,
+                                        "maximum": 255
+                                    },
+                                    "description": "Exit codes for failing violations by layer"
+                                }
+                            },
+                            "additionalProperties": false
+                        }
+                    }
+                },
+                "MetadataPolicyConfig": {
+                    "type": "object",
+                    "properties": {
+                        "require_rust_version": {
+                            "type": "boolean",
+                            "description": "Require `rust-version` in [package] (FP-MD-001)"
+                        },
+                        "forbid_wildcard_dependencies": {
+                            "type": "boolean",
+                            "description": "Reject `*` dependency versions (FP-MD-002)"
+                        },
+                        "require_license": {
+                            "type": "boolean",
+                            "description": "Require `license` or `license-file` in [package] (FP-MD-003)"
                         }
                     },
-                    "required": ["fail_on_violations", "generate_artifacts", "upload_reports"]
-                }
-            }
-        });
-
-        JSONSchema::compile(&schema).map_err(|e| anyhow!("Failed to compile config schema: {}", e))
-    }
-
-    /// Build the JSON schema for module configuration (partial config)
-    fn build_module_schema() -> Result<JSONSchema> {
-        let schema = json!({
-            "$schema": "http://json-schema.org/draft-07/schema#",
-            "title": "FerrisProof Module Configuration",
-            "description": "Partial configuration for specific modules",
-            "type": "object",
-            "properties": {
-                "profile": {
+                    "additionalProperties": false
+                },
+                "LayerGateConfig": {
                     "type": "object",
                     "properties": {
-                        "level": {
+                        "requires": {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                                "enum": ["formal", "type_level", "property_based", "monitoring", "api_surface"]
+                            },
+                            "description": "Layers that must pass first, otherwise this layer is skipped (gated)"
+                        },
+                        "normalization": {
+                            "$ref": "#/definitions/NormalizationConfig"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "NormalizationConfig": {
+                    "type": "object",
+                    "properties": {
+                        "strip_cfg_attrs": {
+                            "type": "boolean",
+                            "description": "Drop #[cfg(..)] attributes of functions before hashing (default: true)"
+                        },
+                        "strip_doc_comments": {
+                            "type": "boolean",
+                            "description": "Drop doc comments of functions and methods before hashing (default: false)"
+                        },
+                        "ignore_bodies": {
+                            "type": "boolean",
+                            "description": "Hash function signatures only, ignoring bodies (default: false)"
+                        },
+                        "keep_macros": {
+                            "type": "boolean",
+                            "description": "Hash macro invocations verbatim rather than dropping them (default: true)"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "TargetsConfig": {
+                    "type": "object",
+                    "properties": {
+                        "include_examples": {
+                            "type": "boolean",
+                            "description": "Verify examples/ (default: strict and formal levels)"
+                        },
+                        "include_benches": {
+                            "type": "boolean",
+                            "description": "Verify benches/ (default: formal level)"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "GateConfig": {
+                    "type": "object",
+                    "properties": {
+                        "modules": {
                             "type": "string",
-                            "enum": ["minimal", "standard", "strict", "formal"]
+                            "description": "Module path pattern, e.g. payment::*"
                         },
-                        "enforcement": {
+                        "deny": {
+                            "$ref": "#/definitions/SeverityList",
+                            "description": "Severities the latest run must have no violations of"
+                        },
+                        "require_layer": {
                             "type": "string",
-                            "enum": ["advisory", "warning", "error"]
+                            "enum": ["formal", "type_level", "property_based", "monitoring", "api_surface"],
+                            "description": "Layer that must have run for a matching module"
                         },
-                        "enabled_techniques": {
+                        "within_days": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "How recently require_layer must have run"
+                        }
+                    },
+                    "required": ["modules"],
+                    "additionalProperties": false
+                },
+                "SpecDriftConfig": {
+                    "type": "object",
+                    "properties": {
+                        "enabled": {
+                            "type": "boolean",
+                            "description": "Report specs whose linked Rust definitions changed"
+                        },
+                        "severity": {
+                            "type": "string",
+                            "enum": ["Warning", "Error"],
+                            "description": "Severity of stale spec violations"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "ApiSurfaceConfig": {
+                    "type": "object",
+                    "properties": {
+                        "enabled": {
+                            "type": "boolean",
+                            "description": "Compare the public API with the last recorded snapshot on every check"
+                        },
+                        "severity": {
+                            "type": "string",
+                            "enum": ["Warning", "Error"],
+                            "description": "Severity of broken invariants and removed verified items"
+                        },
+                        "acknowledge": {
                             "type": "array",
-                            "items": {
-                                "type": "string"
-                            }
+                            "items": { "type": "string" },
+                            "description": "Item paths whose breaking changes are intended; `*` matches any substring"
                         }
-                    }
+                    },
+                    "additionalProperties": false
                 },
-                "modules": {
+                "CacheConfig": {
                     "type": "object",
-                    "patternProperties": {
-                        "^.*$": {
-                            "$ref": "#/definitions/ModuleConfig"
+                    "properties": {
+                        "ttl": {
+                            "type": "object",
+                            "additionalProperties": {
+                                "type": "string",
+                                "pattern": "^[0-9]+[smhdw]$"
+                            },
+                            "description": "Age after which a layer's cached results are re-verified, e.g. 30d or 24h"
                         }
-                    }
-                }
-            },
-            "additionalProperties": false,
-            "definitions": {
-                "ModuleConfig": {
+                    },
+                    "additionalProperties": false
+                },
+                "ReportConfig": {
                     "type": "object",
                     "properties": {
-                        "level": {
+                        "link_template": {
                             "type": "string",
-                            "enum": ["minimal", "standard", "strict", "formal"]
+                            "description": "Deep link added to every violation, with {path}, {abs_path}, {line}, {column} and {commit} placeholders"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "MetricsConfig": {
+                    "type": "object",
+                    "properties": {
+                        "enabled": {
+                            "type": "boolean",
+                            "description": "Aggregate local usage metrics from every check run"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "AttestationConfig": {
+                    "type": "object",
+                    "properties": {
+                        "enabled": {
+                            "type": "boolean",
+                            "description": "Sign the report of every check run"
                         },
-                        "enforcement": {
+                        "signing_key_file": {
                             "type": "string",
-                            "enum": ["advisory", "warning", "error"]
+                            "description": "File with the hex-encoded ed25519 secret key"
                         },
-                        "enabled_techniques": {
+                        "public_key": {
+                            "type": "string",
+                            "pattern": "^[0-9a-fA-F]{64}$",
+                            "description": "Hex-encoded ed25519 public key attestations must be signed with"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "ArtifactsConfig": {
+                    "type": "object",
+                    "properties": {
+                        "max_bytes": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Maximum size of each artifact and tool output stream"
+                        },
+                        "redact": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Regexes replaced with [REDACTED] before artifacts are persisted"
+                        },
+                        "spill_bytes": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Tool outputs larger than this are stored compressed under the cache directory"
+                        },
+                        "retention_days": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Prune generated artifacts older than this after each run"
+                        },
+                        "max_total_size": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Prune the oldest generated artifacts after each run until the rest fit, in bytes"
+                        }
+                    },
+                    "additionalProperties": false
+                },
+                "PluginsConfig": {
+                    "type": "object",
+                    "properties": {
+                        "prefer": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Plugins to try first when several support a technique"
+                        },
+                        "fan_out": {
                             "type": "array",
                             "items": {
-                                "type": "string"
-                            }
+                                "type": "string",
+                                "enum": ["TypeSafety", "PropertyTests", "SessionTypes", "RefinementTypes", "ConcurrencyTesting", "FormalSpecs", "ModelChecking", "MutationTesting", "UndefinedBehaviorChecks", "DeductiveProofs"]
+                            },
+                            "description": "Techniques run by every compatible plugin, reporting disagreements"
                         },
-                        "spec_file": {
-                            "type": "string"
+                        "disabled": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Plugins that are never selected"
                         }
-                    }
+                    },
+                    "additionalProperties": false
                 }
             }
         });
 
-        JSONSchema::compile(&schema).map_err(|e| anyhow!("Failed to compile module schema: {}", e))
+        allow_null_for_optional(&mut schema);
+        schema
+    }
+}
+
+/// `tools.tla_plus.timeout` style path of a JSON pointer, with `[n]` indices
+fn dotted_path(pointer: &JSONPointer) -> String {
+    let mut path = String::new();
+    for chunk in pointer {
+        match chunk {
+            PathChunk::Property(name) => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(name);
+            }
+            PathChunk::Index(index) => path.push_str(&format!("[{}]", index)),
+            PathChunk::Keyword(keyword) => path.push_str(keyword),
+        }
+    }
+    path
+}
+
+fn without_indices(field: &str) -> String {
+    field.split('[').next().unwrap_or_default().to_string()
+}
+
+/// Keys the schema allows in the table at `field`
+fn known_keys(schema: &Value, field: &str) -> Vec<String> {
+    let mut current = schema;
+    for key in field.split('.').filter(|key| !key.is_empty()) {
+        let key = without_indices(key);
+        current = resolve(schema, current);
+        current = match current["properties"].get(&key) {
+            Some(property) => property,
+            None if current["additionalProperties"].is_object() => &current["additionalProperties"],
+            None => return Vec::new(),
+        };
+    }
+    resolve(schema, current)["properties"]
+        .as_object()
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Follow a local `#/definitions/...` reference
+fn resolve<'s>(root: &'s Value, schema: &'s Value) -> &'s Value {
+    match schema["$ref"]
+        .as_str()
+        .and_then(|reference| reference.strip_prefix("#/definitions/"))
+    {
+        Some(name) => &root["definitions"][name],
+        None => schema,
+    }
+}
+
+/// Append `table` at `path` and its nested tables to a TOML reference
+///
+/// Scalar and array keys are listed as commented-out assignments under the
+/// table's header; tables keyed by user-chosen names appear as `<name>`.
+fn document_table(
+    root: &Value,
+    table: &Value,
+    path: &str,
+    description: Option<&str>,
+    out: &mut String,
+) {
+    let resolved = resolve(root, table);
+    let empty = serde_json::Map::new();
+    let properties = resolved["properties"].as_object().unwrap_or(&empty);
+    let required: Vec<&str> = resolved["required"]
+        .as_array()
+        .map(|keys| keys.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let (tables, keys): (Vec<_>, Vec<_>) = properties
+        .iter()
+        .partition(|(_, property)| is_table(root, property));
+
+    if !path.is_empty() && (!keys.is_empty() || tables.is_empty()) {
+        out.push('\n');
+        if let Some(text) = description.or(resolved["description"].as_str()) {
+            out.push_str(&format!("# {}\n", text));
+        }
+        out.push_str(&format!("[{}]\n", path));
+    }
+    for (name, property) in keys {
+        if let Some(text) = property_description(root, property) {
+            out.push_str(&format!("# {}\n", text));
+        }
+        out.push_str(&format!(
+            "# {} = <{}>{}\n",
+            name,
+            type_hint(root, property),
+            if required.contains(&name.as_str()) {
+                " (required)"
+            } else {
+                ""
+            }
+        ));
+    }
+
+    let join = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", path, name)
+        }
+    };
+    for (name, property) in tables {
+        let entries = named_entries(root, property);
+        match entries {
+            Some((placeholder, entry)) => document_table(
+                root,
+                entry,
+                &join(&format!("{}.{}", name, placeholder)),
+                property_description(root, property),
+                out,
+            ),
+            None => document_table(
+                root,
+                property,
+                &join(name),
+                property_description(root, property),
+                out,
+            ),
+        }
+    }
+}
+
+/// Whether a property is written as a TOML table rather than a value
+fn is_table(root: &Value, property: &Value) -> bool {
+    let resolved = resolve(root, property);
+    resolved["properties"].is_object() || named_entries(root, property).is_some()
+}
+
+/// Placeholder and schema of the entries of a table keyed by user-chosen
+/// names, e.g. `[modules."<pattern>"]`
+fn named_entries<'s>(root: &'s Value, property: &'s Value) -> Option<(&'static str, &'s Value)> {
+    let resolved = resolve(root, property);
+    let (placeholder, entry) = match resolved["patternProperties"].as_object() {
+        Some(patterns) => ("\"<pattern>\"", patterns.values().next()?),
+        None => ("<name>", &resolved["additionalProperties"]),
+    };
+    resolve(root, entry)["properties"]
+        .is_object()
+        .then_some((placeholder, entry))
+}
+
+fn property_description<'s>(root: &'s Value, property: &'s Value) -> Option<&'s str> {
+    property["description"]
+        .as_str()
+        .or(resolve(root, property)["description"].as_str())
+}
+
+/// Expected value of a property, e.g. `integer >= 1` or `"tlc" | "apalache"`
+fn type_hint(root: &Value, property: &Value) -> String {
+    let property = resolve(root, property);
+    if let Some(options) = property["enum"].as_array() {
+        return options
+            .iter()
+            .filter(|option| !option.is_null())
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+
+    let kinds: Vec<&str> = match &property["type"] {
+        Value::String(kind) => vec![kind.as_str()],
+        Value::Array(kinds) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .filter(|kind| *kind != "null")
+            .collect(),
+        _ => vec!["any"],
+    };
+    let mut hint = kinds
+        .iter()
+        .map(|kind| match *kind {
+            "array" => format!("array of {}", type_hint(root, &property["items"])),
+            "object" => match &property["additionalProperties"] {
+                Value::Bool(false) | Value::Null => "table".to_string(),
+                Value::Bool(true) => "table of any".to_string(),
+                values => format!("table of {}", type_hint(root, values)),
+            },
+            kind => kind.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
+    if let Some(minimum) = property["minimum"].as_f64() {
+        hint.push_str(&format!(" >= {}", minimum));
+    }
+    if let Some(maximum) = property["maximum"].as_f64() {
+        hint.push_str(&format!(" <= {}", maximum));
+    }
+    hint
+}
+
+/// Closest known key to a misspelled one, if any is similar enough
+fn closest<'k>(key: &str, known: &'k [String]) -> Option<&'k str> {
+    known
+        .iter()
+        .map(|candidate| (strsim::jaro_winkler(key, candidate), candidate))
+        .filter(|(score, _)| *score >= 0.8)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Let every property that is not `required`, and every definition, be `null`
+fn allow_null_for_optional(schema: &mut Value) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+
+    let required: Vec<String> = object
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| {
+            required
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(properties) = object.get_mut("properties").and_then(Value::as_object_mut) {
+        for (name, property) in properties.iter_mut() {
+            if !required.contains(name) {
+                allow_null(property);
+            }
+            allow_null_for_optional(property);
+        }
+    }
+    if let Some(definitions) = object.get_mut("definitions").and_then(Value::as_object_mut) {
+        for definition in definitions.values_mut() {
+            allow_null(definition);
+            allow_null_for_optional(definition);
+        }
+    }
+    if let Some(patterns) = object
+        .get_mut("patternProperties")
+        .and_then(Value::as_object_mut)
+    {
+        patterns.values_mut().for_each(allow_null_for_optional);
+    }
+    for key in ["additionalProperties", "items"] {
+        if let Some(nested) = object.get_mut(key) {
+            allow_null_for_optional(nested);
+        }
+    }
+}
+
+fn allow_null(schema: &mut Value) {
+    match schema.get_mut("type") {
+        Some(Value::String(kind)) => {
+            let kind = std::mem::take(kind);
+            schema["type"] = json!([kind, "null"]);
+        }
+        Some(Value::Array(kinds)) if !kinds.contains(&json!("null")) => kinds.push(json!("null")),
+        _ => {}
+    }
+    if let Some(Value::Array(options)) = schema.get_mut("enum") {
+        if !options.contains(&Value::Null) {
+            options.push(Value::Null);
+        }
     }
 }
 
@@ -620,6 +1651,19 @@ pub struct ValidationErrorDetail {
     pub suggestion: Option<String>,
 }
 
+impl std::fmt::Display for ValidationErrorDetail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)?;
+        if let Some(expected) = &self.expected_value {
+            write!(f, " (expected {})", expected)?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ". {}", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ValidationWarning {
     pub field: String,
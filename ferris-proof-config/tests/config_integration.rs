@@ -1,7 +1,12 @@
 use ferris_proof_config::attributes::parse_verification_attributes;
-use ferris_proof_config::ConfigManager;
-use ferris_proof_core::{EnforcementMode, Technique, VerificationLevel};
+use ferris_proof_config::config::ProptestConfig;
+use ferris_proof_config::{Config, ConfigManager, ProptestRunner, SchemaValidator};
+use ferris_proof_core::gates::GateRule;
+use ferris_proof_core::normalization::NormalizationPolicy;
+use ferris_proof_core::plugins::ToolEnv;
+use ferris_proof_core::{EnforcementMode, Layer, Severity, Technique, VerificationLevel};
 use std::fs;
+use std::time::Duration;
 use tempfile::TempDir;
 
 #[test]
@@ -119,6 +124,225 @@ enabled_techniques = ["TypeSafety", "PropertyTests"]
     assert_eq!(effective_config.level, VerificationLevel::Standard); // Default from root
 }
 
+#[test]
+fn test_release_gates_from_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let root_config = r#"
+[profile]
+level = "standard"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+
+[gates.crypto-formal]
+modules = "crypto::*"
+require_layer = "formal"
+within_days = 7
+
+[gates.payments]
+modules = "payment::*"
+deny = ["Error", "Critical"]
+"#;
+    fs::write(project_root.join("ferrisproof.toml"), root_config).unwrap();
+
+    let gates = ConfigManager::from_project_root(project_root)
+        .unwrap()
+        .release_gates()
+        .unwrap();
+    assert_eq!(gates.len(), 2);
+    assert_eq!(gates[0].name, "crypto-formal");
+    assert_eq!(
+        gates[0].rules,
+        vec![GateRule::RequireLayer {
+            layer: Layer::Formal,
+            within: Some(std::time::Duration::from_secs(7 * 86_400)),
+        }]
+    );
+    assert_eq!(
+        gates[1].rules,
+        vec![GateRule::Deny(vec![Severity::Error, Severity::Critical])]
+    );
+
+    let invalid = root_config.replace("require_layer = \"formal\"\n", "");
+    fs::write(project_root.join("ferrisproof.toml"), invalid).unwrap();
+    assert!(ConfigManager::from_project_root(project_root)
+        .unwrap()
+        .release_gates()
+        .is_err());
+}
+
+#[test]
+fn test_normalization_policies_from_layers() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let root_config = r#"
+[profile]
+level = "formal"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests", "FormalSpecs"]
+
+[layers.formal]
+requires = ["property_based"]
+
+[layers.formal.normalization]
+ignore_bodies = true
+strip_doc_comments = true
+"#;
+    fs::write(project_root.join("ferrisproof.toml"), root_config).unwrap();
+
+    let manager = ConfigManager::from_project_root(project_root).unwrap();
+    manager.validate().unwrap();
+    let policies = manager.normalization_policies().unwrap();
+    let formal = policies.for_layer(Layer::Formal);
+    assert!(formal.ignore_bodies && formal.strip_doc_comments);
+    // Unset fields keep the default
+    assert!(formal.strip_cfg_attrs && formal.keep_macros);
+    assert_eq!(
+        policies.for_layer(Layer::PropertyBased),
+        NormalizationPolicy::default()
+    );
+}
+
+#[test]
+fn test_cache_ttls_per_layer() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let root_config = r#"
+[profile]
+level = "formal"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests", "FormalSpecs"]
+
+[thresholds]
+max_verification_time = 300
+max_memory_usage = 2147483648
+cache_ttl = 3600
+
+[cache.ttl]
+formal_specs = "30d"
+property_based = "24h"
+"#;
+    fs::write(project_root.join("ferrisproof.toml"), root_config).unwrap();
+
+    let manager = ConfigManager::from_project_root(project_root).unwrap();
+    manager.validate().unwrap();
+    let ttls = manager.cache_ttls().unwrap();
+    let day = Duration::from_secs(24 * 60 * 60);
+    assert_eq!(ttls.for_layer(Layer::Formal), 30 * day);
+    assert_eq!(ttls.for_layer(Layer::PropertyBased), day);
+    // Layers without their own TTL keep the global threshold
+    assert_eq!(ttls.for_layer(Layer::TypeLevel), Duration::from_secs(3600));
+
+    fs::write(
+        project_root.join("ferrisproof.toml"),
+        root_config.replace("property_based", "fuzzing"),
+    )
+    .unwrap();
+    let manager = ConfigManager::from_project_root(project_root).unwrap();
+    assert!(manager
+        .validate()
+        .unwrap_err()
+        .to_string()
+        .contains("fuzzing"));
+}
+
+#[test]
+fn test_tool_env_allow_list() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let root_config = r#"
+[profile]
+level = "formal"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests", "FormalSpecs"]
+
+[tools.tla_plus.env]
+pass = ["JAVA_HOME"]
+set = { TLA_TOOLS = "/opt/tla/tla2tools.jar" }
+"#;
+    fs::write(project_root.join("ferrisproof.toml"), root_config).unwrap();
+
+    let manager = ConfigManager::from_project_root(project_root).unwrap();
+    manager.validate().unwrap();
+    let env = &manager.tool_envs().unwrap()["tla-plus"];
+    assert_eq!(env.pass, vec!["JAVA_HOME".to_string()]);
+    assert_eq!(env.set["TLA_TOOLS"], "/opt/tla/tla2tools.jar");
+
+    fs::write(
+        project_root.join("ferrisproof.toml"),
+        root_config.replace("JAVA_HOME", "JAVA=HOME"),
+    )
+    .unwrap();
+    let manager = ConfigManager::from_project_root(project_root).unwrap();
+    let error = manager.validate().unwrap_err();
+    assert!(error.to_string().contains("[tools] tla-plus env"));
+    assert!(error.to_string().contains("JAVA=HOME"));
+    assert_eq!(error.code(), "FP-CF-001");
+
+    let env = ToolEnv {
+        pass: vec![String::new()],
+        ..ToolEnv::default()
+    };
+    assert_eq!(env.validate().unwrap_err().code(), "FP-CF-001");
+}
+
+#[test]
+fn test_enforcement_policy_module_overrides() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let root_config = r#"
+[profile]
+level = "standard"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+
+[enforcement]
+warning = ["Warning", "Info"]
+
+[modules."crypto::*".enforcement_policy]
+error = ["Warning", "Error", "Critical"]
+"#;
+
+    fs::write(project_root.join("ferrisproof.toml"), root_config).unwrap();
+    fs::create_dir_all(project_root.join("src/crypto")).unwrap();
+
+    let config_manager = ConfigManager::from_project_root(project_root).unwrap();
+
+    let policy = config_manager
+        .for_file(&project_root.join("src/net.rs"))
+        .enforcement_policy;
+    assert_eq!(
+        policy.outcome(&Severity::Info, EnforcementMode::Error),
+        EnforcementMode::Warning
+    );
+    assert_eq!(
+        policy.outcome(&Severity::Critical, EnforcementMode::Error),
+        EnforcementMode::Error
+    );
+
+    // The module keeps the root's warning list and escalates warnings to errors
+    let policy = config_manager
+        .for_file(&project_root.join("src/crypto/aes.rs"))
+        .enforcement_policy;
+    assert_eq!(
+        policy.outcome(&Severity::Warning, EnforcementMode::Error),
+        EnforcementMode::Error
+    );
+    assert_eq!(
+        policy.outcome(&Severity::Info, EnforcementMode::Error),
+        EnforcementMode::Warning
+    );
+    assert_eq!(
+        policy.outcome(&Severity::Warning, EnforcementMode::Warning),
+        EnforcementMode::Warning
+    );
+}
+
 #[test]
 fn test_verification_attribute_parsing() {
     // Test simple level attribute
@@ -218,6 +442,141 @@ enabled_techniques = ["PropertyTests"]
     assert!(config_manager2.validate().is_err());
 }
 
+#[test]
+fn test_schema_accepts_serialized_configs() {
+    let validator = SchemaValidator::new().unwrap();
+
+    // Unset optional fields serialize as null
+    let mut config = Config::default();
+    config.tools.tla_plus = Some(Default::default());
+    config.tools.proptest = Some(ProptestConfig {
+        cases: Some(500),
+        max_shrink_iters: None,
+        runner: Some(ProptestRunner::Nextest),
+        retries: None,
+        backoff: None,
+        env: None,
+    });
+    let json = serde_json::to_value(&config).unwrap();
+    assert!(json["tools"]["kani"].is_null());
+
+    let result = validator.validate_with_details(&json);
+    assert!(result.is_valid, "{:?}", result.errors);
+}
+
+#[test]
+fn test_exported_schema() {
+    // The exported JSON schema is the one configs are validated against
+    let schema = SchemaValidator::config_schema();
+    let config = serde_json::to_value(Config::default()).unwrap();
+    assert!(jsonschema::JSONSchema::compile(&schema)
+        .unwrap()
+        .is_valid(&config));
+
+    let doc = SchemaValidator::toml_doc();
+    assert!(doc.starts_with("# FerrisProof Configuration\n"));
+    assert!(doc.contains(
+        "[profile]\n# List of verification techniques to enable\n# enabled_techniques = <array of "
+    ));
+    assert!(doc.contains("[tools.tla_plus]\n"));
+    assert!(doc.contains("# workers = <integer >= 1>\n"));
+    assert!(doc.contains("# backend = <\"tlc\" | \"apalache\">\n"));
+    assert!(doc.contains("[modules.\"<pattern>\"]\n"));
+    assert!(doc.contains("[gates.<name>]\n"));
+    // Every header is a table TOML accepts once placeholders are filled in
+    for header in doc.lines().filter(|line| line.starts_with('[')) {
+        let filled = header
+            .replace("<pattern>", "crypto::*")
+            .replace("<name>", "x");
+        assert!(filled.parse::<toml::Table>().is_ok(), "{}", header);
+    }
+}
+
+#[test]
+fn test_schema_errors_name_fields_and_suggest_fixes() {
+    let validator = SchemaValidator::new().unwrap();
+    let config: toml::Value = toml::from_str(
+        r#"
+[profile]
+level = "Strict"
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "property_tests"]
+
+[tools.tla_plus]
+tlc_pth = "/opt/tlc"
+"#,
+    )
+    .unwrap();
+    let result = validator.validate_with_details(&serde_json::to_value(config).unwrap());
+    assert!(!result.is_valid);
+
+    let error = |field: &str| {
+        result
+            .errors
+            .iter()
+            .find(|e| e.field == field)
+            .unwrap_or_else(|| panic!("no error for {} in {:?}", field, result.errors))
+    };
+    assert_eq!(
+        error("profile.level").expected_value.as_deref(),
+        Some("one of: minimal, standard, strict, formal")
+    );
+    assert!(error("profile.enabled_techniques[1]")
+        .suggestion
+        .as_deref()
+        .unwrap()
+        .contains("PascalCase"));
+    assert!(error("tools.tla_plus")
+        .suggestion
+        .as_deref()
+        .unwrap()
+        .contains("did you mean 'tlc_path'"));
+}
+
+#[test]
+fn test_module_configs_are_schema_validated() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+    fs::write(
+        project_root.join("ferrisproof.toml"),
+        r#"
+[profile]
+level = "standard"
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+"#,
+    )
+    .unwrap();
+
+    // Module files may override any section
+    let module_dir = project_root.join("src/payment");
+    fs::create_dir_all(&module_dir).unwrap();
+    let module_config = |cases: u32| {
+        format!(
+            r#"
+[profile]
+level = "strict"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests", "SessionTypes"]
+
+[tools.proptest]
+cases = {}
+"#,
+            cases
+        )
+    };
+    let file = module_dir.join("lib.rs");
+
+    fs::write(module_dir.join("ferrisproof.toml"), module_config(5000)).unwrap();
+    let manager = ConfigManager::from_project_root(project_root).unwrap();
+    assert_eq!(manager.for_file(&file).level, VerificationLevel::Strict);
+
+    // A module file failing the schema is skipped like one failing to parse
+    fs::write(module_dir.join("ferrisproof.toml"), module_config(0)).unwrap();
+    let manager = ConfigManager::from_project_root(project_root).unwrap();
+    assert_eq!(manager.for_file(&file).level, VerificationLevel::Standard);
+}
+
 #[test]
 fn test_configuration_precedence() {
     let temp_dir = TempDir::new().unwrap();
@@ -289,3 +648,116 @@ enabled_techniques = ["TypeSafety", "PropertyTests"]
     assert_eq!(effective_config1.level, effective_config2.level);
     assert_eq!(effective_config1.enforcement, effective_config2.enforcement);
 }
+
+#[test]
+fn test_crate_qualified_module_patterns() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let write = |path: &str, content: &str| {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    };
+
+    write(
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\nresolver = \"2\"\n",
+    );
+    for package in ["billing", "auth-core"] {
+        write(
+            &format!("crates/{}/Cargo.toml", package),
+            &format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+                package
+            ),
+        );
+        write(
+            &format!("crates/{}/src/lib.rs", package),
+            "pub mod crypto;\n",
+        );
+        write(
+            &format!("crates/{}/src/crypto/mod.rs", package),
+            "pub mod aes;\n",
+        );
+        write(&format!("crates/{}/src/crypto/aes.rs", package), "");
+    }
+    write(
+        "ferrisproof.toml",
+        r#"
+[profile]
+level = "standard"
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+
+# Unqualified patterns apply in every crate
+[modules."crypto::aes"]
+level = "strict"
+
+[modules."auth-core::*"]
+level = "formal"
+"#,
+    );
+
+    let manager = ConfigManager::from_project_root(root).unwrap();
+    let level = |file: &str| manager.for_file(&root.join(file)).level;
+
+    assert_eq!(
+        level("crates/billing/src/crypto/aes.rs"),
+        VerificationLevel::Strict
+    );
+    // Crate-qualified patterns win over more specific unqualified ones
+    assert_eq!(
+        level("crates/auth-core/src/crypto/aes.rs"),
+        VerificationLevel::Formal
+    );
+    assert_eq!(
+        level("crates/auth-core/src/lib.rs"),
+        VerificationLevel::Formal
+    );
+    assert_eq!(
+        level("crates/billing/src/lib.rs"),
+        VerificationLevel::Standard
+    );
+}
+
+#[test]
+fn test_violation_budget_accepts_total_or_per_severity() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let config_with = |max_violations: &str| {
+        format!(
+            r#"
+[profile]
+level = "standard"
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+
+[thresholds]
+max_verification_time = 300
+max_memory_usage = 2147483648
+cache_ttl = 86400
+max_violations = {}
+"#,
+            max_violations
+        )
+    };
+
+    fs::write(root.join("ferrisproof.toml"), config_with("120")).unwrap();
+    let manager = ConfigManager::from_project_root(root).unwrap();
+    let budget = &manager.root_config().thresholds.max_violations;
+    assert_eq!(budget.total, Some(120));
+    assert_eq!(budget.error, None);
+
+    fs::write(
+        root.join("ferrisproof.toml"),
+        config_with("{ total = 200, error = 0 }"),
+    )
+    .unwrap();
+    let manager = ConfigManager::from_project_root(root).unwrap();
+    let budget = &manager.root_config().thresholds.max_violations;
+    assert_eq!(budget.total, Some(200));
+    assert_eq!(budget.error, Some(0));
+
+    fs::write(root.join("ferrisproof.toml"), config_with("{ errors = 0 }")).unwrap();
+    assert!(ConfigManager::from_project_root(root).is_err());
+}
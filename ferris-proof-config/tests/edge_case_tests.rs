@@ -184,14 +184,18 @@ upload_reports = false
 
     fs::write(project_root.join("ferrisproof.toml"), invalid_config).unwrap();
 
-    let config_manager = ConfigManager::from_project_root(project_root).unwrap();
-
-    // Validation should fail
-    let validation_result = config_manager.validate();
-    assert!(validation_result.is_err());
-
-    let error_message = validation_result.unwrap_err().to_string();
-    assert!(error_message.contains("must be > 0"));
+    // Schema validation rejects the file while loading, naming each field
+    let error_message = ConfigManager::from_project_root(project_root)
+        .unwrap_err()
+        .to_string();
+    assert!(error_message.contains("ferrisproof.toml"));
+    for field in [
+        "thresholds.max_verification_time",
+        "thresholds.max_memory_usage",
+        "thresholds.cache_ttl",
+    ] {
+        assert!(error_message.contains(field), "{}", error_message);
+    }
 }
 
 #[test]
@@ -238,6 +242,187 @@ upload_reports = false
     assert!(error_message.contains("Minimal level must include TypeSafety"));
 }
 
+#[test]
+fn test_invalid_redaction_pattern_validation() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let config = r#"
+[profile]
+level = "standard"
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+
+[artifacts]
+max_bytes = 1048576
+redact = ["/home/[^/]+", "token=(\\w+"]
+"#;
+
+    fs::write(project_root.join("ferrisproof.toml"), config).unwrap();
+
+    let config_manager = ConfigManager::from_project_root(project_root).unwrap();
+    assert_eq!(
+        config_manager.root_config().artifacts.max_bytes,
+        Some(1048576)
+    );
+
+    let validation_result = config_manager.validate();
+    assert!(validation_result.is_err());
+    assert!(validation_result
+        .unwrap_err()
+        .to_string()
+        .contains("Invalid redaction pattern"));
+}
+
+#[test]
+fn test_mutation_thresholds() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let config = r#"
+[profile]
+level = "standard"
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "PropertyTests", "MutationTesting"]
+
+[tools.mutants]
+min_score = 0.6
+
+[modules."crypto::*"]
+mutation_threshold = 0.9
+
+[modules."ui::*"]
+level = "minimal"
+"#;
+
+    fs::write(project_root.join("ferrisproof.toml"), config).unwrap();
+
+    let config_manager = ConfigManager::from_project_root(project_root).unwrap();
+    assert!(config_manager.validate().is_ok());
+    let thresholds = config_manager.mutation_thresholds();
+    assert_eq!(thresholds.len(), 1);
+    assert_eq!(thresholds.get("crypto::*"), Some(&0.9));
+
+    let out_of_range = config.replace("mutation_threshold = 0.9", "mutation_threshold = 1.5");
+    fs::write(project_root.join("ferrisproof.toml"), out_of_range).unwrap();
+    assert!(ConfigManager::from_project_root(project_root)
+        .and_then(|manager| manager.validate())
+        .is_err());
+}
+
+#[test]
+fn test_reload_if_changed() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+    let config_path = project_root.join("ferrisproof.toml");
+    let source = project_root.join("src/lib.rs");
+
+    let config = r#"
+[profile]
+level = "standard"
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+"#;
+    fs::write(&config_path, config).unwrap();
+
+    let mut config_manager = ConfigManager::from_project_root(project_root).unwrap();
+    let before = config_manager.for_file(&source);
+    assert!(config_manager.changed_config_files().is_empty());
+    assert!(config_manager
+        .reload_if_changed(std::slice::from_ref(&source))
+        .unwrap()
+        .is_none());
+
+    // Bump the mtime explicitly so coarse filesystem timestamps still differ
+    fs::write(
+        &config_path,
+        config.replace("enforcement = \"warning\"", "enforcement = \"error\""),
+    )
+    .unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&config_path)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+        .unwrap();
+    fs::create_dir_all(project_root.join("src/ui")).unwrap();
+    fs::write(project_root.join("src/ui/ferrisproof.toml"), config).unwrap();
+
+    assert_eq!(
+        config_manager.changed_config_files(),
+        vec![config_path, project_root.join("src/ui/ferrisproof.toml")]
+    );
+    let reload = config_manager
+        .reload_if_changed(std::slice::from_ref(&source))
+        .unwrap()
+        .unwrap();
+    assert_eq!(reload.affected_files, vec![source.clone()]);
+    assert!(reload.stale_config_hashes.contains(&before.config_hash));
+
+    let after = config_manager.for_file(&source);
+    assert_eq!(after.enforcement, EnforcementMode::Error);
+    assert_ne!(after.config_hash, before.config_hash);
+    assert!(config_manager.changed_config_files().is_empty());
+}
+
+#[test]
+fn test_named_tla_models() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let config = r#"
+[profile]
+level = "formal"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests", "FormalSpecs"]
+
+[tools.tla_plus.models.small]
+constants = { Nodes = 3, Faulty = false }
+
+[tools.tla_plus.models.large]
+constants = { Nodes = 7, Faulty = true }
+"#;
+    fs::write(project_root.join("ferrisproof.toml"), config).unwrap();
+    fs::create_dir_all(project_root.join("specs/nightly")).unwrap();
+    fs::write(
+        project_root.join("specs/nightly/ferrisproof.toml"),
+        "[profile]\nlevel = \"formal\"\nenforcement = \"error\"\nenabled_techniques = []\n\n[tools.tla_plus]\nmodel = \"large\"\n",
+    )
+    .unwrap();
+
+    let config_manager = ConfigManager::from_project_root(project_root).unwrap();
+    let spec = project_root.join("specs/consensus.tla");
+    let nightly_spec = project_root.join("specs/nightly/consensus.tla");
+
+    assert!(config_manager.tla_model_for(&spec, None).unwrap().is_none());
+
+    let (name, small) = config_manager
+        .tla_model_for(&spec, Some("small"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(name, "small");
+    assert_eq!(small.constants.get("Nodes").unwrap(), "3");
+    assert_eq!(small.constants.get("Faulty").unwrap(), "FALSE");
+
+    // The module directory selects the exhaustive model from the root config
+    let (name, _) = config_manager
+        .tla_model_for(&nightly_spec, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(name, "large");
+    let tool_config = config_manager
+        .tla_tool_config(&nightly_spec, Some("small"))
+        .unwrap();
+    assert_eq!(tool_config["model"], "small");
+    assert_eq!(tool_config["models"]["large"]["constants"]["Nodes"], "7");
+
+    let error = config_manager
+        .tla_model_for(&spec, Some("huge"))
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("configured: large, small"));
+}
+
 #[test]
 fn test_malformed_glob_patterns() {
     let temp_dir = TempDir::new().unwrap();
@@ -501,3 +686,60 @@ enabled_techniques = ["TypeSafety", "PropertyTests", "FormalSpecs"]
     assert_eq!(effective_config.level, VerificationLevel::Standard);
     assert_eq!(effective_config.enforcement, EnforcementMode::Warning);
 }
+
+#[test]
+fn test_generate_strategies_section() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let config = r#"
+[profile]
+level = "standard"
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+
+[generate.proptest.strategies]
+"crate::money::Amount" = "(0u64..1_000_000).prop_map(Amount::from_cents)"
+"#;
+    fs::write(project_root.join("ferrisproof.toml"), config).unwrap();
+
+    let config_manager = ConfigManager::from_project_root(project_root).unwrap();
+    let strategies = &config_manager.root_config().generate.proptest.strategies;
+    assert_eq!(
+        strategies.get("crate::money::Amount").map(String::as_str),
+        Some("(0u64..1_000_000).prop_map(Amount::from_cents)")
+    );
+    assert!(config_manager.validate().is_ok());
+}
+
+#[test]
+fn test_generate_snapshots_section() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path();
+
+    let config = r#"
+[profile]
+level = "standard"
+enforcement = "warning"
+enabled_techniques = ["TypeSafety", "PropertyTests"]
+
+[generate.snapshots]
+modules = ["crate::report::*"]
+
+[generate.snapshots.inputs]
+"crate::report::render" = "&shop::Report::sample()"
+"#;
+    fs::write(project_root.join("ferrisproof.toml"), config).unwrap();
+
+    let config_manager = ConfigManager::from_project_root(project_root).unwrap();
+    let snapshots = &config_manager.root_config().generate.snapshots;
+    assert_eq!(snapshots.modules, vec!["crate::report::*"]);
+    assert_eq!(
+        snapshots
+            .inputs
+            .get("crate::report::render")
+            .map(String::as_str),
+        Some("&shop::Report::sample()")
+    );
+    assert!(config_manager.validate().is_ok());
+}
@@ -0,0 +1,669 @@
+//! Public API snapshots and the invariants declared on them
+//!
+//! The API-surface layer records every public item of each crate in the
+//! project, much like `cargo public-api`, together with the contracts and
+//! refinements declared on it. Each run is compared with the snapshot kept
+//! in the run history, so a change that weakens a declared guarantee, adds
+//! a precondition or removes a verified item is reported even when the
+//! other layers still pass.
+
+use crate::cache::{module_path_of, wildcard_match};
+use crate::discovery::files_with_extension;
+use crate::types::*;
+use anyhow::{Context, Result};
+use quote::ToTokens;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use syn::{Attribute, Fields, ImplItem, Item, TraitItem, Visibility};
+use tracing::{debug, warn};
+
+/// Violation id for a public item whose declared invariant was weakened
+pub const INVARIANT_BROKEN: &str = "FP-VR-004";
+
+/// Violation id for a verified public item that was removed or lost its verification
+pub const VERIFIED_ITEM_REMOVED: &str = "FP-VR-005";
+
+/// Directories never searched for member crates
+const SKIPPED_DIRS: [&str; 3] = ["target", "node_modules", "vendor"];
+
+/// How deep below the project root member crates are searched for
+const MAX_CRATE_DEPTH: usize = 2;
+
+/// Kind of clause declared on a public item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvariantKind {
+    /// Precondition, from `#[contract(requires = ..)]` or `#[requires(..)]`
+    Requires,
+    /// Postcondition, from `#[contract(ensures = ..)]` or `#[ensures(..)]`
+    Ensures,
+    /// Type invariant, from `#[invariant(..)]`
+    Invariant,
+    /// Field refinement, from `#[refine(..)]`
+    Refine,
+}
+
+impl InvariantKind {
+    /// Preconditions bind callers, so adding one breaks them; every other
+    /// kind is a guarantee, so removing one does
+    pub fn is_precondition(self) -> bool {
+        self == InvariantKind::Requires
+    }
+}
+
+/// One clause declared on a public item, e.g. `requires: x >= 0`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Invariant {
+    pub kind: InvariantKind,
+    pub clause: String,
+}
+
+impl std::fmt::Display for Invariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = serde_json::to_value(self.kind)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        write!(f, "{}: {}", kind, self.clause)
+    }
+}
+
+/// A public item as seen by dependents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiItem {
+    /// `fn`, `struct`, `enum`, `trait`, `const`, `static` or `type`
+    pub kind: String,
+    /// Declaration without attributes, bodies or private fields
+    pub signature: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invariants: Vec<Invariant>,
+    /// Carries `#[verification]`, `#[contract]`, `#[derive(Verified)]` or any invariant
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verified: bool,
+    /// File relative to the project root
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Public items of every crate in a project, keyed by full path,
+/// e.g. `billing::invoice::Invoice::total`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApiSurface {
+    pub items: BTreeMap<String, ApiItem>,
+}
+
+/// How a change broke the surface recorded by an earlier run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiBreakKind {
+    /// A guarantee was removed or changed
+    GuaranteeWeakened(Invariant),
+    /// A precondition was added or changed
+    PreconditionAdded(Invariant),
+    /// A verified item no longer exists
+    VerifiedItemRemoved,
+    /// An item still exists but no longer carries any verification
+    VerificationDropped,
+}
+
+/// A breaking change to one public item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiBreak {
+    pub item: String,
+    pub kind: ApiBreakKind,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+impl ApiSurface {
+    /// Snapshot the library crates at and below the project root
+    ///
+    /// Binaries, `#[cfg(test)]` modules and items in private modules are
+    /// left out, as are trait implementations.
+    pub fn snapshot(project_root: &Path) -> Result<Self> {
+        let mut surface = Self::default();
+        for (crate_name, crate_root) in crate_roots(project_root) {
+            surface.add_crate(project_root, &crate_name, &crate_root)?;
+        }
+        debug!("API surface: {} public items", surface.items.len());
+        Ok(surface)
+    }
+
+    fn add_crate(
+        &mut self,
+        project_root: &Path,
+        crate_name: &str,
+        crate_root: &Path,
+    ) -> Result<()> {
+        let src = crate_root.join("src");
+        let mut private_modules = BTreeSet::new();
+        let mut items = BTreeMap::new();
+        for path in files_with_extension(&src, &["rs"]) {
+            let relative = path.strip_prefix(&src).unwrap_or(&path);
+            if relative == Path::new("main.rs") || relative.starts_with("bin") {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {:?}", path))?;
+            let syntax = match syn::parse_file(&content) {
+                Ok(syntax) => syntax,
+                Err(e) => {
+                    warn!("Skipping API surface of {:?}: {}", path, e);
+                    continue;
+                }
+            };
+
+            let module = match module_path_of(&format!("src/{}", relative.display())).as_str() {
+                "crate" => crate_name.to_string(),
+                module => format!("{}::{}", crate_name, module),
+            };
+            let mut collector = SurfaceCollector {
+                file: path.strip_prefix(project_root).unwrap_or(&path),
+                content: &content,
+                items: &mut items,
+                private_modules: &mut private_modules,
+            };
+            collector.visit_items(&syntax.items, &module);
+        }
+
+        self.items.extend(items.into_iter().filter(|(path, _)| {
+            !private_modules
+                .iter()
+                .any(|module| path.starts_with(&format!("{}::", module)))
+        }));
+        Ok(())
+    }
+
+    /// Items of `previous` that this surface breaks, except those matching an
+    /// `acknowledge` pattern, where `*` matches any substring
+    pub fn breaks(&self, previous: &ApiSurface, acknowledge: &[String]) -> Vec<ApiBreak> {
+        let mut breaks = Vec::new();
+        for (path, before) in &previous.items {
+            if acknowledge
+                .iter()
+                .any(|pattern| wildcard_match(pattern, path))
+            {
+                continue;
+            }
+            let Some(after) = self.items.get(path) else {
+                if before.verified {
+                    breaks.push(ApiBreak {
+                        item: path.clone(),
+                        kind: ApiBreakKind::VerifiedItemRemoved,
+                        file: before.file.clone(),
+                        line: before.line,
+                    });
+                }
+                continue;
+            };
+
+            let located = |kind| ApiBreak {
+                item: path.clone(),
+                kind,
+                file: after.file.clone(),
+                line: after.line,
+            };
+            if before.verified && !after.verified {
+                breaks.push(located(ApiBreakKind::VerificationDropped));
+            }
+            for invariant in &before.invariants {
+                if !invariant.kind.is_precondition() && !after.invariants.contains(invariant) {
+                    breaks.push(located(ApiBreakKind::GuaranteeWeakened(invariant.clone())));
+                }
+            }
+            for invariant in &after.invariants {
+                if invariant.kind.is_precondition() && !before.invariants.contains(invariant) {
+                    breaks.push(located(ApiBreakKind::PreconditionAdded(invariant.clone())));
+                }
+            }
+        }
+        breaks
+    }
+}
+
+/// One violation per breaking change, at the changed item
+pub fn api_break_violations(breaks: &[ApiBreak], severity: Severity) -> Vec<Violation> {
+    breaks
+        .iter()
+        .map(|api_break| {
+            let (id, message) = match &api_break.kind {
+                ApiBreakKind::GuaranteeWeakened(invariant) => (
+                    INVARIANT_BROKEN,
+                    format!("{} no longer guarantees `{}`", api_break.item, invariant),
+                ),
+                ApiBreakKind::PreconditionAdded(invariant) => (
+                    INVARIANT_BROKEN,
+                    format!("{} now requires `{}`", api_break.item, invariant.clause),
+                ),
+                ApiBreakKind::VerifiedItemRemoved => (
+                    VERIFIED_ITEM_REMOVED,
+                    format!("Verified public item {} was removed", api_break.item),
+                ),
+                ApiBreakKind::VerificationDropped => (
+                    VERIFIED_ITEM_REMOVED,
+                    format!("Public item {} is no longer verified", api_break.item),
+                ),
+            };
+            Violation {
+                id: id.to_string(),
+                severity: severity.clone(),
+                location: Location {
+                    line: u32::try_from(api_break.line).ok().filter(|line| *line > 0),
+                    ..Location::file(&api_break.file)
+                },
+                message,
+                suggestion: Some(format!(
+                    "Restore the previous contract, or add \"{}\" to [api_surface] acknowledge",
+                    api_break.item
+                )),
+                rule: "api_surface".to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Library crates at and below the project root, with their crate names
+fn crate_roots(project_root: &Path) -> Vec<(String, PathBuf)> {
+    fn visit(dir: &Path, depth: usize, roots: &mut Vec<(String, PathBuf)>) {
+        if let Some(name) = package_name(dir) {
+            if dir.join("src").is_dir() {
+                roots.push((name.replace('-', "_"), dir.to_path_buf()));
+            }
+        }
+        if depth == MAX_CRATE_DEPTH {
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut dirs: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.is_dir())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        !name.starts_with('.') && name != "src" && !SKIPPED_DIRS.contains(&name)
+                    })
+            })
+            .collect();
+        dirs.sort();
+        for dir in dirs {
+            visit(&dir, depth + 1, roots);
+        }
+    }
+
+    let mut roots = Vec::new();
+    visit(project_root, 0, &mut roots);
+    roots
+}
+
+/// `name` of the `[package]` table in a directory's `Cargo.toml`
+fn package_name(dir: &Path) -> Option<String> {
+    let manifest = std::fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    let package = manifest.split("[package]").nth(1)?;
+    let package = package.split("\n[").next().unwrap_or(package);
+    Regex::new(r#"(?m)^\s*name\s*=\s*"([^"]+)""#)
+        .ok()?
+        .captures(package)
+        .map(|captures| captures[1].to_string())
+}
+
+struct SurfaceCollector<'a> {
+    file: &'a Path,
+    content: &'a str,
+    items: &'a mut BTreeMap<String, ApiItem>,
+    /// Modules declared without `pub`, whose items are unreachable
+    private_modules: &'a mut BTreeSet<String>,
+}
+
+impl SurfaceCollector<'_> {
+    fn visit_items(&mut self, items: &[Item], module: &str) {
+        for item in items {
+            match item {
+                Item::Fn(f) if is_public(&f.vis) => {
+                    let name = f.sig.ident.to_string();
+                    let signature = format!("pub {}", f.sig.to_token_stream());
+                    self.record(module, "fn", &name, signature, &f.attrs, Vec::new());
+                }
+                Item::Struct(s) if is_public(&s.vis) => {
+                    let mut public = s.clone();
+                    public.attrs.clear();
+                    let mut refinements = Vec::new();
+                    if let Fields::Named(fields) = &mut public.fields {
+                        fields.named = std::mem::take(&mut fields.named)
+                            .into_iter()
+                            .filter(|field| is_public(&field.vis))
+                            .collect();
+                    }
+                    for field in public.fields.iter_mut() {
+                        let name = field
+                            .ident
+                            .as_ref()
+                            .map_or_else(String::new, |ident| format!("{}: ", ident));
+                        refinements.extend(
+                            field
+                                .attrs
+                                .iter()
+                                .filter(|attr| attr.path().is_ident("refine"))
+                                .map(|attr| Invariant {
+                                    kind: InvariantKind::Refine,
+                                    clause: format!("{}{}", name, attr_args(attr)),
+                                }),
+                        );
+                        field.attrs.clear();
+                    }
+                    let signature = public.to_token_stream().to_string();
+                    let name = s.ident.to_string();
+                    self.record(module, "struct", &name, signature, &s.attrs, refinements);
+                }
+                Item::Enum(e) if is_public(&e.vis) => {
+                    let mut public = e.clone();
+                    public.attrs.clear();
+                    for variant in public.variants.iter_mut() {
+                        variant.attrs.clear();
+                        variant.fields.iter_mut().for_each(|f| f.attrs.clear());
+                    }
+                    let signature = public.to_token_stream().to_string();
+                    let name = e.ident.to_string();
+                    self.record(module, "enum", &name, signature, &e.attrs, Vec::new());
+                }
+                Item::Trait(t) if is_public(&t.vis) => {
+                    let mut public = t.clone();
+                    public.attrs.clear();
+                    for trait_item in public.items.iter_mut() {
+                        match trait_item {
+                            TraitItem::Fn(f) => {
+                                f.attrs.clear();
+                                f.default = None;
+                                f.semi_token = Some(Default::default());
+                            }
+                            TraitItem::Const(c) => {
+                                c.attrs.clear();
+                                c.default = None;
+                            }
+                            TraitItem::Type(t) => t.attrs.clear(),
+                            _ => {}
+                        }
+                    }
+                    let signature = public.to_token_stream().to_string();
+                    let name = t.ident.to_string();
+                    self.record(module, "trait", &name, signature, &t.attrs, Vec::new());
+                }
+                Item::Const(c) if is_public(&c.vis) => {
+                    let signature = format!("pub const {}: {}", c.ident, c.ty.to_token_stream());
+                    let name = c.ident.to_string();
+                    self.record(module, "const", &name, signature, &c.attrs, Vec::new());
+                }
+                Item::Static(s) if is_public(&s.vis) => {
+                    let signature = format!("pub static {}: {}", s.ident, s.ty.to_token_stream());
+                    let name = s.ident.to_string();
+                    self.record(module, "static", &name, signature, &s.attrs, Vec::new());
+                }
+                Item::Type(t) if is_public(&t.vis) => {
+                    let mut public = t.clone();
+                    public.attrs.clear();
+                    let signature = public.to_token_stream().to_string();
+                    let name = t.ident.to_string();
+                    self.record(module, "type", &name, signature, &t.attrs, Vec::new());
+                }
+                Item::Mod(m) => {
+                    let path = format!("{}::{}", module, m.ident);
+                    if !is_public(&m.vis) || is_cfg_test(&m.attrs) {
+                        self.private_modules.insert(path.clone());
+                    }
+                    if let Some((_, items)) = &m.content {
+                        self.visit_items(items, &path);
+                    }
+                }
+                Item::Impl(i) if i.trait_.is_none() => {
+                    let syn::Type::Path(self_ty) = &*i.self_ty else {
+                        continue;
+                    };
+                    let Some(segment) = self_ty.path.segments.last() else {
+                        continue;
+                    };
+                    let owner = format!("{}::{}", module, segment.ident);
+                    for impl_item in &i.items {
+                        match impl_item {
+                            ImplItem::Fn(f) if is_public(&f.vis) => {
+                                let name = f.sig.ident.to_string();
+                                let signature = format!("pub {}", f.sig.to_token_stream());
+                                self.record(&owner, "fn", &name, signature, &f.attrs, Vec::new());
+                            }
+                            ImplItem::Const(c) if is_public(&c.vis) => {
+                                let signature =
+                                    format!("pub const {}: {}", c.ident, c.ty.to_token_stream());
+                                let name = c.ident.to_string();
+                                self.record(
+                                    &owner,
+                                    "const",
+                                    &name,
+                                    signature,
+                                    &c.attrs,
+                                    Vec::new(),
+                                );
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn record(
+        &mut self,
+        owner: &str,
+        kind: &str,
+        name: &str,
+        signature: String,
+        attrs: &[Attribute],
+        mut invariants: Vec<Invariant>,
+    ) {
+        invariants.extend(declared_invariants(attrs));
+        invariants.sort();
+        invariants.dedup();
+        let verified = !invariants.is_empty() || attrs.iter().any(marks_verified);
+        self.items.insert(
+            format!("{}::{}", owner, name),
+            ApiItem {
+                kind: kind.to_string(),
+                signature,
+                invariants,
+                verified,
+                file: self.file.to_path_buf(),
+                line: declaration_line(self.content, kind, name),
+            },
+        );
+    }
+}
+
+fn is_public(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("cfg") && attr_args(attr) == "test")
+}
+
+/// Whether an attribute marks its item as verified
+fn marks_verified(attr: &Attribute) -> bool {
+    let path = attr.path();
+    path.is_ident("verification")
+        || path.is_ident("contract")
+        || (path.is_ident("derive") && attr_args(attr).split(", ").any(|d| d == "Verified"))
+}
+
+/// Clauses of `#[contract(..)]`, `#[requires(..)]`, `#[ensures(..)]` and `#[invariant(..)]`
+fn declared_invariants(attrs: &[Attribute]) -> Vec<Invariant> {
+    let mut invariants = Vec::new();
+    for attr in attrs {
+        let path = attr.path();
+        if path.is_ident("contract") {
+            let parsed = attr.parse_nested_meta(|meta| {
+                let kind = if meta.path.is_ident("requires") {
+                    InvariantKind::Requires
+                } else if meta.path.is_ident("ensures") {
+                    InvariantKind::Ensures
+                } else {
+                    return Err(meta.error("expected `requires` or `ensures`"));
+                };
+                let clause: syn::Expr = meta.value()?.parse()?;
+                invariants.push(Invariant {
+                    kind,
+                    clause: clause.to_token_stream().to_string(),
+                });
+                Ok(())
+            });
+            if let Err(e) = parsed {
+                warn!("Ignoring malformed #[contract]: {}", e);
+            }
+        } else {
+            let kind = if path.is_ident("requires") {
+                InvariantKind::Requires
+            } else if path.is_ident("ensures") {
+                InvariantKind::Ensures
+            } else if path.is_ident("invariant") {
+                InvariantKind::Invariant
+            } else {
+                continue;
+            };
+            invariants.push(Invariant {
+                kind,
+                clause: attr_args(attr),
+            });
+        }
+    }
+    invariants
+}
+
+/// Tokens inside an attribute's parentheses, or empty for a bare attribute
+fn attr_args(attr: &Attribute) -> String {
+    match &attr.meta {
+        syn::Meta::List(list) => list.tokens.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Line of the first `keyword name` declaration, or 1 if it is not found
+fn declaration_line(content: &str, keyword: &str, name: &str) -> usize {
+    Regex::new(&format!(r"\b{}\s+{}\b", keyword, regex::escape(name)))
+        .ok()
+        .and_then(|re| re.find(content))
+        .map_or(1, |m| content[..m.start()].matches('\n').count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn surface(lib: &str) -> ApiSurface {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"billing-core\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src/lib.rs"), lib).unwrap();
+        ApiSurface::snapshot(temp_dir.path()).unwrap()
+    }
+
+    const BEFORE: &str = r#"
+pub struct Invoice {
+    #[refine(range = 0..=10_000)]
+    pub total: u32,
+    secret: u32,
+}
+
+impl Invoice {
+    #[contract(requires = amount > 0, ensures = result >= amount)]
+    pub fn charge(&self, amount: u32) -> u32 { amount }
+
+    fn internal(&self) {}
+}
+
+#[verification(property_test)]
+pub fn refund() {}
+
+mod private {
+    #[verification(formal)]
+    pub fn hidden() {}
+}
+"#;
+
+    #[test]
+    fn test_snapshot_keeps_public_items_and_invariants() {
+        let surface = surface(BEFORE);
+        let paths: Vec<&str> = surface.items.keys().map(String::as_str).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "billing_core::Invoice",
+                "billing_core::Invoice::charge",
+                "billing_core::refund"
+            ]
+        );
+
+        let invoice = &surface.items["billing_core::Invoice"];
+        assert!(invoice.verified);
+        assert!(!invoice.signature.contains("secret"));
+        assert_eq!(
+            invoice.invariants[0].to_string(),
+            "refine: total: range = 0 ..= 10_000"
+        );
+        assert_eq!(invoice.line, 2);
+
+        let charge = &surface.items["billing_core::Invoice::charge"];
+        assert_eq!(charge.invariants.len(), 2);
+        assert_eq!(charge.invariants[0].kind, InvariantKind::Requires);
+    }
+
+    #[test]
+    fn test_breaks_and_acknowledgments() {
+        let before = surface(BEFORE);
+        let after = surface(
+            r#"
+pub struct Invoice {
+    #[refine(range = 0..=10_000)]
+    pub total: u32,
+}
+
+impl Invoice {
+    #[contract(requires = amount > 10)]
+    pub fn charge(&self, amount: u32) -> u32 { amount }
+}
+"#,
+        );
+
+        let breaks = after.breaks(&before, &[]);
+        let kinds: Vec<&ApiBreakKind> = breaks.iter().map(|b| &b.kind).collect();
+        assert_eq!(breaks.len(), 3);
+        assert!(
+            matches!(kinds[0], ApiBreakKind::GuaranteeWeakened(i) if i.clause == "result >= amount")
+        );
+        assert!(
+            matches!(kinds[1], ApiBreakKind::PreconditionAdded(i) if i.clause == "amount > 10")
+        );
+        assert_eq!(kinds[2], &ApiBreakKind::VerifiedItemRemoved);
+        assert_eq!(breaks[2].item, "billing_core::refund");
+
+        let violations = api_break_violations(&breaks, Severity::Error);
+        assert_eq!(violations[0].id, INVARIANT_BROKEN);
+        assert_eq!(violations[2].id, VERIFIED_ITEM_REMOVED);
+        assert_eq!(violations[2].location.line, Some(16));
+
+        let acknowledged = after.breaks(
+            &before,
+            &["billing_core::refund".to_string(), "*::charge".to_string()],
+        );
+        assert!(acknowledged.is_empty());
+    }
+}
@@ -0,0 +1,384 @@
+use crate::types::*;
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Replacement for text matched by a redaction rule
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Size caps and redaction applied before artifacts leave the process
+///
+/// Redaction runs first so a secret straddling the cap is never half-kept.
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactPolicy {
+    max_bytes: Option<usize>,
+    redactions: Vec<Regex>,
+}
+
+impl ArtifactPolicy {
+    pub fn new(max_bytes: Option<u64>, redact: &[String]) -> Result<Self> {
+        let redactions = redact
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| anyhow!("Invalid redaction pattern '{}': {}", pattern, e))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            max_bytes: max_bytes.map(|b| b as usize),
+            redactions,
+        })
+    }
+
+    pub fn is_noop(&self) -> bool {
+        self.max_bytes.is_none() && self.redactions.is_empty()
+    }
+
+    /// Replace every match of a redaction rule
+    pub fn redact(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for redaction in &self.redactions {
+            if let std::borrow::Cow::Owned(replaced) = redaction.replace_all(&text, REDACTED) {
+                text = replaced;
+            }
+        }
+        text
+    }
+
+    /// Redact, then truncate to the cap with a marker saying how much was cut
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = self.redact(text);
+
+        match self.max_bytes {
+            Some(max) if text.len() > max => {
+                let mut cut = max;
+                while !text.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                let omitted = text.len() - cut;
+                text.truncate(cut);
+                text.push_str(&format!(
+                    "\n... [truncated {} bytes by ferris-proof artifact limits]\n",
+                    omitted
+                ));
+                text
+            }
+            _ => text,
+        }
+    }
+
+    pub fn apply_to_tool_output(&self, output: &mut ToolOutput) {
+        output.stdout = self.apply(&output.stdout);
+        output.stderr = self.apply(&output.stderr);
+    }
+
+    /// Apply the policy to every artifact and tool output in a result
+    ///
+    /// Covers the per-target results kept for the cache as well, so nothing
+    /// the policy strips is persisted.
+    pub fn apply_to_result(&self, result: &mut VerificationResult) {
+        if self.is_noop() {
+            return;
+        }
+
+        for artifact in &mut result.artifacts {
+            artifact.content = self.apply(&artifact.content);
+        }
+        let completed = result.completed.iter_mut().map(|(_, layer)| layer);
+        for layer_result in result.layer_results.values_mut().chain(completed) {
+            for output in &mut layer_result.tool_outputs {
+                self.apply_to_tool_output(output);
+            }
+        }
+    }
+}
+
+/// Stream of a spilled tool output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    fn extension(self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "stdout.zst",
+            OutputStream::Stderr => "stderr.zst",
+        }
+    }
+}
+
+/// Compressed on-disk store for tool outputs too large to keep in results
+///
+/// Outputs over the spill threshold are written to `<cache>/artifacts/` and
+/// replaced in the `ToolOutput` by a preview naming the artifact id.
+#[derive(Debug, Clone)]
+pub struct ArtifactStore {
+    dir: PathBuf,
+    spill_bytes: usize,
+    preview_bytes: usize,
+}
+
+impl ArtifactStore {
+    /// Combined stdout/stderr size above which outputs are spilled
+    pub const DEFAULT_SPILL_BYTES: usize = 1024 * 1024;
+    /// Bytes of each stream kept inline after spilling
+    pub const PREVIEW_BYTES: usize = 16 * 1024;
+
+    pub fn new(cache_dir: &Path) -> Self {
+        Self {
+            dir: cache_dir.join("artifacts"),
+            spill_bytes: Self::DEFAULT_SPILL_BYTES,
+            preview_bytes: Self::PREVIEW_BYTES,
+        }
+    }
+
+    pub fn with_spill_bytes(mut self, spill_bytes: usize) -> Self {
+        self.spill_bytes = spill_bytes;
+        self.preview_bytes = self.preview_bytes.min(spill_bytes);
+        self
+    }
+
+    /// Spill `output` if it is over the threshold, keeping a preview inline
+    ///
+    /// Returns the artifact id when the output was spilled.
+    pub fn spill(&self, output: &mut ToolOutput) -> Result<Option<String>> {
+        if output.stdout.len() + output.stderr.len() <= self.spill_bytes {
+            return Ok(None);
+        }
+
+        let mut hasher = blake3::Hasher::new();
+        for part in [&output.tool, &output.stdout, &output.stderr] {
+            hasher.update(part.as_bytes());
+            hasher.update(&[0]);
+        }
+        let id = hasher.finalize().to_hex()[..16].to_string();
+
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create artifact store {:?}", self.dir))?;
+        for (stream, text) in [
+            (OutputStream::Stdout, &output.stdout),
+            (OutputStream::Stderr, &output.stderr),
+        ] {
+            let path = self.path(&id, stream);
+            if !path.exists() {
+                let compressed = zstd::encode_all(text.as_bytes(), 3)?;
+                std::fs::write(&path, compressed)
+                    .with_context(|| format!("Failed to write artifact {:?}", path))?;
+            }
+        }
+
+        output.stdout = self.preview(&output.stdout, &id);
+        output.stderr = self.preview(&output.stderr, &id);
+        output.artifact_id = Some(id.clone());
+        Ok(Some(id))
+    }
+
+    /// Redact and spill every tool output in a result, logging failures
+    pub fn spill_result(&self, result: &mut VerificationResult, policy: &ArtifactPolicy) {
+        for layer_result in result.layer_results.values_mut() {
+            for output in &mut layer_result.tool_outputs {
+                if output.stdout.len() + output.stderr.len() <= self.spill_bytes {
+                    continue;
+                }
+                // Spilled files must never hold what the policy redacts
+                output.stdout = policy.redact(&output.stdout);
+                output.stderr = policy.redact(&output.stderr);
+                if let Err(e) = self.spill(output) {
+                    tracing::warn!("Failed to spill {} output: {}", output.tool, e);
+                }
+            }
+        }
+    }
+
+    /// Reader streaming the full, decompressed output of one stream
+    pub fn open(&self, id: &str, stream: OutputStream) -> Result<impl Read> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!("Invalid artifact id '{}'", id));
+        }
+        let path = self.path(id, stream);
+        let file = std::fs::File::open(&path)
+            .map_err(|e| anyhow!("Artifact '{}' not found in {:?}: {}", id, self.dir, e))?;
+        Ok(zstd::stream::read::Decoder::new(file)?)
+    }
+
+    fn path(&self, id: &str, stream: OutputStream) -> PathBuf {
+        self.dir.join(format!("{}.{}", id, stream.extension()))
+    }
+
+    fn preview(&self, text: &str, id: &str) -> String {
+        if text.len() <= self.preview_bytes {
+            return text.to_string();
+        }
+        let mut cut = self.preview_bytes;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        format!(
+            "{}\n... [{} more bytes in artifact {}; run `ferris-proof artifacts show {}`]\n",
+            &text[..cut],
+            text.len() - cut,
+            id,
+            id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_before_truncating() {
+        let policy = ArtifactPolicy::new(
+            Some(41),
+            &[r"/home/[^/\s]+".to_string(), r"token=\w+".to_string()],
+        )
+        .unwrap();
+
+        let output = policy.apply("error in /home/alice/src/lib.rs token=abc123 and a long tail");
+        assert!(output.starts_with("error in [REDACTED]/src/lib.rs [REDACTED]"));
+        assert!(!output.contains("alice"));
+        assert!(output.contains("[truncated 16 bytes"));
+
+        assert_eq!(policy.apply("short"), "short");
+        assert!(ArtifactPolicy::new(None, &["(".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_redacted_output_never_reaches_the_cache() {
+        use crate::cache::{CacheTtls, VerificationCache};
+        use crate::verification::Target;
+        use chrono::Utc;
+        use std::collections::{BTreeMap, HashMap};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let lib = temp_dir.path().join("lib.rs");
+        std::fs::write(&lib, "pub fn connect() {}\n").unwrap();
+
+        let layer_result = LayerResult {
+            layer: Layer::PropertyBased,
+            status: Status::Error,
+            violations: vec![Violation {
+                id: "PROPTEST_FAILURE".to_string(),
+                severity: Severity::Error,
+                location: Location::file("lib.rs"),
+                message: "connect failed".to_string(),
+                suggestion: None,
+                rule: "proptest_verification".to_string(),
+            }],
+            execution_time: std::time::Duration::ZERO,
+            tool_outputs: vec![ToolOutput {
+                tool: "proptest".to_string(),
+                stdout: "connecting with token=abc123".to_string(),
+                stderr: "rejected token=abc123".to_string(),
+                exit_code: 101,
+                execution_time: std::time::Duration::ZERO,
+                artifact_id: None,
+                retries: 0,
+            }],
+        };
+        let mut result = VerificationResult {
+            overall_status: Status::Error,
+            layer_results: HashMap::from([(Layer::PropertyBased, layer_result.clone())]),
+            metrics: VerificationMetrics {
+                total_time: std::time::Duration::ZERO,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+            artifacts: vec![],
+            timestamp: Utc::now(),
+            skipped_targets: vec![],
+            gated_layers: vec![],
+            unavailable_layers: vec![],
+            skip_reasons: HashMap::new(),
+            spec_changes: vec![],
+            traceability: None,
+            budget: None,
+            seed: None,
+            environment: None,
+            target_statuses: BTreeMap::new(),
+            interrupted: false,
+            project_root: None,
+            link_template: None,
+            plugin_breakdowns: Vec::new(),
+            completed: vec![(Target::RustFile(lib), layer_result)],
+            api_surface: None,
+        };
+
+        ArtifactPolicy::new(None, &[r"token=\w+".to_string()])
+            .unwrap()
+            .apply_to_result(&mut result);
+
+        let cache_dir = temp_dir.path().join("cache");
+        let cache = VerificationCache::with_cache_dir(cache_dir.clone());
+        let stored = cache.store_results(
+            &result.completed,
+            |_| "cfg".to_string(),
+            &CacheTtls::default(),
+            None,
+        );
+        assert_eq!(stored, 1);
+
+        let mut reloaded = VerificationCache::with_cache_dir(cache_dir);
+        reloaded.load_from_disk().unwrap();
+        let entries = reloaded.entries_with_violation("PROPTEST_FAILURE");
+        assert_eq!(entries.len(), 1);
+        let output = &entries[0].result.tool_outputs[0];
+        assert_eq!(output.stdout, "connecting with [REDACTED]");
+        assert_eq!(output.stderr, "rejected [REDACTED]");
+    }
+
+    #[test]
+    fn test_truncates_on_char_boundary() {
+        let policy = ArtifactPolicy::new(Some(3), &[]).unwrap();
+        assert!(policy.apply("aéé").starts_with("aé\n"));
+    }
+
+    #[test]
+    fn test_spills_large_output_with_preview() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = ArtifactStore::new(temp_dir.path()).with_spill_bytes(8);
+        let full = "state 1\nstate 2\nstate 3\n".repeat(4);
+        let mut output = ToolOutput {
+            tool: "tla_plus".to_string(),
+            stdout: full.clone(),
+            stderr: String::new(),
+            exit_code: 0,
+            execution_time: std::time::Duration::from_millis(1),
+            artifact_id: None,
+            retries: 0,
+        };
+
+        let id = store.spill(&mut output).unwrap().unwrap();
+        assert_eq!(output.artifact_id.as_deref(), Some(id.as_str()));
+        assert!(output.stdout.starts_with("state 1\n"));
+        assert!(output
+            .stdout
+            .contains(&format!("ferris-proof artifacts show {}", id)));
+        assert!(output.stderr.is_empty());
+
+        let mut restored = String::new();
+        store
+            .open(&id, OutputStream::Stdout)
+            .unwrap()
+            .read_to_string(&mut restored)
+            .unwrap();
+        assert_eq!(restored, full);
+
+        let mut small = ToolOutput {
+            stdout: "ok".to_string(),
+            artifact_id: None,
+            retries: 0,
+            ..output
+        };
+        assert!(store.spill(&mut small).unwrap().is_none());
+        assert!(store.open("../etc", OutputStream::Stdout).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+use crate::cache::CacheKey;
+use crate::verification::Target;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Attestation format version; other versions are rejected when verifying
+const ATTESTATION_VERSION: u32 = 1;
+
+/// Hex-encoded ed25519 secret key used when `[attestation] signing_key_file` is unset
+pub const SIGNING_KEY_ENV: &str = "FERRIS_PROOF_SIGNING_KEY";
+
+/// What an attestation vouches for; this is the signed part
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttestationPayload {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// FerrisProof and external tool versions the report was produced with
+    pub tool_versions: BTreeMap<String, String>,
+    /// Content hash per verified target label
+    pub content_hashes: BTreeMap<String, String>,
+    /// blake3 of `report`
+    pub report_hash: String,
+    /// The JSON report, embedded verbatim
+    pub report: String,
+    /// Digest of the preceding attestation in the log, if any
+    pub previous: Option<String>,
+}
+
+impl AttestationPayload {
+    /// Payload for a JSON report of a run over `targets`
+    pub fn new(report: String, targets: &[Target]) -> Result<Self> {
+        let versions = CacheKey::get_tool_versions()?;
+        let mut tool_versions: BTreeMap<String, String> =
+            versions.external_tools.into_iter().collect();
+        tool_versions.insert("FerrisProof".to_string(), versions.ferris_proof);
+
+        let content_hashes = targets
+            .iter()
+            .filter_map(|target| match CacheKey::compute_content_hash(target) {
+                Ok(hash) => Some((target.label(), hash.0)),
+                Err(e) => {
+                    debug!("No content hash for {}: {}", target.label(), e);
+                    None
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            version: ATTESTATION_VERSION,
+            created_at: Utc::now(),
+            tool_versions,
+            content_hashes,
+            report_hash: blake3::hash(report.as_bytes()).to_hex().to_string(),
+            report,
+            previous: None,
+        })
+    }
+}
+
+/// Signed, tamper-evident evidence that a verification run produced a report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attestation {
+    pub payload: AttestationPayload,
+    /// Hex-encoded ed25519 public key of the signer
+    pub public_key: String,
+    /// Hex-encoded ed25519 signature over the JSON-encoded payload
+    pub signature: String,
+}
+
+impl Attestation {
+    pub fn sign(payload: AttestationPayload, key: &SigningKey) -> Result<Self> {
+        let signature = key.sign(&serde_json::to_vec(&payload)?);
+        Ok(Self {
+            payload,
+            public_key: hex::encode(key.verifying_key().to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
+
+    /// Digest that the next attestation in a log records as `previous`
+    pub fn digest(&self) -> Result<String> {
+        Ok(blake3::hash(&serde_json::to_vec(self)?)
+            .to_hex()
+            .to_string())
+    }
+
+    /// Check the signature and the embedded report hash
+    ///
+    /// With `trusted`, the attestation must also have been signed by that key;
+    /// without it only integrity, not origin, is established.
+    pub fn verify(&self, trusted: Option<&VerifyingKey>) -> Result<()> {
+        if self.payload.version != ATTESTATION_VERSION {
+            return Err(anyhow!(
+                "Unsupported attestation version {}",
+                self.payload.version
+            ));
+        }
+
+        let key = parse_verifying_key(&self.public_key)?;
+        if trusted.is_some_and(|trusted| *trusted != key) {
+            return Err(anyhow!(
+                "Attestation was signed by an untrusted key {}",
+                self.public_key
+            ));
+        }
+        let signature: [u8; 64] = hex::decode(&self.signature)?
+            .try_into()
+            .map_err(|_| anyhow!("Signature must be 64 bytes"))?;
+        key.verify(
+            &serde_json::to_vec(&self.payload)?,
+            &Signature::from_bytes(&signature),
+        )
+        .map_err(|_| anyhow!("Signature does not match the attested payload"))?;
+
+        let report_hash = blake3::hash(self.payload.report.as_bytes()).to_hex();
+        if report_hash.as_str() != self.payload.report_hash {
+            return Err(anyhow!("Embedded report does not match its hash"));
+        }
+        Ok(())
+    }
+}
+
+/// Append-only log of attestations, each chained to its predecessor
+pub struct AttestationLog {
+    path: PathBuf,
+}
+
+impl AttestationLog {
+    pub fn for_project(project_root: &Path) -> Self {
+        Self {
+            path: project_root
+                .join(".ferris-proof")
+                .join("attestations.jsonl"),
+        }
+    }
+
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every attestation in the log, oldest first
+    pub fn load(&self) -> Result<Vec<Attestation>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        std::fs::read_to_string(&self.path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(idx, line)| {
+                serde_json::from_str(line)
+                    .with_context(|| format!("Invalid attestation on line {}", idx + 1))
+            })
+            .collect()
+    }
+
+    /// Sign `payload`, chaining it to the last attestation, and append it
+    pub fn append(&self, mut payload: AttestationPayload, key: &SigningKey) -> Result<Attestation> {
+        payload.previous = match self.load()?.last() {
+            Some(last) => Some(last.digest()?),
+            None => None,
+        };
+        let attestation = Attestation::sign(payload, key)?;
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&attestation)?)?;
+        Ok(attestation)
+    }
+}
+
+/// Verify each attestation and that each links to the one before it
+///
+/// Returns the number of attestations checked.
+pub fn verify_chain(attestations: &[Attestation], trusted: Option<&VerifyingKey>) -> Result<usize> {
+    let mut previous: Option<String> = None;
+    for (idx, attestation) in attestations.iter().enumerate() {
+        attestation
+            .verify(trusted)
+            .with_context(|| format!("Attestation {} failed verification", idx + 1))?;
+        // The first entry may continue a log that was rotated away
+        if idx > 0 && attestation.payload.previous != previous {
+            return Err(anyhow!(
+                "Attestation {} does not chain to attestation {}",
+                idx + 1,
+                idx
+            ));
+        }
+        previous = Some(attestation.digest()?);
+    }
+    Ok(attestations.len())
+}
+
+/// Signing key from a hex-encoded 32-byte secret
+pub fn parse_signing_key(hex_key: &str) -> Result<SigningKey> {
+    let bytes: [u8; 32] = hex::decode(hex_key.trim())
+        .context("Signing key must be hex-encoded")?
+        .try_into()
+        .map_err(|_| anyhow!("Signing key must be 32 bytes"))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+pub fn parse_verifying_key(hex_key: &str) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(hex_key.trim())
+        .context("Public key must be hex-encoded")?
+        .try_into()
+        .map_err(|_| anyhow!("Public key must be 32 bytes"))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| anyhow!("Invalid public key: {}", e))
+}
+
+/// Signing key from `key_file`, falling back to `FERRIS_PROOF_SIGNING_KEY`
+pub fn load_signing_key(key_file: Option<&Path>) -> Result<SigningKey> {
+    match key_file {
+        Some(path) => parse_signing_key(
+            &std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read signing key {:?}", path))?,
+        ),
+        None => parse_signing_key(&std::env::var(SIGNING_KEY_ENV).map_err(|_| {
+            anyhow!(
+                "No signing key: set [attestation] signing_key_file or {}",
+                SIGNING_KEY_ENV
+            )
+        })?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn payload(report: &str) -> AttestationPayload {
+        AttestationPayload {
+            version: ATTESTATION_VERSION,
+            created_at: Utc::now(),
+            tool_versions: BTreeMap::from([("FerrisProof".to_string(), "0.1.0".to_string())]),
+            content_hashes: BTreeMap::from([("src/lib.rs".to_string(), "ab".repeat(32))]),
+            report_hash: blake3::hash(report.as_bytes()).to_hex().to_string(),
+            report: report.to_string(),
+            previous: None,
+        }
+    }
+
+    #[test]
+    fn test_attestation_log_detects_tampering() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AttestationLog::with_path(temp_dir.path().join("attestations.jsonl"));
+        let key = parse_signing_key(&"07".repeat(32)).unwrap();
+        let trusted = key.verifying_key();
+
+        log.append(payload(r#"{"overall_status":"Success"}"#), &key)
+            .unwrap();
+        log.append(payload(r#"{"overall_status":"Error"}"#), &key)
+            .unwrap();
+        let attestations = log.load().unwrap();
+        assert_eq!(verify_chain(&attestations, Some(&trusted)).unwrap(), 2);
+
+        let other = parse_signing_key(&"08".repeat(32)).unwrap().verifying_key();
+        assert!(verify_chain(&attestations, Some(&other)).is_err());
+
+        let mut edited = attestations.clone();
+        edited[1].payload.report = r#"{"overall_status":"Success"}"#.to_string();
+        assert!(verify_chain(&edited, None).is_err());
+
+        let mut reordered = attestations.clone();
+        reordered.swap(0, 1);
+        assert!(verify_chain(&reordered, None).is_err());
+    }
+}
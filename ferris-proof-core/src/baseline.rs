@@ -0,0 +1,332 @@
+use crate::history::ViolationRecord;
+use crate::types::*;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Accepted violation that should no longer be reported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub fingerprint: String,
+    pub rule: String,
+    pub file: PathBuf,
+    pub reason: Option<String>,
+    pub added_at: DateTime<Utc>,
+    /// Snoozed entries stop suppressing the violation after this time
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl BaselineEntry {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires| expires > now)
+    }
+}
+
+/// Project baseline stored in `.ferris-proof/baseline.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    pub fn path_for_project(project_root: &Path) -> PathBuf {
+        project_root.join(".ferris-proof").join("baseline.json")
+    }
+
+    /// Load the baseline, returning an empty one if the file does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| anyhow!("Invalid baseline {:?}: {}", path, e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn is_suppressed(&self, violation: &ViolationRecord, now: DateTime<Utc>) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.fingerprint == violation.fingerprint && e.is_active(now))
+    }
+
+    /// Fingerprints of the entries still suppressing at `now`
+    pub fn active_fingerprints(&self, now: DateTime<Utc>) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.is_active(now))
+            .map(|e| e.fingerprint.clone())
+            .collect()
+    }
+
+    /// Accept a violation permanently
+    pub fn add(&mut self, violation: &ViolationRecord, reason: Option<String>) {
+        self.insert(violation, reason, None);
+    }
+
+    /// Suppress a violation until `until`
+    pub fn snooze(&mut self, violation: &ViolationRecord, until: DateTime<Utc>) {
+        self.insert(violation, Some("snoozed".to_string()), Some(until));
+    }
+
+    fn insert(
+        &mut self,
+        violation: &ViolationRecord,
+        reason: Option<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) {
+        self.entries
+            .retain(|e| e.fingerprint != violation.fingerprint);
+        self.entries.push(BaselineEntry {
+            fingerprint: violation.fingerprint.clone(),
+            rule: violation.rule.clone(),
+            file: violation.file.clone(),
+            reason,
+            added_at: Utc::now(),
+            expires_at,
+        });
+    }
+
+    /// Remove suppressed violations from a result, returning how many were dropped
+    pub fn apply(&self, result: &mut VerificationResult) -> usize {
+        let now = Utc::now();
+        let mut suppressed = 0;
+
+        for layer_result in result.layer_results.values_mut() {
+            let before = layer_result.violations.len();
+            layer_result
+                .violations
+                .retain(|v| !self.is_suppressed(&ViolationRecord::from_violation(v), now));
+
+            if layer_result.violations.len() < before {
+                suppressed += before - layer_result.violations.len();
+                layer_result.status = status_for(&layer_result.violations);
+                // With every finding accepted the layer's verdict is suppressed
+                if layer_result.violations.is_empty() {
+                    layer_result.status = Status::Skipped;
+                    result
+                        .skip_reasons
+                        .insert(layer_result.layer, SkipReason::Suppressed);
+                }
+            }
+        }
+
+        // Only relax the overall status; failures outside layer results stand
+        if suppressed > 0 && result.skipped_targets.is_empty() {
+            let has = |status: Status| result.layer_results.values().any(|l| l.status == status);
+            result.overall_status = match result.overall_status {
+                Status::Error if !has(Status::Error) && has(Status::Warning) => Status::Warning,
+                Status::Error | Status::Warning if !has(Status::Error) && !has(Status::Warning) => {
+                    Status::Success
+                }
+                status => status,
+            };
+        }
+
+        suppressed
+    }
+}
+
+fn status_for(violations: &[Violation]) -> Status {
+    if violations
+        .iter()
+        .any(|v| matches!(v.severity, Severity::Error | Severity::Critical))
+    {
+        Status::Error
+    } else if violations.is_empty() {
+        Status::Success
+    } else {
+        Status::Warning
+    }
+}
+
+/// Kind of decision recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Baselined,
+    Snoozed,
+    Fixed,
+    Opened,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub timestamp: DateTime<Utc>,
+    pub action: AuditAction,
+    pub fingerprint: String,
+    pub user: Option<String>,
+    pub note: Option<String>,
+}
+
+/// Append-only log of triage decisions in `.ferris-proof/audit.jsonl`
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn for_project(project_root: &Path) -> Self {
+        Self {
+            path: project_root.join(".ferris-proof").join("audit.jsonl"),
+        }
+    }
+
+    pub fn record(
+        &self,
+        action: AuditAction,
+        violation: &ViolationRecord,
+        note: Option<String>,
+    ) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let event = AuditEvent {
+            timestamp: Utc::now(),
+            action,
+            fingerprint: violation.fingerprint.clone(),
+            user: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok(),
+            note,
+        };
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&event)?)?;
+
+        debug!("Audit: {:?} {}", action, violation.fingerprint);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn violation(rule: &str, severity: Severity) -> Violation {
+        Violation {
+            id: rule.to_string(),
+            severity,
+            location: Location {
+                file: PathBuf::from("src/lib.rs"),
+                line: Some(1),
+                column: None,
+                span: None,
+            },
+            message: "failed".to_string(),
+            suggestion: None,
+            rule: rule.to_string(),
+        }
+    }
+
+    fn result(violations: Vec<Violation>) -> VerificationResult {
+        let mut layer_results = HashMap::new();
+        layer_results.insert(
+            Layer::PropertyBased,
+            LayerResult {
+                layer: Layer::PropertyBased,
+                status: status_for(&violations),
+                violations,
+                execution_time: std::time::Duration::ZERO,
+                tool_outputs: vec![],
+            },
+        );
+
+        VerificationResult {
+            overall_status: Status::Error,
+            layer_results,
+            metrics: VerificationMetrics {
+                total_time: std::time::Duration::ZERO,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+            artifacts: vec![],
+            timestamp: Utc::now(),
+            skipped_targets: vec![],
+            gated_layers: vec![],
+            unavailable_layers: vec![],
+            skip_reasons: HashMap::new(),
+            spec_changes: vec![],
+            traceability: None,
+            budget: None,
+            seed: None,
+            environment: None,
+            target_statuses: BTreeMap::new(),
+            interrupted: false,
+            project_root: None,
+            link_template: None,
+            plugin_breakdowns: Vec::new(),
+            completed: Vec::new(),
+            api_surface: None,
+        }
+    }
+
+    #[test]
+    fn test_baseline_suppresses_matching_violations() {
+        let accepted = violation("a", Severity::Error);
+        let mut baseline = Baseline::default();
+        baseline.add(&ViolationRecord::from_violation(&accepted), None);
+
+        let mut result = result(vec![accepted, violation("b", Severity::Warning)]);
+        let suppressed = baseline.apply(&mut result);
+
+        assert_eq!(suppressed, 1);
+        assert_eq!(result.overall_status, Status::Warning);
+        assert_eq!(
+            result.layer_results[&Layer::PropertyBased].violations.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_fully_suppressed_layer_is_skipped() {
+        let accepted = violation("a", Severity::Error);
+        let mut baseline = Baseline::default();
+        baseline.add(&ViolationRecord::from_violation(&accepted), None);
+
+        let mut result = result(vec![accepted]);
+        assert_eq!(baseline.apply(&mut result), 1);
+
+        assert_eq!(result.overall_status, Status::Success);
+        assert_eq!(
+            result.layer_results[&Layer::PropertyBased].status,
+            Status::Skipped
+        );
+        assert_eq!(
+            result.skip_reasons[&Layer::PropertyBased],
+            SkipReason::Suppressed
+        );
+    }
+
+    #[test]
+    fn test_expired_snooze_no_longer_suppresses() {
+        let snoozed = violation("a", Severity::Error);
+        let record = ViolationRecord::from_violation(&snoozed);
+        let mut baseline = Baseline::default();
+        baseline.snooze(&record, Utc::now() - chrono::Duration::days(1));
+
+        assert!(!baseline.is_suppressed(&record, Utc::now()));
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = Baseline::path_for_project(temp_dir.path());
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap().entries.len(), 1);
+    }
+}
@@ -0,0 +1,264 @@
+use crate::types::{Severity, VerificationResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Violations a run may report and still pass, from `thresholds.max_violations`
+///
+/// Either a single number capping the total, or a table with a `total` and
+/// per-severity caps, e.g. `max_violations = { total = 200, error = 0 }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "BudgetSpec")]
+pub struct ViolationBudget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub critical: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BudgetSpec {
+    Total(usize),
+    Table {
+        #[serde(default)]
+        total: Option<usize>,
+        #[serde(default)]
+        critical: Option<usize>,
+        #[serde(default)]
+        error: Option<usize>,
+        #[serde(default)]
+        warning: Option<usize>,
+        #[serde(default)]
+        info: Option<usize>,
+    },
+}
+
+impl From<BudgetSpec> for ViolationBudget {
+    fn from(spec: BudgetSpec) -> Self {
+        match spec {
+            BudgetSpec::Total(total) => Self {
+                total: Some(total),
+                ..Self::default()
+            },
+            BudgetSpec::Table {
+                total,
+                critical,
+                error,
+                warning,
+                info,
+            } => Self {
+                total,
+                critical,
+                error,
+                warning,
+                info,
+            },
+        }
+    }
+}
+
+impl ViolationBudget {
+    /// No cap is configured, so violations fail the run as usual
+    pub fn is_empty(&self) -> bool {
+        self.limits().next().is_none()
+    }
+
+    /// How much of the budget a run's violations use
+    pub fn evaluate(&self, result: &VerificationResult) -> BudgetReport {
+        let severities: Vec<&Severity> = result
+            .layer_results
+            .values()
+            .flat_map(|layer| layer.violations.iter().map(|v| &v.severity))
+            .collect();
+
+        let entries = self
+            .limits()
+            .map(|(scope, limit)| {
+                let used = match &scope {
+                    BudgetScope::Total => severities.len(),
+                    BudgetScope::Severity(severity) => {
+                        severities.iter().filter(|s| **s == severity).count()
+                    }
+                };
+                BudgetEntry {
+                    scope: scope.to_string(),
+                    used,
+                    limit,
+                }
+            })
+            .collect();
+
+        BudgetReport { entries }
+    }
+
+    fn limits(&self) -> impl Iterator<Item = (BudgetScope, usize)> + '_ {
+        [
+            (BudgetScope::Total, self.total),
+            (BudgetScope::Severity(Severity::Critical), self.critical),
+            (BudgetScope::Severity(Severity::Error), self.error),
+            (BudgetScope::Severity(Severity::Warning), self.warning),
+            (BudgetScope::Severity(Severity::Info), self.info),
+        ]
+        .into_iter()
+        .filter_map(|(scope, limit)| Some((scope, limit?)))
+    }
+}
+
+enum BudgetScope {
+    Total,
+    Severity(Severity),
+}
+
+impl fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetScope::Total => write!(f, "total"),
+            BudgetScope::Severity(severity) => {
+                f.write_str(&format!("{:?}", severity).to_lowercase())
+            }
+        }
+    }
+}
+
+/// Budget consumption of a run, attached to its report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetReport {
+    pub entries: Vec<BudgetEntry>,
+}
+
+/// Violations counted against one cap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetEntry {
+    /// `total` or a lowercase severity
+    pub scope: String,
+    pub used: usize,
+    pub limit: usize,
+}
+
+impl BudgetEntry {
+    pub fn exceeded(&self) -> bool {
+        self.used > self.limit
+    }
+}
+
+impl BudgetReport {
+    pub fn exceeded(&self) -> bool {
+        self.entries.iter().any(BudgetEntry::exceeded)
+    }
+}
+
+impl fmt::Display for BudgetReport {
+    /// e.g. `total 40/100, error 2/0 (exceeded)`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let mut text = format!("{} {}/{}", entry.scope, entry.used, entry.limit);
+                if entry.exceeded() {
+                    text.push_str(" (exceeded)");
+                }
+                text
+            })
+            .collect();
+        write!(f, "{}", entries.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+    use std::collections::{BTreeMap, HashMap};
+    use std::time::Duration;
+
+    fn result(severities: &[Severity]) -> VerificationResult {
+        let violations = severities
+            .iter()
+            .enumerate()
+            .map(|(i, severity)| Violation {
+                id: format!("V{}", i),
+                severity: severity.clone(),
+                location: Location::file("src/lib.rs"),
+                message: String::new(),
+                suggestion: None,
+                rule: "rule".to_string(),
+            })
+            .collect();
+
+        VerificationResult {
+            overall_status: Status::Warning,
+            layer_results: HashMap::from([(
+                Layer::PropertyBased,
+                LayerResult {
+                    layer: Layer::PropertyBased,
+                    status: Status::Warning,
+                    violations,
+                    execution_time: Duration::ZERO,
+                    tool_outputs: Vec::new(),
+                },
+            )]),
+            metrics: VerificationMetrics {
+                total_time: Duration::ZERO,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+            artifacts: Vec::new(),
+            timestamp: chrono::Utc::now(),
+            skipped_targets: Vec::new(),
+            gated_layers: Vec::new(),
+            unavailable_layers: Vec::new(),
+            skip_reasons: HashMap::new(),
+            spec_changes: Vec::new(),
+            traceability: None,
+            budget: None,
+            seed: None,
+            environment: None,
+            target_statuses: BTreeMap::new(),
+            interrupted: false,
+            project_root: None,
+            link_template: None,
+            plugin_breakdowns: Vec::new(),
+            completed: Vec::new(),
+            api_surface: None,
+        }
+    }
+
+    #[test]
+    fn test_accepts_a_number_or_a_table() {
+        let total: ViolationBudget = serde_json::from_str("25").unwrap();
+        assert_eq!(total.total, Some(25));
+
+        let table: ViolationBudget =
+            serde_json::from_str(r#"{ "total": 100, "error": 0 }"#).unwrap();
+        assert_eq!(table.total, Some(100));
+        assert_eq!(table.error, Some(0));
+        assert!(ViolationBudget::default().is_empty());
+    }
+
+    #[test]
+    fn test_reports_consumption_per_cap() {
+        let budget = ViolationBudget {
+            total: Some(3),
+            error: Some(0),
+            ..Default::default()
+        };
+
+        let within = budget.evaluate(&result(&[Severity::Warning, Severity::Warning]));
+        assert!(!within.exceeded());
+        assert_eq!(within.to_string(), "total 2/3, error 0/0");
+
+        let over = budget.evaluate(&result(&[Severity::Warning, Severity::Error]));
+        assert!(over.exceeded());
+        assert_eq!(over.to_string(), "total 2/3, error 1/0 (exceeded)");
+    }
+}
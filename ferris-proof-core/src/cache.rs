@@ -1,13 +1,39 @@
+use crate::errors::{FerrisProofError, Result};
+use crate::normalization::{NormalizationPolicies, NormalizationPolicy};
+use crate::spec_normalization::{
+    normalize_line_endings, normalize_spec, SpecLanguage, SPEC_NORMALIZATION_VERSION,
+};
 use crate::types::*;
-use anyhow::{anyhow, Result};
 use blake3::Hasher;
 use quote::ToTokens;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
 use syn::{parse_file, Attribute, File, Item, ItemEnum, ItemFn, ItemMod, ItemStruct};
 
+/// Leading bytes of every persisted cache entry
+const ENTRY_MAGIC: &[u8; 4] = b"FPCE";
+/// Version of the serialized key and entry types, written after the magic bytes
+///
+/// Bump on any change to [`CacheKey`], [`CacheEntry`] or the types they
+/// contain, and teach `decode_entry` to read the previous version.
+pub const ENTRY_SCHEMA_VERSION: u16 = 2;
+/// Schema of headerless entries, written before entries were versioned
+const LEGACY_SCHEMA_VERSION: u16 = 0;
+/// Oldest schema still decoded; older entries are discarded as stale
+///
+/// Schemas 0 and 1 left empty `ToolOutput` fields out, which bincode cannot
+/// detect, so their tool outputs would be misread rather than rejected.
+const MIN_READABLE_SCHEMA_VERSION: u16 = 2;
+/// Advisory lock file shared by every process using a cache directory
+const LOCK_FILE: &str = ".lock";
+/// Attempts at taking the cache lock before the operation is given up
+const LOCK_ATTEMPTS: u32 = 40;
+const LOCK_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(25);
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct CacheKey {
     pub content_hash: ContentHash,
@@ -23,7 +49,17 @@ impl CacheKey {
         layer: Layer,
         config_hash: &str,
     ) -> Result<Self> {
-        let content_hash = Self::compute_content_hash(target)?;
+        Self::with_policy(target, layer, config_hash, &NormalizationPolicy::default())
+    }
+
+    /// Create a cache key, normalizing the target's source with `policy`
+    pub fn with_policy(
+        target: &crate::verification::Target,
+        layer: Layer,
+        config_hash: &str,
+        policy: &NormalizationPolicy,
+    ) -> Result<Self> {
+        let content_hash = Self::compute_content_hash_with(target, policy)?;
         let tool_versions = Self::get_tool_versions()?;
 
         Ok(Self {
@@ -34,23 +70,62 @@ impl CacheKey {
         })
     }
 
+    /// Stable digest of every key field, used to name the persisted entry
+    ///
+    /// Unlike the `Hash` impl this does not depend on the Rust version, so
+    /// entries written by one build are found by the next.
+    pub fn fingerprint(&self) -> String {
+        let layer = format!("{:?}", self.layer);
+        let mut hasher = Hasher::new();
+        for field in [
+            self.content_hash.0.as_str(),
+            self.config_hash.0.as_str(),
+            self.tool_versions.ferris_proof.as_str(),
+            layer.as_str(),
+        ] {
+            hasher.update(field.as_bytes());
+            hasher.update(&[0]);
+        }
+        for (tool, version) in &self.tool_versions.external_tools {
+            hasher.update(tool.as_bytes());
+            hasher.update(&[0]);
+            hasher.update(version.as_bytes());
+            hasher.update(&[0]);
+        }
+        hasher.finalize().to_hex().to_string()
+    }
+
     /// Compute content hash for a verification target
-    fn compute_content_hash(target: &crate::verification::Target) -> Result<ContentHash> {
+    pub fn compute_content_hash(target: &crate::verification::Target) -> Result<ContentHash> {
+        Self::compute_content_hash_with(target, &NormalizationPolicy::default())
+    }
+
+    /// Compute content hash for a verification target normalized with `policy`
+    ///
+    /// The policy and its rules version are part of the hash.
+    pub fn compute_content_hash_with(
+        target: &crate::verification::Target,
+        policy: &NormalizationPolicy,
+    ) -> Result<ContentHash> {
         let mut hasher = Hasher::new();
+        hasher.update(policy.tag().as_bytes());
+        hasher.update(&[0]);
 
         match target {
             crate::verification::Target::RustFile(path) => {
-                let ast = parse_file(
-                    &std::fs::read_to_string(path)
-                        .map_err(|e| anyhow!("Failed to read Rust file {:?}: {}", path, e))?,
-                )?;
-                let normalized = Self::normalize_ast(&ast);
+                let ast = parse_file(&read_source(path)?)
+                    .map_err(|e| FerrisProofError::parse(e.to_string(), Some(path)))?;
+                let normalized = Self::normalize_ast(&ast, policy);
                 hasher.update(normalized.as_bytes());
             }
             crate::verification::Target::FormalSpec(path) => {
-                let spec = std::fs::read_to_string(path)
-                    .map_err(|e| anyhow!("Failed to read formal spec {:?}: {}", path, e))?;
-                let normalized = Self::normalize_spec(&spec);
+                let spec = read_source(path)?;
+                let normalized = match SpecLanguage::from_path(path) {
+                    Some(language) => normalize_spec(&spec, language),
+                    None => spec,
+                };
+                hasher.update(format!("spec-v{}", SPEC_NORMALIZATION_VERSION).as_bytes());
+                hasher.update(&[0]);
                 hasher.update(normalized.as_bytes());
             }
             crate::verification::Target::Module(module_path) => {
@@ -58,22 +133,113 @@ impl CacheKey {
                 // TODO: Implement module content hashing
                 hasher.update(module_path.as_bytes());
             }
+            crate::verification::Target::Item { file, item_path } => {
+                // Only the item itself, so edits elsewhere in the file keep it cached
+                let ast = parse_file(&read_source(file)?)
+                    .map_err(|e| FerrisProofError::parse(e.to_string(), Some(file)))?;
+                let item = Self::find_item_with(&ast, item_path, policy).ok_or_else(|| {
+                    FerrisProofError::parse(
+                        format!("No item `{}` in {}", item_path, file.display()),
+                        Some(file),
+                    )
+                })?;
+                hasher.update(item_path.as_bytes());
+                hasher.update(&[0]);
+                hasher.update(item.as_bytes());
+            }
         }
 
         Ok(ContentHash(hex::encode(hasher.finalize().as_bytes())))
     }
 
+    /// Normalized tokens of the item named by `item_path` within a file
+    ///
+    /// Leading segments naming the file's own module are skipped, so
+    /// `codec::parse_header` is found in `src/codec.rs`. Inline modules,
+    /// `Type::method` in impl blocks and trait methods are followed.
+    pub fn find_item(ast: &File, item_path: &str) -> Option<String> {
+        Self::find_item_with(ast, item_path, &NormalizationPolicy::default())
+    }
+
+    /// Like [`Self::find_item`], normalizing functions with `policy`
+    pub fn find_item_with(
+        ast: &File,
+        item_path: &str,
+        policy: &NormalizationPolicy,
+    ) -> Option<String> {
+        let segments: Vec<&str> = item_path.split("::").collect();
+        (0..segments.len())
+            .find_map(|start| Self::lookup_item(&ast.items, &segments[start..], policy))
+    }
+
+    fn lookup_item(
+        items: &[Item],
+        segments: &[&str],
+        policy: &NormalizationPolicy,
+    ) -> Option<String> {
+        let (name, rest) = segments.split_first()?;
+        items.iter().find_map(|item| match (item, rest) {
+            (Item::Mod(item_mod), [_, ..]) if item_mod.ident == name => item_mod
+                .content
+                .as_ref()
+                .and_then(|(_, items)| Self::lookup_item(items, rest, policy)),
+            (Item::Impl(item_impl), [method])
+                if type_name(&item_impl.self_ty).is_some_and(|ty| ty == name) =>
+            {
+                item_impl
+                    .items
+                    .iter()
+                    .find_map(|impl_item| match impl_item {
+                        syn::ImplItem::Fn(f) if f.sig.ident == method => Some(
+                            Self::normalize_method(f, policy)
+                                .to_token_stream()
+                                .to_string(),
+                        ),
+                        _ => None,
+                    })
+            }
+            (Item::Trait(item_trait), [method]) if item_trait.ident == name => item_trait
+                .items
+                .iter()
+                .find_map(|trait_item| match trait_item {
+                    syn::TraitItem::Fn(f) if f.sig.ident == method => {
+                        Some(f.to_token_stream().to_string())
+                    }
+                    _ => None,
+                }),
+            (Item::Fn(item_fn), []) if item_fn.sig.ident == name => Some(
+                Self::normalize_function(item_fn, policy)
+                    .to_token_stream()
+                    .to_string(),
+            ),
+            (item, []) if item_ident(item).is_some_and(|ident| ident == name) => {
+                Some(item.to_token_stream().to_string())
+            }
+            _ => None,
+        })
+    }
+
     /// Normalize Rust AST by removing comments, whitespace, and other irrelevant details
-    fn normalize_ast(ast: &File) -> String {
+    fn normalize_ast(ast: &File, policy: &NormalizationPolicy) -> String {
         let mut normalized_items = Vec::new();
 
         for item in &ast.items {
             match item {
                 Item::Fn(item_fn) => {
                     // Normalize function signature and body structure
-                    let normalized_item = Self::normalize_function(item_fn);
+                    let normalized_item = Self::normalize_function(item_fn, policy);
                     normalized_items.push(Item::Fn(normalized_item));
                 }
+                Item::Impl(item_impl) => {
+                    let mut normalized_item = item_impl.clone();
+                    for impl_item in &mut normalized_item.items {
+                        if let syn::ImplItem::Fn(method) = impl_item {
+                            *method = Self::normalize_method(method, policy);
+                        }
+                    }
+                    normalized_items.push(Item::Impl(normalized_item));
+                }
+                Item::Macro(_) if !policy.keep_macros => {}
                 Item::Struct(item_struct) => {
                     // Normalize struct definition
                     let normalized_item = Self::normalize_struct(item_struct);
@@ -107,37 +273,58 @@ impl CacheKey {
     }
 
     /// Normalize a function item
-    fn normalize_function(item_fn: &ItemFn) -> ItemFn {
-        // Remove attributes that don't affect verification
-        let attrs: Vec<Attribute> = item_fn
-            .attrs
-            .iter()
-            .filter(|attr| {
-                // Keep verification attributes, remove others like #[cfg(test)]
-                attr.path().is_ident("verification")
-                    || !attr
-                        .path()
-                        .segments
-                        .first()
-                        .map(|seg| seg.ident == "cfg")
-                        .unwrap_or(false)
-            })
-            .cloned()
-            .collect();
-
-        // Normalize function body by removing comments and normalizing whitespace
-        let normalized_block = Self::normalize_block(&item_fn.block);
-
+    fn normalize_function(item_fn: &ItemFn, policy: &NormalizationPolicy) -> ItemFn {
         ItemFn {
-            attrs,
+            attrs: Self::normalize_fn_attrs(&item_fn.attrs, policy),
             vis: item_fn.vis.clone(),
             sig: item_fn.sig.clone(),
-            block: Box::new(normalized_block),
+            block: Box::new(Self::normalize_block(&item_fn.block, policy)),
+        }
+    }
+
+    /// Normalize a method of an impl block
+    fn normalize_method(method: &syn::ImplItemFn, policy: &NormalizationPolicy) -> syn::ImplItemFn {
+        let mut normalized = method.clone();
+        if policy.strip_cfg_attrs || policy.strip_doc_comments {
+            normalized.attrs = Self::normalize_fn_attrs(&method.attrs, policy);
+        }
+        if policy.ignore_bodies || !policy.keep_macros {
+            normalized.block = Self::normalize_block(&method.block, policy);
         }
+        normalized
+    }
+
+    /// Remove function attributes that don't affect verification under `policy`
+    fn normalize_fn_attrs(attrs: &[Attribute], policy: &NormalizationPolicy) -> Vec<Attribute> {
+        attrs
+            .iter()
+            .filter(|attr| {
+                // Verification attributes are always kept
+                if attr.path().is_ident("verification") {
+                    return true;
+                }
+                let is_cfg = attr
+                    .path()
+                    .segments
+                    .first()
+                    .map(|seg| seg.ident == "cfg")
+                    .unwrap_or(false);
+                !(policy.strip_cfg_attrs && is_cfg
+                    || policy.strip_doc_comments && attr.path().is_ident("doc"))
+            })
+            .cloned()
+            .collect()
     }
 
     /// Normalize a block by removing comments and normalizing structure
-    fn normalize_block(block: &syn::Block) -> syn::Block {
+    fn normalize_block(block: &syn::Block, policy: &NormalizationPolicy) -> syn::Block {
+        if policy.ignore_bodies {
+            return syn::Block {
+                brace_token: block.brace_token,
+                stmts: Vec::new(),
+            };
+        }
+
         use syn::Stmt;
 
         let mut normalized_stmts = Vec::new();
@@ -157,8 +344,10 @@ impl CacheKey {
                     normalized_stmts.push(Stmt::Expr(expr.clone(), *semi));
                 }
                 Stmt::Macro(_mac) => {
-                    // Keep macro calls as-is
-                    normalized_stmts.push(stmt.clone());
+                    // Keep macro calls as-is unless the policy drops them
+                    if policy.keep_macros {
+                        normalized_stmts.push(stmt.clone());
+                    }
                 }
             }
         }
@@ -232,93 +421,8 @@ impl CacheKey {
         }
     }
 
-    /// Normalize formal specification content
-    fn normalize_spec(spec: &str) -> String {
-        // Remove comments and normalize whitespace for TLA+ and Alloy specs
-        let mut normalized = String::new();
-        let mut chars = spec.chars().peekable();
-        let mut in_line_comment = false;
-        let mut in_block_comment = false;
-        let mut in_string = false;
-        let mut escape_next = false;
-
-        while let Some(char) = chars.next() {
-            if escape_next {
-                if in_string {
-                    normalized.push('\\');
-                    normalized.push(char);
-                }
-                escape_next = false;
-                continue;
-            }
-
-            match char {
-                '\\' if in_string => {
-                    escape_next = true;
-                    continue;
-                }
-                '"' if !in_line_comment && !in_block_comment => {
-                    in_string = !in_string;
-                    normalized.push(char);
-                }
-                '/' if !in_string && !in_line_comment && !in_block_comment => {
-                    if let Some(&next_char) = chars.peek() {
-                        if next_char == '/' {
-                            // Start of line comment
-                            chars.next(); // consume the second '/'
-                            in_line_comment = true;
-                            continue;
-                        } else if next_char == '*' {
-                            // Start of block comment
-                            chars.next(); // consume the '*'
-                            in_block_comment = true;
-                            continue;
-                        }
-                    }
-                    normalized.push(char);
-                }
-                '*' if in_block_comment && !in_string => {
-                    if let Some(&next_char) = chars.peek() {
-                        if next_char == '/' {
-                            // End of block comment
-                            chars.next(); // consume the '/'
-                            in_block_comment = false;
-                            continue;
-                        }
-                    }
-                }
-                '\n' | '\r' => {
-                    if in_line_comment {
-                        in_line_comment = false;
-                    }
-                    if !in_block_comment && !in_string {
-                        // Normalize line breaks to single space
-                        if !normalized.ends_with(' ') && !normalized.is_empty() {
-                            normalized.push(' ');
-                        }
-                    }
-                }
-                c if c.is_whitespace() => {
-                    if !in_line_comment && !in_block_comment && !in_string {
-                        // Normalize whitespace to single space
-                        if !normalized.ends_with(' ') && !normalized.is_empty() {
-                            normalized.push(' ');
-                        }
-                    }
-                }
-                c => {
-                    if !in_line_comment && !in_block_comment {
-                        normalized.push(c);
-                    }
-                }
-            }
-        }
-
-        normalized.trim().to_string()
-    }
-
     /// Get current tool versions for cache invalidation
-    fn get_tool_versions() -> Result<ToolVersions> {
+    pub fn get_tool_versions() -> Result<ToolVersions> {
         let ferris_proof_version = env!("CARGO_PKG_VERSION").to_string();
         let mut external_tools = Vec::new();
 
@@ -338,6 +442,12 @@ impl CacheKey {
             }
         }
 
+        // Miri is a cargo subcommand of the nightly toolchain, not a binary
+        if let Ok(version) = Self::get_command_version("cargo", &["+nightly", "miri", "--version"])
+        {
+            external_tools.push(("Miri".to_string(), version));
+        }
+
         // Add Rust toolchain information
         if let Ok(rustc_version) = Self::get_rustc_commit_hash() {
             external_tools.push(("Rust Commit".to_string(), rustc_version));
@@ -356,17 +466,20 @@ impl CacheKey {
                 return Ok(version);
             }
         }
-        Err(anyhow!(
-            "Could not determine version for tool: {}",
-            tool_name
+        Err(FerrisProofError::tool(
+            tool_name,
+            "Could not determine version",
         ))
     }
 
     /// Get version of an external tool with a specific argument
     fn get_tool_version_with_arg(tool_name: &str, version_arg: &str) -> Result<String> {
-        let output = std::process::Command::new(tool_name)
-            .arg(version_arg)
-            .output();
+        Self::get_command_version(tool_name, &[version_arg])
+    }
+
+    /// Version printed by a tool invoked with `args`
+    fn get_command_version(tool_name: &str, args: &[&str]) -> Result<String> {
+        let output = std::process::Command::new(tool_name).args(args).output();
 
         match output {
             Ok(result) => {
@@ -387,13 +500,16 @@ impl CacheKey {
                 if !version.is_empty() {
                     Ok(version)
                 } else {
-                    Err(anyhow!(
-                        "Could not parse version from output: {}",
-                        version_line
+                    Err(FerrisProofError::tool(
+                        tool_name,
+                        format!("Could not parse version from output: {}", version_line),
                     ))
                 }
             }
-            Err(e) => Err(anyhow!("Failed to execute tool {}: {}", tool_name, e)),
+            Err(e) => Err(FerrisProofError::ToolNotFound {
+                tool: tool_name.to_string(),
+                message: e.to_string(),
+            }),
         }
     }
 
@@ -443,13 +559,21 @@ impl CacheKey {
                             .to_string());
                     }
                 }
-                Err(anyhow!("Could not find commit hash in rustc output"))
+                Err(FerrisProofError::tool(
+                    "rustc",
+                    "Could not find commit hash in output",
+                ))
             }
-            Ok(result) => Err(anyhow!(
-                "rustc returned non-zero exit code: {}",
-                result.status
-            )),
-            Err(e) => Err(anyhow!("Failed to execute rustc: {}", e)),
+            Ok(result) => Err(FerrisProofError::Tool {
+                tool: "rustc".to_string(),
+                message: "Returned non-zero exit code".to_string(),
+                exit_code: result.status.code(),
+                stderr: Some(String::from_utf8_lossy(&result.stderr).to_string()),
+            }),
+            Err(e) => Err(FerrisProofError::ToolNotFound {
+                tool: "rustc".to_string(),
+                message: e.to_string(),
+            }),
         }
     }
 }
@@ -466,10 +590,20 @@ pub struct ToolVersions {
     pub external_tools: Vec<(String, String)>, // Use Vec instead of HashMap for Hash trait
 }
 
+/// Verification results keyed by [`CacheKey`], persisted below a cache directory
+///
+/// Entries are read from disk on the first lookup of their key, so opening a
+/// cache costs nothing however many entries it holds. [`Self::load_from_disk`]
+/// is only needed by operations over every entry, such as filtering or
+/// compaction.
 pub struct VerificationCache {
     cache_dir: PathBuf,
-    entries: HashMap<CacheKey, CacheEntry>,
+    entries: ShardedEntries,
     persistent_storage: Option<PersistentStorage>,
+    environment: Option<crate::environment::EnvironmentSnapshot>,
+    normalization: NormalizationPolicies,
+    /// Detected by the first key built, since detection spawns every tool
+    tool_versions: OnceLock<ToolVersions>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -478,6 +612,19 @@ pub struct CacheEntry {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub ttl: std::time::Duration,
     pub metadata: CacheMetadata,
+    /// Files needed to reproduce the result, e.g. proptest regression seeds
+    ///
+    /// Always serialized: bincode entries have no field names to skip by.
+    #[serde(default)]
+    pub artifacts: Vec<Artifact>,
+}
+
+impl CacheEntry {
+    /// Whether the entry's TTL has run out at `now`
+    pub fn is_expired_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let age = now.signed_duration_since(self.timestamp);
+        age.to_std().unwrap_or(std::time::Duration::MAX) >= self.ttl
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -486,10 +633,316 @@ pub struct CacheMetadata {
     pub execution_time: std::time::Duration,
     pub memory_usage: u64,
     pub cache_hit_count: u32,
+    /// Module path or file the result was computed for
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Run seed, so a cached randomized result can be reproduced
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Environment the result was computed in
+    #[serde(default)]
+    pub environment: Option<crate::environment::EnvironmentSnapshot>,
+}
+
+/// Selects cache entries for a scoped clear; unset criteria match everything
+#[derive(Debug, Clone, Default)]
+pub struct CacheFilter {
+    pub layer: Option<Layer>,
+    /// Module path pattern where `*` matches any sequence, e.g. `crypto::*`
+    pub module: Option<String>,
+    pub older_than: Option<std::time::Duration>,
+    /// Entries verified under this configuration hash, e.g. after a config reload
+    pub config_hash: Option<String>,
+}
+
+impl CacheFilter {
+    pub fn is_empty(&self) -> bool {
+        self.layer.is_none()
+            && self.module.is_none()
+            && self.older_than.is_none()
+            && self.config_hash.is_none()
+    }
+
+    pub fn matches(
+        &self,
+        key: &CacheKey,
+        entry: &CacheEntry,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        if self.layer.is_some_and(|layer| layer != key.layer) {
+            return false;
+        }
+
+        if self
+            .config_hash
+            .as_ref()
+            .is_some_and(|hash| *hash != key.config_hash.0)
+        {
+            return false;
+        }
+
+        if let Some(pattern) = &self.module {
+            // Entries without a recorded target cannot be attributed to a module
+            let Some(target) = &entry.metadata.target else {
+                return false;
+            };
+            if !wildcard_match(pattern, &module_path_of(target)) {
+                return false;
+            }
+        }
+
+        if let Some(older_than) = self.older_than {
+            let age = now.signed_duration_since(entry.timestamp);
+            if age.to_std().unwrap_or_default() < older_than {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Last path segment of a type such as `codec::Header<T>`
+fn type_name(ty: &syn::Type) -> Option<&syn::Ident> {
+    match ty {
+        syn::Type::Path(type_path) => type_path.path.segments.last().map(|s| &s.ident),
+        _ => None,
+    }
+}
+
+/// Name of a named item such as a struct, const or macro
+fn item_ident(item: &Item) -> Option<&syn::Ident> {
+    match item {
+        Item::Const(i) => Some(&i.ident),
+        Item::Enum(i) => Some(&i.ident),
+        Item::Fn(i) => Some(&i.sig.ident),
+        Item::Macro(i) => i.ident.as_ref(),
+        Item::Mod(i) => Some(&i.ident),
+        Item::Static(i) => Some(&i.ident),
+        Item::Struct(i) => Some(&i.ident),
+        Item::Trait(i) => Some(&i.ident),
+        Item::Type(i) => Some(&i.ident),
+        Item::Union(i) => Some(&i.ident),
+        _ => None,
+    }
+}
+
+/// Source file with line endings unified, so checkouts on any OS hash alike
+fn read_source(path: &Path) -> Result<String> {
+    let source = std::fs::read_to_string(path).map_err(|e| FerrisProofError::io(path, e))?;
+    Ok(normalize_line_endings(&source))
+}
+
+/// Module path for a target, mapping `src/crypto/aes.rs` to `crypto::aes`
+pub fn module_path_of(target: &str) -> String {
+    let Some(path) = target.strip_suffix(".rs") else {
+        return target.to_string();
+    };
+
+    let path = path.replace('\\', "/");
+    let path = path
+        .split_once("src/")
+        .map_or(path.as_str(), |(_, rest)| rest);
+    let path = path.strip_suffix("/mod").unwrap_or(path);
+    if path == "lib" || path == "main" {
+        return "crate".to_string();
+    }
+
+    path.replace('/', "::")
+}
+
+/// Match a module path against a pattern where `*` matches any substring
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|&i| text.is_char_boundary(i))
+                .any(|i| wildcard_match(rest, &text[i..]))
+        }
+    }
+}
+
+/// Time cached results stay valid, per layer
+///
+/// A passing model-checking result may be trusted longer than a property
+/// test run, which samples different inputs on every run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheTtls {
+    default: std::time::Duration,
+    layers: HashMap<Layer, std::time::Duration>,
+}
+
+impl CacheTtls {
+    /// `default` for every layer without its own TTL
+    pub fn new(default: std::time::Duration, layers: HashMap<Layer, std::time::Duration>) -> Self {
+        Self { default, layers }
+    }
+
+    pub fn for_layer(&self, layer: Layer) -> std::time::Duration {
+        self.layers.get(&layer).copied().unwrap_or(self.default)
+    }
+}
+
+impl Default for CacheTtls {
+    fn default() -> Self {
+        Self::new(std::time::Duration::from_secs(24 * 60 * 60), HashMap::new())
+    }
+}
+
+/// Parse an age such as `30m`, `12h`, `7d` or `2w`
+pub fn parse_age(value: &str) -> std::result::Result<std::time::Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid age '{}': expected e.g. 7d", value))?;
+
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        "" => {
+            return Err(format!(
+                "missing unit in age '{}': use s, m, h, d or w",
+                value
+            ))
+        }
+        _ => return Err(format!("invalid age unit '{}': use s, m, h, d or w", unit)),
+    };
+
+    let total = amount
+        .checked_mul(seconds)
+        .ok_or_else(|| format!("invalid age '{}': too large", value))?;
+    Ok(std::time::Duration::from_secs(total))
+}
+
+/// Independently locked shards of the in-memory cache
+const ENTRY_SHARDS: usize = 16;
+
+/// In-memory entries split across independently locked shards
+///
+/// Lookups and stores through `&self` lock one shard, so concurrent access
+/// to different keys rarely waits. Removal through `&mut self` bypasses the
+/// locks entirely.
+struct ShardedEntries {
+    shards: Vec<RwLock<HashMap<CacheKey, Arc<CacheEntry>>>>,
+}
+
+impl ShardedEntries {
+    fn new() -> Self {
+        Self {
+            shards: (0..ENTRY_SHARDS)
+                .map(|_| RwLock::new(HashMap::new()))
+                .collect(),
+        }
+    }
+
+    fn shard_index(&self, key: &CacheKey) -> usize {
+        use std::hash::{Hash, Hasher as _};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        key.hash(&mut hasher);
+        (hasher.finish() as usize) % self.shards.len()
+    }
+
+    // A shard only ever holds whole entries, so a poisoned one is still usable
+    fn read(
+        shard: &RwLock<HashMap<CacheKey, Arc<CacheEntry>>>,
+    ) -> std::sync::RwLockReadGuard<'_, HashMap<CacheKey, Arc<CacheEntry>>> {
+        shard.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn shard_mut(&mut self, key: &CacheKey) -> &mut HashMap<CacheKey, Arc<CacheEntry>> {
+        let index = self.shard_index(key);
+        self.shards[index]
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<Arc<CacheEntry>> {
+        Self::read(&self.shards[self.shard_index(key)])
+            .get(key)
+            .cloned()
+    }
+
+    /// Insert through a shared reference, keeping an entry stored meanwhile
+    fn insert_loaded(&self, key: CacheKey, entry: Arc<CacheEntry>) -> Arc<CacheEntry> {
+        let shard = &self.shards[self.shard_index(&key)];
+        shard
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(key)
+            .or_insert(entry)
+            .clone()
+    }
+
+    fn insert(&self, key: CacheKey, entry: CacheEntry) {
+        let shard = &self.shards[self.shard_index(&key)];
+        shard
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, Arc::new(entry));
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        self.shard_mut(key).remove(key);
+    }
+
+    fn clear(&mut self) {
+        for shard in &mut self.shards {
+            shard.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| Self::read(shard).len())
+            .sum()
+    }
+
+    /// Every entry with its key, shard by shard
+    fn snapshot(&self) -> Vec<(CacheKey, Arc<CacheEntry>)> {
+        self.shards
+            .iter()
+            .flat_map(|shard| {
+                Self::read(shard)
+                    .iter()
+                    .map(|(key, entry)| (key.clone(), entry.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn values(&self) -> Vec<Arc<CacheEntry>> {
+        self.shards
+            .iter()
+            .flat_map(|shard| Self::read(shard).values().cloned().collect::<Vec<_>>())
+            .collect()
+    }
 }
 
+/// On-disk entries, shared safely between concurrent `ferris-proof` processes
+///
+/// Entry reads and writes hold a shared advisory lock on `<cache>/.lock` and
+/// replace files atomically, so they only exclude maintenance such as clear
+/// and compaction, which hold the lock exclusively. An operation that cannot
+/// take the lock gives up with [`FerrisProofError::CacheLocked`], which the
+/// cache treats as a miss or a skipped write.
+#[derive(Clone)]
 pub struct PersistentStorage {
     cache_dir: PathBuf,
+    /// Set while this process holds the exclusive lock, so entry operations
+    /// run during maintenance do not wait on it
+    maintenance: Arc<AtomicBool>,
 }
 
 impl VerificationCache {
@@ -501,8 +954,11 @@ impl VerificationCache {
 
         Self {
             cache_dir: cache_dir.clone(),
-            entries: HashMap::new(),
+            entries: ShardedEntries::new(),
             persistent_storage: Some(persistent_storage),
+            environment: None,
+            normalization: NormalizationPolicies::default(),
+            tool_versions: OnceLock::new(),
         }
     }
 
@@ -513,38 +969,152 @@ impl VerificationCache {
 
         Self {
             cache_dir: cache_dir.clone(),
-            entries: HashMap::new(),
+            entries: ShardedEntries::new(),
             persistent_storage: Some(persistent_storage),
+            environment: None,
+            normalization: NormalizationPolicies::default(),
+            tool_versions: OnceLock::new(),
+        }
+    }
+
+    /// Cache that never touches the disk, for throwaway runs
+    pub fn in_memory() -> Self {
+        Self {
+            cache_dir: std::env::temp_dir().join("ferris-proof-cache"),
+            entries: ShardedEntries::new(),
+            persistent_storage: None,
+            environment: None,
+            normalization: NormalizationPolicies::default(),
+            tool_versions: OnceLock::new(),
         }
     }
 
     /// Get cache entry, checking for expiration and validity
-    pub fn get(&self, key: &CacheKey) -> Option<&CacheEntry> {
-        if let Some(entry) = self.entries.get(key) {
-            if self.is_entry_valid(entry) {
-                return Some(entry);
+    ///
+    /// An entry not yet in memory is read from disk by its key.
+    pub fn get(&self, key: &CacheKey) -> Option<Arc<CacheEntry>> {
+        let entry = match self.entries.get(key) {
+            Some(entry) => entry,
+            None => {
+                let loaded = self.persistent_storage.as_ref()?.read_entry(key)?;
+                self.entries.insert_loaded(key.clone(), Arc::new(loaded))
             }
-        }
-        None
+        };
+        self.is_entry_valid(&entry).then_some(entry)
+    }
+
+    /// Like [`Self::get`], reading a missing entry on the blocking thread pool
+    pub async fn get_async(&self, key: &CacheKey) -> Option<Arc<CacheEntry>> {
+        let entry = match self.entries.get(key) {
+            Some(entry) => entry,
+            None => {
+                let storage = self.persistent_storage.clone()?;
+                let owned_key = key.clone();
+                let loaded = tokio::task::spawn_blocking(move || storage.read_entry(&owned_key))
+                    .await
+                    .ok()??;
+                self.entries.insert_loaded(key.clone(), Arc::new(loaded))
+            }
+        };
+        self.is_entry_valid(&entry).then_some(entry)
     }
 
     /// Check if a cache entry is still valid (not expired)
     fn is_entry_valid(&self, entry: &CacheEntry) -> bool {
-        let now = chrono::Utc::now();
-        let age = now.signed_duration_since(entry.timestamp);
-        age.to_std().unwrap_or(std::time::Duration::MAX) < entry.ttl
+        !entry.is_expired_at(chrono::Utc::now())
     }
 
-    /// Store cache entry with TTL and automatic persistence
-    pub fn store(&mut self, key: CacheKey, entry: CacheEntry) {
-        self.entries.insert(key.clone(), entry.clone());
+    /// Environment recorded in entries stored from now on that have none
+    pub fn set_environment(&mut self, environment: crate::environment::EnvironmentSnapshot) {
+        self.environment = Some(environment);
+    }
+
+    /// Per-layer normalization of sources hashed into keys built by this cache
+    pub fn set_normalization(&mut self, normalization: NormalizationPolicies) {
+        self.normalization = normalization;
+    }
 
+    /// Cache key for a target's layer under this cache's normalization policies
+    pub fn key_for(
+        &self,
+        target: &crate::verification::Target,
+        layer: Layer,
+        config_hash: &str,
+    ) -> Result<CacheKey> {
+        let tool_versions = match self.tool_versions.get() {
+            Some(versions) => versions.clone(),
+            None => {
+                let detected = CacheKey::get_tool_versions()?;
+                self.tool_versions.get_or_init(|| detected).clone()
+            }
+        };
+        Ok(CacheKey {
+            content_hash: CacheKey::compute_content_hash_with(
+                target,
+                &self.normalization.for_layer(layer),
+            )?,
+            config_hash: ConfigHash(config_hash.to_string()),
+            tool_versions,
+            layer,
+        })
+    }
+
+    /// Store cache entry with TTL and automatic persistence
+    pub fn store(&self, key: CacheKey, mut entry: CacheEntry) {
+        if entry.metadata.environment.is_none() {
+            entry.metadata.environment = self.environment.clone();
+        }
         // Persist to disk if persistent storage is available
         if let Some(storage) = &self.persistent_storage {
             if let Err(e) = storage.store(&key, &entry) {
                 tracing::warn!("Failed to persist cache entry: {}", e);
             }
         }
+
+        self.entries.insert(key, entry);
+    }
+
+    /// Store the layer results of verified targets, returning how many were stored
+    pub fn store_results(
+        &self,
+        results: &[(crate::verification::Target, LayerResult)],
+        config_hash: impl Fn(&crate::verification::Target) -> String,
+        ttls: &CacheTtls,
+        seed: Option<u64>,
+    ) -> usize {
+        let mut stored = 0;
+        for (target, result) in results {
+            let key = match self.key_for(target, result.layer, &config_hash(target)) {
+                Ok(key) => key,
+                Err(e) => {
+                    tracing::debug!("Not caching {}: {}", target.label(), e);
+                    continue;
+                }
+            };
+            let file_size = target.file().map_or(0, |path| {
+                std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+            });
+            self.store(
+                key,
+                CacheEntry {
+                    result: result.clone(),
+                    timestamp: chrono::Utc::now(),
+                    ttl: ttls.for_layer(result.layer),
+                    metadata: CacheMetadata {
+                        file_size,
+                        execution_time: result.execution_time,
+                        memory_usage: 0,
+                        cache_hit_count: 0,
+                        target: Some(target.label()),
+                        seed,
+                        environment: None,
+                    },
+                    artifacts: Vec::new(),
+                },
+            );
+            stored += 1;
+        }
+        stored
     }
 
     /// Invalidate cache entry (remove from memory and disk)
@@ -569,20 +1139,89 @@ impl VerificationCache {
         }
     }
 
+    /// Remove the entries selected by `filter` from memory and disk
+    ///
+    /// Returns the number of entries removed.
+    pub fn clear_matching(&mut self, filter: &CacheFilter) -> usize {
+        let now = chrono::Utc::now();
+        let keys: Vec<CacheKey> = self
+            .entries
+            .snapshot()
+            .into_iter()
+            .filter(|(key, entry)| filter.matches(key, entry, now))
+            .map(|(key, _)| key)
+            .collect();
+
+        for key in &keys {
+            self.invalidate(key);
+        }
+
+        keys.len()
+    }
+
+    /// Valid entries whose result contains a violation with the given id
+    pub fn entries_with_violation(&self, violation_id: &str) -> Vec<Arc<CacheEntry>> {
+        self.entries_matching(|v| v.id == violation_id)
+    }
+
+    /// Valid entries whose result contains a violation matching `predicate`
+    pub fn entries_matching(&self, predicate: impl Fn(&Violation) -> bool) -> Vec<Arc<CacheEntry>> {
+        self.entries
+            .values()
+            .into_iter()
+            .filter(|entry| self.is_entry_valid(entry))
+            .filter(|entry| entry.result.violations.iter().any(&predicate))
+            .collect()
+    }
+
     /// Load cache from persistent storage with validation
     pub fn load_from_disk(&mut self) -> Result<()> {
-        if let Some(storage) = &self.persistent_storage {
-            let loaded_entries = storage.load_all()?;
+        if let Some(storage) = self.persistent_storage.clone() {
+            let loaded = storage.load_all();
+            self.insert_loaded(&storage, loaded)?;
+        }
+        Ok(())
+    }
 
-            // Filter out expired entries during load
-            let _now = chrono::Utc::now();
-            for (key, entry) in loaded_entries {
-                if self.is_entry_valid(&entry) {
-                    self.entries.insert(key, entry);
-                } else {
-                    // Remove expired entries from disk
-                    let _ = storage.remove(&key);
-                }
+    /// Like [`Self::load_from_disk`], reading entries on the blocking thread pool
+    pub async fn load_from_disk_async(&mut self) -> Result<()> {
+        if let Some(storage) = self.persistent_storage.clone() {
+            let reader = storage.clone();
+            let loaded = tokio::task::spawn_blocking(move || reader.load_all())
+                .await
+                .map_err(|e| {
+                    FerrisProofError::cache(
+                        format!("Cache load task failed: {}", e),
+                        Some(&self.cache_dir),
+                    )
+                })?;
+            self.insert_loaded(&storage, loaded)?;
+        }
+        Ok(())
+    }
+
+    fn insert_loaded(
+        &mut self,
+        storage: &PersistentStorage,
+        loaded: Result<HashMap<CacheKey, CacheEntry>>,
+    ) -> Result<()> {
+        let loaded_entries = match loaded {
+            Ok(entries) => entries,
+            Err(e @ FerrisProofError::CacheLocked { .. }) => {
+                // Another run is compacting or clearing; start cold
+                tracing::warn!("{}; continuing without cached results", e);
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+
+        // Filter out expired entries during load
+        for (key, entry) in loaded_entries {
+            if self.is_entry_valid(&entry) {
+                self.entries.insert(key, entry);
+            } else {
+                // Remove expired entries from disk
+                let _ = storage.remove(&key);
             }
         }
         Ok(())
@@ -591,7 +1230,7 @@ impl VerificationCache {
     /// Save cache to persistent storage
     pub fn save_to_disk(&self) -> Result<()> {
         if let Some(storage) = &self.persistent_storage {
-            storage.save_all(&self.entries)?;
+            storage.save_all(&self.entries.snapshot())?;
         }
         Ok(())
     }
@@ -601,10 +1240,9 @@ impl VerificationCache {
         let now = chrono::Utc::now();
         let mut expired_keys = Vec::new();
 
-        for (key, entry) in &self.entries {
-            let age = now.signed_duration_since(entry.timestamp);
-            if age.to_std().unwrap_or(std::time::Duration::MAX) >= entry.ttl {
-                expired_keys.push(key.clone());
+        for (key, entry) in self.entries.snapshot() {
+            if entry.is_expired_at(now) {
+                expired_keys.push(key);
             }
         }
 
@@ -624,8 +1262,7 @@ impl VerificationCache {
         let now = chrono::Utc::now();
 
         for entry in self.entries.values() {
-            let age = now.signed_duration_since(entry.timestamp);
-            if age.to_std().unwrap_or(std::time::Duration::MAX) >= entry.ttl {
+            if entry.is_expired_at(now) {
                 expired_entries += 1;
             }
             total_size += entry.metadata.file_size;
@@ -668,7 +1305,17 @@ impl VerificationCache {
     }
 
     /// Compact cache by removing expired entries and optimizing storage
+    ///
+    /// Holds the cache lock exclusively, so concurrent runs see misses rather
+    /// than a half-compacted cache.
     pub fn compact(&mut self) -> Result<CompactionResult> {
+        match self.persistent_storage.clone() {
+            Some(storage) => storage.with_exclusive(|| self.compact_entries()),
+            None => self.compact_entries(),
+        }
+    }
+
+    fn compact_entries(&mut self) -> Result<CompactionResult> {
         let initial_entries = self.entries.len();
         let initial_size = self.disk_size().unwrap_or(0);
 
@@ -692,41 +1339,184 @@ impl VerificationCache {
     }
 }
 
+/// A persisted entry as read from disk
+enum StoredEntry {
+    Loaded {
+        key: CacheKey,
+        entry: Box<CacheEntry>,
+        schema_version: u16,
+    },
+    /// Written by a newer FerrisProof; left on disk and treated as a miss
+    Newer(u16),
+    /// Written in a schema too old to decode; removed and treated as a miss
+    Stale(u16),
+}
+
+/// Deserialize a key and entry written with `schema_version`
+fn decode_entry(schema_version: u16, serialized: &[u8]) -> Result<(CacheKey, CacheEntry)> {
+    match schema_version {
+        ENTRY_SCHEMA_VERSION => Ok(bincode::deserialize(serialized)?),
+        _ => Err(FerrisProofError::cache(
+            format!("Unknown entry schema version {}", schema_version),
+            None,
+        )),
+    }
+}
+
 impl PersistentStorage {
     fn new(cache_dir: &Path) -> Self {
         Self {
             cache_dir: cache_dir.to_path_buf(),
+            maintenance: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn lock_file(&self) -> Result<fd_lock::RwLock<std::fs::File>> {
+        std::fs::create_dir_all(&self.cache_dir)?;
+        let path = self.cache_dir.join(LOCK_FILE);
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| FerrisProofError::io(&path, e))?;
+        Ok(fd_lock::RwLock::new(file))
+    }
+
+    /// Run an entry operation under the shared lock
+    fn with_shared<T>(&self, op: impl FnOnce() -> Result<T>) -> Result<T> {
+        if self.maintenance.load(Ordering::Acquire) {
+            return op();
+        }
+        let lock = self.lock_file()?;
+        for attempt in 1..=LOCK_ATTEMPTS {
+            match lock.try_read() {
+                Ok(_guard) => return op(),
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    if attempt < LOCK_ATTEMPTS {
+                        std::thread::sleep(LOCK_RETRY_INTERVAL);
+                    }
+                }
+                Err(e) => return Err(FerrisProofError::io(self.cache_dir.join(LOCK_FILE), e)),
+            }
         }
+        Err(FerrisProofError::CacheLocked {
+            path: self.cache_dir.clone(),
+        })
+    }
+
+    /// Run maintenance such as clear or compaction under the exclusive lock
+    fn with_exclusive<T>(&self, op: impl FnOnce() -> Result<T>) -> Result<T> {
+        let mut lock = self.lock_file()?;
+        for attempt in 1..=LOCK_ATTEMPTS {
+            match lock.try_write() {
+                Ok(_guard) => {
+                    self.maintenance.store(true, Ordering::Release);
+                    let result = op();
+                    self.maintenance.store(false, Ordering::Release);
+                    return result;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    if attempt < LOCK_ATTEMPTS {
+                        std::thread::sleep(LOCK_RETRY_INTERVAL);
+                    }
+                }
+                Err(e) => return Err(FerrisProofError::io(self.cache_dir.join(LOCK_FILE), e)),
+            }
+        }
+        Err(FerrisProofError::CacheLocked {
+            path: self.cache_dir.clone(),
+        })
     }
 
     fn store(&self, key: &CacheKey, entry: &CacheEntry) -> Result<()> {
-        let file_name = self.key_to_filename(key);
-        let file_path = self.cache_dir.join(file_name);
+        self.with_shared(|| self.write_entry(key, entry))
+    }
 
-        // Create cache directory if it doesn't exist
-        std::fs::create_dir_all(&self.cache_dir)?;
+    fn write_entry(&self, key: &CacheKey, entry: &CacheEntry) -> Result<()> {
+        let file_path = self.entry_path(key);
+
+        // Create the entry's subdirectory if it doesn't exist
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
 
         let serialized = bincode::serialize(&(key, entry))?;
 
+        let mut content = Vec::with_capacity(serialized.len() / 2 + 6);
+        content.extend_from_slice(ENTRY_MAGIC);
+        content.extend_from_slice(&ENTRY_SCHEMA_VERSION.to_le_bytes());
         // Use zstd compression with level 3 for good balance of speed/compression
-        let compressed = zstd::encode_all(serialized.as_slice(), 3)?;
-
-        // Write atomically using a temporary file
-        let temp_path = file_path.with_extension("tmp");
-        std::fs::write(&temp_path, compressed)?;
-        std::fs::rename(temp_path, file_path)?;
+        zstd::stream::copy_encode(serialized.as_slice(), &mut content, 3)?;
+
+        // Write atomically through a temporary file no other writer shares
+        let temp_path = file_path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4().simple()));
+        std::fs::write(&temp_path, content)?;
+        if let Err(e) = std::fs::rename(&temp_path, file_path) {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
 
         Ok(())
     }
 
     fn remove(&self, key: &CacheKey) -> Result<()> {
-        let file_name = self.key_to_filename(key);
-        let file_path = self.cache_dir.join(file_name);
+        let file_path = self.entry_path(key);
 
-        if file_path.exists() {
-            std::fs::remove_file(file_path)?;
+        self.with_shared(|| match std::fs::remove_file(&file_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        })
+    }
+
+    /// Read the entry stored for `key`, if any
+    ///
+    /// Entries are replaced atomically, so a single read needs no lock; a
+    /// concurrent clear at worst turns it into a miss.
+    fn read_entry(&self, key: &CacheKey) -> Option<CacheEntry> {
+        let path = self.entry_path(key);
+        match self.with_shared(|| self.load_entry(&path)) {
+            Ok(StoredEntry::Loaded {
+                key: stored_key,
+                entry,
+                schema_version,
+            }) if stored_key == *key => {
+                if schema_version < ENTRY_SCHEMA_VERSION {
+                    let _ = self.with_shared(|| {
+                        self.migrate_entry(&path, schema_version, key, &entry);
+                        Ok(())
+                    });
+                }
+                Some(*entry)
+            }
+            Ok(StoredEntry::Loaded { .. }) => {
+                tracing::warn!("Cache entry {:?} is stored under another key", path);
+                None
+            }
+            Ok(StoredEntry::Newer(_)) => None,
+            Ok(StoredEntry::Stale(schema_version)) => {
+                tracing::debug!(
+                    "Discarding cache entry {:?} with stale schema {}",
+                    path,
+                    schema_version
+                );
+                let _ = self.remove(key);
+                None
+            }
+            Err(FerrisProofError::Io { source, .. })
+                if source.kind() == std::io::ErrorKind::NotFound =>
+            {
+                None
+            }
+            Err(FerrisProofError::CacheLocked { .. }) => {
+                tracing::debug!("Cache is locked, treating {:?} as a miss", path);
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Failed to read cache entry {:?}: {}", path, e);
+                None
+            }
         }
-        Ok(())
     }
 
     fn load_all(&self) -> Result<HashMap<CacheKey, CacheEntry>> {
@@ -753,8 +1543,29 @@ impl PersistentStorage {
                     && path.extension().and_then(|s| s.to_str()) == Some("cache")
                 {
                     match storage.load_entry(&path) {
-                        Ok((key, cache_entry)) => {
-                            entries.insert(key, cache_entry);
+                        Ok(StoredEntry::Loaded {
+                            key,
+                            entry: cache_entry,
+                            schema_version,
+                        }) => {
+                            storage.migrate_entry(&path, schema_version, &key, &cache_entry);
+                            entries.insert(key, *cache_entry);
+                        }
+                        Ok(StoredEntry::Newer(schema_version)) => {
+                            // Kept for the newer FerrisProof sharing this cache
+                            tracing::debug!(
+                                "Skipping cache entry {:?} with newer schema {}",
+                                path,
+                                schema_version
+                            );
+                        }
+                        Ok(StoredEntry::Stale(schema_version)) => {
+                            tracing::debug!(
+                                "Discarding cache entry {:?} with stale schema {}",
+                                path,
+                                schema_version
+                            );
+                            let _ = std::fs::remove_file(&path);
                         }
                         Err(e) => {
                             tracing::warn!("Failed to load cache entry {:?}: {}", path, e);
@@ -767,20 +1578,20 @@ impl PersistentStorage {
             Ok(())
         }
 
-        visit_dir(&self.cache_dir, &mut entries, self)?;
+        self.with_shared(|| visit_dir(&self.cache_dir, &mut entries, self))?;
         Ok(entries)
     }
 
-    fn save_all(&self, entries: &HashMap<CacheKey, CacheEntry>) -> Result<()> {
-        std::fs::create_dir_all(&self.cache_dir)?;
-
-        for (key, entry) in entries {
-            if let Err(e) = self.store(key, entry) {
-                tracing::warn!("Failed to save cache entry: {}", e);
-                // Continue with other entries even if one fails
+    fn save_all(&self, entries: &[(CacheKey, Arc<CacheEntry>)]) -> Result<()> {
+        self.with_shared(|| {
+            for (key, entry) in entries {
+                if let Err(e) = self.write_entry(key, entry) {
+                    tracing::warn!("Failed to save cache entry: {}", e);
+                    // Continue with other entries even if one fails
+                }
             }
-        }
-        Ok(())
+            Ok(())
+        })
     }
 
     fn clear(&self) -> Result<()> {
@@ -800,8 +1611,13 @@ impl PersistentStorage {
                     // Try to remove empty directory
                     let _ = std::fs::remove_dir(&path);
                 } else if path.is_file()
-                    && path.extension().and_then(|s| s.to_str()) == Some("cache")
+                    && matches!(
+                        path.extension().and_then(|s| s.to_str()),
+                        Some("cache") | Some("tmp")
+                    )
                 {
+                    // Temporary files are leftovers of interrupted writes, as
+                    // no writer can be active under the exclusive lock
                     if let Err(e) = std::fs::remove_file(&path) {
                         tracing::warn!("Failed to remove cache file {:?}: {}", path, e);
                     }
@@ -810,37 +1626,70 @@ impl PersistentStorage {
             Ok(())
         }
 
-        visit_dir(&self.cache_dir)?;
-        Ok(())
-    }
-
-    fn load_entry(&self, path: &Path) -> Result<(CacheKey, CacheEntry)> {
-        let compressed = std::fs::read(path)?;
-        let serialized = zstd::decode_all(compressed.as_slice())?;
-        let (key, entry): (CacheKey, CacheEntry) = bincode::deserialize(&serialized)?;
-        Ok((key, entry))
+        self.with_exclusive(|| visit_dir(&self.cache_dir))
     }
 
-    /// Generate content-addressed filename from cache key
-    fn key_to_filename(&self, key: &CacheKey) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        let hash = hasher.finish();
+    /// Read an entry, accepting every readable schema version up to the current one
+    fn load_entry(&self, path: &Path) -> Result<StoredEntry> {
+        let content = std::fs::read(path).map_err(|e| FerrisProofError::io(path, e))?;
+        let (schema_version, compressed) = match content.strip_prefix(ENTRY_MAGIC.as_slice()) {
+            Some(rest) if rest.len() >= 2 => (u16::from_le_bytes([rest[0], rest[1]]), &rest[2..]),
+            Some(_) => {
+                return Err(FerrisProofError::cache(
+                    "Truncated entry header",
+                    Some(path),
+                ))
+            }
+            None => (LEGACY_SCHEMA_VERSION, content.as_slice()),
+        };
+        if schema_version > ENTRY_SCHEMA_VERSION {
+            return Ok(StoredEntry::Newer(schema_version));
+        }
+        if schema_version < MIN_READABLE_SCHEMA_VERSION {
+            return Ok(StoredEntry::Stale(schema_version));
+        }
 
-        // Use content-addressed storage: first two hex digits as subdirectory
-        let subdir = format!("{:02x}", (hash >> 56) & 0xFF);
-        let filename = format!("{:016x}.cache", hash);
+        let serialized = zstd::decode_all(compressed)?;
+        let (key, entry) = decode_entry(schema_version, &serialized).map_err(|e| {
+            FerrisProofError::cache(
+                format!("Unreadable schema {} entry: {}", schema_version, e),
+                Some(path),
+            )
+        })?;
+        Ok(StoredEntry::Loaded {
+            key,
+            entry: Box::new(entry),
+            schema_version,
+        })
+    }
 
-        // Create subdirectory path
-        let subdir_path = self.cache_dir.join(&subdir);
-        if let Err(e) = std::fs::create_dir_all(&subdir_path) {
-            tracing::warn!("Failed to create cache subdirectory {}: {}", subdir, e);
+    /// Rewrite an entry from an older schema or outside its canonical path
+    fn migrate_entry(&self, path: &Path, schema_version: u16, key: &CacheKey, entry: &CacheEntry) {
+        let canonical = self.entry_path(key);
+        if schema_version == ENTRY_SCHEMA_VERSION && path == canonical {
+            return;
         }
+        match self.write_entry(key, entry) {
+            Ok(()) => {
+                tracing::debug!(
+                    "Migrated cache entry {:?} from schema {}",
+                    path,
+                    schema_version
+                );
+                if path != canonical {
+                    let _ = std::fs::remove_file(path);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to migrate cache entry {:?}: {}", path, e),
+        }
+    }
 
-        format!("{}/{}", subdir, filename)
+    /// Content-addressed path of an entry: `<first two hex digits>/<fingerprint>.cache`
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        let fingerprint = key.fingerprint();
+        self.cache_dir
+            .join(&fingerprint[..2])
+            .join(format!("{}.cache", fingerprint))
     }
 
     /// Get cache directory size in bytes
@@ -0,0 +1,261 @@
+use crate::errors::{FerrisProofError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+const INDEX_FILE: &str = "index.json";
+/// Held exclusively while a run reads, changes and writes back the index
+const INDEX_LOCK: &str = "index.lock";
+const PROJECTS_DIR: &str = "projects";
+
+/// Project and toolchain a cache namespace belongs to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamespaceEntry {
+    pub project_root: PathBuf,
+    pub toolchain: String,
+    pub last_used: DateTime<Utc>,
+}
+
+/// Top-level index of the per-project namespaces below a cache root
+///
+/// Each project and toolchain pair gets its own directory under
+/// `<root>/projects/<id>`, so projects cannot collide and can be cleared
+/// independently. Caches from before namespacing are moved into the first
+/// namespace opened. Concurrent runs update the index one at a time, each
+/// starting from what is on disk, so no run drops another's namespace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheIndex {
+    #[serde(skip)]
+    root: PathBuf,
+    pub namespaces: BTreeMap<String, NamespaceEntry>,
+}
+
+impl CacheIndex {
+    /// Load the index under `root`; a missing index is empty
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(INDEX_FILE);
+        let mut index: Self = if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&content).map_err(|e| {
+                FerrisProofError::cache(format!("Invalid cache index: {}", e), Some(&path))
+            })?
+        } else {
+            Self::default()
+        };
+        index.root = root.to_path_buf();
+        Ok(index)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.root)?;
+        // Replace atomically so concurrent runs never read a partial index
+        let path = self.root.join(INDEX_FILE);
+        let temp_path = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4().simple()));
+        std::fs::write(&temp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&temp_path, &path).map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            FerrisProofError::io(&path, e)
+        })?;
+        Ok(())
+    }
+
+    /// Namespace id for a project and toolchain
+    pub fn namespace_id(project_root: &Path, toolchain: &str) -> String {
+        let project_root = project_root
+            .canonicalize()
+            .unwrap_or_else(|_| project_root.to_path_buf());
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(project_root.to_string_lossy().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(toolchain.as_bytes());
+        hasher.finalize().to_hex()[..16].to_string()
+    }
+
+    pub fn namespace_dir(&self, id: &str) -> PathBuf {
+        self.root.join(PROJECTS_DIR).join(id)
+    }
+
+    /// Reload the index, apply `op` and save it, all under the exclusive index lock
+    fn update<T>(&mut self, op: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        std::fs::create_dir_all(&self.root)?;
+        let lock_path = self.root.join(INDEX_LOCK);
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .map_err(|e| FerrisProofError::io(&lock_path, e))?;
+        let mut lock = fd_lock::RwLock::new(file);
+        let _guard = lock
+            .write()
+            .map_err(|e| FerrisProofError::io(&lock_path, e))?;
+
+        *self = Self::load(&self.root)?;
+        let result = op(self)?;
+        self.save()?;
+        Ok(result)
+    }
+
+    /// Directory for the project's cache, registering it and migrating a flat cache
+    pub fn open_namespace(&mut self, project_root: &Path, toolchain: &str) -> Result<PathBuf> {
+        let id = Self::namespace_id(project_root, toolchain);
+        let dir = self.namespace_dir(&id);
+
+        self.update(|index| {
+            if index.namespaces.is_empty() {
+                index.migrate_flat_layout(&dir)?;
+            }
+            index.namespaces.insert(
+                id,
+                NamespaceEntry {
+                    project_root: project_root
+                        .canonicalize()
+                        .unwrap_or_else(|_| project_root.to_path_buf()),
+                    toolchain: toolchain.to_string(),
+                    last_used: Utc::now(),
+                },
+            );
+            Ok(())
+        })?;
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Drop every namespace of a project, returning the removed directories
+    pub fn remove_project(&mut self, project_root: &Path) -> Result<Vec<PathBuf>> {
+        let project_root = project_root
+            .canonicalize()
+            .unwrap_or_else(|_| project_root.to_path_buf());
+
+        self.update(|index| {
+            let ids: Vec<String> = index
+                .namespaces
+                .iter()
+                .filter(|(_, entry)| entry.project_root == project_root)
+                .map(|(id, _)| id.clone())
+                .collect();
+
+            let mut removed = Vec::new();
+            for id in ids {
+                let dir = index.namespace_dir(&id);
+                if dir.exists() {
+                    std::fs::remove_dir_all(&dir)?;
+                }
+                index.namespaces.remove(&id);
+                removed.push(dir);
+            }
+            Ok(removed)
+        })
+    }
+
+    /// Move everything a pre-namespace cache left in the root into `dir`
+    fn migrate_flat_layout(&self, dir: &Path) -> Result<()> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return Ok(());
+        };
+        let legacy: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| {
+                path.file_name().is_some_and(|name| {
+                    name != INDEX_FILE && name != INDEX_LOCK && name != PROJECTS_DIR
+                })
+            })
+            .collect();
+        if legacy.is_empty() {
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(dir)?;
+        for path in &legacy {
+            if let Some(name) = path.file_name() {
+                debug!("Migrating {:?} into {:?}", path, dir);
+                std::fs::rename(path, dir.join(name))?;
+            }
+        }
+        info!(
+            "Migrated {} cache item(s) from the flat layout into {:?}",
+            legacy.len(),
+            dir
+        );
+        Ok(())
+    }
+}
+
+/// Active Rust toolchain, e.g. `rustc 1.80.0 (051478957 2024-07-21)`
+pub fn toolchain_id() -> String {
+    std::process::Command::new("rustc")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .map(|line| line.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_namespaces_separate_projects_and_migrate_flat_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("cache");
+        let project_a = temp_dir.path().join("a");
+        let project_b = temp_dir.path().join("b");
+        std::fs::create_dir_all(root.join("ab")).unwrap();
+        std::fs::write(root.join("ab").join("00ab.cache"), b"entry").unwrap();
+        std::fs::write(root.join("plugin-stats.json"), b"{}").unwrap();
+
+        let mut index = CacheIndex::load(&root).unwrap();
+        let dir_a = index.open_namespace(&project_a, "rustc 1.80.0").unwrap();
+        assert!(dir_a.join("ab").join("00ab.cache").exists());
+        assert!(dir_a.join("plugin-stats.json").exists());
+        assert!(!root.join("ab").exists());
+
+        let dir_b = index.open_namespace(&project_b, "rustc 1.80.0").unwrap();
+        let dir_a_nightly = index
+            .open_namespace(&project_a, "rustc 1.82.0-nightly")
+            .unwrap();
+        assert_ne!(dir_a, dir_b);
+        assert_ne!(dir_a, dir_a_nightly);
+        assert!(!dir_b.join("plugin-stats.json").exists());
+
+        let mut index = CacheIndex::load(&root).unwrap();
+        assert_eq!(index.namespaces.len(), 3);
+        assert_eq!(index.remove_project(&project_a).unwrap().len(), 2);
+        assert!(!dir_a.exists());
+        assert!(dir_b.exists());
+        assert_eq!(CacheIndex::load(&root).unwrap().namespaces.len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_runs_keep_each_others_namespaces() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("cache");
+
+        let handles: Vec<_> = (0..8)
+            .map(|n| {
+                let root = root.clone();
+                let project = temp_dir.path().join(format!("project-{}", n));
+                std::thread::spawn(move || {
+                    let mut index = CacheIndex::load(&root).unwrap();
+                    index.open_namespace(&project, "rustc 1.80.0").unwrap()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.join().unwrap().exists());
+        }
+
+        assert_eq!(CacheIndex::load(&root).unwrap().namespaces.len(), 8);
+    }
+}
@@ -1,5 +1,5 @@
-use crate::cache::{CompactionResult, VerificationCache};
-use anyhow::Result;
+use crate::cache::{CacheFilter, CompactionResult, VerificationCache};
+use crate::errors::Result;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -82,6 +82,18 @@ impl CacheManager {
         })
     }
 
+    /// Clear only the entries selected by `filter`
+    pub fn clear_matching(&mut self, filter: &CacheFilter) -> Result<ClearResult> {
+        self.cache.load_from_disk()?;
+        let initial_size = self.cache.disk_size().unwrap_or(0);
+        let entries_removed = self.cache.clear_matching(filter);
+
+        Ok(ClearResult {
+            entries_removed,
+            size_freed: initial_size.saturating_sub(self.cache.disk_size().unwrap_or(0)),
+        })
+    }
+
     /// Compact cache by removing expired entries and optimizing storage
     pub fn compact(&mut self) -> Result<CompactionResult> {
         self.cache.compact()
@@ -284,7 +296,11 @@ mod tests {
                 execution_time: Duration::from_millis(100),
                 memory_usage: 512 * 1024 * 1024,
                 cache_hit_count: 0,
+                target: None,
+                seed: None,
+                environment: None,
             },
+            artifacts: Vec::new(),
         };
 
         manager.cache_mut().store(cache_key, expired_entry);
@@ -328,7 +344,11 @@ mod tests {
                 execution_time: Duration::from_millis(100),
                 memory_usage: 512 * 1024 * 1024,
                 cache_hit_count: 0,
+                target: None,
+                seed: None,
+                environment: None,
             },
+            artifacts: Vec::new(),
         };
 
         manager.cache_mut().store(cache_key, cache_entry);
@@ -0,0 +1,303 @@
+use crate::errors::{FerrisProofError, Result};
+use crate::types::{Layer, Status};
+use crate::verification::Target;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+use tracing::{debug, warn};
+
+/// Dependency graph between verification targets
+///
+/// Edges are derived from `use crate::`/`use super::` statements in Rust files
+/// and from `EXTENDS`/`INSTANCE` (TLA+) or `open` (Alloy) references in specs.
+/// Only references that resolve to another target in the same run are kept.
+#[derive(Debug, Clone)]
+pub struct TargetGraph {
+    /// `dependencies[i]` lists the targets that target `i` depends on
+    dependencies: Vec<Vec<usize>>,
+}
+
+impl TargetGraph {
+    pub fn build(targets: &[Target]) -> Self {
+        let mut index_by_name: HashMap<String, usize> = HashMap::new();
+        for (idx, target) in targets.iter().enumerate() {
+            if let Some(name) = target_name(target) {
+                index_by_name.entry(name).or_insert(idx);
+            }
+        }
+
+        let dependencies = targets
+            .iter()
+            .enumerate()
+            .map(|(idx, target)| {
+                let deps: BTreeSet<usize> = referenced_names(target)
+                    .into_iter()
+                    .filter_map(|name| index_by_name.get(&name).copied())
+                    .filter(|dep| *dep != idx)
+                    .collect();
+                deps.into_iter().collect()
+            })
+            .collect();
+
+        Self { dependencies }
+    }
+
+    /// Targets that `idx` depends on
+    pub fn dependencies(&self, idx: usize) -> &[usize] {
+        self.dependencies
+            .get(idx)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Order targets so that dependencies are verified before dependents
+    ///
+    /// Ties keep the original target order. Targets participating in a cycle
+    /// are appended in their original order.
+    pub fn topological_order(&self) -> Vec<usize> {
+        let count = self.dependencies.len();
+        let mut remaining: Vec<usize> = self.dependencies.iter().map(Vec::len).collect();
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); count];
+        for (idx, deps) in self.dependencies.iter().enumerate() {
+            for dep in deps {
+                dependents[*dep].push(idx);
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..count).filter(|i| remaining[*i] == 0).collect();
+        let mut order = Vec::with_capacity(count);
+        let mut placed = vec![false; count];
+
+        while let Some(idx) = ready.pop_front() {
+            order.push(idx);
+            placed[idx] = true;
+
+            for dependent in &dependents[idx] {
+                remaining[*dependent] -= 1;
+                if remaining[*dependent] == 0 {
+                    ready.push_back(*dependent);
+                }
+            }
+        }
+
+        if order.len() < count {
+            warn!("Dependency cycle detected between verification targets; verifying remaining targets in declaration order");
+            order.extend((0..count).filter(|i| !placed[*i]));
+        }
+
+        debug!("Verification order: {:?}", order);
+        order
+    }
+}
+
+/// Layers that only run for a target once the layers they require have passed
+///
+/// Configured as `[layers] formal.requires = ["property_based"]`.
+#[derive(Debug, Clone, Default)]
+pub struct LayerGates {
+    requires: HashMap<Layer, Vec<Layer>>,
+}
+
+impl LayerGates {
+    /// Fails if the requirements form a cycle, which would gate a layer on itself
+    pub fn new(requires: HashMap<Layer, Vec<Layer>>) -> Result<Self> {
+        let gates = Self { requires };
+        for layer in gates.requires.keys() {
+            let mut stack = vec![*layer];
+            let mut seen = HashSet::new();
+            while let Some(current) = stack.pop() {
+                for required in gates.requires(current) {
+                    if required == layer {
+                        return Err(FerrisProofError::configuration(format!(
+                            "Layer {:?} transitively requires itself",
+                            layer
+                        )));
+                    }
+                    if seen.insert(*required) {
+                        stack.push(*required);
+                    }
+                }
+            }
+        }
+        Ok(gates)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requires.values().all(Vec::is_empty)
+    }
+
+    pub fn requires(&self, layer: Layer) -> &[Layer] {
+        self.requires
+            .get(&layer)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Order `layers` so required layers run first, otherwise keeping their order
+    pub fn order(&self, layers: &[Layer]) -> Vec<Layer> {
+        let mut order = Vec::with_capacity(layers.len());
+        let mut remaining = layers.to_vec();
+        while !remaining.is_empty() {
+            // Cycles are rejected by `new`, so some layer is always ready
+            let ready = remaining
+                .iter()
+                .position(|layer| self.requires(*layer).iter().all(|r| !remaining.contains(r)))
+                .unwrap_or(0);
+            order.push(remaining.remove(ready));
+        }
+        order
+    }
+
+    /// First required layer that did not pass, given the outcomes so far
+    ///
+    /// A required layer that never ran counts as not passed.
+    pub fn blocked_by(&self, layer: Layer, outcomes: &HashMap<Layer, Status>) -> Option<Layer> {
+        self.requires(layer).iter().copied().find(|required| {
+            !matches!(
+                outcomes.get(required),
+                Some(Status::Success | Status::Warning)
+            )
+        })
+    }
+}
+
+/// Name other targets use to refer to this one
+fn target_name(target: &Target) -> Option<String> {
+    match target {
+        Target::RustFile(path) | Target::Item { file: path, .. } => rust_module_name(path),
+        Target::FormalSpec(path) => path.file_stem().map(|s| s.to_string_lossy().to_string()),
+        Target::Module(module_path) => module_path.rsplit("::").next().map(str::to_string),
+    }
+}
+
+fn rust_module_name(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_string_lossy().to_string();
+    if stem == "mod" {
+        path.parent()
+            .and_then(|p| p.file_name())
+            .map(|s| s.to_string_lossy().to_string())
+    } else {
+        Some(stem)
+    }
+}
+
+/// Names referenced by a target's source
+fn referenced_names(target: &Target) -> Vec<String> {
+    let (path, patterns): (&Path, &[&str]) = match target {
+        Target::RustFile(path) | Target::Item { file: path, .. } => (
+            path,
+            &[r"\buse\s+(?:crate|super)::([A-Za-z_][A-Za-z0-9_]*)"],
+        ),
+        Target::FormalSpec(path) if path.extension().and_then(|s| s.to_str()) == Some("als") => (
+            path,
+            &[r"(?m)^\s*open\s+(?:[A-Za-z0-9_]+/)*([A-Za-z_][A-Za-z0-9_]*)"],
+        ),
+        Target::FormalSpec(path) => (
+            path,
+            &[
+                r"(?m)^\s*EXTENDS\s+([A-Za-z0-9_, \t]+)$",
+                r"\bINSTANCE\s+([A-Za-z_][A-Za-z0-9_]*)",
+            ],
+        ),
+        Target::Module(_) => return Vec::new(),
+    };
+
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    for pattern in patterns {
+        let regex = Regex::new(pattern).expect("valid dependency pattern");
+        for captures in regex.captures_iter(&content) {
+            names.extend(
+                captures[1]
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+            );
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_spec_dependencies_ordered_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = temp_dir.path().join("A.tla");
+        let b = temp_dir.path().join("B.tla");
+        std::fs::write(&a, "---- MODULE A ----\nEXTENDS Naturals, B\n====\n").unwrap();
+        std::fs::write(&b, "---- MODULE B ----\nEXTENDS Naturals\n====\n").unwrap();
+
+        let targets = vec![Target::FormalSpec(a), Target::FormalSpec(b)];
+        let graph = TargetGraph::build(&targets);
+
+        assert_eq!(graph.dependencies(0), &[1]);
+        assert_eq!(graph.topological_order(), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_rust_use_statements() {
+        let temp_dir = TempDir::new().unwrap();
+        let api = temp_dir.path().join("api.rs");
+        let store = temp_dir.path().join("store.rs");
+        std::fs::write(&api, "use crate::store::Store;\npub fn handle() {}\n").unwrap();
+        std::fs::write(&store, "pub struct Store;\n").unwrap();
+
+        let targets = vec![Target::RustFile(api), Target::RustFile(store)];
+        let graph = TargetGraph::build(&targets);
+
+        assert_eq!(graph.topological_order(), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_cycle_keeps_all_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.rs");
+        let b = temp_dir.path().join("b.rs");
+        std::fs::write(&a, "use crate::b::B;\n").unwrap();
+        std::fs::write(&b, "use crate::a::A;\n").unwrap();
+
+        let targets = vec![Target::RustFile(a), Target::RustFile(b)];
+        let order = TargetGraph::build(&targets).topological_order();
+
+        assert_eq!(order, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_layer_gates_order_and_block() {
+        let mut requires = HashMap::new();
+        requires.insert(Layer::Formal, vec![Layer::PropertyBased]);
+        let gates = LayerGates::new(requires).unwrap();
+
+        assert_eq!(
+            gates.order(&[Layer::Formal, Layer::TypeLevel, Layer::PropertyBased]),
+            vec![Layer::TypeLevel, Layer::PropertyBased, Layer::Formal]
+        );
+
+        let mut outcomes = HashMap::new();
+        assert_eq!(
+            gates.blocked_by(Layer::Formal, &outcomes),
+            Some(Layer::PropertyBased)
+        );
+        outcomes.insert(Layer::PropertyBased, Status::Error);
+        assert_eq!(
+            gates.blocked_by(Layer::Formal, &outcomes),
+            Some(Layer::PropertyBased)
+        );
+        outcomes.insert(Layer::PropertyBased, Status::Warning);
+        assert_eq!(gates.blocked_by(Layer::Formal, &outcomes), None);
+        assert_eq!(gates.blocked_by(Layer::TypeLevel, &outcomes), None);
+
+        let mut cyclic = HashMap::new();
+        cyclic.insert(Layer::Formal, vec![Layer::PropertyBased]);
+        cyclic.insert(Layer::PropertyBased, vec![Layer::Formal]);
+        assert!(LayerGates::new(cyclic).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::verification::Target;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Which optional Cargo target directories to verify alongside `src/`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiscoveryOptions {
+    pub include_examples: bool,
+    pub include_benches: bool,
+}
+
+/// Collect verification targets below a project root
+///
+/// Rust sources come from `src/` plus, when enabled, `examples/` and
+/// `benches/`. TLA+ and Alloy specs are picked up from `specs/`.
+pub fn discover_targets(project_root: &Path, options: &DiscoveryOptions) -> Vec<Target> {
+    let mut rust_dirs = vec!["src"];
+    if options.include_examples {
+        rust_dirs.push("examples");
+    }
+    if options.include_benches {
+        rust_dirs.push("benches");
+    }
+
+    let mut targets = Vec::new();
+    for dir in rust_dirs {
+        for path in files_with_extension(&project_root.join(dir), &["rs"]) {
+            targets.push(Target::RustFile(path));
+        }
+    }
+    for path in files_with_extension(&project_root.join("specs"), &["tla", "als"]) {
+        targets.push(Target::FormalSpec(path));
+    }
+
+    debug!(
+        "Discovered {} targets in {:?} ({:?})",
+        targets.len(),
+        project_root,
+        options
+    );
+    targets
+}
+
+pub(crate) fn files_with_extension(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    fn visit(dir: &Path, extensions: &[&str], files: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                visit(&path, extensions, files);
+            } else if path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| extensions.contains(&e))
+            {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    visit(dir, extensions, &mut files);
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verification::TargetKind;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_examples_and_benches_are_opt_in() {
+        let temp_dir = TempDir::new().unwrap();
+        for file in ["src/lib.rs", "examples/demo.rs", "benches/hot_path.rs"] {
+            let path = temp_dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "fn main() {}\n").unwrap();
+        }
+
+        let default = discover_targets(temp_dir.path(), &DiscoveryOptions::default());
+        assert_eq!(default.len(), 1);
+
+        let all = discover_targets(
+            temp_dir.path(),
+            &DiscoveryOptions {
+                include_examples: true,
+                include_benches: true,
+            },
+        );
+        let kinds: Vec<TargetKind> = all.iter().map(|t| t.kind(temp_dir.path())).collect();
+        assert_eq!(
+            kinds,
+            vec![TargetKind::Library, TargetKind::Example, TargetKind::Bench]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use crate::cache::CacheKey;
+use crate::history::current_git_commit;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use tracing::debug;
+
+/// Environment variables that change how code is built or how tools behave
+const RELEVANT_ENV_VARS: &[&str] = &[
+    "RUSTFLAGS",
+    "RUSTC_WRAPPER",
+    "RUSTUP_TOOLCHAIN",
+    "CARGO_BUILD_TARGET",
+    "CARGO_TARGET_DIR",
+    "PROPTEST_CASES",
+    "PROPTEST_MAX_SHRINK_ITERS",
+    "JAVA_HOME",
+    "TLA_HOME",
+    "KANI_HOME",
+    "CI",
+];
+
+/// `FERRIS_PROOF_*` variables are recorded too, except ones that look secret
+const ENV_PREFIX: &str = "FERRIS_PROOF_";
+const SECRET_MARKERS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD"];
+
+/// Where and with what a run was verified, recorded in reports and cache entries
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentSnapshot {
+    pub os: String,
+    pub arch: String,
+    pub rustc_version: Option<String>,
+    pub rustc_commit: Option<String>,
+    /// FerrisProof and external tool versions
+    pub tool_versions: BTreeMap<String, String>,
+    pub env_vars: BTreeMap<String, String>,
+    /// Hash of the project's root configuration
+    pub config_hash: Option<String>,
+    pub git_commit: Option<String>,
+    /// Whether tracked files had uncommitted changes
+    pub git_dirty: Option<bool>,
+}
+
+impl EnvironmentSnapshot {
+    /// Capture the environment of a run over `project_root`
+    pub fn capture(project_root: &Path, config_hash: Option<String>) -> Self {
+        let mut tool_versions = BTreeMap::new();
+        match CacheKey::get_tool_versions() {
+            Ok(versions) => {
+                tool_versions.extend(versions.external_tools);
+                tool_versions.insert("FerrisProof".to_string(), versions.ferris_proof);
+            }
+            Err(e) => debug!("No tool versions for the environment snapshot: {}", e),
+        }
+
+        let env_vars = std::env::vars()
+            .filter(|(name, _)| {
+                RELEVANT_ENV_VARS.contains(&name.as_str())
+                    || (name.starts_with(ENV_PREFIX)
+                        && !SECRET_MARKERS.iter().any(|marker| name.contains(marker)))
+            })
+            .collect();
+
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            rustc_version: tool_versions.get("Rust Compiler").cloned(),
+            rustc_commit: tool_versions.get("Rust Commit").cloned(),
+            tool_versions,
+            env_vars,
+            config_hash,
+            git_commit: current_git_commit(project_root),
+            git_dirty: git_dirty(project_root),
+        }
+    }
+
+    /// Label and value of each captured setting, in display order
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries = vec![("OS".to_string(), format!("{} ({})", self.os, self.arch))];
+        let optional = [
+            ("rustc", &self.rustc_version),
+            ("rustc commit", &self.rustc_commit),
+            ("Config hash", &self.config_hash),
+        ];
+        entries.extend(
+            optional
+                .into_iter()
+                .filter_map(|(label, value)| Some((label.to_string(), value.clone()?))),
+        );
+        if let Some(commit) = &self.git_commit {
+            let dirty = if self.git_dirty == Some(true) {
+                " (dirty)"
+            } else {
+                ""
+            };
+            entries.push(("Git commit".to_string(), format!("{}{}", commit, dirty)));
+        }
+        entries.extend(
+            self.tool_versions
+                .iter()
+                .filter(|(tool, _)| !tool.starts_with("Rust "))
+                .map(|(tool, version)| (tool.clone(), version.clone())),
+        );
+        entries.extend(
+            self.env_vars
+                .iter()
+                .map(|(name, value)| (format!("${}", name), value.clone())),
+        );
+        entries
+    }
+}
+
+/// Whether tracked files in the repository have uncommitted changes
+fn git_dirty(project_root: &Path) -> Option<bool> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .current_dir(project_root)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    Some(!output.stdout.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_skips_secrets_and_entries_flag_dirty_trees() {
+        std::env::set_var("FERRIS_PROOF_SIGNING_KEY_TEST", "secret");
+        std::env::set_var("FERRIS_PROOF_ENV_TEST", "visible");
+        let snapshot = EnvironmentSnapshot::capture(Path::new("."), Some("cfg".to_string()));
+        assert!(!snapshot
+            .env_vars
+            .contains_key("FERRIS_PROOF_SIGNING_KEY_TEST"));
+        assert_eq!(
+            snapshot
+                .env_vars
+                .get("FERRIS_PROOF_ENV_TEST")
+                .map(String::as_str),
+            Some("visible")
+        );
+        assert_eq!(snapshot.os, std::env::consts::OS);
+
+        let snapshot = EnvironmentSnapshot {
+            git_commit: Some("abc123".to_string()),
+            git_dirty: Some(true),
+            ..snapshot
+        };
+        assert!(snapshot
+            .entries()
+            .contains(&("Git commit".to_string(), "abc123 (dirty)".to_string())));
+    }
+}
@@ -0,0 +1,131 @@
+//! Error codes contributed by plugins to the `explain` catalog
+//!
+//! Plugins declare codes in their metadata, or under `error_codes` in their
+//! manifest, inside their own namespace `FP-PL-<plugin>-xxx`, so they cannot
+//! shadow built-in codes or each other.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Guidance shown by `ferris-proof explain <code>`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorExplanation {
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub causes: Vec<String>,
+    #[serde(default)]
+    pub solutions: Vec<String>,
+    #[serde(default)]
+    pub examples: Vec<String>,
+    #[serde(default)]
+    pub related_codes: Vec<String>,
+}
+
+/// Error code declared by a plugin
+///
+/// ```json
+/// "error_codes": [
+///     { "code": "FP-PL-kani-001", "title": "Unwinding assertion", "description": "..." }
+/// ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginErrorCode {
+    pub code: String,
+    #[serde(flatten)]
+    pub explanation: ErrorExplanation,
+}
+
+/// Prefix of every code `plugin` may register, e.g. `FP-PL-kani-`
+pub fn plugin_namespace(plugin: &str) -> String {
+    format!("FP-PL-{}-", plugin)
+}
+
+/// Whether `code` is `FP-PL-<plugin>-` followed by three digits
+pub fn in_namespace(plugin: &str, code: &str) -> bool {
+    code.strip_prefix(&plugin_namespace(plugin))
+        .is_some_and(|number| number.len() == 3 && number.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Add `plugin`'s codes to `catalog`, returning a problem for each code left out
+///
+/// Codes outside the plugin's namespace and codes already in the catalog,
+/// whether built in or registered by another plugin, are rejected; the entry
+/// registered first is kept.
+pub fn register_plugin_codes(
+    catalog: &mut HashMap<String, ErrorExplanation>,
+    plugin: &str,
+    codes: &[PluginErrorCode],
+) -> Vec<String> {
+    let mut issues = Vec::new();
+    for entry in codes {
+        if !in_namespace(plugin, &entry.code) {
+            issues.push(format!(
+                "{} from plugin {} is outside its namespace {}xxx",
+                entry.code,
+                plugin,
+                plugin_namespace(plugin)
+            ));
+        } else if catalog.contains_key(&entry.code) {
+            issues.push(format!(
+                "{} from plugin {} is already registered",
+                entry.code, plugin
+            ));
+        } else {
+            catalog.insert(entry.code.clone(), entry.explanation.clone());
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(code: &str, title: &str) -> PluginErrorCode {
+        PluginErrorCode {
+            code: code.to_string(),
+            explanation: ErrorExplanation {
+                title: title.to_string(),
+                description: String::new(),
+                causes: Vec::new(),
+                solutions: Vec::new(),
+                examples: Vec::new(),
+                related_codes: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_plugin_codes_are_namespaced() {
+        assert!(in_namespace("kani", "FP-PL-kani-001"));
+        assert!(!in_namespace("kani", "FP-PL-001"));
+        assert!(!in_namespace("kani", "FP-PL-kani-1"));
+        // `kani` cannot take codes of a plugin named `kani-ext`
+        assert!(!in_namespace("kani", "FP-PL-kani-ext-001"));
+    }
+
+    #[test]
+    fn test_conflicting_codes_are_rejected() {
+        let mut catalog = HashMap::new();
+        let issues = register_plugin_codes(
+            &mut catalog,
+            "kani",
+            &[
+                code("FP-PL-kani-001", "Unwinding assertion"),
+                code("FP-VR-001", "Shadowed"),
+            ],
+        );
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].contains("outside its namespace FP-PL-kani-xxx"));
+
+        let issues =
+            register_plugin_codes(&mut catalog, "kani", &[code("FP-PL-kani-001", "Again")]);
+        assert_eq!(
+            issues,
+            ["FP-PL-kani-001 from plugin kani is already registered"]
+        );
+        assert_eq!(catalog["FP-PL-kani-001"].title, "Unwinding assertion");
+        assert!(!catalog.contains_key("FP-VR-001"));
+    }
+}
@@ -1,7 +1,11 @@
 use crate::types::{Location, Violation};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+/// Result of the cache, configuration and plugin manager APIs
+pub type Result<T, E = FerrisProofError> = std::result::Result<T, E>;
+
+/// Error with a code from the `ferris-proof explain` catalog
 #[derive(Debug, Error)]
 pub enum FerrisProofError {
     #[error("Configuration error: {message}")]
@@ -11,6 +15,15 @@ pub enum FerrisProofError {
         suggestions: Vec<String>,
     },
 
+    #[error("Unknown configuration section: '{section}'")]
+    UnknownSection { section: String },
+
+    #[error("Tool not found: {tool} - {message}")]
+    ToolNotFound { tool: String, message: String },
+
+    #[error("Incompatible tool version: {tool} - {message}")]
+    ToolVersion { tool: String, message: String },
+
     #[error("Tool error: {tool} - {message}")]
     Tool {
         tool: String,
@@ -36,30 +49,227 @@ pub enum FerrisProofError {
     #[error("Parse error: {message}")]
     Parse {
         message: String,
-        location: Location,
+        location: Option<Location>,
         expected: Option<String>,
     },
+
+    #[error("Cache error: {message}")]
+    Cache {
+        message: String,
+        path: Option<PathBuf>,
+    },
+
+    #[error("Cache at {path} is locked by another ferris-proof run")]
+    CacheLocked { path: PathBuf },
+
+    #[error("Plugin error: {plugin} - {message}")]
+    Plugin { plugin: String, message: String },
+
+    #[error("No plugin available for {technique}")]
+    NoPlugin { technique: String },
+
+    #[error("Cancelled: {tool}")]
+    Cancelled { tool: String },
 }
 
 impl FerrisProofError {
-    pub fn code(&self) -> &str {
+    /// Every code returned by [`FerrisProofError::code`]
+    pub const CODES: &'static [&'static str] = &[
+        "FP-CF-001",
+        "FP-CF-004",
+        "FP-TL-001",
+        "FP-TL-002",
+        "FP-TL-003",
+        "FP-VR-001",
+        "FP-IO-001",
+        "FP-PS-001",
+        "FP-CH-001",
+        "FP-CH-002",
+        "FP-PL-001",
+        "FP-PL-002",
+        "FP-PL-004",
+    ];
+
+    pub fn code(&self) -> &'static str {
         match self {
             Self::Configuration { .. } => "FP-CF-001",
-            Self::Tool { .. } => "FP-TL-001",
+            Self::UnknownSection { .. } => "FP-CF-004",
+            Self::ToolNotFound { .. } => "FP-TL-001",
+            Self::ToolVersion { .. } => "FP-TL-002",
+            Self::Tool { .. } => "FP-TL-003",
             Self::Verification { .. } => "FP-VR-001",
             Self::Io { .. } => "FP-IO-001",
             Self::Parse { .. } => "FP-PS-001",
+            Self::Cache { .. } => "FP-CH-001",
+            Self::CacheLocked { .. } => "FP-CH-002",
+            Self::Plugin { .. } => "FP-PL-001",
+            Self::NoPlugin { .. } => "FP-PL-002",
+            Self::Cancelled { .. } => "FP-PL-004",
         }
     }
 
     pub fn explanation(&self) -> String {
         match self.code() {
             "FP-CF-001" => "Invalid configuration detected. Check your ferrisproof.toml file for syntax errors or invalid values.".to_string(),
+            "FP-CF-004" => "The configuration contains a section FerrisProof does not know. Check the section name for typos.".to_string(),
             "FP-TL-001" => "External verification tool error. Ensure all required tools are installed and accessible.".to_string(),
+            "FP-TL-002" => "An external verification tool has an unsupported version. Install a version in the supported range.".to_string(),
+            "FP-TL-003" => "An external verification tool failed while running. Check its output for details.".to_string(),
             "FP-VR-001" => "Verification violations found. Review the reported issues and fix them before proceeding.".to_string(),
             "FP-IO-001" => "File system operation failed. Check file permissions and disk space.".to_string(),
             "FP-PS-001" => "Parse error in input file. Check syntax and format.".to_string(),
+            "FP-CH-001" => "The verification cache could not be read or written. Run 'ferris-proof cache repair' or clear the cache.".to_string(),
+            "FP-CH-002" => "Another ferris-proof run is clearing or compacting the cache. Retry once it finishes.".to_string(),
+            "FP-PL-001" => "A verification plugin could not be loaded or run. Check the plugin manifest and its tool.".to_string(),
+            "FP-PL-002" => "No enabled plugin supports the requested technique. Install one or enable a built-in plugin.".to_string(),
+            "FP-PL-004" => "A tool or plugin was stopped by a timeout or Ctrl-C before it finished. Its results are incomplete.".to_string(),
             _ => format!("No detailed explanation available for error code {}", self.code()),
         }
     }
+
+    /// Command printing the catalog entry for this error
+    pub fn explain_command(&self) -> String {
+        format!("ferris-proof explain {}", self.code())
+    }
+
+    pub fn configuration(message: impl Into<String>) -> Self {
+        Self::Configuration {
+            message: message.into(),
+            location: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Configuration error in a specific file
+    pub fn configuration_in(message: impl Into<String>, file: &Path) -> Self {
+        Self::Configuration {
+            message: message.into(),
+            location: Some(Location::file(file)),
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        let path = path.into();
+        Self::Io {
+            message: format!("{}: {}", path.display(), source),
+            path: Some(path),
+            source,
+        }
+    }
+
+    pub fn parse(message: impl Into<String>, file: Option<&Path>) -> Self {
+        Self::Parse {
+            message: message.into(),
+            location: file.map(Location::file),
+            expected: None,
+        }
+    }
+
+    pub fn cache(message: impl Into<String>, path: Option<&Path>) -> Self {
+        Self::Cache {
+            message: message.into(),
+            path: path.map(Path::to_path_buf),
+        }
+    }
+
+    pub fn plugin(plugin: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Plugin {
+            plugin: plugin.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn tool_not_found(tool: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::ToolNotFound {
+            tool: tool.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Error raised inside `plugin`, keeping a [`FerrisProofError`] in its
+    /// chain, such as a cancelled tool, as it is
+    pub fn in_plugin(plugin: impl Into<String>, error: anyhow::Error) -> Self {
+        match error.downcast::<Self>() {
+            Ok(error) => error,
+            Err(error) => Self::plugin(plugin, format!("{:#}", error)),
+        }
+    }
+
+    pub fn tool(tool: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Tool {
+            tool: tool.into(),
+            message: message.into(),
+            exit_code: None,
+            stderr: None,
+        }
+    }
+}
+
+impl From<std::io::Error> for FerrisProofError {
+    fn from(source: std::io::Error) -> Self {
+        Self::Io {
+            message: source.to_string(),
+            path: None,
+            source,
+        }
+    }
+}
+
+impl From<serde_json::Error> for FerrisProofError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::parse(error.to_string(), None)
+    }
+}
+
+impl From<bincode::Error> for FerrisProofError {
+    fn from(error: bincode::Error) -> Self {
+        Self::cache(error.to_string(), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_link_to_explain_catalog() {
+        let error =
+            FerrisProofError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "missing"));
+        assert_eq!(error.code(), "FP-IO-001");
+        assert_eq!(error.explain_command(), "ferris-proof explain FP-IO-001");
+
+        let error = FerrisProofError::configuration_in("bad level", Path::new("ferrisproof.toml"));
+        assert!(matches!(
+            error,
+            FerrisProofError::Configuration {
+                location: Some(_),
+                ..
+            }
+        ));
+        assert_eq!(error.code(), "FP-CF-001");
+        assert_eq!(
+            FerrisProofError::plugin("tla-plus", "bad manifest").code(),
+            "FP-PL-001"
+        );
+    }
+
+    #[test]
+    fn test_plugin_errors_keep_typed_causes() {
+        let cancelled = anyhow::Error::from(FerrisProofError::Cancelled {
+            tool: "tlc".to_string(),
+        })
+        .context("running TLC");
+        assert_eq!(
+            FerrisProofError::in_plugin("tla-plus", cancelled).code(),
+            "FP-PL-004"
+        );
+
+        let error = FerrisProofError::in_plugin("tla-plus", anyhow::anyhow!("bad spec"));
+        assert!(matches!(
+            error,
+            FerrisProofError::Plugin { ref plugin, ref message }
+                if plugin == "tla-plus" && message == "bad spec"
+        ));
+    }
 }
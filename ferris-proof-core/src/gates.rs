@@ -0,0 +1,194 @@
+use crate::cache::{module_path_of, wildcard_match};
+use crate::history::RunRecord;
+use crate::types::*;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Release gate declared under `[gates]` and checked by `ferris-proof gate`
+#[derive(Debug, Clone)]
+pub struct ReleaseGate {
+    pub name: String,
+    /// Module path pattern where `*` matches any sequence, e.g. `payment::*`
+    pub modules: String,
+    pub rules: Vec<GateRule>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GateRule {
+    /// The latest run has no violations of these severities in matching modules
+    Deny(Vec<Severity>),
+    /// The layer ran for a matching module, within `within` of now if set
+    RequireLayer {
+        layer: Layer,
+        within: Option<Duration>,
+    },
+}
+
+/// Result of one gate; it passes when there are no failures
+#[derive(Debug, Clone)]
+pub struct GateOutcome {
+    pub gate: String,
+    pub failures: Vec<String>,
+}
+
+impl GateOutcome {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl ReleaseGate {
+    /// Evaluate against run history, oldest run first
+    pub fn evaluate(&self, history: &[RunRecord], now: DateTime<Utc>) -> GateOutcome {
+        let failures = self
+            .rules
+            .iter()
+            .filter_map(|rule| match rule {
+                GateRule::Deny(severities) => self.check_denied(history.last(), severities),
+                GateRule::RequireLayer { layer, within } => {
+                    self.check_freshness(history, *layer, *within, now)
+                }
+            })
+            .collect();
+
+        GateOutcome {
+            gate: self.name.clone(),
+            failures,
+        }
+    }
+
+    fn check_denied(&self, latest: Option<&RunRecord>, severities: &[Severity]) -> Option<String> {
+        let Some(latest) = latest else {
+            return Some("no recorded runs; run `ferris-proof check` first".to_string());
+        };
+
+        let denied: Vec<_> = latest
+            .violations
+            .iter()
+            .filter(|v| severities.contains(&v.severity))
+            .filter(|v| wildcard_match(&self.modules, &module_path_of(&v.file.to_string_lossy())))
+            .collect();
+        let first = denied.first()?;
+        Some(format!(
+            "{} {:?} violation(s) in {}, e.g. {}: {}",
+            denied.len(),
+            severities,
+            self.modules,
+            first.file.display(),
+            first.message
+        ))
+    }
+
+    fn check_freshness(
+        &self,
+        history: &[RunRecord],
+        layer: Layer,
+        within: Option<Duration>,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let ran = history
+            .iter()
+            .filter(|record| {
+                within.is_none_or(|within| {
+                    // Clock skew can put a record in the future; it still counts
+                    (now - record.timestamp)
+                        .to_std()
+                        .ok()
+                        .is_none_or(|age| age <= within)
+                })
+            })
+            .flat_map(|record| &record.targets)
+            .any(|target| {
+                target.layer == layer
+                    && target.status != Status::Skipped
+                    && wildcard_match(&self.modules, &target.module)
+            });
+        if ran {
+            return None;
+        }
+
+        Some(match within {
+            Some(within) => format!(
+                "{:?} has not run for {} within the last {} day(s)",
+                layer,
+                self.modules,
+                within.as_secs() / SECONDS_PER_DAY
+            ),
+            None => format!("{:?} has never run for {}", layer, self.modules),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{TargetRecord, ViolationRecord};
+    use std::path::PathBuf;
+
+    fn run(
+        days_ago: i64,
+        targets: Vec<TargetRecord>,
+        violations: Vec<ViolationRecord>,
+    ) -> RunRecord {
+        RunRecord {
+            id: String::new(),
+            timestamp: Utc::now() - chrono::Duration::days(days_ago),
+            git_commit: None,
+            level: None,
+            overall_status: Status::Success,
+            violations,
+            total_time: Duration::ZERO,
+            cache_hit_rate: 0.0,
+            layers: Vec::new(),
+            targets,
+            api_surface: None,
+            suppressions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_gates_check_latest_violations_and_layer_freshness() {
+        let formal_crypto = TargetRecord {
+            module: "crypto::aes".to_string(),
+            layer: Layer::Formal,
+            status: Status::Success,
+        };
+        let payment_error = ViolationRecord {
+            fingerprint: String::new(),
+            rule: "property".to_string(),
+            severity: Severity::Error,
+            file: PathBuf::from("src/payment/refund.rs"),
+            message: "refund exceeds charge".to_string(),
+        };
+        let history = vec![
+            run(10, vec![formal_crypto.clone()], vec![payment_error.clone()]),
+            run(1, Vec::new(), Vec::new()),
+        ];
+
+        let payments = ReleaseGate {
+            name: "payments".to_string(),
+            modules: "payment::*".to_string(),
+            rules: vec![GateRule::Deny(vec![Severity::Error, Severity::Critical])],
+        };
+        // Only the latest run counts for violations
+        assert!(payments.evaluate(&history, Utc::now()).passed());
+        let mut failing = history.clone();
+        failing.push(run(0, Vec::new(), vec![payment_error]));
+        assert!(!payments.evaluate(&failing, Utc::now()).passed());
+
+        let crypto = |days: u64| ReleaseGate {
+            name: "crypto".to_string(),
+            modules: "crypto::*".to_string(),
+            rules: vec![GateRule::RequireLayer {
+                layer: Layer::Formal,
+                within: Some(Duration::from_secs(days * SECONDS_PER_DAY)),
+            }],
+        };
+        assert!(crypto(14).evaluate(&history, Utc::now()).passed());
+        let stale = crypto(7).evaluate(&history, Utc::now());
+        assert_eq!(stale.failures.len(), 1);
+        assert!(stale.failures[0].contains("7 day"));
+    }
+}
@@ -0,0 +1,770 @@
+use crate::api_surface::ApiSurface;
+use crate::cache::module_path_of;
+use crate::types::*;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// A single recorded verification run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    pub git_commit: Option<String>,
+    pub level: Option<VerificationLevel>,
+    pub overall_status: Status,
+    pub violations: Vec<ViolationRecord>,
+    #[serde(default)]
+    pub total_time: Duration,
+    #[serde(default)]
+    pub cache_hit_rate: f64,
+    /// Per-layer timing and violation counts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<LayerRecord>,
+    /// Layers run per target module, used by `[gates]` freshness rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<TargetRecord>,
+    /// Public API snapshot, when the API-surface layer ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_surface: Option<ApiSurface>,
+    /// Fingerprints the baseline suppressed at the time of the run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<String>,
+}
+
+/// Summary of one layer within a recorded run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerRecord {
+    pub layer: Layer,
+    pub duration: Duration,
+    pub violations: usize,
+    /// Tool reruns after spurious failures
+    #[serde(default)]
+    pub retries: u32,
+    /// Passed only after a rerun
+    #[serde(default)]
+    pub flaky_pass: bool,
+}
+
+/// Outcome of one layer for one target within a recorded run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetRecord {
+    pub module: String,
+    pub layer: Layer,
+    pub status: Status,
+}
+
+/// Condensed violation entry stored in run history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViolationRecord {
+    /// Stable identity used to match a violation across runs
+    pub fingerprint: String,
+    pub rule: String,
+    pub severity: Severity,
+    pub file: PathBuf,
+    pub message: String,
+}
+
+impl RunRecord {
+    /// Build a history record from a verification result
+    pub fn from_result(
+        result: &VerificationResult,
+        level: Option<VerificationLevel>,
+        git_commit: Option<String>,
+    ) -> Self {
+        let violations = result
+            .layer_results
+            .values()
+            .flat_map(|layer| layer.violations.iter())
+            .map(ViolationRecord::from_violation)
+            .collect();
+
+        // Skipped layers took no time and would skew duration trends
+        let mut layers: Vec<LayerRecord> = result
+            .layer_results
+            .values()
+            .filter(|layer| layer.status.executed())
+            .map(|layer| LayerRecord {
+                layer: layer.layer,
+                duration: layer.execution_time,
+                violations: layer.violations.len(),
+                retries: layer.retries(),
+                flaky_pass: layer.is_flaky_pass(),
+            })
+            .collect();
+        layers.sort_by_key(|l| format!("{:?}", l.layer));
+
+        let targets = result
+            .completed
+            .iter()
+            .map(|(target, layer)| TargetRecord {
+                module: module_path_of(&target.label()),
+                layer: layer.layer,
+                status: layer.status,
+            })
+            .collect();
+
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: result.timestamp,
+            git_commit,
+            level,
+            overall_status: result.overall_status,
+            violations,
+            total_time: result.metrics.total_time,
+            cache_hit_rate: result.metrics.cache_hit_rate,
+            layers,
+            targets,
+            api_surface: result.api_surface.clone(),
+            suppressions: Vec::new(),
+        }
+    }
+
+    pub fn layer_duration(&self, layer: Layer) -> Option<Duration> {
+        self.layers
+            .iter()
+            .find(|l| l.layer == layer)
+            .map(|l| l.duration)
+    }
+}
+
+impl ViolationRecord {
+    pub fn from_violation(violation: &Violation) -> Self {
+        let fingerprint = format!(
+            "{}|{}|{}",
+            violation.rule,
+            violation.location.file.to_string_lossy().replace('\\', "/"),
+            violation.message
+        );
+
+        Self {
+            fingerprint,
+            rule: violation.rule.clone(),
+            severity: violation.severity.clone(),
+            file: violation.location.file.clone(),
+            message: violation.message.clone(),
+        }
+    }
+}
+
+/// Append-only run history stored as JSON lines under `.ferris-proof/`
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    /// History store for the given project root
+    pub fn for_project(project_root: &Path) -> Self {
+        Self {
+            path: project_root.join(".ferris-proof").join("history.jsonl"),
+        }
+    }
+
+    /// History store backed by an explicit file
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a run record
+    pub fn record(&self, record: &RunRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| anyhow!("Failed to open history file {:?}: {}", self.path, e))?;
+
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        debug!("Recorded verification run {} in {:?}", record.id, self.path);
+        Ok(())
+    }
+
+    /// Load all run records in chronological order
+    pub fn load(&self) -> Result<Vec<RunRecord>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&self.path)?;
+        let mut records: Vec<RunRecord> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    warn!("Skipping malformed history entry: {}", e);
+                    None
+                }
+            })
+            .collect();
+
+        records.sort_by_key(|r| r.timestamp);
+        Ok(records)
+    }
+
+    /// The `n` most recent records in chronological order
+    pub fn last(&self, n: usize) -> Result<Vec<RunRecord>> {
+        let mut records = self.load()?;
+        let skip = records.len().saturating_sub(n);
+        records.drain(..skip);
+        Ok(records)
+    }
+
+    /// Load records within `[since, until]`
+    pub fn between(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> Result<Vec<RunRecord>> {
+        Ok(self
+            .load()?
+            .into_iter()
+            .filter(|r| r.timestamp >= since && r.timestamp <= until)
+            .collect())
+    }
+}
+
+/// Aggregated view of run history across a release window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseSummary {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub total_runs: usize,
+    pub failed_runs: usize,
+    pub violations_fixed: Vec<ViolationRecord>,
+    pub violations_introduced: Vec<ViolationRecord>,
+    pub outstanding_violations: Vec<ViolationRecord>,
+    pub level_changes: Vec<LevelChange>,
+    /// Baseline entries and snoozes present at the end but not the start
+    #[serde(default)]
+    pub suppressions_added: usize,
+    /// Baseline entries and snoozes present at the start but not the end
+    #[serde(default)]
+    pub suppressions_removed: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelChange {
+    pub timestamp: DateTime<Utc>,
+    pub from: VerificationLevel,
+    pub to: VerificationLevel,
+}
+
+impl ReleaseSummary {
+    /// Aggregate chronologically ordered records from a release window
+    pub fn from_records(records: &[RunRecord], since: DateTime<Utc>, until: DateTime<Utc>) -> Self {
+        let first = fingerprints(records.first());
+        let last = fingerprints(records.last());
+
+        let violations_fixed = first
+            .iter()
+            .filter(|(key, _)| !last.contains_key(*key))
+            .map(|(_, v)| v.clone())
+            .collect();
+        let violations_introduced = last
+            .iter()
+            .filter(|(key, _)| !first.contains_key(*key))
+            .map(|(_, v)| v.clone())
+            .collect();
+
+        let first_suppressions = suppressions(records.first());
+        let last_suppressions = suppressions(records.last());
+
+        let mut level_changes = Vec::new();
+        let mut previous_level: Option<VerificationLevel> = None;
+        for record in records {
+            if let (Some(from), Some(to)) = (previous_level, record.level) {
+                if from != to {
+                    level_changes.push(LevelChange {
+                        timestamp: record.timestamp,
+                        from,
+                        to,
+                    });
+                }
+            }
+            previous_level = record.level.or(previous_level);
+        }
+
+        Self {
+            since,
+            until,
+            total_runs: records.len(),
+            failed_runs: records
+                .iter()
+                .filter(|r| r.overall_status == Status::Error)
+                .count(),
+            violations_fixed,
+            violations_introduced,
+            outstanding_violations: last.into_values().collect(),
+            level_changes,
+            suppressions_added: last_suppressions.difference(&first_suppressions).count(),
+            suppressions_removed: first_suppressions.difference(&last_suppressions).count(),
+        }
+    }
+
+    /// Levels were only ever raised (never lowered) within the window
+    pub fn levels_only_raised(&self) -> bool {
+        self.level_changes.iter().all(|c| c.to > c.from)
+    }
+
+    /// Render a release-readiness document
+    pub fn to_markdown(&self, since_label: &str, until_label: &str) -> String {
+        let mut out = String::new();
+
+        out.push_str(&format!(
+            "# Release Readiness: {} → {}\n\n",
+            since_label, until_label
+        ));
+        out.push_str(&format!(
+            "_Window: {} – {}_\n\n",
+            self.since.to_rfc3339(),
+            self.until.to_rfc3339()
+        ));
+
+        out.push_str("## Summary\n\n");
+        out.push_str(&format!("- Verification runs: {}\n", self.total_runs));
+        out.push_str(&format!("- Failed runs: {}\n", self.failed_runs));
+        out.push_str(&format!(
+            "- Violations fixed: {}\n",
+            self.violations_fixed.len()
+        ));
+        out.push_str(&format!(
+            "- Violations introduced: {}\n",
+            self.violations_introduced.len()
+        ));
+        out.push_str(&format!(
+            "- Outstanding violations: {}\n",
+            self.outstanding_violations.len()
+        ));
+        out.push_str(&format!("- Level changes: {}\n", self.level_changes.len()));
+        out.push_str(&format!(
+            "- Suppressions added: {}\n",
+            self.suppressions_added
+        ));
+        out.push_str(&format!(
+            "- Suppressions removed: {}\n",
+            self.suppressions_removed
+        ));
+
+        if !self.level_changes.is_empty() {
+            out.push_str("\n## Verification Level Changes\n\n");
+            for change in &self.level_changes {
+                out.push_str(&format!(
+                    "- {}: {:?} → {:?}\n",
+                    change.timestamp.format("%Y-%m-%d"),
+                    change.from,
+                    change.to
+                ));
+            }
+        }
+
+        for (title, violations) in [
+            ("Violations Fixed", &self.violations_fixed),
+            ("Violations Introduced", &self.violations_introduced),
+            ("Outstanding Violations", &self.outstanding_violations),
+        ] {
+            if violations.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {}\n\n", title));
+            for violation in violations {
+                out.push_str(&format!(
+                    "- **{:?}** `{}` in `{}`: {}\n",
+                    violation.severity,
+                    violation.rule,
+                    violation.file.display(),
+                    violation.message
+                ));
+            }
+        }
+
+        out.push_str("\n## Sign-off\n\n");
+        let ready = self.outstanding_violations.is_empty() && self.levels_only_raised();
+        out.push_str(&format!(
+            "Release readiness: **{}**\n",
+            if ready { "READY" } else { "NEEDS REVIEW" }
+        ));
+
+        out
+    }
+}
+
+/// Latest run's timing compared with the median of the runs before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimingTrend {
+    pub latest: Duration,
+    pub median: Duration,
+    /// Relative change against the median, e.g. `0.5` for 50% slower
+    pub change: f64,
+    pub regression: bool,
+}
+
+impl TimingTrend {
+    /// `None` without earlier timings to compare against
+    fn new(latest: Duration, earlier: Vec<Duration>, threshold: f64) -> Option<Self> {
+        let median = median(earlier)?;
+        if median.is_zero() {
+            return None;
+        }
+        let change = latest.as_secs_f64() / median.as_secs_f64() - 1.0;
+        Some(Self {
+            latest,
+            median,
+            change,
+            regression: change > threshold,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerTrend {
+    pub layer: Layer,
+    pub timing: TimingTrend,
+}
+
+/// Verification-time trends over a window of recent runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryTrend {
+    pub runs: usize,
+    pub total: Option<TimingTrend>,
+    pub layers: Vec<LayerTrend>,
+    /// Violation count of the latest run minus that of the previous run
+    pub violation_delta: i64,
+}
+
+impl HistoryTrend {
+    /// Compare the last of chronologically ordered `records` with the rest
+    ///
+    /// A run is a regression when it is more than `threshold` (a fraction)
+    /// slower than the median of the earlier runs.
+    pub fn from_records(records: &[RunRecord], threshold: f64) -> Option<Self> {
+        let (latest, earlier) = records.split_last()?;
+
+        let total = TimingTrend::new(
+            latest.total_time,
+            earlier.iter().map(|r| r.total_time).collect(),
+            threshold,
+        );
+        let layers = latest
+            .layers
+            .iter()
+            .filter_map(|layer| {
+                let earlier = earlier
+                    .iter()
+                    .filter_map(|r| r.layer_duration(layer.layer))
+                    .collect();
+                Some(LayerTrend {
+                    layer: layer.layer,
+                    timing: TimingTrend::new(layer.duration, earlier, threshold)?,
+                })
+            })
+            .collect();
+        let violation_delta = earlier.last().map_or(0, |previous| {
+            latest.violations.len() as i64 - previous.violations.len() as i64
+        });
+
+        Some(Self {
+            runs: records.len(),
+            total,
+            layers,
+            violation_delta,
+        })
+    }
+
+    pub fn has_regression(&self) -> bool {
+        self.total.as_ref().is_some_and(|t| t.regression)
+            || self.layers.iter().any(|l| l.timing.regression)
+    }
+}
+
+fn median(mut durations: Vec<Duration>) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+    durations.sort();
+    let mid = durations.len() / 2;
+    Some(if durations.len() % 2 == 0 {
+        (durations[mid - 1] + durations[mid]) / 2
+    } else {
+        durations[mid]
+    })
+}
+
+/// Violation changes between two runs, matched by fingerprint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ViolationDiff {
+    pub added: Vec<ViolationRecord>,
+    pub fixed: Vec<ViolationRecord>,
+    pub unchanged: Vec<ViolationRecord>,
+}
+
+impl ViolationDiff {
+    pub fn between(old: &RunRecord, new: &RunRecord) -> Self {
+        let old = fingerprints(Some(old));
+        let new = fingerprints(Some(new));
+
+        Self {
+            added: new
+                .iter()
+                .filter(|(key, _)| !old.contains_key(*key))
+                .map(|(_, v)| v.clone())
+                .collect(),
+            fixed: old
+                .iter()
+                .filter(|(key, _)| !new.contains_key(*key))
+                .map(|(_, v)| v.clone())
+                .collect(),
+            unchanged: new
+                .iter()
+                .filter(|(key, _)| old.contains_key(*key))
+                .map(|(_, v)| v.clone())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.fixed.is_empty()
+    }
+}
+
+impl HistoryStore {
+    /// Most recent run recorded for a commit (full or abbreviated hash)
+    pub fn latest_for_commit(&self, commit: &str) -> Result<Option<RunRecord>> {
+        Ok(self.load()?.into_iter().rev().find(|r| {
+            r.git_commit
+                .as_deref()
+                .is_some_and(|c| c.starts_with(commit) || commit.starts_with(c))
+        }))
+    }
+
+    /// Public API recorded by the most recent run of the API-surface layer
+    pub fn latest_api_surface(&self) -> Result<Option<ApiSurface>> {
+        Ok(self.load()?.into_iter().rev().find_map(|r| r.api_surface))
+    }
+}
+
+/// Current `HEAD` commit of the repository containing `project_root`, if any
+pub fn current_git_commit(project_root: &Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(project_root)
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        None
+    }
+}
+
+fn fingerprints(record: Option<&RunRecord>) -> BTreeMap<String, ViolationRecord> {
+    record
+        .map(|r| {
+            r.violations
+                .iter()
+                .map(|v| (v.fingerprint.clone(), v.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn suppressions(record: Option<&RunRecord>) -> BTreeSet<&str> {
+    record
+        .into_iter()
+        .flat_map(|r| r.suppressions.iter().map(String::as_str))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn violation(rule: &str) -> ViolationRecord {
+        ViolationRecord {
+            fingerprint: format!("{}|src/lib.rs|failed", rule),
+            rule: rule.to_string(),
+            severity: Severity::Error,
+            file: PathBuf::from("src/lib.rs"),
+            message: "failed".to_string(),
+        }
+    }
+
+    fn record(
+        offset_hours: i64,
+        level: VerificationLevel,
+        violations: Vec<ViolationRecord>,
+    ) -> RunRecord {
+        RunRecord {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: Utc::now() - chrono::Duration::hours(offset_hours),
+            git_commit: None,
+            level: Some(level),
+            overall_status: if violations.is_empty() {
+                Status::Success
+            } else {
+                Status::Error
+            },
+            violations,
+            total_time: Duration::ZERO,
+            cache_hit_rate: 0.0,
+            layers: Vec::new(),
+            targets: Vec::new(),
+            api_surface: None,
+            suppressions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_store_roundtrip_and_window() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = HistoryStore::for_project(temp_dir.path());
+
+        store
+            .record(&record(48, VerificationLevel::Standard, vec![]))
+            .unwrap();
+        store
+            .record(&record(1, VerificationLevel::Strict, vec![]))
+            .unwrap();
+
+        assert_eq!(store.load().unwrap().len(), 2);
+
+        let recent = store
+            .between(Utc::now() - chrono::Duration::hours(24), Utc::now())
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].level, Some(VerificationLevel::Strict));
+    }
+
+    #[test]
+    fn test_diff_between_runs() {
+        let old = record(
+            2,
+            VerificationLevel::Standard,
+            vec![violation("a"), violation("b")],
+        );
+        let new = record(
+            1,
+            VerificationLevel::Standard,
+            vec![violation("b"), violation("c")],
+        );
+
+        let diff = ViolationDiff::between(&old, &new);
+
+        assert_eq!(diff.added[0].rule, "c");
+        assert_eq!(diff.fixed[0].rule, "a");
+        assert_eq!(diff.unchanged[0].rule, "b");
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_summary_tracks_fixed_and_introduced() {
+        let records = vec![
+            record(3, VerificationLevel::Standard, vec![violation("a")]),
+            record(2, VerificationLevel::Strict, vec![violation("a")]),
+            record(1, VerificationLevel::Strict, vec![violation("b")]),
+        ];
+
+        let summary = ReleaseSummary::from_records(
+            &records,
+            Utc::now() - chrono::Duration::hours(4),
+            Utc::now(),
+        );
+
+        assert_eq!(summary.total_runs, 3);
+        assert_eq!(summary.failed_runs, 3);
+        assert_eq!(summary.violations_fixed[0].rule, "a");
+        assert_eq!(summary.violations_introduced[0].rule, "b");
+        assert_eq!(summary.level_changes.len(), 1);
+        assert!(summary.levels_only_raised());
+        assert!(summary
+            .to_markdown("v1.2.0", "HEAD")
+            .contains("NEEDS REVIEW"));
+    }
+
+    #[test]
+    fn test_summary_counts_suppression_changes() {
+        let suppressing = |offset_hours: i64, fingerprints: &[&str]| {
+            let mut run = record(offset_hours, VerificationLevel::Standard, vec![]);
+            run.suppressions = fingerprints.iter().map(|f| f.to_string()).collect();
+            run
+        };
+        let records = vec![
+            suppressing(3, &["a", "b"]),
+            suppressing(2, &["a", "b", "c", "d"]),
+            suppressing(1, &["a", "c", "d"]),
+        ];
+
+        let summary = ReleaseSummary::from_records(
+            &records,
+            Utc::now() - chrono::Duration::hours(4),
+            Utc::now(),
+        );
+
+        assert_eq!(summary.suppressions_added, 2);
+        assert_eq!(summary.suppressions_removed, 1);
+        let markdown = summary.to_markdown("v1.2.0", "HEAD");
+        assert!(markdown.contains("- Suppressions added: 2"));
+        assert!(markdown.contains("- Suppressions removed: 1"));
+    }
+
+    #[test]
+    fn test_trend_flags_slow_layers() {
+        let timed = |offset_hours: i64, formal_secs: u64, property_secs: u64| {
+            let mut run = record(offset_hours, VerificationLevel::Standard, vec![]);
+            run.layers = vec![
+                LayerRecord {
+                    layer: Layer::Formal,
+                    duration: Duration::from_secs(formal_secs),
+                    violations: 0,
+                    retries: 0,
+                    flaky_pass: false,
+                },
+                LayerRecord {
+                    layer: Layer::PropertyBased,
+                    duration: Duration::from_secs(property_secs),
+                    violations: 0,
+                    retries: 0,
+                    flaky_pass: false,
+                },
+            ];
+            run.total_time = Duration::from_secs(formal_secs + property_secs);
+            run
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let store = HistoryStore::for_project(temp_dir.path());
+        for run in [
+            timed(4, 100, 10),
+            timed(3, 10, 10),
+            timed(2, 12, 11),
+            timed(1, 30, 10),
+        ] {
+            store.record(&run).unwrap();
+        }
+
+        let records = store.last(3).unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(HistoryTrend::from_records(&records[..1], 0.2)
+            .unwrap()
+            .total
+            .is_none());
+
+        let trend = HistoryTrend::from_records(&records, 0.2).unwrap();
+        assert_eq!(trend.runs, 3);
+        assert_eq!(trend.layers[0].layer, Layer::Formal);
+        assert_eq!(trend.layers[0].timing.median, Duration::from_secs(11));
+        assert!(trend.layers[0].timing.regression);
+        assert!(!trend.layers[1].timing.regression);
+        assert!(trend.has_regression());
+    }
+}
@@ -1,7 +1,34 @@
+pub mod api_surface;
+pub mod artifacts;
+pub mod attestation;
+pub mod baseline;
+pub mod budget;
 pub mod cache;
+pub mod cache_index;
 pub mod cache_manager;
+pub mod dependency_graph;
+pub mod discovery;
+pub mod environment;
+pub mod error_catalog;
 pub mod errors;
+pub mod gates;
+pub mod history;
+pub mod links;
+pub mod lockfile;
+pub mod metrics;
+pub mod minimize;
+pub mod normalization;
+pub mod plugin_manifest;
 pub mod plugins;
+pub mod reports;
+pub mod retention;
+pub mod seed;
+pub mod spec_diff;
+pub mod spec_drift;
+pub mod spec_lint;
+pub mod spec_normalization;
+pub mod timings;
+pub mod traceability;
 pub mod types;
 pub mod verification;
 
@@ -9,12 +36,13 @@ pub mod verification;
 mod tests;
 
 pub use types::{
-    EnforcementMode, Layer, LayerResult, Severity, Status, Technique, VerificationLevel,
-    VerificationResult,
+    EnforcementMode, EnforcementPolicy, Layer, LayerResult, Severity, Status, Technique,
+    VerificationLevel, VerificationResult,
 };
 
 pub use cache::VerificationCache;
 pub use cache_manager::CacheManager;
-pub use errors::FerrisProofError;
+pub use errors::{FerrisProofError, Result};
 pub use plugins::PluginManager;
-pub use verification::VerificationEngine;
+pub use reports::{RendererRegistry, ReportRenderer};
+pub use verification::{VerificationEngine, VerificationEngineBuilder, VerificationEvent};
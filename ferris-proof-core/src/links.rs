@@ -0,0 +1,129 @@
+//! Deep links from violations to their source, from `[report] link_template`
+
+use crate::types::Location;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Placeholders a link template may use
+///
+/// `{path}` is relative to the project root and `{abs_path}` absolute;
+/// `{line}` and `{column}` default to 1 for locations without them.
+pub const PLACEHOLDERS: [&str; 5] = ["path", "abs_path", "line", "column", "commit"];
+
+/// URL template such as `vscode://file{abs_path}:{line}` or
+/// `https://github.com/org/repo/blob/{commit}/{path}#L{line}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct LinkTemplate {
+    template: String,
+}
+
+impl TryFrom<String> for LinkTemplate {
+    type Error = String;
+
+    fn try_from(template: String) -> Result<Self, String> {
+        Self::parse(&template)
+    }
+}
+
+impl From<LinkTemplate> for String {
+    fn from(template: LinkTemplate) -> Self {
+        template.template
+    }
+}
+
+impl LinkTemplate {
+    /// Check that every `{...}` in `template` is a known placeholder
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}') else {
+                return Err(format!("unclosed '{{' in link template '{}'", template));
+            };
+            let name = &rest[start + 1..start + end];
+            if !PLACEHOLDERS.contains(&name) {
+                return Err(format!(
+                    "unknown placeholder {{{}}} in link template (expected one of {})",
+                    name,
+                    PLACEHOLDERS.map(|p| format!("{{{}}}", p)).join(", ")
+                ));
+            }
+            rest = &rest[start + end + 1..];
+        }
+        Ok(Self {
+            template: template.to_string(),
+        })
+    }
+
+    /// Link to `location`, a path relative to `project_root`
+    ///
+    /// `None` when the template needs a commit and the run has none, as a
+    /// link to another revision could point at the wrong lines.
+    pub fn render(
+        &self,
+        location: &Location,
+        project_root: &Path,
+        commit: Option<&str>,
+    ) -> Option<String> {
+        if location.file.as_os_str().is_empty() {
+            return None;
+        }
+        if self.template.contains("{commit}") && commit.is_none() {
+            return None;
+        }
+
+        let path = location.file.to_string_lossy().replace('\\', "/");
+        let abs_path = project_root
+            .join(&location.file)
+            .to_string_lossy()
+            .replace('\\', "/");
+        Some(
+            self.template
+                .replace("{path}", &path)
+                .replace("{abs_path}", &abs_path)
+                .replace("{line}", &location.line.unwrap_or(1).to_string())
+                .replace("{column}", &location.column.unwrap_or(1).to_string())
+                .replace("{commit}", commit.unwrap_or_default()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_links() {
+        let location = Location::point("src/lib.rs", 42, 7);
+        let root = Path::new("/work/shop");
+
+        let vscode = LinkTemplate::parse("vscode://file{abs_path}:{line}:{column}").unwrap();
+        assert_eq!(
+            vscode.render(&location, root, None).as_deref(),
+            Some("vscode://file/work/shop/src/lib.rs:42:7")
+        );
+
+        let github =
+            LinkTemplate::parse("https://github.com/org/shop/blob/{commit}/{path}#L{line}")
+                .unwrap();
+        assert_eq!(
+            github.render(&location, root, Some("abc123")).as_deref(),
+            Some("https://github.com/org/shop/blob/abc123/src/lib.rs#L42")
+        );
+        assert_eq!(github.render(&location, root, None), None);
+        assert_eq!(
+            github
+                .render(&Location::file("specs/raft.tla"), root, Some("abc123"))
+                .as_deref(),
+            Some("https://github.com/org/shop/blob/abc123/specs/raft.tla#L1")
+        );
+    }
+
+    #[test]
+    fn test_unknown_placeholders_are_rejected() {
+        assert!(LinkTemplate::parse("https://x/{file}")
+            .unwrap_err()
+            .contains("{file}"));
+        assert!(LinkTemplate::parse("https://x/{path").is_err());
+    }
+}
@@ -0,0 +1,328 @@
+use crate::cache::CacheKey;
+use crate::dependency_graph::TargetGraph;
+use crate::types::{relative_path, Status};
+use crate::verification::Target;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Lock format version; older or newer locks are ignored rather than trusted
+const LOCK_VERSION: u32 = 2;
+
+/// External verifiers whose versions the lock pins, as named by
+/// [`CacheKey::get_tool_versions`]
+pub const PINNED_TOOLS: [&str; 3] = ["TLA+ TLC", "Alloy Analyzer", "Kani Verifier"];
+
+/// Last verified state of a target as recorded in `.ferrisproof.lock`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockedTarget {
+    pub content_hash: String,
+    pub config_hash: String,
+    pub status: Status,
+    pub verified_at: DateTime<Utc>,
+}
+
+/// Committed manifest of passing targets, so fresh clones can skip them
+///
+/// Only file targets are locked: module targets hash their path rather than
+/// their contents. Files are keyed by their path relative to the project
+/// root with `/` separators, so the lock holds in any checkout. Entries are
+/// trusted only for the FerrisProof version and [`PINNED_TOOLS`] versions
+/// that wrote them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerificationLock {
+    pub version: u32,
+    pub tool_version: String,
+    /// Versions of the installed [`PINNED_TOOLS`] the targets were verified with
+    #[serde(default)]
+    pub tools: BTreeMap<String, String>,
+    pub targets: BTreeMap<String, LockedTarget>,
+}
+
+/// Targets split by whether the lock vouches for them
+#[derive(Debug, Default)]
+pub struct LockPartition {
+    pub stale: Vec<Target>,
+    /// Labels and locked statuses of unchanged targets
+    pub up_to_date: BTreeMap<String, Status>,
+}
+
+impl Default for VerificationLock {
+    fn default() -> Self {
+        Self {
+            version: LOCK_VERSION,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            tools: BTreeMap::new(),
+            targets: BTreeMap::new(),
+        }
+    }
+}
+
+impl VerificationLock {
+    pub fn path_for_project(project_root: &Path) -> PathBuf {
+        project_root.join(".ferrisproof.lock")
+    }
+
+    /// Load the lock, or `None` when it is missing or written by another version
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)?;
+        let lock: Self = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid verification lock {:?}: {}", path, e))?;
+        if lock.version != LOCK_VERSION || lock.tool_version != env!("CARGO_PKG_VERSION") {
+            debug!(
+                "Ignoring lock {:?} from ferris-proof {} (format {})",
+                path, lock.tool_version, lock.version
+            );
+            return Ok(None);
+        }
+        Ok(Some(lock))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Split targets into those needing verification and those the lock covers
+    ///
+    /// A target is up to date when its content and config hashes match a
+    /// passing entry and none of its dependencies is stale. Every target is
+    /// stale when `tools`, the installed [`PINNED_TOOLS`] versions, differ
+    /// from those the lock was written with.
+    pub fn partition(
+        &self,
+        targets: &[Target],
+        project_root: &Path,
+        tools: &BTreeMap<String, String>,
+        config_hash: impl Fn(&Target) -> String,
+    ) -> LockPartition {
+        if self.tools != *tools {
+            debug!(
+                "Ignoring lock entries verified with {:?}, installed tools are {:?}",
+                self.tools, tools
+            );
+            return LockPartition {
+                stale: targets.to_vec(),
+                up_to_date: BTreeMap::new(),
+            };
+        }
+
+        let graph = TargetGraph::build(targets);
+        let mut stale = HashSet::new();
+        let mut partition = LockPartition::default();
+
+        for idx in graph.topological_order() {
+            let target = &targets[idx];
+            let locked = self
+                .targets
+                .get(&lock_key(target, project_root))
+                .filter(|locked| {
+                    locked.status != Status::Error
+                        && locked.config_hash == config_hash(target)
+                        && content_hash(target).as_ref() == Some(&locked.content_hash)
+                });
+            match locked {
+                Some(locked) if !graph.dependencies(idx).iter().any(|d| stale.contains(d)) => {
+                    partition.up_to_date.insert(target.label(), locked.status);
+                }
+                _ => {
+                    stale.insert(idx);
+                }
+            }
+        }
+
+        // Keep the caller's order for the targets still to verify
+        partition.stale = targets
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| stale.contains(idx))
+            .map(|(_, target)| target.clone())
+            .collect();
+        partition
+    }
+
+    /// Record verified targets: passing ones are locked, failing ones dropped
+    ///
+    /// `statuses` are keyed by target label. Entries verified with other
+    /// [`PINNED_TOOLS`] versions than `tools` are dropped first.
+    pub fn update(
+        &mut self,
+        targets: &[Target],
+        project_root: &Path,
+        tools: &BTreeMap<String, String>,
+        statuses: &BTreeMap<String, Status>,
+        config_hash: impl Fn(&Target) -> String,
+    ) {
+        if self.tools != *tools {
+            self.targets.clear();
+            self.tools = tools.clone();
+        }
+
+        let now = Utc::now();
+        for target in targets {
+            let Some(status) = statuses.get(&target.label()) else {
+                continue;
+            };
+            let key = lock_key(target, project_root);
+            match (status, content_hash(target)) {
+                (Status::Success | Status::Warning, Some(content_hash)) => {
+                    self.targets.insert(
+                        key,
+                        LockedTarget {
+                            content_hash,
+                            config_hash: config_hash(target),
+                            status: *status,
+                            verified_at: now,
+                        },
+                    );
+                }
+                _ => {
+                    self.targets.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+/// Installed versions of [`PINNED_TOOLS`]; tools that are not installed are left out
+pub fn pinned_tool_versions() -> BTreeMap<String, String> {
+    match CacheKey::get_tool_versions() {
+        Ok(versions) => versions
+            .external_tools
+            .into_iter()
+            .filter(|(name, _)| PINNED_TOOLS.contains(&name.as_str()))
+            .collect(),
+        Err(e) => {
+            debug!("No tool versions for the lock: {}", e);
+            BTreeMap::new()
+        }
+    }
+}
+
+/// Key of a target in the lock; file paths are relative with `/` separators
+fn lock_key(target: &Target, project_root: &Path) -> String {
+    match target {
+        Target::RustFile(path) | Target::FormalSpec(path) => relative_path(path, project_root)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Target::Module(_) | Target::Item { .. } => target.label(),
+    }
+}
+
+/// Content hash of a file target; modules are never locked
+fn content_hash(target: &Target) -> Option<String> {
+    if matches!(target, Target::Module(_)) {
+        return None;
+    }
+    match CacheKey::compute_content_hash(target) {
+        Ok(hash) => Some(hash.0),
+        Err(e) => {
+            debug!("Not locking {}: {}", target.label(), e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_partition_skips_unchanged_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let lib = temp_dir.path().join("lib.rs");
+        let spec = temp_dir.path().join("protocol.tla");
+        std::fs::write(&lib, "pub fn add(a: u32, b: u32) -> u32 { a + b }\n").unwrap();
+        std::fs::write(&spec, "---- MODULE protocol ----\n====\n").unwrap();
+        let targets = vec![
+            Target::RustFile(lib.clone()),
+            Target::FormalSpec(spec.clone()),
+            Target::Module("core".to_string()),
+        ];
+        let config_hash = |_: &Target| "cfg-1".to_string();
+
+        let root = temp_dir.path();
+        let tools = BTreeMap::from([("TLA+ TLC".to_string(), "2.18".to_string())]);
+
+        let mut lock = VerificationLock::default();
+        let statuses = BTreeMap::from([
+            (lib.display().to_string(), Status::Success),
+            (spec.display().to_string(), Status::Error),
+            ("core".to_string(), Status::Success),
+        ]);
+        lock.update(&targets, root, &tools, &statuses, config_hash);
+        assert_eq!(lock.targets.keys().collect::<Vec<_>>(), ["lib.rs"]);
+
+        let partition = lock.partition(&targets, root, &tools, config_hash);
+        assert_eq!(
+            partition.up_to_date.keys().collect::<Vec<_>>(),
+            vec![&lib.display().to_string()]
+        );
+        assert_eq!(partition.stale.len(), 2);
+
+        // Another TLC version invalidates every entry
+        let upgraded = BTreeMap::from([("TLA+ TLC".to_string(), "2.19".to_string())]);
+        assert!(lock
+            .partition(&targets, root, &upgraded, config_hash)
+            .up_to_date
+            .is_empty());
+
+        // Comments do not change the normalized hash, code does
+        std::fs::write(
+            &lib,
+            "// docs\npub fn add(a: u32, b: u32) -> u32 { a + b }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            lock.partition(&targets, root, &tools, config_hash)
+                .up_to_date
+                .len(),
+            1
+        );
+        std::fs::write(&lib, "pub fn add(a: u32, b: u32) -> u32 { a - b }\n").unwrap();
+        assert!(lock
+            .partition(&targets, root, &tools, config_hash)
+            .up_to_date
+            .is_empty());
+
+        let path = VerificationLock::path_for_project(temp_dir.path());
+        lock.save(&path).unwrap();
+        assert_eq!(VerificationLock::load(&path).unwrap(), Some(lock));
+    }
+
+    #[test]
+    fn test_lock_holds_in_another_checkout() {
+        let source = "pub fn add(a: u32, b: u32) -> u32 { a + b }\n";
+        let checkout = |dir: &TempDir| {
+            let lib = dir.path().join("src").join("lib.rs");
+            std::fs::create_dir_all(lib.parent().unwrap()).unwrap();
+            std::fs::write(&lib, source).unwrap();
+            vec![Target::RustFile(lib)]
+        };
+        let config_hash = |_: &Target| "cfg-1".to_string();
+        let tools = BTreeMap::new();
+
+        let local = TempDir::new().unwrap();
+        let targets = checkout(&local);
+        let mut lock = VerificationLock::default();
+        let statuses = BTreeMap::from([(targets[0].label(), Status::Success)]);
+        lock.update(&targets, local.path(), &tools, &statuses, config_hash);
+        assert!(lock.targets.contains_key("src/lib.rs"));
+
+        // The same file in a CI checkout elsewhere is covered by the committed lock
+        let ci = TempDir::new().unwrap();
+        let targets = checkout(&ci);
+        let partition = lock.partition(&targets, ci.path(), &tools, config_hash);
+        assert!(partition.stale.is_empty());
+        assert_eq!(partition.up_to_date.len(), 1);
+    }
+}
@@ -0,0 +1,345 @@
+use crate::history::RunRecord;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+
+/// Usage metrics aggregated across verification runs, kept on the local machine only
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageMetrics {
+    pub runs: u64,
+    pub first_run: Option<DateTime<Utc>>,
+    pub last_run: Option<DateTime<Utc>>,
+    /// Per-layer usage keyed by layer name
+    #[serde(default)]
+    pub layers: BTreeMap<String, LayerUsage>,
+    /// Per-rule violation counts keyed by rule
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleUsage>,
+    /// Sum of per-run cache hit rates, divided by `runs` for the average
+    #[serde(default)]
+    pub cache_hit_rate_sum: f64,
+}
+
+/// How often a layer ran and how long it took
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayerUsage {
+    pub runs: u64,
+    pub total_duration: Duration,
+    pub violations: u64,
+    /// Tool reruns after spurious failures, see `[tools.*] retries`
+    #[serde(default)]
+    pub retries: u64,
+    /// Runs where the layer passed only after a rerun
+    #[serde(default)]
+    pub flaky_passes: u64,
+}
+
+/// How often a rule was violated
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleUsage {
+    /// Violations reported in total
+    pub violations: u64,
+    /// Runs reporting at least one violation of the rule
+    pub runs: u64,
+}
+
+impl UsageMetrics {
+    /// Aggregate a sequence of recorded runs
+    pub fn from_records(records: &[RunRecord]) -> Self {
+        let mut metrics = Self::default();
+        for record in records {
+            metrics.add(record);
+        }
+        metrics
+    }
+
+    /// Fold one run into the aggregate
+    pub fn add(&mut self, record: &RunRecord) {
+        self.runs += 1;
+        self.first_run = Some(
+            self.first_run
+                .map_or(record.timestamp, |first| first.min(record.timestamp)),
+        );
+        self.last_run = Some(
+            self.last_run
+                .map_or(record.timestamp, |last| last.max(record.timestamp)),
+        );
+        self.cache_hit_rate_sum += record.cache_hit_rate;
+
+        for layer in &record.layers {
+            let usage = self.layers.entry(format!("{:?}", layer.layer)).or_default();
+            usage.runs += 1;
+            usage.total_duration += layer.duration;
+            usage.violations += layer.violations as u64;
+            usage.retries += u64::from(layer.retries);
+            usage.flaky_passes += u64::from(layer.flaky_pass);
+        }
+
+        let mut seen = BTreeSet::new();
+        for violation in &record.violations {
+            let usage = self.rules.entry(violation.rule.clone()).or_default();
+            usage.violations += 1;
+            if seen.insert(violation.rule.as_str()) {
+                usage.runs += 1;
+            }
+        }
+    }
+
+    pub fn average_cache_hit_rate(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            self.cache_hit_rate_sum / self.runs as f64
+        }
+    }
+
+    /// Fraction of all runs in which the rule was violated
+    pub fn violation_rate(&self, rule: &RuleUsage) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            rule.runs as f64 / self.runs as f64
+        }
+    }
+
+    /// Derived figures for export, alongside the raw counters
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "runs": self.runs,
+            "first_run": self.first_run,
+            "last_run": self.last_run,
+            "average_cache_hit_rate": self.average_cache_hit_rate(),
+            "layers": self.layers.iter().map(|(name, usage)| serde_json::json!({
+                "layer": name,
+                "runs": usage.runs,
+                "average_duration_ms": usage.average_duration().as_secs_f64() * 1000.0,
+                "violations": usage.violations,
+                "retries": usage.retries,
+                "flaky_passes": usage.flaky_passes,
+            })).collect::<Vec<_>>(),
+            "rules": self.rules.iter().map(|(rule, usage)| serde_json::json!({
+                "rule": rule,
+                "violations": usage.violations,
+                "runs": usage.runs,
+                "violation_rate": self.violation_rate(usage),
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    /// One `section,name,metric,value` row per figure, for loading into dashboards
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("section,name,metric,value\n");
+        let mut row = |section: &str, name: &str, metric: &str, value: String| {
+            let _ = writeln!(csv, "{},{},{},{}", section, csv_field(name), metric, value);
+        };
+
+        row("summary", "", "runs", self.runs.to_string());
+        row(
+            "summary",
+            "",
+            "average_cache_hit_rate",
+            format!("{:.4}", self.average_cache_hit_rate()),
+        );
+        for (name, usage) in &self.layers {
+            row("layer", name, "runs", usage.runs.to_string());
+            row(
+                "layer",
+                name,
+                "average_duration_ms",
+                format!("{:.1}", usage.average_duration().as_secs_f64() * 1000.0),
+            );
+            row("layer", name, "violations", usage.violations.to_string());
+            row("layer", name, "retries", usage.retries.to_string());
+            row(
+                "layer",
+                name,
+                "flaky_passes",
+                usage.flaky_passes.to_string(),
+            );
+        }
+        for (rule, usage) in &self.rules {
+            row("rule", rule, "violations", usage.violations.to_string());
+            row("rule", rule, "runs", usage.runs.to_string());
+            row(
+                "rule",
+                rule,
+                "violation_rate",
+                format!("{:.4}", self.violation_rate(usage)),
+            );
+        }
+        csv
+    }
+}
+
+impl LayerUsage {
+    pub fn average_duration(&self) -> Duration {
+        if self.runs == 0 {
+            Duration::ZERO
+        } else {
+            self.total_duration / self.runs as u32
+        }
+    }
+}
+
+/// Quote a CSV field when it contains separators or quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Aggregated usage metrics stored as JSON under `.ferris-proof/`
+pub struct MetricsStore {
+    path: PathBuf,
+}
+
+impl MetricsStore {
+    /// Metrics store for the given project root
+    pub fn for_project(project_root: &Path) -> Self {
+        Self {
+            path: project_root.join(".ferris-proof").join("metrics.json"),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the aggregate, empty if nothing has been collected yet
+    pub fn load(&self) -> Result<UsageMetrics> {
+        if !self.path.exists() {
+            return Ok(UsageMetrics::default());
+        }
+
+        let content = std::fs::read_to_string(&self.path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Failed to parse metrics file {:?}: {}", self.path, e))
+    }
+
+    /// Fold a run into the stored aggregate
+    pub fn record(&self, record: &RunRecord) -> Result<UsageMetrics> {
+        let mut metrics = self.load()?;
+        metrics.add(record);
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&metrics)?)?;
+        debug!(
+            "Recorded usage metrics for run {} in {:?}",
+            record.id, self.path
+        );
+        Ok(metrics)
+    }
+
+    /// Discard all collected metrics
+    pub fn reset(&self) -> Result<()> {
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{LayerRecord, ViolationRecord};
+    use crate::types::{Layer, Severity, Status};
+    use tempfile::TempDir;
+
+    fn run(cache_hit_rate: f64, rules: &[&str], millis: u64) -> RunRecord {
+        RunRecord {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            git_commit: None,
+            level: None,
+            overall_status: Status::Success,
+            violations: rules
+                .iter()
+                .map(|rule| ViolationRecord {
+                    fingerprint: rule.to_string(),
+                    rule: rule.to_string(),
+                    severity: Severity::Warning,
+                    file: PathBuf::from("src/lib.rs"),
+                    message: String::new(),
+                })
+                .collect(),
+            total_time: Duration::from_millis(millis),
+            cache_hit_rate,
+            layers: vec![LayerRecord {
+                layer: Layer::PropertyBased,
+                duration: Duration::from_millis(millis),
+                violations: rules.len(),
+                retries: 0,
+                flaky_pass: false,
+            }],
+            targets: Vec::new(),
+            api_surface: None,
+            suppressions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_aggregates_layers_rules_and_cache() {
+        let mut flaky = run(0.5, &[], 300);
+        flaky.layers[0].retries = 2;
+        flaky.layers[0].flaky_pass = true;
+        let metrics = UsageMetrics::from_records(&[
+            run(1.0, &["unsafe-code", "unsafe-code"], 100),
+            flaky,
+            run(0.0, &["unsafe-code", "missing-docs"], 200),
+            run(0.5, &[], 200),
+        ]);
+
+        assert_eq!(metrics.runs, 4);
+        assert!((metrics.average_cache_hit_rate() - 0.5).abs() < f64::EPSILON);
+
+        let layer = &metrics.layers["PropertyBased"];
+        assert_eq!(layer.runs, 4);
+        assert_eq!(layer.average_duration(), Duration::from_millis(200));
+        assert_eq!(layer.violations, 4);
+        assert_eq!(layer.retries, 2);
+        assert_eq!(layer.flaky_passes, 1);
+
+        let unsafe_code = &metrics.rules["unsafe-code"];
+        assert_eq!(unsafe_code.violations, 3);
+        assert_eq!(unsafe_code.runs, 2);
+        assert!((metrics.violation_rate(unsafe_code) - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_store_accumulates_across_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = MetricsStore::for_project(temp_dir.path());
+        assert_eq!(store.load().unwrap(), UsageMetrics::default());
+
+        store.record(&run(1.0, &["a"], 10)).unwrap();
+        store.record(&run(0.0, &["a"], 30)).unwrap();
+
+        let metrics = store.load().unwrap();
+        assert_eq!(metrics.runs, 2);
+        assert_eq!(metrics.rules["a"].runs, 2);
+
+        store.reset().unwrap();
+        assert_eq!(store.load().unwrap().runs, 0);
+    }
+
+    #[test]
+    fn test_csv_export_quotes_fields() {
+        let metrics = UsageMetrics::from_records(&[run(1.0, &["odd,rule"], 10)]);
+        let csv = metrics.to_csv();
+
+        assert!(csv.starts_with("section,name,metric,value\n"));
+        assert!(csv.contains("summary,,runs,1\n"));
+        assert!(csv.contains("layer,PropertyBased,average_duration_ms,10.0\n"));
+        assert!(csv.contains("rule,\"odd,rule\",violation_rate,1.0000\n"));
+    }
+}
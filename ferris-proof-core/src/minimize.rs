@@ -0,0 +1,390 @@
+use crate::types::{ToolOutput, Violation};
+use regex::Regex;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Prefix of suggestions carrying a minimal reproducer
+pub const REPRODUCER_PREFIX: &str = "Minimal reproducer: ";
+
+/// Reduces a violation's counterexample to a minimal reproducer
+///
+/// Built-in minimizers read proptest's shrunk input and shorten TLC error
+/// traces. Plugins contribute their own through
+/// [`VerificationPlugin::minimizers`](crate::plugins::VerificationPlugin::minimizers).
+pub trait Minimizer: Send + Sync {
+    /// Identifier, e.g. "proptest-shrink"
+    fn name(&self) -> &str;
+
+    /// Minimal reproducer for `violation` from the output of the tool that
+    /// reported it, or `None` when this minimizer does not apply
+    fn minimize(&self, violation: &Violation, output: &ToolOutput) -> Option<Reproducer>;
+}
+
+/// Smallest known input or trace reproducing a violation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reproducer {
+    /// One-line description, e.g. `a = 0, b = -1`
+    pub summary: String,
+    /// Steps of a trace, in order
+    pub steps: Vec<String>,
+}
+
+impl Reproducer {
+    /// Suggestion text, the reproducer followed by any earlier suggestion
+    ///
+    /// ```text
+    /// Minimal reproducer: 2-state trace (shortened from 5)
+    ///   1. /\ x = 0
+    ///   2. /\ x = 3
+    /// Inspect the counterexample trace
+    /// ```
+    pub fn suggestion(&self, previous: Option<&str>) -> String {
+        let mut text = format!("{}{}", REPRODUCER_PREFIX, self.summary);
+        for (idx, step) in self.steps.iter().enumerate() {
+            text.push_str(&format!("\n  {}. {}", idx + 1, step));
+        }
+        if let Some(previous) = previous.filter(|p| !p.is_empty()) {
+            text.push('\n');
+            text.push_str(previous);
+        }
+        text
+    }
+}
+
+/// Minimizers consulted for every plugin output
+pub struct MinimizerRegistry {
+    minimizers: Vec<Arc<dyn Minimizer>>,
+}
+
+impl MinimizerRegistry {
+    /// Create a registry containing the built-in minimizers
+    pub fn new() -> Self {
+        let mut registry = Self {
+            minimizers: Vec::new(),
+        };
+
+        registry.register(Arc::new(ProptestShrinkMinimizer));
+        registry.register(Arc::new(TlcTraceMinimizer));
+
+        registry
+    }
+
+    /// Register a minimizer, replacing any existing one with the same name
+    ///
+    /// Later registrations are tried first, so plugins can refine the
+    /// built-in minimizers.
+    pub fn register(&mut self, minimizer: Arc<dyn Minimizer>) {
+        let name = minimizer.name().to_string();
+        if let Some(idx) = self.minimizers.iter().position(|m| m.name() == name) {
+            warn!("Replacing existing counterexample minimizer: {}", name);
+            self.minimizers.remove(idx);
+        }
+        debug!("Registered counterexample minimizer: {}", name);
+        self.minimizers.push(minimizer);
+    }
+
+    /// Names of the registered minimizers, most recent first
+    pub fn names(&self) -> Vec<String> {
+        self.minimizers
+            .iter()
+            .rev()
+            .map(|m| m.name().to_string())
+            .collect()
+    }
+
+    /// Attach a minimal reproducer to every violation one can be found for
+    pub fn apply(&self, violations: &mut [Violation], output: &ToolOutput) {
+        for violation in violations.iter_mut() {
+            if violation
+                .suggestion
+                .as_deref()
+                .is_some_and(|s| s.starts_with(REPRODUCER_PREFIX))
+            {
+                continue;
+            }
+            let found = self.minimizers.iter().rev().find_map(|minimizer| {
+                minimizer
+                    .minimize(violation, output)
+                    .map(|reproducer| (minimizer.name(), reproducer))
+            });
+            if let Some((name, reproducer)) = found {
+                debug!("Minimized {} with {}", violation.id, name);
+                violation.suggestion = Some(reproducer.suggestion(violation.suggestion.as_deref()));
+            }
+        }
+    }
+}
+
+impl Default for MinimizerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shortest behaviour with the same first and last state as `states`
+///
+/// A state seen twice closes a loop, and the steps in between can be cut
+/// without changing what the trace reaches. Consecutive duplicates
+/// (stuttering steps) are loops of length one.
+pub fn shorten_trace<T: PartialEq + Clone>(states: &[T]) -> Vec<T> {
+    let mut shortened: Vec<T> = Vec::with_capacity(states.len());
+    for state in states {
+        match shortened.iter().position(|seen| seen == state) {
+            Some(idx) => shortened.truncate(idx + 1),
+            None => shortened.push(state.clone()),
+        }
+    }
+    shortened
+}
+
+/// Reads the input proptest shrank a failing property test down to
+///
+/// Applies to violations naming a test in backticks, e.g.
+/// ``Property test `roundtrip` failed``, whose captured output contains
+/// `minimal failing input: ...`.
+pub struct ProptestShrinkMinimizer;
+
+impl Minimizer for ProptestShrinkMinimizer {
+    fn name(&self) -> &str {
+        "proptest-shrink"
+    }
+
+    fn minimize(&self, violation: &Violation, output: &ToolOutput) -> Option<Reproducer> {
+        let minimal = Regex::new(r"minimal failing input: ([^\n]+)").unwrap();
+        let test = Regex::new(r"`([^`]+)`")
+            .unwrap()
+            .captures(&violation.message)
+            .map(|caps| caps[1].to_string());
+
+        // libtest JSON events carry each failed test's captured output
+        let mut captured: Vec<String> = output
+            .stdout
+            .lines()
+            .filter(|line| line.trim_start().starts_with('{'))
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter(|event| event.get("event").and_then(|e| e.as_str()) == Some("failed"))
+            .filter(|event| {
+                test.as_deref().is_none_or(|test| {
+                    event
+                        .get("name")
+                        .and_then(|n| n.as_str())
+                        .is_some_and(|name| name == test || name.ends_with(&format!("::{}", test)))
+                })
+            })
+            .filter_map(|event| event.get("stdout")?.as_str().map(String::from))
+            .collect();
+        if test.is_none() || captured.is_empty() {
+            captured.push(format!("{}\n{}", output.stdout, output.stderr));
+        }
+
+        let input = captured.iter().find_map(|text| {
+            minimal
+                .captures(text)
+                .map(|caps| caps[1].trim().to_string())
+        })?;
+        Some(Reproducer {
+            summary: input,
+            steps: Vec::new(),
+        })
+    }
+}
+
+/// Shortens the error trace TLC prints for a violated invariant
+///
+/// Liveness counterexamples end in a loop (`Back to state N`) that is part
+/// of the violation, so their trace is reported unshortened.
+pub struct TlcTraceMinimizer;
+
+impl Minimizer for TlcTraceMinimizer {
+    fn name(&self) -> &str {
+        "tlc-trace"
+    }
+
+    fn minimize(&self, _violation: &Violation, output: &ToolOutput) -> Option<Reproducer> {
+        if output.tool != "tlc" {
+            return None;
+        }
+        let states = tlc_trace(&output.stdout);
+        if states.is_empty() {
+            return None;
+        }
+
+        let lasso = output.stdout.contains("Back to state");
+        let minimal = if lasso {
+            states.clone()
+        } else {
+            shorten_trace(&states)
+        };
+        let summary = if minimal.len() < states.len() {
+            format!(
+                "{}-state trace (shortened from {})",
+                minimal.len(),
+                states.len()
+            )
+        } else {
+            format!("{}-state trace", minimal.len())
+        };
+
+        Some(Reproducer {
+            summary,
+            steps: minimal
+                .into_iter()
+                .map(|state| state.lines().collect::<Vec<_>>().join(" "))
+                .collect(),
+        })
+    }
+}
+
+/// Variable assignments of each state in a TLC error trace
+///
+/// ```text
+/// State 1: <Initial predicate>
+/// /\ x = 0
+///
+/// State 2: <Next line 8, col 9 to line 8, col 20 of module Counter>
+/// /\ x = 1
+/// ```
+pub fn tlc_trace(output: &str) -> Vec<String> {
+    let header = Regex::new(r"^State \d+: ").unwrap();
+    let mut states: Vec<String> = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in output.lines() {
+        if header.is_match(line) {
+            if let Some(body) = current.take() {
+                states.push(body.join("\n"));
+            }
+            current = Some(Vec::new());
+        } else if let Some(body) = current.as_mut() {
+            let line = line.trim();
+            if !line.is_empty() {
+                body.push(line);
+            } else if !body.is_empty() {
+                states.push(body.join("\n"));
+                current = None;
+            }
+        }
+    }
+    if let Some(body) = current.filter(|body| !body.is_empty()) {
+        states.push(body.join("\n"));
+    }
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Location, Severity};
+    use std::time::Duration;
+
+    fn violation(message: &str, suggestion: Option<&str>) -> Violation {
+        Violation {
+            id: "V".to_string(),
+            severity: Severity::Error,
+            location: Location::file("src/lib.rs"),
+            message: message.to_string(),
+            suggestion: suggestion.map(String::from),
+            rule: "rule".to_string(),
+        }
+    }
+
+    fn output(tool: &str, stdout: &str) -> ToolOutput {
+        ToolOutput {
+            tool: tool.to_string(),
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            exit_code: 1,
+            execution_time: Duration::ZERO,
+            artifact_id: None,
+            retries: 0,
+        }
+    }
+
+    #[test]
+    fn test_shorten_trace_cuts_loops_and_stutters() {
+        assert_eq!(shorten_trace(&[0, 1, 1, 2, 1, 3]), vec![0, 1, 3]);
+        assert_eq!(shorten_trace(&[0, 1, 2]), vec![0, 1, 2]);
+        assert_eq!(shorten_trace::<u8>(&[]), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_proptest_shrunk_input_from_libtest_events() {
+        let stdout = r#"{ "type": "test", "event": "started", "name": "codec::roundtrip" }
+{ "type": "test", "name": "codec::roundtrip", "event": "failed", "stdout": "thread 'codec::roundtrip' panicked at tests/property/codec.rs:12:5:\nTest failed: assertion failed: a < 10; minimal failing input: a = 10, b = \"\"\n\tsuccesses: 4\n" }
+"#;
+        let mut violations = vec![violation(
+            "Property test `roundtrip` failed",
+            Some("Check the test output"),
+        )];
+
+        MinimizerRegistry::new().apply(&mut violations, &output("proptest", stdout));
+        assert_eq!(
+            violations[0].suggestion.as_deref(),
+            Some("Minimal reproducer: a = 10, b = \"\"\nCheck the test output")
+        );
+    }
+
+    #[test]
+    fn test_tlc_trace_is_shortened() {
+        let stdout = "\
+Error: Invariant Bounded is violated.
+Error: The behavior up to this point is:
+State 1: <Initial predicate>
+/\\ x = 0
+
+State 2: <Inc line 8, col 9 to line 8, col 20 of module Counter>
+/\\ x = 1
+
+State 3: <Dec line 9, col 9 to line 9, col 20 of module Counter>
+/\\ x = 0
+
+State 4: <Jump line 10, col 9 to line 10, col 20 of module Counter>
+/\\ x = 3
+
+5 states generated, 4 distinct states found, 0 states left on queue.
+";
+        assert_eq!(
+            tlc_trace(stdout),
+            vec!["/\\ x = 0", "/\\ x = 1", "/\\ x = 0", "/\\ x = 3"]
+        );
+
+        let reproducer = TlcTraceMinimizer
+            .minimize(
+                &violation("Invariant violated", None),
+                &output("tlc", stdout),
+            )
+            .unwrap();
+        assert_eq!(reproducer.summary, "2-state trace (shortened from 4)");
+        assert_eq!(
+            reproducer.suggestion(None),
+            "Minimal reproducer: 2-state trace (shortened from 4)\n  1. /\\ x = 0\n  2. /\\ x = 3"
+        );
+    }
+
+    #[test]
+    fn test_later_minimizers_take_precedence() {
+        struct Fixed;
+        impl Minimizer for Fixed {
+            fn name(&self) -> &str {
+                "fixed"
+            }
+            fn minimize(&self, _: &Violation, _: &ToolOutput) -> Option<Reproducer> {
+                Some(Reproducer {
+                    summary: "n = 0".to_string(),
+                    steps: Vec::new(),
+                })
+            }
+        }
+
+        let mut registry = MinimizerRegistry::new();
+        registry.register(Arc::new(Fixed));
+        assert_eq!(registry.names()[0], "fixed");
+
+        let mut violations = vec![violation("failed", None)];
+        registry.apply(&mut violations, &output("custom", ""));
+        registry.apply(&mut violations, &output("custom", ""));
+        assert_eq!(
+            violations[0].suggestion.as_deref(),
+            Some("Minimal reproducer: n = 0")
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Policy for normalizing Rust sources before they are hashed into cache keys
+//!
+//! Normalizing too little causes cache misses on edits that cannot change a
+//! layer's result; normalizing too much causes hits on edits that can. The
+//! right trade-off differs per layer, so `[layers.<layer>.normalization]`
+//! overrides the default policy of each layer.
+
+use crate::types::Layer;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Version of the normalization rules, hashed into every content hash
+///
+/// Bump when normalization changes for an unchanged policy, so that results
+/// cached under the old rules are not found by the new ones.
+pub const NORMALIZATION_VERSION: u32 = 1;
+
+/// What is dropped from a Rust source before it is hashed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NormalizationPolicy {
+    /// Drop `#[cfg(..)]` attributes of functions, e.g. `#[cfg(test)]`
+    pub strip_cfg_attrs: bool,
+    /// Drop doc comments of functions and methods
+    pub strip_doc_comments: bool,
+    /// Hash function and method signatures only, ignoring their bodies
+    pub ignore_bodies: bool,
+    /// Hash macro invocations verbatim; otherwise they are dropped
+    pub keep_macros: bool,
+}
+
+impl Default for NormalizationPolicy {
+    fn default() -> Self {
+        Self {
+            strip_cfg_attrs: true,
+            strip_doc_comments: false,
+            ignore_bodies: false,
+            keep_macros: true,
+        }
+    }
+}
+
+impl NormalizationPolicy {
+    /// Rules version and settings, hashed ahead of the normalized source
+    pub fn tag(&self) -> String {
+        format!(
+            "normalization-v{}:cfg={}:doc={}:bodies={}:macros={}",
+            NORMALIZATION_VERSION,
+            self.strip_cfg_attrs,
+            self.strip_doc_comments,
+            self.ignore_bodies,
+            self.keep_macros
+        )
+    }
+}
+
+/// Normalization policy of each layer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NormalizationPolicies {
+    layers: HashMap<Layer, NormalizationPolicy>,
+}
+
+impl NormalizationPolicies {
+    /// Policies overriding the default for the given layers
+    pub fn new(layers: HashMap<Layer, NormalizationPolicy>) -> Self {
+        Self { layers }
+    }
+
+    pub fn for_layer(&self, layer: Layer) -> NormalizationPolicy {
+        self.layers.get(&layer).copied().unwrap_or_default()
+    }
+}
@@ -0,0 +1,211 @@
+use crate::error_catalog;
+use crate::errors::{FerrisProofError, Result};
+use jsonschema::JSONSchema;
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// JSON schema that plugin manifests are validated against on discovery
+pub fn manifest_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "FerrisProof Plugin Manifest",
+        "type": "object",
+        "required": ["name", "version"],
+        "properties": {
+            "$schema": { "type": "string" },
+            "name": {
+                "type": "string",
+                "pattern": "^[A-Za-z0-9][A-Za-z0-9_.-]*$",
+                "description": "Plugin identifier used by `[plugins]` and `--plugin`"
+            },
+            "version": {
+                "type": "string",
+                "pattern": "^[0-9]+\\.[0-9]+\\.[0-9]+([-+].*)?$",
+                "description": "Semantic version of the plugin"
+            },
+            "description": { "type": "string" },
+            "author": { "type": "string" },
+            "license": { "type": "string" },
+            "homepage": { "type": "string" },
+            "techniques": {
+                "type": "array",
+                "items": {
+                    "type": "string",
+                    "enum": ["TypeSafety", "PropertyTests", "SessionTypes", "RefinementTypes", "ConcurrencyTesting", "FormalSpecs", "ModelChecking", "MutationTesting", "UndefinedBehaviorChecks", "DeductiveProofs"]
+                },
+                "uniqueItems": true
+            },
+            "platforms": {
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "dependencies": {
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "renderers": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["format", "command"],
+                    "properties": {
+                        "format": { "type": "string", "minLength": 1 },
+                        "extension": { "type": "string", "minLength": 1 },
+                        "command": { "type": "string", "minLength": 1 },
+                        "args": {
+                            "type": "array",
+                            "items": { "type": "string" }
+                        }
+                    },
+                    "additionalProperties": false
+                }
+            },
+            "error_codes": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["code", "title", "description"],
+                    "properties": {
+                        "code": {
+                            "type": "string",
+                            "pattern": "^FP-PL-[A-Za-z0-9][A-Za-z0-9_.-]*-[0-9]{3}$",
+                            "description": "Code in the plugin's namespace, FP-PL-<name>-xxx"
+                        },
+                        "title": { "type": "string", "minLength": 1 },
+                        "description": { "type": "string" },
+                        "causes": { "type": "array", "items": { "type": "string" } },
+                        "solutions": { "type": "array", "items": { "type": "string" } },
+                        "examples": { "type": "array", "items": { "type": "string" } },
+                        "related_codes": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "additionalProperties": false
+                }
+            }
+        },
+        "additionalProperties": false
+    })
+}
+
+/// Check a parsed manifest, listing every problem with its JSON path
+pub fn validate_manifest(manifest: &Value) -> Result<()> {
+    let schema = manifest_schema();
+    let compiled = JSONSchema::compile(&schema).map_err(|e| {
+        FerrisProofError::plugin(
+            "manifest",
+            format!("Failed to compile plugin manifest schema: {}", e),
+        )
+    })?;
+
+    let plugin = manifest
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("manifest");
+    if let Err(errors) = compiled.validate(manifest) {
+        let problems: Vec<String> = errors
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                let path = if path.is_empty() {
+                    "/".to_string()
+                } else {
+                    path
+                };
+                format!("  {}: {}", path, e)
+            })
+            .collect();
+        return Err(FerrisProofError::plugin(
+            plugin,
+            format!("Invalid plugin manifest:\n{}", problems.join("\n")),
+        ));
+    }
+
+    // The schema cannot compare codes with the name, so check namespaces here
+    let foreign: Vec<String> = manifest
+        .get("error_codes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let code = entry.get("code").and_then(Value::as_str)?;
+            (!error_catalog::in_namespace(plugin, code)).then(|| {
+                format!(
+                    "  /error_codes/{}/code: {} is outside the plugin's namespace {}xxx",
+                    i,
+                    code,
+                    error_catalog::plugin_namespace(plugin)
+                )
+            })
+        })
+        .collect();
+    if !foreign.is_empty() {
+        return Err(FerrisProofError::plugin(
+            plugin,
+            format!("Invalid plugin manifest:\n{}", foreign.join("\n")),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Read, parse and validate a manifest file
+pub fn load_manifest(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path).map_err(|e| FerrisProofError::io(path, e))?;
+    let manifest: Value = serde_json::from_str(&content).map_err(|e| {
+        FerrisProofError::parse(
+            format!("Plugin manifest is not valid JSON: {}", e),
+            Some(path),
+        )
+    })?;
+    validate_manifest(&manifest)?;
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_validation_reports_paths() {
+        let valid = json!({
+            "name": "kani",
+            "version": "0.3.1",
+            "techniques": ["ModelChecking"],
+            "renderers": [{ "format": "servicenow", "command": "bin/fp-servicenow" }]
+        });
+        assert!(validate_manifest(&valid).is_ok());
+
+        let invalid = json!({
+            "name": "kani",
+            "version": "latest",
+            "techniques": ["ModelCheck"],
+            "renderers": [{ "format": "servicenow" }],
+            "technique": []
+        });
+        let message = validate_manifest(&invalid).unwrap_err().to_string();
+        assert!(message.contains("/version"), "{}", message);
+        assert!(message.contains("/techniques/0"), "{}", message);
+        assert!(message.contains("/renderers/0"), "{}", message);
+        assert!(message.contains("technique"), "{}", message);
+
+        let foreign = json!({
+            "name": "kani",
+            "version": "0.3.1",
+            "error_codes": [
+                { "code": "FP-PL-kani-001", "title": "Unwinding assertion", "description": "" },
+                { "code": "FP-PL-cbmc-001", "title": "Solver crash", "description": "" }
+            ]
+        });
+        let message = validate_manifest(&foreign).unwrap_err().to_string();
+        assert!(message.contains("/error_codes/1/code"), "{}", message);
+        assert!(!message.contains("/error_codes/0"), "{}", message);
+
+        let message = validate_manifest(&json!({ "version": "1.0.0" }))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.contains("\"name\" is a required property"),
+            "{}",
+            message
+        );
+    }
+}
@@ -1,17 +1,31 @@
+use crate::error_catalog::PluginErrorCode;
+use crate::errors::{FerrisProofError, Result};
+use crate::minimize::{Minimizer, MinimizerRegistry};
+use crate::plugin_manifest;
+use crate::reports::RendererManifest;
 use crate::types::*;
-use anyhow::{anyhow, Result};
 use semver::Version;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
 use std::sync::{Arc, RwLock};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+pub use tokio_util::sync::CancellationToken;
+
+/// How long a cancelled plugin gets to stop before it is abandoned
+const CANCEL_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+/// How often a running tool is checked for exit or cancellation
+const TOOL_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
 /// Stable ABI trait for verification plugins
 ///
 /// This trait defines the interface that all verification plugins must implement.
 /// The ABI is designed to be stable across minor version updates to ensure
-/// plugin compatibility.
+/// plugin compatibility. Errors carry a catalog code, so callers can tell a
+/// cancelled tool ([`FerrisProofError::Cancelled`]) from a failed one.
 pub trait VerificationPlugin: Send + Sync {
     /// Plugin name and identifier
     fn name(&self) -> &str;
@@ -42,6 +56,11 @@ pub trait VerificationPlugin: Send + Sync {
 
     /// Cleanup plugin resources
     fn cleanup(&mut self) -> Result<()>;
+
+    /// Counterexample minimizers applied to every plugin's violations
+    fn minimizers(&self) -> Vec<Arc<dyn Minimizer>> {
+        Vec::new()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -89,8 +108,16 @@ pub struct VerificationOutput {
 pub struct EffectiveConfig {
     pub level: VerificationLevel,
     pub enforcement: EnforcementMode,
+    pub enforcement_policy: EnforcementPolicy,
     pub enabled_techniques: Vec<Technique>,
+    /// Settings of the plugin running, its `[tools]` section
     pub tool_config: serde_json::Value,
+    /// `[tools]` sections resolved for the target, keyed by plugin name
+    ///
+    /// The plugin manager passes the selected plugin's entry as `tool_config`.
+    pub plugin_tools: HashMap<String, serde_json::Value>,
+    /// Memory a tool may use, from `thresholds.max_memory_usage`, in bytes
+    pub memory_budget: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -100,6 +127,12 @@ pub struct VerificationContext {
     pub cache_dir: PathBuf,
     pub timeout: Option<std::time::Duration>,
     pub parallel_id: Option<u32>,
+    /// Run seed for randomized tools, e.g. exported as `PROPTEST_RNG_SEED`
+    pub seed: u64,
+    /// Fired on timeout or Ctrl-C; tools started via [`run_tool`] are killed
+    pub cancellation: CancellationToken,
+    /// Where generated files go in hermetic runs, instead of next to the inputs
+    pub scratch_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -113,6 +146,9 @@ pub struct PluginMetadata {
     pub techniques: Vec<Technique>,
     pub supported_platforms: Vec<String>,
     pub dependencies: Vec<String>,
+    pub renderers: Vec<RendererManifest>,
+    /// Codes added to the `explain` catalog, each `FP-PL-<name>-xxx`
+    pub error_codes: Vec<PluginErrorCode>,
 }
 
 #[derive(Debug, Clone)]
@@ -138,11 +174,225 @@ pub struct PerformanceMetrics {
     pub cache_hits: u32,
 }
 
+/// How `PluginManager::verify` picks among plugins supporting a technique
+#[derive(Debug, Clone, Default)]
+pub struct SelectionStrategy {
+    /// Use exactly this plugin (the `--plugin` CLI override)
+    pub forced: Option<String>,
+    /// Preferred plugins in order, from `[plugins] prefer`
+    pub prefer: Vec<String>,
+    /// Techniques run by every compatible plugin, from `[plugins] fan_out`
+    pub fan_out: Vec<Technique>,
+    /// Plugins that are never selected, from `[plugins] disabled`
+    pub disabled: Vec<String>,
+}
+
+/// Historical outcomes of a plugin, persisted alongside the cache
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginStats {
+    pub runs: u32,
+    pub successes: u32,
+    pub total_time: std::time::Duration,
+}
+
+impl PluginStats {
+    pub fn success_rate(&self) -> f64 {
+        if self.runs == 0 {
+            // Untried plugins rank as if they always succeed
+            1.0
+        } else {
+            self.successes as f64 / self.runs as f64
+        }
+    }
+
+    pub fn mean_latency(&self) -> std::time::Duration {
+        self.total_time / self.runs.max(1)
+    }
+
+    fn record(&mut self, success: bool, elapsed: std::time::Duration) {
+        self.runs += 1;
+        if success {
+            self.successes += 1;
+        }
+        self.total_time += elapsed;
+    }
+}
+
+/// How one plugin fared during a fan-out run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginOutcome {
+    pub plugin: String,
+    pub status: Status,
+    pub violations: usize,
+    pub execution_time: std::time::Duration,
+    /// Set when the plugin errored, its tool crashed or is not installed;
+    /// such outcomes are not verdicts
+    pub error: Option<String>,
+}
+
+/// Per-plugin outcomes of one technique fanned out for one target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginBreakdown {
+    pub target: String,
+    pub layer: Layer,
+    pub technique: Technique,
+    pub outcomes: Vec<PluginOutcome>,
+    /// Some plugins passed while others found violations
+    pub inconsistent: bool,
+}
+
+/// Violation id reported when fanned-out plugins reach different verdicts
+pub const PLUGIN_DISAGREEMENT: &str = "FP-PL-003";
+
+/// Merged result of running all compatible plugins for a technique
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanOutResult {
+    pub result: LayerResult,
+    pub breakdown: Vec<PluginOutcome>,
+    /// Some plugins passed while others found violations
+    pub inconsistent: bool,
+    /// Plugins that produced no verdict, kept out of the consistency check
+    pub errored: Vec<String>,
+}
+
+/// Reruns of a plugin whose tool failed spuriously, e.g. at JVM startup or
+/// out of memory, from its `[tools.*] retries` and `backoff`
+///
+/// Only runs whose tool crashed or failed transiently are retried; violations
+/// are results, not failures of the tool, and a missing tool or bad
+/// configuration fails the same way every time. The wait doubles after each
+/// attempt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub retries: u32,
+    pub backoff: std::time::Duration,
+}
+
+impl RetryPolicy {
+    /// Policy in a plugin's `tool_config`, none when `retries` is unset
+    pub fn from_tool_config(tool_config: &serde_json::Value) -> Self {
+        let retries = tool_config
+            .get("retries")
+            .and_then(|r| r.as_u64())
+            .map_or(0, |r| r.min(u32::MAX as u64) as u32);
+        let backoff = match tool_config.get("backoff").and_then(|b| b.as_str()) {
+            Some(backoff) => parse_backoff(backoff).unwrap_or_else(|e| {
+                warn!("Ignoring backoff: {}", e);
+                std::time::Duration::ZERO
+            }),
+            None => std::time::Duration::ZERO,
+        };
+        Self { retries, backoff }
+    }
+
+    /// Wait before rerun number `attempt`, counting from 1
+    pub fn delay(&self, attempt: u32) -> std::time::Duration {
+        self.backoff.saturating_mul(
+            1u32.checked_shl(attempt.saturating_sub(1))
+                .unwrap_or(u32::MAX),
+        )
+    }
+}
+
+/// Environment a tool gets from its `[tools.*.env]` section
+///
+/// `pass` names variables copied from the invoking environment, e.g.
+/// `JAVA_HOME`, and `set` fixes values. Sandboxed tools see only these on
+/// top of a minimal base; other tools inherit the environment and get `set`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolEnv {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pass: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub set: BTreeMap<String, String>,
+}
+
+impl ToolEnv {
+    /// Environment in a plugin's `tool_config`, empty when `env` is unset
+    pub fn from_tool_config(tool_config: &serde_json::Value) -> Self {
+        match tool_config.get("env") {
+            Some(env) => serde_json::from_value(env.clone()).unwrap_or_else(|e| {
+                warn!("Ignoring env: {}", e);
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    /// Every variable name, passed or set
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.pass
+            .iter()
+            .map(String::as_str)
+            .chain(self.set.keys().map(String::as_str))
+    }
+
+    /// Reject names a process environment cannot hold
+    pub fn validate(&self) -> Result<()> {
+        for name in self.names() {
+            if name.is_empty() || name.contains(['=', '\0']) {
+                return Err(FerrisProofError::configuration(format!(
+                    "invalid environment variable name '{}'",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Passed variables present in the invoking environment, then `set`
+    pub fn vars(&self) -> HashMap<String, String> {
+        let mut vars: HashMap<String, String> = self
+            .pass
+            .iter()
+            .filter_map(|name| Some((name.clone(), std::env::var(name).ok()?)))
+            .collect();
+        vars.extend(self.set.clone());
+        vars
+    }
+
+    /// Add `set` to a command run outside the sandbox
+    pub fn apply(&self, cmd: &mut Command) {
+        cmd.envs(&self.set);
+    }
+}
+
+/// Parse a backoff such as `500ms`, `5s` or `1m`
+pub fn parse_backoff(value: &str) -> Result<std::time::Duration> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| {
+        FerrisProofError::configuration(format!("invalid backoff '{}': expected e.g. 5s", value))
+    })?;
+    match unit {
+        "ms" => Ok(std::time::Duration::from_millis(amount)),
+        "s" | "" => Ok(std::time::Duration::from_secs(amount)),
+        "m" => amount
+            .checked_mul(60)
+            .map(std::time::Duration::from_secs)
+            .ok_or_else(|| {
+                FerrisProofError::configuration(format!("invalid backoff '{}': too large", value))
+            }),
+        _ => Err(FerrisProofError::configuration(format!(
+            "invalid backoff unit '{}': use ms, s or m",
+            unit
+        ))),
+    }
+}
+
 pub struct PluginManager {
     plugins: HashMap<String, Arc<RwLock<Box<dyn VerificationPlugin>>>>,
     plugin_registry: PluginRegistry,
     version_checker: VersionChecker,
     discovery: PluginDiscovery,
+    selection: SelectionStrategy,
+    stats: RwLock<HashMap<String, PluginStats>>,
+    /// Tool availability per plugin, checked once rather than on every ranking
+    availability: RwLock<HashMap<String, bool>>,
+    minimizers: MinimizerRegistry,
 }
 
 #[derive(Debug, Default)]
@@ -155,6 +405,7 @@ struct PluginRegistry {
 #[allow(dead_code)]
 struct PluginRegistration {
     metadata: PluginMetadata,
+    manifest_path: PathBuf,
     library_path: PathBuf,
     version_compatible: bool,
 }
@@ -179,7 +430,48 @@ impl PluginManager {
             plugin_registry: PluginRegistry::default(),
             version_checker: VersionChecker::new(current_version),
             discovery: PluginDiscovery::new(),
+            selection: SelectionStrategy::default(),
+            stats: RwLock::new(HashMap::new()),
+            availability: RwLock::new(HashMap::new()),
+            minimizers: MinimizerRegistry::new(),
+        }
+    }
+
+    pub fn set_selection_strategy(&mut self, strategy: SelectionStrategy) {
+        self.selection = strategy;
+    }
+
+    /// Load historical plugin outcomes, ignoring a missing file
+    pub fn load_plugin_stats(&mut self, path: &Path) -> Result<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let content = std::fs::read_to_string(path).map_err(|e| FerrisProofError::io(path, e))?;
+        let stats = serde_json::from_str(&content).map_err(|e| {
+            FerrisProofError::parse(format!("Invalid plugin stats: {}", e), Some(path))
+        })?;
+        self.stats = RwLock::new(stats);
+        Ok(())
+    }
+
+    pub fn save_plugin_stats(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
         }
+        let stats = self
+            .stats
+            .read()
+            .map_err(|e| FerrisProofError::plugin("stats", e.to_string()))?;
+        std::fs::write(path, serde_json::to_string_pretty(&*stats)?)?;
+        Ok(())
+    }
+
+    pub fn plugin_stats(&self, name: &str) -> PluginStats {
+        self.stats
+            .read()
+            .ok()
+            .and_then(|stats| stats.get(name).cloned())
+            .unwrap_or_default()
     }
 
     /// Register a plugin instance
@@ -190,16 +482,24 @@ impl PluginManager {
         // Check version compatibility
         let version_range = plugin.supported_versions();
         if !self.version_checker.is_compatible(&version_range) {
-            return Err(anyhow!(
-                "Plugin {} version {} is not compatible with FerrisProof {}",
-                name,
-                version,
-                self.version_checker.current_version
+            return Err(FerrisProofError::plugin(
+                &name,
+                format!(
+                    "version {} is not compatible with FerrisProof {}",
+                    version, self.version_checker.current_version
+                ),
             ));
         }
 
+        for minimizer in plugin.minimizers() {
+            self.minimizers.register(minimizer);
+        }
+
         let plugin_arc = Arc::new(RwLock::new(plugin));
         self.plugins.insert(name.clone(), plugin_arc);
+        if let Ok(availability) = self.availability.get_mut() {
+            availability.remove(&name);
+        }
 
         info!("Registered plugin: {} v{}", name, version);
         Ok(())
@@ -228,7 +528,7 @@ impl PluginManager {
     fn discover_in_directory(&mut self, dir: &Path) -> Result<usize> {
         let mut discovered_count = 0;
 
-        for entry in std::fs::read_dir(dir)? {
+        for entry in std::fs::read_dir(dir).map_err(|e| FerrisProofError::io(dir, e))? {
             let entry = entry?;
             let path = entry.path();
 
@@ -237,7 +537,7 @@ impl PluginManager {
                 match self.load_plugin_from_manifest(&path) {
                     Ok(_) => discovered_count += 1,
                     Err(e) => {
-                        warn!("Failed to load plugin from manifest {:?}: {}", path, e);
+                        warn!("Failed to load plugin from manifest {:?}: {:#}", path, e);
                     }
                 }
             }
@@ -246,12 +546,11 @@ impl PluginManager {
         Ok(discovered_count)
     }
 
-    /// Load plugin from manifest file
+    /// Load plugin from a manifest file after validating it against the manifest schema
     fn load_plugin_from_manifest(&mut self, manifest_path: &Path) -> Result<()> {
-        let manifest_content = std::fs::read_to_string(manifest_path)?;
-        let manifest: serde_json::Value = serde_json::from_str(&manifest_content)?;
+        let manifest = plugin_manifest::load_manifest(manifest_path)?;
 
-        let metadata = PluginMetadata::from_json(&manifest)?;
+        let metadata = PluginMetadata::from_json(&manifest, manifest_path.parent())?;
 
         // Check if already loaded
         if self.plugin_registry.loaded_plugins.contains(&metadata.name) {
@@ -262,6 +561,7 @@ impl PluginManager {
         // TODO: Implement dynamic loading with libloading
         let registration = PluginRegistration {
             metadata: metadata.clone(),
+            manifest_path: manifest_path.to_path_buf(),
             library_path: manifest_path
                 .parent()
                 .unwrap_or_else(|| Path::new("."))
@@ -283,7 +583,9 @@ impl PluginManager {
         technique: &Technique,
     ) -> Vec<Arc<RwLock<Box<dyn VerificationPlugin>>>> {
         self.plugins
-            .values()
+            .iter()
+            .filter(|(name, _)| self.is_enabled(name))
+            .map(|(_, plugin)| plugin)
             .filter(|plugin| {
                 let plugin_guard = plugin.read().ok();
                 if let Some(plugin) = plugin_guard {
@@ -358,50 +660,465 @@ impl PluginManager {
         Ok(validation_results)
     }
 
-    /// Execute verification using appropriate plugin
+    /// Layers none of whose `techniques` has a usable tool
+    ///
+    /// A technique is usable when a plugin ranked for it reports its tool
+    /// available in a compatible version. Layers with one usable technique
+    /// run as usual; the rest carry the catalog code of the first problem.
+    pub fn preflight(&self, techniques: &[Technique]) -> Vec<UnavailableLayer> {
+        let mut layers: Vec<(Layer, Vec<Technique>)> = Vec::new();
+        for technique in techniques {
+            match layers
+                .iter_mut()
+                .find(|(layer, _)| *layer == technique.layer())
+            {
+                Some((_, grouped)) => grouped.push(technique.clone()),
+                None => layers.push((technique.layer(), vec![technique.clone()])),
+            }
+        }
+
+        layers
+            .into_iter()
+            .filter_map(|(layer, techniques)| {
+                let mut problems = Vec::new();
+                for technique in &techniques {
+                    match self.check_technique(technique) {
+                        Ok(()) => return None,
+                        Err(e) => problems.push(e),
+                    }
+                }
+                warn!("No tool available for {:?}: skipping it", layer);
+                let first = &problems[0];
+                Some(UnavailableLayer {
+                    layer,
+                    code: first.code().to_string(),
+                    reason: problems
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                    hint: format!(
+                        "{} Run `{}` for details.",
+                        first.explanation(),
+                        first.explain_command()
+                    ),
+                    techniques,
+                })
+            })
+            .collect()
+    }
+
+    /// Ok when a plugin ranked for `technique` has a usable tool
+    fn check_technique(&self, technique: &Technique) -> Result<()> {
+        let mut problem = FerrisProofError::NoPlugin {
+            technique: format!("{:?}", technique),
+        };
+        for name in self.rank_plugins(technique)? {
+            let plugin = self.plugins[&name]
+                .read()
+                .map_err(|e| FerrisProofError::plugin(&name, e.to_string()))?;
+            problem = match plugin.check_availability() {
+                Ok(info) if info.available => {
+                    match self.version_checker.validate_tool_version(&info, None) {
+                        Ok(()) => return Ok(()),
+                        Err(e) => e,
+                    }
+                }
+                Ok(info) => FerrisProofError::ToolNotFound {
+                    tool: info.name,
+                    message: "reported unavailable".to_string(),
+                },
+                Err(e) => FerrisProofError::ToolNotFound {
+                    tool: name.clone(),
+                    message: e.to_string(),
+                },
+            };
+        }
+        Err(problem)
+    }
+
+    /// Names of plugins supporting `technique`, best candidate first
+    ///
+    /// A forced plugin short-circuits ranking. Otherwise plugins are ordered
+    /// by tool availability, position in the preference list, specialization
+    /// (fewer declared techniques first), historical success rate and mean
+    /// latency, then name.
+    pub fn rank_plugins(&self, technique: &Technique) -> Result<Vec<String>> {
+        if let Some(forced) = &self.selection.forced {
+            if !self.is_enabled(forced) {
+                return Err(FerrisProofError::plugin(
+                    forced,
+                    "disabled in [plugins] disabled",
+                ));
+            }
+            let plugin = self
+                .plugins
+                .get(forced)
+                .ok_or_else(|| FerrisProofError::plugin(forced, "not registered"))?;
+            let supported = plugin
+                .read()
+                .map_err(|e| FerrisProofError::plugin(forced, e.to_string()))?
+                .supported_techniques()
+                .contains(technique);
+            if !supported {
+                return Err(FerrisProofError::plugin(
+                    forced,
+                    format!("does not support technique {:?}", technique),
+                ));
+            }
+            return Ok(vec![forced.clone()]);
+        }
+
+        struct Candidate {
+            name: String,
+            available: bool,
+            preference: usize,
+            techniques: usize,
+            stats: PluginStats,
+        }
+
+        let mut candidates: Vec<Candidate> = self
+            .plugins
+            .iter()
+            .filter(|(name, _)| self.is_enabled(name))
+            .filter_map(|(name, plugin)| {
+                let plugin = plugin.read().ok()?;
+                let techniques = plugin.supported_techniques();
+                if !techniques.contains(technique) {
+                    return None;
+                }
+
+                Some(Candidate {
+                    name: name.clone(),
+                    available: self.is_available(name, plugin.as_ref()),
+                    preference: self
+                        .selection
+                        .prefer
+                        .iter()
+                        .position(|p| p == name)
+                        .unwrap_or(usize::MAX),
+                    techniques: techniques.len(),
+                    stats: self.plugin_stats(name),
+                })
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| {
+            b.available
+                .cmp(&a.available)
+                .then(a.preference.cmp(&b.preference))
+                .then(a.techniques.cmp(&b.techniques))
+                .then(b.stats.success_rate().total_cmp(&a.stats.success_rate()))
+                .then(a.stats.mean_latency().cmp(&b.stats.mean_latency()))
+                .then(a.name.cmp(&b.name))
+        });
+
+        debug!(
+            "Plugin ranking for {:?}: {:?}",
+            technique,
+            candidates.iter().map(|c| &c.name).collect::<Vec<_>>()
+        );
+        Ok(candidates.into_iter().map(|c| c.name).collect())
+    }
+
+    /// Whether `--plugin` forces a plugin that does not support `technique`
+    ///
+    /// The engine leaves such techniques out rather than failing their layer.
+    pub fn forced_plugin_skips(&self, technique: &Technique) -> bool {
+        let Some(forced) = &self.selection.forced else {
+            return false;
+        };
+        self.plugins.get(forced).is_some_and(|plugin| {
+            plugin
+                .read()
+                .is_ok_and(|plugin| !plugin.supported_techniques().contains(technique))
+        })
+    }
+
+    /// Execute verification using the best-ranked plugin
     pub async fn verify(
         &self,
         technique: &Technique,
         input: VerificationInput,
     ) -> Result<VerificationOutput> {
-        let plugins = self.plugins_for_technique(technique);
+        let name = self.best_plugin(technique)?;
+        self.run_plugin(&name, input).await
+    }
 
-        if plugins.is_empty() {
-            return Err(anyhow!(
-                "No plugins available for technique: {:?}",
-                technique
-            ));
+    fn best_plugin(&self, technique: &Technique) -> Result<String> {
+        self.rank_plugins(technique)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| FerrisProofError::NoPlugin {
+                technique: format!("{:?}", technique),
+            })
+    }
+
+    async fn run_plugin(&self, name: &str, input: VerificationInput) -> Result<VerificationOutput> {
+        let plugin = AsyncVerificationPlugin::new(name, Arc::clone(&self.plugins[name]));
+        info!("Verifying with plugin {}", name);
+
+        let start = std::time::Instant::now();
+        let mut output = verify_with_retries(&plugin, with_fresh_context(input, name)).await;
+        self.record_outcome(name, &output, start.elapsed());
+        if let Ok(output) = &mut output {
+            self.minimizers
+                .apply(&mut output.violations, &output.tool_output);
+        }
+
+        output
+    }
+
+    /// Register a counterexample minimizer outside of any plugin
+    pub fn register_minimizer(&mut self, minimizer: Arc<dyn Minimizer>) {
+        self.minimizers.register(minimizer);
+    }
+
+    /// Verify a technique for one layer, fanning out when configured to
+    ///
+    /// Without fan-out the best-ranked plugin's output is reported as a
+    /// breakdown of one.
+    pub async fn verify_layer(
+        &self,
+        layer: Layer,
+        technique: &Technique,
+        input: VerificationInput,
+    ) -> Result<FanOutResult> {
+        if self.fans_out(technique) {
+            return self.fan_out(layer, technique, input).await;
         }
 
-        // Use first available plugin
-        // TODO: Implement plugin selection strategy
-        if let Some(plugin_arc) = plugins.first() {
-            let plugin = plugin_arc
+        let name = self.best_plugin(technique)?;
+        let start = std::time::Instant::now();
+        let output = self.run_plugin(&name, input).await?;
+
+        Ok(FanOutResult {
+            breakdown: vec![PluginOutcome {
+                plugin: name,
+                status: output.status,
+                violations: output.violations.len(),
+                execution_time: start.elapsed(),
+                error: None,
+            }],
+            result: LayerResult {
+                layer,
+                status: output.status,
+                violations: output.violations,
+                execution_time: start.elapsed(),
+                tool_outputs: vec![output.tool_output],
+            },
+            inconsistent: false,
+            errored: Vec::new(),
+        })
+    }
+
+    /// Whether `verify_layer` runs every compatible plugin for `technique`,
+    /// i.e. it is in `[plugins] fan_out` and no plugin is forced
+    pub fn fans_out(&self, technique: &Technique) -> bool {
+        self.selection.fan_out.contains(technique) && self.selection.forced.is_none()
+    }
+
+    /// Run every plugin supporting `technique` concurrently on one target
+    ///
+    /// Outputs are merged into a single layer result. When one plugin passes
+    /// and another finds violations, the disagreement is reported as a
+    /// warning so that neither verdict is silently trusted. Plugins whose tool
+    /// is not installed are skipped, and they and plugins that error are
+    /// listed in `errored` rather than counted as a verdict.
+    pub async fn fan_out(
+        &self,
+        layer: Layer,
+        technique: &Technique,
+        input: VerificationInput,
+    ) -> Result<FanOutResult> {
+        let names = self.rank_plugins(technique)?;
+        if names.is_empty() {
+            return Err(FerrisProofError::NoPlugin {
+                technique: format!("{:?}", technique),
+            });
+        }
+        let (names, unavailable): (Vec<_>, Vec<_>) = names.into_iter().partition(|name| {
+            self.plugins[name]
                 .read()
-                .map_err(|e| anyhow!("Failed to acquire plugin lock: {}", e))?;
-
-            // Create verification context
-            let context = VerificationContext {
-                session_id: Uuid::new_v4().to_string(),
-                working_dir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/tmp")),
-                cache_dir: std::env::temp_dir(),
-                timeout: Some(std::time::Duration::from_secs(300)), // 5 minutes default
-                parallel_id: None,
+                .is_ok_and(|plugin| self.is_available(name, plugin.as_ref()))
+        });
+        if names.is_empty() {
+            return Err(FerrisProofError::NoPlugin {
+                technique: format!("{:?}", technique),
+            });
+        }
+        info!("Fanning out {:?} to plugins: {:?}", technique, names);
+
+        let start = std::time::Instant::now();
+        let handles: Vec<_> = names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                let plugin = AsyncVerificationPlugin::new(name, Arc::clone(&self.plugins[name]));
+                let mut input = with_fresh_context(input.clone(), name);
+                input.context.parallel_id = Some(idx as u32);
+                tokio::spawn(async move {
+                    let start = std::time::Instant::now();
+                    let output = verify_with_retries(&plugin, input).await;
+                    (output, start.elapsed())
+                })
+            })
+            .collect();
+
+        let mut result = LayerResult {
+            layer,
+            status: Status::Success,
+            violations: Vec::new(),
+            execution_time: std::time::Duration::ZERO,
+            tool_outputs: Vec::new(),
+        };
+        let mut breakdown: Vec<_> = unavailable
+            .into_iter()
+            .map(|name| PluginOutcome {
+                plugin: name,
+                status: Status::Skipped,
+                violations: 0,
+                execution_time: std::time::Duration::ZERO,
+                error: Some("tool not available".to_string()),
+            })
+            .collect();
+
+        for (name, handle) in names.into_iter().zip(handles) {
+            let (output, elapsed) = handle
+                .await
+                .map_err(|e| FerrisProofError::plugin(&name, format!("panicked: {}", e)))?;
+            self.record_outcome(&name, &output, elapsed);
+
+            let outcome = match output {
+                Ok(mut output) => {
+                    self.minimizers
+                        .apply(&mut output.violations, &output.tool_output);
+                    let outcome = PluginOutcome {
+                        plugin: name,
+                        status: output.status,
+                        violations: output.violations.len(),
+                        execution_time: elapsed,
+                        error: tool_crashed(&output).then(|| {
+                            format!(
+                                "{} failed without reporting violations",
+                                output.tool_output.tool
+                            )
+                        }),
+                    };
+                    result.violations.extend(output.violations);
+                    result.tool_outputs.push(output.tool_output);
+                    outcome
+                }
+                Err(e) => {
+                    warn!("Plugin {} failed: {}", name, e);
+                    PluginOutcome {
+                        plugin: name,
+                        status: Status::Error,
+                        violations: 0,
+                        execution_time: elapsed,
+                        error: Some(e.to_string()),
+                    }
+                }
             };
+            if outcome.status.rank() > result.status.rank() {
+                result.status = outcome.status;
+            }
+            breakdown.push(outcome);
+        }
 
-            let enhanced_input = VerificationInput {
-                target: input.target,
-                config: input.config,
-                context,
-            };
+        let verdicts = || breakdown.iter().filter(|o| o.error.is_none());
+        let passed: Vec<&str> = verdicts()
+            .filter(|o| o.status == Status::Success)
+            .map(|o| o.plugin.as_str())
+            .collect();
+        let failed: Vec<&str> = verdicts()
+            .filter(|o| o.status == Status::Error)
+            .map(|o| o.plugin.as_str())
+            .collect();
+        let inconsistent = !passed.is_empty() && !failed.is_empty();
+        if inconsistent {
+            result.violations.push(Violation {
+                id: PLUGIN_DISAGREEMENT.to_string(),
+                severity: Severity::Warning,
+                location: Location::file(PathBuf::from(input.target.label())),
+                message: format!(
+                    "Plugins disagree on {:?}: {} passed but {} found violations",
+                    technique,
+                    passed.join(", "),
+                    failed.join(", ")
+                ),
+                suggestion: Some(
+                    "Check whether the passing plugin covers the failing case".to_string(),
+                ),
+                rule: "fan_out_consistency".to_string(),
+            });
+        }
+        let errored = breakdown
+            .iter()
+            .filter(|o| o.error.is_some())
+            .map(|o| o.plugin.clone())
+            .collect();
+        result.execution_time = start.elapsed();
+
+        Ok(FanOutResult {
+            result,
+            breakdown,
+            inconsistent,
+            errored,
+        })
+    }
 
-            plugin.verify(enhanced_input)
-        } else {
-            Err(anyhow!(
-                "No plugin available for technique: {:?}",
-                technique
-            ))
+    /// Count a run towards the plugin's success rate
+    ///
+    /// Only execution failures count against it: an error from the plugin or
+    /// a crashed tool. Finding violations is the plugin doing its job.
+    fn record_outcome(
+        &self,
+        name: &str,
+        output: &Result<VerificationOutput>,
+        elapsed: std::time::Duration,
+    ) {
+        let success = output.as_ref().is_ok_and(|o| !tool_crashed(o));
+        if let Ok(mut stats) = self.stats.write() {
+            stats
+                .entry(name.to_string())
+                .or_default()
+                .record(success, elapsed);
+        }
+    }
+
+    /// Whether a plugin's tool is installed, checked once per manager
+    fn is_available(&self, name: &str, plugin: &dyn VerificationPlugin) -> bool {
+        let known = self
+            .availability
+            .read()
+            .ok()
+            .and_then(|availability| availability.get(name).copied());
+        if let Some(available) = known {
+            return available;
         }
+        let available = plugin.check_availability().is_ok_and(|info| info.available);
+        if let Ok(mut availability) = self.availability.write() {
+            availability.insert(name.to_string(), available);
+        }
+        available
+    }
+
+    /// Whether a plugin may be selected, i.e. is not in `[plugins] disabled`
+    pub fn is_enabled(&self, name: &str) -> bool {
+        !self
+            .selection
+            .disabled
+            .iter()
+            .any(|disabled| disabled == name)
+    }
+
+    /// Manifest a discovered plugin was loaded from; `None` for built-in plugins
+    pub fn manifest_path(&self, name: &str) -> Option<&Path> {
+        self.plugin_registry
+            .registered_plugins
+            .get(name)
+            .map(|registration| registration.manifest_path.as_path())
     }
 
     /// Get plugin metadata
@@ -450,6 +1167,23 @@ impl PluginManager {
         metadata_list
     }
 
+    /// Collect report renderers declared by loaded and discovered plugins
+    pub fn report_renderers(&self) -> Vec<RendererManifest> {
+        self.list_plugins()
+            .into_iter()
+            .flat_map(|metadata| metadata.renderers)
+            .collect()
+    }
+
+    /// Error codes declared by loaded and discovered plugins, keyed by plugin name
+    pub fn error_codes(&self) -> Vec<(String, Vec<PluginErrorCode>)> {
+        self.list_plugins()
+            .into_iter()
+            .filter(|metadata| !metadata.error_codes.is_empty())
+            .map(|metadata| (metadata.name, metadata.error_codes))
+            .collect()
+    }
+
     /// Initialize all plugins with configuration
     pub fn initialize_plugins(&mut self, config: &serde_json::Value) -> Result<()> {
         for (name, plugin_arc) in &self.plugins {
@@ -488,17 +1222,18 @@ impl PluginManager {
 }
 
 impl PluginMetadata {
-    fn from_json(value: &serde_json::Value) -> Result<Self> {
+    /// Parse manifest metadata, resolving relative renderer commands against `base_dir`
+    fn from_json(value: &serde_json::Value, base_dir: Option<&Path>) -> Result<Self> {
         Ok(Self {
             name: value
                 .get("name")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow!("Missing plugin name"))?
+                .ok_or_else(|| FerrisProofError::plugin("manifest", "Missing plugin name"))?
                 .to_string(),
             version: value
                 .get("version")
                 .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow!("Missing plugin version"))?
+                .ok_or_else(|| FerrisProofError::plugin("manifest", "Missing plugin version"))?
                 .to_string(),
             description: value
                 .get("description")
@@ -533,6 +1268,9 @@ impl PluginMetadata {
                             "ConcurrencyTesting" => Some(Technique::ConcurrencyTesting),
                             "FormalSpecs" => Some(Technique::FormalSpecs),
                             "ModelChecking" => Some(Technique::ModelChecking),
+                            "MutationTesting" => Some(Technique::MutationTesting),
+                            "UndefinedBehaviorChecks" => Some(Technique::UndefinedBehaviorChecks),
+                            "DeductiveProofs" => Some(Technique::DeductiveProofs),
                             _ => None,
                         })
                         .collect()
@@ -558,6 +1296,42 @@ impl PluginMetadata {
                         .collect()
                 })
                 .unwrap_or_default(),
+            renderers: value
+                .get("renderers")
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| {
+                            serde_json::from_value::<RendererManifest>(v.clone())
+                                .map_err(|e| warn!("Ignoring invalid renderer entry: {}", e))
+                                .ok()
+                        })
+                        .map(|mut renderer| {
+                            if let Some(base) = base_dir {
+                                if renderer.command.components().count() > 1
+                                    && renderer.command.is_relative()
+                                {
+                                    renderer.command = base.join(&renderer.command);
+                                }
+                            }
+                            renderer
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            error_codes: value
+                .get("error_codes")
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| {
+                            serde_json::from_value::<PluginErrorCode>(v.clone())
+                                .map_err(|e| warn!("Ignoring invalid error code entry: {}", e))
+                                .ok()
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
         })
     }
 }
@@ -618,34 +1392,37 @@ impl VersionChecker {
             if let Ok(tool_version) = tool_info.version.parse::<Version>() {
                 if let Some(min_version) = &range.min {
                     if tool_version < *min_version {
-                        return Err(anyhow!(
-                            "Tool {} version {} is below minimum required version {}",
-                            tool_info.name,
-                            tool_version,
-                            min_version
-                        ));
+                        return Err(FerrisProofError::ToolVersion {
+                            tool: tool_info.name.clone(),
+                            message: format!(
+                                "version {} is below minimum required version {}",
+                                tool_version, min_version
+                            ),
+                        });
                     }
                 }
 
                 if let Some(max_version) = &range.max {
                     if tool_version > *max_version {
-                        return Err(anyhow!(
-                            "Tool {} version {} is above maximum supported version {}",
-                            tool_info.name,
-                            tool_version,
-                            max_version
-                        ));
+                        return Err(FerrisProofError::ToolVersion {
+                            tool: tool_info.name.clone(),
+                            message: format!(
+                                "version {} is above maximum supported version {}",
+                                tool_version, max_version
+                            ),
+                        });
                     }
                 }
 
                 if let Some(exact_version) = &range.requires_exact {
                     if tool_version != *exact_version {
-                        return Err(anyhow!(
-                            "Tool {} version {} does not match required exact version {}",
-                            tool_info.name,
-                            tool_version,
-                            exact_version
-                        ));
+                        return Err(FerrisProofError::ToolVersion {
+                            tool: tool_info.name.clone(),
+                            message: format!(
+                                "version {} does not match required exact version {}",
+                                tool_version, exact_version
+                            ),
+                        });
                     }
                 }
             } else {
@@ -679,6 +1456,232 @@ impl PluginDiscovery {
     }
 }
 
+/// Async adapter running a synchronous plugin on the blocking pool
+///
+/// When the context's token is cancelled or its timeout elapses, the token
+/// is cancelled so tools started through [`run_tool`] are killed, and the
+/// call returns an error once the plugin has stopped.
+pub struct AsyncVerificationPlugin {
+    name: String,
+    plugin: Arc<RwLock<Box<dyn VerificationPlugin>>>,
+}
+
+impl AsyncVerificationPlugin {
+    pub fn new(name: &str, plugin: Arc<RwLock<Box<dyn VerificationPlugin>>>) -> Self {
+        Self {
+            name: name.to_string(),
+            plugin,
+        }
+    }
+
+    pub async fn verify(&self, input: VerificationInput) -> Result<VerificationOutput> {
+        let token = input.context.cancellation.clone();
+        let timeout = input.context.timeout;
+        let plugin = Arc::clone(&self.plugin);
+        let name = self.name.clone();
+        let mut handle = tokio::task::spawn_blocking(move || {
+            plugin
+                .read()
+                .map_err(|e| FerrisProofError::plugin(&name, e.to_string()))?
+                .verify(input)
+        });
+        let deadline = async {
+            match timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => std::future::pending().await,
+            }
+        };
+
+        let timed_out = tokio::select! {
+            joined = &mut handle => {
+                return joined
+                    .map_err(|e| FerrisProofError::plugin(&self.name, format!("panicked: {}", e)))?;
+            }
+            _ = token.cancelled() => false,
+            _ = deadline => true,
+        };
+
+        token.cancel();
+        warn!(
+            "Plugin {} {}, stopping its tools",
+            self.name,
+            if timed_out {
+                "timed out"
+            } else {
+                "was cancelled"
+            }
+        );
+        if tokio::time::timeout(CANCEL_GRACE, handle).await.is_err() {
+            warn!(
+                "Plugin {} did not stop within {:?} of cancellation",
+                self.name, CANCEL_GRACE
+            );
+        }
+        Err(if timed_out {
+            FerrisProofError::plugin(&self.name, "timed out")
+        } else {
+            FerrisProofError::Cancelled {
+                tool: self.name.clone(),
+            }
+        })
+    }
+}
+
+/// Run a tool to completion, killing it and its children when `cancellation` fires
+///
+/// Use instead of [`Command::output`] in plugins so timeouts and Ctrl-C do
+/// not leave orphaned processes behind. A killed tool is reported as
+/// [`FerrisProofError::Cancelled`], one that cannot be started as
+/// [`FerrisProofError::ToolNotFound`].
+pub fn run_tool(cmd: &mut Command, cancellation: &CancellationToken) -> Result<Output> {
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    let tool = cmd.get_program().to_string_lossy().to_string();
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| FerrisProofError::tool_not_found(&tool, e.to_string()))?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if cancellation.is_cancelled() {
+            debug!("Killing cancelled tool {}", tool);
+            kill_tree(&mut child);
+            let _ = child.wait();
+            return Err(FerrisProofError::Cancelled { tool });
+        }
+        std::thread::sleep(TOOL_POLL_INTERVAL);
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn drain<R: std::io::Read + Send + 'static>(pipe: Option<R>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+/// Kill a tool's whole process group, so e.g. `cargo` takes its test binaries with it
+fn kill_tree(child: &mut std::process::Child) {
+    #[cfg(unix)]
+    {
+        // SAFETY: the child was spawned as the leader of its own process group
+        unsafe {
+            libc::kill(-(child.id() as i32), libc::SIGKILL);
+        }
+    }
+    let _ = child.kill();
+}
+
+/// Run a plugin, rerunning it per its [`RetryPolicy`] while it errors
+///
+/// Each attempt gets a fresh cancellation token, so a timed-out attempt does
+/// not cancel the next; cancelling the run stops retrying. The returned
+/// output's `retries` counts the reruns, and its violation locations are
+/// made relative to the working directory.
+async fn verify_with_retries(
+    plugin: &AsyncVerificationPlugin,
+    input: VerificationInput,
+) -> Result<VerificationOutput> {
+    let policy = RetryPolicy::from_tool_config(&input.config.tool_config);
+    let run = input.context.cancellation.clone();
+    let mut attempt = 0;
+    loop {
+        let mut attempt_input = input.clone();
+        attempt_input.context.cancellation = run.child_token();
+        let outcome = plugin.verify(attempt_input).await;
+        let failure = match &outcome {
+            Ok(output) if tool_crashed(output) => Some("tool crashed".to_string()),
+            Err(e) if transient(e) => Some(e.to_string()),
+            _ => None,
+        };
+        if let Some(failure) = failure.filter(|_| attempt < policy.retries && !run.is_cancelled()) {
+            let delay = policy.delay(attempt + 1);
+            warn!(
+                "Plugin {} failed ({}), retrying in {:?} ({}/{})",
+                plugin.name,
+                failure,
+                delay,
+                attempt + 1,
+                policy.retries
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {
+                    attempt += 1;
+                    continue;
+                }
+                _ = run.cancelled() => {}
+            }
+        }
+        return outcome.map(|mut output| {
+            output.tool_output.retries = attempt;
+            for violation in &mut output.violations {
+                violation.location.relativize(&input.context.working_dir);
+            }
+            output
+        });
+    }
+}
+
+/// Whether the tool itself failed rather than reporting findings: an error
+/// without a single violation to show for it
+fn tool_crashed(output: &VerificationOutput) -> bool {
+    output.status == Status::Error && output.violations.is_empty()
+}
+
+/// Whether a failed run may pass when rerun: tool and plugin failures can be
+/// spurious, while a missing tool, bad configuration or cancellation is not
+fn transient(error: &FerrisProofError) -> bool {
+    matches!(
+        error,
+        FerrisProofError::Plugin { .. }
+            | FerrisProofError::Tool { .. }
+            | FerrisProofError::Io { .. }
+    )
+}
+
+/// Give plugins their own session and cancellation token, and their own
+/// `[tools]` section when one was resolved for the target
+///
+/// The caller's working and cache directories are kept; without a timeout
+/// the plugin gets five minutes.
+fn with_fresh_context(input: VerificationInput, plugin: &str) -> VerificationInput {
+    let mut config = input.config;
+    if let Some(tool_config) = config.plugin_tools.get(plugin) {
+        config.tool_config = tool_config.clone();
+    }
+    VerificationInput {
+        target: input.target,
+        config,
+        context: VerificationContext {
+            session_id: Uuid::new_v4().to_string(),
+            timeout: input
+                .context
+                .timeout
+                .or(Some(std::time::Duration::from_secs(300))),
+            cancellation: input.context.cancellation.child_token(),
+            ..input.context
+        },
+    }
+}
+
 impl Default for PluginManager {
     fn default() -> Self {
         Self::new()
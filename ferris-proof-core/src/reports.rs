@@ -0,0 +1,981 @@
+use crate::spec_diff::ChangeKind;
+use crate::types::*;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Renders a verification result into a specific report format
+///
+/// Built-in renderers cover JSON, Markdown, HTML and SARIF. Additional
+/// formats can be contributed by plugins through their manifest files.
+pub trait ReportRenderer: Send + Sync {
+    /// Format identifier used on the command line (e.g. "sarif")
+    fn format(&self) -> &str;
+
+    /// File extension for rendered reports, without the leading dot
+    fn file_extension(&self) -> &str;
+
+    /// Render the verification result into the report body
+    fn render(&self, result: &VerificationResult) -> Result<String>;
+}
+
+/// Renderer declaration found in a plugin manifest
+///
+/// ```json
+/// "renderers": [
+///     { "format": "servicenow", "extension": "json", "command": "fp-servicenow" }
+/// ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RendererManifest {
+    pub format: String,
+    #[serde(default = "default_extension")]
+    pub extension: String,
+    pub command: PathBuf,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+fn default_extension() -> String {
+    "txt".to_string()
+}
+
+/// Registry of available report renderers keyed by format name
+pub struct RendererRegistry {
+    renderers: HashMap<String, Arc<dyn ReportRenderer>>,
+}
+
+impl RendererRegistry {
+    /// Create a registry containing the built-in renderers
+    pub fn new() -> Self {
+        let mut registry = Self {
+            renderers: HashMap::new(),
+        };
+
+        registry.register(Arc::new(JsonRenderer));
+        registry.register(Arc::new(MarkdownRenderer));
+        registry.register(Arc::new(HtmlRenderer));
+        registry.register(Arc::new(SarifRenderer));
+
+        registry
+    }
+
+    /// Register a renderer, replacing any existing renderer for the same format
+    pub fn register(&mut self, renderer: Arc<dyn ReportRenderer>) {
+        let format = renderer.format().to_lowercase();
+        if self.renderers.contains_key(&format) {
+            warn!("Replacing existing report renderer for format: {}", format);
+        }
+        debug!("Registered report renderer: {}", format);
+        self.renderers.insert(format, renderer);
+    }
+
+    /// Register renderers declared in plugin manifests
+    pub fn register_manifests(&mut self, manifests: &[RendererManifest]) {
+        for manifest in manifests {
+            info!(
+                "Registering plugin report renderer '{}' ({:?})",
+                manifest.format, manifest.command
+            );
+            self.register(Arc::new(ExternalRenderer::new(manifest.clone())));
+        }
+    }
+
+    /// Look up a renderer by format name (case-insensitive)
+    pub fn get(&self, format: &str) -> Option<Arc<dyn ReportRenderer>> {
+        self.renderers.get(&format.to_lowercase()).cloned()
+    }
+
+    /// List all available format names, sorted
+    pub fn formats(&self) -> Vec<String> {
+        let mut formats: Vec<String> = self.renderers.keys().cloned().collect();
+        formats.sort();
+        formats
+    }
+
+    /// Render a result with the named format
+    pub fn render(&self, format: &str, result: &VerificationResult) -> Result<String> {
+        let renderer = self.get(format).ok_or_else(|| {
+            anyhow!(
+                "Unknown report format '{}'. Available formats: {}",
+                format,
+                self.formats().join(", ")
+            )
+        })?;
+
+        renderer.render(result)
+    }
+}
+
+impl Default for RendererRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Machine-readable JSON report
+pub struct JsonRenderer;
+
+impl ReportRenderer for JsonRenderer {
+    fn format(&self) -> &str {
+        "json"
+    }
+
+    fn file_extension(&self) -> &str {
+        "json"
+    }
+
+    fn render(&self, result: &VerificationResult) -> Result<String> {
+        // Going through a Value sorts map keys, so layers render in a stable order
+        let mut json = serde_json::to_value(result)?;
+        if result.link_template.is_some() {
+            for (layer, layer_result) in &result.layer_results {
+                let key = serde_json::to_value(layer)?;
+                let Some(violations) = key
+                    .as_str()
+                    .and_then(|key| json["layer_results"][key]["violations"].as_array_mut())
+                else {
+                    continue;
+                };
+                for (violation, value) in layer_result.violations.iter().zip(violations) {
+                    if let Some(link) = result.violation_link(violation) {
+                        value["link"] = json!(link);
+                    }
+                }
+            }
+        }
+        Ok(serde_json::to_string_pretty(&json)?)
+    }
+}
+
+/// Markdown report suitable for PR comments and CI artifacts
+pub struct MarkdownRenderer;
+
+impl ReportRenderer for MarkdownRenderer {
+    fn format(&self) -> &str {
+        "markdown"
+    }
+
+    fn file_extension(&self) -> &str {
+        "md"
+    }
+
+    fn render(&self, result: &VerificationResult) -> Result<String> {
+        let mut out = String::new();
+
+        out.push_str("# FerrisProof Verification Report\n\n");
+        out.push_str(&format!(
+            "- **Status:** {:?}\n- **Timestamp:** {}\n- **Total time:** {:.2}s\n",
+            result.overall_status,
+            result.timestamp.to_rfc3339(),
+            result.metrics.total_time.as_secs_f64()
+        ));
+        if let Some(scope) = result.metrics.max_verified_scope {
+            out.push_str(&format!("- **Max verified Alloy scope:** {}\n", scope));
+        }
+        if !result.metrics.tool_settings.is_empty() {
+            let settings: Vec<String> = result
+                .metrics
+                .tool_settings
+                .iter()
+                .map(|(key, value)| format!("{} = {}", key, value))
+                .collect();
+            out.push_str(&format!("- **Tool settings:** `{}`\n", settings.join(", ")));
+        }
+        if let Some(targets) = result.target_summary() {
+            out.push_str(&format!("- **Targets:** {}\n", targets));
+        }
+        if let Some(root) = &result.project_root {
+            out.push_str(&format!("- **Project root:** `{}`\n", root.display()));
+        }
+        if let Some(seed) = result.seed {
+            out.push_str(&format!("- **Seed:** `{}`\n", seed));
+        }
+        if let Some(budget) = &result.budget {
+            out.push_str(&format!("- **Violation budget:** {}\n", budget));
+        }
+        out.push('\n');
+
+        out.push_str("## Layers\n\n");
+        out.push_str("| Layer | Status | Violations | Time |\n");
+        out.push_str("|-------|--------|------------|------|\n");
+        for layer_result in sorted_layers(result) {
+            out.push_str(&format!(
+                "| {:?} | {} | {} | {:.2}s |\n",
+                layer_result.layer,
+                layer_status(result, layer_result),
+                layer_result.violations.len(),
+                layer_result.execution_time.as_secs_f64()
+            ));
+        }
+
+        if !result.gated_layers.is_empty() {
+            out.push_str("\n## Gated Layers\n\n");
+            out.push_str("| Target | Layer | Status |\n");
+            out.push_str("|--------|-------|--------|\n");
+            for gated in &result.gated_layers {
+                out.push_str(&format!(
+                    "| `{}` | {:?} | skipped (gated on {:?}) |\n",
+                    gated.target, gated.layer, gated.required
+                ));
+            }
+        }
+
+        if !result.unavailable_layers.is_empty() {
+            out.push_str("\n## Unavailable Tools\n\n");
+            out.push_str("| Layer | Code | Reason | Hint |\n");
+            out.push_str("|-------|------|--------|------|\n");
+            for unavailable in &result.unavailable_layers {
+                out.push_str(&format!(
+                    "| {:?} | `{}` | {} | {} |\n",
+                    unavailable.layer, unavailable.code, unavailable.reason, unavailable.hint
+                ));
+            }
+        }
+
+        if !result.plugin_breakdowns.is_empty() {
+            out.push_str("\n## Plugin Breakdown\n\n");
+            out.push_str("| Target | Technique | Plugin | Status | Violations | Time |\n");
+            out.push_str("|--------|-----------|--------|--------|------------|------|\n");
+            for breakdown in &result.plugin_breakdowns {
+                let technique = if breakdown.inconsistent {
+                    format!("{:?} (plugins disagree)", breakdown.technique)
+                } else {
+                    format!("{:?}", breakdown.technique)
+                };
+                for outcome in &breakdown.outcomes {
+                    let status = match &outcome.error {
+                        Some(error) => format!("{:?} ({})", outcome.status, error),
+                        None => format!("{:?}", outcome.status),
+                    };
+                    out.push_str(&format!(
+                        "| `{}` | {} | {} | {} | {} | {:.2}s |\n",
+                        breakdown.target,
+                        technique,
+                        outcome.plugin,
+                        status.replace('|', "\\|"),
+                        outcome.violations,
+                        outcome.execution_time.as_secs_f64()
+                    ));
+                }
+            }
+        }
+
+        if !result.spec_changes.is_empty() {
+            out.push_str("\n## Specification Changes\n");
+            for diff in &result.spec_changes {
+                out.push_str(&format!("\n### `{}`\n\n", diff.path.display()));
+                for change in &diff.changes {
+                    out.push_str(&format!(
+                        "- {} {} `{}`\n",
+                        change_label(change.change),
+                        change.kind.label(),
+                        change.name
+                    ));
+                }
+            }
+        }
+
+        let violations = all_violations(result);
+        if !violations.is_empty() {
+            out.push_str("\n## Violations\n\n");
+            for violation in violations {
+                let location = format!("`{}`", format_location(&violation.location));
+                out.push_str(&format!(
+                    "- **{:?}** `{}` at {}: {}\n",
+                    violation.severity,
+                    violation.rule,
+                    match result.violation_link(violation) {
+                        Some(link) => format!("[{}]({})", location, link),
+                        None => location,
+                    },
+                    violation.message
+                ));
+                if let Some(suggestion) = &violation.suggestion {
+                    out.push_str(&format!("  - Suggestion: {}\n", suggestion));
+                }
+            }
+        }
+
+        if let Some(environment) = &result.environment {
+            out.push_str("\n## Environment\n\n");
+            out.push_str("| Setting | Value |\n");
+            out.push_str("|---------|-------|\n");
+            for (label, value) in environment.entries() {
+                out.push_str(&format!(
+                    "| {} | `{}` |\n",
+                    label,
+                    value.replace('|', "\\|")
+                ));
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+/// Standalone HTML report
+pub struct HtmlRenderer;
+
+impl ReportRenderer for HtmlRenderer {
+    fn format(&self) -> &str {
+        "html"
+    }
+
+    fn file_extension(&self) -> &str {
+        "html"
+    }
+
+    fn render(&self, result: &VerificationResult) -> Result<String> {
+        let mut out = String::new();
+
+        out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str("<title>FerrisProof Verification Report</title>\n</head>\n<body>\n");
+        out.push_str("<h1>FerrisProof Verification Report</h1>\n");
+        out.push_str(&format!(
+            "<p>Status: <strong>{:?}</strong> &middot; {}</p>\n",
+            result.overall_status,
+            escape_html(&result.timestamp.to_rfc3339())
+        ));
+        if let Some(targets) = result.target_summary() {
+            out.push_str(&format!("<p>Targets: {}</p>\n", targets));
+        }
+        if let Some(root) = &result.project_root {
+            out.push_str(&format!(
+                "<p>Project root: <code>{}</code></p>\n",
+                escape_html(&root.display().to_string())
+            ));
+        }
+        if let Some(seed) = result.seed {
+            out.push_str(&format!("<p>Seed: <code>{}</code></p>\n", seed));
+        }
+        if let Some(budget) = &result.budget {
+            out.push_str(&format!(
+                "<p>Violation budget: {}</p>\n",
+                escape_html(&budget.to_string())
+            ));
+        }
+
+        out.push_str("<h2>Layers</h2>\n<table>\n");
+        out.push_str("<tr><th>Layer</th><th>Status</th><th>Violations</th><th>Time</th></tr>\n");
+        for layer_result in sorted_layers(result) {
+            out.push_str(&format!(
+                "<tr><td>{:?}</td><td>{}</td><td>{}</td><td>{:.2}s</td></tr>\n",
+                layer_result.layer,
+                layer_status(result, layer_result),
+                layer_result.violations.len(),
+                layer_result.execution_time.as_secs_f64()
+            ));
+        }
+        out.push_str("</table>\n");
+
+        if !result.gated_layers.is_empty() {
+            out.push_str("<h2>Gated Layers</h2>\n<table>\n");
+            out.push_str("<tr><th>Target</th><th>Layer</th><th>Status</th></tr>\n");
+            for gated in &result.gated_layers {
+                out.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td>{:?}</td><td>skipped (gated on {:?})</td></tr>\n",
+                    escape_html(&gated.target),
+                    gated.layer,
+                    gated.required
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+
+        if !result.unavailable_layers.is_empty() {
+            out.push_str("<h2>Unavailable Tools</h2>\n<table>\n");
+            out.push_str("<tr><th>Layer</th><th>Code</th><th>Reason</th><th>Hint</th></tr>\n");
+            for unavailable in &result.unavailable_layers {
+                out.push_str(&format!(
+                    "<tr><td>{:?}</td><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+                    unavailable.layer,
+                    escape_html(&unavailable.code),
+                    escape_html(&unavailable.reason),
+                    escape_html(&unavailable.hint)
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+
+        if !result.spec_changes.is_empty() {
+            out.push_str("<h2>Specification Changes</h2>\n");
+            for diff in &result.spec_changes {
+                out.push_str(&format!(
+                    "<h3><code>{}</code></h3>\n<ul>\n",
+                    escape_html(&diff.path.display().to_string())
+                ));
+                for change in &diff.changes {
+                    out.push_str(&format!(
+                        "<li>{} {} <code>{}</code></li>\n",
+                        change_label(change.change),
+                        change.kind.label(),
+                        escape_html(&change.name)
+                    ));
+                }
+                out.push_str("</ul>\n");
+            }
+        }
+
+        if let Some(matrix) = result.traceability.as_ref().filter(|m| !m.is_empty()) {
+            out.push_str("<h2>Traceability</h2>\n<table>\n");
+            out.push_str("<tr><th>Specification</th><th>Action</th><th>Implemented by</th></tr>\n");
+            for (spec, actions) in &matrix.spec_actions {
+                for action in actions {
+                    let items: Vec<String> = matrix
+                        .implementations(spec, action)
+                        .iter()
+                        .map(|link| {
+                            format!(
+                                "<code>{}</code> ({}:{})",
+                                escape_html(&link.item),
+                                escape_html(&link.file.display().to_string()),
+                                link.line
+                            )
+                        })
+                        .collect();
+                    out.push_str(&format!(
+                        "<tr><td><code>{}</code></td><td><code>{}</code></td><td>{}</td></tr>\n",
+                        escape_html(&spec.display().to_string()),
+                        escape_html(action),
+                        if items.is_empty() {
+                            "<em>not implemented</em>".to_string()
+                        } else {
+                            items.join("<br>")
+                        }
+                    ));
+                }
+            }
+            for link in matrix.dangling_links() {
+                out.push_str(&format!(
+                    "<tr><td><code>{}</code></td><td><code>{}</code></td><td><code>{}</code> <em>(unknown action)</em></td></tr>\n",
+                    escape_html(&link.spec.display().to_string()),
+                    escape_html(&link.action),
+                    escape_html(&link.item)
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+
+        let violations = all_violations(result);
+        if !violations.is_empty() {
+            out.push_str("<h2>Violations</h2>\n<ul>\n");
+            for violation in violations {
+                let location = format!(
+                    "<code>{}</code>",
+                    escape_html(&format_location(&violation.location))
+                );
+                out.push_str(&format!(
+                    "<li><strong>{:?}</strong> <code>{}</code> at {}: {}</li>\n",
+                    violation.severity,
+                    escape_html(&violation.rule),
+                    match result.violation_link(violation) {
+                        Some(link) =>
+                            format!("<a href=\"{}\">{}</a>", escape_html(&link), location),
+                        None => location,
+                    },
+                    escape_html(&violation.message)
+                ));
+            }
+            out.push_str("</ul>\n");
+        }
+
+        if let Some(environment) = &result.environment {
+            out.push_str("<h2>Environment</h2>\n<table>\n");
+            for (label, value) in environment.entries() {
+                out.push_str(&format!(
+                    "<tr><th>{}</th><td><code>{}</code></td></tr>\n",
+                    escape_html(&label),
+                    escape_html(&value)
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+
+        out.push_str("</body>\n</html>\n");
+        Ok(out)
+    }
+}
+
+/// SARIF 2.1.0 report for code scanning integrations
+pub struct SarifRenderer;
+
+impl ReportRenderer for SarifRenderer {
+    fn format(&self) -> &str {
+        "sarif"
+    }
+
+    fn file_extension(&self) -> &str {
+        "sarif"
+    }
+
+    fn render(&self, result: &VerificationResult) -> Result<String> {
+        let results: Vec<serde_json::Value> = all_violations(result)
+            .into_iter()
+            .map(|violation| {
+                let mut region = serde_json::Map::new();
+                if let Some(line) = violation.location.line {
+                    region.insert("startLine".to_string(), json!(line));
+                }
+                if let Some(column) = violation.location.column {
+                    region.insert("startColumn".to_string(), json!(column));
+                }
+
+                let level = match violation.severity {
+                    Severity::Critical | Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Info => "note",
+                };
+
+                let mut sarif_result = json!({
+                    "ruleId": violation.rule,
+                    "level": level,
+                    "message": { "text": violation.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": violation.location.file.to_string_lossy().replace('\\', "/")
+                            },
+                            "region": region
+                        }
+                    }]
+                });
+                if let Some(link) = result.violation_link(violation) {
+                    sarif_result["hostedViewerUri"] = json!(link);
+                }
+                sarif_result
+            })
+            .collect();
+
+        let mut sarif = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "ferris-proof",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://ferris-proof.dev"
+                    }
+                },
+                "results": results
+            }]
+        });
+        if let Some(seed) = result.seed {
+            sarif["runs"][0]["properties"]["seed"] = json!(seed);
+        }
+        if let Some(budget) = &result.budget {
+            sarif["runs"][0]["properties"]["budget"] = serde_json::to_value(budget)?;
+        }
+        if let Some(environment) = &result.environment {
+            sarif["runs"][0]["properties"]["environment"] = serde_json::to_value(environment)?;
+        }
+
+        Ok(serde_json::to_string_pretty(&sarif)?)
+    }
+}
+
+/// Renderer backed by an external command declared in a plugin manifest
+///
+/// The verification result is written to the command's stdin as JSON and the
+/// command's stdout is used as the rendered report.
+pub struct ExternalRenderer {
+    manifest: RendererManifest,
+}
+
+impl ExternalRenderer {
+    pub fn new(manifest: RendererManifest) -> Self {
+        Self { manifest }
+    }
+}
+
+impl ReportRenderer for ExternalRenderer {
+    fn format(&self) -> &str {
+        &self.manifest.format
+    }
+
+    fn file_extension(&self) -> &str {
+        &self.manifest.extension
+    }
+
+    fn render(&self, result: &VerificationResult) -> Result<String> {
+        let input = serde_json::to_vec(result)?;
+
+        let mut child = Command::new(&self.manifest.command)
+            .args(&self.manifest.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to start renderer '{}' ({:?}): {}",
+                    self.manifest.format,
+                    self.manifest.command,
+                    e
+                )
+            })?;
+
+        // Fed from its own thread, so a renderer that writes output before it
+        // has read all of its input cannot block on a full pipe
+        let writer = child
+            .stdin
+            .take()
+            .map(|mut stdin| std::thread::spawn(move || stdin.write_all(&input)));
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Renderer '{}' failed: {}",
+                self.manifest.format,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        if let Some(writer) = writer {
+            writer.join().map_err(|_| {
+                anyhow!("Renderer '{}' input writer panicked", self.manifest.format)
+            })??;
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+fn sorted_layers(result: &VerificationResult) -> Vec<&LayerResult> {
+    let mut layers: Vec<&LayerResult> = result.layer_results.values().collect();
+    layers.sort_by_key(|l| format!("{:?}", l.layer));
+    layers
+}
+
+/// Layer status, marking passes that needed a tool rerun as flaky and
+/// saying why a skipped layer did not run
+fn layer_status(result: &VerificationResult, layer_result: &LayerResult) -> String {
+    if let Some(reason) = result
+        .skip_reasons
+        .get(&layer_result.layer)
+        .filter(|_| layer_result.status == Status::Skipped)
+    {
+        format!("Skipped ({})", reason)
+    } else if layer_result.is_flaky_pass() {
+        format!(
+            "{:?} (flaky pass, {} retries)",
+            layer_result.status,
+            layer_result.retries()
+        )
+    } else {
+        format!("{:?}", layer_result.status)
+    }
+}
+
+fn all_violations(result: &VerificationResult) -> Vec<&Violation> {
+    sorted_layers(result)
+        .into_iter()
+        .flat_map(|l| l.violations.iter())
+        .collect()
+}
+
+fn format_location(location: &Location) -> String {
+    match (location.line, location.column) {
+        (Some(line), Some(column)) => {
+            format!("{}:{}:{}", location.file.display(), line, column)
+        }
+        (Some(line), None) => format!("{}:{}", location.file.display(), line),
+        _ => location.file.display().to_string(),
+    }
+}
+
+fn change_label(change: ChangeKind) -> &'static str {
+    match change {
+        ChangeKind::Added => "Added",
+        ChangeKind::Removed => "Removed",
+        ChangeKind::Modified => "Modified",
+    }
+}
+
+fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    fn sample_result() -> VerificationResult {
+        let mut layer_results = HashMap::new();
+        layer_results.insert(
+            Layer::PropertyBased,
+            LayerResult {
+                layer: Layer::PropertyBased,
+                status: Status::Error,
+                violations: vec![Violation {
+                    id: "v1".to_string(),
+                    severity: Severity::Error,
+                    location: Location {
+                        file: PathBuf::from("src/lib.rs"),
+                        line: Some(12),
+                        column: Some(5),
+                        span: None,
+                    },
+                    message: "property <roundtrip> failed".to_string(),
+                    suggestion: None,
+                    rule: "proptest_verification".to_string(),
+                }],
+                execution_time: Duration::from_millis(250),
+                tool_outputs: vec![],
+            },
+        );
+
+        VerificationResult {
+            overall_status: Status::Error,
+            layer_results,
+            metrics: VerificationMetrics {
+                total_time: Duration::from_millis(250),
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+            artifacts: vec![],
+            timestamp: chrono::Utc::now(),
+            skipped_targets: vec![],
+            gated_layers: vec![],
+            unavailable_layers: vec![],
+            skip_reasons: HashMap::new(),
+            spec_changes: vec![],
+            traceability: None,
+            budget: None,
+            seed: None,
+            environment: None,
+            target_statuses: BTreeMap::new(),
+            interrupted: false,
+            project_root: None,
+            link_template: None,
+            plugin_breakdowns: Vec::new(),
+            completed: Vec::new(),
+            api_surface: None,
+        }
+    }
+
+    #[test]
+    fn test_builtin_formats_registered() {
+        let registry = RendererRegistry::new();
+        assert_eq!(
+            registry.formats(),
+            vec!["html", "json", "markdown", "sarif"]
+        );
+        assert!(registry.get("SARIF").is_some());
+    }
+
+    #[test]
+    fn test_sarif_contains_violation() {
+        let rendered = SarifRenderer.render(&sample_result()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+
+        let result = &value["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "proptest_verification");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            12
+        );
+    }
+
+    #[test]
+    fn test_markdown_includes_spec_changes() {
+        use crate::spec_diff::{SpecChange, SpecDiff, SpecElementKind};
+
+        let mut result = sample_result();
+        result.spec_changes.push(SpecDiff {
+            path: PathBuf::from("specs/Queue.tla"),
+            changes: vec![SpecChange {
+                change: ChangeKind::Added,
+                kind: SpecElementKind::Invariant,
+                name: "BoundedInv".to_string(),
+            }],
+        });
+
+        let rendered = MarkdownRenderer.render(&result).unwrap();
+        assert!(rendered.contains("### `specs/Queue.tla`"));
+        assert!(rendered.contains("- Added invariant `BoundedInv`"));
+    }
+
+    #[test]
+    fn test_markdown_lists_gated_layers() {
+        let mut result = sample_result();
+        result.gated_layers.push(GatedLayer {
+            target: "src/lib.rs".to_string(),
+            layer: Layer::Formal,
+            required: Layer::PropertyBased,
+        });
+
+        result.seed = Some(1234);
+
+        result.layer_results.insert(
+            Layer::Formal,
+            LayerResult {
+                layer: Layer::Formal,
+                status: Status::Skipped,
+                violations: vec![],
+                execution_time: Duration::ZERO,
+                tool_outputs: vec![],
+            },
+        );
+        result.skip_reasons.insert(Layer::Formal, SkipReason::Gated);
+        result.target_statuses = BTreeMap::from([
+            ("src/lib.rs".to_string(), Status::Error),
+            ("src/queue.rs".to_string(), Status::Cached),
+            ("src/pool.rs".to_string(), Status::Skipped),
+        ]);
+        result.seed = Some(1234);
+
+        let rendered = MarkdownRenderer.render(&result).unwrap();
+        assert!(rendered.contains("- **Seed:** `1234`"));
+        assert!(rendered.contains("- **Targets:** 1 verified, 1 cached, 1 skipped"));
+        assert!(rendered.contains("| Formal | Skipped (gated) | 0 | 0.00s |"));
+        assert!(rendered.contains("## Gated Layers"));
+        assert!(rendered.contains("| `src/lib.rs` | Formal | skipped (gated on PropertyBased) |"));
+    }
+
+    #[test]
+    fn test_html_escapes_messages() {
+        let rendered = HtmlRenderer.render(&sample_result()).unwrap();
+        assert!(rendered.contains("property &lt;roundtrip&gt; failed"));
+    }
+
+    #[test]
+    fn test_html_includes_traceability_matrix() {
+        use crate::traceability::{TraceLink, TraceMatrix};
+
+        let mut result = sample_result();
+        let mut matrix = TraceMatrix::default();
+        matrix.spec_actions.insert(
+            PathBuf::from("specs/Queue.tla"),
+            vec!["Dequeue".to_string(), "Enqueue".to_string()],
+        );
+        matrix.links.push(TraceLink {
+            spec: PathBuf::from("specs/Queue.tla"),
+            action: "Enqueue".to_string(),
+            item: "Queue::push".to_string(),
+            file: PathBuf::from("src/queue.rs"),
+            line: 10,
+        });
+        result.traceability = Some(matrix);
+
+        let rendered = HtmlRenderer.render(&result).unwrap();
+        assert!(rendered.contains("<h2>Traceability</h2>"));
+        assert!(rendered.contains("<code>Queue::push</code> (src/queue.rs:10)"));
+        assert!(rendered.contains("<code>Dequeue</code></td><td><em>not implemented</em>"));
+    }
+
+    #[test]
+    fn test_reproducible_json_is_stable() {
+        let mut first = sample_result();
+        let mut second = sample_result();
+        second.timestamp = first.timestamp + chrono::Duration::seconds(90);
+        first.make_reproducible(std::path::Path::new("/work"));
+        second.make_reproducible(std::path::Path::new("/work"));
+
+        assert_eq!(first.timestamp, chrono::DateTime::<chrono::Utc>::UNIX_EPOCH);
+        assert_eq!(
+            JsonRenderer.render(&first).unwrap(),
+            JsonRenderer.render(&second).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_locations_relative_to_recorded_root() {
+        let mut result = sample_result();
+        let layer = result.layer_results.get_mut(&Layer::PropertyBased).unwrap();
+        layer.violations[0].location.file = PathBuf::from("/work/src/lib.rs");
+        result.relativize(std::path::Path::new("/work"));
+
+        let layer = &result.layer_results[&Layer::PropertyBased];
+        assert_eq!(
+            layer.violations[0].location.file,
+            PathBuf::from("src/lib.rs")
+        );
+        let rendered = MarkdownRenderer.render(&result).unwrap();
+        assert!(rendered.contains("- **Project root:** `/work`"));
+        assert!(!rendered.contains("/work/src/lib.rs"));
+    }
+
+    #[test]
+    fn test_violation_deep_links() {
+        let mut result = sample_result();
+        let template = crate::links::LinkTemplate::parse(
+            "https://github.com/org/repo/blob/{commit}/{path}#L{line}",
+        )
+        .unwrap();
+        let root = std::path::Path::new("/work");
+
+        // Without a recorded commit the link could point at the wrong lines
+        result.relativize(root);
+        result.link_template = Some(template);
+        assert!(!MarkdownRenderer
+            .render(&result)
+            .unwrap()
+            .contains("](https"));
+
+        let mut environment = crate::environment::EnvironmentSnapshot::capture(root, None);
+        environment.git_commit = Some("abc123".to_string());
+        result.environment = Some(environment);
+        let link = "https://github.com/org/repo/blob/abc123/src/lib.rs#L12";
+
+        let markdown = MarkdownRenderer.render(&result).unwrap();
+        assert!(markdown.contains(&format!("at [`src/lib.rs:12:5`]({})", link)));
+        let html = HtmlRenderer.render(&result).unwrap();
+        assert!(html.contains(&format!(
+            "<a href=\"{}\"><code>src/lib.rs:12:5</code></a>",
+            link
+        )));
+        let json: serde_json::Value =
+            serde_json::from_str(&JsonRenderer.render(&result).unwrap()).unwrap();
+        assert_eq!(
+            json["layer_results"]["PropertyBased"]["violations"][0]["link"],
+            link
+        );
+        let sarif: serde_json::Value =
+            serde_json::from_str(&SarifRenderer.render(&result).unwrap()).unwrap();
+        assert_eq!(sarif["runs"][0]["results"][0]["hostedViewerUri"], link);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_external_renderer_streams_large_results() {
+        let mut result = sample_result();
+        let layer = result.layer_results.get_mut(&Layer::PropertyBased).unwrap();
+        let violation = layer.violations[0].clone();
+        layer.violations = vec![violation; 5000];
+
+        // `cat` echoes its input as it reads, filling its stdout pipe long
+        // before a result this size is written
+        let renderer = ExternalRenderer::new(RendererManifest {
+            format: "echo".to_string(),
+            extension: "json".to_string(),
+            command: PathBuf::from("cat"),
+            args: Vec::new(),
+        });
+        let rendered = renderer.render(&result).unwrap();
+        assert_eq!(rendered, serde_json::to_string(&result).unwrap());
+    }
+
+    #[test]
+    fn test_unknown_format_lists_available() {
+        let registry = RendererRegistry::new();
+        let err = registry.render("jira", &sample_result()).unwrap_err();
+        assert!(err.to_string().contains("markdown"));
+    }
+}
@@ -0,0 +1,473 @@
+//! Retention of generated artifacts
+//!
+//! Spilled tool outputs, reports, TLC `states/` directories and proptest
+//! regression files accumulate with every run. `[artifacts] retention_days`
+//! and `max_total_size` bound them; anything the baseline or a signed
+//! attestation refers to is pinned and never pruned. Files under version
+//! control belong to the project, e.g. committed regression seeds, and are
+//! never considered artifacts.
+
+use crate::attestation::AttestationLog;
+use crate::baseline::Baseline;
+use crate::discovery::files_with_extension;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// What produced a stored artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StoredArtifactKind {
+    /// Tool output spilled to `<cache>/artifacts/`
+    ToolOutput,
+    /// Report under `.ferris-proof/`, e.g. a partial report of an interrupted run
+    Report,
+    /// One TLC run below a `states/` directory
+    TlcStates,
+    /// Seed file under `proptest-regressions/`
+    Regression,
+}
+
+impl std::fmt::Display for StoredArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            StoredArtifactKind::ToolOutput => "tool output",
+            StoredArtifactKind::Report => "report",
+            StoredArtifactKind::TlcStates => "TLC states",
+            StoredArtifactKind::Regression => "regression",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A file or directory holding one artifact
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredArtifact {
+    pub path: PathBuf,
+    pub kind: StoredArtifactKind,
+    /// Total size in bytes, including everything below a directory
+    pub size: u64,
+    pub modified: DateTime<Utc>,
+    /// Referenced by the baseline or an attestation
+    pub pinned: bool,
+}
+
+/// Limits applied by [`RetentionPolicy::plan`]; unset limits are not enforced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub retention_days: Option<u32>,
+    pub max_total_size: Option<u64>,
+}
+
+/// Artifacts a policy removes, and what remains
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrunePlan {
+    pub remove: Vec<StoredArtifact>,
+    pub kept: usize,
+    pub kept_bytes: u64,
+    /// Pinned artifacts among those kept
+    pub pinned: usize,
+}
+
+impl PrunePlan {
+    pub fn freed_bytes(&self) -> u64 {
+        self.remove.iter().map(|a| a.size).sum()
+    }
+
+    /// Delete every artifact in the plan, returning how many were removed
+    ///
+    /// Failures are logged and the artifact is skipped.
+    pub fn execute(&self) -> usize {
+        let mut removed = 0;
+        for artifact in &self.remove {
+            let outcome = if artifact.path.is_dir() {
+                std::fs::remove_dir_all(&artifact.path)
+            } else {
+                std::fs::remove_file(&artifact.path)
+            };
+            match outcome {
+                Ok(()) => removed += 1,
+                Err(e) => warn!("Failed to prune {:?}: {}", artifact.path, e),
+            }
+        }
+        debug!("Pruned {} of {} artifact(s)", removed, self.remove.len());
+        removed
+    }
+}
+
+impl RetentionPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.retention_days.is_none() && self.max_total_size.is_none()
+    }
+
+    /// Unpinned artifacts older than `retention_days`, then the oldest
+    /// unpinned ones until the rest fit in `max_total_size`
+    pub fn plan(&self, mut artifacts: Vec<StoredArtifact>, now: DateTime<Utc>) -> PrunePlan {
+        artifacts.sort_by(|a, b| a.modified.cmp(&b.modified).then(a.path.cmp(&b.path)));
+
+        let cutoff = self
+            .retention_days
+            .map(|days| now - chrono::Duration::days(i64::from(days)));
+        let (mut remove, mut keep): (Vec<_>, Vec<_>) = artifacts
+            .into_iter()
+            .partition(|a| !a.pinned && cutoff.is_some_and(|cutoff| a.modified < cutoff));
+
+        if let Some(max_total_size) = self.max_total_size {
+            let mut total: u64 = keep.iter().map(|a| a.size).sum();
+            let mut index = 0;
+            while total > max_total_size && index < keep.len() {
+                if keep[index].pinned {
+                    index += 1;
+                    continue;
+                }
+                let artifact = keep.remove(index);
+                total -= artifact.size;
+                remove.push(artifact);
+            }
+        }
+
+        PrunePlan {
+            kept: keep.len(),
+            kept_bytes: keep.iter().map(|a| a.size).sum(),
+            pinned: keep.iter().filter(|a| a.pinned).count(),
+            remove,
+        }
+    }
+}
+
+/// Artifact ids and paths that must survive pruning
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Pins {
+    /// Ids of spilled tool outputs
+    pub ids: BTreeSet<String>,
+    /// Paths relative to the project root
+    pub paths: BTreeSet<PathBuf>,
+}
+
+impl Pins {
+    /// Files of baselined violations, and tool outputs and artifacts of
+    /// every attested report
+    pub fn for_project(project_root: &Path) -> Result<Self> {
+        let mut pins = Self::default();
+        let baseline = Baseline::load(&Baseline::path_for_project(project_root))?;
+        pins.paths
+            .extend(baseline.entries.into_iter().map(|entry| entry.file));
+
+        for attestation in AttestationLog::for_project(project_root).load()? {
+            match serde_json::from_str(&attestation.payload.report) {
+                Ok(report) => pins.add_report(&report),
+                Err(e) => warn!(
+                    "Not pinning artifacts of an unparsable attested report: {}",
+                    e
+                ),
+            }
+        }
+        Ok(pins)
+    }
+
+    /// Pin every `artifact_id` and artifact `path` in a JSON report
+    pub fn add_report(&mut self, value: &serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                if let Some(id) = map.get("artifact_id").and_then(|id| id.as_str()) {
+                    self.ids.insert(id.to_string());
+                }
+                if let Some(artifacts) = map.get("artifacts").and_then(|a| a.as_array()) {
+                    self.paths.extend(
+                        artifacts
+                            .iter()
+                            .filter_map(|a| a.get("path").and_then(|p| p.as_str()))
+                            .map(PathBuf::from),
+                    );
+                }
+                map.values().for_each(|v| self.add_report(v));
+            }
+            serde_json::Value::Array(values) => values.iter().for_each(|v| self.add_report(v)),
+            _ => {}
+        }
+    }
+
+    fn pins(&self, project_root: &Path, artifact: &StoredArtifact) -> bool {
+        if artifact.kind == StoredArtifactKind::ToolOutput {
+            let id = artifact
+                .path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.split('.').next());
+            return id.is_some_and(|id| self.ids.contains(id));
+        }
+        let relative = artifact
+            .path
+            .strip_prefix(project_root)
+            .unwrap_or(&artifact.path);
+        self.paths
+            .iter()
+            .any(|pinned| pinned.starts_with(relative) || pinned == &artifact.path)
+    }
+}
+
+/// Every prunable artifact of a project and its cache directory
+///
+/// Artifacts holding a file git tracks are left out.
+pub fn inventory(
+    project_root: &Path,
+    cache_dir: &Path,
+    pins: &Pins,
+) -> Result<Vec<StoredArtifact>> {
+    let mut found = Vec::new();
+    for path in files_with_extension(&cache_dir.join("artifacts"), &["zst"]) {
+        found.push((path, StoredArtifactKind::ToolOutput));
+    }
+
+    let state_dir = project_root.join(".ferris-proof");
+    if let Ok(entries) = std::fs::read_dir(&state_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.path().is_file() && name.ends_with("report.json") {
+                found.push((entry.path(), StoredArtifactKind::Report));
+            }
+        }
+    }
+    for path in files_with_extension(
+        &state_dir.join("reports"),
+        &["json", "html", "md", "sarif", "xml"],
+    ) {
+        found.push((path, StoredArtifactKind::Report));
+    }
+
+    for states in states_dirs(&project_root.join("specs")) {
+        let Ok(entries) = std::fs::read_dir(&states) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            found.push((entry.path(), StoredArtifactKind::TlcStates));
+        }
+    }
+
+    for path in files_with_extension(&project_root.join("proptest-regressions"), &["txt"]) {
+        found.push((path, StoredArtifactKind::Regression));
+    }
+
+    let tracked = git_tracked(project_root);
+    found.retain(|(path, _)| {
+        let Ok(relative) = path.strip_prefix(project_root) else {
+            return true;
+        };
+        let is_tracked = tracked.iter().any(|file| file.starts_with(relative));
+        if is_tracked {
+            debug!("Not pruning {:?}: tracked by git", path);
+        }
+        !is_tracked
+    });
+
+    let mut artifacts = Vec::with_capacity(found.len());
+    for (path, kind) in found {
+        let metadata =
+            std::fs::metadata(&path).with_context(|| format!("Failed to stat {:?}", path))?;
+        let mut artifact = StoredArtifact {
+            size: if metadata.is_dir() {
+                dir_size(&path)
+            } else {
+                metadata.len()
+            },
+            modified: metadata
+                .modified()
+                .map(DateTime::from)
+                .unwrap_or_else(|_| Utc::now()),
+            path,
+            kind,
+            pinned: false,
+        };
+        artifact.pinned = pins.pins(project_root, &artifact);
+        artifacts.push(artifact);
+    }
+    Ok(artifacts)
+}
+
+/// Files git tracks below `project_root`, relative to it; empty outside a repository
+fn git_tracked(project_root: &Path) -> BTreeSet<PathBuf> {
+    let output = std::process::Command::new("git")
+        .args(["ls-files", "-z"])
+        .current_dir(project_root)
+        .output();
+    match output {
+        Ok(output) if output.status.success() => output
+            .stdout
+            .split(|&b| b == 0)
+            .filter(|path| !path.is_empty())
+            .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+            .collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+/// `states/` directories TLC left next to specs below `dir`
+fn states_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs = Vec::new();
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().is_some_and(|name| name == "states") {
+            dirs.push(path);
+        } else {
+            dirs.extend(states_dirs(&path));
+        }
+    }
+    dirs.sort();
+    dirs
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn artifact(name: &str, days_old: i64, size: u64, pinned: bool) -> StoredArtifact {
+        StoredArtifact {
+            path: PathBuf::from(name),
+            kind: StoredArtifactKind::ToolOutput,
+            size,
+            modified: Utc::now() - chrono::Duration::days(days_old),
+            pinned,
+        }
+    }
+
+    #[test]
+    fn test_plan_respects_age_size_and_pins() {
+        let artifacts = vec![
+            artifact("old", 40, 10, false),
+            artifact("old-pinned", 50, 10, true),
+            artifact("older-big", 20, 100, false),
+            artifact("recent", 1, 60, false),
+        ];
+
+        let by_age = RetentionPolicy {
+            retention_days: Some(30),
+            max_total_size: None,
+        }
+        .plan(artifacts.clone(), Utc::now());
+        let removed: Vec<_> = by_age
+            .remove
+            .iter()
+            .map(|a| a.path.to_str().unwrap())
+            .collect();
+        assert_eq!(removed, vec!["old"]);
+        assert_eq!(by_age.pinned, 1);
+
+        let by_size = RetentionPolicy {
+            retention_days: Some(30),
+            max_total_size: Some(100),
+        }
+        .plan(artifacts, Utc::now());
+        let removed: Vec<_> = by_size
+            .remove
+            .iter()
+            .map(|a| a.path.to_str().unwrap())
+            .collect();
+        assert_eq!(removed, vec!["old", "older-big"]);
+        assert_eq!(by_size.kept_bytes, 70);
+        assert_eq!(by_size.freed_bytes(), 110);
+    }
+
+    #[test]
+    fn test_inventory_pins_attested_outputs_and_baselined_files() {
+        let project = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        let spilled = cache.path().join("artifacts");
+        std::fs::create_dir_all(&spilled).unwrap();
+        for name in [
+            "abc123.stdout.zst",
+            "abc123.stderr.zst",
+            "def456.stdout.zst",
+        ] {
+            std::fs::write(spilled.join(name), "x").unwrap();
+        }
+        let regressions = project.path().join("proptest-regressions");
+        std::fs::create_dir_all(&regressions).unwrap();
+        std::fs::write(regressions.join("a.txt"), "cc 1").unwrap();
+        std::fs::write(regressions.join("b.txt"), "cc 2").unwrap();
+        let run = project.path().join("specs/queue/states/24-01-01-00-00-00");
+        std::fs::create_dir_all(&run).unwrap();
+        std::fs::write(run.join("MC.st"), "12345").unwrap();
+
+        let mut pins = Pins::default();
+        pins.add_report(&serde_json::json!({
+            "layer_results": { "Formal": { "tool_outputs": [{ "artifact_id": "abc123" }] } },
+            "artifacts": [{ "path": "proptest-regressions/a.txt" }]
+        }));
+
+        let artifacts = inventory(project.path(), cache.path(), &pins).unwrap();
+        let pinned: Vec<String> = artifacts
+            .iter()
+            .filter(|a| a.pinned)
+            .map(|a| a.path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(artifacts.len(), 6);
+        assert_eq!(
+            pinned,
+            vec!["abc123.stderr.zst", "abc123.stdout.zst", "a.txt"]
+        );
+
+        let states = artifacts
+            .iter()
+            .find(|a| a.kind == StoredArtifactKind::TlcStates)
+            .unwrap();
+        assert_eq!(states.size, 5);
+    }
+
+    #[test]
+    fn test_prune_keeps_tracked_regressions() {
+        let project = TempDir::new().unwrap();
+        let cache = TempDir::new().unwrap();
+        let regressions = project.path().join("proptest-regressions");
+        std::fs::create_dir_all(&regressions).unwrap();
+        std::fs::write(regressions.join("committed.txt"), "cc 1").unwrap();
+        std::fs::write(regressions.join("local.txt"), "cc 2").unwrap();
+        // A `states` directory of the project's own is not TLC output
+        std::fs::create_dir_all(project.path().join("states/idle")).unwrap();
+
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(project.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "--quiet"]);
+        git(&["add", "proptest-regressions/committed.txt"]);
+
+        let artifacts = inventory(project.path(), cache.path(), &Pins::default()).unwrap();
+        let plan = RetentionPolicy {
+            retention_days: Some(0),
+            max_total_size: Some(0),
+        }
+        .plan(artifacts, Utc::now() + chrono::Duration::days(1));
+        assert_eq!(plan.remove.len(), 1);
+        assert_eq!(plan.execute(), 1);
+
+        assert!(regressions.join("committed.txt").exists());
+        assert!(!regressions.join("local.txt").exists());
+        assert!(project.path().join("states/idle").exists());
+    }
+}
@@ -0,0 +1,53 @@
+use uuid::Uuid;
+
+/// Environment variable proptest reads its RNG seed from
+pub const PROPTEST_SEED_ENV: &str = "PROPTEST_RNG_SEED";
+
+/// Seed for runs without `--seed`; printed so a failing run can be repeated
+pub fn random_seed() -> u64 {
+    Uuid::new_v4().as_u64_pair().0
+}
+
+/// Shuffle `items` in an order determined only by `seed`
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut rng = SplitMix64(seed);
+    for i in (1..items.len()).rev() {
+        let j = (rng.next() % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// Small, stable generator so orders stay reproducible across releases
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffle_is_determined_by_seed() {
+        let mut first: Vec<u32> = (0..32).collect();
+        let mut second = first.clone();
+        shuffle(&mut first, 42);
+        shuffle(&mut second, 42);
+        assert_eq!(first, second);
+
+        let mut sorted = first.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..32).collect::<Vec<_>>());
+
+        let mut other: Vec<u32> = (0..32).collect();
+        shuffle(&mut other, 43);
+        assert_ne!(first, other);
+    }
+}
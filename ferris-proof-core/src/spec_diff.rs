@@ -0,0 +1,478 @@
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Kind of named element declared in a TLA+ or Alloy specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpecElementKind {
+    Variable,
+    Constant,
+    /// TLA+ definitions mentioning primed variables, Alloy predicates
+    Action,
+    /// TLA+ definitions named like invariants, Alloy facts and assertions
+    Invariant,
+    /// Alloy signatures
+    Signature,
+    /// Any other operator or function definition
+    Operator,
+}
+
+impl SpecElementKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            SpecElementKind::Variable => "variable",
+            SpecElementKind::Constant => "constant",
+            SpecElementKind::Action => "action",
+            SpecElementKind::Invariant => "invariant",
+            SpecElementKind::Signature => "signature",
+            SpecElementKind::Operator => "operator",
+        }
+    }
+
+    /// Constants, variables, signatures and plain definitions, as opposed to
+    /// actions and invariants
+    pub fn is_data(self) -> bool {
+        !matches!(self, SpecElementKind::Action | SpecElementKind::Invariant)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SpecElement {
+    kind: SpecElementKind,
+    /// Definition body with comments removed and whitespace collapsed
+    body: String,
+}
+
+/// Named elements of a specification, keyed by name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpecOutline {
+    elements: BTreeMap<String, SpecElement>,
+}
+
+impl SpecOutline {
+    /// Outline a specification, choosing the syntax from the file extension
+    pub fn parse(path: &Path, content: &str) -> Option<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("tla") => Some(Self::parse_tla(content)),
+            Some("als") => Some(Self::parse_alloy(content)),
+            _ => None,
+        }
+    }
+
+    pub fn parse_tla(content: &str) -> Self {
+        let content = strip_tla_comments(content);
+        let declaration = Regex::new(r"^(VARIABLES?|CONSTANTS?)\s+(.*)$").unwrap();
+        let definition =
+            Regex::new(r"^([A-Za-z_][A-Za-z0-9_]*)\s*(?:\([^)]*\))?\s*==(.*)$").unwrap();
+        let invariant_name = Regex::new(r"(?i)(inv|typeok|safety|correct)").unwrap();
+        let primed = Regex::new(r"[A-Za-z0-9_)]'|\bUNCHANGED\b").unwrap();
+
+        let mut outline = Self::default();
+        let mut lines = content.lines().peekable();
+
+        while let Some(line) = lines.next() {
+            if line.starts_with("====") {
+                break;
+            }
+
+            if let Some(captures) = declaration.captures(line) {
+                let kind = if captures[1].starts_with("VARIABLE") {
+                    SpecElementKind::Variable
+                } else {
+                    SpecElementKind::Constant
+                };
+                // Declarations may continue on indented lines
+                let mut names = captures[2].to_string();
+                while let Some(next) = lines.next_if(|l| l.starts_with(char::is_whitespace)) {
+                    names.push(',');
+                    names.push_str(next);
+                }
+                for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                    let name = name.split('(').next().unwrap_or(name).trim();
+                    outline.insert(name, kind, String::new());
+                }
+                continue;
+            }
+
+            if let Some(captures) = definition.captures(line) {
+                let mut body = captures[2].to_string();
+                while let Some(next) =
+                    lines.next_if(|l| l.is_empty() || l.starts_with(char::is_whitespace))
+                {
+                    body.push(' ');
+                    body.push_str(next);
+                }
+
+                let name = &captures[1];
+                let kind = if invariant_name.is_match(name) {
+                    SpecElementKind::Invariant
+                } else if primed.is_match(&body) {
+                    SpecElementKind::Action
+                } else {
+                    SpecElementKind::Operator
+                };
+                outline.insert(name, kind, body);
+            }
+        }
+
+        outline
+    }
+
+    pub fn parse_alloy(content: &str) -> Self {
+        let content = strip_alloy_comments(content);
+        let item = Regex::new(
+            r"(?m)^\s*(?:(?:abstract|one|lone|some|private)\s+)*(sig|fact|pred|fun|assert)\s+([A-Za-z_][\w,\s]*?)\s*[\[{(:]",
+        )
+        .unwrap();
+
+        let mut outline = Self::default();
+        let starts: Vec<_> = item.captures_iter(&content).collect();
+        for (idx, captures) in starts.iter().enumerate() {
+            let start = captures.get(0).map_or(0, |m| m.end());
+            let end = starts
+                .get(idx + 1)
+                .and_then(|next| next.get(0))
+                .map_or(content.len(), |m| m.start());
+            let body = content[start..end].to_string();
+
+            let kind = match &captures[1] {
+                "sig" => SpecElementKind::Signature,
+                "pred" => SpecElementKind::Action,
+                "fact" | "assert" => SpecElementKind::Invariant,
+                _ => SpecElementKind::Operator,
+            };
+            // `sig A, B extends C` declares several signatures at once
+            for name in captures[2].split(',').map(str::trim) {
+                let name = name.split_whitespace().next().unwrap_or(name);
+                outline.insert(name, kind, body.clone());
+            }
+        }
+
+        outline
+    }
+
+    /// Names and kinds of all declared elements, sorted by name
+    pub fn elements(&self) -> impl Iterator<Item = (&str, SpecElementKind)> {
+        self.elements
+            .iter()
+            .map(|(name, element)| (name.as_str(), element.kind))
+    }
+
+    /// Normalized definition text of an element
+    pub fn body(&self, name: &str) -> Option<&str> {
+        self.elements.get(name).map(|element| element.body.as_str())
+    }
+
+    fn insert(&mut self, name: &str, kind: SpecElementKind, body: String) {
+        if name.is_empty() {
+            return;
+        }
+        self.elements.insert(
+            name.to_string(),
+            SpecElement {
+                kind,
+                body: body.split_whitespace().collect::<Vec<_>>().join(" "),
+            },
+        );
+    }
+}
+
+/// How a named element changed between two versions of a spec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecChange {
+    pub change: ChangeKind,
+    pub kind: SpecElementKind,
+    pub name: String,
+}
+
+/// Semantic changes to one specification file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecDiff {
+    pub path: PathBuf,
+    pub changes: Vec<SpecChange>,
+}
+
+impl SpecDiff {
+    pub fn between(path: PathBuf, old: &SpecOutline, new: &SpecOutline) -> Self {
+        let names: BTreeSet<&String> = old.elements.keys().chain(new.elements.keys()).collect();
+
+        let mut changes: Vec<SpecChange> = names
+            .into_iter()
+            .filter_map(|name| {
+                let (change, kind) = match (old.elements.get(name), new.elements.get(name)) {
+                    (None, Some(new)) => (ChangeKind::Added, new.kind),
+                    (Some(old), None) => (ChangeKind::Removed, old.kind),
+                    (Some(old), Some(new)) if old != new => (ChangeKind::Modified, new.kind),
+                    _ => return None,
+                };
+                Some(SpecChange {
+                    change,
+                    kind,
+                    name: name.clone(),
+                })
+            })
+            .collect();
+        changes.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+
+        Self { path, changes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Semantic diffs of every TLA+/Alloy spec changed since `rev`
+///
+/// Compares the spec at `rev` with the working tree; specs added or deleted
+/// since then diff against an empty outline.
+pub fn diff_against_rev(project_root: &Path, rev: &str) -> Result<Vec<SpecDiff>> {
+    let mut paths: BTreeSet<PathBuf> = git_spec_paths(project_root, rev)?.into_iter().collect();
+    paths.extend(
+        find_specs(project_root)
+            .into_iter()
+            .filter_map(|p| p.strip_prefix(project_root).ok().map(Path::to_path_buf)),
+    );
+
+    let mut diffs = Vec::new();
+    for path in paths {
+        let old = git_show(project_root, rev, &path)?
+            .and_then(|content| SpecOutline::parse(&path, &content))
+            .unwrap_or_default();
+        let new = std::fs::read_to_string(project_root.join(&path))
+            .ok()
+            .and_then(|content| SpecOutline::parse(&path, &content))
+            .unwrap_or_default();
+
+        let diff = SpecDiff::between(path, &old, &new);
+        if !diff.is_empty() {
+            diffs.push(diff);
+        }
+    }
+
+    Ok(diffs)
+}
+
+fn is_spec(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("tla") | Some("als")
+    )
+}
+
+/// Specs below `root`, skipping hidden directories and target/
+pub(crate) fn find_specs(root: &Path) -> Vec<PathBuf> {
+    fn visit(dir: &Path, specs: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if path.is_dir() {
+                if !name.starts_with('.') && name != "target" {
+                    visit(&path, specs);
+                }
+            } else if is_spec(&path) {
+                specs.push(path);
+            }
+        }
+    }
+
+    let mut specs = Vec::new();
+    visit(root, &mut specs);
+    specs
+}
+
+fn git_spec_paths(project_root: &Path, rev: &str) -> Result<Vec<PathBuf>> {
+    let output = Command::new("git")
+        .args(["ls-tree", "-r", "--name-only", "--full-tree", rev])
+        .current_dir(project_root)
+        .output()
+        .context("Failed to execute git")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Unknown git revision '{}': {}",
+            rev,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    // ls-tree paths are relative to the repository root
+    let prefix = git_prefix(project_root)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| Path::new(line).strip_prefix(&prefix).ok())
+        .filter(|path| is_spec(path))
+        .map(Path::to_path_buf)
+        .collect())
+}
+
+fn git_prefix(project_root: &Path) -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-prefix"])
+        .current_dir(project_root)
+        .output()
+        .context("Failed to execute git")?;
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    ))
+}
+
+/// Content of `path` (relative to `project_root`) at `rev`, if it existed
+fn git_show(project_root: &Path, rev: &str, path: &Path) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .arg("show")
+        .arg(format!(
+            "{}:./{}",
+            rev,
+            path.to_string_lossy().replace('\\', "/")
+        ))
+        .current_dir(project_root)
+        .output()
+        .context("Failed to execute git")?;
+
+    Ok(output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+/// Remove `\* line` and `(* block *)` comments, keeping line structure
+pub(crate) fn strip_tla_comments(content: &str) -> String {
+    let block = Regex::new(r"(?s)\(\*.*?\*\)").unwrap();
+    let without_blocks = block.replace_all(content, |caps: &regex::Captures| {
+        "\n".repeat(caps[0].matches('\n').count())
+    });
+    without_blocks
+        .lines()
+        .map(|line| line.split("\\*").next().unwrap_or(line).trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Remove `//`, `--` and `/* */` comments, keeping line structure
+pub(crate) fn strip_alloy_comments(content: &str) -> String {
+    let block = Regex::new(r"(?s)/\*.*?\*/").unwrap();
+    block
+        .replace_all(content, |caps: &regex::Captures| {
+            "\n".repeat(caps[0].matches('\n').count())
+        })
+        .lines()
+        .map(|line| {
+            let end = [line.find("//"), line.find("--")]
+                .into_iter()
+                .flatten()
+                .min()
+                .unwrap_or(line.len());
+            &line[..end]
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_TLA: &str = r#"---- MODULE Queue ----
+EXTENDS Naturals
+CONSTANT Capacity
+VARIABLES items, head
+
+TypeOK == items \in Nat
+
+Init == items = 0 /\ head = 0
+
+Enqueue == items < Capacity /\ items' = items + 1 /\ UNCHANGED head
+
+Size == items
+====
+"#;
+
+    const NEW_TLA: &str = r#"---- MODULE Queue ----
+EXTENDS Naturals
+CONSTANT Capacity
+VARIABLES items, head,
+          tail \* added for the ring buffer
+
+TypeOK == items \in Nat
+
+Init == items = 0 /\ head = 0 /\ tail = 0
+
+Enqueue == items < Capacity /\ items' = items + 1 /\ UNCHANGED head
+
+Dequeue == items > 0 /\ items' = items - 1 /\ UNCHANGED tail
+
+BoundedInv == items <= Capacity
+====
+"#;
+
+    #[test]
+    fn test_tla_semantic_diff() {
+        let diff = SpecDiff::between(
+            PathBuf::from("specs/Queue.tla"),
+            &SpecOutline::parse_tla(OLD_TLA),
+            &SpecOutline::parse_tla(NEW_TLA),
+        );
+
+        let summary: Vec<(ChangeKind, SpecElementKind, &str)> = diff
+            .changes
+            .iter()
+            .map(|c| (c.change, c.kind, c.name.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Added, SpecElementKind::Variable, "tail"),
+                (ChangeKind::Added, SpecElementKind::Action, "Dequeue"),
+                (ChangeKind::Added, SpecElementKind::Invariant, "BoundedInv"),
+                (ChangeKind::Modified, SpecElementKind::Operator, "Init"),
+                (ChangeKind::Removed, SpecElementKind::Operator, "Size"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alloy_outline() {
+        let outline = SpecOutline::parse_alloy(
+            r#"
+abstract sig Node { next: lone Node }
+one sig Head, Tail extends Node {}
+fact Acyclic { no n: Node | n in n.^next } // no cycles
+pred insert[n: Node] { n.next = Head }
+assert NoSelfLoop { all n: Node | n.next != n }
+"#,
+        );
+
+        let kinds: Vec<(&str, SpecElementKind)> = outline
+            .elements
+            .iter()
+            .map(|(name, element)| (name.as_str(), element.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Acyclic", SpecElementKind::Invariant),
+                ("Head", SpecElementKind::Signature),
+                ("NoSelfLoop", SpecElementKind::Invariant),
+                ("Node", SpecElementKind::Signature),
+                ("Tail", SpecElementKind::Signature),
+                ("insert", SpecElementKind::Action),
+            ]
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use crate::spec_diff::SpecOutline;
+use crate::traceability::{linked_item_hashes, TraceLink, TraceMatrix};
+use crate::types::*;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Violation id for a spec whose linked Rust definition changed underneath it
+pub const STALE_SPEC: &str = "FP-VR-003";
+
+/// Hashes of both sides of a data link when they were last in agreement
+///
+/// A declaration without a body, such as a TLA+ `CONSTANT`, is hashed as
+/// the whole spec and its model `.cfg`, since that is where its value lives.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DriftRecord {
+    pub rust_hash: String,
+    pub spec_hash: String,
+}
+
+/// Last agreed hashes of every link between a spec definition and a Rust
+/// definition, kept in `.ferris-proof/spec-drift.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DriftBaseline {
+    #[serde(skip)]
+    path: PathBuf,
+    pub links: BTreeMap<String, DriftRecord>,
+}
+
+impl DriftBaseline {
+    /// Load the project's baseline; a missing baseline is empty
+    pub fn for_project(project_root: &Path) -> Result<Self> {
+        let path = project_root.join(".ferris-proof").join("spec-drift.json");
+        let mut baseline: Self = if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&content)
+                .map_err(|e| anyhow!("Invalid spec drift baseline {:?}: {}", path, e))?
+        } else {
+            Self::default()
+        };
+        baseline.path = path;
+        Ok(baseline)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Links whose Rust definition changed while the spec element did not
+    ///
+    /// Stale links keep their old baseline, so they are reported until the
+    /// spec is edited or the drift is accepted. New links are recorded and
+    /// links that no longer exist are forgotten.
+    pub fn check<'m>(
+        &mut self,
+        project_root: &Path,
+        matrix: &'m TraceMatrix,
+    ) -> Result<Vec<&'m TraceLink>> {
+        let current = current_hashes(project_root, matrix)?;
+        let mut stale = Vec::new();
+        let mut links = BTreeMap::new();
+        for (key, (record, link)) in current {
+            let agreed = match self.links.remove(&key) {
+                Some(previous)
+                    if previous.spec_hash == record.spec_hash
+                        && previous.rust_hash != record.rust_hash =>
+                {
+                    stale.push(link);
+                    previous
+                }
+                _ => record,
+            };
+            links.insert(key, agreed);
+        }
+        self.links = links;
+        Ok(stale)
+    }
+
+    /// Record the current hashes of every data link, returning how many there are
+    pub fn accept(&mut self, project_root: &Path, matrix: &TraceMatrix) -> Result<usize> {
+        self.links = current_hashes(project_root, matrix)?
+            .into_iter()
+            .map(|(key, (record, _))| (key, record))
+            .collect();
+        Ok(self.links.len())
+    }
+}
+
+/// One violation per stale link, at the link's annotation
+pub fn stale_spec_violations(stale: &[&TraceLink], severity: Severity) -> Vec<Violation> {
+    stale
+        .iter()
+        .map(|link| Violation {
+            id: STALE_SPEC.to_string(),
+            severity: severity.clone(),
+            location: Location {
+                line: u32::try_from(link.line).ok().filter(|line| *line > 0),
+                ..Location::file(&link.file)
+            },
+            message: format!(
+                "Spec possibly stale: {} changed but {}#{} did not",
+                link.item,
+                link.spec.display(),
+                link.action
+            ),
+            suggestion: Some(
+                "Update the specification, or run `ferris-proof trace --accept-drift` if it still holds"
+                    .to_string(),
+            ),
+            rule: "spec_drift".to_string(),
+        })
+        .collect()
+}
+
+/// Current hashes of each data link, keyed by `spec#element@file::item`
+fn current_hashes<'m>(
+    project_root: &Path,
+    matrix: &'m TraceMatrix,
+) -> Result<BTreeMap<String, (DriftRecord, &'m TraceLink)>> {
+    let mut specs: HashMap<&Path, Option<(String, SpecOutline)>> = HashMap::new();
+    let mut items: HashMap<&Path, BTreeMap<String, String>> = HashMap::new();
+    let mut current = BTreeMap::new();
+
+    for link in matrix.links.iter().filter(|link| matrix.is_data_link(link)) {
+        let spec = specs.entry(link.spec.as_path()).or_insert_with(|| {
+            let path = project_root.join(&link.spec);
+            let mut content = std::fs::read_to_string(&path).ok()?;
+            let outline = SpecOutline::parse(&link.spec, &content)?;
+            if let Ok(model) = std::fs::read_to_string(path.with_extension("cfg")) {
+                content.push_str(&model);
+            }
+            Some((content, outline))
+        });
+        let Some((content, outline)) = spec.as_ref() else {
+            continue;
+        };
+        let Some(spec_body) = outline.body(&link.action) else {
+            continue;
+        };
+        let spec_body = if spec_body.is_empty() {
+            content.as_str()
+        } else {
+            spec_body
+        };
+
+        if !items.contains_key(link.file.as_path()) {
+            let path = project_root.join(&link.file);
+            let hashes = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| linked_item_hashes(&link.file, &content))
+                .unwrap_or_else(|e| {
+                    warn!("Skipping spec drift for {:?}: {}", path, e);
+                    BTreeMap::new()
+                });
+            items.insert(link.file.as_path(), hashes);
+        }
+        let Some(rust_hash) = items[link.file.as_path()].get(&link.item) else {
+            continue;
+        };
+
+        let key = format!(
+            "{}#{}@{}::{}",
+            link.spec.display(),
+            link.action,
+            link.file.display(),
+            link.item
+        );
+        let record = DriftRecord {
+            rust_hash: rust_hash.clone(),
+            spec_hash: blake3::hash(spec_body.as_bytes()).to_hex().to_string(),
+        };
+        current.insert(key, (record, link));
+    }
+
+    debug!("Spec drift: {} data link(s) hashed", current.len());
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SPEC: &str = "---- MODULE Queue ----\nMaxSize == 8\n====\n";
+
+    fn write(root: &Path, rust: &str, spec: &str) -> TraceMatrix {
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("specs")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), rust).unwrap();
+        std::fs::write(root.join("specs/queue.tla"), spec).unwrap();
+        TraceMatrix::build(root).unwrap()
+    }
+
+    #[test]
+    fn test_reports_rust_change_until_spec_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let linked = |size: u32| {
+            format!(
+                "#[verification(spec = \"specs/queue.tla#MaxSize\")]\npub const MAX_SIZE: usize = {};\n",
+                size
+            )
+        };
+
+        let matrix = write(root, &linked(8), SPEC);
+        assert!(matrix.dangling_links().is_empty());
+        let mut baseline = DriftBaseline::for_project(root).unwrap();
+        assert!(baseline.check(root, &matrix).unwrap().is_empty());
+        assert_eq!(baseline.links.len(), 1);
+        baseline.save().unwrap();
+
+        // Only the doc comment changed, which is not drift
+        let documented = format!("/// Queue capacity\n{}", linked(8));
+        let matrix = write(root, &documented, SPEC);
+        let mut baseline = DriftBaseline::for_project(root).unwrap();
+        assert!(baseline.check(root, &matrix).unwrap().is_empty());
+
+        let matrix = write(root, &linked(16), SPEC);
+        let stale = baseline.check(root, &matrix).unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].item, "MAX_SIZE");
+        let violations = stale_spec_violations(&stale, Severity::Error);
+        assert_eq!(violations[0].id, STALE_SPEC);
+        assert_eq!(violations[0].location.line, Some(1));
+        // Still stale on the next run
+        assert_eq!(baseline.check(root, &matrix).unwrap().len(), 1);
+
+        let matrix = write(root, &linked(16), &SPEC.replace("== 8", "== 16"));
+        assert!(baseline.check(root, &matrix).unwrap().is_empty());
+    }
+}
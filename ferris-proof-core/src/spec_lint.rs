@@ -0,0 +1,367 @@
+use crate::spec_diff::{
+    find_specs, strip_alloy_comments, strip_tla_comments, SpecElementKind, SpecOutline,
+};
+use crate::types::*;
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::path::Path;
+use tracing::warn;
+
+/// Rule family of every spec lint violation
+pub const RULE: &str = "spec-lint";
+
+pub const UNDEFINED_CFG_SYMBOL: &str = "SPEC_LINT_UNDEFINED_CFG_SYMBOL";
+pub const UNUSED_VARIABLE: &str = "SPEC_LINT_UNUSED_VARIABLE";
+pub const MISSING_DEFINITION: &str = "SPEC_LINT_MISSING_DEFINITION";
+pub const NON_ASCII: &str = "SPEC_LINT_NON_ASCII";
+pub const MODULE_NAME: &str = "SPEC_LINT_MODULE_NAME";
+
+/// TLC configuration keywords, each followed by the names it refers to
+const CFG_KEYWORDS: &[&str] = &[
+    "SPECIFICATION",
+    "INIT",
+    "NEXT",
+    "INVARIANT",
+    "INVARIANTS",
+    "PROPERTY",
+    "PROPERTIES",
+    "CONSTANT",
+    "CONSTANTS",
+    "CONSTRAINT",
+    "CONSTRAINTS",
+    "ACTION_CONSTRAINT",
+    "ACTION_CONSTRAINTS",
+    "SYMMETRY",
+    "VIEW",
+    "ALIAS",
+    "POSTCONDITION",
+    "CHECK_DEADLOCK",
+];
+
+/// Lint every TLA+ and Alloy spec below `project_root`
+///
+/// Runs without external tools, so it catches problems TLC or the Alloy
+/// analyzer would otherwise report only once installed. TLA+ specs are
+/// checked together with the `.cfg` model next to them.
+pub fn lint_project(project_root: &Path) -> Vec<Violation> {
+    let mut specs = find_specs(project_root);
+    specs.sort();
+
+    let mut violations = Vec::new();
+    for path in specs {
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Skipping spec lint for {:?}: {}", path, e);
+                continue;
+            }
+        };
+        let relative = path.strip_prefix(project_root).unwrap_or(&path);
+        let cfg_path = path.with_extension("cfg");
+        let cfg = std::fs::read_to_string(&cfg_path).ok();
+        violations.extend(lint_spec(relative, &content, cfg.as_deref()));
+    }
+    violations
+}
+
+/// Lint one spec; `cfg` is the TLC model of a TLA+ spec, if it has one
+pub fn lint_spec(path: &Path, content: &str, cfg: Option<&str>) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("tla") => {
+            let stripped = strip_tla_comments(content);
+            lint_module_name(path, &stripped, r"-{4,}\s*MODULE\s+(\w+)", &mut violations);
+            lint_non_ascii(path, &stripped, &mut violations);
+            let outline = SpecOutline::parse_tla(content);
+            lint_tla_definitions(path, &stripped, &outline, &mut violations);
+            if let Some(cfg) = cfg {
+                lint_cfg(path, cfg, &outline, &mut violations);
+            }
+        }
+        Some("als") => {
+            let stripped = strip_alloy_comments(content);
+            lint_module_name(
+                path,
+                &stripped,
+                r"(?m)^\s*module\s+([\w/]+)",
+                &mut violations,
+            );
+            lint_non_ascii(path, &stripped, &mut violations);
+        }
+        _ => {}
+    }
+    violations
+}
+
+/// The module name must match the file name, or the tools refuse to load it
+fn lint_module_name(path: &Path, content: &str, pattern: &str, violations: &mut Vec<Violation>) {
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return;
+    };
+    let Some(captures) = Regex::new(pattern).unwrap().captures(content) else {
+        return;
+    };
+    let declared = &captures[1];
+    // Alloy modules are named by path, e.g. `module protocols/queue`
+    let name = declared.rsplit('/').next().unwrap_or(declared);
+    if name != stem {
+        violations.push(violation(
+            MODULE_NAME,
+            Severity::Warning,
+            path,
+            line_of(content, captures.get(0).map_or(0, |m| m.start())),
+            format!("Module `{}` is declared in {}", declared, file_name(path)),
+            format!("Rename the module to `{}` or the file to `{}`", stem, name),
+        ));
+    }
+}
+
+/// Unicode operators such as `∧` are not accepted by SANY, TLC or Alloy
+fn lint_non_ascii(path: &Path, content: &str, violations: &mut Vec<Violation>) {
+    for (index, line) in content.lines().enumerate() {
+        if let Some((column, c)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+            violations.push(Violation {
+                location: Location::point(
+                    path,
+                    index as u32 + 1,
+                    line[..column].chars().count() as u32 + 1,
+                ),
+                ..violation(
+                    NON_ASCII,
+                    Severity::Warning,
+                    path,
+                    index + 1,
+                    format!("Non-ASCII character `{}` outside a comment", c),
+                    "Use the ASCII form of the operator, e.g. `/\\` for `∧`".to_string(),
+                )
+            });
+        }
+    }
+}
+
+/// Declared variables nothing refers to, and behavior specs without `Init` or `Spec`
+fn lint_tla_definitions(
+    path: &Path,
+    content: &str,
+    outline: &SpecOutline,
+    violations: &mut Vec<Violation>,
+) {
+    let variables: Vec<&str> = outline
+        .elements()
+        .filter(|(_, kind)| *kind == SpecElementKind::Variable)
+        .map(|(name, _)| name)
+        .collect();
+    // Modules without variables are operator libraries
+    if variables.is_empty() {
+        return;
+    }
+
+    for variable in &variables {
+        let word = Regex::new(&format!(r"\b{}\b", regex::escape(variable))).unwrap();
+        let mut uses = word.find_iter(content);
+        let declaration = uses.next();
+        if uses.next().is_none() {
+            violations.push(violation(
+                UNUSED_VARIABLE,
+                Severity::Warning,
+                path,
+                line_of(content, declaration.map_or(0, |m| m.start())),
+                format!("Variable `{}` is declared but never used", variable),
+                format!(
+                    "Remove `{}` from VARIABLES or constrain it in Init and Next",
+                    variable
+                ),
+            ));
+        }
+    }
+
+    for definition in ["Init", "Spec"] {
+        if outline.body(definition).is_none() {
+            violations.push(violation(
+                MISSING_DEFINITION,
+                Severity::Warning,
+                path,
+                1,
+                format!(
+                    "Spec declares variables but does not define `{}`",
+                    definition
+                ),
+                match definition {
+                    "Init" => "Define `Init` with the initial value of every variable",
+                    _ => "Define `Spec == Init /\\ [][Next]_vars` for TLC to check",
+                }
+                .to_string(),
+            ));
+        }
+    }
+}
+
+/// Names the TLC model refers to that the spec does not define
+fn lint_cfg(path: &Path, cfg: &str, outline: &SpecOutline, violations: &mut Vec<Violation>) {
+    let defined: BTreeSet<&str> = outline.elements().map(|(name, _)| name).collect();
+    let constants: BTreeSet<&str> = outline
+        .elements()
+        .filter(|(_, kind)| *kind == SpecElementKind::Constant)
+        .map(|(name, _)| name)
+        .collect();
+    let identifier = Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap();
+    let assignment = Regex::new(r"^([A-Za-z_]\w*)\s*(=|<-)\s*(.*)$").unwrap();
+    let cfg_path = path.with_extension("cfg");
+
+    let mut keyword = "";
+    for (index, line) in strip_tla_comments(cfg).lines().enumerate() {
+        let mut rest = line.trim();
+        if let Some(first) = rest.split_whitespace().next() {
+            if let Some(known) = CFG_KEYWORDS.iter().find(|k| **k == first) {
+                keyword = known;
+                rest = rest[first.len()..].trim();
+            }
+        }
+        if rest.is_empty() || keyword == "CHECK_DEADLOCK" {
+            continue;
+        }
+
+        let mut undefined = Vec::new();
+        if keyword.starts_with("CONSTANT") {
+            let Some(captures) = assignment.captures(rest) else {
+                continue;
+            };
+            if !constants.contains(&captures[1]) {
+                undefined.push(captures[1].to_string());
+            }
+            let value = captures[3].trim();
+            if &captures[2] == "<-" && identifier.is_match(value) && !defined.contains(value) {
+                undefined.push(value.to_string());
+            }
+        } else {
+            undefined.extend(
+                rest.split_whitespace()
+                    .filter(|name| identifier.is_match(name) && !defined.contains(name))
+                    .map(str::to_string),
+            );
+        }
+
+        for name in undefined {
+            violations.push(violation(
+                UNDEFINED_CFG_SYMBOL,
+                Severity::Error,
+                &cfg_path,
+                index + 1,
+                format!(
+                    "{} refers to `{}`, which {} does not define",
+                    keyword,
+                    name,
+                    file_name(path)
+                ),
+                format!("Define `{}` in the spec or fix the name in the model", name),
+            ));
+        }
+    }
+}
+
+fn violation(
+    id: &str,
+    severity: Severity,
+    path: &Path,
+    line: usize,
+    message: String,
+    suggestion: String,
+) -> Violation {
+    Violation {
+        id: id.to_string(),
+        severity,
+        location: Location {
+            line: u32::try_from(line).ok(),
+            ..Location::file(path)
+        },
+        message,
+        suggestion: Some(suggestion),
+        rule: RULE.to_string(),
+    }
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUEUE: &str = r#"---- MODULE Queue ----
+EXTENDS Naturals
+CONSTANT Capacity
+VARIABLES items, head, spare
+
+TypeOK == items \in Nat
+
+Init == items = 0 /\ head = 0
+
+Next == items < Capacity /\ items' = items + 1 /\ UNCHANGED head \* ≤ in a comment is fine
+
+Spec == Init /\ [][Next]_<<items, head>>
+===="#;
+
+    fn ids(violations: &[Violation]) -> Vec<&str> {
+        violations.iter().map(|v| v.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_clean_spec_has_no_findings() {
+        let spec = QUEUE.replace(", spare", "");
+        let cfg = "SPECIFICATION Spec\nCONSTANT Capacity = 3\nINVARIANT TypeOK\n";
+        assert!(lint_spec(Path::new("specs/Queue.tla"), &spec, Some(cfg)).is_empty());
+    }
+
+    #[test]
+    fn test_unused_variable_and_module_name() {
+        let violations = lint_spec(Path::new("specs/queue.tla"), QUEUE, None);
+        assert_eq!(ids(&violations), [MODULE_NAME, UNUSED_VARIABLE]);
+        assert!(violations[1].message.contains("`spare`"));
+        assert_eq!(violations[1].location.line, Some(4));
+        assert!(violations.iter().all(|v| v.rule == RULE));
+    }
+
+    #[test]
+    fn test_undefined_cfg_symbols() {
+        let spec = QUEUE.replace(", spare", "");
+        let cfg = "INIT Init\nNEXT Next\nCONSTANTS\n    Capacity = 3\n    Size <- Limit\nINVARIANTS\n    TypeOK\n    NoOverflow\n";
+        let violations = lint_spec(Path::new("specs/Queue.tla"), &spec, Some(cfg));
+
+        let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(ids(&violations), [UNDEFINED_CFG_SYMBOL; 3]);
+        assert!(messages[0].contains("`Size`"));
+        assert!(messages[1].contains("`Limit`"));
+        assert!(messages[2].contains("INVARIANTS refers to `NoOverflow`"));
+        assert_eq!(violations[2].location.file, Path::new("specs/Queue.cfg"));
+        assert_eq!(violations[2].location.line, Some(8));
+        assert_eq!(violations[2].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_missing_definitions_and_non_ascii() {
+        let spec = "---- MODULE Counter ----\nVARIABLE x\nStep == x' = x + 1 ∧ x < 3\n====";
+        let violations = lint_spec(Path::new("Counter.tla"), spec, None);
+        assert_eq!(
+            ids(&violations),
+            [NON_ASCII, MISSING_DEFINITION, MISSING_DEFINITION]
+        );
+        assert_eq!(violations[0].location.line, Some(3));
+        assert_eq!(violations[0].location.column, Some(20));
+    }
+
+    #[test]
+    fn test_alloy_module_name_and_non_ascii() {
+        let spec = "module protocols/queue\n// → in a comment\nsig Node { next: lone Node }\nfact { all n: Node | n ∉ n.^next }\n";
+        let violations = lint_spec(Path::new("specs/Queue.als"), spec, None);
+        assert_eq!(ids(&violations), [MODULE_NAME, NON_ASCII]);
+        assert_eq!(violations[1].location.line, Some(4));
+    }
+}
@@ -0,0 +1,235 @@
+//! Platform-independent form of TLA+ and Alloy specs, hashed into cache keys
+//!
+//! Comments are removed with each language's own grammar and line endings
+//! are unified, so a spec checked out with CRLF on Windows hashes like the
+//! same spec with LF elsewhere and caches can be shared between them.
+
+use std::path::Path;
+
+/// Version of the spec normalization rules, hashed ahead of every spec
+///
+/// Bump when normalization changes, so that results cached under the old
+/// rules are not found by the new ones.
+pub const SPEC_NORMALIZATION_VERSION: u32 = 1;
+
+/// Specification language, which decides the comment grammar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecLanguage {
+    TlaPlus,
+    Alloy,
+}
+
+/// Comment and string syntax of a spec language
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_open: &'static str,
+    block_close: &'static str,
+    /// Block comments may contain block comments
+    nested_blocks: bool,
+    /// `"..."` literals, inside which comment markers are text
+    strings: bool,
+}
+
+impl SpecLanguage {
+    /// Language of a spec file by extension: `.tla` or `.als`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("tla") => Some(SpecLanguage::TlaPlus),
+            Some("als") => Some(SpecLanguage::Alloy),
+            _ => None,
+        }
+    }
+
+    fn syntax(self) -> Syntax {
+        match self {
+            SpecLanguage::TlaPlus => Syntax {
+                line_comments: &["\\*"],
+                block_open: "(*",
+                block_close: "*)",
+                nested_blocks: true,
+                strings: true,
+            },
+            SpecLanguage::Alloy => Syntax {
+                line_comments: &["//", "--"],
+                block_open: "/*",
+                block_close: "*/",
+                nested_blocks: false,
+                strings: false,
+            },
+        }
+    }
+}
+
+/// Line endings unified to `\n` and a leading byte order mark dropped
+pub fn normalize_line_endings(source: &str) -> String {
+    source
+        .strip_prefix('\u{feff}')
+        .unwrap_or(source)
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+}
+
+/// Spec with comments, line endings and insignificant whitespace normalized
+///
+/// TLA+ aligns `/\` and `\/` lists by column, so its indentation is kept and
+/// only trailing whitespace, blank lines and text outside the module are
+/// dropped. Alloy is not layout-sensitive and its whitespace is collapsed.
+pub fn normalize_spec(spec: &str, language: SpecLanguage) -> String {
+    let spec = strip_comments(&normalize_line_endings(spec), language);
+    match language {
+        SpecLanguage::TlaPlus => {
+            let lines: Vec<&str> = spec.lines().map(str::trim_end).collect();
+            module_lines(&lines)
+                .iter()
+                .filter(|line| !line.is_empty())
+                .copied()
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        SpecLanguage::Alloy => spec.split_whitespace().collect::<Vec<_>>().join(" "),
+    }
+}
+
+/// Replace comments with spaces, keeping line breaks and columns
+fn strip_comments(spec: &str, language: SpecLanguage) -> String {
+    let syntax = language.syntax();
+    let mut out = String::with_capacity(spec.len());
+    let blank = |out: &mut String, text: &str| {
+        out.extend(text.chars().map(|c| if c == '\n' { '\n' } else { ' ' }));
+    };
+
+    let mut rest = spec;
+    let mut depth = 0usize;
+    let mut in_string = false;
+    while let Some(c) = rest.chars().next() {
+        let len = if depth > 0 {
+            if syntax.nested_blocks && rest.starts_with(syntax.block_open) {
+                depth += 1;
+                syntax.block_open.len()
+            } else if rest.starts_with(syntax.block_close) {
+                depth -= 1;
+                syntax.block_close.len()
+            } else {
+                c.len_utf8()
+            }
+        } else if in_string {
+            // Strings end at an unescaped quote or, unterminated, at the line end
+            let len = match c {
+                '\\' => rest[1..]
+                    .chars()
+                    .next()
+                    .map_or(1, |next| 1 + next.len_utf8()),
+                _ => c.len_utf8(),
+            };
+            in_string = !matches!(c, '"' | '\n');
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        } else if syntax.strings && c == '"' {
+            in_string = true;
+            out.push(c);
+            rest = &rest[1..];
+            continue;
+        } else if rest.starts_with(syntax.block_open) {
+            depth = 1;
+            syntax.block_open.len()
+        } else if syntax.line_comments.iter().any(|m| rest.starts_with(m)) {
+            rest.find('\n').unwrap_or(rest.len())
+        } else {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+        blank(&mut out, &rest[..len]);
+        rest = &rest[len..];
+    }
+    out
+}
+
+/// Lines from the `---- MODULE` header through the last `====` line
+///
+/// SANY ignores text before and after the module, so it does not affect
+/// results. Specs without a header are kept whole.
+fn module_lines<'a>(lines: &'a [&'a str]) -> &'a [&'a str] {
+    let Some(start) = lines.iter().position(|line| {
+        let line = line.trim_start();
+        line.starts_with("----") && line.contains("MODULE")
+    }) else {
+        return lines;
+    };
+    let end = lines
+        .iter()
+        .rposition(|line| line.trim_start().starts_with("===="))
+        .filter(|end| *end >= start)
+        .map_or(lines.len(), |end| end + 1);
+    &lines[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUEUE: &str = "Written by the queue team, ignored by SANY\n\
+        ---- MODULE Queue ----\n\
+        EXTENDS Naturals \\* for Nat\n\
+        (* Bounded queue (* nested *) of items *)\n\
+        VARIABLE items\n\
+        \n\
+        Msg == \"not \\* a comment\"\n\
+        Next == /\\ items < 3\n\
+        \x20       /\\ items' = items + 1\n\
+        ====\n\
+        Notes after the module\n";
+
+    #[test]
+    fn test_tla_comment_grammar() {
+        let normalized = normalize_spec(QUEUE, SpecLanguage::TlaPlus);
+        assert_eq!(
+            normalized,
+            "---- MODULE Queue ----\n\
+             EXTENDS Naturals\n\
+             VARIABLE items\n\
+             Msg == \"not \\* a comment\"\n\
+             Next == /\\ items < 3\n\
+             \x20       /\\ items' = items + 1\n\
+             ===="
+        );
+
+        // `//` is not a TLA+ comment, so text after it is significant
+        let with_slashes = QUEUE.replace("VARIABLE items", "VARIABLE items // a");
+        assert_ne!(
+            normalize_spec(&with_slashes, SpecLanguage::TlaPlus),
+            normalized
+        );
+
+        // Junction lists are aligned by column
+        let realigned = QUEUE.replace("\x20       /\\ items'", "  /\\ items'");
+        assert_ne!(
+            normalize_spec(&realigned, SpecLanguage::TlaPlus),
+            normalized
+        );
+    }
+
+    #[test]
+    fn test_alloy_comment_grammar() {
+        let model = "sig Node { next: lone Node } -- linked\n\
+                     // acyclic\n\
+                     fact { no n: Node | n in n.^next } /* no loops */\n";
+        assert_eq!(
+            normalize_spec(model, SpecLanguage::Alloy),
+            "sig Node { next: lone Node } fact { no n: Node | n in n.^next }"
+        );
+    }
+
+    #[test]
+    fn test_line_endings_do_not_change_the_form() {
+        let crlf = format!("\u{feff}{}", QUEUE.replace('\n', "\r\n"));
+        for language in [SpecLanguage::TlaPlus, SpecLanguage::Alloy] {
+            assert_eq!(
+                normalize_spec(&crlf, language),
+                normalize_spec(QUEUE, language)
+            );
+        }
+        assert_eq!(normalize_line_endings("a\r\nb\rc\n"), "a\nb\nc\n");
+    }
+}
@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::json;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Part of a verification run that timings are broken down by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Phase {
+    ConfigResolution,
+    Discovery,
+    Hashing,
+    PluginExecution,
+    Parsing,
+    CacheIo,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::ConfigResolution => "config resolution",
+            Phase::Discovery => "discovery",
+            Phase::Hashing => "hashing",
+            Phase::PluginExecution => "plugin execution",
+            Phase::Parsing => "parsing",
+            Phase::CacheIo => "cache io",
+        }
+    }
+}
+
+/// One timed section, relative to when recording started
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimingSpan {
+    pub phase: Phase,
+    pub label: String,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// Total time spent in a phase
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseTiming {
+    pub phase: Phase,
+    pub total: Duration,
+    pub spans: usize,
+}
+
+/// Shared recorder for per-phase timings
+///
+/// The default recorder is disabled and ignores every span, so instrumented
+/// code does not need to check whether `--timings` was requested.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    inner: Option<Arc<Recorder>>,
+}
+
+#[derive(Debug)]
+struct Recorder {
+    origin: Instant,
+    spans: Mutex<Vec<TimingSpan>>,
+}
+
+impl Timings {
+    pub fn enabled() -> Self {
+        Self {
+            inner: Some(Arc::new(Recorder {
+                origin: Instant::now(),
+                spans: Mutex::new(Vec::new()),
+            })),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Time a synchronous section
+    pub fn time<T>(&self, phase: Phase, label: impl Into<String>, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let value = f();
+        self.record(phase, label, start);
+        value
+    }
+
+    /// Record a section that began at `start` and ends now
+    pub fn record(&self, phase: Phase, label: impl Into<String>, start: Instant) {
+        let Some(recorder) = &self.inner else {
+            return;
+        };
+        let span = TimingSpan {
+            phase,
+            label: label.into(),
+            start: start.saturating_duration_since(recorder.origin),
+            duration: start.elapsed(),
+        };
+        recorder.spans.lock().unwrap().push(span);
+    }
+
+    /// Recorded spans in start order
+    pub fn spans(&self) -> Vec<TimingSpan> {
+        let mut spans = self
+            .inner
+            .as_ref()
+            .map(|recorder| recorder.spans.lock().unwrap().clone())
+            .unwrap_or_default();
+        spans.sort_by_key(|span| span.start);
+        spans
+    }
+
+    /// Per-phase totals, slowest first
+    pub fn breakdown(&self) -> Vec<PhaseTiming> {
+        let mut phases: Vec<PhaseTiming> = Vec::new();
+        for span in self.spans() {
+            match phases.iter_mut().find(|p| p.phase == span.phase) {
+                Some(timing) => {
+                    timing.total += span.duration;
+                    timing.spans += 1;
+                }
+                None => phases.push(PhaseTiming {
+                    phase: span.phase,
+                    total: span.duration,
+                    spans: 1,
+                }),
+            }
+        }
+        phases.sort_by(|a, b| b.total.cmp(&a.total).then(a.phase.cmp(&b.phase)));
+        phases
+    }
+
+    /// Chrome trace event JSON, viewable in chrome://tracing, Perfetto or speedscope
+    pub fn chrome_trace(&self) -> serde_json::Value {
+        let events: Vec<serde_json::Value> = self
+            .spans()
+            .iter()
+            .map(|span| {
+                json!({
+                    "name": span.label,
+                    "cat": span.phase.name(),
+                    "ph": "X",
+                    "ts": span.start.as_micros() as u64,
+                    "dur": span.duration.as_micros() as u64,
+                    "pid": 1,
+                    "tid": 1,
+                })
+            })
+            .collect();
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+    }
+
+    pub fn write_chrome_trace(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(&self.chrome_trace())?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write timings to {:?}", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown_and_chrome_trace() {
+        let disabled = Timings::default();
+        disabled.time(Phase::Hashing, "ignored", || ());
+        assert!(disabled.spans().is_empty());
+
+        let timings = Timings::enabled();
+        let value = timings.time(Phase::ConfigResolution, "load config", || 42);
+        assert_eq!(value, 42);
+        timings.time(Phase::PluginExecution, "src/lib.rs PropertyBased", || {
+            std::thread::sleep(Duration::from_millis(5))
+        });
+        timings.record(Phase::PluginExecution, "src/main.rs Formal", Instant::now());
+
+        let breakdown = timings.breakdown();
+        assert_eq!(breakdown[0].phase, Phase::PluginExecution);
+        assert_eq!(breakdown[0].spans, 2);
+        assert!(breakdown[0].total >= Duration::from_millis(5));
+        assert_eq!(breakdown.len(), 2);
+
+        let trace = timings.chrome_trace();
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["name"], "load config");
+        assert_eq!(events[0]["cat"], "config resolution");
+        assert_eq!(events[0]["ph"], "X");
+    }
+}
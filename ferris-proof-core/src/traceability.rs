@@ -0,0 +1,357 @@
+use crate::discovery::files_with_extension;
+use crate::spec_diff::{SpecElementKind, SpecOutline};
+use anyhow::{Context, Result};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, ImplItem, Item, Token};
+use tracing::{debug, warn};
+
+/// A code item claiming to implement a specification action
+///
+/// Written as `#[verification(spec = "specs/formal/tla/protocol.tla#SendMessage")]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceLink {
+    /// Specification path relative to the project root
+    pub spec: PathBuf,
+    pub action: String,
+    /// Item path within its file, e.g. `Client::send`
+    pub item: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Links between code items and specification actions across a project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TraceMatrix {
+    pub links: Vec<TraceLink>,
+    /// Actions declared by each known specification
+    pub spec_actions: BTreeMap<PathBuf, Vec<String>>,
+    /// Constants, variables, signatures and other definitions of each known specification
+    #[serde(default)]
+    pub spec_data: BTreeMap<PathBuf, Vec<String>>,
+}
+
+impl TraceMatrix {
+    /// Collect `spec = ...` links from `src/` and actions from `specs/` and linked specs
+    pub fn build(project_root: &Path) -> Result<Self> {
+        let mut links = Vec::new();
+        for path in files_with_extension(&project_root.join("src"), &["rs"]) {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {:?}", path))?;
+            let file = path.strip_prefix(project_root).unwrap_or(&path);
+            match links_in_source(file, &content) {
+                Ok(found) => links.extend(found),
+                Err(e) => warn!("Skipping traceability links in {:?}: {}", path, e),
+            }
+        }
+
+        let mut specs: Vec<PathBuf> =
+            files_with_extension(&project_root.join("specs"), &["tla", "als"])
+                .into_iter()
+                .map(|p| {
+                    p.strip_prefix(project_root)
+                        .map(Path::to_path_buf)
+                        .unwrap_or(p)
+                })
+                .collect();
+        specs.extend(links.iter().map(|link| link.spec.clone()));
+        specs.sort();
+        specs.dedup();
+
+        let mut spec_actions = BTreeMap::new();
+        let mut spec_data = BTreeMap::new();
+        for spec in specs {
+            let Ok(content) = std::fs::read_to_string(project_root.join(&spec)) else {
+                continue;
+            };
+            if let Some(outline) = SpecOutline::parse(&spec, &content) {
+                let actions = outline
+                    .elements()
+                    .filter(|(_, kind)| *kind == SpecElementKind::Action)
+                    .map(|(name, _)| name.to_string())
+                    .collect();
+                let data = outline
+                    .elements()
+                    .filter(|(_, kind)| kind.is_data())
+                    .map(|(name, _)| name.to_string())
+                    .collect();
+                spec_actions.insert(spec.clone(), actions);
+                spec_data.insert(spec, data);
+            }
+        }
+
+        debug!(
+            "Traceability: {} links across {} specifications",
+            links.len(),
+            spec_actions.len()
+        );
+        Ok(Self {
+            links,
+            spec_actions,
+            spec_data,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// Links for a specification action, in source order
+    pub fn implementations(&self, spec: &Path, action: &str) -> Vec<&TraceLink> {
+        self.links
+            .iter()
+            .filter(|link| link.spec == spec && link.action == action)
+            .collect()
+    }
+
+    /// Declared actions with no implementing code item
+    pub fn unimplemented_actions(&self) -> Vec<(&Path, &str)> {
+        self.spec_actions
+            .iter()
+            .flat_map(|(spec, actions)| actions.iter().map(move |a| (spec.as_path(), a.as_str())))
+            .filter(|(spec, action)| self.implementations(spec, action).is_empty())
+            .collect()
+    }
+
+    /// Links naming a specification or element that does not exist
+    pub fn dangling_links(&self) -> Vec<&TraceLink> {
+        self.links
+            .iter()
+            .filter(|link| {
+                self.spec_actions
+                    .get(&link.spec)
+                    .is_none_or(|actions| !actions.contains(&link.action))
+                    && !self.is_data_link(link)
+            })
+            .collect()
+    }
+
+    /// Whether a link ties a Rust definition to a spec element other than an action
+    pub fn is_data_link(&self, link: &TraceLink) -> bool {
+        self.spec_data
+            .get(&link.spec)
+            .is_some_and(|data| data.contains(&link.action))
+    }
+}
+
+/// Parse the traceability links declared in one Rust source file
+pub fn links_in_source(file: &Path, content: &str) -> Result<Vec<TraceLink>> {
+    let syntax = syn::parse_file(content)?;
+    let mut collector = LinkCollector::new(file, content);
+    collector.visit_items(&syntax.items, "");
+    Ok(collector.links)
+}
+
+/// Digest of each linked item in one Rust source file, keyed by item path
+///
+/// Attributes are left out, so editing a doc comment or the link itself
+/// does not count as a change to the definition.
+pub fn linked_item_hashes(file: &Path, content: &str) -> Result<BTreeMap<String, String>> {
+    let syntax = syn::parse_file(content)?;
+    let mut collector = LinkCollector::new(file, content);
+    collector.visit_items(&syntax.items, "");
+    Ok(collector.hashes)
+}
+
+struct LinkCollector<'a> {
+    file: &'a Path,
+    content: &'a str,
+    /// Where to resume searching for each spec reference, so repeated
+    /// references resolve to successive lines
+    search_from: HashMap<String, usize>,
+    links: Vec<TraceLink>,
+    hashes: BTreeMap<String, String>,
+}
+
+impl<'a> LinkCollector<'a> {
+    fn new(file: &'a Path, content: &'a str) -> Self {
+        Self {
+            file,
+            content,
+            search_from: HashMap::new(),
+            links: Vec::new(),
+            hashes: BTreeMap::new(),
+        }
+    }
+
+    fn visit_items(&mut self, items: &[Item], prefix: &str) {
+        for item in items {
+            match item {
+                Item::Fn(f) => self.record(&f.attrs, prefix, &f.sig.ident.to_string(), item),
+                Item::Struct(s) => self.record(&s.attrs, prefix, &s.ident.to_string(), item),
+                Item::Enum(e) => self.record(&e.attrs, prefix, &e.ident.to_string(), item),
+                Item::Trait(t) => self.record(&t.attrs, prefix, &t.ident.to_string(), item),
+                Item::Const(c) => self.record(&c.attrs, prefix, &c.ident.to_string(), item),
+                Item::Static(s) => self.record(&s.attrs, prefix, &s.ident.to_string(), item),
+                Item::Type(t) => self.record(&t.attrs, prefix, &t.ident.to_string(), item),
+                Item::Mod(m) => {
+                    let name = m.ident.to_string();
+                    self.record(&m.attrs, prefix, &name, item);
+                    if let Some((_, items)) = &m.content {
+                        self.visit_items(items, &qualify(prefix, &name));
+                    }
+                }
+                Item::Impl(i) => {
+                    let self_ty = match &*i.self_ty {
+                        syn::Type::Path(p) => p
+                            .path
+                            .segments
+                            .last()
+                            .map(|s| s.ident.to_string())
+                            .unwrap_or_default(),
+                        _ => continue,
+                    };
+                    let impl_prefix = qualify(prefix, &self_ty);
+                    for impl_item in &i.items {
+                        match impl_item {
+                            ImplItem::Fn(f) => self.record(
+                                &f.attrs,
+                                &impl_prefix,
+                                &f.sig.ident.to_string(),
+                                impl_item,
+                            ),
+                            ImplItem::Const(c) => {
+                                self.record(&c.attrs, &impl_prefix, &c.ident.to_string(), impl_item)
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn record(&mut self, attrs: &[Attribute], prefix: &str, name: &str, item: &dyn ToTokens) {
+        let references = spec_references(attrs);
+        if !references.is_empty() {
+            self.hashes
+                .insert(qualify(prefix, name), digest_without_attrs(item));
+        }
+        for reference in references {
+            let Some((spec, action)) = reference.split_once('#') else {
+                warn!(
+                    "Ignoring spec reference '{}' in {:?}: expected 'path#Action'",
+                    reference, self.file
+                );
+                continue;
+            };
+
+            let quoted = format!("\"{}\"", reference);
+            let from = self.search_from.get(&reference).copied().unwrap_or(0);
+            let offset = self.content[from..].find(&quoted).map(|o| from + o);
+            if let Some(offset) = offset {
+                self.search_from
+                    .insert(reference.clone(), offset + quoted.len());
+            }
+            let line = offset.map_or(0, |o| self.content[..o].matches('\n').count() + 1);
+
+            self.links.push(TraceLink {
+                spec: PathBuf::from(spec),
+                action: action.to_string(),
+                item: qualify(prefix, name),
+                file: self.file.to_path_buf(),
+                line,
+            });
+        }
+    }
+}
+
+/// `spec = "..."` values from `#[verification(...)]` attributes
+fn spec_references(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("verification"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter_map(|meta| match meta {
+            syn::Meta::NameValue(nv) if nv.path.is_ident("spec") => match nv.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// blake3 of an item's tokens after its leading `#[...]` attributes
+fn digest_without_attrs(item: &dyn ToTokens) -> String {
+    let mut tokens = item.to_token_stream().into_iter().peekable();
+    while matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '#') {
+        tokens.next();
+        tokens.next();
+    }
+    let body = tokens.collect::<TokenStream>().to_string();
+    blake3::hash(body.as_bytes()).to_hex().to_string()
+}
+
+fn qualify(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}::{}", prefix, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links_and_gaps() {
+        let source = r#"
+#[verification(spec = "specs/protocol.tla#Send")]
+pub fn send() {}
+
+pub struct Client;
+
+impl Client {
+    #[verification(formal, spec = "specs/protocol.tla#Receive")]
+    pub fn receive(&self) {}
+
+    #[verification(spec = "specs/protocol.tla#Missing")]
+    pub fn close(&self) {}
+}
+"#;
+        let links = links_in_source(Path::new("src/lib.rs"), source).unwrap();
+        assert_eq!(links.len(), 3);
+        assert_eq!(links[0].item, "send");
+        assert_eq!(links[0].line, 2);
+        assert_eq!(links[1].item, "Client::receive");
+        assert_eq!(links[1].action, "Receive");
+        assert_eq!(links[1].line, 8);
+
+        let mut spec_actions = BTreeMap::new();
+        spec_actions.insert(
+            PathBuf::from("specs/protocol.tla"),
+            vec![
+                "Receive".to_string(),
+                "Send".to_string(),
+                "Timeout".to_string(),
+            ],
+        );
+        let matrix = TraceMatrix {
+            links,
+            spec_actions,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            matrix.unimplemented_actions(),
+            vec![(Path::new("specs/protocol.tla"), "Timeout")]
+        );
+        let dangling = matrix.dangling_links();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].item, "Client::close");
+    }
+}
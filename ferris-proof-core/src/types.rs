@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -32,23 +32,26 @@ pub enum Layer {
     TypeLevel,     // Layer 2: Type-level verification
     PropertyBased, // Layer 3: Property-based testing
     Monitoring,    // Layer 4: Production monitoring
+    ApiSurface,    // Public API snapshot and declared invariants
 }
 
 impl std::str::FromStr for Layer {
     type Err = String;
 
+    /// Accepts `property-based` as well as the config spelling `property_based`
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "formal" => Ok(Layer::Formal),
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "formal" | "formal-specs" => Ok(Layer::Formal),
             "type-level" => Ok(Layer::TypeLevel),
             "property-based" => Ok(Layer::PropertyBased),
             "monitoring" => Ok(Layer::Monitoring),
+            "api-surface" => Ok(Layer::ApiSurface),
             _ => Err(format!("Invalid layer: {}", s)),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum EnforcementMode {
     Advisory, // Log violations, don't fail builds
@@ -66,6 +69,29 @@ pub enum Technique {
     ConcurrencyTesting,
     FormalSpecs,
     ModelChecking,
+    MutationTesting,
+    /// Running tests under Miri to detect undefined behavior
+    UndefinedBehaviorChecks,
+    /// Proving `#[requires]`/`#[ensures]` contracts with Prusti or Creusot
+    DeductiveProofs,
+}
+
+impl Technique {
+    /// Verification layer the technique belongs to
+    pub fn layer(&self) -> Layer {
+        match self {
+            Technique::FormalSpecs | Technique::ModelChecking | Technique::DeductiveProofs => {
+                Layer::Formal
+            }
+            Technique::TypeSafety | Technique::SessionTypes | Technique::RefinementTypes => {
+                Layer::TypeLevel
+            }
+            Technique::PropertyTests
+            | Technique::ConcurrencyTesting
+            | Technique::MutationTesting
+            | Technique::UndefinedBehaviorChecks => Layer::PropertyBased,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -73,16 +99,98 @@ pub enum Status {
     Success,
     Warning,
     Error,
+    /// Not run, for a [`SkipReason`]
     Skipped,
+    /// Not run because an earlier pass over unchanged inputs was reused
+    Cached,
+}
+
+impl Status {
+    /// Whether tools actually ran to produce this status
+    pub fn executed(self) -> bool {
+        !matches!(self, Status::Skipped | Status::Cached)
+    }
+
+    /// Severity order used to merge statuses: the higher rank wins
+    pub fn rank(self) -> u8 {
+        match self {
+            Status::Skipped => 0,
+            Status::Cached => 1,
+            Status::Success => 2,
+            Status::Warning => 3,
+            Status::Error => 4,
+        }
+    }
+}
+
+/// Why a layer has [`Status::Skipped`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// A layer it requires under `[layers]` did not pass
+    Gated,
+    /// No tool for the layer's techniques is available
+    ToolUnavailable,
+    /// Every violation it found is suppressed by the baseline
+    Suppressed,
+    /// The tools found nothing to verify, e.g. no property tests
+    NothingToVerify,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SkipReason::Gated => "gated",
+            SkipReason::ToolUnavailable => "tool unavailable",
+            SkipReason::Suppressed => "suppressed",
+            SkipReason::NothingToVerify => "nothing to verify",
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Severity {
+    Critical,
     Error,
     Warning,
     Info,
 }
 
+/// Which severities block, warn or are informational, under `[enforcement]`
+///
+/// Severities listed nowhere are informational. The outcome is capped by the
+/// module's enforcement mode, so an `advisory` module never blocks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnforcementPolicy {
+    pub advisory: Vec<Severity>,
+    pub warning: Vec<Severity>,
+    pub error: Vec<Severity>,
+}
+
+impl Default for EnforcementPolicy {
+    fn default() -> Self {
+        Self {
+            advisory: vec![Severity::Info],
+            warning: vec![Severity::Warning],
+            error: vec![Severity::Error, Severity::Critical],
+        }
+    }
+}
+
+impl EnforcementPolicy {
+    /// Whether a violation of `severity` blocks, warns or is informational
+    pub fn outcome(&self, severity: &Severity, mode: EnforcementMode) -> EnforcementMode {
+        let outcome = if self.error.contains(severity) {
+            EnforcementMode::Error
+        } else if self.warning.contains(severity) {
+            EnforcementMode::Warning
+        } else {
+            EnforcementMode::Advisory
+        };
+        outcome.min(mode)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationResult {
     pub overall_status: Status,
@@ -90,6 +198,205 @@ pub struct VerificationResult {
     pub metrics: VerificationMetrics,
     pub artifacts: Vec<Artifact>,
     pub timestamp: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_targets: Vec<SkippedTarget>,
+    /// Layers skipped per target by `[layers]` gating
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gated_layers: Vec<GatedLayer>,
+    /// Layers skipped because no tool for their techniques is available
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unavailable_layers: Vec<UnavailableLayer>,
+    /// Why each layer with [`Status::Skipped`] was not run
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub skip_reasons: HashMap<Layer, SkipReason>,
+    /// Semantic spec changes attached for PR reports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spec_changes: Vec<crate::spec_diff::SpecDiff>,
+    /// Spec action to code item links, when the project declares any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traceability: Option<crate::traceability::TraceMatrix>,
+    /// Consumption of `thresholds.max_violations`, when a budget is configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<crate::budget::BudgetReport>,
+    /// Seed that ordered targets and drove randomized tools; rerun with `--seed`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Machine, toolchain and repository state the run was verified in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<crate::environment::EnvironmentSnapshot>,
+    /// Outcome per verified target label, including targets skipped as up to date
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub target_statuses: BTreeMap<String, Status>,
+    /// Run was cancelled, e.g. by Ctrl-C, before every target was verified
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub interrupted: bool,
+    /// Root that violation locations and artifact paths are relative to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_root: Option<PathBuf>,
+    /// Template of the deep links reports add to violations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_template: Option<crate::links::LinkTemplate>,
+    /// Per-plugin outcomes of techniques fanned out to every compatible plugin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugin_breakdowns: Vec<crate::plugins::PluginBreakdown>,
+    /// Layer results per verified target, kept for flushing to the cache
+    #[serde(skip)]
+    pub completed: Vec<(crate::verification::Target, LayerResult)>,
+    /// Public API taken by the API-surface layer, kept for the run history
+    #[serde(skip)]
+    pub api_surface: Option<crate::api_surface::ApiSurface>,
+}
+
+impl VerificationResult {
+    /// Drop what varies between runs over the same inputs
+    ///
+    /// Timestamps, durations, the environment snapshot, the recorded root and
+    /// artifact ids are cleared, paths under `project_root` become relative,
+    /// and violations are sorted, so a report can serve as a cacheable build
+    /// output.
+    pub fn make_reproducible(&mut self, project_root: &std::path::Path) {
+        self.relativize(project_root);
+        self.project_root = None;
+
+        self.timestamp = DateTime::<Utc>::UNIX_EPOCH;
+        self.environment = None;
+        self.metrics.total_time = std::time::Duration::ZERO;
+        for layer in self.layer_results.values_mut() {
+            layer.execution_time = std::time::Duration::ZERO;
+            layer.violations.sort_by(|a, b| {
+                (
+                    &a.location.file,
+                    a.location.line,
+                    a.location.column,
+                    &a.id,
+                    &a.message,
+                )
+                    .cmp(&(
+                        &b.location.file,
+                        b.location.line,
+                        b.location.column,
+                        &b.id,
+                        &b.message,
+                    ))
+            });
+            for output in &mut layer.tool_outputs {
+                output.execution_time = std::time::Duration::ZERO;
+                output.artifact_id = None;
+            }
+            layer.tool_outputs.sort_by(|a, b| a.tool.cmp(&b.tool));
+        }
+        for breakdown in &mut self.plugin_breakdowns {
+            for outcome in &mut breakdown.outcomes {
+                outcome.execution_time = std::time::Duration::ZERO;
+            }
+            breakdown.outcomes.sort_by(|a, b| a.plugin.cmp(&b.plugin));
+        }
+        self.plugin_breakdowns
+            .sort_by_key(|b| (b.target.clone(), format!("{:?}", b.technique)));
+        self.artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    /// Record `project_root` and make locations under it relative
+    ///
+    /// Violation files, artifact paths and target labels lose the root, so
+    /// fingerprints and baselines match across checkouts on other machines.
+    pub fn relativize(&mut self, project_root: &std::path::Path) {
+        let relative_label = |label: String| {
+            label
+                .strip_prefix(&format!("{}/", project_root.display()))
+                .map_or(label.clone(), String::from)
+        };
+
+        let layers = self
+            .layer_results
+            .values_mut()
+            .chain(self.completed.iter_mut().map(|(_, result)| result));
+        for layer in layers {
+            for violation in &mut layer.violations {
+                violation.location.relativize(project_root);
+            }
+        }
+        for artifact in &mut self.artifacts {
+            artifact.path = relative_path(&artifact.path, project_root);
+        }
+        self.target_statuses = std::mem::take(&mut self.target_statuses)
+            .into_iter()
+            .map(|(label, status)| (relative_label(label), status))
+            .collect();
+        for breakdown in &mut self.plugin_breakdowns {
+            breakdown.target = relative_label(std::mem::take(&mut breakdown.target));
+        }
+        self.project_root = Some(project_root.to_path_buf());
+    }
+
+    /// Targets that ran, were served from the cache or lock, and were skipped,
+    /// e.g. `3 verified, 2 cached, 1 skipped`
+    pub fn target_summary(&self) -> Option<String> {
+        if self.target_statuses.is_empty() {
+            return None;
+        }
+        let count = |wanted: fn(Status) -> bool| {
+            self.target_statuses
+                .values()
+                .filter(|status| wanted(**status))
+                .count()
+        };
+        Some(format!(
+            "{} verified, {} cached, {} skipped",
+            count(Status::executed),
+            count(|status| status == Status::Cached),
+            count(|status| status == Status::Skipped)
+        ))
+    }
+
+    /// Deep link to a violation's location, from `[report] link_template`
+    ///
+    /// Templates using `{commit}` only link when the environment recorded a
+    /// commit, so a link never points at another revision.
+    pub fn violation_link(&self, violation: &Violation) -> Option<String> {
+        let template = self.link_template.as_ref()?;
+        let commit = self
+            .environment
+            .as_ref()
+            .and_then(|environment| environment.git_commit.as_deref());
+        template.render(
+            &violation.location,
+            self.project_root
+                .as_deref()
+                .unwrap_or(std::path::Path::new("")),
+            commit,
+        )
+    }
+}
+
+/// Target that was not verified because one of its dependencies failed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedTarget {
+    pub target: String,
+    pub failed_dependency: String,
+}
+
+/// Layer skipped for a target because a layer it requires did not pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatedLayer {
+    pub target: String,
+    pub layer: Layer,
+    pub required: Layer,
+}
+
+/// Layer none of whose configured techniques has an available tool
+///
+/// Found by the preflight before any target runs; the layer is skipped for
+/// every target, or fails every target under `--strict-tools`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnavailableLayer {
+    pub layer: Layer,
+    pub techniques: Vec<Technique>,
+    /// Catalog code for `ferris-proof explain`, e.g. `FP-TL-001`
+    pub code: String,
+    pub reason: String,
+    /// How to make a tool available
+    pub hint: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -101,6 +408,18 @@ pub struct LayerResult {
     pub tool_outputs: Vec<ToolOutput>,
 }
 
+impl LayerResult {
+    /// Reruns of failed tool invocations across the layer's outputs
+    pub fn retries(&self) -> u32 {
+        self.tool_outputs.iter().map(|output| output.retries).sum()
+    }
+
+    /// Passed, but only after at least one tool was rerun
+    pub fn is_flaky_pass(&self) -> bool {
+        self.status != Status::Error && self.retries() > 0
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Violation {
     pub id: String,
@@ -119,6 +438,71 @@ pub struct Location {
     pub span: Option<Span>,
 }
 
+impl Location {
+    /// Location covering a whole file
+    pub fn file(file: impl Into<PathBuf>) -> Self {
+        Self {
+            file: file.into(),
+            line: None,
+            column: None,
+            span: None,
+        }
+    }
+
+    /// Location of a single point, with a one-character span
+    pub fn point(file: impl Into<PathBuf>, line: u32, column: u32) -> Self {
+        Self::span(file, line, column, line, column + 1)
+    }
+
+    /// Location of a range; `line`/`column` are kept in sync with the span start
+    pub fn span(
+        file: impl Into<PathBuf>,
+        start_line: u32,
+        start_column: u32,
+        end_line: u32,
+        end_column: u32,
+    ) -> Self {
+        Self {
+            file: file.into(),
+            line: Some(start_line),
+            column: Some(start_column),
+            span: Some(Span {
+                start: Position {
+                    line: start_line,
+                    column: start_column,
+                },
+                end: Position {
+                    line: end_line,
+                    column: end_column,
+                },
+            }),
+        }
+    }
+
+    /// Make `file` relative to `project_root` when it lies under it
+    ///
+    /// The canonical form of the root is tried as well, so tools reporting
+    /// resolved paths (e.g. through a symlinked checkout) still match, and a
+    /// leading `./` is dropped.
+    pub fn relativize(&mut self, project_root: &std::path::Path) {
+        self.file = relative_path(&self.file, project_root);
+    }
+}
+
+/// `path` relative to `project_root`, or unchanged when outside of it
+pub fn relative_path(path: &std::path::Path, project_root: &std::path::Path) -> PathBuf {
+    if let Ok(stripped) = path.strip_prefix(project_root) {
+        return stripped.to_path_buf();
+    }
+    if let Ok(canonical) = project_root.canonicalize() {
+        if let Ok(stripped) = path.strip_prefix(&canonical) {
+            return stripped.to_path_buf();
+        }
+    }
+    path.strip_prefix(".").unwrap_or(path).to_path_buf()
+}
+
+/// Source range; lines and columns are 1-based and `end` is exclusive
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Span {
     pub start: Position,
@@ -137,6 +521,13 @@ pub struct VerificationMetrics {
     pub cache_hit_rate: f64,
     pub memory_usage: u64,
     pub test_cases_executed: u32,
+    /// Largest Alloy scope checked without a counterexample, from a scope sweep
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_verified_scope: Option<u32>,
+    /// Resource settings the tool was run with, e.g. TLC `workers` and `heap`
+    /// chosen for the host, recorded so a run can be reproduced elsewhere
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_settings: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -153,6 +544,8 @@ pub enum ArtifactType {
     RefinementType,
     FormalSpec,
     Report,
+    /// Persisted failing case, e.g. a `proptest-regressions/*.txt` seed file
+    Regression,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -162,4 +555,12 @@ pub struct ToolOutput {
     pub stderr: String,
     pub exit_code: i32,
     pub execution_time: std::time::Duration,
+    /// Artifact holding the full output when stdout/stderr were cut to a preview
+    ///
+    /// Always serialized: cache entries are bincode, which has no field names to skip by.
+    #[serde(default)]
+    pub artifact_id: Option<String>,
+    /// Times the tool was rerun after failing spuriously, per `[tools.*] retries`
+    #[serde(default)]
+    pub retries: u32,
 }
@@ -1,67 +1,915 @@
-use crate::cache::VerificationCache;
-use crate::plugins::PluginManager;
+use crate::artifacts::{ArtifactPolicy, ArtifactStore};
+use crate::cache::{CacheEntry, CacheFilter, CacheKey, CacheTtls, VerificationCache};
+use crate::dependency_graph::{LayerGates, TargetGraph};
+use crate::discovery::{discover_targets, DiscoveryOptions};
+use crate::environment::EnvironmentSnapshot;
+use crate::errors::FerrisProofError;
+use crate::normalization::NormalizationPolicies;
+use crate::plugins::{
+    CancellationToken, EffectiveConfig, PluginBreakdown, PluginManager, SelectionStrategy,
+    VerificationContext, VerificationInput,
+};
+use crate::seed;
+use crate::timings::{Phase, Timings};
 use crate::types::*;
 use anyhow::Result;
-use std::collections::HashMap;
-use tracing::{debug, info};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+/// Progress of a verification run, in the order it happens
+///
+/// Serializes as an object tagged with `"event"`, e.g. `{"event":"layer_started",...}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum VerificationEvent {
+    Started {
+        targets: usize,
+    },
+    TargetStarted {
+        target: String,
+    },
+    TargetFinished {
+        target: String,
+        status: Status,
+    },
+    LayerStarted {
+        target: String,
+        layer: Layer,
+    },
+    ViolationFound {
+        target: String,
+        layer: Layer,
+        violation: Violation,
+    },
+    LayerFinished {
+        target: String,
+        layer: Layer,
+        status: Status,
+        execution_time: Duration,
+    },
+    TargetSkipped(SkippedTarget),
+    LayerGated(GatedLayer),
+    /// Layer found to have no usable tool before any target runs
+    LayerUnavailable(UnavailableLayer),
+    Finished {
+        status: Status,
+        total_time: Duration,
+    },
+    /// Configuration was reloaded between runs
+    ConfigReloaded {
+        changed_files: Vec<PathBuf>,
+        /// Cache entries dropped because their config hash went stale
+        invalidated: usize,
+    },
+}
+
+/// Callback invoked synchronously for every event
+pub type ProgressCallback = Arc<dyn Fn(&VerificationEvent) + Send + Sync>;
+
+/// Configuration hash of a target, part of its cache keys
+pub type ConfigHasher = Arc<dyn Fn(&Target) -> String + Send + Sync>;
+
+/// Events buffered per subscriber before slow receivers start lagging
+const EVENT_CAPACITY: usize = 256;
 
-#[allow(dead_code)]
 pub struct VerificationEngine {
     plugin_manager: PluginManager,
     cache: VerificationCache,
+    keep_going: bool,
+    plugin_stats_path: Option<std::path::PathBuf>,
+    artifact_policy: ArtifactPolicy,
+    artifact_store: Option<ArtifactStore>,
+    config: Option<EffectiveConfig>,
+    discovery: DiscoveryOptions,
+    layer_gates: LayerGates,
+    unavailable_layers: Vec<UnavailableLayer>,
+    strict_tools: bool,
+    progress: Option<ProgressCallback>,
+    events: broadcast::Sender<VerificationEvent>,
+    seed: u64,
+    timings: Timings,
+    cancellation: CancellationToken,
+    environment: Option<EnvironmentSnapshot>,
+    normalization: NormalizationPolicies,
+    project_root: Option<PathBuf>,
+    only_layer: Option<Layer>,
+    scratch_dir: Option<PathBuf>,
+    config_hash: Option<ConfigHasher>,
+    cache_ttls: CacheTtls,
 }
 
 impl VerificationEngine {
     pub fn new() -> Self {
+        Self::with_parts(VerificationCache::new())
+    }
+
+    /// Engine whose cache is never written to disk
+    pub fn in_memory() -> Self {
+        Self::with_parts(VerificationCache::in_memory())
+    }
+
+    /// Configure a reusable engine for embedding in IDEs and build tools
+    pub fn builder() -> VerificationEngineBuilder {
+        VerificationEngineBuilder::default()
+    }
+
+    fn with_parts(cache: VerificationCache) -> Self {
         Self {
             plugin_manager: PluginManager::new(),
-            cache: VerificationCache::new(),
+            cache,
+            keep_going: false,
+            plugin_stats_path: None,
+            artifact_policy: ArtifactPolicy::default(),
+            artifact_store: None,
+            config: None,
+            discovery: DiscoveryOptions::default(),
+            layer_gates: LayerGates::default(),
+            unavailable_layers: Vec::new(),
+            strict_tools: false,
+            progress: None,
+            events: broadcast::channel(EVENT_CAPACITY).0,
+            seed: seed::random_seed(),
+            timings: Timings::default(),
+            cancellation: CancellationToken::new(),
+            environment: None,
+            normalization: NormalizationPolicies::default(),
+            project_root: None,
+            only_layer: None,
+            scratch_dir: None,
+            config_hash: None,
+            cache_ttls: CacheTtls::default(),
+        }
+    }
+
+    /// Configuration the engine was built with, if any
+    pub fn config(&self) -> Option<&EffectiveConfig> {
+        self.config.as_ref()
+    }
+
+    /// Seed ordering targets and passed to randomized tools
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Per-phase timings recorded by this engine
+    pub fn timings(&self) -> &Timings {
+        &self.timings
+    }
+
+    /// Token that stops the current run and kills its in-flight tools
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
+    /// Receive events from every subsequent run of this engine
+    pub fn subscribe(&self) -> broadcast::Receiver<VerificationEvent> {
+        self.events.subscribe()
+    }
+
+    fn emit(&self, event: VerificationEvent) {
+        if let Some(progress) = &self.progress {
+            progress(&event);
+        }
+        // No subscribers is not an error
+        let _ = self.events.send(event);
+    }
+
+    /// Adopt a reloaded configuration without rebuilding the engine
+    ///
+    /// Cache entries verified under any of `stale_config_hashes` are dropped.
+    /// Returns the number of entries removed.
+    pub fn apply_config_reload(
+        &mut self,
+        config: Option<EffectiveConfig>,
+        changed_files: Vec<PathBuf>,
+        stale_config_hashes: &BTreeSet<String>,
+    ) -> usize {
+        if config.is_some() {
+            self.config = config;
+        }
+
+        let invalidated = stale_config_hashes
+            .iter()
+            .map(|hash| {
+                self.cache.clear_matching(&CacheFilter {
+                    config_hash: Some(hash.clone()),
+                    ..CacheFilter::default()
+                })
+            })
+            .sum();
+        info!(
+            "Configuration reloaded: {} cache entries invalidated",
+            invalidated
+        );
+
+        self.emit(VerificationEvent::ConfigReloaded {
+            changed_files,
+            invalidated,
+        });
+        invalidated
+    }
+
+    pub fn with_cache(mut self, cache: VerificationCache) -> Self {
+        self.cache = cache;
+        if let Some(environment) = &self.environment {
+            self.cache.set_environment(environment.clone());
         }
+        self.cache.set_normalization(self.normalization.clone());
+        self
+    }
+
+    /// Reuse and cache layer results, keyed with the hash `config_hash` gives each target
+    ///
+    /// Without it the cache is never used, since results could not be told
+    /// apart across configuration changes.
+    pub fn with_config_hash(
+        mut self,
+        config_hash: impl Fn(&Target) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.config_hash = Some(Arc::new(config_hash));
+        self
+    }
+
+    /// How long layer results cached by this engine stay valid
+    pub fn with_cache_ttls(mut self, ttls: CacheTtls) -> Self {
+        self.cache_ttls = ttls;
+        self
+    }
+
+    /// Normalize sources per layer before hashing them into cache keys
+    pub fn with_normalization(mut self, normalization: NormalizationPolicies) -> Self {
+        self.cache.set_normalization(normalization.clone());
+        self.normalization = normalization;
+        self
+    }
+
+    /// Record the environment in results and in cache entries written by this engine
+    pub fn with_environment(mut self, environment: EnvironmentSnapshot) -> Self {
+        self.cache.set_environment(environment.clone());
+        self.environment = Some(environment);
+        self
+    }
+
+    /// Root that `verify` and `verify_target` classify target paths against
+    ///
+    /// Without one the current directory is used; `verify_project` always
+    /// uses the root it is given.
+    pub fn with_project_root(mut self, project_root: impl Into<PathBuf>) -> Self {
+        self.project_root = Some(project_root.into());
+        self
+    }
+
+    /// Verify the techniques enabled in `config`
+    pub fn with_config(mut self, config: EffectiveConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Run layers with these plugins
+    ///
+    /// Replaces the plugin manager, so call it before `with_selection_strategy`
+    /// and `with_plugin_stats`.
+    pub fn with_plugin_manager(mut self, plugin_manager: PluginManager) -> Self {
+        self.plugin_manager = plugin_manager;
+        self
+    }
+
+    /// Verify only `layer` of the enabled techniques, or every layer when `None`
+    pub fn with_layer(mut self, layer: Option<Layer>) -> Self {
+        self.only_layer = layer;
+        self
+    }
+
+    /// Run hermetically: plugins write generated files to `scratch_dir`
+    /// instead of next to their inputs
+    pub fn with_scratch_dir(mut self, scratch_dir: PathBuf) -> Self {
+        self.scratch_dir = Some(scratch_dir);
+        self
+    }
+
+    /// Whether the engine runs hermetically, see [`Self::with_scratch_dir`]
+    pub fn is_hermetic(&self) -> bool {
+        self.scratch_dir.is_some()
+    }
+
+    /// Keep verifying dependents of failed targets instead of skipping them
+    pub fn with_keep_going(mut self, keep_going: bool) -> Self {
+        self.keep_going = keep_going;
+        self
+    }
+
+    /// Control which plugin handles each technique
+    pub fn with_selection_strategy(mut self, strategy: SelectionStrategy) -> Self {
+        self.plugin_manager.set_selection_strategy(strategy);
+        self
+    }
+
+    /// Cap and redact tool outputs and artifacts before they are returned
+    pub fn with_artifact_policy(mut self, policy: ArtifactPolicy) -> Self {
+        self.artifact_policy = policy;
+        self
+    }
+
+    /// Spill large tool outputs to compressed files, keeping previews in results
+    pub fn with_artifact_store(mut self, store: ArtifactStore) -> Self {
+        self.artifact_store = Some(store);
+        self
+    }
+
+    /// Skip layers for a target until the layers they require have passed
+    pub fn with_layer_gates(mut self, gates: LayerGates) -> Self {
+        self.layer_gates = gates;
+        self
+    }
+
+    /// Skip layers the tool preflight found no usable tool for
+    pub fn with_unavailable_layers(mut self, layers: Vec<UnavailableLayer>) -> Self {
+        self.unavailable_layers = layers;
+        self
+    }
+
+    /// Fail targets on layers without a usable tool instead of skipping them
+    pub fn with_strict_tools(mut self, strict_tools: bool) -> Self {
+        self.strict_tools = strict_tools;
+        self
+    }
+
+    /// Make target order and randomized tools reproducible
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Call `progress` synchronously for every event of every run
+    pub fn with_progress(
+        mut self,
+        progress: impl Fn(&VerificationEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Record per-phase timings of every run into `timings`
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// Stop runs when `token` is cancelled, e.g. on Ctrl-C
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Rank plugins using, and record outcomes to, a persisted stats file
+    pub fn with_plugin_stats(mut self, path: std::path::PathBuf) -> Self {
+        let start = Instant::now();
+        if let Err(e) = self.plugin_manager.load_plugin_stats(&path) {
+            warn!("Ignoring plugin stats: {}", e);
+        }
+        self.timings
+            .record(Phase::CacheIo, "load plugin stats", start);
+        self.plugin_stats_path = Some(path);
+        self
+    }
+
+    /// Verify every target discovered below a project root
+    pub async fn verify_project(&self, project_root: &Path) -> Result<VerificationResult> {
+        let targets = self.timings.time(Phase::Discovery, "discover targets", || {
+            discover_targets(project_root, &self.discovery)
+        });
+        self.verify_in(project_root, &targets).await
+    }
+
+    /// Verify a single target, e.g. the file just saved in an editor
+    pub async fn verify_target(&self, target: &Target) -> Result<VerificationResult> {
+        self.verify(std::slice::from_ref(target)).await
     }
 
     pub async fn verify(&self, targets: &[Target]) -> Result<VerificationResult> {
-        info!("Starting verification for {} targets", targets.len());
+        self.verify_in(&self.project_root(), targets).await
+    }
 
-        let layer_results = HashMap::new();
-        let start_time = std::time::Instant::now();
+    /// Root targets are classified against when none is passed in
+    fn project_root(&self) -> PathBuf {
+        self.project_root
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default()
+    }
 
-        // TODO: Implement actual verification logic
-        for target in targets {
-            debug!("Verifying target: {:?}", target);
+    async fn verify_in(
+        &self,
+        project_root: &Path,
+        targets: &[Target],
+    ) -> Result<VerificationResult> {
+        info!(
+            "Starting verification for {} targets with seed {}",
+            targets.len(),
+            self.seed
+        );
+        self.emit(VerificationEvent::Started {
+            targets: targets.len(),
+        });
+        for unavailable in &self.unavailable_layers {
+            self.emit(VerificationEvent::LayerUnavailable(unavailable.clone()));
+        }
+
+        // Independent targets run in a seed-determined order so that
+        // order-dependent failures can be reproduced
+        let mut order: Vec<usize> = (0..targets.len()).collect();
+        seed::shuffle(&mut order, self.seed);
+        let targets: Vec<Target> = order.into_iter().map(|i| targets[i].clone()).collect();
+
+        let mut layer_results: HashMap<Layer, LayerResult> = HashMap::new();
+        let mut completed = Vec::new();
+        let mut interrupted = false;
+        let start_time = Instant::now();
+
+        let graph = TargetGraph::build(&targets);
+        let mut failed: HashSet<usize> = HashSet::new();
+        let mut skipped_targets = Vec::new();
+        let mut gated_layers = Vec::new();
+        let mut plugin_breakdowns = Vec::new();
+        let mut target_statuses = BTreeMap::new();
+
+        for idx in graph.topological_order() {
+            let target = &targets[idx];
+            if self.cancellation.is_cancelled() {
+                interrupted = true;
+                break;
+            }
+
+            if !self.keep_going {
+                if let Some(dep) = graph.dependencies(idx).iter().find(|d| failed.contains(d)) {
+                    warn!(
+                        "Skipping {} because dependency {} failed",
+                        target.label(),
+                        targets[*dep].label()
+                    );
+                    let skipped = SkippedTarget {
+                        target: target.label(),
+                        failed_dependency: targets[*dep].label(),
+                    };
+                    self.emit(VerificationEvent::TargetSkipped(skipped.clone()));
+                    target_statuses.insert(target.label(), Status::Skipped);
+                    skipped_targets.push(skipped);
+                    // Dependents of a skipped target are skipped as well
+                    failed.insert(idx);
+                    continue;
+                }
+            }
+
+            self.emit(VerificationEvent::TargetStarted {
+                target: target.label(),
+            });
+            let (status, results) = self
+                .run_target(
+                    project_root,
+                    target,
+                    &mut gated_layers,
+                    &mut plugin_breakdowns,
+                )
+                .await?;
+            for result in results {
+                merge_layer_result(&mut layer_results, result.clone());
+                completed.push((target.clone(), result));
+            }
+            // A target cut short keeps its finished layers but gets no status
+            if self.cancellation.is_cancelled() {
+                warn!("Verification interrupted during {}", target.label());
+                interrupted = true;
+                break;
+            }
+            self.emit(VerificationEvent::TargetFinished {
+                target: target.label(),
+                status,
+            });
+            if status == Status::Error {
+                failed.insert(idx);
+            }
+            target_statuses.insert(target.label(), status);
         }
 
         let total_time = start_time.elapsed();
 
-        Ok(VerificationResult {
-            overall_status: Status::Success,
+        if let Some(path) = &self.plugin_stats_path {
+            self.timings.time(Phase::CacheIo, "save plugin stats", || {
+                if let Err(e) = self.plugin_manager.save_plugin_stats(path) {
+                    warn!("Failed to save plugin stats: {}", e);
+                }
+            });
+        }
+
+        let skip_reasons = self.record_skipped_layers(&mut layer_results, &gated_layers);
+
+        // Under `--strict-tools` a missing tool fails the run even when no
+        // target reached the layer
+        let missing_tools = self.strict_tools && !self.unavailable_layers.is_empty();
+        let mut result = VerificationResult {
+            overall_status: if failed.is_empty() && !missing_tools {
+                Status::Success
+            } else {
+                Status::Error
+            },
             layer_results,
             metrics: VerificationMetrics {
                 total_time,
                 cache_hit_rate: 0.0,
                 memory_usage: 0,
                 test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
             },
             artifacts: Vec::new(),
             timestamp: chrono::Utc::now(),
-        })
+            skipped_targets,
+            gated_layers,
+            unavailable_layers: self.unavailable_layers.clone(),
+            skip_reasons,
+            spec_changes: Vec::new(),
+            traceability: None,
+            budget: None,
+            seed: Some(self.seed),
+            environment: self.environment.clone(),
+            target_statuses,
+            interrupted,
+            project_root: None,
+            link_template: None,
+            plugin_breakdowns,
+            completed,
+            api_surface: None,
+        };
+        if let Some(store) = &self.artifact_store {
+            self.timings.time(Phase::CacheIo, "spill artifacts", || {
+                store.spill_result(&mut result, &self.artifact_policy)
+            });
+        }
+        self.artifact_policy.apply_to_result(&mut result);
+
+        self.emit(VerificationEvent::Finished {
+            status: result.overall_status,
+            total_time,
+        });
+        Ok(result)
+    }
+
+    /// Verify every enabled layer of a target, stopping early when cancelled
+    ///
+    /// A cancelled target returns the layers it finished.
+    async fn run_target(
+        &self,
+        project_root: &Path,
+        target: &Target,
+        gated: &mut Vec<GatedLayer>,
+        breakdowns: &mut Vec<PluginBreakdown>,
+    ) -> Result<(Status, Vec<LayerResult>)> {
+        debug!("Verifying target: {:?}", target);
+
+        let mut outcomes = HashMap::new();
+        let mut results = Vec::new();
+        let mut status = Status::Success;
+        for layer in self.layer_gates.order(&self.enabled_layers()) {
+            if self.cancellation.is_cancelled() {
+                break;
+            }
+            if let Some(required) = self.layer_gates.blocked_by(layer, &outcomes) {
+                info!(
+                    "Skipping {:?} for {} (gated: {:?} did not pass)",
+                    layer,
+                    target.label(),
+                    required
+                );
+                let gated_layer = GatedLayer {
+                    target: target.label(),
+                    layer,
+                    required,
+                };
+                self.emit(VerificationEvent::LayerGated(gated_layer.clone()));
+                gated.push(gated_layer);
+                outcomes.insert(layer, Status::Skipped);
+                continue;
+            }
+            if let Some(unavailable) = self.unavailable_layers.iter().find(|u| u.layer == layer) {
+                if !self.strict_tools {
+                    outcomes.insert(layer, Status::Skipped);
+                    continue;
+                }
+                status = Status::Error;
+                outcomes.insert(layer, Status::Error);
+                results.push(unavailable_tool_result(target, unavailable));
+                continue;
+            }
+
+            if let Some(mut cached) = self.cached_layer(target, layer).await {
+                debug!("Using cached {:?} result for {}", layer, target.label());
+                // Gates see the status the layer was verified with
+                outcomes.insert(layer, cached.status);
+                cached.status = Status::Cached;
+                self.emit(VerificationEvent::LayerFinished {
+                    target: target.label(),
+                    layer,
+                    status: cached.status,
+                    execution_time: cached.execution_time,
+                });
+                results.push(cached);
+                continue;
+            }
+
+            self.emit(VerificationEvent::LayerStarted {
+                target: target.label(),
+                layer,
+            });
+            let start = Instant::now();
+            let result = match self
+                .verify_layer_in(project_root, layer, target, breakdowns)
+                .await
+            {
+                Ok(result) => result,
+                // The layers finished before the cancellation are kept
+                Err(_) if self.cancellation.is_cancelled() => break,
+                Err(e) => return Err(e),
+            };
+            self.timings.record(
+                Phase::PluginExecution,
+                format!("{} {:?}", target.label(), layer),
+                start,
+            );
+            for violation in &result.violations {
+                self.emit(VerificationEvent::ViolationFound {
+                    target: target.label(),
+                    layer,
+                    violation: violation.clone(),
+                });
+            }
+            self.emit(VerificationEvent::LayerFinished {
+                target: target.label(),
+                layer,
+                status: result.status,
+                execution_time: result.execution_time,
+            });
+            self.cache_layer(target, &result);
+            if result.status == Status::Error {
+                status = Status::Error;
+            }
+            outcomes.insert(layer, result.status);
+            results.push(result);
+        }
+
+        Ok((status, results))
+    }
+
+    /// Give layers that never ran a skipped result and say why each was skipped
+    ///
+    /// A layer that ran for some targets keeps its result; one whose tools
+    /// ran but reported nothing to verify is skipped with that reason.
+    fn record_skipped_layers(
+        &self,
+        layer_results: &mut HashMap<Layer, LayerResult>,
+        gated: &[GatedLayer],
+    ) -> HashMap<Layer, SkipReason> {
+        let unavailable = self
+            .unavailable_layers
+            .iter()
+            .filter(|_| !self.strict_tools)
+            .map(|unavailable| (unavailable.layer, SkipReason::ToolUnavailable));
+        let gated = gated.iter().map(|gated| (gated.layer, SkipReason::Gated));
+
+        let mut reasons = HashMap::new();
+        for (layer, reason) in unavailable.chain(gated) {
+            let result = layer_results.entry(layer).or_insert_with(|| LayerResult {
+                layer,
+                status: Status::Skipped,
+                violations: Vec::new(),
+                execution_time: Duration::ZERO,
+                tool_outputs: Vec::new(),
+            });
+            if result.status == Status::Skipped {
+                reasons.entry(layer).or_insert(reason);
+            }
+        }
+        for result in layer_results.values() {
+            if result.status == Status::Skipped {
+                reasons
+                    .entry(result.layer)
+                    .or_insert(SkipReason::NothingToVerify);
+            }
+        }
+        reasons
+    }
+
+    /// Layers of the configured techniques, in first-enabled order
+    fn enabled_layers(&self) -> Vec<Layer> {
+        let mut layers = Vec::new();
+        for technique in self.config.iter().flat_map(|c| &c.enabled_techniques) {
+            let layer = technique.layer();
+            if self.only_layer.is_some_and(|only| only != layer) {
+                continue;
+            }
+            if !layers.contains(&layer) {
+                layers.push(layer);
+            }
+        }
+        layers
     }
 
     pub async fn verify_layer(&self, layer: Layer, target: &Target) -> Result<LayerResult> {
+        self.verify_layer_in(&self.project_root(), layer, target, &mut Vec::new())
+            .await
+    }
+
+    /// Run the layer's enabled techniques through the plugin manager
+    ///
+    /// The manager picks, fans out and retries plugins per its selection
+    /// strategy. Techniques no plugin supports, or the plugin forced with
+    /// `--plugin` does not, are left out; a layer with none left is skipped.
+    /// A plugin that still fails after its retries fails the layer with the
+    /// plugin's error code. Fanned-out techniques add their per-plugin
+    /// outcomes to `breakdowns`.
+    async fn verify_layer_in(
+        &self,
+        project_root: &Path,
+        layer: Layer,
+        target: &Target,
+        breakdowns: &mut Vec<PluginBreakdown>,
+    ) -> Result<LayerResult> {
         info!("Verifying layer {:?} for target {:?}", layer, target);
 
-        // TODO: Implement layer-specific verification
-        Ok(LayerResult {
+        let start = Instant::now();
+        let mut result = LayerResult {
             layer,
-            status: Status::Success,
+            status: Status::Skipped,
             violations: Vec::new(),
-            execution_time: std::time::Duration::from_millis(100),
+            execution_time: Duration::ZERO,
             tool_outputs: Vec::new(),
+        };
+        let Some(config) = &self.config else {
+            return Ok(result);
+        };
+
+        for technique in config
+            .enabled_techniques
+            .iter()
+            .filter(|technique| technique.layer() == layer)
+        {
+            if self.plugin_manager.forced_plugin_skips(technique) {
+                debug!(
+                    "Forced plugin does not support {:?}, leaving it out",
+                    technique
+                );
+                continue;
+            }
+            let input = VerificationInput {
+                target: target.clone(),
+                config: config.clone(),
+                context: VerificationContext {
+                    session_id: String::new(),
+                    working_dir: project_root.to_path_buf(),
+                    cache_dir: std::env::temp_dir(),
+                    timeout: None,
+                    parallel_id: None,
+                    seed: self.seed,
+                    cancellation: self.cancellation.child_token(),
+                    scratch_dir: self.scratch_dir.clone(),
+                },
+            };
+            match self
+                .plugin_manager
+                .verify_layer(layer, technique, input)
+                .await
+            {
+                Ok(fan_out) => {
+                    if self.plugin_manager.fans_out(technique) {
+                        breakdowns.push(PluginBreakdown {
+                            target: target.label(),
+                            layer,
+                            technique: technique.clone(),
+                            outcomes: fan_out.breakdown,
+                            inconsistent: fan_out.inconsistent,
+                        });
+                    }
+                    merge_into(&mut result, fan_out.result);
+                }
+                Err(FerrisProofError::NoPlugin { .. }) => {
+                    debug!("No plugin supports {:?}, leaving it out", technique);
+                }
+                Err(e) if self.cancellation.is_cancelled() => return Err(e.into()),
+                Err(e) => {
+                    warn!("{:?} failed for {}: {}", technique, target.label(), e);
+                    merge_into(&mut result, plugin_failure_result(target, layer, &e));
+                }
+            }
+        }
+        result.execution_time = start.elapsed();
+
+        relax_for_target(target, project_root, &mut result);
+        apply_enforcement(&config.enforcement_policy, config.enforcement, &mut result);
+
+        Ok(result)
+    }
+
+    /// Whether any enabled layer of the target has no usable cached result
+    ///
+    /// Always true without [`Self::with_config_hash`], as the cache is not used.
+    pub fn needs_verification(&self, target: &Target) -> bool {
+        if self.config_hash.is_none() {
+            return true;
+        }
+        self.enabled_layers().into_iter().any(|layer| {
+            self.cache_key(target, layer)
+                .and_then(|key| self.cache.get(&key))
+                .and_then(|entry| usable_cached_result(&entry))
+                .is_none()
         })
     }
 
-    pub fn needs_verification(&self, _target: &Target) -> bool {
-        // TODO: Check cache validity
-        true
+    /// Cached result of a target's layer, unless expired or failed
+    async fn cached_layer(&self, target: &Target, layer: Layer) -> Option<LayerResult> {
+        let key = self.cache_key(target, layer)?;
+        usable_cached_result(&*self.cache.get_async(&key).await?)
+    }
+
+    /// Cache a layer result that passed, with the layer's TTL
+    fn cache_layer(&self, target: &Target, result: &LayerResult) {
+        let Some(config_hash) = &self.config_hash else {
+            return;
+        };
+        if !matches!(result.status, Status::Success | Status::Warning) {
+            return;
+        }
+        self.timings.time(Phase::CacheIo, "store result", || {
+            self.cache.store_results(
+                &[(target.clone(), result.clone())],
+                config_hash.as_ref(),
+                &self.cache_ttls,
+                Some(self.seed),
+            )
+        });
+    }
+
+    fn cache_key(&self, target: &Target, layer: Layer) -> Option<CacheKey> {
+        let config_hash = self.config_hash.as_ref()?;
+        match self.cache.key_for(target, layer, &config_hash(target)) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                debug!("No cache key for {}: {}", target.label(), e);
+                None
+            }
+        }
+    }
+}
+
+/// Failures are verified again rather than served from the cache
+fn usable_cached_result(entry: &CacheEntry) -> Option<LayerResult> {
+    (entry.result.status != Status::Error).then(|| entry.result.clone())
+}
+
+/// Failed layer result reporting a plugin that errored on every attempt
+fn plugin_failure_result(target: &Target, layer: Layer, error: &FerrisProofError) -> LayerResult {
+    let file = target
+        .file()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(target.label()));
+    LayerResult {
+        layer,
+        status: Status::Error,
+        violations: vec![Violation {
+            id: error.code().to_string(),
+            severity: Severity::Error,
+            location: Location::file(file),
+            message: error.to_string(),
+            suggestion: Some(format!("Run `{}` for details.", error.explain_command())),
+            rule: "plugin_failure".to_string(),
+        }],
+        execution_time: Duration::ZERO,
+        tool_outputs: Vec::new(),
+    }
+}
+
+/// Failed layer result reporting a missing tool, as under `--strict-tools`
+fn unavailable_tool_result(target: &Target, unavailable: &UnavailableLayer) -> LayerResult {
+    let file = target
+        .file()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(target.label()));
+    LayerResult {
+        layer: unavailable.layer,
+        status: Status::Error,
+        violations: vec![Violation {
+            id: unavailable.code.clone(),
+            severity: Severity::Error,
+            location: Location::file(file),
+            message: format!("No tool available: {}", unavailable.reason),
+            suggestion: Some(unavailable.hint.clone()),
+            rule: "tool_unavailable".to_string(),
+        }],
+        execution_time: Duration::ZERO,
+        tool_outputs: Vec::new(),
     }
 }
 
@@ -70,6 +918,141 @@ pub enum Target {
     RustFile(std::path::PathBuf),
     FormalSpec(std::path::PathBuf),
     Module(String),
+    /// A single item, e.g. a function or `Type::method`, inside a Rust file
+    ///
+    /// `item_path` is the full path such as `my_mod::parse_header`; its
+    /// trailing segments name the item within `file`.
+    Item {
+        file: std::path::PathBuf,
+        item_path: String,
+    },
+}
+
+/// Cargo target a source file belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Library,
+    Example,
+    Bench,
+}
+
+impl Target {
+    /// Human-readable name used in logs and reports
+    pub fn label(&self) -> String {
+        match self {
+            Target::RustFile(path) | Target::FormalSpec(path) => path.display().to_string(),
+            Target::Module(module_path) => module_path.clone(),
+            Target::Item { item_path, .. } => item_path.clone(),
+        }
+    }
+
+    /// Source file the target is read from; `None` for modules
+    pub fn file(&self) -> Option<&std::path::Path> {
+        match self {
+            Target::RustFile(path) | Target::FormalSpec(path) => Some(path),
+            Target::Item { file, .. } => Some(file),
+            Target::Module(_) => None,
+        }
+    }
+
+    /// Name of an item target without its module path, e.g. `parse_header`
+    pub fn item_name(&self) -> Option<&str> {
+        match self {
+            Target::Item { item_path, .. } => item_path.rsplit("::").next(),
+            _ => None,
+        }
+    }
+
+    /// Cargo target of the file, judged by its path below `project_root`
+    ///
+    /// Directories above the root, e.g. a checkout under `~/examples`, do
+    /// not make a library file an example.
+    pub fn kind(&self, project_root: &Path) -> TargetKind {
+        let (Target::RustFile(path) | Target::Item { file: path, .. }) = self else {
+            return TargetKind::Library;
+        };
+
+        let path = path.strip_prefix(project_root).unwrap_or(path);
+        for component in path.components() {
+            match component.as_os_str().to_str() {
+                Some("examples") => return TargetKind::Example,
+                Some("benches") => return TargetKind::Bench,
+                _ => {}
+            }
+        }
+        TargetKind::Library
+    }
+}
+
+/// Fold one target's layer result into the run's per-layer results
+fn merge_layer_result(layer_results: &mut HashMap<Layer, LayerResult>, result: LayerResult) {
+    match layer_results.get_mut(&result.layer) {
+        Some(merged) => merge_into(merged, result),
+        None => {
+            layer_results.insert(result.layer, result);
+        }
+    }
+}
+
+/// Add `result` to `merged`, keeping the worse of the two statuses
+fn merge_into(merged: &mut LayerResult, result: LayerResult) {
+    if result.status.rank() > merged.status.rank() {
+        merged.status = result.status;
+    }
+    merged.violations.extend(result.violations);
+    merged.execution_time += result.execution_time;
+    merged.tool_outputs.extend(result.tool_outputs);
+}
+
+/// Downgrade errors to warnings for example and bench targets
+///
+/// Only the property-based and type-level layers are relaxed; formal
+/// results are reported as-is wherever they come from.
+pub fn relax_for_target(target: &Target, project_root: &Path, result: &mut LayerResult) {
+    if target.kind(project_root) == TargetKind::Library
+        || !matches!(result.layer, Layer::PropertyBased | Layer::TypeLevel)
+    {
+        return;
+    }
+
+    for violation in &mut result.violations {
+        if matches!(violation.severity, Severity::Error | Severity::Critical) {
+            violation.severity = Severity::Warning;
+        }
+    }
+    if result.status == Status::Error {
+        result.status = Status::Warning;
+    }
+}
+
+/// Re-grade violations by the enforcement policy and derive the layer status
+///
+/// Blocking violations keep their severity, warning ones become `Warning` and
+/// the rest `Info`. Tool failures without violations and skipped layers keep
+/// their status.
+pub fn apply_enforcement(
+    policy: &EnforcementPolicy,
+    mode: EnforcementMode,
+    result: &mut LayerResult,
+) {
+    if result.violations.is_empty() || result.status == Status::Skipped {
+        return;
+    }
+
+    let mut status = Status::Success;
+    for violation in &mut result.violations {
+        match policy.outcome(&violation.severity, mode) {
+            EnforcementMode::Error => status = Status::Error,
+            EnforcementMode::Warning => {
+                violation.severity = Severity::Warning;
+                if status == Status::Success {
+                    status = Status::Warning;
+                }
+            }
+            EnforcementMode::Advisory => violation.severity = Severity::Info,
+        }
+    }
+    result.status = status;
 }
 
 impl Default for VerificationEngine {
@@ -77,3 +1060,357 @@ impl Default for VerificationEngine {
         Self::new()
     }
 }
+
+/// Builder for [`VerificationEngine`]
+///
+/// Without a cache the engine uses the default on-disk cache.
+#[derive(Default)]
+pub struct VerificationEngineBuilder {
+    config: Option<EffectiveConfig>,
+    cache: Option<VerificationCache>,
+    progress: Option<ProgressCallback>,
+    discovery: DiscoveryOptions,
+    keep_going: bool,
+    selection: Option<SelectionStrategy>,
+    artifact_policy: Option<ArtifactPolicy>,
+    artifact_store: Option<ArtifactStore>,
+    plugin_stats_path: Option<PathBuf>,
+    layer_gates: LayerGates,
+    seed: Option<u64>,
+    timings: Timings,
+    cancellation: Option<CancellationToken>,
+    project_root: Option<PathBuf>,
+    plugin_manager: Option<PluginManager>,
+    config_hash: Option<ConfigHasher>,
+    cache_ttls: Option<CacheTtls>,
+}
+
+impl VerificationEngineBuilder {
+    pub fn config(mut self, config: EffectiveConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn cache(mut self, cache: VerificationCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Reuse and cache layer results; see [`VerificationEngine::with_config_hash`]
+    pub fn config_hash(
+        mut self,
+        config_hash: impl Fn(&Target) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.config_hash = Some(Arc::new(config_hash));
+        self
+    }
+
+    pub fn cache_ttls(mut self, ttls: CacheTtls) -> Self {
+        self.cache_ttls = Some(ttls);
+        self
+    }
+
+    pub fn progress(
+        mut self,
+        progress: impl Fn(&VerificationEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Which optional Cargo targets `verify_project` picks up
+    pub fn discovery(mut self, options: DiscoveryOptions) -> Self {
+        self.discovery = options;
+        self
+    }
+
+    pub fn keep_going(mut self, keep_going: bool) -> Self {
+        self.keep_going = keep_going;
+        self
+    }
+
+    pub fn selection_strategy(mut self, strategy: SelectionStrategy) -> Self {
+        self.selection = Some(strategy);
+        self
+    }
+
+    pub fn artifact_policy(mut self, policy: ArtifactPolicy) -> Self {
+        self.artifact_policy = Some(policy);
+        self
+    }
+
+    pub fn artifact_store(mut self, store: ArtifactStore) -> Self {
+        self.artifact_store = Some(store);
+        self
+    }
+
+    pub fn plugin_stats(mut self, path: PathBuf) -> Self {
+        self.plugin_stats_path = Some(path);
+        self
+    }
+
+    pub fn layer_gates(mut self, gates: LayerGates) -> Self {
+        self.layer_gates = gates;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn timings(mut self, timings: Timings) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub fn project_root(mut self, project_root: PathBuf) -> Self {
+        self.project_root = Some(project_root);
+        self
+    }
+
+    /// Plugins the engine runs; without one no layer has a plugin
+    pub fn plugin_manager(mut self, plugin_manager: PluginManager) -> Self {
+        self.plugin_manager = Some(plugin_manager);
+        self
+    }
+
+    pub fn build(self) -> VerificationEngine {
+        let mut engine = VerificationEngine::with_parts(self.cache.unwrap_or_default())
+            .with_keep_going(self.keep_going)
+            .with_timings(self.timings);
+        if let Some(plugin_manager) = self.plugin_manager {
+            engine = engine.with_plugin_manager(plugin_manager);
+        }
+        if let Some(strategy) = self.selection {
+            engine = engine.with_selection_strategy(strategy);
+        }
+        if let Some(policy) = self.artifact_policy {
+            engine = engine.with_artifact_policy(policy);
+        }
+        if let Some(store) = self.artifact_store {
+            engine = engine.with_artifact_store(store);
+        }
+        if let Some(path) = self.plugin_stats_path {
+            engine = engine.with_plugin_stats(path);
+        }
+        engine.config = self.config;
+        engine.discovery = self.discovery;
+        engine.layer_gates = self.layer_gates;
+        engine.progress = self.progress;
+        engine.project_root = self.project_root;
+        if let Some(seed) = self.seed {
+            engine.seed = seed;
+        }
+        if let Some(token) = self.cancellation {
+            engine.cancellation = token;
+        }
+        engine.config_hash = self.config_hash;
+        if let Some(ttls) = self.cache_ttls {
+            engine.cache_ttls = ttls;
+        }
+        engine
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn failing(layer: Layer) -> LayerResult {
+        LayerResult {
+            layer,
+            status: Status::Error,
+            violations: vec![Violation {
+                id: "FP-VR-001".to_string(),
+                severity: Severity::Error,
+                location: Location {
+                    file: PathBuf::from("examples/demo.rs"),
+                    line: Some(3),
+                    column: None,
+                    span: None,
+                },
+                message: "property failed".to_string(),
+                suggestion: None,
+                rule: "property".to_string(),
+            }],
+            execution_time: std::time::Duration::ZERO,
+            tool_outputs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_example_targets_are_relaxed() {
+        let root = Path::new("/work/project");
+        let example = Target::RustFile(root.join("examples/demo.rs"));
+
+        let mut property = failing(Layer::PropertyBased);
+        relax_for_target(&example, root, &mut property);
+        assert_eq!(property.status, Status::Warning);
+        assert_eq!(property.violations[0].severity, Severity::Warning);
+
+        let mut formal = failing(Layer::Formal);
+        relax_for_target(&example, root, &mut formal);
+        assert_eq!(formal.status, Status::Error);
+
+        let mut library = failing(Layer::PropertyBased);
+        relax_for_target(
+            &Target::RustFile(root.join("src/lib.rs")),
+            root,
+            &mut library,
+        );
+        assert_eq!(library.status, Status::Error);
+
+        // Only the path below the project root counts
+        let nested = Path::new("/home/dev/examples/demo-crate");
+        let mut library = failing(Layer::PropertyBased);
+        relax_for_target(
+            &Target::RustFile(nested.join("src/lib.rs")),
+            nested,
+            &mut library,
+        );
+        assert_eq!(library.status, Status::Error);
+    }
+
+    #[test]
+    fn test_enforcement_policy_grades_violations() {
+        let policy = EnforcementPolicy {
+            warning: vec![Severity::Warning, Severity::Error],
+            error: vec![Severity::Critical],
+            ..EnforcementPolicy::default()
+        };
+
+        let mut escalated = failing(Layer::PropertyBased);
+        apply_enforcement(&policy, EnforcementMode::Error, &mut escalated);
+        assert_eq!(escalated.status, Status::Warning);
+        assert_eq!(escalated.violations[0].severity, Severity::Warning);
+
+        let mut critical = failing(Layer::Formal);
+        critical.violations[0].severity = Severity::Critical;
+        apply_enforcement(&policy, EnforcementMode::Error, &mut critical);
+        assert_eq!(critical.status, Status::Error);
+        assert_eq!(critical.violations[0].severity, Severity::Critical);
+
+        // Advisory modules never block, whatever the policy says
+        let mut advisory = failing(Layer::Formal);
+        advisory.violations[0].severity = Severity::Critical;
+        apply_enforcement(&policy, EnforcementMode::Advisory, &mut advisory);
+        assert_eq!(advisory.status, Status::Success);
+        assert_eq!(advisory.violations[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn test_events_serialize_tagged() {
+        let event = VerificationEvent::ViolationFound {
+            target: "src/lib.rs".to_string(),
+            layer: Layer::PropertyBased,
+            violation: failing(Layer::PropertyBased).violations.remove(0),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "violation_found");
+        assert_eq!(json["violation"]["id"], "FP-VR-001");
+
+        let json = serde_json::to_value(VerificationEvent::Started { targets: 3 }).unwrap();
+        assert_eq!(json, serde_json::json!({"event": "started", "targets": 3}));
+    }
+
+    #[tokio::test]
+    async fn test_seed_fixes_target_order() {
+        let targets: Vec<Target> = (0..8)
+            .map(|i| Target::Module(format!("module_{}", i)))
+            .collect();
+
+        let run = |seed: u64| {
+            let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&order);
+            let engine = VerificationEngine::builder()
+                .cache(VerificationCache::in_memory())
+                .seed(seed)
+                .progress(move |event| {
+                    if let VerificationEvent::TargetStarted { target } = event {
+                        recorded.lock().unwrap().push(target.clone());
+                    }
+                })
+                .build();
+            (engine, order)
+        };
+
+        let (first, first_order) = run(7);
+        let result = first.verify(&targets).await.unwrap();
+        assert_eq!(result.seed, Some(7));
+
+        let (second, second_order) = run(7);
+        second.verify(&targets).await.unwrap();
+        assert_eq!(*first_order.lock().unwrap(), *second_order.lock().unwrap());
+        assert_eq!(first_order.lock().unwrap().len(), targets.len());
+    }
+
+    #[tokio::test]
+    async fn test_timings_record_layer_execution() {
+        let config = EffectiveConfig {
+            level: VerificationLevel::Standard,
+            enforcement: EnforcementMode::Warning,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::PropertyTests],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        };
+        let timings = Timings::enabled();
+        let engine = VerificationEngine::builder()
+            .cache(VerificationCache::in_memory())
+            .config(config)
+            .timings(timings.clone())
+            .build();
+        engine
+            .verify(&[Target::Module("core".to_string())])
+            .await
+            .unwrap();
+
+        let spans = engine.timings().spans();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].phase, Phase::PluginExecution);
+        assert_eq!(spans[0].label, "core PropertyBased");
+        assert_eq!(timings.breakdown()[0].spans, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_keeps_completed_targets() {
+        let config = EffectiveConfig {
+            level: VerificationLevel::Standard,
+            enforcement: EnforcementMode::Warning,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::PropertyTests],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        };
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        let engine = VerificationEngine::builder()
+            .cache(VerificationCache::in_memory())
+            .config(config)
+            .cancellation(token)
+            .progress(move |event| {
+                if let VerificationEvent::TargetFinished { .. } = event {
+                    cancel.cancel();
+                }
+            })
+            .build();
+        let targets: Vec<Target> = (0..3)
+            .map(|i| Target::Module(format!("module_{}", i)))
+            .collect();
+
+        let result = engine.verify(&targets).await.unwrap();
+        assert!(result.interrupted);
+        assert_eq!(result.target_statuses.len(), 1);
+        assert_eq!(result.completed.len(), 1);
+        assert!(result.layer_results.contains_key(&Layer::PropertyBased));
+    }
+}
@@ -1,5 +1,6 @@
 use ferris_proof_core::cache::{
-    CacheKey, ConfigHash, ContentHash, ToolVersions, VerificationCache,
+    CacheFilter, CacheKey, ConfigHash, ContentHash, ToolVersions, VerificationCache,
+    ENTRY_SCHEMA_VERSION,
 };
 use ferris_proof_core::types::*;
 use std::time::Duration;
@@ -10,7 +11,7 @@ fn test_cache_basic_operations() {
     let temp_dir = TempDir::new().unwrap();
     let cache_dir = temp_dir.path().join("cache");
 
-    let mut cache = VerificationCache::with_cache_dir(cache_dir);
+    let cache = VerificationCache::with_cache_dir(cache_dir);
 
     // Create test cache key
     let cache_key = CacheKey {
@@ -39,7 +40,11 @@ fn test_cache_basic_operations() {
             execution_time: Duration::from_millis(100),
             memory_usage: 512 * 1024 * 1024, // 512MB
             cache_hit_count: 0,
+            target: None,
+            seed: None,
+            environment: None,
         },
+        artifacts: Vec::new(),
     };
 
     // Test store operation
@@ -59,7 +64,7 @@ fn test_cache_expiration() {
     let temp_dir = TempDir::new().unwrap();
     let cache_dir = temp_dir.path().join("cache");
 
-    let mut cache = VerificationCache::with_cache_dir(cache_dir);
+    let cache = VerificationCache::with_cache_dir(cache_dir);
 
     let cache_key = CacheKey {
         content_hash: ContentHash("test_hash".to_string()),
@@ -87,7 +92,11 @@ fn test_cache_expiration() {
             execution_time: Duration::from_millis(100),
             memory_usage: 512 * 1024 * 1024,
             cache_hit_count: 0,
+            target: None,
+            seed: None,
+            environment: None,
         },
+        artifacts: Vec::new(),
     };
 
     cache.store(cache_key.clone(), cache_entry);
@@ -103,7 +112,7 @@ fn test_cache_persistence() {
     let cache_dir = temp_dir.path().join("cache");
 
     {
-        let mut cache1 = VerificationCache::with_cache_dir(cache_dir.clone());
+        let cache1 = VerificationCache::with_cache_dir(cache_dir.clone());
 
         let cache_key = CacheKey {
             content_hash: ContentHash("persistent_test".to_string()),
@@ -130,7 +139,11 @@ fn test_cache_persistence() {
                 execution_time: Duration::from_millis(200),
                 memory_usage: 1024 * 1024 * 1024, // 1GB
                 cache_hit_count: 0,
+                target: None,
+                seed: None,
+                environment: None,
             },
+            artifacts: Vec::new(),
         };
 
         cache1.store(cache_key.clone(), cache_entry);
@@ -163,6 +176,76 @@ fn test_cache_persistence() {
     }
 }
 
+#[test]
+fn test_tool_outputs_survive_reload() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+
+    let cache_key = CacheKey {
+        content_hash: ContentHash("tool_output_test".to_string()),
+        config_hash: ConfigHash("config_hash".to_string()),
+        tool_versions: ToolVersions {
+            ferris_proof: "0.1.0".to_string(),
+            external_tools: vec![],
+        },
+        layer: Layer::Formal,
+    };
+    let tool_output = |tool: &str, artifact_id: Option<&str>, retries: u32| ToolOutput {
+        tool: tool.to_string(),
+        stdout: format!("{} stdout", tool),
+        stderr: String::new(),
+        exit_code: 12,
+        execution_time: Duration::from_millis(40),
+        artifact_id: artifact_id.map(str::to_string),
+        retries,
+    };
+    let cache_entry = ferris_proof_core::cache::CacheEntry {
+        result: LayerResult {
+            layer: Layer::Formal,
+            status: Status::Error,
+            violations: vec![],
+            execution_time: Duration::from_millis(100),
+            tool_outputs: vec![
+                tool_output("tlc", Some("3f9a0c"), 2),
+                tool_output("apalache", None, 0),
+            ],
+        },
+        timestamp: chrono::Utc::now(),
+        ttl: Duration::from_secs(3600),
+        metadata: ferris_proof_core::cache::CacheMetadata {
+            file_size: 256,
+            execution_time: Duration::from_millis(100),
+            memory_usage: 0,
+            cache_hit_count: 0,
+            target: Some("specs/raft.tla".to_string()),
+            seed: None,
+            environment: None,
+        },
+        artifacts: Vec::new(),
+    };
+    VerificationCache::with_cache_dir(cache_dir.clone()).store(cache_key.clone(), cache_entry);
+
+    let check = |cache: &VerificationCache| {
+        let entry = cache
+            .get(&cache_key)
+            .expect("entry with tool outputs reloads");
+        let outputs = &entry.result.tool_outputs;
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].tool, "tlc");
+        assert_eq!(outputs[0].artifact_id.as_deref(), Some("3f9a0c"));
+        assert_eq!(outputs[0].retries, 2);
+        assert_eq!(outputs[1].stdout, "apalache stdout");
+        assert_eq!(outputs[1].artifact_id, None);
+        assert_eq!(outputs[1].retries, 0);
+    };
+
+    // Read lazily by key, and through a full load
+    check(&VerificationCache::with_cache_dir(cache_dir.clone()));
+    let mut loaded = VerificationCache::with_cache_dir(cache_dir);
+    loaded.load_from_disk().unwrap();
+    check(&loaded);
+}
+
 #[test]
 fn test_cache_cleanup() {
     let temp_dir = TempDir::new().unwrap();
@@ -197,7 +280,11 @@ fn test_cache_cleanup() {
                 execution_time: Duration::from_millis(100),
                 memory_usage: 512 * 1024 * 1024,
                 cache_hit_count: 0,
+                target: None,
+                seed: None,
+                environment: None,
             },
+            artifacts: Vec::new(),
         };
 
         cache.store(cache_key, cache_entry);
@@ -215,7 +302,7 @@ fn test_cache_statistics() {
     let temp_dir = TempDir::new().unwrap();
     let cache_dir = temp_dir.path().join("cache");
 
-    let mut cache = VerificationCache::with_cache_dir(cache_dir);
+    let cache = VerificationCache::with_cache_dir(cache_dir);
 
     // Add some test entries
     for i in 0..3 {
@@ -244,7 +331,11 @@ fn test_cache_statistics() {
                 execution_time: Duration::from_millis(100 * (i + 1)),
                 memory_usage: 512 * 1024 * 1024 * (i + 1),
                 cache_hit_count: 0,
+                target: None,
+                seed: None,
+                environment: None,
             },
+            artifacts: Vec::new(),
         };
 
         cache.store(cache_key, cache_entry);
@@ -304,6 +395,40 @@ fn test_cache_key_hash() {
     assert_ne!(hasher1.finish(), hasher2.finish());
 }
 
+#[test]
+fn test_content_hashes_ignore_line_endings() {
+    use ferris_proof_core::verification::Target;
+
+    let temp_dir = TempDir::new().unwrap();
+    let hash = |name: &str, content: &str, target: fn(std::path::PathBuf) -> Target| {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        CacheKey::compute_content_hash(&target(path)).unwrap()
+    };
+
+    let spec =
+        "---- MODULE Clock ----\nVARIABLE hr \\* hour\n(* tick *)\nNext == hr' = hr + 1\n====\n";
+    let lf = hash("lf.tla", spec, Target::FormalSpec);
+    assert_eq!(
+        hash("crlf.tla", &spec.replace('\n', "\r\n"), Target::FormalSpec),
+        lf
+    );
+    assert_eq!(
+        hash(
+            "commented.tla",
+            &spec.replace("(* tick *)", "\\* advances the clock"),
+            Target::FormalSpec
+        ),
+        lf
+    );
+
+    let source = "/// Tick\npub fn tick(hr: u8) -> u8 {\n    hr + 1\n}\n";
+    assert_eq!(
+        hash("crlf.rs", &source.replace('\n', "\r\n"), Target::RustFile),
+        hash("lf.rs", source, Target::RustFile)
+    );
+}
+
 #[test]
 fn test_cache_invalidation() {
     let temp_dir = TempDir::new().unwrap();
@@ -336,7 +461,11 @@ fn test_cache_invalidation() {
             execution_time: Duration::from_millis(100),
             memory_usage: 512 * 1024 * 1024,
             cache_hit_count: 0,
+            target: None,
+            seed: None,
+            environment: None,
         },
+        artifacts: Vec::new(),
     };
 
     // Store and verify
@@ -382,7 +511,11 @@ fn test_cache_clear() {
                 execution_time: Duration::from_millis(100),
                 memory_usage: 512 * 1024 * 1024,
                 cache_hit_count: 0,
+                target: None,
+                seed: None,
+                environment: None,
             },
+            artifacts: Vec::new(),
         };
 
         cache.store(cache_key, cache_entry);
@@ -399,3 +532,473 @@ fn test_cache_clear() {
     let stats_after = cache.statistics();
     assert_eq!(stats_after.total_entries, 0);
 }
+
+#[test]
+fn test_cache_clear_matching_filters() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cache = VerificationCache::with_cache_dir(temp_dir.path().join("cache"));
+
+    let entries = [
+        ("a", Layer::Formal, "crypto::aes", 10),
+        ("b", Layer::PropertyBased, "src/crypto/rsa.rs", 0),
+        ("c", Layer::Formal, "net::tcp", 10),
+        ("d", Layer::Formal, "net::udp", 0),
+    ];
+    for (hash, layer, target, age_days) in entries {
+        let config_hash = if hash == "d" { "stale" } else { "config_hash" };
+        let key = CacheKey {
+            content_hash: ContentHash(hash.to_string()),
+            config_hash: ConfigHash(config_hash.to_string()),
+            tool_versions: ToolVersions {
+                ferris_proof: "0.1.0".to_string(),
+                external_tools: vec![],
+            },
+            layer,
+        };
+        let entry = ferris_proof_core::cache::CacheEntry {
+            result: LayerResult {
+                layer,
+                status: Status::Success,
+                violations: vec![],
+                execution_time: Duration::from_millis(100),
+                tool_outputs: vec![],
+            },
+            timestamp: chrono::Utc::now() - chrono::Duration::days(age_days),
+            ttl: Duration::from_secs(30 * 24 * 3600),
+            metadata: ferris_proof_core::cache::CacheMetadata {
+                file_size: 1024,
+                execution_time: Duration::from_millis(100),
+                memory_usage: 0,
+                cache_hit_count: 0,
+                target: Some(target.to_string()),
+                seed: None,
+                environment: None,
+            },
+            artifacts: Vec::new(),
+        };
+        cache.store(key, entry);
+    }
+
+    let stale_config = CacheFilter {
+        config_hash: Some("stale".to_string()),
+        ..CacheFilter::default()
+    };
+    assert_eq!(cache.clear_matching(&stale_config), 1);
+
+    let crypto_formal = CacheFilter {
+        layer: Some(Layer::Formal),
+        module: Some("crypto::*".to_string()),
+        older_than: None,
+        config_hash: None,
+    };
+    assert_eq!(cache.clear_matching(&crypto_formal), 1);
+
+    let old = CacheFilter {
+        older_than: Some(Duration::from_secs(7 * 24 * 3600)),
+        ..CacheFilter::default()
+    };
+    assert_eq!(cache.clear_matching(&old), 1);
+
+    let crypto = CacheFilter {
+        module: Some("crypto::*".to_string()),
+        ..CacheFilter::default()
+    };
+    assert_eq!(cache.clear_matching(&crypto), 1);
+    assert_eq!(cache.statistics().total_entries, 0);
+}
+
+#[test]
+fn test_module_clear_matches_stored_results() {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("src");
+    std::fs::create_dir_all(src.join("crypto")).unwrap();
+    std::fs::create_dir_all(src.join("net")).unwrap();
+    std::fs::write(src.join("crypto/aes.rs"), "pub fn encrypt() {}\n").unwrap();
+    std::fs::write(src.join("net/tcp.rs"), "pub fn connect() {}\n").unwrap();
+
+    let results: Vec<_> = ["crypto/aes.rs", "net/tcp.rs"]
+        .into_iter()
+        .map(|file| {
+            (
+                ferris_proof_core::verification::Target::RustFile(src.join(file)),
+                LayerResult {
+                    layer: Layer::Formal,
+                    status: Status::Success,
+                    violations: vec![],
+                    execution_time: Duration::from_millis(100),
+                    tool_outputs: vec![],
+                },
+            )
+        })
+        .collect();
+    let cache_dir = temp_dir.path().join("cache");
+    let cache = VerificationCache::with_cache_dir(cache_dir.clone());
+    let stored = cache.store_results(
+        &results,
+        |_| "config_hash".to_string(),
+        &ferris_proof_core::cache::CacheTtls::default(),
+        None,
+    );
+    assert_eq!(stored, 2);
+
+    // A later `cache clear` only sees what was written to disk
+    let mut reloaded = VerificationCache::with_cache_dir(cache_dir);
+    reloaded.load_from_disk().unwrap();
+    let crypto = CacheFilter {
+        module: Some("crypto::*".to_string()),
+        ..CacheFilter::default()
+    };
+    assert_eq!(reloaded.clear_matching(&crypto), 1);
+    assert_eq!(reloaded.clear_matching(&crypto), 0);
+
+    let net = CacheFilter {
+        module: Some("net::tcp".to_string()),
+        ..CacheFilter::default()
+    };
+    assert_eq!(reloaded.clear_matching(&net), 1);
+}
+
+#[test]
+fn test_regression_artifacts_survive_reload() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+
+    let cache_key = CacheKey {
+        content_hash: ContentHash("prop_hash".to_string()),
+        config_hash: ConfigHash("config_hash".to_string()),
+        tool_versions: ToolVersions {
+            ferris_proof: "0.1.0".to_string(),
+            external_tools: vec![],
+        },
+        layer: Layer::PropertyBased,
+    };
+    let regression = Artifact {
+        path: "proptest-regressions/tests/prop_tests.txt".into(),
+        artifact_type: ArtifactType::Regression,
+        content: "cc 4f2a9b # shrinks to input = 0".to_string(),
+    };
+    let cache_entry = ferris_proof_core::cache::CacheEntry {
+        result: LayerResult {
+            layer: Layer::PropertyBased,
+            status: Status::Error,
+            violations: vec![Violation {
+                id: "PROPTEST_FAILURE".to_string(),
+                severity: Severity::Error,
+                location: Location::file("tests/prop_tests.rs"),
+                message: "Property-based tests failed".to_string(),
+                suggestion: None,
+                rule: "proptest_verification".to_string(),
+            }],
+            execution_time: Duration::from_millis(100),
+            tool_outputs: vec![],
+        },
+        timestamp: chrono::Utc::now(),
+        ttl: Duration::from_secs(3600),
+        metadata: ferris_proof_core::cache::CacheMetadata {
+            file_size: 1024,
+            execution_time: Duration::from_millis(100),
+            memory_usage: 0,
+            cache_hit_count: 0,
+            target: Some("tests/prop_tests.rs".to_string()),
+            seed: None,
+            environment: None,
+        },
+        artifacts: vec![regression],
+    };
+    VerificationCache::with_cache_dir(cache_dir.clone()).store(cache_key, cache_entry);
+
+    let mut reloaded = VerificationCache::with_cache_dir(cache_dir);
+    reloaded.load_from_disk().unwrap();
+
+    let entries = reloaded.entries_with_violation("PROPTEST_FAILURE");
+    assert_eq!(entries.len(), 1);
+    assert!(matches!(
+        entries[0].artifacts[0].artifact_type,
+        ArtifactType::Regression
+    ));
+    assert!(entries[0].artifacts[0].content.starts_with("cc 4f2a9b"));
+    assert!(reloaded.entries_with_violation("FP-MD-002").is_empty());
+}
+
+#[test]
+fn test_legacy_entries_are_discarded_on_load() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+
+    let cache_key = CacheKey {
+        content_hash: ContentHash("legacy_hash".to_string()),
+        config_hash: ConfigHash("config_hash".to_string()),
+        tool_versions: ToolVersions {
+            ferris_proof: "0.1.0".to_string(),
+            external_tools: vec![("rustc".to_string(), "1.83.0".to_string())],
+        },
+        layer: Layer::Formal,
+    };
+    let cache_entry = ferris_proof_core::cache::CacheEntry {
+        result: LayerResult {
+            layer: Layer::Formal,
+            status: Status::Success,
+            violations: vec![],
+            execution_time: Duration::from_millis(100),
+            tool_outputs: vec![],
+        },
+        timestamp: chrono::Utc::now(),
+        ttl: Duration::from_secs(3600),
+        metadata: ferris_proof_core::cache::CacheMetadata {
+            file_size: 512,
+            execution_time: Duration::from_millis(100),
+            memory_usage: 0,
+            cache_hit_count: 0,
+            target: None,
+            seed: None,
+            environment: None,
+        },
+        artifacts: Vec::new(),
+    };
+
+    // Headerless entry under a DefaultHasher-derived name, as older versions wrote them
+    let legacy_path = cache_dir.join("9c").join("9c41d07e5b2a8f13.cache");
+    std::fs::create_dir_all(legacy_path.parent().unwrap()).unwrap();
+    let serialized = bincode::serialize(&(&cache_key, &cache_entry)).unwrap();
+    std::fs::write(
+        &legacy_path,
+        zstd::encode_all(serialized.as_slice(), 3).unwrap(),
+    )
+    .unwrap();
+
+    // Headerless entries predate the current layout and cannot be read reliably
+    let mut cache = VerificationCache::with_cache_dir(cache_dir.clone());
+    cache.load_from_disk().unwrap();
+    assert!(cache.get(&cache_key).is_none());
+    assert!(!legacy_path.exists());
+}
+
+/// Cache files written by every readable schema version, for the compatibility tests
+const SCHEMA_FIXTURES: &[(u16, &str)] = &[(2, "schema-2.cache")];
+
+/// Cache files of schemas whose tool outputs bincode cannot read back reliably
+const STALE_SCHEMA_FIXTURES: &[&str] = &["schema-0.cache", "schema-1.cache"];
+
+#[test]
+fn test_previous_schema_fixtures_load_and_migrate() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/cache");
+
+    let copied: Vec<_> = SCHEMA_FIXTURES
+        .iter()
+        .map(|(_, name)| {
+            let path = cache_dir.join("00").join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::copy(fixtures.join(name), &path).unwrap();
+            path
+        })
+        .collect();
+
+    let mut cache = VerificationCache::with_cache_dir(cache_dir.clone());
+    cache.load_from_disk().unwrap();
+    for (schema, _) in SCHEMA_FIXTURES {
+        let entries = cache.entries_with_violation(&format!("FIXTURE_SCHEMA_{}", schema));
+        assert_eq!(entries.len(), 1, "schema {} fixture not loaded", schema);
+        assert_eq!(entries[0].result.status, Status::Error);
+        assert_eq!(entries[0].metadata.target.as_deref(), Some("src/lib.rs"));
+        if *schema >= 2 {
+            let outputs = &entries[0].result.tool_outputs;
+            assert_eq!(outputs.len(), 2);
+            assert_eq!(
+                (outputs[0].artifact_id.as_deref(), outputs[0].retries),
+                (None, 0)
+            );
+            assert_eq!(outputs[1].artifact_id.as_deref(), Some("a1b2c3"));
+        }
+    }
+
+    // Rewritten under their fingerprints in the current schema
+    assert!(copied.iter().all(|path| !path.exists()));
+    let mut header = b"FPCE".to_vec();
+    header.extend_from_slice(&ENTRY_SCHEMA_VERSION.to_le_bytes());
+    let mut reloaded = VerificationCache::with_cache_dir(cache_dir.clone());
+    reloaded.load_from_disk().unwrap();
+    assert_eq!(reloaded.statistics().total_entries, SCHEMA_FIXTURES.len());
+    for entry in walk_cache_files(&cache_dir) {
+        assert!(std::fs::read(&entry).unwrap().starts_with(&header));
+    }
+}
+
+#[test]
+fn test_stale_schema_fixtures_are_discarded() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/cache");
+
+    for name in STALE_SCHEMA_FIXTURES {
+        let path = cache_dir.join("00").join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::copy(fixtures.join(name), &path).unwrap();
+    }
+
+    // Rejected as stale rather than misread, and removed
+    let mut cache = VerificationCache::with_cache_dir(cache_dir.clone());
+    cache.load_from_disk().unwrap();
+    assert_eq!(cache.statistics().total_entries, 0);
+    assert!(walk_cache_files(&cache_dir).is_empty());
+}
+
+#[test]
+fn test_newer_schema_entries_are_kept_as_misses() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+
+    let path = cache_dir.join("ff").join("from-the-future.cache");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut content = b"FPCE".to_vec();
+    content.extend_from_slice(&(ENTRY_SCHEMA_VERSION + 1).to_le_bytes());
+    content.extend_from_slice(b"layout this version cannot read");
+    std::fs::write(&path, &content).unwrap();
+
+    let mut cache = VerificationCache::with_cache_dir(cache_dir);
+    cache.load_from_disk().unwrap();
+    assert_eq!(cache.statistics().total_entries, 0);
+    assert_eq!(std::fs::read(&path).unwrap(), content);
+}
+
+fn walk_cache_files(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(walk_cache_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "cache") {
+            files.push(path);
+        }
+    }
+    files
+}
+
+fn locking_test_entry(key: &CacheKey) -> (CacheKey, ferris_proof_core::cache::CacheEntry) {
+    let entry = ferris_proof_core::cache::CacheEntry {
+        result: LayerResult {
+            layer: key.layer,
+            status: Status::Success,
+            violations: vec![],
+            execution_time: Duration::from_millis(10),
+            tool_outputs: vec![],
+        },
+        timestamp: chrono::Utc::now(),
+        ttl: Duration::from_secs(3600),
+        metadata: ferris_proof_core::cache::CacheMetadata {
+            file_size: 64,
+            execution_time: Duration::from_millis(10),
+            memory_usage: 0,
+            cache_hit_count: 0,
+            target: None,
+            seed: None,
+            environment: None,
+        },
+        artifacts: Vec::new(),
+    };
+    (key.clone(), entry)
+}
+
+fn locking_test_key(name: &str) -> CacheKey {
+    CacheKey {
+        content_hash: ContentHash(name.to_string()),
+        config_hash: ConfigHash("config_hash".to_string()),
+        tool_versions: ToolVersions {
+            ferris_proof: "0.1.0".to_string(),
+            external_tools: vec![],
+        },
+        layer: Layer::PropertyBased,
+    }
+}
+
+#[test]
+fn test_locked_cache_degrades_to_misses() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+    let key = locking_test_key("locked");
+
+    let cache = VerificationCache::with_cache_dir(cache_dir.clone());
+    let (key, entry) = locking_test_entry(&key);
+    cache.store(key.clone(), entry);
+
+    // Another run holding the lock exclusively, as during compaction
+    let lock_file = std::fs::OpenOptions::new()
+        .write(true)
+        .open(cache_dir.join(".lock"))
+        .unwrap();
+    let mut lock = fd_lock::RwLock::new(lock_file);
+    let guard = lock.try_write().unwrap();
+
+    let mut blocked = VerificationCache::with_cache_dir(cache_dir.clone());
+    blocked.load_from_disk().unwrap();
+    assert_eq!(blocked.statistics().total_entries, 0);
+    assert!(blocked.get(&key).is_none());
+    assert!(matches!(
+        blocked.compact(),
+        Err(ferris_proof_core::FerrisProofError::CacheLocked { .. })
+    ));
+    blocked.clear();
+    drop(guard);
+
+    // The clear above gave up, so the entry is still there
+    let mut reloaded = VerificationCache::with_cache_dir(cache_dir);
+    reloaded.load_from_disk().unwrap();
+    assert!(reloaded.get(&key).is_some());
+}
+
+#[test]
+fn test_concurrent_runs_do_not_corrupt_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+
+    let handles: Vec<_> = (0..8)
+        .map(|run| {
+            let cache_dir = cache_dir.clone();
+            std::thread::spawn(move || {
+                let mut cache = VerificationCache::with_cache_dir(cache_dir);
+                cache.load_from_disk().unwrap();
+                for i in 0..10 {
+                    // Half the keys are shared, so runs race on the same files
+                    let name = format!("key-{}", if i % 2 == 0 { i } else { run * 100 + i });
+                    let (key, entry) = locking_test_entry(&locking_test_key(&name));
+                    cache.store(key, entry);
+                }
+                if run == 0 {
+                    cache.compact().unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let mut reloaded = VerificationCache::with_cache_dir(cache_dir.clone());
+    reloaded.load_from_disk().unwrap();
+    assert_eq!(reloaded.statistics().total_entries, 5 + 8 * 5);
+    assert_eq!(walk_cache_files(&cache_dir).len(), 5 + 8 * 5);
+}
+
+#[tokio::test]
+async fn test_entries_load_lazily_by_key() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join("cache");
+
+    let writer = VerificationCache::with_cache_dir(cache_dir.clone());
+    let keys: Vec<CacheKey> = (0..50)
+        .map(|i| locking_test_key(&format!("lazy-{}", i)))
+        .collect();
+    for key in &keys {
+        let (key, entry) = locking_test_entry(key);
+        writer.store(key, entry);
+    }
+
+    // Nothing is read until a key is looked up
+    let cache = VerificationCache::with_cache_dir(cache_dir);
+    assert_eq!(cache.statistics().total_entries, 0);
+    assert!(cache.get(&keys[0]).is_some());
+    assert!(cache.get_async(&keys[1]).await.is_some());
+    assert!(cache.get(&locking_test_key("never-stored")).is_none());
+    assert_eq!(cache.statistics().total_entries, 2);
+}
@@ -1,6 +1,7 @@
 use ferris_proof_core::cache::{
     CacheEntry, CacheKey, CacheMetadata, ConfigHash, ContentHash, ToolVersions, VerificationCache,
 };
+use ferris_proof_core::normalization::NormalizationPolicy;
 use ferris_proof_core::types::*;
 use ferris_proof_core::verification::Target;
 use proptest::prelude::*;
@@ -41,6 +42,7 @@ prop_compose! {
         Layer::TypeLevel,
         Layer::PropertyBased,
         Layer::Monitoring,
+        Layer::ApiSurface,
     ])) -> Layer {
         layer
     }
@@ -101,6 +103,9 @@ prop_compose! {
             execution_time: Duration::from_millis(execution_time_ms),
             memory_usage,
             cache_hit_count,
+            target: None,
+            seed: None,
+            environment: None,
         }
     }
 }
@@ -108,7 +113,9 @@ prop_compose! {
 prop_compose! {
     fn arb_cache_entry()(
         result in arb_layer_result(),
-        ttl_secs in 1u64..86400u64, // 1 second to 1 day
+        // An hour to a year, so no entry expires while a case runs; expiry
+        // itself is checked against fixed instants below
+        ttl_secs in 3600u64..31_536_000u64,
         metadata in arb_cache_metadata(),
     ) -> CacheEntry {
         CacheEntry {
@@ -116,6 +123,7 @@ prop_compose! {
             timestamp: chrono::Utc::now(),
             ttl: Duration::from_secs(ttl_secs),
             metadata,
+            artifacts: Vec::new(),
         }
     }
 }
@@ -133,7 +141,7 @@ proptest! {
     ) {
         let temp_dir = TempDir::new().unwrap();
         let cache_dir = temp_dir.path().join("cache");
-        let mut cache = VerificationCache::with_cache_dir(cache_dir);
+        let cache = VerificationCache::with_cache_dir(cache_dir);
 
         // Store the cache entry
         cache.store(cache_key.clone(), cache_entry.clone());
@@ -156,6 +164,17 @@ proptest! {
         prop_assert_eq!(retrieved_entry.metadata.memory_usage, cache_entry.metadata.memory_usage);
     }
 
+    /// Property: An entry is valid until exactly its TTL has elapsed
+    #[test]
+    fn cache_ttl_expiry_property(cache_entry in arb_cache_entry()) {
+        let ttl = chrono::Duration::from_std(cache_entry.ttl).unwrap();
+        let stored = cache_entry.timestamp;
+
+        prop_assert!(!cache_entry.is_expired_at(stored));
+        prop_assert!(!cache_entry.is_expired_at(stored + ttl - chrono::Duration::milliseconds(1)));
+        prop_assert!(cache_entry.is_expired_at(stored + ttl));
+    }
+
     /// Property: Cache keys with different content should produce different cache entries
     #[test]
     fn cache_key_uniqueness_property(
@@ -169,7 +188,7 @@ proptest! {
 
         let temp_dir = TempDir::new().unwrap();
         let cache_dir = temp_dir.path().join("cache");
-        let mut cache = VerificationCache::with_cache_dir(cache_dir);
+        let cache = VerificationCache::with_cache_dir(cache_dir);
 
         // Store two different entries with different keys
         cache.store(key1.clone(), entry1.clone());
@@ -228,7 +247,7 @@ proptest! {
     ) {
         let temp_dir = TempDir::new().unwrap();
         let cache_dir = temp_dir.path().join("cache");
-        let mut cache = VerificationCache::with_cache_dir(cache_dir);
+        let cache = VerificationCache::with_cache_dir(cache_dir);
 
         // Store all entries
         let mut expected_total_size = 0u64;
@@ -262,7 +281,7 @@ proptest! {
 
         // First cache instance - store and save
         {
-            let mut cache1 = VerificationCache::with_cache_dir(cache_dir.clone());
+            let cache1 = VerificationCache::with_cache_dir(cache_dir.clone());
             cache1.store(cache_key.clone(), cache_entry.clone());
             cache1.save_to_disk().unwrap();
         }
@@ -353,4 +372,77 @@ mod cache_content_hash_tests {
             }
         }
     }
+
+    #[test]
+    fn item_content_hash_only_tracks_the_item() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("codec.rs");
+        let item = |item_path: &str| Target::Item {
+            file: file.clone(),
+            item_path: item_path.to_string(),
+        };
+        let hash = |item_path: &str| {
+            CacheKey::new(&item(item_path), Layer::PropertyBased, "test_config")
+                .unwrap()
+                .content_hash
+        };
+
+        fs::write(
+            &file,
+            "fn parse_header() -> u8 { 1 }\nfn other() {}\nstruct Frame;\nimpl Frame { fn len(&self) -> usize { 0 } }\n",
+        )
+        .unwrap();
+        let header = hash("codec::parse_header");
+        let method = hash("codec::Frame::len");
+
+        fs::write(
+            &file,
+            "fn parse_header() -> u8 { 1 }\nfn other() { let _ = 2; }\nstruct Frame;\nimpl Frame { fn len(&self) -> usize { 1 } }\n",
+        )
+        .unwrap();
+        assert_eq!(hash("codec::parse_header"), header);
+        assert_ne!(hash("codec::Frame::len"), method);
+        assert!(CacheKey::new(&item("codec::missing"), Layer::PropertyBased, "").is_err());
+    }
+
+    #[test]
+    fn normalization_policy_decides_what_invalidates() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("lib.rs");
+        let target = Target::RustFile(file.clone());
+        let signatures_only = NormalizationPolicy {
+            ignore_bodies: true,
+            ..NormalizationPolicy::default()
+        };
+        let hash = |policy: &NormalizationPolicy| {
+            CacheKey::with_policy(&target, Layer::Formal, "test_config", policy)
+                .unwrap()
+                .content_hash
+        };
+
+        fs::write(
+            &file,
+            "fn settle(x: u8) -> u8 { x }\nimpl A { fn f(&self) { g() } }\n",
+        )
+        .unwrap();
+        let full = hash(&NormalizationPolicy::default());
+        let signatures = hash(&signatures_only);
+        // The policy is part of the key
+        assert_ne!(full, signatures);
+
+        fs::write(
+            &file,
+            "fn settle(x: u8) -> u8 { x + 1 }\nimpl A { fn f(&self) { h() } }\n",
+        )
+        .unwrap();
+        assert_ne!(hash(&NormalizationPolicy::default()), full);
+        assert_eq!(hash(&signatures_only), signatures);
+
+        fs::write(
+            &file,
+            "fn settle(x: u16) -> u8 { 0 }\nimpl A { fn f(&self) { h() } }\n",
+        )
+        .unwrap();
+        assert_ne!(hash(&signatures_only), signatures);
+    }
 }
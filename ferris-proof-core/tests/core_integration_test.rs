@@ -1,9 +1,21 @@
 use ferris_proof_config::ConfigManager;
-use ferris_proof_core::plugins::VerificationPlugin;
-use ferris_proof_core::types::VerificationLevel;
-use ferris_proof_core::{PluginManager, VerificationEngine};
+use ferris_proof_core::cache::{CacheEntry, CacheMetadata, CacheTtls};
+use ferris_proof_core::plugins::{
+    parse_backoff, run_tool, CancellationToken, EffectiveConfig, PluginMetadata, SelectionStrategy,
+    StructuredResult, ToolInfo, VerificationContext, VerificationInput, VerificationOutput,
+    VerificationPlugin, VersionRange,
+};
+use ferris_proof_core::types::*;
+use ferris_proof_core::verification::Target;
+use ferris_proof_core::{
+    FerrisProofError, PluginManager, RendererRegistry, Result, VerificationCache,
+    VerificationEngine, VerificationEvent,
+};
 use ferris_proof_plugins::{ProptestPlugin, TlaPlusPlugin};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tempfile::TempDir;
 
 /// Integration test to verify all core systems work together
@@ -30,7 +42,7 @@ enabled_techniques = ["TypeSafety", "PropertyTests"]
 
 [tools.proptest]
 cases = 1000
-max_shrink_iterations = 10000
+max_shrink_iters = 10000
 
 [features]
 cache_enabled = true
@@ -136,6 +148,965 @@ fn test_plugin_system_integration() {
     println!("🎉 Plugin system integration test passed!");
 }
 
+#[test]
+fn test_plugin_selection_strategy() {
+    let mut plugin_manager = PluginManager::new();
+    plugin_manager
+        .register_plugin(Box::new(ProptestPlugin::new()))
+        .unwrap();
+    plugin_manager
+        .register_plugin(Box::new(TlaPlusPlugin::new()))
+        .unwrap();
+
+    let ranked = plugin_manager
+        .rank_plugins(&Technique::PropertyTests)
+        .unwrap();
+    assert_eq!(ranked, vec!["proptest".to_string()]);
+    assert!(plugin_manager
+        .rank_plugins(&Technique::ConcurrencyTesting)
+        .unwrap()
+        .is_empty());
+
+    // A forced plugin must support the technique
+    plugin_manager.set_selection_strategy(SelectionStrategy {
+        forced: Some("tla-plus".to_string()),
+        ..Default::default()
+    });
+    assert!(plugin_manager
+        .rank_plugins(&Technique::PropertyTests)
+        .is_err());
+    assert_eq!(
+        plugin_manager
+            .rank_plugins(&Technique::ModelChecking)
+            .unwrap(),
+        vec!["tla-plus".to_string()]
+    );
+
+    // Disabled plugins are never ranked, even when forced
+    plugin_manager.set_selection_strategy(SelectionStrategy {
+        disabled: vec!["proptest".to_string()],
+        ..Default::default()
+    });
+    assert!(!plugin_manager.is_enabled("proptest"));
+    assert!(plugin_manager
+        .rank_plugins(&Technique::PropertyTests)
+        .unwrap()
+        .is_empty());
+    plugin_manager.set_selection_strategy(SelectionStrategy {
+        forced: Some("proptest".to_string()),
+        disabled: vec!["proptest".to_string()],
+        ..Default::default()
+    });
+    assert!(plugin_manager
+        .rank_plugins(&Technique::PropertyTests)
+        .is_err());
+
+    // Stats round-trip through the cache file
+    let temp_dir = TempDir::new().unwrap();
+    let stats_path = temp_dir.path().join("plugin-stats.json");
+    plugin_manager.save_plugin_stats(&stats_path).unwrap();
+    plugin_manager.load_plugin_stats(&stats_path).unwrap();
+    assert_eq!(plugin_manager.plugin_stats("proptest").runs, 0);
+}
+
+/// Stand-in for a tool verifying `technique` that always reports the same
+/// status, with one violation when it fails, and echoes its `tool_config` as
+/// stdout, after failing `spurious_failures` times: running out of memory, or
+/// as its `tool_config` `failure` says, crashing or missing its tool
+struct FixedPlugin {
+    name: &'static str,
+    technique: Technique,
+    status: Status,
+    spurious_failures: AtomicU32,
+    availability_checks: Arc<AtomicU32>,
+}
+
+impl VerificationPlugin for FixedPlugin {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn version(&self) -> &str {
+        "0.1.0"
+    }
+
+    fn supported_techniques(&self) -> Vec<Technique> {
+        vec![self.technique.clone()]
+    }
+
+    fn supported_versions(&self) -> VersionRange {
+        VersionRange {
+            min: None,
+            max: None,
+            requires_exact: None,
+        }
+    }
+
+    fn check_availability(&self) -> Result<ToolInfo> {
+        self.availability_checks.fetch_add(1, Ordering::SeqCst);
+        Ok(ToolInfo {
+            name: self.name.to_string(),
+            version: "0.1.0".to_string(),
+            path: PathBuf::from(self.name),
+            available: true,
+            capabilities: Vec::new(),
+        })
+    }
+
+    fn verify(&self, input: VerificationInput) -> Result<VerificationOutput> {
+        if self
+            .spurious_failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            match input.config.tool_config["failure"].as_str() {
+                Some("crash") => return Ok(fixed_output(self.name, Status::Error, Vec::new(), "")),
+                Some("missing") => {
+                    return Err(FerrisProofError::tool_not_found(self.name, "not in PATH"))
+                }
+                _ => {
+                    return Err(FerrisProofError::plugin(
+                        self.name,
+                        "java.lang.OutOfMemoryError: Java heap space",
+                    ))
+                }
+            }
+        }
+        let violations = if self.status == Status::Error {
+            vec![Violation {
+                id: "FIXED_VIOLATION".to_string(),
+                severity: Severity::Error,
+                location: Location::file(PathBuf::from(input.target.label())),
+                message: format!("{} found a counterexample", self.name),
+                suggestion: None,
+                rule: "fixed".to_string(),
+            }]
+        } else {
+            Vec::new()
+        };
+        Ok(fixed_output(
+            self.name,
+            self.status,
+            violations,
+            &input.config.tool_config.to_string(),
+        ))
+    }
+
+    fn parse_output(&self, _raw_output: &str) -> Result<StructuredResult> {
+        unimplemented!()
+    }
+
+    fn metadata(&self) -> PluginMetadata {
+        unimplemented!()
+    }
+
+    fn initialize(&mut self, _config: &serde_json::Value) -> Result<()> {
+        Ok(())
+    }
+
+    fn cleanup(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn fixed_output(
+    tool: &str,
+    status: Status,
+    violations: Vec<Violation>,
+    stdout: &str,
+) -> VerificationOutput {
+    VerificationOutput {
+        status,
+        violations,
+        artifacts: Vec::new(),
+        tool_output: ToolOutput {
+            tool: tool.to_string(),
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            exit_code: 0,
+            execution_time: std::time::Duration::ZERO,
+            artifact_id: None,
+            retries: 0,
+        },
+        metrics: VerificationMetrics {
+            total_time: std::time::Duration::ZERO,
+            cache_hit_rate: 0.0,
+            memory_usage: 0,
+            test_cases_executed: 0,
+            max_verified_scope: None,
+            tool_settings: Default::default(),
+        },
+    }
+}
+
+#[tokio::test]
+async fn test_engine_runs_the_selected_plugin() {
+    let run = |strategy: SelectionStrategy| async move {
+        let mut plugin_manager = PluginManager::new();
+        for (name, technique, status) in [
+            ("kani", Technique::ModelChecking, Status::Error),
+            ("tla-plus", Technique::ModelChecking, Status::Success),
+            ("clippy", Technique::TypeSafety, Status::Success),
+        ] {
+            plugin_manager
+                .register_plugin(Box::new(FixedPlugin {
+                    name,
+                    technique,
+                    status,
+                    spurious_failures: AtomicU32::new(0),
+                    availability_checks: Default::default(),
+                }))
+                .unwrap();
+        }
+        let engine = VerificationEngine::builder()
+            .config(EffectiveConfig {
+                level: VerificationLevel::Formal,
+                enforcement: EnforcementMode::Error,
+                enforcement_policy: EnforcementPolicy::default(),
+                enabled_techniques: vec![Technique::TypeSafety, Technique::ModelChecking],
+                tool_config: serde_json::Value::Null,
+                plugin_tools: Default::default(),
+                memory_budget: None,
+            })
+            .cache(VerificationCache::in_memory())
+            .plugin_manager(plugin_manager)
+            .selection_strategy(strategy)
+            .build();
+        engine
+            .verify(&[Target::Module("queue".to_string())])
+            .await
+            .unwrap()
+    };
+    let tools = |result: &VerificationResult, layer: Layer| -> Vec<String> {
+        result.layer_results[&layer]
+            .tool_outputs
+            .iter()
+            .map(|output| output.tool.clone())
+            .collect()
+    };
+
+    let preferred = run(SelectionStrategy {
+        prefer: vec!["tla-plus".to_string()],
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(preferred.overall_status, Status::Success);
+    assert_eq!(tools(&preferred, Layer::Formal), vec!["tla-plus"]);
+    assert_eq!(tools(&preferred, Layer::TypeLevel), vec!["clippy"]);
+
+    // `--plugin` wins over the preference and leaves out what it cannot verify
+    let forced = run(SelectionStrategy {
+        forced: Some("kani".to_string()),
+        prefer: vec!["tla-plus".to_string()],
+        ..Default::default()
+    })
+    .await;
+    assert_eq!(forced.overall_status, Status::Error);
+    assert_eq!(tools(&forced, Layer::Formal), vec!["kani"]);
+    assert_eq!(
+        forced.layer_results[&Layer::TypeLevel].status,
+        Status::Skipped
+    );
+}
+
+#[tokio::test]
+async fn test_fan_out_reports_disagreement() {
+    let mut plugin_manager = PluginManager::new();
+    for (name, status, spurious_failures) in [
+        ("kani", Status::Error, 0),
+        ("proptest", Status::Success, 0),
+        ("tla-plus", Status::Success, 1),
+    ] {
+        plugin_manager
+            .register_plugin(Box::new(FixedPlugin {
+                name,
+                technique: Technique::ModelChecking,
+                status,
+                spurious_failures: AtomicU32::new(spurious_failures),
+                availability_checks: Default::default(),
+            }))
+            .unwrap();
+    }
+    plugin_manager.set_selection_strategy(SelectionStrategy {
+        fan_out: vec![Technique::ModelChecking],
+        ..Default::default()
+    });
+
+    let input = VerificationInput {
+        target: Target::Module("queue".to_string()),
+        config: EffectiveConfig {
+            level: VerificationLevel::Formal,
+            enforcement: EnforcementMode::Error,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::ModelChecking],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        },
+        context: VerificationContext {
+            session_id: String::new(),
+            working_dir: PathBuf::from("."),
+            cache_dir: PathBuf::from("."),
+            timeout: None,
+            parallel_id: None,
+            seed: 0,
+            cancellation: CancellationToken::new(),
+            scratch_dir: None,
+        },
+    };
+
+    let fan_out = plugin_manager
+        .verify_layer(Layer::Formal, &Technique::ModelChecking, input)
+        .await
+        .unwrap();
+
+    assert!(fan_out.inconsistent);
+    assert_eq!(fan_out.breakdown.len(), 3);
+    // A plugin that errors is not a verdict either way
+    assert_eq!(fan_out.errored, vec!["tla-plus".to_string()]);
+    assert_eq!(fan_out.result.status, Status::Error);
+    assert_eq!(fan_out.result.tool_outputs.len(), 2);
+    let disagreement = fan_out
+        .result
+        .violations
+        .iter()
+        .find(|v| v.rule == "fan_out_consistency")
+        .unwrap();
+    assert_eq!(disagreement.id, "FP-PL-003");
+    assert!(disagreement
+        .message
+        .ends_with(": proptest passed but kani found violations"));
+    assert_eq!(plugin_manager.plugin_stats("kani").runs, 1);
+    // Finding a counterexample is not a failure of the plugin
+    assert_eq!(plugin_manager.plugin_stats("kani").successes, 1);
+}
+
+#[tokio::test]
+async fn test_engine_reports_fan_out_breakdown() {
+    let mut plugin_manager = PluginManager::new();
+    for (name, status) in [("kani", Status::Error), ("proptest", Status::Success)] {
+        plugin_manager
+            .register_plugin(Box::new(FixedPlugin {
+                name,
+                technique: Technique::ModelChecking,
+                status,
+                spurious_failures: AtomicU32::new(0),
+                availability_checks: Default::default(),
+            }))
+            .unwrap();
+    }
+    let engine = VerificationEngine::builder()
+        .config(EffectiveConfig {
+            level: VerificationLevel::Formal,
+            enforcement: EnforcementMode::Error,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::ModelChecking],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        })
+        .cache(VerificationCache::in_memory())
+        .plugin_manager(plugin_manager)
+        .selection_strategy(SelectionStrategy {
+            fan_out: vec![Technique::ModelChecking],
+            ..Default::default()
+        })
+        .build();
+
+    let result = engine
+        .verify(&[Target::Module("queue".to_string())])
+        .await
+        .unwrap();
+
+    assert_eq!(result.plugin_breakdowns.len(), 1);
+    let breakdown = &result.plugin_breakdowns[0];
+    assert_eq!(breakdown.target, "queue");
+    assert!(breakdown.inconsistent);
+    assert_eq!(breakdown.outcomes.len(), 2);
+    assert!(result.layer_results[&Layer::Formal]
+        .violations
+        .iter()
+        .any(|v| v.id == "FP-PL-003"));
+    let report = RendererRegistry::new().render("markdown", &result).unwrap();
+    assert!(report.contains("## Plugin Breakdown"));
+    assert!(report.contains("| `queue` | ModelChecking (plugins disagree) | kani | Error | 1 |"));
+}
+
+#[tokio::test]
+async fn test_engine_serves_cached_layers() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("queue.rs");
+    std::fs::write(&file, "pub fn push() {}\n").unwrap();
+    let target = Target::RustFile(file);
+
+    let cache = VerificationCache::in_memory();
+    let key = cache.key_for(&target, Layer::Formal, "config").unwrap();
+    cache.store(
+        key,
+        CacheEntry {
+            result: LayerResult {
+                layer: Layer::Formal,
+                status: Status::Success,
+                violations: Vec::new(),
+                execution_time: Duration::from_secs(3),
+                tool_outputs: Vec::new(),
+            },
+            timestamp: chrono::Utc::now(),
+            ttl: Duration::from_secs(3600),
+            metadata: CacheMetadata {
+                file_size: 0,
+                execution_time: Duration::from_secs(3),
+                memory_usage: 0,
+                cache_hit_count: 0,
+                target: None,
+                seed: None,
+                environment: None,
+            },
+            artifacts: Vec::new(),
+        },
+    );
+
+    // The plugin would fail, so a successful run can only come from the cache
+    let mut plugin_manager = PluginManager::new();
+    plugin_manager
+        .register_plugin(Box::new(FixedPlugin {
+            name: "tlc",
+            technique: Technique::ModelChecking,
+            status: Status::Error,
+            spurious_failures: AtomicU32::new(0),
+            availability_checks: Default::default(),
+        }))
+        .unwrap();
+    let engine = VerificationEngine::builder()
+        .config(EffectiveConfig {
+            level: VerificationLevel::Formal,
+            enforcement: EnforcementMode::Error,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::ModelChecking],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        })
+        .cache(cache)
+        .plugin_manager(plugin_manager)
+        .build()
+        .with_config_hash(|_| "config".to_string());
+
+    assert!(!engine.needs_verification(&target));
+    let result = engine.verify(&[target]).await.unwrap();
+    assert_eq!(result.overall_status, Status::Success);
+    assert_eq!(result.layer_results[&Layer::Formal].status, Status::Cached);
+
+    // Another configuration misses the cache and runs the plugin
+    let engine = engine.with_config_hash(|_| "changed".to_string());
+    let result = engine
+        .verify(&[Target::RustFile(temp_dir.path().join("queue.rs"))])
+        .await
+        .unwrap();
+    assert_eq!(result.layer_results[&Layer::Formal].status, Status::Error);
+}
+
+#[tokio::test]
+async fn test_engine_caches_passing_layers_with_their_ttl() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("queue.rs");
+    std::fs::write(&file, "pub fn push() {}\n").unwrap();
+    let target = Target::RustFile(file);
+
+    let ttl = Duration::from_secs(30 * 24 * 3600);
+    let engine = VerificationEngine::builder()
+        .config(EffectiveConfig {
+            level: VerificationLevel::Formal,
+            enforcement: EnforcementMode::Error,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::ModelChecking],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        })
+        .cache(VerificationCache::with_cache_dir(
+            temp_dir.path().join("cache"),
+        ))
+        .plugin_manager(passing_plugin("tlc", Technique::ModelChecking))
+        .config_hash(|_| "config".to_string())
+        .cache_ttls(CacheTtls::new(
+            Duration::from_secs(3600),
+            [(Layer::Formal, ttl)].into(),
+        ))
+        .build();
+
+    assert!(engine.needs_verification(&target));
+    let first = engine.verify(std::slice::from_ref(&target)).await.unwrap();
+    assert_eq!(first.layer_results[&Layer::Formal].status, Status::Success);
+    assert!(!engine.needs_verification(&target));
+
+    let cache = VerificationCache::with_cache_dir(temp_dir.path().join("cache"));
+    let key = cache.key_for(&target, Layer::Formal, "config").unwrap();
+    assert_eq!(cache.get(&key).unwrap().ttl, ttl);
+
+    let second = engine.verify(&[target]).await.unwrap();
+    assert_eq!(second.layer_results[&Layer::Formal].status, Status::Cached);
+}
+
+#[tokio::test]
+async fn test_only_execution_failures_count_against_plugins() {
+    let availability_checks = Arc::new(AtomicU32::new(0));
+    let mut plugin_manager = PluginManager::new();
+    for (name, status, spurious_failures) in [
+        ("kani", Status::Error, 0),
+        ("proptest", Status::Success, 0),
+        ("tla-plus", Status::Success, 1),
+    ] {
+        plugin_manager
+            .register_plugin(Box::new(FixedPlugin {
+                name,
+                technique: Technique::ModelChecking,
+                status,
+                spurious_failures: AtomicU32::new(spurious_failures),
+                availability_checks: Arc::clone(&availability_checks),
+            }))
+            .unwrap();
+    }
+    plugin_manager.set_selection_strategy(SelectionStrategy {
+        fan_out: vec![Technique::ModelChecking],
+        ..Default::default()
+    });
+
+    let input = VerificationInput {
+        target: Target::Module("queue".to_string()),
+        config: EffectiveConfig {
+            level: VerificationLevel::Formal,
+            enforcement: EnforcementMode::Error,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::ModelChecking],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        },
+        context: VerificationContext {
+            session_id: String::new(),
+            working_dir: PathBuf::from("."),
+            cache_dir: PathBuf::from("."),
+            timeout: None,
+            parallel_id: None,
+            seed: 0,
+            cancellation: CancellationToken::new(),
+            scratch_dir: None,
+        },
+    };
+    plugin_manager
+        .verify_layer(Layer::Formal, &Technique::ModelChecking, input)
+        .await
+        .unwrap();
+
+    let kani = plugin_manager.plugin_stats("kani");
+    assert_eq!((kani.runs, kani.successes), (1, 1));
+    let tla_plus = plugin_manager.plugin_stats("tla-plus");
+    assert_eq!((tla_plus.runs, tla_plus.successes), (1, 0));
+
+    // Each tool is looked up once, however often plugins are ranked
+    plugin_manager
+        .rank_plugins(&Technique::ModelChecking)
+        .unwrap();
+    assert_eq!(availability_checks.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_module_tool_overrides_reach_plugins() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    std::fs::write(
+        root.join("ferrisproof.toml"),
+        r#"
+[profile]
+level = "formal"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests", "SessionTypes", "FormalSpecs", "ModelChecking"]
+
+[tools.tla_plus]
+workers = 2
+
+[tools.alloy]
+scope = 4
+"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("src/consensus")).unwrap();
+    std::fs::write(
+        root.join("src/consensus/ferrisproof.toml"),
+        r#"
+[profile]
+level = "formal"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "PropertyTests", "SessionTypes", "FormalSpecs", "ModelChecking"]
+
+[tools.tla_plus]
+workers = 8
+"#,
+    )
+    .unwrap();
+
+    let mut plugin_manager = PluginManager::new();
+    for name in ["tla-plus", "alloy"] {
+        plugin_manager
+            .register_plugin(Box::new(FixedPlugin {
+                name,
+                technique: Technique::ModelChecking,
+                status: Status::Success,
+                spurious_failures: AtomicU32::new(0),
+                availability_checks: Default::default(),
+            }))
+            .unwrap();
+    }
+    plugin_manager.set_selection_strategy(SelectionStrategy {
+        fan_out: vec![Technique::ModelChecking],
+        ..Default::default()
+    });
+
+    let config_manager = ConfigManager::from_project_root(root).unwrap();
+    let file = root.join("src/consensus/raft.rs");
+    let input = VerificationInput {
+        target: Target::RustFile(file.clone()),
+        config: config_manager.for_file(&file).plugin_config(),
+        context: VerificationContext {
+            session_id: String::new(),
+            working_dir: PathBuf::from("."),
+            cache_dir: PathBuf::from("."),
+            timeout: None,
+            parallel_id: None,
+            seed: 0,
+            cancellation: CancellationToken::new(),
+            scratch_dir: None,
+        },
+    };
+    let fan_out = plugin_manager
+        .verify_layer(Layer::Formal, &Technique::ModelChecking, input)
+        .await
+        .unwrap();
+
+    // Each plugin sees its own section, with the module's override applied
+    let tool_config = |tool: &str| -> serde_json::Value {
+        let output = fan_out
+            .result
+            .tool_outputs
+            .iter()
+            .find(|output| output.tool == tool)
+            .unwrap();
+        serde_json::from_str(&output.stdout).unwrap()
+    };
+    assert_eq!(tool_config("tla-plus")["workers"], 8);
+    assert_eq!(tool_config("alloy")["scope"], 4);
+    assert!(tool_config("alloy").get("workers").is_none());
+}
+
+/// Formal layer of a `tla-plus` stand-in that fails `spurious_failures`
+/// times, run under `tool_config`
+async fn verify_flaky(
+    spurious_failures: u32,
+    tool_config: serde_json::Value,
+) -> Result<LayerResult> {
+    let mut plugin_manager = PluginManager::new();
+    plugin_manager
+        .register_plugin(Box::new(FixedPlugin {
+            name: "tla-plus",
+            technique: Technique::ModelChecking,
+            status: Status::Success,
+            spurious_failures: AtomicU32::new(spurious_failures),
+            availability_checks: Default::default(),
+        }))
+        .unwrap();
+    let input = VerificationInput {
+        target: Target::Module("raft".to_string()),
+        config: EffectiveConfig {
+            level: VerificationLevel::Formal,
+            enforcement: EnforcementMode::Error,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::ModelChecking],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: [("tla-plus".to_string(), tool_config)].into(),
+            memory_budget: None,
+        },
+        context: VerificationContext {
+            session_id: String::new(),
+            working_dir: PathBuf::from("."),
+            cache_dir: PathBuf::from("."),
+            timeout: None,
+            parallel_id: None,
+            seed: 0,
+            cancellation: CancellationToken::new(),
+            scratch_dir: None,
+        },
+    };
+    plugin_manager
+        .verify_layer(Layer::Formal, &Technique::ModelChecking, input)
+        .await
+        .map(|fan_out| fan_out.result)
+}
+
+#[tokio::test]
+async fn test_spurious_tool_failures_are_retried() {
+    let retries = |retries: u32| serde_json::json!({ "retries": retries, "backoff": "1ms" });
+
+    let flaky = verify_flaky(2, retries(2)).await.unwrap();
+    assert_eq!(flaky.status, Status::Success);
+    assert_eq!(flaky.tool_outputs[0].retries, 2);
+    assert!(flaky.is_flaky_pass());
+
+    let error = verify_flaky(2, retries(1)).await.unwrap_err();
+    assert!(error.to_string().contains("OutOfMemoryError"));
+
+    // A crash is reported as an error without violations, and is retried too
+    let crashed = serde_json::json!({ "retries": 1, "backoff": "1ms", "failure": "crash" });
+    let flaky = verify_flaky(1, crashed).await.unwrap();
+    assert_eq!(flaky.status, Status::Success);
+    assert_eq!(flaky.tool_outputs[0].retries, 1);
+
+    // A missing tool fails the same way every time
+    let missing = serde_json::json!({ "retries": 1, "backoff": "1ms", "failure": "missing" });
+    let error = verify_flaky(1, missing).await.unwrap_err();
+    assert_eq!(error.code(), "FP-TL-001");
+}
+
+#[tokio::test]
+async fn test_engine_retries_flaky_plugins() {
+    let mut plugin_manager = PluginManager::new();
+    plugin_manager
+        .register_plugin(Box::new(FixedPlugin {
+            name: "tla-plus",
+            technique: Technique::ModelChecking,
+            status: Status::Success,
+            spurious_failures: AtomicU32::new(1),
+            availability_checks: Default::default(),
+        }))
+        .unwrap();
+    let engine = VerificationEngine::builder()
+        .config(EffectiveConfig {
+            level: VerificationLevel::Formal,
+            enforcement: EnforcementMode::Error,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::ModelChecking],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: [(
+                "tla-plus".to_string(),
+                serde_json::json!({ "retries": 1, "backoff": "1ms" }),
+            )]
+            .into(),
+            memory_budget: None,
+        })
+        .cache(VerificationCache::in_memory())
+        .plugin_manager(plugin_manager)
+        .build();
+
+    let result = engine
+        .verify(&[Target::Module("raft".to_string())])
+        .await
+        .unwrap();
+
+    assert_eq!(result.overall_status, Status::Success);
+    let formal = &result.layer_results[&Layer::Formal];
+    assert_eq!(formal.status, Status::Success);
+    assert_eq!(formal.retries(), 1);
+    assert!(formal.is_flaky_pass());
+    let report = RendererRegistry::new().render("markdown", &result).unwrap();
+    assert!(report.contains("flaky pass, 1 retries"));
+}
+
+#[test]
+fn test_parse_backoff() {
+    assert_eq!(parse_backoff("500ms").unwrap(), Duration::from_millis(500));
+    assert_eq!(parse_backoff("5s").unwrap(), Duration::from_secs(5));
+    assert_eq!(parse_backoff(" 2m ").unwrap(), Duration::from_secs(120));
+    assert_eq!(parse_backoff("5h").unwrap_err().code(), "FP-CF-001");
+    assert_eq!(parse_backoff("soon").unwrap_err().code(), "FP-CF-001");
+    assert_eq!(
+        parse_backoff(&format!("{}m", u64::MAX)).unwrap_err().code(),
+        "FP-CF-001"
+    );
+}
+
+#[tokio::test]
+async fn test_engine_builder_streams_events() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+    std::fs::write(temp_dir.path().join("src/lib.rs"), "pub mod codec;\n").unwrap();
+    std::fs::write(temp_dir.path().join("src/codec.rs"), "pub fn decode() {}\n").unwrap();
+
+    let progress = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let seen = progress.clone();
+    let engine = VerificationEngine::builder()
+        .config(EffectiveConfig {
+            level: VerificationLevel::Standard,
+            enforcement: EnforcementMode::Warning,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::TypeSafety, Technique::PropertyTests],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        })
+        .cache(VerificationCache::in_memory())
+        .progress(move |event| seen.lock().unwrap().push(format!("{:?}", event)))
+        .build();
+    let mut events = engine.subscribe();
+
+    let result = engine.verify_project(temp_dir.path()).await.unwrap();
+    assert_eq!(result.overall_status, Status::Success);
+    assert_eq!(engine.config().unwrap().level, VerificationLevel::Standard);
+
+    assert!(matches!(
+        events.try_recv().unwrap(),
+        VerificationEvent::Started { targets: 2 }
+    ));
+    let mut finished = 0;
+    while let Ok(event) = events.try_recv() {
+        match event {
+            VerificationEvent::TargetFinished { status, .. } => {
+                assert_eq!(status, Status::Success);
+                finished += 1;
+            }
+            VerificationEvent::Finished { status, .. } => assert_eq!(status, Status::Success),
+            _ => {}
+        }
+    }
+    assert_eq!(finished, 2);
+    // Started, two targets started and finished with two layers each, Finished
+    assert_eq!(progress.lock().unwrap().len(), 14);
+    assert!(progress.lock().unwrap()[2].starts_with("LayerStarted"));
+
+    // The engine is reusable for single targets
+    let single = engine
+        .verify_target(&Target::RustFile(temp_dir.path().join("src/codec.rs")))
+        .await
+        .unwrap();
+    assert_eq!(single.overall_status, Status::Success);
+    assert_eq!(progress.lock().unwrap().len(), 22);
+}
+
+/// Plugin manager with a single plugin that always passes `technique`
+fn passing_plugin(name: &'static str, technique: Technique) -> PluginManager {
+    let mut plugin_manager = PluginManager::new();
+    plugin_manager
+        .register_plugin(Box::new(FixedPlugin {
+            name,
+            technique,
+            status: Status::Success,
+            spurious_failures: AtomicU32::new(0),
+            availability_checks: Default::default(),
+        }))
+        .unwrap();
+    plugin_manager
+}
+
+#[tokio::test]
+async fn test_cancelled_target_keeps_finished_layers() {
+    let mut plugin_manager = passing_plugin("clippy", Technique::TypeSafety);
+    plugin_manager
+        .register_plugin(Box::new(FixedPlugin {
+            name: "tlc",
+            technique: Technique::ModelChecking,
+            status: Status::Success,
+            spurious_failures: AtomicU32::new(1),
+            availability_checks: Default::default(),
+        }))
+        .unwrap();
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+    let engine = VerificationEngine::builder()
+        .config(EffectiveConfig {
+            level: VerificationLevel::Formal,
+            enforcement: EnforcementMode::Error,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::TypeSafety, Technique::ModelChecking],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        })
+        .cache(VerificationCache::in_memory())
+        .plugin_manager(plugin_manager)
+        .cancellation(token)
+        .progress(move |event| {
+            if let VerificationEvent::LayerStarted {
+                layer: Layer::Formal,
+                ..
+            } = event
+            {
+                cancel.cancel();
+            }
+        })
+        .build();
+
+    let result = engine
+        .verify(&[Target::Module("core".to_string())])
+        .await
+        .unwrap();
+    assert!(result.interrupted);
+    assert!(result.target_statuses.is_empty());
+    assert_eq!(result.completed.len(), 1);
+    assert_eq!(
+        result.layer_results[&Layer::TypeLevel].status,
+        Status::Success
+    );
+    assert!(!result.layer_results.contains_key(&Layer::Formal));
+}
+
+#[tokio::test]
+async fn test_preflight_skips_layers_without_tools() {
+    let plugin_manager = passing_plugin("tlc", Technique::ModelChecking);
+    let techniques = [
+        Technique::TypeSafety,
+        Technique::PropertyTests,
+        Technique::ModelChecking,
+    ];
+    let unavailable = plugin_manager.preflight(&techniques);
+    let layers: Vec<_> = unavailable.iter().map(|u| u.layer).collect();
+    assert_eq!(layers, vec![Layer::TypeLevel, Layer::PropertyBased]);
+    assert_eq!(unavailable[0].code, "FP-PL-002");
+    assert!(unavailable[0]
+        .hint
+        .contains("ferris-proof explain FP-PL-002"));
+
+    let config = EffectiveConfig {
+        level: VerificationLevel::Formal,
+        enforcement: EnforcementMode::Error,
+        enforcement_policy: EnforcementPolicy::default(),
+        enabled_techniques: techniques.to_vec(),
+        tool_config: serde_json::Value::Null,
+        plugin_tools: Default::default(),
+        memory_budget: None,
+    };
+    let engine = |strict_tools: bool| {
+        VerificationEngine::builder()
+            .config(config.clone())
+            .cache(VerificationCache::in_memory())
+            .plugin_manager(passing_plugin("tlc", Technique::ModelChecking))
+            .build()
+            .with_unavailable_layers(unavailable.clone())
+            .with_strict_tools(strict_tools)
+    };
+    let target = Target::RustFile(PathBuf::from("src/lib.rs"));
+
+    let skipped = engine(false).verify_target(&target).await.unwrap();
+    assert_eq!(skipped.overall_status, Status::Success);
+    assert_eq!(skipped.unavailable_layers.len(), 2);
+    assert_eq!(
+        skipped.layer_results[&Layer::TypeLevel].status,
+        Status::Skipped
+    );
+    assert_eq!(
+        skipped.skip_reasons[&Layer::TypeLevel],
+        SkipReason::ToolUnavailable
+    );
+    assert_eq!(
+        skipped.layer_results[&Layer::Formal].status,
+        Status::Success
+    );
+
+    let strict = engine(true).verify_target(&target).await.unwrap();
+    assert_eq!(strict.overall_status, Status::Error);
+    let type_level = &strict.layer_results[&Layer::TypeLevel];
+    assert_eq!(type_level.status, Status::Error);
+    assert_eq!(type_level.violations[0].rule, "tool_unavailable");
+}
+
 #[test]
 fn test_configuration_system_integration() {
     println!("⚙️  Testing Configuration System Integration");
@@ -164,3 +1135,80 @@ enabled_techniques = ["TypeSafety"]
     println!("  ✓ Configuration resolution works");
     println!("🎉 Configuration system integration test passed!");
 }
+
+#[tokio::test]
+async fn test_layer_gating_from_config() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("ferrisproof.toml"),
+        r#"
+[profile]
+level = "formal"
+enforcement = "error"
+enabled_techniques = ["TypeSafety", "FormalSpecs"]
+
+[layers]
+formal_specs.requires = ["property_based"]
+"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+    std::fs::write(temp_dir.path().join("src/lib.rs"), "pub fn run() {}\n").unwrap();
+
+    let config_manager = ConfigManager::from_project_root(temp_dir.path()).unwrap();
+    assert!(config_manager.validate().is_ok());
+    let engine = VerificationEngine::builder()
+        .config(EffectiveConfig {
+            level: VerificationLevel::Formal,
+            enforcement: EnforcementMode::Error,
+            enforcement_policy: EnforcementPolicy::default(),
+            enabled_techniques: vec![Technique::TypeSafety, Technique::FormalSpecs],
+            tool_config: serde_json::Value::Null,
+            plugin_tools: Default::default(),
+            memory_budget: None,
+        })
+        .cache(VerificationCache::in_memory())
+        .plugin_manager(passing_plugin("clippy", Technique::TypeSafety))
+        .layer_gates(config_manager.layer_gates().unwrap())
+        .build();
+
+    // Property-based tests are not enabled, so they never pass and Formal stays gated
+    let result = engine.verify_project(temp_dir.path()).await.unwrap();
+    assert_eq!(result.overall_status, Status::Success);
+    assert_eq!(result.gated_layers.len(), 1);
+    assert_eq!(result.gated_layers[0].layer, Layer::Formal);
+    assert_eq!(result.gated_layers[0].required, Layer::PropertyBased);
+    assert_eq!(result.layer_results[&Layer::Formal].status, Status::Skipped);
+    assert_eq!(result.skip_reasons[&Layer::Formal], SkipReason::Gated);
+    assert_eq!(
+        result.layer_results[&Layer::TypeLevel].status,
+        Status::Success
+    );
+    assert!(!result.skip_reasons.contains_key(&Layer::TypeLevel));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_tool_kills_cancelled_tool() {
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        canceller.cancel();
+    });
+
+    let start = std::time::Instant::now();
+    let mut cmd = std::process::Command::new("sh");
+    cmd.args(["-c", "sleep 30; echo done"]);
+    let result = run_tool(&mut cmd, &token);
+
+    assert!(matches!(result, Err(FerrisProofError::Cancelled { .. })));
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+    let output = run_tool(
+        std::process::Command::new("sh").args(["-c", "echo hi"]),
+        &CancellationToken::new(),
+    )
+    .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hi");
+}
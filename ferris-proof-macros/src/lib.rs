@@ -0,0 +1,475 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, ItemFn, LitStr, Member, Type};
+
+/// Tuples of strategies implement `Strategy` up to this arity
+const MAX_FIELDS: usize = 12;
+
+/// Derive validators and proptest strategies from `#[refine(...)]` field attributes
+///
+/// Supported refinements are `range = <range expression>`, `regex = "<pattern>"`
+/// (the whole value must match) and `non_empty`. The derive generates
+/// `validate()`, `debug_validate()` and an `Arbitrary` impl whose strategy
+/// only produces values satisfying every refinement, so deriving crates
+/// depend on `proptest`, and on `regex` when a `regex` refinement is used.
+///
+/// ```ignore
+/// #[derive(Debug, Clone, Verified)]
+/// pub struct Account {
+///     #[refine(range = 1..=120)]
+///     pub age: u8,
+///     #[refine(regex = "[a-z][a-z0-9_]{2,15}")]
+///     pub handle: String,
+///     #[refine(non_empty)]
+///     pub roles: Vec<String>,
+/// }
+/// ```
+#[proc_macro_derive(Verified, attributes(refine))]
+pub fn derive_verified(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Refinement predicates on a function boundary
+///
+/// `requires = <expr>` constrains the arguments and `ensures = <expr>` the
+/// return value, named `result`; both may be repeated. Preconditions become
+/// `debug_assert!`s at the start of the function. Postconditions have no
+/// runtime check: the `smt` plugin proves or refutes them statically, along
+/// with the satisfiability of the preconditions.
+///
+/// When the `deductive` plugin runs Prusti or Creusot, it sets
+/// `FERRIS_PROOF_DEDUCTIVE` and the clauses are emitted as that verifier's
+/// `#[requires]`/`#[ensures]` attributes instead of runtime checks.
+///
+/// ```ignore
+/// #[contract(requires = x >= 0 && x <= 100, ensures = result >= x)]
+/// pub fn bump(x: i32) -> i32 {
+///     x + 1
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn contract(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut contract = Contract::default();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("requires") {
+            contract.requires.push(meta.value()?.parse()?);
+        } else if meta.path.is_ident("ensures") {
+            contract.ensures.push(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `requires` or `ensures`"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(args with parser);
+    let function = parse_macro_input!(item as ItemFn);
+    let verifier = std::env::var(DEDUCTIVE_ENV)
+        .ok()
+        .and_then(|name| Verifier::parse(&name));
+    expand_contract(contract, function, verifier)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Set by the `deductive` plugin to the verifier it runs, `prusti` or `creusot`
+const DEDUCTIVE_ENV: &str = "FERRIS_PROOF_DEDUCTIVE";
+
+#[derive(Clone, Copy)]
+enum Verifier {
+    Prusti,
+    Creusot,
+}
+
+impl Verifier {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "prusti" => Some(Verifier::Prusti),
+            "creusot" => Some(Verifier::Creusot),
+            _ => None,
+        }
+    }
+
+    fn contracts_crate(self) -> &'static str {
+        match self {
+            Verifier::Prusti => "prusti_contracts",
+            Verifier::Creusot => "creusot_contracts",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Contract {
+    requires: Vec<Expr>,
+    ensures: Vec<Expr>,
+}
+
+fn expand_contract(
+    contract: Contract,
+    mut function: ItemFn,
+    verifier: Option<Verifier>,
+) -> syn::Result<TokenStream2> {
+    if let Some(ensures) = contract.ensures.first() {
+        if matches!(function.sig.output, syn::ReturnType::Default) {
+            return Err(syn::Error::new(
+                ensures.span(),
+                "`ensures` needs a function returning a value",
+            ));
+        }
+    }
+
+    // Deductive verifiers read the clauses through their own attributes
+    if let Some(krate) = verifier.map(|v| format_ident!("{}", v.contracts_crate())) {
+        for requires in &contract.requires {
+            function
+                .attrs
+                .push(syn::parse_quote!(#[::#krate::requires(#requires)]));
+        }
+        for ensures in &contract.ensures {
+            function
+                .attrs
+                .push(syn::parse_quote!(#[::#krate::ensures(#ensures)]));
+        }
+        return Ok(quote!(#function));
+    }
+
+    let name = function.sig.ident.to_string();
+    let checks = contract.requires.iter().map(|requires| {
+        let text = quote!(#requires).to_string();
+        // Predicates stay in the fragment the `smt` plugin translates, so
+        // range checks are spelled out as comparisons
+        quote! {
+            #[cfg(debug_assertions)]
+            {
+                #[allow(clippy::manual_range_contains)]
+                let holds: bool = #requires;
+                ::core::assert!(holds, "precondition `{}` of `{}` violated", #text, #name);
+            }
+        }
+    });
+    let body = &function.block;
+    function.block = syn::parse_quote!({
+        #(#checks)*
+        #body
+    });
+
+    Ok(quote!(#function))
+}
+
+enum Refinement {
+    Range(Expr),
+    Regex(LitStr),
+    NonEmpty,
+}
+
+struct RefinedField {
+    member: Member,
+    ty: Type,
+    refinements: Vec<Refinement>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`Verified` can only be derived for structs",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "`Verified` does not support generic structs",
+        ));
+    }
+    if data.fields.len() > MAX_FIELDS {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            format!("`Verified` supports at most {} fields", MAX_FIELDS),
+        ));
+    }
+
+    let fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            Ok(RefinedField {
+                member: match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(index.into()),
+                },
+                ty: field.ty.clone(),
+                refinements: parse_refinements(field)?,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let checks = fields.iter().flat_map(|field| {
+        field
+            .refinements
+            .iter()
+            .map(move |refinement| check(name, field, refinement))
+    });
+    let strategy = strategy(&data.fields, &fields);
+
+    Ok(quote! {
+        impl #name {
+            /// Check every `#[refine]` constraint, naming the first violated one
+            pub fn validate(&self) -> ::core::result::Result<(), ::std::string::String> {
+                #(#checks)*
+                ::core::result::Result::Ok(())
+            }
+
+            /// Panic on a violated refinement in debug builds; a no-op in release builds
+            #[track_caller]
+            pub fn debug_validate(&self) {
+                #[cfg(debug_assertions)]
+                if let ::core::result::Result::Err(e) = self.validate() {
+                    panic!("{}", e);
+                }
+            }
+        }
+
+        impl ::proptest::arbitrary::Arbitrary for #name {
+            type Parameters = ();
+            type Strategy = ::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                use ::proptest::strategy::Strategy as _;
+                #strategy
+            }
+        }
+    })
+}
+
+fn parse_refinements(field: &syn::Field) -> syn::Result<Vec<Refinement>> {
+    let mut refinements = Vec::new();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("refine")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("range") {
+                refinements.push(Refinement::Range(meta.value()?.parse()?));
+            } else if meta.path.is_ident("regex") {
+                let pattern: LitStr = meta.value()?.parse()?;
+                if let Err(e) = regex::Regex::new(&anchored(&pattern.value())) {
+                    return Err(syn::Error::new(pattern.span(), e));
+                }
+                refinements.push(Refinement::Regex(pattern));
+            } else if meta.path.is_ident("non_empty") {
+                refinements.push(Refinement::NonEmpty);
+            } else {
+                return Err(meta.error("expected `range`, `regex` or `non_empty`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(refinements)
+}
+
+/// Validation code returning an error when `refinement` does not hold
+fn check(name: &syn::Ident, field: &RefinedField, refinement: &Refinement) -> TokenStream2 {
+    let member = &field.member;
+    let label = format!("{}.{}", name, quote!(#member));
+
+    match refinement {
+        Refinement::Range(range) => {
+            let range_text = quote!(#range).to_string();
+            let range = typed_range(range, &field.ty);
+            quote! {
+                if !(#range).contains(&self.#member) {
+                    return ::core::result::Result::Err(::std::format!(
+                        "{}: {:?} is outside {}", #label, self.#member, #range_text
+                    ));
+                }
+            }
+        }
+        Refinement::Regex(pattern) => {
+            let anchored = LitStr::new(&anchored(&pattern.value()), pattern.span());
+            quote! {
+                {
+                    static PATTERN: ::std::sync::OnceLock<::regex::Regex> =
+                        ::std::sync::OnceLock::new();
+                    let pattern = PATTERN.get_or_init(|| {
+                        ::regex::Regex::new(#anchored).expect("checked by #[derive(Verified)]")
+                    });
+                    let value: &str = ::core::convert::AsRef::as_ref(&self.#member);
+                    if !pattern.is_match(value) {
+                        return ::core::result::Result::Err(::std::format!(
+                            "{}: {:?} does not match /{}/", #label, value, #pattern
+                        ));
+                    }
+                }
+            }
+        }
+        Refinement::NonEmpty => quote! {
+            if self.#member.is_empty() {
+                return ::core::result::Result::Err(::std::format!("{}: must not be empty", #label));
+            }
+        },
+    }
+}
+
+/// Strategy building `Self` from per-field strategies honouring the refinements
+fn strategy(shape: &Fields, fields: &[RefinedField]) -> TokenStream2 {
+    if fields.is_empty() {
+        return quote!(::proptest::strategy::Just(Self).boxed());
+    }
+
+    let strategies = fields.iter().map(field_strategy);
+    let bindings: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("field_{}", i))
+        .collect();
+    let construct = match shape {
+        Fields::Named(_) => {
+            let members = fields.iter().map(|f| &f.member);
+            quote!(Self { #(#members: #bindings),* })
+        }
+        _ => quote!(Self(#(#bindings),*)),
+    };
+
+    quote! {
+        (#(#strategies,)*)
+            .prop_map(|(#(#bindings,)*)| #construct)
+            .boxed()
+    }
+}
+
+fn field_strategy(field: &RefinedField) -> TokenStream2 {
+    let ty = &field.ty;
+    let base = field
+        .refinements
+        .iter()
+        .find_map(|refinement| match refinement {
+            Refinement::Range(range) => {
+                let range = typed_range(range, ty);
+                Some(quote!(#range.boxed()))
+            }
+            Refinement::Regex(pattern) => Some(quote! {
+                ::proptest::string::string_regex(#pattern)
+                    .expect("checked by #[derive(Verified)]")
+                    .prop_map(::core::convert::Into::<#ty>::into)
+                    .boxed()
+            }),
+            Refinement::NonEmpty => None,
+        })
+        .unwrap_or_else(|| quote!(::proptest::arbitrary::any::<#ty>().boxed()));
+
+    if field
+        .refinements
+        .iter()
+        .any(|r| matches!(r, Refinement::NonEmpty))
+    {
+        quote!(#base.prop_filter("non-empty", |value| !value.is_empty()).boxed())
+    } else {
+        base
+    }
+}
+
+/// Range with its bounds pinned to the field type, since `1..=120` alone
+/// leaves the integer type ambiguous for proptest's per-type range strategies
+fn typed_range(range: &Expr, ty: &Type) -> TokenStream2 {
+    let Expr::Range(range) = range else {
+        return quote!((#range));
+    };
+    let start = range
+        .start
+        .as_ref()
+        .map(|start| quote!({ let start: #ty = #start; start }));
+    let end = range
+        .end
+        .as_ref()
+        .map(|end| quote!({ let end: #ty = #end; end }));
+    let limits = &range.limits;
+    quote!((#start #limits #end))
+}
+
+fn anchored(pattern: &str) -> String {
+    format!("^(?:{})$", pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(source: &str) -> Result<String, String> {
+        let input: DeriveInput = syn::parse_str(source).unwrap();
+        expand(input)
+            .map(|tokens| tokens.to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_expand_refinements() {
+        let expanded = expand_str(
+            r#"struct Account {
+                #[refine(range = 1..=120)]
+                age: u8,
+                #[refine(regex = "[a-z]+", non_empty)]
+                handle: String,
+                notes: Vec<String>,
+            }"#,
+        )
+        .unwrap();
+
+        assert!(expanded.contains("pub fn validate"));
+        assert!(expanded.contains("\"Account.age\""));
+        assert!(expanded.contains("\"^(?:[a-z]+)$\""));
+        assert!(expanded.contains("string_regex (\"[a-z]+\")"));
+        assert!(expanded.contains("any :: < Vec < String > >"));
+        assert!(expanded.contains("Self { age : field_0 , handle : field_1 , notes : field_2 }"));
+    }
+
+    #[test]
+    fn test_expand_contract_asserts_preconditions() {
+        let contract = Contract {
+            requires: vec![syn::parse_str("x > 0").unwrap()],
+            ensures: vec![syn::parse_str("result > x").unwrap()],
+        };
+        let function: ItemFn = syn::parse_str("fn bump(x: i32) -> i32 { x + 1 }").unwrap();
+        let expanded = expand_contract(contract, function, None)
+            .unwrap()
+            .to_string();
+        assert!(expanded.contains("let holds : bool = x > 0"));
+        assert!(!expanded.contains("result > x"));
+
+        let contract = Contract {
+            requires: Vec::new(),
+            ensures: vec![syn::parse_str("result > 0").unwrap()],
+        };
+        let function: ItemFn = syn::parse_str("fn log(x: i32) {}").unwrap();
+        assert!(expand_contract(contract, function, None)
+            .unwrap_err()
+            .to_string()
+            .contains("returning a value"));
+    }
+
+    #[test]
+    fn test_expand_contract_for_deductive_verifiers() {
+        let contract = Contract {
+            requires: vec![syn::parse_str("x > 0").unwrap()],
+            ensures: vec![syn::parse_str("result > x").unwrap()],
+        };
+        let function: ItemFn = syn::parse_str("fn bump(x: i32) -> i32 { x + 1 }").unwrap();
+        let expanded = expand_contract(contract, function, Verifier::parse("prusti"))
+            .unwrap()
+            .to_string();
+        assert!(expanded.starts_with(
+            "# [:: prusti_contracts :: requires (x > 0)] # [:: prusti_contracts :: ensures (result > x)]"
+        ));
+        assert!(!expanded.contains("holds"));
+    }
+
+    #[test]
+    fn test_expand_rejects_unsupported_input() {
+        assert!(expand_str("enum Mode { A, B }")
+            .unwrap_err()
+            .contains("only be derived for structs"));
+        assert!(expand_str("struct Id(#[refine(positive)] u32);")
+            .unwrap_err()
+            .contains("expected `range`, `regex` or `non_empty`"));
+        assert!(expand_str(r#"struct Id(#[refine(regex = "(")] String);"#).is_err());
+    }
+}
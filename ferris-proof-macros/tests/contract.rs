@@ -0,0 +1,18 @@
+use ferris_proof_macros::contract;
+
+#[contract(requires = x >= 0 && x < 100, ensures = result > x)]
+fn bump(x: i32) -> i32 {
+    x + 1
+}
+
+#[test]
+fn test_contract_keeps_the_function_body() {
+    assert_eq!(bump(41), 42);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "precondition `x >= 0 && x < 100` of `bump` violated")]
+fn test_contract_asserts_preconditions_in_debug_builds() {
+    bump(-1);
+}
@@ -0,0 +1,65 @@
+use ferris_proof_macros::Verified;
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Verified)]
+pub struct Account {
+    #[refine(range = 1..=120)]
+    pub age: u8,
+    #[refine(regex = "[a-z][a-z0-9_]{2,15}")]
+    pub handle: String,
+    #[refine(non_empty)]
+    pub roles: Vec<String>,
+    pub verified: bool,
+}
+
+#[derive(Debug, Clone, Verified)]
+pub struct Ratio(#[refine(range = 0.0..1.0)] f64);
+
+#[test]
+fn test_validate_reports_violated_refinement() {
+    let mut account = Account {
+        age: 30,
+        handle: "ferris".to_string(),
+        roles: vec!["admin".to_string()],
+        verified: true,
+    };
+    assert_eq!(account.validate(), Ok(()));
+
+    account.handle = "Ferris!".to_string();
+    let error = account.validate().unwrap_err();
+    assert!(error.starts_with("Account.handle:"), "{}", error);
+
+    account.handle = "ferris".to_string();
+    account.roles.clear();
+    assert_eq!(
+        account.validate().unwrap_err(),
+        "Account.roles: must not be empty"
+    );
+
+    assert!(Ratio(1.5).validate().is_err());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Account.age")]
+fn test_debug_validate_panics_in_debug_builds() {
+    Account {
+        age: 0,
+        handle: "ferris".to_string(),
+        roles: vec!["admin".to_string()],
+        verified: false,
+    }
+    .debug_validate();
+}
+
+proptest! {
+    #[test]
+    fn generated_accounts_satisfy_refinements(account in any::<Account>()) {
+        prop_assert_eq!(account.validate(), Ok(()));
+    }
+
+    #[test]
+    fn generated_ratios_satisfy_refinements(ratio in any::<Ratio>()) {
+        prop_assert!(ratio.validate().is_ok());
+    }
+}
@@ -0,0 +1,466 @@
+use anyhow::{anyhow, Result};
+use ferris_proof_core::{
+    plugins::{
+        run_tool, CancellationToken, PerformanceMetrics, PluginMetadata, StructuredResult, ToolEnv,
+        ToolInfo, VerificationInput, VerificationOutput, VerificationPlugin, VersionRange,
+    },
+    types::*,
+    verification::Target,
+    FerrisProofError,
+};
+use regex::Regex;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Scopes to check assertions at, smallest first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeSweep {
+    pub from: u32,
+    pub to: u32,
+    /// Stop starting new scopes once this much time has been spent
+    pub budget: Option<Duration>,
+}
+
+impl ScopeSweep {
+    /// Read `sweep = { from, to, budget }` from the tool config
+    pub fn from_tool_config(tool_config: &serde_json::Value) -> Option<Self> {
+        let sweep = tool_config.get("sweep")?;
+        let from = sweep.get("from").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+        let to = sweep.get("to").and_then(|v| v.as_u64())? as u32;
+        Some(Self {
+            from,
+            to,
+            budget: sweep
+                .get("budget")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_secs),
+        })
+    }
+}
+
+/// Result of checking a spec at one or more scopes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SweepOutcome {
+    /// Largest scope at which every assertion held
+    pub max_verified_scope: Option<u32>,
+    /// Scope and assertions of the first counterexample found
+    pub counterexample: Option<(u32, Vec<String>)>,
+    /// The budget ran out before the last scope was checked
+    pub budget_exhausted: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Rewrite every `check Name [for N]` command to use `scope`
+///
+/// `but` clauses are kept, so `check A for 3 but 4 Int` keeps its integer width.
+pub fn with_scope(content: &str, scope: u32) -> String {
+    let check = Regex::new(r"(?m)^(\s*check\s+[A-Za-z_][A-Za-z0-9_']*)(?:\s+for\s+\d+)?").unwrap();
+    check
+        .replace_all(content, |caps: &regex::Captures| {
+            format!("{} for {}", &caps[1], scope)
+        })
+        .into_owned()
+}
+
+/// Assertions with a counterexample in `alloy exec` output
+///
+/// A satisfiable `check` command means the assertion can be violated.
+pub fn counterexamples(stdout: &str) -> Vec<String> {
+    let summary = Regex::new(r"(?m)^\s*\d+\.\s+check\s+(\S+)\s+.*\bSAT\s*$").unwrap();
+    summary
+        .captures_iter(stdout)
+        .filter(|caps| !caps[0].trim_end().ends_with("UNSAT"))
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// 1-based line of `check <assertion>` in the spec, if present
+fn check_line(content: &str, assertion: &str) -> Option<u32> {
+    let pattern = Regex::new(&format!(r"^\s*check\s+{}\b", regex::escape(assertion))).ok()?;
+    content
+        .lines()
+        .position(|line| pattern.is_match(line))
+        .map(|idx| idx as u32 + 1)
+}
+
+/// Alloy Analyzer integration with an optional scope sweep
+///
+/// Reads `scope` and `sweep = { from, to, budget }` from the tool config.
+pub struct AlloyPlugin {
+    analyzer_path: PathBuf,
+    initialized: bool,
+}
+
+impl AlloyPlugin {
+    pub fn new() -> Self {
+        Self {
+            analyzer_path: PathBuf::from("alloy"),
+            initialized: false,
+        }
+    }
+
+    /// Check `spec_path` with every `check` command rewritten to `scope`
+    ///
+    /// The scoped copy is written next to the spec so `open` statements resolve.
+    fn run_at_scope(
+        &self,
+        spec_path: &Path,
+        content: &str,
+        scope: Option<u32>,
+        env: &ToolEnv,
+        cancellation: &CancellationToken,
+    ) -> Result<(String, String)> {
+        let scoped_path = match scope {
+            Some(scope) => {
+                let name = spec_path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let path = spec_path.with_file_name(format!(".{}.scope{}.als", name, scope));
+                std::fs::write(&path, with_scope(content, scope))?;
+                path
+            }
+            None => spec_path.to_path_buf(),
+        };
+
+        let mut cmd = Command::new(&self.analyzer_path);
+        cmd.args(["exec", "--type", "none"]).arg(&scoped_path);
+        env.apply(&mut cmd);
+        debug!("Executing command: {:?}", cmd);
+        let output = run_tool(&mut cmd, cancellation);
+
+        if scoped_path != spec_path {
+            let _ = std::fs::remove_file(&scoped_path);
+        }
+        let output = output.map_err(|e| anyhow!("Failed to execute Alloy: {}", e))?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Alloy failed for {:?}: {}",
+                spec_path,
+                stderr.trim()
+            ));
+        }
+        Ok((stdout, stderr))
+    }
+
+    /// Check increasing scopes until a counterexample or the budget stops the sweep
+    pub fn sweep(
+        &self,
+        spec_path: &Path,
+        sweep: &ScopeSweep,
+        env: &ToolEnv,
+        cancellation: &CancellationToken,
+    ) -> Result<SweepOutcome> {
+        let content = std::fs::read_to_string(spec_path)
+            .map_err(|e| anyhow!("Failed to read Alloy spec {:?}: {}", spec_path, e))?;
+        let start = Instant::now();
+        let mut outcome = SweepOutcome::default();
+
+        for scope in sweep.from..=sweep.to {
+            if sweep.budget.is_some_and(|budget| start.elapsed() >= budget) {
+                info!(
+                    "Alloy sweep budget exhausted for {:?} before scope {}",
+                    spec_path, scope
+                );
+                outcome.budget_exhausted = true;
+                break;
+            }
+
+            let (stdout, stderr) =
+                self.run_at_scope(spec_path, &content, Some(scope), env, cancellation)?;
+            let failed = counterexamples(&stdout);
+            outcome.stdout.push_str(&stdout);
+            outcome.stderr.push_str(&stderr);
+
+            if !failed.is_empty() {
+                outcome.counterexample = Some((scope, failed));
+                break;
+            }
+            outcome.max_verified_scope = Some(scope);
+        }
+
+        Ok(outcome)
+    }
+
+    fn run_alloy(&self, input: &VerificationInput) -> Result<VerificationOutput> {
+        let start_time = Instant::now();
+        let spec_path = match &input.target {
+            Target::FormalSpec(path)
+                if path.extension().and_then(|e| e.to_str()) == Some("als") =>
+            {
+                path.clone()
+            }
+            _ => return Err(anyhow!("Alloy plugin only supports .als specifications")),
+        };
+        let tool_config = &input.config.tool_config;
+        let env = ToolEnv::from_tool_config(tool_config);
+
+        let outcome = match ScopeSweep::from_tool_config(tool_config) {
+            Some(sweep) => self.sweep(&spec_path, &sweep, &env, &input.context.cancellation)?,
+            None => {
+                let content = std::fs::read_to_string(&spec_path)?;
+                let scope = tool_config
+                    .get("scope")
+                    .and_then(|v| v.as_u64())
+                    .map(|s| s as u32);
+                let (stdout, stderr) = self.run_at_scope(
+                    &spec_path,
+                    &content,
+                    scope,
+                    &env,
+                    &input.context.cancellation,
+                )?;
+                let failed = counterexamples(&stdout);
+                SweepOutcome {
+                    max_verified_scope: if failed.is_empty() { scope } else { None },
+                    counterexample: (!failed.is_empty()).then(|| (scope.unwrap_or(0), failed)),
+                    budget_exhausted: false,
+                    stdout,
+                    stderr,
+                }
+            }
+        };
+
+        let content = std::fs::read_to_string(&spec_path).unwrap_or_default();
+        let verified = outcome
+            .max_verified_scope
+            .map_or("no scope".to_string(), |s| format!("scope {}", s));
+        let mut violations = Vec::new();
+        if let Some((scope, assertions)) = &outcome.counterexample {
+            for assertion in assertions {
+                let location = match check_line(&content, assertion) {
+                    Some(line) => Location::point(&spec_path, line, 1),
+                    None => Location::file(&spec_path),
+                };
+                violations.push(Violation {
+                    id: "ALLOY_COUNTEREXAMPLE".to_string(),
+                    severity: Severity::Error,
+                    location,
+                    message: format!(
+                        "Assertion `{}` has a counterexample at scope {} (verified up to {})",
+                        assertion, scope, verified
+                    ),
+                    suggestion: Some(
+                        "Open the spec in the Alloy Analyzer at this scope to inspect the instance"
+                            .to_string(),
+                    ),
+                    rule: "alloy_assertion".to_string(),
+                });
+            }
+        } else if outcome.budget_exhausted {
+            violations.push(Violation {
+                id: "ALLOY_SWEEP_BUDGET_EXHAUSTED".to_string(),
+                severity: Severity::Info,
+                location: Location::file(&spec_path),
+                message: format!(
+                    "Scope sweep stopped at the time budget; verified up to {}",
+                    verified
+                ),
+                suggestion: Some("Raise `sweep.budget` to check larger scopes".to_string()),
+                rule: "alloy_sweep".to_string(),
+            });
+        }
+
+        let status = if outcome.counterexample.is_some() {
+            Status::Error
+        } else {
+            Status::Success
+        };
+        if let Some(scope) = outcome.max_verified_scope {
+            info!("Alloy verified {:?} up to scope {}", spec_path, scope);
+        } else if outcome.counterexample.is_none() {
+            warn!("Alloy checked no scope for {:?}", spec_path);
+        }
+        let execution_time = start_time.elapsed();
+
+        Ok(VerificationOutput {
+            status,
+            violations,
+            artifacts: vec![],
+            tool_output: ToolOutput {
+                tool: "alloy".to_string(),
+                stdout: outcome.stdout,
+                stderr: outcome.stderr,
+                exit_code: 0,
+                execution_time,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: execution_time,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: outcome.max_verified_scope,
+                tool_settings: Default::default(),
+            },
+        })
+    }
+}
+
+impl VerificationPlugin for AlloyPlugin {
+    fn name(&self) -> &str {
+        "alloy"
+    }
+
+    fn version(&self) -> &str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn supported_techniques(&self) -> Vec<Technique> {
+        vec![Technique::FormalSpecs, Technique::ModelChecking]
+    }
+
+    fn supported_versions(&self) -> VersionRange {
+        VersionRange {
+            min: Some(semver::Version::new(0, 1, 0)),
+            max: Some(semver::Version::new(1, 0, 0)),
+            requires_exact: None,
+        }
+    }
+
+    fn check_availability(&self) -> ferris_proof_core::Result<ToolInfo> {
+        let output = Command::new(&self.analyzer_path)
+            .arg("version")
+            .output()
+            .map_err(|e| {
+                FerrisProofError::tool_not_found(
+                    "alloy",
+                    format!("Alloy Analyzer not found: {}", e),
+                )
+            })?;
+        if !output.status.success() {
+            return Err(FerrisProofError::tool_not_found(
+                "alloy",
+                format!(
+                    "Alloy Analyzer command failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            ));
+        }
+
+        Ok(ToolInfo {
+            name: "Alloy Analyzer".to_string(),
+            version: String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .find(|word| word.chars().next().is_some_and(|c| c.is_ascii_digit()))
+                .unwrap_or("unknown")
+                .to_string(),
+            path: self.analyzer_path.clone(),
+            available: true,
+            capabilities: vec!["assertion_checking".to_string(), "scope_sweep".to_string()],
+        })
+    }
+
+    fn verify(&self, input: VerificationInput) -> ferris_proof_core::Result<VerificationOutput> {
+        if !self.initialized {
+            return Err(FerrisProofError::plugin(
+                "alloy",
+                "Alloy plugin not initialized",
+            ));
+        }
+
+        self.run_alloy(&input)
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
+    }
+
+    fn parse_output(&self, raw_output: &str) -> ferris_proof_core::Result<StructuredResult> {
+        let failed = counterexamples(raw_output);
+        Ok(StructuredResult {
+            status: if failed.is_empty() {
+                Status::Success
+            } else {
+                Status::Error
+            },
+            violations: vec![],
+            statistics: json!({ "counterexamples": failed }),
+            performance: PerformanceMetrics {
+                execution_time: Duration::from_millis(0),
+                memory_usage: 0,
+                cpu_usage: 0.0,
+                cache_hits: 0,
+            },
+        })
+    }
+
+    fn metadata(&self) -> PluginMetadata {
+        PluginMetadata {
+            name: "alloy".to_string(),
+            version: self.version().to_string(),
+            description: "Alloy assertion checking with increasing-scope sweeps".to_string(),
+            author: "FerrisProof Team".to_string(),
+            license: "MIT".to_string(),
+            homepage: Some("https://alloytools.org".to_string()),
+            techniques: vec![Technique::FormalSpecs, Technique::ModelChecking],
+            supported_platforms: vec![
+                "linux".to_string(),
+                "macos".to_string(),
+                "windows".to_string(),
+            ],
+            dependencies: vec!["alloy".to_string()],
+            renderers: Vec::new(),
+            error_codes: Vec::new(),
+        }
+    }
+
+    fn initialize(&mut self, config: &serde_json::Value) -> ferris_proof_core::Result<()> {
+        if let Some(path) = config
+            .get("alloy")
+            .and_then(|c| c.get("analyzer_path"))
+            .and_then(|v| v.as_str())
+        {
+            self.analyzer_path = PathBuf::from(path);
+        }
+
+        self.check_availability()?;
+        self.initialized = true;
+        info!(
+            "Alloy plugin initialized with analyzer: {:?}",
+            self.analyzer_path
+        );
+        Ok(())
+    }
+
+    fn cleanup(&mut self) -> ferris_proof_core::Result<()> {
+        self.initialized = false;
+        debug!("Alloy plugin cleaned up");
+        Ok(())
+    }
+}
+
+impl Default for AlloyPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_scope_rewrites_checks() {
+        let spec = "sig Node {}\nassert Acyclic { no n: Node | n in n.^next }\ncheck Acyclic for 3 but 4 Int\ncheck Reachable\nrun show for 2\n";
+
+        assert_eq!(
+            with_scope(spec, 6),
+            "sig Node {}\nassert Acyclic { no n: Node | n in n.^next }\ncheck Acyclic for 6 but 4 Int\ncheck Reachable for 6\nrun show for 2\n"
+        );
+        assert_eq!(check_line(spec, "Reachable"), Some(4));
+    }
+
+    #[test]
+    fn test_counterexamples_from_summary() {
+        let stdout = "00. check Acyclic          0       UNSAT\n\
+                      01. check Reachable        1/1     SAT\n\
+                      02. run   show             1/1     SAT\n";
+
+        assert_eq!(counterexamples(stdout), vec!["Reachable"]);
+    }
+}
@@ -0,0 +1,298 @@
+use anyhow::{anyhow, Result};
+use ferris_proof_core::plugins::{run_tool, CancellationToken, ToolEnv};
+use ferris_proof_core::types::{Location, Severity, Violation};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tracing::debug;
+
+/// Exit code of `apalache-mc check` when an invariant has a counterexample
+pub const EXIT_COUNTEREXAMPLE: i32 = 12;
+/// Exit code when the Snowcat type checker rejects the spec
+pub const EXIT_TYPECHECK: i32 = 120;
+
+/// Steps explored when `length` is not configured, matching Apalache's default
+pub const DEFAULT_LENGTH: u32 = 10;
+
+/// Apalache symbolic model checker, the alternative TLA+ backend to TLC
+pub struct Apalache {
+    path: PathBuf,
+    env: ToolEnv,
+}
+
+impl Apalache {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            env: ToolEnv::default(),
+        }
+    }
+
+    /// Run checks with the variables from `[tools.tla_plus.env]`
+    pub fn with_env(mut self, env: ToolEnv) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// `apalache-mc` from the configured path or `PATH`
+    pub fn locate(configured: Option<&Path>) -> Option<PathBuf> {
+        match configured {
+            Some(path) => Some(path.to_path_buf()),
+            None => crate::tla_plus::find_executable("apalache-mc"),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Version reported by `apalache-mc version`, e.g. `0.44.11`
+    pub fn version(&self) -> Result<String> {
+        let output = Command::new(&self.path)
+            .arg("version")
+            .output()
+            .map_err(|e| anyhow!("Failed to execute Apalache at {:?}: {}", self.path, e))?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Apalache command failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .find(|word| word.chars().next().is_some_and(|c| c.is_ascii_digit()))
+            .unwrap_or("unknown")
+            .to_string())
+    }
+
+    /// Check `spec` against the model in `cfg`, writing traces to `run_dir`
+    pub fn check(
+        &self,
+        spec: &Path,
+        cfg: &Path,
+        length: u32,
+        run_dir: &Path,
+        cancellation: &CancellationToken,
+    ) -> Result<Output> {
+        let mut cmd = Command::new(&self.path);
+        if let Some(dir) = spec.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            cmd.current_dir(dir);
+        }
+        cmd.arg("check")
+            .arg(format!("--config={}", cfg.display()))
+            .arg(format!("--length={}", length))
+            .arg(format!("--run-dir={}", run_dir.display()))
+            .arg(spec);
+        self.env.apply(&mut cmd);
+        debug!("Executing command: {:?}", cmd);
+        Ok(run_tool(&mut cmd, cancellation)?)
+    }
+}
+
+/// Type annotation error reported by Apalache's type checker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeError {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+    pub message: String,
+}
+
+impl TypeError {
+    pub fn violation(&self, spec_dir: &Path) -> Violation {
+        Violation {
+            id: "APALACHE_TYPE_ERROR".to_string(),
+            severity: Severity::Error,
+            location: Location::point(spec_dir.join(&self.file), self.line, self.column),
+            message: self.message.clone(),
+            suggestion: Some(
+                "Add or fix the `@type:` annotation; Apalache needs types for constants and variables"
+                    .to_string(),
+            ),
+            rule: "apalache_type".to_string(),
+        }
+    }
+}
+
+/// Type errors in Apalache output, e.g. `[Spec.tla:12:5-12:9]: Undefined name Foo. E@10:00:00.000`
+pub fn type_errors(output: &str) -> Vec<TypeError> {
+    let error =
+        Regex::new(r"(?m)^\[([^\]:]+\.tla):(\d+):(\d+)-\d+:\d+\]: (.+?)(?:\s+E@\S+)?\s*$").unwrap();
+    error
+        .captures_iter(output)
+        .map(|caps| TypeError {
+            file: caps[1].to_string(),
+            line: caps[2].parse().unwrap_or(1),
+            column: caps[3].parse().unwrap_or(1),
+            message: caps[4].trim().to_string(),
+        })
+        .collect()
+}
+
+/// Invariant violation found by Apalache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample {
+    /// Violated invariant, when its index maps onto the model's invariants
+    pub invariant: Option<String>,
+    /// States in the counterexample trace
+    pub states: usize,
+    /// ITF trace written by Apalache, if found
+    pub trace: Option<PathBuf>,
+}
+
+impl Counterexample {
+    /// Parse the violation from Apalache output and the trace in `run_dir`
+    ///
+    /// `invariants` are the model's `INVARIANT`s in order, which Apalache
+    /// refers to by index.
+    pub fn parse(output: &str, invariants: &[String], run_dir: &Path) -> Option<Self> {
+        let violated =
+            Regex::new(r"(?m)^State (\d+): (?:state |action )?invariant (\d+) violated").unwrap();
+        let caps = violated.captures(output);
+        if caps.is_none() && !output.contains("The outcome is: Error") {
+            return None;
+        }
+
+        let state = caps.as_ref().and_then(|caps| caps[1].parse::<usize>().ok());
+        let invariant = caps
+            .as_ref()
+            .and_then(|caps| caps[2].parse::<usize>().ok())
+            .and_then(|index| invariants.get(index).cloned());
+
+        let trace = run_dir.join("violation1.itf.json");
+        let trace_states = std::fs::read_to_string(&trace)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|itf| itf.get("states").and_then(|s| s.as_array()).map(Vec::len));
+
+        Some(Self {
+            invariant,
+            states: trace_states.or(state.map(|s| s + 1)).unwrap_or(0),
+            trace: trace_states.map(|_| trace),
+        })
+    }
+
+    pub fn violation(&self, spec_path: &Path) -> Violation {
+        let spec = std::fs::read_to_string(spec_path).unwrap_or_default();
+        let location = self
+            .invariant
+            .as_deref()
+            .and_then(|name| definition_line(&spec, name))
+            .map_or_else(
+                || Location::file(spec_path),
+                |line| Location::point(spec_path, line, 1),
+            );
+        let invariant = self
+            .invariant
+            .as_deref()
+            .map_or("An invariant".to_string(), |name| {
+                format!("Invariant `{}`", name)
+            });
+
+        Violation {
+            id: "APALACHE_COUNTEREXAMPLE".to_string(),
+            severity: Severity::Error,
+            location,
+            message: format!("{} is violated by a {}-state trace", invariant, self.states),
+            suggestion: Some(match &self.trace {
+                Some(trace) => format!("Inspect the counterexample trace in {}", trace.display()),
+                None => "Rerun apalache-mc check to inspect the counterexample".to_string(),
+            }),
+            rule: "tla_invariant".to_string(),
+        }
+    }
+}
+
+/// `INVARIANT`/`INVARIANTS` names declared in a TLC model config
+pub fn cfg_invariants(cfg: &str) -> Vec<String> {
+    let mut invariants = Vec::new();
+    let mut in_section = false;
+    for line in cfg.lines().map(str::trim) {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("INVARIANT" | "INVARIANTS") => {
+                in_section = true;
+                invariants.extend(words.map(str::to_string));
+            }
+            Some(word) if word.chars().all(|c| c.is_ascii_uppercase()) => in_section = false,
+            Some(_) if in_section => invariants.extend(line.split_whitespace().map(str::to_string)),
+            _ => {}
+        }
+    }
+    invariants
+}
+
+/// 1-based line of `name ==` in a TLA+ module
+pub(crate) fn definition_line(spec: &str, name: &str) -> Option<u32> {
+    let pattern = Regex::new(&format!(r"^\s*{}\s*(\(.*\))?\s*==", regex::escape(name))).ok()?;
+    spec.lines()
+        .position(|line| pattern.is_match(line))
+        .map(|idx| idx as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_type_errors_are_located() {
+        let output = "\
+PASS #1: TypeCheckerSnowcat                                       I@10:00:00.100
+[Counter.tla:12:5-12:9]: Undefined name Foo. Introduce a type annotation. E@10:00:00.200
+ > Snowcat asks you to fix the types. Meow.                       E@10:00:00.201
+Type checker [FAILED]
+";
+        assert_eq!(
+            type_errors(output),
+            vec![TypeError {
+                file: "Counter.tla".to_string(),
+                line: 12,
+                column: 5,
+                message: "Undefined name Foo. Introduce a type annotation.".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_counterexample_maps_invariant_and_trace() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("violation1.itf.json"),
+            r#"{ "vars": ["x"], "states": [{ "x": 0 }, { "x": 1 }, { "x": 2 }] }"#,
+        )
+        .unwrap();
+        let output = "\
+State 2: Checking 2 state invariants                              I@10:00:01.000
+State 2: state invariant 1 violated.                              E@10:00:01.100
+The outcome is: Error                                             I@10:00:01.200
+";
+        let invariants = vec!["TypeOK".to_string(), "Bounded".to_string()];
+
+        let counterexample = Counterexample::parse(output, &invariants, temp_dir.path()).unwrap();
+        assert_eq!(counterexample.invariant.as_deref(), Some("Bounded"));
+        assert_eq!(counterexample.states, 3);
+
+        let spec = temp_dir.path().join("Counter.tla");
+        std::fs::write(
+            &spec,
+            "---- MODULE Counter ----\nVARIABLE x\nBounded == x < 2\n====\n",
+        )
+        .unwrap();
+        let violation = counterexample.violation(&spec);
+        assert_eq!(violation.location.line, Some(3));
+        assert!(violation.message.contains("`Bounded`"));
+
+        assert!(
+            Counterexample::parse("The outcome is: NoError", &invariants, temp_dir.path())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_cfg_invariants_in_order() {
+        let cfg = "INIT Init\nNEXT Next\nINVARIANT TypeOK\nINVARIANTS\n  Safe\n  Bounded\nCONSTANT N = 3\n";
+        assert_eq!(cfg_invariants(cfg), vec!["TypeOK", "Safe", "Bounded"]);
+    }
+}
@@ -0,0 +1,243 @@
+use anyhow::{anyhow, Result};
+use ferris_proof_core::types::{Location, Severity, Violation};
+use std::path::Path;
+
+pub const MISSING_RUST_VERSION: &str = "FP-MD-001";
+pub const WILDCARD_DEPENDENCY: &str = "FP-MD-002";
+pub const MISSING_LICENSE: &str = "FP-MD-003";
+
+/// Crate metadata rules, typically read from `[metadata]` in ferrisproof.toml
+#[derive(Debug, Clone, Default)]
+pub struct MetadataPolicy {
+    pub require_rust_version: bool,
+    pub require_license: bool,
+    pub forbid_wildcard_dependencies: bool,
+}
+
+impl MetadataPolicy {
+    pub fn is_empty(&self) -> bool {
+        !self.require_rust_version && !self.require_license && !self.forbid_wildcard_dependencies
+    }
+}
+
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Check a `Cargo.toml` against the policy
+///
+/// Violations are reported as warnings; metadata rules are advisory.
+pub fn check_manifest(manifest_path: &Path, policy: &MetadataPolicy) -> Result<Vec<Violation>> {
+    let content = std::fs::read_to_string(manifest_path)
+        .map_err(|e| anyhow!("Failed to read {:?}: {}", manifest_path, e))?;
+    let manifest: toml::Value = toml::from_str(&content)
+        .map_err(|e| anyhow!("Failed to parse {:?}: {}", manifest_path, e))?;
+
+    let mut violations = Vec::new();
+    let package = manifest.get("package");
+
+    // Virtual workspace manifests only carry dependency rules
+    if let Some(package) = package {
+        if policy.require_rust_version && package.get("rust-version").is_none() {
+            violations.push(violation(
+                MISSING_RUST_VERSION,
+                "metadata.rust_version",
+                header_location(manifest_path, &content, "[package]"),
+                "Crate does not declare a minimum supported Rust version".to_string(),
+                "Add `rust-version = \"1.xx\"` to [package]",
+            ));
+        }
+
+        if policy.require_license
+            && package.get("license").is_none()
+            && package.get("license-file").is_none()
+        {
+            violations.push(violation(
+                MISSING_LICENSE,
+                "metadata.license",
+                header_location(manifest_path, &content, "[package]"),
+                "Crate does not declare a license".to_string(),
+                "Add `license = \"MIT OR Apache-2.0\"` (or `license-file`) to [package]",
+            ));
+        }
+    }
+
+    if policy.forbid_wildcard_dependencies {
+        for (table_name, dependencies) in dependency_tables(&manifest) {
+            for (name, spec) in dependencies {
+                if is_wildcard(spec) {
+                    violations.push(violation(
+                        WILDCARD_DEPENDENCY,
+                        "metadata.wildcard_dependency",
+                        dependency_location(manifest_path, &content, name),
+                        format!(
+                            "Dependency `{}` in [{}] uses a wildcard version",
+                            name, table_name
+                        ),
+                        "Pin a semver requirement such as \"1.2\"",
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+/// All dependency tables, including `[target.*]` and `[workspace]` ones
+fn dependency_tables(manifest: &toml::Value) -> Vec<(String, &toml::value::Table)> {
+    fn collect<'a>(
+        prefix: &str,
+        root: &'a toml::Value,
+        tables: &mut Vec<(String, &'a toml::value::Table)>,
+    ) {
+        for name in DEPENDENCY_TABLES {
+            if let Some(table) = root.get(name).and_then(|t| t.as_table()) {
+                tables.push((format!("{}{}", prefix, name), table));
+            }
+        }
+    }
+
+    let mut tables = Vec::new();
+    collect("", manifest, &mut tables);
+    if let Some(workspace) = manifest.get("workspace") {
+        collect("workspace.", workspace, &mut tables);
+    }
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        for (target, value) in targets {
+            collect(&format!("target.{}.", target), value, &mut tables);
+        }
+    }
+
+    tables
+}
+
+fn is_wildcard(spec: &toml::Value) -> bool {
+    let version = match spec {
+        toml::Value::String(version) => Some(version.as_str()),
+        toml::Value::Table(table) => table.get("version").and_then(|v| v.as_str()),
+        _ => None,
+    };
+    version.is_some_and(|v| v.trim() == "*")
+}
+
+/// Span of a table header such as `[package]`
+fn header_location(manifest_path: &Path, content: &str, header: &str) -> Location {
+    content
+        .lines()
+        .position(|line| line.trim_start().starts_with(header))
+        .map_or_else(
+            || Location::file(manifest_path),
+            |idx| {
+                let line = idx as u32 + 1;
+                Location::span(manifest_path, line, 1, line, header.len() as u32 + 1)
+            },
+        )
+}
+
+/// Span of a dependency's `"*"` version, or of its name when not found
+fn dependency_location(manifest_path: &Path, content: &str, name: &str) -> Location {
+    let found = content.lines().enumerate().find(|(_, line)| {
+        line.trim_start()
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.trim_start().starts_with('=') || rest.starts_with('.'))
+    });
+    let Some((idx, text)) = found else {
+        return Location::file(manifest_path);
+    };
+
+    let line = idx as u32 + 1;
+    let (start, len) = match text.find("\"*\"") {
+        Some(start) => (start, 3),
+        None => (text.len() - text.trim_start().len(), name.len()),
+    };
+    let column = text[..start].chars().count() as u32 + 1;
+
+    Location::span(manifest_path, line, column, line, column + len as u32)
+}
+
+fn violation(
+    code: &str,
+    rule: &str,
+    location: Location,
+    message: String,
+    suggestion: &str,
+) -> Violation {
+    Violation {
+        id: code.to_string(),
+        severity: Severity::Warning,
+        location,
+        message,
+        suggestion: Some(suggestion.to_string()),
+        rule: rule.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_rules() -> MetadataPolicy {
+        MetadataPolicy {
+            require_rust_version: true,
+            require_license: true,
+            forbid_wildcard_dependencies: true,
+        }
+    }
+
+    #[test]
+    fn test_policy_violations() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            r#"[package]
+name = "demo"
+version = "0.1.0"
+
+[dependencies]
+serde = "1.0"
+rand = "*"
+
+[target.'cfg(unix)'.dependencies]
+libc = { version = "*" }
+"#,
+        )
+        .unwrap();
+
+        let violations = check_manifest(&manifest, &all_rules()).unwrap();
+        let codes: Vec<&str> = violations.iter().map(|v| v.id.as_str()).collect();
+
+        assert_eq!(
+            codes,
+            vec![
+                MISSING_RUST_VERSION,
+                MISSING_LICENSE,
+                WILDCARD_DEPENDENCY,
+                WILDCARD_DEPENDENCY
+            ]
+        );
+        assert_eq!(violations[2].location.line, Some(7));
+        assert_eq!(violations[2].location.column, Some(8));
+        assert_eq!(violations[3].location.column, Some(20));
+    }
+
+    #[test]
+    fn test_compliant_manifest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            r#"[package]
+name = "demo"
+version = "0.1.0"
+license.workspace = true
+rust-version = "1.75"
+
+[dependencies]
+serde = { workspace = true }
+"#,
+        )
+        .unwrap();
+
+        assert!(check_manifest(&manifest, &all_rules()).unwrap().is_empty());
+    }
+}
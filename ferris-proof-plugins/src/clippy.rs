@@ -0,0 +1,441 @@
+use crate::proptest_harness::CargoPackage;
+use anyhow::{anyhow, Result};
+use ferris_proof_core::{
+    plugins::{
+        run_tool, PerformanceMetrics, PluginMetadata, StructuredResult, ToolInfo,
+        VerificationInput, VerificationOutput, VerificationPlugin, VersionRange,
+    },
+    types::*,
+    verification::Target,
+    FerrisProofError,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Line of `cargo --message-format=json` output
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<CompilerMessage>,
+}
+
+#[derive(Deserialize)]
+struct CompilerMessage {
+    message: String,
+    code: Option<DiagnosticCode>,
+    level: String,
+    #[serde(default)]
+    spans: Vec<DiagnosticSpan>,
+    #[serde(default)]
+    children: Vec<CompilerMessage>,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticSpan {
+    file_name: String,
+    line_start: u32,
+    line_end: u32,
+    column_start: u32,
+    column_end: u32,
+    is_primary: bool,
+    suggested_replacement: Option<String>,
+}
+
+impl CompilerMessage {
+    fn severity(&self) -> Severity {
+        match self.level.as_str() {
+            "error" | "error: internal compiler error" => Severity::Error,
+            "warning" => Severity::Warning,
+            _ => Severity::Info,
+        }
+    }
+
+    /// First `help` child, with its suggested replacement when there is one
+    fn suggestion(&self) -> Option<String> {
+        let help = self.children.iter().find(|child| child.level == "help")?;
+        let replacement = help
+            .spans
+            .iter()
+            .find_map(|span| span.suggested_replacement.as_deref())
+            .filter(|replacement| !replacement.is_empty());
+        Some(match replacement {
+            Some(replacement) => format!("{}: `{}`", help.message, replacement),
+            None => help.message.clone(),
+        })
+    }
+}
+
+/// Compiler and clippy diagnostics in cargo JSON output, as violations
+///
+/// Span paths are relative to the workspace root. Diagnostics without a
+/// primary span, such as "aborting due to previous error", are skipped.
+pub fn parse_diagnostics(output: &str, workspace_root: &Path) -> Vec<Violation> {
+    let mut violations: Vec<Violation> = Vec::new();
+    for line in output.lines().filter(|line| line.starts_with('{')) {
+        let Ok(CargoMessage {
+            reason,
+            message: Some(message),
+        }) = serde_json::from_str(line)
+        else {
+            continue;
+        };
+        if reason != "compiler-message" {
+            continue;
+        }
+        let Some(span) = message.spans.iter().find(|span| span.is_primary) else {
+            continue;
+        };
+
+        let violation = Violation {
+            id: message
+                .code
+                .as_ref()
+                .map_or_else(|| "rustc".to_string(), |code| code.code.clone()),
+            severity: message.severity(),
+            location: Location::span(
+                workspace_root.join(&span.file_name),
+                span.line_start,
+                span.column_start,
+                span.line_end,
+                span.column_end,
+            ),
+            message: message.message.clone(),
+            suggestion: message.suggestion(),
+            rule: "type_safety".to_string(),
+        };
+        // cargo check and clippy report the same compiler warnings
+        let duplicate = violations.iter().any(|existing| {
+            existing.id == violation.id
+                && existing.message == violation.message
+                && existing.location.file == violation.location.file
+                && existing.location.line == violation.location.line
+                && existing.location.column == violation.location.column
+        });
+        if !duplicate {
+            violations.push(violation);
+        }
+    }
+    violations
+}
+
+/// Type-safety checks with `cargo check` and `cargo clippy`
+///
+/// Reads `warn`, `deny` and `allow` lint lists from the tool config, passed
+/// to clippy as `-W`, `-D` and `-A`. Clippy is skipped when the crate does
+/// not compile, or when it is not installed.
+pub struct ClippyPlugin {
+    tool_path: PathBuf,
+    initialized: bool,
+}
+
+impl ClippyPlugin {
+    pub fn new() -> Self {
+        Self {
+            tool_path: PathBuf::from("cargo"),
+            initialized: false,
+        }
+    }
+
+    fn clippy_available(&self) -> bool {
+        Command::new(&self.tool_path)
+            .args(["clippy", "--version"])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    fn run_clippy(&self, input: &VerificationInput) -> Result<VerificationOutput> {
+        let start_time = std::time::Instant::now();
+
+        let (anchor, only_file) = match &input.target {
+            Target::RustFile(path) | Target::Item { file: path, .. } => {
+                (path.clone(), Some(path.clone()))
+            }
+            Target::Module(_) => (input.context.working_dir.clone(), None),
+            Target::FormalSpec(_) => {
+                return Err(anyhow!("Clippy plugin only supports Rust targets"))
+            }
+        };
+        let package = CargoPackage::locate(&anchor)?;
+
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+        let mut exit_code = 0;
+        let mut subcommands = vec![("check", Vec::new())];
+        if self.clippy_available() {
+            subcommands.push(("clippy", lint_flags(&input.config.tool_config)));
+        } else {
+            warn!("cargo clippy is not installed, reporting cargo check diagnostics only");
+        }
+        for (subcommand, lint_flags) in subcommands {
+            // Clippy would only repeat the errors of a crate that does not compile
+            if exit_code != 0 {
+                break;
+            }
+            let mut cmd = Command::new(&self.tool_path);
+            cmd.current_dir(&package.workspace_root).args([
+                subcommand,
+                "--message-format=json",
+                "--all-targets",
+                "--package",
+                &package.name,
+            ]);
+            if !lint_flags.is_empty() {
+                cmd.arg("--").args(lint_flags);
+            }
+            debug!("Executing command: {:?}", cmd);
+
+            let output = run_tool(&mut cmd, &input.context.cancellation)?;
+            stdout.push_str(&String::from_utf8_lossy(&output.stdout));
+            stderr.push_str(&String::from_utf8_lossy(&output.stderr));
+            exit_code = output.status.code().unwrap_or(-1);
+        }
+
+        let mut violations = parse_diagnostics(&stdout, &package.workspace_root);
+        if let Some(file) = &only_file {
+            let file = file.canonicalize().unwrap_or_else(|_| file.clone());
+            violations.retain(|violation| {
+                violation
+                    .location
+                    .file
+                    .canonicalize()
+                    .is_ok_and(|path| path == file)
+            });
+        }
+        info!(
+            "{} type-safety diagnostics for {:?}",
+            violations.len(),
+            anchor
+        );
+
+        let status = if violations.iter().any(|v| v.severity == Severity::Error) {
+            Status::Error
+        } else if violations.iter().any(|v| v.severity == Severity::Warning) {
+            Status::Warning
+        } else {
+            Status::Success
+        };
+        let execution_time = start_time.elapsed();
+
+        Ok(VerificationOutput {
+            status,
+            violations,
+            artifacts: vec![],
+            tool_output: ToolOutput {
+                tool: "clippy".to_string(),
+                stdout,
+                stderr,
+                exit_code,
+                execution_time,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: execution_time,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+        })
+    }
+}
+
+/// `-W`/`-D`/`-A` arguments for the configured lint lists
+fn lint_flags(tool_config: &serde_json::Value) -> Vec<String> {
+    let mut flags = Vec::new();
+    for (key, flag) in [("allow", "-A"), ("warn", "-W"), ("deny", "-D")] {
+        let lints = tool_config.get(key).and_then(|v| v.as_array());
+        for lint in lints.into_iter().flatten().filter_map(|v| v.as_str()) {
+            flags.push(flag.to_string());
+            flags.push(lint.to_string());
+        }
+    }
+    flags
+}
+
+impl VerificationPlugin for ClippyPlugin {
+    fn name(&self) -> &str {
+        "clippy"
+    }
+
+    fn version(&self) -> &str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn supported_techniques(&self) -> Vec<Technique> {
+        vec![Technique::TypeSafety]
+    }
+
+    fn supported_versions(&self) -> VersionRange {
+        VersionRange {
+            min: Some(semver::Version::new(0, 1, 0)),
+            max: Some(semver::Version::new(1, 0, 0)),
+            requires_exact: None,
+        }
+    }
+
+    fn check_availability(&self) -> ferris_proof_core::Result<ToolInfo> {
+        let output = Command::new(&self.tool_path)
+            .arg("--version")
+            .output()
+            .map_err(|e| {
+                FerrisProofError::tool_not_found("clippy", format!("Cargo not found: {}", e))
+            })?;
+        if !output.status.success() {
+            return Err(FerrisProofError::tool_not_found(
+                "clippy",
+                "Cargo is not working properly",
+            ));
+        }
+
+        let mut capabilities = vec!["compiler_diagnostics".to_string()];
+        if self.clippy_available() {
+            capabilities.push("clippy_lints".to_string());
+        }
+        Ok(ToolInfo {
+            name: "clippy".to_string(),
+            version: String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or("unknown")
+                .to_string(),
+            path: self.tool_path.clone(),
+            available: true,
+            capabilities,
+        })
+    }
+
+    fn verify(&self, input: VerificationInput) -> ferris_proof_core::Result<VerificationOutput> {
+        if !self.initialized {
+            return Err(FerrisProofError::plugin(
+                "clippy",
+                "Clippy plugin not initialized",
+            ));
+        }
+
+        self.run_clippy(&input)
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
+    }
+
+    fn parse_output(&self, raw_output: &str) -> ferris_proof_core::Result<StructuredResult> {
+        let violations = parse_diagnostics(raw_output, Path::new(""));
+        let errors = violations
+            .iter()
+            .filter(|v| v.severity == Severity::Error)
+            .count();
+        Ok(StructuredResult {
+            status: if errors > 0 {
+                Status::Error
+            } else if violations.is_empty() {
+                Status::Success
+            } else {
+                Status::Warning
+            },
+            statistics: json!({
+                "errors": errors,
+                "diagnostics": violations.len()
+            }),
+            violations,
+            performance: PerformanceMetrics {
+                execution_time: Duration::from_millis(0),
+                memory_usage: 0,
+                cpu_usage: 0.0,
+                cache_hits: 0,
+            },
+        })
+    }
+
+    fn metadata(&self) -> PluginMetadata {
+        PluginMetadata {
+            name: "clippy".to_string(),
+            version: self.version().to_string(),
+            description: "Compiler and clippy diagnostics for the type-safety layer".to_string(),
+            author: "FerrisProof Team".to_string(),
+            license: "MIT".to_string(),
+            homepage: Some("https://doc.rust-lang.org/clippy/".to_string()),
+            techniques: vec![Technique::TypeSafety],
+            supported_platforms: vec![
+                "linux".to_string(),
+                "macos".to_string(),
+                "windows".to_string(),
+            ],
+            dependencies: vec!["cargo".to_string()],
+            renderers: Vec::new(),
+            error_codes: Vec::new(),
+        }
+    }
+
+    fn initialize(&mut self, _config: &serde_json::Value) -> ferris_proof_core::Result<()> {
+        self.check_availability()?;
+        self.initialized = true;
+        info!("Clippy plugin initialized with tool: {:?}", self.tool_path);
+        Ok(())
+    }
+
+    fn cleanup(&mut self) -> ferris_proof_core::Result<()> {
+        self.initialized = false;
+        debug!("Clippy plugin cleaned up");
+        Ok(())
+    }
+}
+
+impl Default for ClippyPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = r#"{"reason":"compiler-artifact","package_id":"codec 0.1.0"}
+{"reason":"compiler-message","message":{"message":"used `unwrap()` on an `Option` value","code":{"code":"clippy::unwrap_used","explanation":null},"level":"warning","spans":[{"file_name":"codec/src/frame.rs","line_start":14,"line_end":14,"column_start":9,"column_end":27,"is_primary":true,"suggested_replacement":null}],"children":[{"message":"if this value is `None`, it will panic","code":null,"level":"help","spans":[],"children":[]}]}}
+{"reason":"compiler-message","message":{"message":"mismatched types","code":{"code":"E0308","explanation":"..."},"level":"error","spans":[{"file_name":"codec/src/frame.rs","line_start":20,"line_end":20,"column_start":5,"column_end":8,"is_primary":true,"suggested_replacement":null}],"children":[{"message":"try using a conversion method","code":null,"level":"help","spans":[{"file_name":"codec/src/frame.rs","line_start":20,"line_end":20,"column_start":5,"column_end":8,"is_primary":true,"suggested_replacement":"len.into()"}],"children":[]}]}}
+{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[]}}
+{"reason":"compiler-message","message":{"message":"used `unwrap()` on an `Option` value","code":{"code":"clippy::unwrap_used","explanation":null},"level":"warning","spans":[{"file_name":"codec/src/frame.rs","line_start":14,"line_end":14,"column_start":9,"column_end":27,"is_primary":true,"suggested_replacement":null}],"children":[]}}
+{"reason":"build-finished","success":false}"#;
+
+    #[test]
+    fn test_parse_diagnostics_into_violations() {
+        let violations = parse_diagnostics(OUTPUT, Path::new("/workspace"));
+        assert_eq!(violations.len(), 2);
+
+        assert_eq!(violations[0].id, "clippy::unwrap_used");
+        assert_eq!(violations[0].severity, Severity::Warning);
+        assert_eq!(
+            violations[0].location.file,
+            PathBuf::from("/workspace/codec/src/frame.rs")
+        );
+        assert_eq!(violations[0].location.line, Some(14));
+        assert_eq!(
+            violations[0].suggestion.as_deref(),
+            Some("if this value is `None`, it will panic")
+        );
+
+        assert_eq!(violations[1].id, "E0308");
+        assert_eq!(violations[1].severity, Severity::Error);
+        assert_eq!(
+            violations[1].suggestion.as_deref(),
+            Some("try using a conversion method: `len.into()`")
+        );
+
+        let flags =
+            lint_flags(&json!({ "deny": ["clippy::unwrap_used"], "warn": ["missing_docs"] }));
+        assert_eq!(
+            flags,
+            vec!["-W", "missing_docs", "-D", "clippy::unwrap_used"]
+        );
+    }
+}
@@ -0,0 +1,637 @@
+use crate::sandbox::{ResourceLimits, SandboxedExecutor};
+use crate::tla_plus::find_executable;
+use anyhow::{anyhow, Result};
+use ferris_proof_core::{
+    plugins::{
+        PerformanceMetrics, PluginMetadata, StructuredResult, ToolEnv, ToolInfo, VerificationInput,
+        VerificationOutput, VerificationPlugin, VersionRange,
+    },
+    types::*,
+    verification::Target,
+    FerrisProofError,
+};
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Environment variable telling `#[contract]` which verifier's attributes to emit
+pub const DEDUCTIVE_ENV: &str = "FERRIS_PROOF_DEDUCTIVE";
+
+/// Seconds a verifier may run when no timeout is configured
+const DEFAULT_TIMEOUT: u64 = 600;
+
+/// Deductive verifier driven through its cargo subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verifier {
+    Prusti,
+    Creusot,
+}
+
+impl Verifier {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "prusti" => Some(Self::Prusti),
+            "creusot" => Some(Self::Creusot),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Prusti => "prusti",
+            Self::Creusot => "creusot",
+        }
+    }
+
+    /// Cargo subcommand binary, e.g. `cargo-prusti`
+    pub fn executable(self) -> &'static str {
+        match self {
+            Self::Prusti => "cargo-prusti",
+            Self::Creusot => "cargo-creusot",
+        }
+    }
+
+    /// Arguments cargo would pass when running the subcommand
+    fn subcommand(self) -> &'static [&'static str] {
+        match self {
+            Self::Prusti => &["prusti"],
+            Self::Creusot => &["creusot", "prove"],
+        }
+    }
+}
+
+/// Compiler-style diagnostic reported by the verifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+    /// Path as printed, relative to the package root
+    pub file: Option<PathBuf>,
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// A contract could not be proved
+    ProofFailed,
+    /// A contract is not a valid specification
+    InvalidSpec,
+    /// Code the verifier cannot reason about
+    Unsupported,
+    /// The crate did not compile
+    BuildError,
+}
+
+impl Diagnostic {
+    pub fn violation(&self, package_root: &Path, verifier: Verifier) -> Violation {
+        let (id, severity, suggestion) = match self.kind {
+            DiagnosticKind::ProofFailed => (
+                "DEDUCTIVE_PROOF_FAILED",
+                Severity::Error,
+                "Strengthen the `requires` clauses, add loop invariants or fix the function body",
+            ),
+            DiagnosticKind::InvalidSpec => (
+                "DEDUCTIVE_INVALID_SPEC",
+                Severity::Error,
+                "Keep contracts to pure expressions over the arguments and `result`",
+            ),
+            DiagnosticKind::Unsupported => (
+                "DEDUCTIVE_UNSUPPORTED",
+                Severity::Warning,
+                "Move the code outside verified functions or mark it as trusted",
+            ),
+            DiagnosticKind::BuildError => (
+                "DEDUCTIVE_BUILD_ERROR",
+                Severity::Error,
+                "Fix the compile error; the verifier builds the crate with its own toolchain",
+            ),
+        };
+        let location = match &self.file {
+            Some(file) => Location::point(package_root.join(file), self.line, self.column),
+            None => Location::file(package_root.join("Cargo.toml")),
+        };
+
+        Violation {
+            id: id.to_string(),
+            severity,
+            location,
+            message: format!("{}: {}", verifier.name(), self.message),
+            suggestion: Some(suggestion.to_string()),
+            rule: "deductive_proof".to_string(),
+        }
+    }
+}
+
+/// Diagnostics in verifier output, e.g.
+///
+/// ```text
+/// error: [Prusti: verification error] postcondition might not hold.
+///   --> src/lib.rs:4:29
+/// ```
+///
+/// Plain compiler warnings and the closing `aborting due to` summary are
+/// dropped.
+pub fn diagnostics(output: &str) -> Vec<Diagnostic> {
+    let header = Regex::new(r"^(error|warning)(?:\[\w+\])?: (.+)$").unwrap();
+    let arrow = Regex::new(r"^\s*--> (.+):(\d+):(\d+)\s*$").unwrap();
+    let prusti = Regex::new(r"^\[Prusti: ([a-z ]+)\]\s*(.*)$").unwrap();
+
+    let mut found: Vec<Diagnostic> = Vec::new();
+    let mut awaiting_location = false;
+    for line in output.lines() {
+        if let Some(caps) = header.captures(line) {
+            awaiting_location = false;
+            let is_error = &caps[1] == "error";
+            let text = caps[2].trim();
+
+            let (kind, message) = if let Some(prusti) = prusti.captures(text) {
+                let kind = match &prusti[1] {
+                    "verification error" => DiagnosticKind::ProofFailed,
+                    "invalid specification" => DiagnosticKind::InvalidSpec,
+                    "unsupported feature" => DiagnosticKind::Unsupported,
+                    _ if is_error => DiagnosticKind::BuildError,
+                    _ => continue,
+                };
+                (kind, prusti[2].to_string())
+            } else if proof_failure(text) {
+                (DiagnosticKind::ProofFailed, text.to_string())
+            } else if is_error
+                && !text.starts_with("aborting due to")
+                && !text.starts_with("could not compile")
+            {
+                (DiagnosticKind::BuildError, text.to_string())
+            } else {
+                continue;
+            };
+
+            found.push(Diagnostic {
+                kind,
+                message,
+                file: None,
+                line: 1,
+                column: 1,
+            });
+            awaiting_location = true;
+        } else if let Some(caps) = arrow.captures(line) {
+            if let Some(diagnostic) = found.last_mut().filter(|_| awaiting_location) {
+                diagnostic.file = Some(PathBuf::from(&caps[1]));
+                diagnostic.line = caps[2].parse().unwrap_or(1);
+                diagnostic.column = caps[3].parse().unwrap_or(1);
+            }
+            awaiting_location = false;
+        }
+    }
+    found
+}
+
+/// Goal failures as Creusot reports them from Why3
+fn proof_failure(message: &str) -> bool {
+    let message = message.to_lowercase();
+    ["not proved", "unproved", "failed to prove"]
+        .iter()
+        .any(|phrase| message.contains(phrase))
+}
+
+/// Proves `#[requires]`/`#[ensures]` contracts with Prusti or Creusot
+///
+/// The verifier runs over the whole package inside the sandbox, with
+/// `FERRIS_PROOF_DEDUCTIVE` set so that `#[contract]` expands to the
+/// verifier's own attributes. Its diagnostics become violations; for file
+/// and item targets only those in the target's file are kept.
+pub struct DeductivePlugin {
+    verifier: Verifier,
+    path: Option<PathBuf>,
+    timeout: u64,
+    args: Vec<String>,
+    initialized: bool,
+}
+
+impl DeductivePlugin {
+    pub fn new() -> Self {
+        Self {
+            verifier: Verifier::Prusti,
+            path: None,
+            timeout: DEFAULT_TIMEOUT,
+            args: Vec::new(),
+            initialized: false,
+        }
+    }
+
+    fn executable(&self) -> Option<PathBuf> {
+        self.path
+            .clone()
+            .or_else(|| find_executable(self.verifier.executable()))
+    }
+
+    fn prove(&self, input: &VerificationInput) -> Result<VerificationOutput> {
+        let start_time = std::time::Instant::now();
+
+        let package_root = input.context.working_dir.canonicalize()?;
+        let only = match &input.target {
+            Target::RustFile(path) | Target::Item { file: path, .. } => Some(path.clone()),
+            Target::Module(_) => None,
+            Target::FormalSpec(_) => {
+                return Err(anyhow!("Deductive plugin only supports Rust targets"))
+            }
+        };
+        if input.context.cancellation.is_cancelled() {
+            return Err(anyhow!("Deductive verification cancelled"));
+        }
+
+        let executable = self.executable().ok_or_else(|| {
+            anyhow!(
+                "{} not found; install it or set [tools.deductive] path",
+                self.verifier.executable()
+            )
+        })?;
+        let timeout = input
+            .context
+            .timeout
+            .map_or(self.timeout, |t| t.as_secs().min(self.timeout));
+
+        // Verifiers build the crate and run an SMT backend, well beyond the
+        // default sandbox limits
+        let executor = SandboxedExecutor::new()
+            .with_allowed_paths(vec![package_root.clone()])
+            .with_limits(ResourceLimits {
+                max_memory: 16 * 1024 * 1024 * 1024,
+                max_cpu_time: timeout,
+                max_processes: 4096,
+                ..ResourceLimits::default()
+            })
+            .with_timeout(Duration::from_secs(timeout));
+
+        let mut env = HashMap::from([
+            (DEDUCTIVE_ENV.to_string(), self.verifier.name().to_string()),
+            ("CARGO_NET_OFFLINE".to_string(), "true".to_string()),
+            (
+                "CARGO_TARGET_DIR".to_string(),
+                input
+                    .context
+                    .cache_dir
+                    .join("deductive-target")
+                    .display()
+                    .to_string(),
+            ),
+        ]);
+        // The sandbox restricts PATH, so point the verifier at cargo directly
+        if let Some(cargo) = find_executable("cargo") {
+            env.insert("CARGO".to_string(), cargo.display().to_string());
+        }
+        env.extend(ToolEnv::from_tool_config(&input.config.tool_config).vars());
+
+        let mut args: Vec<&str> = self.verifier.subcommand().to_vec();
+        args.extend(self.args.iter().map(String::as_str));
+        let command = executable.display().to_string();
+        debug!("Executing sandboxed command: {} {:?}", command, args);
+
+        let run = executor.execute(&command, &args, env, Some(&package_root));
+        let output = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle.block_on(run),
+            Err(_) => tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(run),
+        }
+        .map_err(|e| anyhow!("Failed to run {}: {}", self.verifier.executable(), e))?;
+
+        let mut violations: Vec<Violation> = diagnostics(&output.stderr)
+            .into_iter()
+            .filter(|diagnostic| match (&only, &diagnostic.file) {
+                (Some(target), Some(file)) => same_file(&package_root, target, file),
+                _ => true,
+            })
+            .map(|diagnostic| diagnostic.violation(&package_root, self.verifier))
+            .collect();
+        if output.timeout_occurred {
+            violations.push(Violation {
+                id: "DEDUCTIVE_TIMEOUT".to_string(),
+                severity: Severity::Warning,
+                location: Location::file(package_root.join("Cargo.toml")),
+                message: format!(
+                    "{} did not finish within {}s",
+                    self.verifier.name(),
+                    timeout
+                ),
+                suggestion: Some("Raise [tools.deductive] timeout".to_string()),
+                rule: "deductive_proof".to_string(),
+            });
+        } else if output.exit_code != 0 && violations.is_empty() && only.is_none() {
+            violations.push(
+                Diagnostic {
+                    kind: DiagnosticKind::BuildError,
+                    message: output
+                        .stderr
+                        .lines()
+                        .rev()
+                        .find(|line| !line.trim().is_empty())
+                        .unwrap_or("verifier failed")
+                        .trim()
+                        .to_string(),
+                    file: None,
+                    line: 1,
+                    column: 1,
+                }
+                .violation(&package_root, self.verifier),
+            );
+        }
+        info!(
+            "{} finished with exit code {}: {} violation(s)",
+            self.verifier.name(),
+            output.exit_code,
+            violations.len()
+        );
+
+        let status = if violations.iter().any(|v| v.severity == Severity::Error) {
+            Status::Error
+        } else if violations.iter().any(|v| v.severity == Severity::Warning) {
+            Status::Warning
+        } else {
+            Status::Success
+        };
+        let execution_time = start_time.elapsed();
+
+        Ok(VerificationOutput {
+            status,
+            violations,
+            artifacts: vec![],
+            tool_output: ToolOutput {
+                tool: self.verifier.name().to_string(),
+                stdout: output.stdout,
+                stderr: output.stderr,
+                exit_code: output.exit_code,
+                execution_time,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: execution_time,
+                cache_hit_rate: 0.0,
+                memory_usage: output.resource_usage.peak_memory,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+        })
+    }
+}
+
+/// Whether a diagnostic's package-relative `file` is the target file
+fn same_file(package_root: &Path, target: &Path, file: &Path) -> bool {
+    let target = target
+        .canonicalize()
+        .unwrap_or_else(|_| package_root.join(target));
+    let file = package_root
+        .join(file)
+        .canonicalize()
+        .unwrap_or_else(|_| package_root.join(file));
+    target == file
+}
+
+impl VerificationPlugin for DeductivePlugin {
+    fn name(&self) -> &str {
+        "deductive"
+    }
+
+    fn version(&self) -> &str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn supported_techniques(&self) -> Vec<Technique> {
+        vec![Technique::DeductiveProofs]
+    }
+
+    fn supported_versions(&self) -> VersionRange {
+        VersionRange {
+            min: None,
+            max: None,
+            requires_exact: None,
+        }
+    }
+
+    fn check_availability(&self) -> ferris_proof_core::Result<ToolInfo> {
+        let executable = self.executable().ok_or_else(|| {
+            FerrisProofError::tool_not_found(
+                "deductive",
+                format!("{} not found in PATH", self.verifier.executable()),
+            )
+        })?;
+        let output = Command::new(&executable)
+            .args(self.verifier.subcommand().iter().take(1))
+            .arg("--version")
+            .output()
+            .map_err(|e| {
+                FerrisProofError::tool_not_found(
+                    "deductive",
+                    format!("Verifier not found at {:?}: {}", executable, e),
+                )
+            })?;
+        if !output.status.success() {
+            return Err(FerrisProofError::tool_not_found(
+                "deductive",
+                format!("{} is not working properly", self.verifier.executable()),
+            ));
+        }
+
+        Ok(ToolInfo {
+            name: self.verifier.name().to_string(),
+            version: String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .find(|word| word.chars().next().is_some_and(|c| c.is_ascii_digit()))
+                .unwrap_or("unknown")
+                .to_string(),
+            path: executable,
+            available: true,
+            capabilities: vec![
+                "deductive_proofs".to_string(),
+                "contracts".to_string(),
+                "sandboxed".to_string(),
+            ],
+        })
+    }
+
+    fn verify(&self, input: VerificationInput) -> ferris_proof_core::Result<VerificationOutput> {
+        if !self.initialized {
+            return Err(FerrisProofError::plugin(
+                "deductive",
+                "Deductive plugin not initialized",
+            ));
+        }
+
+        self.prove(&input)
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
+    }
+
+    fn parse_output(&self, raw_output: &str) -> ferris_proof_core::Result<StructuredResult> {
+        let found = diagnostics(raw_output);
+        let count = |kind| found.iter().filter(|d| d.kind == kind).count();
+        let errors = found
+            .iter()
+            .filter(|d| d.kind != DiagnosticKind::Unsupported)
+            .count();
+        Ok(StructuredResult {
+            status: if errors > 0 {
+                Status::Error
+            } else if found.is_empty() {
+                Status::Success
+            } else {
+                Status::Warning
+            },
+            violations: vec![],
+            statistics: json!({
+                "proof_failures": count(DiagnosticKind::ProofFailed),
+                "invalid_specs": count(DiagnosticKind::InvalidSpec),
+                "unsupported": count(DiagnosticKind::Unsupported),
+                "build_errors": count(DiagnosticKind::BuildError),
+            }),
+            performance: PerformanceMetrics {
+                execution_time: Duration::from_millis(0),
+                memory_usage: 0,
+                cpu_usage: 0.0,
+                cache_hits: 0,
+            },
+        })
+    }
+
+    fn metadata(&self) -> PluginMetadata {
+        PluginMetadata {
+            name: "deductive".to_string(),
+            version: self.version().to_string(),
+            description: "Proves #[requires]/#[ensures] contracts with Prusti or Creusot"
+                .to_string(),
+            author: "FerrisProof Team".to_string(),
+            license: "MIT".to_string(),
+            homepage: Some("https://github.com/viperproject/prusti-dev".to_string()),
+            techniques: vec![Technique::DeductiveProofs],
+            supported_platforms: vec!["linux".to_string(), "macos".to_string()],
+            dependencies: vec!["cargo-prusti".to_string(), "cargo-creusot".to_string()],
+            renderers: Vec::new(),
+            error_codes: Vec::new(),
+        }
+    }
+
+    fn initialize(&mut self, config: &serde_json::Value) -> ferris_proof_core::Result<()> {
+        if let Some(deductive) = config.get("deductive") {
+            if let Some(name) = deductive.get("verifier").and_then(|v| v.as_str()) {
+                self.verifier = Verifier::parse(name).ok_or_else(|| {
+                    FerrisProofError::plugin(
+                        "deductive",
+                        format!("Unknown deductive verifier: {}", name),
+                    )
+                })?;
+            }
+            if let Some(path) = deductive.get("path").and_then(|v| v.as_str()) {
+                self.path = Some(PathBuf::from(path));
+            }
+            if let Some(timeout) = deductive.get("timeout").and_then(|v| v.as_u64()) {
+                self.timeout = timeout;
+            }
+            if let Some(args) = deductive.get("args").and_then(|v| v.as_array()) {
+                self.args = args
+                    .iter()
+                    .filter_map(|arg| arg.as_str().map(String::from))
+                    .collect();
+            }
+        }
+
+        self.check_availability()?;
+        self.initialized = true;
+        info!(
+            "Deductive plugin initialized with {}",
+            self.verifier.executable()
+        );
+        Ok(())
+    }
+
+    fn cleanup(&mut self) -> ferris_proof_core::Result<()> {
+        self.initialized = false;
+        Ok(())
+    }
+}
+
+impl Default for DeductivePlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_prusti_diagnostics() {
+        let stderr = "\
+warning: unused variable: `y`
+ --> src/lib.rs:2:9
+error: [Prusti: verification error] postcondition might not hold.
+ --> src/lib.rs:4:29
+  |
+4 | #[contract(ensures = result > x)]
+  |                      ^^^^^^^^^^
+error: [Prusti: unsupported feature] raw pointers are not supported
+  --> src/ffi.rs:10:5
+error: aborting due to 2 previous errors
+";
+        assert_eq!(
+            diagnostics(stderr),
+            vec![
+                Diagnostic {
+                    kind: DiagnosticKind::ProofFailed,
+                    message: "postcondition might not hold.".to_string(),
+                    file: Some(PathBuf::from("src/lib.rs")),
+                    line: 4,
+                    column: 29,
+                },
+                Diagnostic {
+                    kind: DiagnosticKind::Unsupported,
+                    message: "raw pointers are not supported".to_string(),
+                    file: Some(PathBuf::from("src/ffi.rs")),
+                    line: 10,
+                    column: 5,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classifies_creusot_and_build_errors() {
+        let stderr = "\
+error[E0425]: cannot find value `z` in this scope
+ --> src/lib.rs:7:5
+error: goal `bump_post` not proved
+ --> src/lib.rs:3:1
+";
+        let found = diagnostics(stderr);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].kind, DiagnosticKind::BuildError);
+        assert_eq!(found[1].kind, DiagnosticKind::ProofFailed);
+
+        let violation = found[1].violation(Path::new("/work"), Verifier::Creusot);
+        assert_eq!(violation.id, "DEDUCTIVE_PROOF_FAILED");
+        assert_eq!(violation.location.file, PathBuf::from("/work/src/lib.rs"));
+        assert_eq!(violation.location.line, Some(3));
+        assert_eq!(violation.message, "creusot: goal `bump_post` not proved");
+    }
+
+    #[test]
+    fn test_reads_verifier_from_config() {
+        let mut plugin = DeductivePlugin::new();
+        let config = json!({
+            "deductive": { "verifier": "creusot", "path": "/nonexistent/cargo-creusot", "timeout": 30 }
+        });
+
+        // Availability fails without the tool, but the settings are read first
+        assert!(plugin.initialize(&config).is_err());
+        assert_eq!(plugin.verifier, Verifier::Creusot);
+        assert_eq!(plugin.timeout, 30);
+        assert!(plugin
+            .initialize(&json!({ "deductive": { "verifier": "kani" } }))
+            .is_err());
+    }
+}
@@ -0,0 +1,786 @@
+//! Contracts written informally in doc comments
+//!
+//! `# Panics`, `# Safety`, `# Errors` and `# Invariants` sections of function
+//! docs are extracted into machine-readable contract stubs. Documented panics
+//! and invariants that neither an assertion in the function nor a test backs
+//! are reported, and property-test skeletons can be generated for them.
+
+use crate::serde_roundtrip::{declaration_line, rust_files};
+use anyhow::{anyhow, Context, Result};
+use ferris_proof_core::{cache::module_path_of, types::*};
+use quote::ToTokens;
+use regex::Regex;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use syn::{Attribute, FnArg, ImplItem, Item, Pat, Signature, Type, Visibility};
+
+/// First line of the generated test file; files without it are never replaced
+pub const GENERATED_HEADER: &str =
+    "// Generated by ferris-proof (`ferris-proof generate --target doc-contracts`). Edits may be overwritten.";
+
+/// Integration test target holding the generated skeletons
+pub const TEST_TARGET: &str = "ferris_proof_doc_contracts";
+
+/// File the contract stubs are written to
+pub const STUBS_FILE: &str = "doc-contracts.json";
+
+/// Macros whose presence in a body counts as an explicit assertion
+const ASSERTIONS: &str = r"\b(assert|assert_eq|assert_ne|debug_assert|debug_assert_eq|debug_assert_ne|panic|unreachable)\s*!";
+
+/// Doc section a clause was written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClauseKind {
+    Panics,
+    Safety,
+    Errors,
+    Invariant,
+}
+
+impl ClauseKind {
+    fn from_heading(heading: &str) -> Option<Self> {
+        match heading.trim().to_lowercase().as_str() {
+            "panics" | "panic" => Some(Self::Panics),
+            "safety" => Some(Self::Safety),
+            "errors" => Some(Self::Errors),
+            "invariants" | "invariant" => Some(Self::Invariant),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Panics => "panics",
+            Self::Safety => "safety",
+            Self::Errors => "errors",
+            Self::Invariant => "invariant",
+        }
+    }
+}
+
+/// One documented condition, e.g. a bullet of a `# Panics` section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Clause {
+    pub kind: ClauseKind,
+    pub text: String,
+}
+
+/// Contract of one function, as documented
+#[derive(Debug, Clone, Serialize)]
+pub struct DocContract {
+    /// Path inside the crate, e.g. `math::divide` or `math::Ratio::new`
+    pub path: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub clauses: Vec<Clause>,
+    /// The body asserts or panics explicitly
+    pub asserts: bool,
+    /// Tests calling the function
+    pub tests: usize,
+    /// `#[should_panic]` tests calling the function
+    pub should_panic_tests: usize,
+    /// Argument names and types, for functions a generated test can call
+    #[serde(skip)]
+    args: Option<Vec<Arg>>,
+}
+
+#[derive(Debug, Clone)]
+struct Arg {
+    name: String,
+    ty: String,
+    by_ref: bool,
+}
+
+impl DocContract {
+    fn has(&self, kind: ClauseKind) -> bool {
+        self.clauses.iter().any(|c| c.kind == kind)
+    }
+
+    fn location(&self) -> Location {
+        Location::point(self.file.clone(), self.line as u32, 1)
+    }
+
+    fn test_name(&self, kind: ClauseKind, index: usize) -> String {
+        let mut name = kind.name().to_string();
+        for segment in self.path.split("::") {
+            name.push_str("__");
+            name.push_str(segment);
+        }
+        if index > 0 {
+            name.push_str(&format!("_{}", index));
+        }
+        name
+    }
+}
+
+/// Body of a test, or of a `proptest!` block
+struct TestBody {
+    should_panic: bool,
+    tokens: String,
+}
+
+/// Doc contracts of one package
+#[derive(Debug, Clone)]
+pub struct DocContracts {
+    pub crate_name: String,
+    pub contracts: Vec<DocContract>,
+}
+
+impl DocContracts {
+    /// Extract contracts from `src/` of the package at `package_root`
+    ///
+    /// Tests are looked up in `src/` and `tests/`, except in the generated
+    /// skeletons, which would otherwise hide every gap they were made for.
+    pub fn build(package_root: &Path) -> Result<Self> {
+        let manifest_path = package_root.join("Cargo.toml");
+        let manifest: toml::Value = toml::from_str(
+            &std::fs::read_to_string(&manifest_path)
+                .with_context(|| format!("Failed to read {:?}", manifest_path))?,
+        )
+        .with_context(|| format!("Failed to parse {:?}", manifest_path))?;
+        let crate_name = manifest
+            .get("lib")
+            .and_then(|lib| lib.get("name"))
+            .or_else(|| manifest.get("package").and_then(|p| p.get("name")))
+            .and_then(|name| name.as_str())
+            .ok_or_else(|| anyhow!("{:?} has no package name", manifest_path))?
+            .replace('-', "_");
+
+        let mut sources = Vec::new();
+        let mut tests = Vec::new();
+        for dir in ["src", "tests"] {
+            for file in rust_files(&package_root.join(dir)) {
+                let content = std::fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read {:?}", file))?;
+                if content.starts_with(GENERATED_HEADER) {
+                    continue;
+                }
+                let ast = syn::parse_file(&content)
+                    .with_context(|| format!("Failed to parse {:?}", file))?;
+                collect_tests(&ast.items, &mut tests);
+                if dir == "src" {
+                    sources.push((file, content, ast));
+                }
+            }
+        }
+
+        let assertions = Regex::new(ASSERTIONS)?;
+        let mut contracts = Vec::new();
+        for (file, content, ast) in &sources {
+            let relative = file.strip_prefix(package_root).unwrap_or(file);
+            let module_path = module_path_of(&relative.to_string_lossy());
+            let module_path = if module_path == "crate" {
+                ""
+            } else {
+                module_path.as_str()
+            };
+            let source = SourceFile {
+                path: file,
+                content,
+                assertions: &assertions,
+            };
+            source.visit_items(&ast.items, module_path, true, &mut contracts);
+        }
+
+        for contract in &mut contracts {
+            let name = contract.path.rsplit("::").next().unwrap_or_default();
+            let call = Regex::new(&format!(r"\b{}\s*\(", regex::escape(name)))?;
+            for test in tests.iter().filter(|t| call.is_match(&t.tokens)) {
+                contract.tests += 1;
+                if test.should_panic {
+                    contract.should_panic_tests += 1;
+                }
+            }
+        }
+
+        Ok(Self {
+            crate_name,
+            contracts,
+        })
+    }
+
+    /// Warnings for documented panics and invariants nothing checks
+    pub fn gaps(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for contract in self.contracts.iter().filter(|c| !c.asserts) {
+            if contract.has(ClauseKind::Panics) && contract.should_panic_tests == 0 {
+                violations.push(Violation {
+                    id: "DOC_CONTRACT_UNTESTED_PANIC".to_string(),
+                    severity: Severity::Warning,
+                    location: contract.location(),
+                    message: format!(
+                        "`{}` documents when it panics, but no #[should_panic] test calls it and it asserts nothing",
+                        contract.path
+                    ),
+                    suggestion: Some(
+                        "Add a #[should_panic] test, or assert the documented condition; \
+                         `ferris-proof generate --target doc-contracts` writes a skeleton"
+                            .to_string(),
+                    ),
+                    rule: "doc_contracts".to_string(),
+                });
+            }
+            if contract.has(ClauseKind::Invariant) && contract.tests == 0 {
+                violations.push(Violation {
+                    id: "DOC_CONTRACT_UNTESTED_INVARIANT".to_string(),
+                    severity: Severity::Warning,
+                    location: contract.location(),
+                    message: format!(
+                        "`{}` documents an invariant, but no test calls it and it asserts nothing",
+                        contract.path
+                    ),
+                    suggestion: Some(
+                        "Add a property test for the invariant, or debug_assert! it".to_string(),
+                    ),
+                    rule: "doc_contracts".to_string(),
+                });
+            }
+        }
+        violations
+    }
+
+    /// Contract stubs as JSON
+    pub fn render_stubs(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.contracts)?)
+    }
+
+    /// Number of skeletons [`Self::render_tests`] generates
+    pub fn skeletons(&self) -> usize {
+        self.callable()
+            .map(|(contract, _)| {
+                contract
+                    .clauses
+                    .iter()
+                    .filter(|c| matches!(c.kind, ClauseKind::Panics | ClauseKind::Invariant))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Property-test skeletons for the panics and invariants of callable functions
+    pub fn render_tests(&self) -> String {
+        let mut out = String::new();
+        out.push_str(GENERATED_HEADER);
+        out.push_str("\n\nuse proptest::prelude::*;\n\nproptest! {\n");
+
+        for (contract, args) in self.callable() {
+            let params = args
+                .iter()
+                .map(|arg| format!("{} in any::<{}>()", arg.name, arg.ty))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let call = format!(
+                "{}::{}({})",
+                self.crate_name,
+                contract.path,
+                args.iter()
+                    .map(|arg| if arg.by_ref {
+                        format!("&{}", arg.name)
+                    } else {
+                        arg.name.clone()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+
+            for kind in [ClauseKind::Panics, ClauseKind::Invariant] {
+                for (index, clause) in contract
+                    .clauses
+                    .iter()
+                    .filter(|c| c.kind == kind)
+                    .enumerate()
+                {
+                    out.push_str(&format!(
+                        "    // {}: {}\n    #[test]\n",
+                        kind.name(),
+                        clause.text
+                    ));
+                    let body = match kind {
+                        ClauseKind::Panics => format!(
+                            "        // TODO: restrict the inputs to the documented condition\n        \
+                             prop_assume!(false);\n        let _ = {};",
+                            call
+                        ),
+                        _ => format!(
+                            "        let result = {};\n        \
+                             // TODO: assert the documented invariant\n        let _ = result;",
+                            call
+                        ),
+                    };
+                    if kind == ClauseKind::Panics {
+                        out.push_str("    #[should_panic]\n");
+                    }
+                    out.push_str(&format!(
+                        "    fn {}({}) {{\n{}\n    }}\n\n",
+                        contract.test_name(kind, index),
+                        params,
+                        body
+                    ));
+                }
+            }
+        }
+        if out.ends_with("\n\n") {
+            out.pop();
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Write the skeletons to `path`, refusing to replace a hand-written file
+    ///
+    /// Returns `false` when an existing file without the generated header was
+    /// left untouched.
+    pub fn write_tests(&self, path: &Path) -> Result<bool> {
+        if path.exists() && !std::fs::read_to_string(path)?.starts_with(GENERATED_HEADER) {
+            return Ok(false);
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.render_tests())
+            .map_err(|e| anyhow!("Failed to write contract skeletons {:?}: {}", path, e))?;
+        Ok(true)
+    }
+
+    fn callable(&self) -> impl Iterator<Item = (&DocContract, &Vec<Arg>)> {
+        self.contracts
+            .iter()
+            .filter_map(|contract| contract.args.as_ref().map(|args| (contract, args)))
+    }
+}
+
+struct SourceFile<'a> {
+    path: &'a Path,
+    content: &'a str,
+    assertions: &'a Regex,
+}
+
+impl SourceFile<'_> {
+    fn visit_items(
+        &self,
+        items: &[Item],
+        module_path: &str,
+        reachable: bool,
+        contracts: &mut Vec<DocContract>,
+    ) {
+        let join = |name: &str| {
+            if module_path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}::{}", module_path, name)
+            }
+        };
+
+        for item in items {
+            match item {
+                Item::Fn(f) => {
+                    let public = reachable && matches!(f.vis, Visibility::Public(_));
+                    self.push(
+                        contracts,
+                        join(&f.sig.ident.to_string()),
+                        &f.attrs,
+                        &f.sig,
+                        &f.block,
+                        public,
+                    );
+                }
+                Item::Impl(imp) if imp.trait_.is_none() => {
+                    let Type::Path(ty) = &*imp.self_ty else {
+                        continue;
+                    };
+                    let Some(ty) = ty.path.segments.last() else {
+                        continue;
+                    };
+                    for impl_item in &imp.items {
+                        if let ImplItem::Fn(f) = impl_item {
+                            // Skeletons only call free functions
+                            self.push(
+                                contracts,
+                                join(&format!("{}::{}", ty.ident, f.sig.ident)),
+                                &f.attrs,
+                                &f.sig,
+                                &f.block,
+                                false,
+                            );
+                        }
+                    }
+                }
+                Item::Mod(m) if !is_cfg_test(&m.attrs) => {
+                    if let Some((_, nested)) = &m.content {
+                        let reachable = reachable && matches!(m.vis, Visibility::Public(_));
+                        self.visit_items(nested, &join(&m.ident.to_string()), reachable, contracts);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn push(
+        &self,
+        contracts: &mut Vec<DocContract>,
+        path: String,
+        attrs: &[Attribute],
+        sig: &Signature,
+        block: &syn::Block,
+        public: bool,
+    ) {
+        let clauses = doc_clauses(attrs);
+        if clauses.is_empty() {
+            return;
+        }
+        let name = sig.ident.to_string();
+        contracts.push(DocContract {
+            line: declaration_line(self.content, "fn", &name),
+            path,
+            file: self.path.to_path_buf(),
+            clauses,
+            asserts: self
+                .assertions
+                .is_match(&block.to_token_stream().to_string()),
+            tests: 0,
+            should_panic_tests: 0,
+            args: if public { callable_args(sig) } else { None },
+        });
+    }
+}
+
+/// Clauses of the contract sections of a doc comment
+///
+/// Each bullet, or each paragraph when a section has no bullets, is a clause.
+/// Code blocks are skipped.
+pub fn doc_clauses(attrs: &[Attribute]) -> Vec<Clause> {
+    let mut clauses = Vec::new();
+    let mut section = None;
+    let mut current = String::new();
+    let mut in_code = false;
+
+    let mut flush = |section: Option<ClauseKind>, current: &mut String| {
+        if let (Some(kind), false) = (section, current.trim().is_empty()) {
+            clauses.push(Clause {
+                kind,
+                text: current.trim().to_string(),
+            });
+        }
+        current.clear();
+    };
+
+    for line in doc_lines(attrs) {
+        let line = line.trim();
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix('#') {
+            flush(section, &mut current);
+            section = ClauseKind::from_heading(heading.trim_start_matches('#'));
+            continue;
+        }
+        if let Some(bullet) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            flush(section, &mut current);
+            current.push_str(bullet);
+        } else if line.is_empty() {
+            flush(section, &mut current);
+        } else {
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(line);
+        }
+    }
+    flush(section, &mut current);
+    clauses
+}
+
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(nv) => match &nv.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .flat_map(|doc| doc.lines().map(String::from).collect::<Vec<_>>())
+        .collect()
+}
+
+/// Arguments of a function a generated test can call with arbitrary values
+///
+/// Async, generic and `&mut` functions, and methods, are not callable.
+fn callable_args(sig: &Signature) -> Option<Vec<Arg>> {
+    if sig.asyncness.is_some()
+        || sig.unsafety.is_some()
+        || sig
+            .generics
+            .params
+            .iter()
+            .any(|p| !matches!(p, syn::GenericParam::Lifetime(_)))
+    {
+        return None;
+    }
+    sig.inputs
+        .iter()
+        .map(|input| {
+            let FnArg::Typed(arg) = input else {
+                return None;
+            };
+            let Pat::Ident(name) = &*arg.pat else {
+                return None;
+            };
+            let (ty, by_ref) = match &*arg.ty {
+                Type::Reference(r) if r.mutability.is_none() => (&*r.elem, true),
+                Type::Reference(_) => return None,
+                ty => (ty, false),
+            };
+            // `&str` and `&[T]` are generated as their owned counterparts
+            let ty = match ty {
+                Type::Path(p) if p.path.is_ident("str") => "String".to_string(),
+                Type::Slice(s) => format!("Vec<{}>", s.elem.to_token_stream()),
+                ty => ty.to_token_stream().to_string(),
+            };
+            Some(Arg {
+                name: name.ident.to_string(),
+                ty,
+                by_ref,
+            })
+        })
+        .collect()
+}
+
+fn collect_tests(items: &[Item], tests: &mut Vec<TestBody>) {
+    for item in items {
+        match item {
+            Item::Fn(f) if has_attr(&f.attrs, "test") => tests.push(TestBody {
+                should_panic: has_attr(&f.attrs, "should_panic"),
+                tokens: f.block.to_token_stream().to_string(),
+            }),
+            Item::Macro(m)
+                if m.mac
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|s| s.ident == "proptest") =>
+            {
+                let tokens = m.mac.tokens.to_string();
+                tests.push(TestBody {
+                    should_panic: tokens.contains("should_panic"),
+                    tokens,
+                });
+            }
+            Item::Mod(m) => {
+                if let Some((_, nested)) = &m.content {
+                    collect_tests(nested, tests);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether an attribute's last path segment is `name`, e.g. `#[tokio::test]`
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs
+        .iter()
+        .any(|attr| attr.path().segments.last().is_some_and(|s| s.ident == name))
+}
+
+fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg")
+            && attr
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|ident| ident == "test")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_package(root: &Path) {
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"ledger-core\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/lib.rs"),
+            r#"pub mod math;
+
+/// Settles an account.
+///
+/// # Panics
+///
+/// Panics if `amount` exceeds the balance.
+pub fn settle(balance: u64, amount: u64) -> u64 {
+    assert!(amount <= balance);
+    balance - amount
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/math.rs"),
+            r#"/// Integer division.
+///
+/// # Panics
+///
+/// - if `divisor` is zero
+///
+/// # Invariants
+///
+/// The result never exceeds `numerator`.
+///
+/// ```
+/// assert_eq!(ledger_core::math::divide(4, 2), 2);
+/// ```
+pub fn divide(numerator: u32, divisor: u32) -> u32 {
+    numerator / divisor
+}
+
+/// # Safety
+///
+/// `ptr` must be valid for reads.
+pub unsafe fn read(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+pub struct Ratio(u32);
+
+impl Ratio {
+    /// # Invariants
+    /// The denominator is never zero.
+    pub fn denominator(&self) -> u32 {
+        self.0
+    }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("tests/ratio.rs"),
+            "#[test]\nfn denominator_is_positive() {\n    assert!(ratio().denominator() > 0);\n}\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_doc_sections_become_clauses() {
+        let item: syn::ItemFn = syn::parse_quote! {
+            /// Summary.
+            ///
+            /// # Panics
+            ///
+            /// - if `a` is zero
+            /// - if `b` is
+            ///   zero
+            ///
+            /// # Examples
+            ///
+            /// Not a contract.
+            ///
+            /// # Errors
+            ///
+            /// Returns `Err` when the file is missing.
+            fn f(a: u8, b: u8) {}
+        };
+        assert_eq!(
+            doc_clauses(&item.attrs),
+            vec![
+                Clause {
+                    kind: ClauseKind::Panics,
+                    text: "if `a` is zero".to_string()
+                },
+                Clause {
+                    kind: ClauseKind::Panics,
+                    text: "if `b` is zero".to_string()
+                },
+                Clause {
+                    kind: ClauseKind::Errors,
+                    text: "Returns `Err` when the file is missing.".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gaps_skip_asserted_and_tested_contracts() {
+        let temp_dir = TempDir::new().unwrap();
+        write_package(temp_dir.path());
+
+        let contracts = DocContracts::build(temp_dir.path()).unwrap();
+        let paths: Vec<&str> = contracts
+            .contracts
+            .iter()
+            .map(|c| c.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "settle",
+                "math::divide",
+                "math::read",
+                "math::Ratio::denominator"
+            ]
+        );
+
+        let gaps = contracts.gaps();
+        let ids: Vec<(&str, &str)> = gaps
+            .iter()
+            .map(|v| (v.id.as_str(), v.message.split('`').nth(1).unwrap()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("DOC_CONTRACT_UNTESTED_PANIC", "math::divide"),
+                ("DOC_CONTRACT_UNTESTED_INVARIANT", "math::divide"),
+            ]
+        );
+        assert_eq!(gaps[0].location.line, Some(14));
+
+        let stubs: serde_json::Value =
+            serde_json::from_str(&contracts.render_stubs().unwrap()).unwrap();
+        assert_eq!(stubs[2]["clauses"][0]["kind"], "safety");
+    }
+
+    #[test]
+    fn test_skeletons_call_free_functions() {
+        let temp_dir = TempDir::new().unwrap();
+        write_package(temp_dir.path());
+        let contracts = DocContracts::build(temp_dir.path()).unwrap();
+
+        let rendered = contracts.render_tests();
+        assert!(rendered.starts_with(GENERATED_HEADER));
+        assert!(rendered.contains(
+            "    #[should_panic]\n    fn panics__math__divide(numerator in any::<u32>(), divisor in any::<u32>()) {"
+        ));
+        assert!(rendered.contains("let result = ledger_core::math::divide(numerator, divisor);"));
+        // Unsafe functions and methods get stubs but no skeleton
+        assert!(!rendered.contains("read("));
+        assert!(!rendered.contains("denominator"));
+        assert_eq!(contracts.skeletons(), 3);
+
+        // The generated file does not count as tests for the gaps it was made for
+        let path = temp_dir
+            .path()
+            .join("tests")
+            .join(format!("{}.rs", TEST_TARGET));
+        assert!(contracts.write_tests(&path).unwrap());
+        assert_eq!(
+            DocContracts::build(temp_dir.path()).unwrap().gaps().len(),
+            2
+        );
+    }
+}
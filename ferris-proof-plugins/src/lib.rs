@@ -1,7 +1,31 @@
+pub mod alloy;
+pub mod apalache;
+pub mod cargo_metadata;
+pub mod clippy;
+pub mod deductive;
+pub mod doc_contracts;
+pub mod libtest;
+pub mod miri;
+pub mod mutants;
+pub mod proptest_harness;
 pub mod proptest_plugin;
 pub mod sandbox;
+pub mod serde_roundtrip;
+pub mod session_types;
+pub mod smt;
+pub mod snapshot_tests;
 pub mod tla_plus;
+pub mod tla_skeleton;
+pub mod tlc_config;
+pub mod tlc_tuning;
 
+pub use alloy::AlloyPlugin;
+pub use clippy::ClippyPlugin;
+pub use deductive::DeductivePlugin;
+pub use miri::MiriPlugin;
+pub use mutants::CargoMutantsPlugin;
 pub use proptest_plugin::ProptestPlugin;
 pub use sandbox::SandboxedExecutor;
+pub use serde_roundtrip::SerdeRoundtripPlugin;
+pub use smt::SmtPlugin;
 pub use tla_plus::TlaPlusPlugin;
@@ -0,0 +1,229 @@
+use serde::Deserialize;
+use serde_json::json;
+use std::process::Command;
+use std::time::Duration;
+
+/// Runner driving the test binaries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TestRunner {
+    #[default]
+    Cargo,
+    /// cargo-nextest, which runs each test in its own process
+    Nextest { retries: u32 },
+}
+
+impl TestRunner {
+    /// Whether `cargo nextest` is installed
+    pub fn nextest_available() -> bool {
+        Command::new("cargo")
+            .args(["nextest", "--version"])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    /// `cargo test` or `cargo nextest run`, before target selection
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new("cargo");
+        match self {
+            TestRunner::Cargo => cmd.arg("test"),
+            TestRunner::Nextest { .. } => cmd.args(["nextest", "run"]),
+        };
+        cmd
+    }
+
+    /// Make the command print one libtest JSON event per line
+    ///
+    /// libtest's `--format json` is unstable, so `RUSTC_BOOTSTRAP` enables it
+    /// on stable toolchains; nextest emits the same events behind its own
+    /// experimental flag. Output of each test is captured into its events.
+    pub fn json_format<'c>(&self, cmd: &'c mut Command) -> &'c mut Command {
+        match self {
+            TestRunner::Cargo => cmd
+                .args([
+                    "--",
+                    "-Z",
+                    "unstable-options",
+                    "--format",
+                    "json",
+                    "--report-time",
+                ])
+                .env("RUSTC_BOOTSTRAP", "1"),
+            TestRunner::Nextest { retries } => cmd
+                .args(["--no-fail-fast", "--message-format", "libtest-json"])
+                .arg(format!("--retries={}", retries))
+                .env("NEXTEST_EXPERIMENTAL_LIBTEST_JSON", "1"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+/// Final event of one test
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    pub outcome: TestOutcome,
+    pub exec_time: Option<Duration>,
+    /// Captured output, reported for failed tests
+    pub stdout: Option<String>,
+}
+
+/// Tests reported by one or more libtest runs
+#[derive(Debug, Clone, Default)]
+pub struct TestRun {
+    pub tests: Vec<TestCase>,
+    /// Test binaries that started, zero when none was built
+    pub suites: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Event {
+    Suite {
+        event: String,
+    },
+    Test {
+        event: String,
+        name: String,
+        exec_time: Option<f64>,
+        stdout: Option<String>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+impl TestRun {
+    /// Collect events from output, skipping lines that are not JSON events
+    pub fn parse(output: &str) -> Self {
+        let mut run = Self::default();
+        for line in output.lines().map(str::trim) {
+            if !line.starts_with('{') {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(Event::Suite { event }) if event == "started" => run.suites += 1,
+                Ok(Event::Test {
+                    event,
+                    name,
+                    exec_time,
+                    stdout,
+                }) => {
+                    let outcome = match event.as_str() {
+                        "ok" => TestOutcome::Passed,
+                        "failed" => TestOutcome::Failed,
+                        "ignored" => TestOutcome::Ignored,
+                        _ => continue,
+                    };
+                    run.tests.push(TestCase {
+                        name,
+                        outcome,
+                        exec_time: exec_time
+                            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                            .map(Duration::from_secs_f64),
+                        stdout,
+                    });
+                }
+                _ => {}
+            }
+        }
+        run
+    }
+
+    pub fn count(&self, outcome: TestOutcome) -> usize {
+        self.tests
+            .iter()
+            .filter(|test| test.outcome == outcome)
+            .count()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &TestCase> {
+        self.tests
+            .iter()
+            .filter(|test| test.outcome == TestOutcome::Failed)
+    }
+
+    /// Counts and per-test durations for `StructuredResult::statistics`
+    pub fn statistics(&self) -> serde_json::Value {
+        let tests: Vec<_> = self
+            .tests
+            .iter()
+            .map(|test| {
+                json!({
+                    "name": test.name,
+                    "outcome": format!("{:?}", test.outcome).to_lowercase(),
+                    "exec_time_ms": test.exec_time.map(|time| time.as_millis() as u64),
+                })
+            })
+            .collect();
+        json!({
+            "test_cases_executed": self.count(TestOutcome::Passed) + self.count(TestOutcome::Failed),
+            "failures": self.count(TestOutcome::Failed),
+            "successes": self.count(TestOutcome::Passed),
+            "ignored": self.count(TestOutcome::Ignored),
+            "tests": tests,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_counts_outcomes_and_skips_other_lines() {
+        let output = r#"
+   Compiling codec v0.1.0
+{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "roundtrip" }
+{ "type": "test", "name": "roundtrip", "event": "ok", "exec_time": 0.25 }
+{ "type": "test", "name": "shrinks_ok", "event": "failed", "exec_time": 1.5, "stdout": "thread 'shrinks_ok' panicked at tests/property/codec.rs:12:5:\n" }
+{ "type": "test", "event": "ignored", "name": "slow" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 1, "measured": 0, "filtered_out": 0, "exec_time": 1.8 }
+test result: ok. this line is not counted
+"#;
+        let run = TestRun::parse(output);
+        assert_eq!(run.suites, 1);
+        assert_eq!(run.count(TestOutcome::Passed), 1);
+        assert_eq!(run.count(TestOutcome::Ignored), 1);
+
+        let failed: Vec<_> = run.failures().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].name, "shrinks_ok");
+        assert_eq!(failed[0].exec_time, Some(Duration::from_millis(1500)));
+        assert!(failed[0].stdout.as_deref().unwrap().contains("codec.rs:12"));
+
+        let statistics = run.statistics();
+        assert_eq!(statistics["test_cases_executed"], 2);
+        assert_eq!(statistics["tests"][0]["exec_time_ms"], 250);
+    }
+
+    #[test]
+    fn test_nextest_runner_requests_libtest_json_with_retries() {
+        let runner = TestRunner::Nextest { retries: 2 };
+        let mut cmd = runner.command();
+        runner.json_format(cmd.args(["--package", "codec"]));
+        let args: Vec<_> = cmd.get_args().map(|arg| arg.to_string_lossy()).collect();
+        assert_eq!(
+            args,
+            vec![
+                "nextest",
+                "run",
+                "--package",
+                "codec",
+                "--no-fail-fast",
+                "--message-format",
+                "libtest-json",
+                "--retries=2"
+            ]
+        );
+        assert!(cmd
+            .get_envs()
+            .any(|(name, _)| name == "NEXTEST_EXPERIMENTAL_LIBTEST_JSON"));
+    }
+}
@@ -0,0 +1,516 @@
+use anyhow::{anyhow, Result};
+use ferris_proof_core::{
+    cache::module_path_of,
+    plugins::{
+        run_tool, PerformanceMetrics, PluginMetadata, StructuredResult, ToolInfo,
+        VerificationInput, VerificationOutput, VerificationPlugin, VersionRange,
+    },
+    types::*,
+    verification::Target,
+    FerrisProofError,
+};
+use regex::Regex;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Kind of error Miri stopped a test on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiriErrorKind {
+    UndefinedBehavior,
+    MemoryLeak,
+    /// An operation Miri cannot interpret, such as most FFI calls
+    Unsupported,
+}
+
+impl MiriErrorKind {
+    fn from_header(header: &str) -> Option<(Self, &str)> {
+        [
+            ("Undefined Behavior: ", MiriErrorKind::UndefinedBehavior),
+            ("memory leaked: ", MiriErrorKind::MemoryLeak),
+            ("unsupported operation: ", MiriErrorKind::Unsupported),
+        ]
+        .into_iter()
+        .find_map(|(prefix, kind)| Some((kind, header.strip_prefix(prefix)?)))
+    }
+
+    fn violation_id(&self) -> &'static str {
+        match self {
+            MiriErrorKind::UndefinedBehavior => "MIRI_UNDEFINED_BEHAVIOR",
+            MiriErrorKind::MemoryLeak => "MIRI_MEMORY_LEAK",
+            MiriErrorKind::Unsupported => "MIRI_UNSUPPORTED",
+        }
+    }
+}
+
+/// One frame of the backtrace Miri prints with an error
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiriFrame {
+    pub function: Option<String>,
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl MiriFrame {
+    /// Whether the frame is in the package's own sources rather than std or a dependency
+    fn is_local(&self) -> bool {
+        self.file.is_relative() && !self.file.starts_with("~")
+    }
+}
+
+/// Error reported by Miri, with the frames of its backtrace
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiriError {
+    pub kind: MiriErrorKind,
+    pub message: String,
+    pub frames: Vec<MiriFrame>,
+}
+
+impl MiriError {
+    /// Errors in `cargo miri test` output, in the order they were reported
+    pub fn parse(output: &str) -> Vec<MiriError> {
+        let arrow = Regex::new(r"^\s*-->\s+(.+?):(\d+):(\d+)").unwrap();
+        let inside_at = Regex::new(r"inside (`.+?`|closure) at (.+?):(\d+):(\d+)").unwrap();
+        let inside = Regex::new(r"^note: inside (`.+?`|closure)\s*$").unwrap();
+
+        let mut errors: Vec<MiriError> = Vec::new();
+        let mut in_error = false;
+        let mut function = None;
+        for line in output.lines() {
+            // libtest prints `test name ... ` before Miri's error on the same line
+            let header = line
+                .strip_prefix("error: ")
+                .or_else(|| line.split_once(" ... error: ").map(|(_, header)| header));
+            if let Some(header) = header {
+                in_error = match MiriErrorKind::from_header(header) {
+                    Some((kind, message)) => {
+                        errors.push(MiriError {
+                            kind,
+                            message: message.trim_end_matches(':').to_string(),
+                            frames: Vec::new(),
+                        });
+                        true
+                    }
+                    None => false,
+                };
+                function = None;
+                continue;
+            }
+            let Some(error) = errors.last_mut().filter(|_| in_error) else {
+                continue;
+            };
+
+            let frame = |function: Option<String>, file: &str, line: &str, column: &str| {
+                Some(MiriFrame {
+                    function: function.map(|f: String| f.trim_matches('`').to_string()),
+                    file: PathBuf::from(file),
+                    line: line.parse().ok()?,
+                    column: column.parse().ok()?,
+                })
+            };
+            if let Some(c) = inside_at.captures(line) {
+                error
+                    .frames
+                    .extend(frame(Some(c[1].to_string()), &c[2], &c[3], &c[4]));
+            } else if let Some(c) = inside.captures(line) {
+                function = Some(c[1].to_string());
+            } else if let Some(c) = arrow.captures(line) {
+                error
+                    .frames
+                    .extend(frame(function.take(), &c[1], &c[2], &c[3]));
+            }
+        }
+        errors
+    }
+
+    /// First frame in the package's own sources, or the first frame
+    pub fn offending_frame(&self) -> Option<&MiriFrame> {
+        self.frames
+            .iter()
+            .find(|frame| frame.is_local())
+            .or_else(|| self.frames.first())
+    }
+
+    pub fn violation(&self, package_root: &Path) -> Violation {
+        let frame = self.offending_frame();
+        let location = frame.map_or_else(
+            || Location::file(package_root),
+            |frame| Location::point(package_root.join(&frame.file), frame.line, frame.column),
+        );
+        let label = match self.kind {
+            MiriErrorKind::UndefinedBehavior => "Undefined behavior",
+            MiriErrorKind::MemoryLeak => "Memory leaked",
+            MiriErrorKind::Unsupported => "Unsupported by Miri",
+        };
+        let function = frame
+            .and_then(|frame| frame.function.as_ref())
+            .map(|function| format!(" in `{}`", function))
+            .unwrap_or_default();
+        let backtrace: Vec<_> = self
+            .frames
+            .iter()
+            .map(|frame| {
+                format!(
+                    "{} at {}:{}:{}",
+                    frame.function.as_deref().unwrap_or("?"),
+                    frame.file.display(),
+                    frame.line,
+                    frame.column
+                )
+            })
+            .collect();
+
+        Violation {
+            id: self.kind.violation_id().to_string(),
+            severity: if self.kind == MiriErrorKind::Unsupported {
+                Severity::Warning
+            } else {
+                Severity::Error
+            },
+            location,
+            message: format!("{}{}: {}", label, function, self.message),
+            suggestion: (!backtrace.is_empty())
+                .then(|| format!("Backtrace: {}", backtrace.join(" <- "))),
+            rule: "miri".to_string(),
+        }
+    }
+}
+
+/// Undefined behavior detection by running tests under Miri
+///
+/// Reads `toolchain` (default `nightly`) and `flags`, extra `MIRIFLAGS`,
+/// from the tool config. Only tests whose path contains the target's
+/// module run.
+pub struct MiriPlugin {
+    toolchain: String,
+    initialized: bool,
+}
+
+impl MiriPlugin {
+    pub fn new() -> Self {
+        Self {
+            toolchain: "nightly".to_string(),
+            initialized: false,
+        }
+    }
+
+    fn run_miri(&self, input: &VerificationInput) -> Result<VerificationOutput> {
+        let start_time = std::time::Instant::now();
+
+        let (anchor, filter) = match &input.target {
+            Target::RustFile(path) => (path.clone(), None),
+            // Tests exercising an item are conventionally named after it
+            Target::Item { file, .. } => (file.clone(), input.target.item_name().map(String::from)),
+            Target::Module(name) => (
+                input.context.working_dir.clone(),
+                Some(name.trim_start_matches("crate::").to_string()),
+            ),
+            Target::FormalSpec(_) => return Err(anyhow!("Miri plugin only supports Rust targets")),
+        };
+        let package_root = anchor
+            .ancestors()
+            .find(|dir| dir.join("Cargo.toml").exists())
+            .ok_or_else(|| anyhow!("No Cargo.toml above {:?}", anchor))?
+            .to_path_buf();
+        // Tests of a source file live in its module, or one nested in it
+        let filter = filter.or_else(|| {
+            let relative = anchor.strip_prefix(&package_root).ok()?;
+            if !relative.starts_with("src") {
+                return None;
+            }
+            Some(module_path_of(&relative.to_string_lossy())).filter(|m| m != "crate")
+        });
+
+        let tool_config = &input.config.tool_config;
+        let toolchain = tool_config
+            .get("toolchain")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&self.toolchain);
+        let mut flags: Vec<String> = std::env::var("MIRIFLAGS")
+            .ok()
+            .into_iter()
+            .filter(|flags| !flags.trim().is_empty())
+            .collect();
+        if let Some(extra) = tool_config.get("flags").and_then(|v| v.as_array()) {
+            flags.extend(extra.iter().filter_map(|f| f.as_str()).map(String::from));
+        }
+
+        let mut cmd = Command::new("cargo");
+        cmd.current_dir(&package_root)
+            .arg(format!("+{}", toolchain))
+            .args(["miri", "test", "--no-fail-fast"])
+            .env("MIRIFLAGS", flags.join(" "));
+        if let Some(filter) = &filter {
+            cmd.arg(filter);
+        }
+        debug!("Executing command: {:?}", cmd);
+
+        let output = run_tool(&mut cmd, &input.context.cancellation)?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let exit_code = output.status.code().unwrap_or(-1);
+
+        let errors = MiriError::parse(&format!("{}\n{}", stdout, stderr));
+        if exit_code != 0 && errors.is_empty() {
+            return Err(anyhow!(
+                "cargo miri test failed with exit code {}: {}",
+                exit_code,
+                stderr.lines().last().unwrap_or("").trim()
+            ));
+        }
+        let violations: Vec<_> = errors
+            .iter()
+            .map(|error| error.violation(&package_root))
+            .collect();
+        info!(
+            "Miri reported {} error(s) in {:?}",
+            violations.len(),
+            package_root
+        );
+
+        let status = if violations.iter().any(|v| v.severity == Severity::Error) {
+            Status::Error
+        } else if violations.is_empty() {
+            Status::Success
+        } else {
+            Status::Warning
+        };
+        let execution_time = start_time.elapsed();
+
+        Ok(VerificationOutput {
+            status,
+            violations,
+            artifacts: vec![],
+            tool_output: ToolOutput {
+                tool: "miri".to_string(),
+                stdout,
+                stderr,
+                exit_code,
+                execution_time,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: execution_time,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+        })
+    }
+}
+
+impl VerificationPlugin for MiriPlugin {
+    fn name(&self) -> &str {
+        "miri"
+    }
+
+    fn version(&self) -> &str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn supported_techniques(&self) -> Vec<Technique> {
+        vec![Technique::UndefinedBehaviorChecks]
+    }
+
+    fn supported_versions(&self) -> VersionRange {
+        VersionRange {
+            min: Some(semver::Version::new(0, 1, 0)),
+            max: Some(semver::Version::new(1, 0, 0)),
+            requires_exact: None,
+        }
+    }
+
+    fn check_availability(&self) -> ferris_proof_core::Result<ToolInfo> {
+        let output = Command::new("cargo")
+            .arg(format!("+{}", self.toolchain))
+            .args(["miri", "--version"])
+            .output()
+            .map_err(|e| {
+                FerrisProofError::tool_not_found("miri", format!("cargo not found: {}", e))
+            })?;
+        if !output.status.success() {
+            return Err(FerrisProofError::tool_not_found(
+                "miri",
+                format!(
+                    "Miri is not installed (rustup +{} component add miri)",
+                    self.toolchain
+                ),
+            ));
+        }
+
+        Ok(ToolInfo {
+            name: "miri".to_string(),
+            version: String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or("unknown")
+                .to_string(),
+            path: PathBuf::from("cargo"),
+            available: true,
+            capabilities: vec![
+                "undefined_behavior".to_string(),
+                "memory_leaks".to_string(),
+                "data_races".to_string(),
+            ],
+        })
+    }
+
+    fn verify(&self, input: VerificationInput) -> ferris_proof_core::Result<VerificationOutput> {
+        if !self.initialized {
+            return Err(FerrisProofError::plugin(
+                "miri",
+                "Miri plugin not initialized",
+            ));
+        }
+
+        self.run_miri(&input)
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
+    }
+
+    fn parse_output(&self, raw_output: &str) -> ferris_proof_core::Result<StructuredResult> {
+        let errors = MiriError::parse(raw_output);
+        let undefined = errors
+            .iter()
+            .filter(|e| e.kind != MiriErrorKind::Unsupported)
+            .count();
+        Ok(StructuredResult {
+            status: if undefined > 0 {
+                Status::Error
+            } else if errors.is_empty() {
+                Status::Success
+            } else {
+                Status::Warning
+            },
+            violations: vec![],
+            statistics: json!({
+                "errors": undefined,
+                "unsupported": errors.len() - undefined
+            }),
+            performance: PerformanceMetrics {
+                execution_time: Duration::from_millis(0),
+                memory_usage: 0,
+                cpu_usage: 0.0,
+                cache_hits: 0,
+            },
+        })
+    }
+
+    fn metadata(&self) -> PluginMetadata {
+        PluginMetadata {
+            name: "miri".to_string(),
+            version: self.version().to_string(),
+            description: "Undefined behavior detection by running tests under Miri".to_string(),
+            author: "FerrisProof Team".to_string(),
+            license: "MIT".to_string(),
+            homepage: Some("https://github.com/rust-lang/miri".to_string()),
+            techniques: vec![Technique::UndefinedBehaviorChecks],
+            supported_platforms: vec![
+                "linux".to_string(),
+                "macos".to_string(),
+                "windows".to_string(),
+            ],
+            dependencies: vec!["cargo".to_string(), "miri".to_string()],
+            renderers: Vec::new(),
+            error_codes: Vec::new(),
+        }
+    }
+
+    fn initialize(&mut self, config: &serde_json::Value) -> ferris_proof_core::Result<()> {
+        if let Some(toolchain) = config
+            .get("miri")
+            .and_then(|c| c.get("toolchain"))
+            .and_then(|v| v.as_str())
+        {
+            self.toolchain = toolchain.to_string();
+        }
+
+        self.check_availability()?;
+        self.initialized = true;
+        info!("Miri plugin initialized with toolchain: {}", self.toolchain);
+        Ok(())
+    }
+
+    fn cleanup(&mut self) -> ferris_proof_core::Result<()> {
+        self.initialized = false;
+        debug!("Miri plugin cleaned up");
+        Ok(())
+    }
+}
+
+impl Default for MiriPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USE_AFTER_FREE: &str = "\
+running 1 test
+test buffer::tests::reads_after_free ... error: Undefined Behavior: memory access failed: alloc1234 has been freed, so this pointer is dangling
+   --> /home/dev/.rustup/toolchains/nightly/lib/rustlib/src/rust/library/core/src/ptr/mod.rs:1205:9
+    |
+    = help: this indicates a bug in the program: it performed an invalid operation, and caused Undefined Behavior
+    = note: BACKTRACE (of the first span) on thread `buffer::tests::reads_after_free`:
+    = note: inside `std::ptr::read::<u8>` at /home/dev/.rustup/toolchains/nightly/lib/rustlib/src/rust/library/core/src/ptr/mod.rs:1205:9: 1205:12
+note: inside `buffer::Buffer::first`
+   --> src/buffer.rs:42:18
+    |
+42  |         unsafe { std::ptr::read(self.ptr) }
+    |                  ^^^^^^^^^^^^^^^^^^^^^^^^
+note: inside `buffer::tests::reads_after_free`
+   --> src/buffer.rs:88:9
+
+error: memory leaked: alloc99 (Rust heap, size: 16, align: 8), allocated here:
+   --> src/buffer.rs:12:20
+
+error: unsupported operation: can't call foreign function `inflate` on OS `linux`
+   --> src/codec.rs:7:5
+
+error: test failed, to rerun pass `--lib`
+";
+
+    #[test]
+    fn test_parse_reports_local_backtrace_location() {
+        let errors = MiriError::parse(USE_AFTER_FREE);
+        let kinds: Vec<_> = errors.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MiriErrorKind::UndefinedBehavior,
+                MiriErrorKind::MemoryLeak,
+                MiriErrorKind::Unsupported
+            ]
+        );
+        assert_eq!(errors[0].frames.len(), 4);
+
+        let violation = errors[0].violation(Path::new("/project"));
+        assert_eq!(violation.id, "MIRI_UNDEFINED_BEHAVIOR");
+        assert_eq!(
+            violation.location.file,
+            PathBuf::from("/project/src/buffer.rs")
+        );
+        assert_eq!(violation.location.line, Some(42));
+        assert!(violation
+            .message
+            .starts_with("Undefined behavior in `buffer::Buffer::first`: memory access failed"));
+
+        let unsupported = errors[2].violation(Path::new("/project"));
+        assert_eq!(unsupported.severity, Severity::Warning);
+        assert_eq!(
+            MiriPlugin::new()
+                .parse_output(USE_AFTER_FREE)
+                .unwrap()
+                .status,
+            Status::Error
+        );
+    }
+}
@@ -0,0 +1,533 @@
+use anyhow::{anyhow, Context, Result};
+use ferris_proof_core::{
+    cache::{module_path_of, wildcard_match},
+    plugins::{
+        run_tool, PerformanceMetrics, PluginMetadata, StructuredResult, ToolInfo,
+        VerificationInput, VerificationOutput, VerificationPlugin, VersionRange,
+    },
+    types::*,
+    verification::Target,
+    FerrisProofError,
+};
+use regex::Regex;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// How the test suite fared against one mutant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutantOutcome {
+    Caught,
+    /// Tests still passed with the mutation applied
+    Missed,
+    Timeout,
+    /// The mutated code did not compile
+    Unviable,
+}
+
+impl MutantOutcome {
+    /// Listing written by cargo-mutants for each outcome under `mutants.out/`
+    const LISTINGS: [(&'static str, MutantOutcome); 4] = [
+        ("caught.txt", MutantOutcome::Caught),
+        ("missed.txt", MutantOutcome::Missed),
+        ("timeout.txt", MutantOutcome::Timeout),
+        ("unviable.txt", MutantOutcome::Unviable),
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mutant {
+    /// Source file relative to the package root
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: Option<u32>,
+    /// e.g. `replace Codec::decode -> Result<Frame> with Ok(Default::default())`
+    pub description: String,
+    pub outcome: MutantOutcome,
+}
+
+/// Killed and surviving mutants in one module
+///
+/// Timeouts count as killed; unviable mutants are not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModuleScore {
+    pub killed: usize,
+    pub survived: usize,
+}
+
+impl ModuleScore {
+    pub fn score(&self) -> f64 {
+        let total = self.killed + self.survived;
+        if total == 0 {
+            1.0
+        } else {
+            self.killed as f64 / total as f64
+        }
+    }
+}
+
+/// Mutation testing results for a package
+#[derive(Debug, Clone, Default)]
+pub struct MutationReport {
+    pub mutants: Vec<Mutant>,
+}
+
+impl MutationReport {
+    /// Read the outcome listings from a cargo-mutants `mutants.out` directory
+    pub fn load(output_dir: &Path) -> Result<Self> {
+        let mut mutants = Vec::new();
+        for (listing, outcome) in MutantOutcome::LISTINGS {
+            let path = output_dir.join(listing);
+            if !path.exists() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {:?}", path))?;
+            mutants.extend(Self::parse_listing(&content, outcome));
+        }
+        Ok(Self { mutants })
+    }
+
+    /// Parse lines like `src/codec.rs:42:9: replace decode -> bool with true`
+    ///
+    /// Older cargo-mutants releases omit the column.
+    pub fn parse_listing(content: &str, outcome: MutantOutcome) -> Vec<Mutant> {
+        let line_re = Regex::new(r"^(.+?\.rs):(\d+)(?::(\d+))?:\s*(.+)$").unwrap();
+
+        content
+            .lines()
+            .filter_map(|line| {
+                let captures = line_re.captures(line.trim())?;
+                Some(Mutant {
+                    file: PathBuf::from(&captures[1]),
+                    line: captures[2].parse().ok()?,
+                    column: captures.get(3).and_then(|c| c.as_str().parse().ok()),
+                    description: captures[4].to_string(),
+                    outcome,
+                })
+            })
+            .collect()
+    }
+
+    pub fn module_scores(&self) -> BTreeMap<String, ModuleScore> {
+        let mut scores: BTreeMap<String, ModuleScore> = BTreeMap::new();
+        for mutant in &self.mutants {
+            let module = module_path_of(&mutant.file.to_string_lossy());
+            let score = scores.entry(module).or_default();
+            match mutant.outcome {
+                MutantOutcome::Caught | MutantOutcome::Timeout => score.killed += 1,
+                MutantOutcome::Missed => score.survived += 1,
+                MutantOutcome::Unviable => {}
+            }
+        }
+        scores
+    }
+
+    /// A warning per surviving mutant and an error per module below its threshold
+    ///
+    /// The most specific matching pattern in `thresholds` wins; modules
+    /// matching none use `min_score`, or are not scored when that is unset.
+    pub fn violations(
+        &self,
+        package_root: &Path,
+        min_score: Option<f64>,
+        thresholds: &BTreeMap<String, f64>,
+    ) -> Vec<Violation> {
+        let mut violations: Vec<Violation> = self
+            .mutants
+            .iter()
+            .filter(|m| m.outcome == MutantOutcome::Missed)
+            .map(|mutant| Violation {
+                id: "MUTANT_SURVIVED".to_string(),
+                severity: Severity::Warning,
+                location: match mutant.column {
+                    Some(column) => {
+                        Location::point(package_root.join(&mutant.file), mutant.line, column)
+                    }
+                    None => Location {
+                        line: Some(mutant.line),
+                        ..Location::file(package_root.join(&mutant.file))
+                    },
+                },
+                message: format!("Mutant survived: {}", mutant.description),
+                suggestion: Some(
+                    "Add a property or assertion that fails when this code changes".to_string(),
+                ),
+                rule: "mutation_survivor".to_string(),
+            })
+            .collect();
+
+        for (module, score) in self.module_scores() {
+            let threshold = thresholds
+                .iter()
+                .filter(|(pattern, _)| wildcard_match(pattern, &module))
+                .max_by_key(|(pattern, _)| pattern.len())
+                .map(|(_, threshold)| *threshold)
+                .or(min_score);
+            let Some(threshold) = threshold else {
+                continue;
+            };
+            if score.score() >= threshold {
+                continue;
+            }
+
+            let file = self
+                .mutants
+                .iter()
+                .find(|m| module_path_of(&m.file.to_string_lossy()) == module)
+                .map(|m| package_root.join(&m.file))
+                .unwrap_or_else(|| package_root.to_path_buf());
+            violations.push(Violation {
+                id: "MUTATION_SCORE_BELOW_THRESHOLD".to_string(),
+                severity: Severity::Error,
+                location: Location::file(file),
+                message: format!(
+                    "Mutation score for `{}` is {:.1}% ({} of {} mutants killed), below the {:.1}% threshold",
+                    module,
+                    score.score() * 100.0,
+                    score.killed,
+                    score.killed + score.survived,
+                    threshold * 100.0
+                ),
+                suggestion: Some(
+                    "Strengthen the module's property tests until surviving mutants are killed"
+                        .to_string(),
+                ),
+                rule: "mutation_score".to_string(),
+            });
+        }
+
+        violations
+    }
+}
+
+/// Mutation testing via cargo-mutants
+///
+/// Reads `timeout`, `min_score` and `thresholds` (module pattern to score)
+/// from the tool config.
+pub struct CargoMutantsPlugin {
+    tool_path: PathBuf,
+    initialized: bool,
+}
+
+impl CargoMutantsPlugin {
+    pub fn new() -> Self {
+        Self {
+            tool_path: PathBuf::from("cargo"),
+            initialized: false,
+        }
+    }
+
+    fn run_mutants(&self, input: &VerificationInput) -> Result<VerificationOutput> {
+        let start_time = std::time::Instant::now();
+
+        let (anchor, only_file) = match &input.target {
+            Target::RustFile(path) | Target::Item { file: path, .. } => {
+                (path.clone(), Some(path.clone()))
+            }
+            Target::Module(_) => (input.context.working_dir.clone(), None),
+            Target::FormalSpec(_) => {
+                return Err(anyhow!("cargo-mutants plugin only supports Rust targets"))
+            }
+        };
+        let package_root = anchor
+            .ancestors()
+            .find(|dir| dir.join("Cargo.toml").exists())
+            .ok_or_else(|| anyhow!("No Cargo.toml above {:?}", anchor))?
+            .to_path_buf();
+
+        let output_dir = input
+            .context
+            .cache_dir
+            .join(format!("mutants_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&output_dir)?;
+
+        let tool_config = &input.config.tool_config;
+        let mut cmd = Command::new(&self.tool_path);
+        cmd.current_dir(&package_root)
+            .args(["mutants", "--no-shuffle", "--output"])
+            .arg(&output_dir);
+        if let Some(file) = &only_file {
+            cmd.arg("--file")
+                .arg(file.strip_prefix(&package_root).unwrap_or(file));
+        }
+        // Mutant names mention the function they mutate
+        if let Some(item) = input.target.item_name() {
+            cmd.args(["--re", &format!(r"\b{}\b", regex::escape(item))]);
+        }
+        if let Some(timeout) = tool_config.get("timeout").and_then(|v| v.as_u64()) {
+            cmd.args(["--timeout", &timeout.to_string()]);
+        }
+        debug!("Executing command: {:?}", cmd);
+
+        let output = run_tool(&mut cmd, &input.context.cancellation)?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let exit_code = output.status.code().unwrap_or(-1);
+        // 0: all caught, 2: some missed, 3: some timed out
+        if ![0, 2, 3].contains(&exit_code) {
+            return Err(anyhow!(
+                "cargo mutants failed with exit code {}: {}",
+                exit_code,
+                stderr.trim()
+            ));
+        }
+
+        let report = MutationReport::load(&output_dir.join("mutants.out"))?;
+        let _ = std::fs::remove_dir_all(&output_dir);
+
+        let min_score = tool_config.get("min_score").and_then(|v| v.as_f64());
+        let thresholds: BTreeMap<String, f64> = tool_config
+            .get("thresholds")
+            .and_then(|v| v.as_object())
+            .map(|map| {
+                map.iter()
+                    .filter_map(|(pattern, score)| Some((pattern.clone(), score.as_f64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let violations = report.violations(&package_root, min_score, &thresholds);
+        info!(
+            "{} mutants tested in {:?}, {} violations",
+            report.mutants.len(),
+            package_root,
+            violations.len()
+        );
+
+        let status = if violations.iter().any(|v| v.severity == Severity::Error) {
+            Status::Error
+        } else if violations.is_empty() {
+            Status::Success
+        } else {
+            Status::Warning
+        };
+        let execution_time = start_time.elapsed();
+
+        Ok(VerificationOutput {
+            status,
+            violations,
+            artifacts: vec![],
+            tool_output: ToolOutput {
+                tool: "cargo-mutants".to_string(),
+                stdout,
+                stderr,
+                exit_code,
+                execution_time,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: execution_time,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: report.mutants.len() as u32,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+        })
+    }
+}
+
+impl VerificationPlugin for CargoMutantsPlugin {
+    fn name(&self) -> &str {
+        "cargo-mutants"
+    }
+
+    fn version(&self) -> &str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn supported_techniques(&self) -> Vec<Technique> {
+        vec![Technique::MutationTesting]
+    }
+
+    fn supported_versions(&self) -> VersionRange {
+        VersionRange {
+            min: Some(semver::Version::new(0, 1, 0)),
+            max: Some(semver::Version::new(1, 0, 0)),
+            requires_exact: None,
+        }
+    }
+
+    fn check_availability(&self) -> ferris_proof_core::Result<ToolInfo> {
+        let output = Command::new(&self.tool_path)
+            .args(["mutants", "--version"])
+            .output()
+            .map_err(|e| {
+                FerrisProofError::tool_not_found(
+                    "cargo-mutants",
+                    format!("cargo-mutants not found: {}", e),
+                )
+            })?;
+        if !output.status.success() {
+            return Err(FerrisProofError::tool_not_found(
+                "cargo-mutants",
+                "cargo-mutants is not installed (cargo install cargo-mutants)",
+            ));
+        }
+
+        Ok(ToolInfo {
+            name: "cargo-mutants".to_string(),
+            version: String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or("unknown")
+                .to_string(),
+            path: self.tool_path.clone(),
+            available: true,
+            capabilities: vec!["mutation_testing".to_string(), "module_scores".to_string()],
+        })
+    }
+
+    fn verify(&self, input: VerificationInput) -> ferris_proof_core::Result<VerificationOutput> {
+        if !self.initialized {
+            return Err(FerrisProofError::plugin(
+                "cargo-mutants",
+                "cargo-mutants plugin not initialized",
+            ));
+        }
+
+        self.run_mutants(&input)
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
+    }
+
+    fn parse_output(&self, raw_output: &str) -> ferris_proof_core::Result<StructuredResult> {
+        let missed = MutationReport::parse_listing(raw_output, MutantOutcome::Missed).len();
+        Ok(StructuredResult {
+            status: if missed > 0 {
+                Status::Warning
+            } else {
+                Status::Success
+            },
+            violations: vec![],
+            statistics: json!({ "missed": missed }),
+            performance: PerformanceMetrics {
+                execution_time: Duration::from_millis(0),
+                memory_usage: 0,
+                cpu_usage: 0.0,
+                cache_hits: 0,
+            },
+        })
+    }
+
+    fn metadata(&self) -> PluginMetadata {
+        PluginMetadata {
+            name: "cargo-mutants".to_string(),
+            version: self.version().to_string(),
+            description: "Mutation testing of Rust code with per-module score thresholds"
+                .to_string(),
+            author: "FerrisProof Team".to_string(),
+            license: "MIT".to_string(),
+            homepage: Some("https://mutants.rs".to_string()),
+            techniques: vec![Technique::MutationTesting],
+            supported_platforms: vec![
+                "linux".to_string(),
+                "macos".to_string(),
+                "windows".to_string(),
+            ],
+            dependencies: vec!["cargo".to_string(), "cargo-mutants".to_string()],
+            renderers: Vec::new(),
+            error_codes: Vec::new(),
+        }
+    }
+
+    fn initialize(&mut self, config: &serde_json::Value) -> ferris_proof_core::Result<()> {
+        if let Some(path) = config
+            .get("mutants")
+            .and_then(|c| c.get("cargo_mutants_path"))
+            .and_then(|v| v.as_str())
+        {
+            self.tool_path = PathBuf::from(path);
+        }
+
+        self.check_availability()?;
+        self.initialized = true;
+        info!(
+            "cargo-mutants plugin initialized with tool: {:?}",
+            self.tool_path
+        );
+        Ok(())
+    }
+
+    fn cleanup(&mut self) -> ferris_proof_core::Result<()> {
+        self.initialized = false;
+        debug!("cargo-mutants plugin cleaned up");
+        Ok(())
+    }
+}
+
+impl Default for CargoMutantsPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> MutationReport {
+        let caught = "src/crypto/aes.rs:10:5: replace encrypt -> Vec<u8> with vec![]\n\
+                      src/crypto/aes.rs:20:9: replace == with != in decrypt\n\
+                      src/ui.rs:3:5: replace render with ()\n";
+        let missed = "src/crypto/aes.rs:31: replace key_len -> usize with 0\n";
+        let unviable = "src/crypto/aes.rs:40:1: replace new -> Self with Default::default()\n";
+
+        let mut mutants = MutationReport::parse_listing(caught, MutantOutcome::Caught);
+        mutants.extend(MutationReport::parse_listing(missed, MutantOutcome::Missed));
+        mutants.extend(MutationReport::parse_listing(
+            unviable,
+            MutantOutcome::Unviable,
+        ));
+        MutationReport { mutants }
+    }
+
+    #[test]
+    fn test_parse_listing_and_scores() {
+        let report = sample_report();
+        assert_eq!(report.mutants.len(), 5);
+        assert_eq!(report.mutants[3].line, 31);
+        assert_eq!(report.mutants[3].column, None);
+        assert_eq!(
+            report.mutants[3].description,
+            "replace key_len -> usize with 0"
+        );
+
+        let scores = report.module_scores();
+        let aes = scores["crypto::aes"];
+        assert_eq!((aes.killed, aes.survived), (2, 1));
+        assert!((aes.score() - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(scores["ui"].score(), 1.0);
+    }
+
+    #[test]
+    fn test_module_thresholds() {
+        let report = sample_report();
+        let root = Path::new("/project");
+
+        let mut thresholds = BTreeMap::new();
+        thresholds.insert("crypto::*".to_string(), 0.9);
+        thresholds.insert("*".to_string(), 0.5);
+
+        let violations = report.violations(root, None, &thresholds);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].id, "MUTANT_SURVIVED");
+        assert_eq!(violations[0].location.line, Some(31));
+        assert_eq!(violations[1].id, "MUTATION_SCORE_BELOW_THRESHOLD");
+        assert!(violations[1].message.contains("`crypto::aes` is 66.7%"));
+        assert_eq!(
+            violations[1].location.file,
+            PathBuf::from("/project/src/crypto/aes.rs")
+        );
+
+        // Without thresholds only survivors are reported
+        assert_eq!(report.violations(root, None, &BTreeMap::new()).len(), 1);
+        assert_eq!(
+            report.violations(root, Some(0.5), &BTreeMap::new()).len(),
+            1
+        );
+    }
+}
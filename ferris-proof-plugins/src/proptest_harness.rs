@@ -0,0 +1,398 @@
+use crate::libtest::TestRunner;
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+
+/// Directory below a package holding property tests, e.g. `tests/property/codec.rs`
+pub const PROPERTY_TEST_DIR: &str = "tests/property";
+
+/// Package owning a target, as reported by `cargo metadata`
+#[derive(Debug, Clone)]
+pub struct CargoPackage {
+    pub name: String,
+    pub edition: String,
+    /// Directory containing the package's `Cargo.toml`
+    pub root: PathBuf,
+    pub workspace_root: PathBuf,
+    pub target_directory: PathBuf,
+    /// Name and source file of each `[[test]]` target
+    pub test_targets: Vec<(String, PathBuf)>,
+    /// Dev-dependency entries from `cargo metadata`
+    dev_dependencies: Vec<Value>,
+}
+
+impl CargoPackage {
+    /// Package of the nearest `Cargo.toml` above `anchor`
+    pub fn locate(anchor: &Path) -> Result<Self> {
+        let root = anchor
+            .ancestors()
+            .find(|dir| dir.join("Cargo.toml").is_file())
+            .ok_or_else(|| anyhow!("No Cargo.toml above {:?}", anchor))?;
+        let manifest_path = root.join("Cargo.toml");
+
+        let output = Command::new("cargo")
+            .args(["metadata", "--format-version", "1", "--no-deps"])
+            .arg("--manifest-path")
+            .arg(&manifest_path)
+            .output()
+            .context("Failed to run cargo metadata")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "cargo metadata failed for {:?}: {}",
+                manifest_path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let metadata: Value = serde_json::from_slice(&output.stdout)?;
+        Self::from_metadata(&metadata, &manifest_path)
+    }
+
+    /// The package with `manifest_path` in `cargo metadata` output
+    pub fn from_metadata(metadata: &Value, manifest_path: &Path) -> Result<Self> {
+        let wanted = canonical(manifest_path);
+        let package = metadata["packages"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|package| {
+                package["manifest_path"]
+                    .as_str()
+                    .is_some_and(|path| canonical(Path::new(path)) == wanted)
+            })
+            .ok_or_else(|| anyhow!("{:?} is not a package manifest", manifest_path))?;
+
+        let test_targets = package["targets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|target| {
+                target["kind"]
+                    .as_array()
+                    .is_some_and(|kinds| kinds.iter().any(|kind| kind == "test"))
+            })
+            .filter_map(|target| {
+                Some((
+                    target["name"].as_str()?.to_string(),
+                    PathBuf::from(target["src_path"].as_str()?),
+                ))
+            })
+            .collect();
+        let dev_dependencies = package["dependencies"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|dependency| dependency["kind"] == "dev")
+            .cloned()
+            .collect();
+
+        let root = wanted.parent().map(Path::to_path_buf).unwrap_or_default();
+        let path_or = |value: &Value, fallback: &Path| {
+            value
+                .as_str()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| fallback.to_path_buf())
+        };
+        Ok(Self {
+            name: package["name"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Package in {:?} has no name", manifest_path))?
+                .to_string(),
+            edition: package["edition"].as_str().unwrap_or("2021").to_string(),
+            workspace_root: path_or(&metadata["workspace_root"], &root),
+            target_directory: path_or(&metadata["target_directory"], &root.join("target")),
+            root,
+            test_targets,
+            dev_dependencies,
+        })
+    }
+}
+
+/// Property tests to run for one target, and how cargo reaches them
+///
+/// Files under `tests/property/` that the package declares as `[[test]]`
+/// targets run in place; the rest run through an ephemeral harness crate
+/// that depends on the package and declares them.
+#[derive(Debug, Clone)]
+pub struct PropertyTestPlan {
+    pub package: CargoPackage,
+    /// Declared test targets, by name
+    pub declared: Vec<String>,
+    /// Property test files the package does not declare
+    pub undeclared: Vec<PathBuf>,
+}
+
+impl PropertyTestPlan {
+    /// Property tests for `target`
+    ///
+    /// A property test file selects itself, `lib.rs` and `main.rs` select
+    /// every property test, and other files select tests named after them,
+    /// e.g. `src/codec.rs` selects `codec.rs` and `codec_roundtrip.rs`.
+    pub fn for_target(package: CargoPackage, target: &Path) -> Self {
+        let target = canonical(target);
+        let mut files = property_test_files(&package.root);
+        let stem = target
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if files.contains(&target) {
+            files = vec![target];
+        } else if stem != "lib" && stem != "main" {
+            files.retain(|file| {
+                file.file_stem().is_some_and(|s| {
+                    let s = s.to_string_lossy();
+                    s == stem || s.starts_with(&format!("{}_", stem))
+                })
+            });
+        }
+
+        let mut declared = Vec::new();
+        let mut undeclared = Vec::new();
+        for file in files {
+            match package
+                .test_targets
+                .iter()
+                .find(|(_, src)| canonical(src) == file)
+            {
+                Some((name, _)) => declared.push(name.clone()),
+                None => undeclared.push(file),
+            }
+        }
+        Self {
+            package,
+            declared,
+            undeclared,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.declared.is_empty() && self.undeclared.is_empty()
+    }
+
+    /// Test runner invocations covering the plan, with libtest JSON output
+    ///
+    /// The harness for undeclared tests is written below `harness_root`.
+    pub fn commands(&self, harness_root: &Path, runner: TestRunner) -> Result<Vec<Command>> {
+        let mut commands = Vec::new();
+        if !self.declared.is_empty() {
+            let mut cmd = runner.command();
+            cmd.current_dir(&self.package.workspace_root)
+                .args(["--package", &self.package.name]);
+            for name in &self.declared {
+                cmd.args(["--test", name]);
+            }
+            runner.json_format(&mut cmd);
+            commands.push(cmd);
+        }
+
+        if !self.undeclared.is_empty() {
+            let harness_dir = harness_root.join(&self.package.name);
+            self.write_harness(&harness_dir)?;
+            let mut cmd = runner.command();
+            cmd.current_dir(&harness_dir)
+                .arg("--manifest-path")
+                .arg(harness_dir.join("Cargo.toml"))
+                // Share build artifacts of the package's dependencies
+                .env("CARGO_TARGET_DIR", &self.package.target_directory);
+            runner.json_format(&mut cmd);
+            commands.push(cmd);
+        }
+        Ok(commands)
+    }
+
+    /// `Cargo.toml` of the harness crate for the undeclared tests
+    pub fn harness_manifest(&self) -> Result<String> {
+        let package = &self.package;
+        let mut manifest = toml::Table::new();
+
+        let mut meta = toml::Table::new();
+        meta.insert(
+            "name".into(),
+            format!("ferris-proof-property-{}", package.name).into(),
+        );
+        meta.insert("version".into(), "0.0.0".into());
+        meta.insert("edition".into(), package.edition.clone().into());
+        meta.insert("publish".into(), false.into());
+        manifest.insert("package".into(), meta.into());
+        // An empty workspace keeps the harness out of any enclosing one
+        manifest.insert("workspace".into(), toml::Table::new().into());
+
+        let mut dependency = toml::Table::new();
+        dependency.insert(
+            "path".into(),
+            package.root.to_string_lossy().as_ref().into(),
+        );
+        let mut dependencies = toml::Table::new();
+        dependencies.insert(package.name.clone(), dependency.into());
+        manifest.insert("dependencies".into(), dependencies.into());
+
+        let dev_dependencies: toml::Table = package
+            .dev_dependencies
+            .iter()
+            .filter(|dependency| dependency["name"] != package.name.as_str())
+            .filter_map(dev_dependency_entry)
+            .collect();
+        manifest.insert("dev-dependencies".into(), dev_dependencies.into());
+
+        let tests: Vec<toml::Value> = self
+            .undeclared
+            .iter()
+            .map(|file| {
+                let mut test = toml::Table::new();
+                let name = file
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                test.insert("name".into(), name.into());
+                test.insert("path".into(), file.to_string_lossy().as_ref().into());
+                test.into()
+            })
+            .collect();
+        manifest.insert("test".into(), tests.into());
+
+        Ok(toml::to_string(&manifest)?)
+    }
+
+    fn write_harness(&self, harness_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(harness_dir)?;
+        std::fs::write(harness_dir.join("Cargo.toml"), self.harness_manifest()?)?;
+        // Resolve the same dependency versions as the package under test
+        let lockfile = self.package.workspace_root.join("Cargo.lock");
+        if lockfile.exists() {
+            std::fs::copy(&lockfile, harness_dir.join("Cargo.lock"))?;
+        }
+        debug!(
+            "Wrote property test harness for {} to {:?}",
+            self.package.name, harness_dir
+        );
+        Ok(())
+    }
+}
+
+/// Rust files directly below the package's `tests/property/`, sorted
+pub fn property_test_files(package_root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(package_root.join(PROPERTY_TEST_DIR)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| canonical(&entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "rs"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Manifest entry for a `cargo metadata` dev-dependency
+fn dev_dependency_entry(dependency: &Value) -> Option<(String, toml::Value)> {
+    let name = dependency["name"].as_str()?;
+    let mut entry = toml::Table::new();
+
+    if let Some(path) = dependency["path"].as_str() {
+        entry.insert("path".into(), path.into());
+    } else if let Some(git) = dependency["source"]
+        .as_str()
+        .and_then(|source| source.strip_prefix("git+"))
+    {
+        let (url, rev) = git.split_once('#').unwrap_or((git, ""));
+        let url = url.split_once('?').map_or(url, |(url, _)| url);
+        entry.insert("git".into(), url.into());
+        if !rev.is_empty() {
+            entry.insert("rev".into(), rev.into());
+        }
+    } else {
+        entry.insert("version".into(), dependency["req"].as_str()?.into());
+    }
+
+    let key = match dependency["rename"].as_str() {
+        Some(rename) => {
+            entry.insert("package".into(), name.into());
+            rename
+        }
+        None => name,
+    };
+    if dependency["uses_default_features"] == false {
+        entry.insert("default-features".into(), false.into());
+    }
+    let features: Vec<toml::Value> = dependency["features"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|feature| feature.as_str().map(Into::into))
+        .collect();
+    if !features.is_empty() {
+        entry.insert("features".into(), features.into());
+    }
+    Some((key.to_string(), entry.into()))
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_plan_runs_declared_targets_in_place_and_others_in_a_harness() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let property_dir = root.join(PROPERTY_TEST_DIR);
+        std::fs::create_dir_all(&property_dir).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\n").unwrap();
+        for file in ["codec.rs", "codec_roundtrip.rs", "parser.rs"] {
+            std::fs::write(property_dir.join(file), "").unwrap();
+        }
+
+        let metadata = json!({
+            "workspace_root": root,
+            "target_directory": root.join("target"),
+            "packages": [{
+                "name": "wire-format",
+                "edition": "2021",
+                "manifest_path": root.join("Cargo.toml"),
+                "targets": [
+                    { "name": "wire-format", "kind": ["lib"], "src_path": root.join("src/lib.rs") },
+                    { "name": "codec", "kind": ["test"], "src_path": property_dir.join("codec.rs") }
+                ],
+                "dependencies": [
+                    { "name": "proptest", "req": "^1.4", "kind": "dev", "rename": null,
+                      "uses_default_features": true, "features": [] },
+                    { "name": "serde", "req": "^1", "kind": null, "rename": null,
+                      "uses_default_features": true, "features": ["derive"] }
+                ]
+            }]
+        });
+        let package = CargoPackage::from_metadata(&metadata, &root.join("Cargo.toml")).unwrap();
+
+        let plan = PropertyTestPlan::for_target(package.clone(), &root.join("src/codec.rs"));
+        assert_eq!(plan.declared, vec!["codec".to_string()]);
+        assert_eq!(
+            plan.undeclared,
+            vec![property_dir.join("codec_roundtrip.rs")]
+        );
+
+        let manifest: toml::Table = plan.harness_manifest().unwrap().parse().unwrap();
+        assert_eq!(
+            manifest["dependencies"]["wire-format"]["path"].as_str(),
+            Some(root.to_string_lossy().as_ref())
+        );
+        assert_eq!(
+            manifest["dev-dependencies"]["proptest"]["version"].as_str(),
+            Some("^1.4")
+        );
+        assert!(manifest["dev-dependencies"].get("serde").is_none());
+        assert_eq!(
+            manifest["test"][0]["name"].as_str(),
+            Some("codec_roundtrip")
+        );
+
+        let all = PropertyTestPlan::for_target(package.clone(), &root.join("src/lib.rs"));
+        assert_eq!(all.declared.len() + all.undeclared.len(), 3);
+        assert!(PropertyTestPlan::for_target(package, &root.join("src/util.rs")).is_empty());
+    }
+}
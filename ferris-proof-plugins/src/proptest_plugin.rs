@@ -1,17 +1,21 @@
+use crate::libtest::{TestOutcome, TestRun, TestRunner};
+use crate::proptest_harness::{CargoPackage, PropertyTestPlan, PROPERTY_TEST_DIR};
 use anyhow::{anyhow, Result};
 use ferris_proof_core::{
     plugins::{
-        PerformanceMetrics, PluginMetadata, StructuredResult, ToolInfo, VerificationInput,
-        VerificationOutput, VerificationPlugin, VersionRange,
+        run_tool, PerformanceMetrics, PluginMetadata, StructuredResult, ToolInfo,
+        VerificationInput, VerificationOutput, VerificationPlugin, VersionRange,
     },
+    seed,
     types::*,
     verification::Target,
+    FerrisProofError,
 };
-use serde_json::json;
-use std::path::PathBuf;
+use regex::Regex;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Duration;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 pub struct ProptestPlugin {
     tool_path: PathBuf,
@@ -74,71 +78,99 @@ proptest = "1.0"
         let start_time = std::time::Instant::now();
 
         match target {
-            Target::RustFile(path) => {
+            Target::RustFile(path) | Target::Item { file: path, .. } => {
                 info!("Running proptest on Rust file: {:?}", path);
 
-                // Create a temporary directory for test execution
-                let temp_dir = config
-                    .context
-                    .cache_dir
-                    .join(format!("proptest_{}", uuid::Uuid::new_v4()));
-                std::fs::create_dir_all(&temp_dir)?;
-
-                // Run cargo test with proptest
-                let mut cmd = Command::new("cargo");
-                cmd.current_dir(path.parent().unwrap_or_else(|| std::path::Path::new(".")));
-                cmd.args(["test", "--test", "prop_tests", "--", "--nocapture"]);
-
-                // Set environment variables for proptest
-                cmd.env(
-                    "PROPTEST_CASES",
-                    config
-                        .config
-                        .tool_config
-                        .get("cases")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(1000)
-                        .to_string(),
-                );
-                cmd.env(
-                    "PROPTEST_MAX_SHRINK_ITERS",
-                    config
-                        .config
-                        .tool_config
-                        .get("max_shrink_iters")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(10000)
-                        .to_string(),
-                );
-
-                debug!("Executing command: {:?}", cmd);
-
-                let output = cmd.output()?;
+                let plan = PropertyTestPlan::for_target(CargoPackage::locate(path)?, path);
+                if plan.is_empty() {
+                    debug!("No property tests under tests/property/ for {:?}", path);
+                    return Ok(no_property_tests(path, start_time.elapsed()));
+                }
+
+                let mut stdout = String::new();
+                let mut stderr = String::new();
+                let mut exit_code = 0;
+                let harness_root = config.context.cache_dir.join("proptest-harness");
+                let runner = self.test_runner(&config.config.tool_config);
+                for mut cmd in plan.commands(&harness_root, runner)? {
+                    // Set environment variables for proptest
+                    cmd.env(
+                        "PROPTEST_CASES",
+                        config
+                            .config
+                            .tool_config
+                            .get("cases")
+                            .and_then(|v| v.as_u64())
+                            .unwrap_or(1000)
+                            .to_string(),
+                    );
+                    cmd.env(
+                        "PROPTEST_MAX_SHRINK_ITERS",
+                        config
+                            .config
+                            .tool_config
+                            .get("max_shrink_iters")
+                            .and_then(|v| v.as_u64())
+                            .unwrap_or(10000)
+                            .to_string(),
+                    );
+                    cmd.env(seed::PROPTEST_SEED_ENV, config.context.seed.to_string());
+
+                    debug!("Executing command: {:?}", cmd);
+
+                    let output = run_tool(&mut cmd, &config.context.cancellation)?;
+                    stdout.push_str(&String::from_utf8_lossy(&output.stdout));
+                    stderr.push_str(&String::from_utf8_lossy(&output.stderr));
+                    if exit_code == 0 {
+                        exit_code = output.status.code().unwrap_or(-1);
+                    }
+                }
                 let execution_time = start_time.elapsed();
 
-                // Parse proptest output
-                let structured_result = self.parse_proptest_output(
-                    &String::from_utf8_lossy(&output.stdout),
-                    &String::from_utf8_lossy(&output.stderr),
-                )?;
+                // Parse libtest events
+                let run = TestRun::parse(&stdout);
+                let mut structured_result = self.parse_proptest_output(&stdout, &stderr)?;
+                if exit_code != 0 {
+                    structured_result.status = Status::Error;
+                }
 
-                // Create violations for any test failures
-                let violations = if structured_result.status == Status::Error {
-                    vec![Violation {
+                // One violation per failed test, or one for a run that failed
+                // before any test did, such as a build error
+                let mut violations: Vec<Violation> = run
+                    .failures()
+                    .map(|test| Violation {
+                        id: "PROPTEST_FAILURE".to_string(),
+                        severity: Severity::Error,
+                        location: test
+                            .stdout
+                            .as_deref()
+                            .and_then(|output| self.failure_location(output, path))
+                            .unwrap_or_else(|| Location::file(path.clone())),
+                        message: format!("Property test `{}` failed", test.name),
+                        suggestion: Some(
+                            "Check the test output for specific failure details".to_string(),
+                        ),
+                        rule: "proptest_verification".to_string(),
+                    })
+                    .collect();
+                if violations.is_empty() && structured_result.status == Status::Error {
+                    violations.push(Violation {
                         id: "PROPTEST_FAILURE".to_string(),
                         severity: Severity::Error,
-                        location: Location {
-                            file: path.clone(),
-                            line: None,
-                            column: None,
-                            span: None,
-                        },
+                        location: self
+                            .failure_location(&stderr, path)
+                            .unwrap_or_else(|| Location::file(path.clone())),
                         message: "Property-based tests failed".to_string(),
                         suggestion: Some(
                             "Check the test output for specific failure details".to_string(),
                         ),
                         rule: "proptest_verification".to_string(),
-                    }]
+                    });
+                }
+
+                // Keep the persisted seeds so the failing case can be replayed
+                let artifacts = if structured_result.status == Status::Error {
+                    regression_artifacts(&plan.package.root)
                 } else {
                     Vec::new()
                 };
@@ -146,13 +178,15 @@ proptest = "1.0"
                 Ok(VerificationOutput {
                     status: structured_result.status,
                     violations,
-                    artifacts: vec![], // TODO: Generate test reports
+                    artifacts,
                     tool_output: ToolOutput {
                         tool: "proptest".to_string(),
-                        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-                        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-                        exit_code: output.status.code().unwrap_or(-1),
+                        stdout,
+                        stderr,
+                        exit_code,
                         execution_time,
+                        artifact_id: None,
+                        retries: 0,
                     },
                     metrics: VerificationMetrics {
                         total_time: execution_time,
@@ -163,6 +197,8 @@ proptest = "1.0"
                             .get("test_cases_executed")
                             .and_then(|v| v.as_u64())
                             .unwrap_or(0) as u32,
+                        max_verified_scope: None,
+                        tool_settings: Default::default(),
                     },
                 })
             }
@@ -170,92 +206,133 @@ proptest = "1.0"
         }
     }
 
-    /// Parse proptest output into structured results
-    fn parse_proptest_output(&self, stdout: &str, stderr: &str) -> Result<StructuredResult> {
-        let output = stdout.to_string() + stderr;
-
-        // Look for test failures
-        if output.contains("test FAILED") || output.contains("panic") {
-            return Ok(StructuredResult {
-                status: Status::Error,
-                violations: vec![],
-                statistics: json!({
-                    "test_cases_executed": self.extract_test_cases(&output),
-                    "failures": self.extract_failures(&output),
-                    "successes": self.extract_successes(&output)
-                }),
-                performance: PerformanceMetrics {
-                    execution_time: Duration::from_millis(0), // Will be set by caller
-                    memory_usage: 0,
-                    cpu_usage: 0.0,
-                    cache_hits: 0,
-                },
-            });
+    /// Runner from `[tools.proptest] runner`, falling back to `cargo test`
+    /// when nextest is requested but not installed
+    fn test_runner(&self, tool_config: &serde_json::Value) -> TestRunner {
+        if tool_config.get("runner").and_then(|v| v.as_str()) != Some("nextest") {
+            return TestRunner::Cargo;
         }
-
-        // Look for successful completion
-        if output.contains("test result: ok") {
-            return Ok(StructuredResult {
-                status: Status::Success,
-                violations: vec![],
-                statistics: json!({
-                    "test_cases_executed": self.extract_test_cases(&output),
-                    "failures": 0,
-                    "successes": self.extract_successes(&output)
-                }),
-                performance: PerformanceMetrics {
-                    execution_time: Duration::from_millis(0),
-                    memory_usage: 0,
-                    cpu_usage: 0.0,
-                    cache_hits: 0,
-                },
-            });
+        if !TestRunner::nextest_available() {
+            warn!("cargo-nextest is not installed, running property tests with cargo test");
+            return TestRunner::Cargo;
         }
+        let retries = tool_config
+            .get("retries")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as u32;
+        TestRunner::Nextest { retries }
+    }
+
+    /// Location of the first panic in test output
+    ///
+    /// Panic paths are relative to the package root, found as the nearest
+    /// ancestor of the target with a `Cargo.toml`.
+    fn failure_location(&self, output: &str, target: &std::path::Path) -> Option<Location> {
+        // Matches both `panicked at src/x.rs:1:2:` and the older
+        // `panicked at 'message', src/x.rs:1:2`
+        let re = Regex::new(r"panicked at (?:'.*', )?([^\s:']+\.rs):(\d+):(\d+)").ok()?;
+        let captures = re.captures(output)?;
+
+        let file = package_root(target).join(&captures[1]);
+        let line = captures[2].parse().ok()?;
+        let column = captures[3].parse().ok()?;
+
+        Some(Location::point(file, line, column))
+    }
+
+    /// Parse libtest JSON output into structured results
+    ///
+    /// A run where no test binary started, such as a failed build, is an error.
+    fn parse_proptest_output(&self, stdout: &str, stderr: &str) -> Result<StructuredResult> {
+        let run = TestRun::parse(stdout);
+        let build_failed = run.suites == 0 && stderr.contains("error");
+        let status = if run.count(TestOutcome::Failed) > 0 || build_failed {
+            Status::Error
+        } else {
+            Status::Success
+        };
 
-        // Default to success if no failures detected
         Ok(StructuredResult {
-            status: Status::Success,
+            status,
             violations: vec![],
-            statistics: json!({
-                "test_cases_executed": 0,
-                "failures": 0,
-                "successes": 0
-            }),
+            statistics: run.statistics(),
             performance: PerformanceMetrics {
-                execution_time: Duration::from_millis(0),
+                execution_time: run.tests.iter().filter_map(|test| test.exec_time).sum(),
                 memory_usage: 0,
                 cpu_usage: 0.0,
                 cache_hits: 0,
             },
         })
     }
+}
 
-    fn extract_test_cases(&self, output: &str) -> u64 {
-        // Look for patterns like "1030 tests run"
-        let regex = regex::Regex::new(r"(\d+)\s+(?:test|tests|case|cases)").unwrap();
-        if let Some(captures) = regex.captures(output) {
-            captures
-                .get(1)
-                .and_then(|m| m.as_str().parse().ok())
-                .unwrap_or(0)
-        } else {
-            0
-        }
-    }
+/// Nearest ancestor of `target` with a `Cargo.toml`, or its parent directory
+pub fn package_root(target: &Path) -> PathBuf {
+    target
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("Cargo.toml").exists())
+        .or_else(|| target.parent())
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf()
+}
 
-    fn extract_failures(&self, output: &str) -> u64 {
-        // Count occurrences of "FAILED" or "panicked"
-        let failed_count = output.matches("FAILED").count();
-        let panic_count = output.matches("panicked").count();
-        (failed_count + panic_count) as u64
+/// Skipped output for a target without property tests
+fn no_property_tests(target: &Path, execution_time: Duration) -> VerificationOutput {
+    VerificationOutput {
+        status: Status::Skipped,
+        violations: Vec::new(),
+        artifacts: Vec::new(),
+        tool_output: ToolOutput {
+            tool: "proptest".to_string(),
+            stdout: format!(
+                "No property tests under {}/ for {}",
+                PROPERTY_TEST_DIR,
+                target.display()
+            ),
+            stderr: String::new(),
+            exit_code: 0,
+            execution_time,
+            artifact_id: None,
+            retries: 0,
+        },
+        metrics: VerificationMetrics {
+            total_time: execution_time,
+            cache_hit_rate: 0.0,
+            memory_usage: 0,
+            test_cases_executed: 0,
+            max_verified_scope: None,
+            tool_settings: Default::default(),
+        },
     }
+}
 
-    fn extract_successes(&self, output: &str) -> u64 {
-        // Look for "passed" or "ok" in test results
-        let passed_count = output.matches("passed").count();
-        let ok_count = output.matches("ok").count();
-        (passed_count + ok_count) as u64
+/// Seed files proptest persisted under `<package>/proptest-regressions/`
+pub fn regression_artifacts(package_root: &Path) -> Vec<Artifact> {
+    fn collect(dir: &Path, artifacts: &mut Vec<Artifact>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.is_dir() {
+                collect(&path, artifacts);
+            } else if path.extension().and_then(|e| e.to_str()) == Some("txt") {
+                match std::fs::read_to_string(&path) {
+                    Ok(content) => artifacts.push(Artifact {
+                        path,
+                        artifact_type: ArtifactType::Regression,
+                        content,
+                    }),
+                    Err(e) => warn!("Failed to read proptest regression {:?}: {}", path, e),
+                }
+            }
+        }
     }
+
+    let mut artifacts = Vec::new();
+    collect(&package_root.join("proptest-regressions"), &mut artifacts);
+    artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+    artifacts
 }
 
 impl VerificationPlugin for ProptestPlugin {
@@ -279,7 +356,7 @@ impl VerificationPlugin for ProptestPlugin {
         }
     }
 
-    fn check_availability(&self) -> Result<ToolInfo> {
+    fn check_availability(&self) -> ferris_proof_core::Result<ToolInfo> {
         // Check if Rust and Cargo are available
         let cargo_result = Command::new("cargo").args(["--version"]).output();
 
@@ -328,31 +405,45 @@ impl VerificationPlugin for ProptestPlugin {
                     capabilities,
                 })
             }
-            (Ok(cargo_output), Ok(_)) if !cargo_output.status.success() => {
-                Err(anyhow!("Cargo is not working properly"))
-            }
-            (Ok(_), Ok(rustc_output)) if !rustc_output.status.success() => {
-                Err(anyhow!("Rustc is not working properly"))
-            }
+            (Ok(cargo_output), Ok(_)) if !cargo_output.status.success() => Err(
+                FerrisProofError::tool_not_found("proptest", "Cargo is not working properly"),
+            ),
+            (Ok(_), Ok(rustc_output)) if !rustc_output.status.success() => Err(
+                FerrisProofError::tool_not_found("proptest", "Rustc is not working properly"),
+            ),
             (Ok(_), Ok(_)) => {
                 // Both commands succeeded but we didn't handle this case above
-                Err(anyhow!("Unexpected cargo/rustc status"))
+                Err(FerrisProofError::tool_not_found(
+                    "proptest",
+                    "Unexpected cargo/rustc status",
+                ))
             }
-            (Err(cargo_err), _) => Err(anyhow!("Cargo not found: {}", cargo_err)),
-            (_, Err(rustc_err)) => Err(anyhow!("Rustc not found: {}", rustc_err)),
+            (Err(cargo_err), _) => Err(FerrisProofError::tool_not_found(
+                "proptest",
+                format!("Cargo not found: {}", cargo_err),
+            )),
+            (_, Err(rustc_err)) => Err(FerrisProofError::tool_not_found(
+                "proptest",
+                format!("Rustc not found: {}", rustc_err),
+            )),
         }
     }
 
-    fn verify(&self, input: VerificationInput) -> Result<VerificationOutput> {
+    fn verify(&self, input: VerificationInput) -> ferris_proof_core::Result<VerificationOutput> {
         if !self.initialized {
-            return Err(anyhow!("Proptest plugin not initialized"));
+            return Err(FerrisProofError::plugin(
+                "proptest",
+                "Proptest plugin not initialized",
+            ));
         }
 
         self.run_proptest(&input.target, &input)
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
     }
 
-    fn parse_output(&self, raw_output: &str) -> Result<StructuredResult> {
+    fn parse_output(&self, raw_output: &str) -> ferris_proof_core::Result<StructuredResult> {
         self.parse_proptest_output(raw_output, "")
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
     }
 
     fn metadata(&self) -> PluginMetadata {
@@ -372,10 +463,12 @@ impl VerificationPlugin for ProptestPlugin {
                 "windows".to_string(),
             ],
             dependencies: vec!["cargo".to_string(), "rustc".to_string()],
+            renderers: Vec::new(),
+            error_codes: Vec::new(),
         }
     }
 
-    fn initialize(&mut self, config: &serde_json::Value) -> Result<()> {
+    fn initialize(&mut self, config: &serde_json::Value) -> ferris_proof_core::Result<()> {
         // Extract proptest configuration
         if let Some(tool_config) = config.get("proptest") {
             if let Some(path) = tool_config.get("path").and_then(|v| v.as_str()) {
@@ -386,7 +479,10 @@ impl VerificationPlugin for ProptestPlugin {
         // Verify tool availability
         let tool_info = self.check_availability()?;
         if !tool_info.available {
-            return Err(anyhow!("Proptest is not available: {}", tool_info.version));
+            return Err(FerrisProofError::plugin(
+                "proptest",
+                format!("Proptest is not available: {}", tool_info.version),
+            ));
         }
 
         self.initialized = true;
@@ -397,7 +493,7 @@ impl VerificationPlugin for ProptestPlugin {
         Ok(())
     }
 
-    fn cleanup(&mut self) -> Result<()> {
+    fn cleanup(&mut self) -> ferris_proof_core::Result<()> {
         self.initialized = false;
         debug!("Proptest plugin cleaned up");
         Ok(())
@@ -437,22 +533,62 @@ mod tests {
     fn test_output_parsing() {
         let plugin = ProptestPlugin::new();
 
-        let success_output = "test result: ok. 1000 tests run.";
+        let success_output = concat!(
+            "{ \"type\": \"suite\", \"event\": \"started\", \"test_count\": 1 }\n",
+            "{ \"type\": \"test\", \"name\": \"roundtrip_ok\", \"event\": \"ok\", \"exec_time\": 0.5 }\n",
+        );
         let result = plugin.parse_proptest_output(success_output, "").unwrap();
         assert_eq!(result.status, Status::Success);
+        // Test names containing "ok" are counted once
+        assert_eq!(result.statistics["successes"], 1);
+        assert_eq!(result.statistics["failures"], 0);
 
-        let failure_output = "test FAILED: property should hold\n1 tests run.";
+        let failure_output = concat!(
+            "{ \"type\": \"suite\", \"event\": \"started\", \"test_count\": 1 }\n",
+            "{ \"type\": \"test\", \"name\": \"roundtrip\", \"event\": \"failed\" }\n",
+        );
         let result = plugin.parse_proptest_output(failure_output, "").unwrap();
         assert_eq!(result.status, Status::Error);
+        assert_eq!(result.statistics["test_cases_executed"], 1);
+
+        let build_error = "error[E0425]: cannot find value `x` in this scope";
+        let result = plugin.parse_proptest_output("", build_error).unwrap();
+        assert_eq!(result.status, Status::Error);
     }
 
     #[test]
-    fn test_statistics_extraction() {
+    fn test_failure_location() {
         let plugin = ProptestPlugin::new();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+        let target = temp_dir.path().join("src").join("lib.rs");
 
-        let output = "test result: ok. 1030 tests run, 0 failed.";
-        assert_eq!(plugin.extract_test_cases(output), 1030);
-        assert_eq!(plugin.extract_failures(output), 0);
-        assert_eq!(plugin.extract_successes(output), 1); // from "ok"
+        let output = "thread 'tests::prop' panicked at src/codec.rs:42:9:\nassertion failed";
+        let location = plugin.failure_location(output, &target).unwrap();
+        assert_eq!(location.file, temp_dir.path().join("src/codec.rs"));
+        assert_eq!((location.line, location.column), (Some(42), Some(9)));
+        assert!(location.span.is_some());
+
+        let legacy = "thread 'main' panicked at 'boom', tests/prop.rs:7:5";
+        let location = plugin.failure_location(legacy, &target).unwrap();
+        assert_eq!(location.line, Some(7));
+
+        assert!(plugin.failure_location("test FAILED", &target).is_none());
+    }
+
+    #[test]
+    fn test_regression_artifacts() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let regressions = temp_dir.path().join("proptest-regressions").join("tests");
+        std::fs::create_dir_all(&regressions).unwrap();
+        std::fs::write(regressions.join("prop_tests.txt"), "cc 4f2a9b").unwrap();
+        std::fs::write(regressions.join("notes.md"), "ignored").unwrap();
+
+        let artifacts = regression_artifacts(temp_dir.path());
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].path, regressions.join("prop_tests.txt"));
+        assert_eq!(artifacts[0].content, "cc 4f2a9b");
+
+        assert!(regression_artifacts(&regressions).is_empty());
     }
 }
@@ -1,12 +1,37 @@
 use anyhow::{anyhow, Result};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::process::{Child, Command, Stdio};
-use std::sync::{Arc, Mutex};
+use std::process::{Child, Command, Output, Stdio};
 use std::time::{Duration, Instant};
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 
+#[cfg(target_os = "linux")]
+mod cgroup;
+#[cfg(windows)]
+mod job_object;
+#[cfg(target_os = "linux")]
+mod netns;
+
+/// Variables every sandboxed tool keeps from the invoking environment
+///
+/// Anything else must be passed explicitly, e.g. through `[tools.*.env]`.
+pub const BASE_ENV: &[&str] = &[
+    "HOME",
+    "USER",
+    "LANG",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    "SYSTEMROOT",
+    "CARGO_HOME",
+    "RUSTUP_HOME",
+    "RUSTUP_TOOLCHAIN",
+];
+
+/// Variables that route traffic through a proxy
+const PROXY_VARS: &[&str] = &["HTTP_PROXY", "HTTPS_PROXY", "FTP_PROXY", "ALL_PROXY"];
+
 /// Sandboxed executor for running external verification tools safely
 ///
 /// This executor provides:
@@ -124,6 +149,7 @@ impl SandboxedExecutor {
         if let Some(dir) = working_dir {
             self.validate_path_access(dir)?;
         }
+        self.validate_environment(&env)?;
 
         // Prepare command with security restrictions
         let cmd = self.prepare_command(command, args, env, working_dir)?;
@@ -166,6 +192,23 @@ impl SandboxedExecutor {
         Ok(())
     }
 
+    /// Reject proxy settings for a tool that may not use the network
+    fn validate_environment(&self, env: &HashMap<String, String>) -> Result<()> {
+        if self.network_policy != NetworkPolicy::Denied {
+            return Ok(());
+        }
+        match env
+            .keys()
+            .find(|name| PROXY_VARS.contains(&name.to_ascii_uppercase().as_str()))
+        {
+            Some(name) => Err(anyhow!(
+                "Environment passes {} but the sandbox denies network access",
+                name
+            )),
+            None => Ok(()),
+        }
+    }
+
     /// Validate that a path is within allowed access
     fn validate_path_access(&self, path: &PathBuf) -> Result<()> {
         if self.allowed_paths.is_empty() {
@@ -217,7 +260,14 @@ impl SandboxedExecutor {
             cmd.current_dir(dir);
         }
 
-        // Configure environment variables for security
+        // Start from a scrubbed environment, so tools get only the base
+        // variables and those they were given
+        cmd.env_clear();
+        cmd.envs(
+            BASE_ENV
+                .iter()
+                .filter_map(|name| Some((*name, std::env::var_os(name)?))),
+        );
         self.configure_environment(&mut env)?;
         cmd.envs(&env);
 
@@ -319,24 +369,27 @@ impl SandboxedExecutor {
 
         #[cfg(windows)]
         {
-            // Windows resource limits would be implemented using Job Objects
-            warn!("Resource limits not yet implemented on Windows");
+            // A Job Object can only be attached once the process exists;
+            // see `execute_with_timeout`
+            let _ = cmd;
         }
 
         Ok(())
     }
 
     /// Apply network access restrictions
-    fn apply_network_restrictions(&self, _cmd: &mut Command) -> Result<()> {
+    ///
+    /// On Linux, `Denied` (and allow lists naming only loopback hosts) run
+    /// the command in a private network namespace. Elsewhere, or when
+    /// namespaces are unavailable, only the environment-based restrictions
+    /// from `configure_environment` apply.
+    fn apply_network_restrictions(&self, cmd: &mut Command) -> Result<()> {
         match &self.network_policy {
             NetworkPolicy::Denied => {
                 debug!("Network access denied for sandboxed execution");
-                // Network restrictions are primarily handled through environment variables
-                // and firewall rules (platform-specific implementation)
             }
             NetworkPolicy::AllowList(hosts) => {
                 debug!("Network access restricted to hosts: {:?}", hosts);
-                // Implementation would involve configuring network namespace or firewall rules
             }
             NetworkPolicy::Unrestricted { user_consent } => {
                 if *user_consent {
@@ -347,50 +400,127 @@ impl SandboxedExecutor {
             }
         }
 
+        if self.wants_network_namespace() {
+            #[cfg(target_os = "linux")]
+            if netns::available() {
+                netns::isolate_on_exec(cmd);
+                return Ok(());
+            }
+
+            static FALLBACK_WARNED: std::sync::Once = std::sync::Once::new();
+            FALLBACK_WARNED.call_once(|| {
+                warn!(
+                    "Network namespaces unavailable; network policy is only enforced through the environment"
+                );
+            });
+        } else if let NetworkPolicy::AllowList(hosts) = &self.network_policy {
+            warn!(
+                "Allow list {:?} names external hosts, which cannot be enforced at the kernel level",
+                hosts
+            );
+        }
+
+        let _ = cmd;
         Ok(())
     }
 
+    /// Whether the policy can be enforced by cutting the command off from
+    /// every non-loopback network
+    fn wants_network_namespace(&self) -> bool {
+        match &self.network_policy {
+            NetworkPolicy::Denied => true,
+            #[cfg(target_os = "linux")]
+            NetworkPolicy::AllowList(hosts) => hosts.iter().all(|h| netns::is_loopback_host(h)),
+            #[cfg(not(target_os = "linux"))]
+            NetworkPolicy::AllowList(_) => true,
+            NetworkPolicy::Unrestricted { .. } => false,
+        }
+    }
+
+    /// Whether network policy is enforced by the kernel rather than only
+    /// through environment variables
+    pub fn enforces_network_isolation(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.wants_network_namespace() && netns::available()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            false
+        }
+    }
+
     /// Execute command with timeout and monitoring
     async fn execute_with_timeout(&self, mut cmd: Command) -> Result<SandboxedOutput> {
         let start_time = Instant::now();
 
+        // Track the whole process tree in its own cgroup when possible
+        #[cfg(target_os = "linux")]
+        let cgroup = cgroup::CgroupScope::create();
+        #[cfg(target_os = "linux")]
+        if let Some(scope) = &cgroup {
+            scope.attach_on_exec(&mut cmd);
+        }
+
         // Spawn the process
         let child = cmd
             .spawn()
             .map_err(|e| anyhow!("Failed to spawn process: {}", e))?;
+        #[cfg(unix)]
+        let signaller = ChildSignaller::new(&child);
+        #[cfg(unix)]
+        let wait = {
+            let signaller = signaller.clone();
+            move || wait_with_usage(child, &signaller)
+        };
+        #[cfg(windows)]
+        let pid = child.id();
+        #[cfg(not(unix))]
+        let wait = move || wait_with_usage(child);
 
-        // Create a shared handle for the child process
-        let child_handle = Arc::new(Mutex::new(Some(child)));
-        let child_handle_clone = Arc::clone(&child_handle);
+        // Enforce limits through a Job Object; anything the child starts
+        // before assignment escapes it, which is a known Windows limitation
+        #[cfg(windows)]
+        let job = match job_object::JobObject::create(&self.limits)
+            .and_then(|job| job.assign(&child).map(|_| job))
+        {
+            Ok(job) => Some(job),
+            Err(e) => {
+                warn!("Running without resource limits: {}", e);
+                None
+            }
+        };
 
         // Set up timeout handling
         let timeout_result = timeout(self.timeout_duration, async {
             // Wait for the process to complete in a blocking task
-            tokio::task::spawn_blocking(move || {
-                let mut child_guard = child_handle_clone.lock().unwrap();
-                if let Some(child) = child_guard.take() {
-                    child.wait_with_output()
-                } else {
-                    Err(std::io::Error::other("Child process not available"))
-                }
-            })
-            .await
-            .unwrap_or_else(|e| Err(std::io::Error::other(e.to_string())))
+            tokio::task::spawn_blocking(wait)
+                .await
+                .unwrap_or_else(|e| Err(std::io::Error::other(e.to_string())))
         })
         .await;
 
         let execution_time = start_time.elapsed();
 
         match timeout_result {
-            Ok(Ok(output)) => {
+            Ok(Ok((output, mut resource_usage))) => {
                 info!("Command completed successfully in {:?}", execution_time);
 
+                #[cfg(target_os = "linux")]
+                if let Some(scope) = &cgroup {
+                    scope.merge_usage(&mut resource_usage);
+                }
+                #[cfg(windows)]
+                if let Some(job) = &job {
+                    job.merge_usage(&mut resource_usage);
+                }
+
                 Ok(SandboxedOutput {
                     stdout: String::from_utf8_lossy(&output.stdout).to_string(),
                     stderr: String::from_utf8_lossy(&output.stderr).to_string(),
                     exit_code: output.status.code().unwrap_or(-1),
                     execution_time,
-                    resource_usage: self.collect_resource_usage(),
+                    resource_usage,
                     timeout_occurred: false,
                 })
             }
@@ -405,7 +535,23 @@ impl SandboxedExecutor {
                 );
 
                 // Attempt graceful termination
-                self.terminate_process_gracefully(&child_handle).await?;
+                #[cfg(unix)]
+                self.terminate_process_gracefully(&signaller).await?;
+                #[cfg(windows)]
+                self.terminate_process_tree(pid, job.as_ref())?;
+
+                // The waiting task still owns the child; only the cgroup or
+                // job can report usage for a process that never finished
+                #[allow(unused_mut)]
+                let mut resource_usage = ResourceUsage::default();
+                #[cfg(target_os = "linux")]
+                if let Some(scope) = &cgroup {
+                    scope.merge_usage(&mut resource_usage);
+                }
+                #[cfg(windows)]
+                if let Some(job) = &job {
+                    job.merge_usage(&mut resource_usage);
+                }
 
                 Ok(SandboxedOutput {
                     stdout: String::new(),
@@ -415,7 +561,7 @@ impl SandboxedExecutor {
                     ),
                     exit_code: -1,
                     execution_time,
-                    resource_usage: self.collect_resource_usage(),
+                    resource_usage,
                     timeout_occurred: true,
                 })
             }
@@ -423,74 +569,38 @@ impl SandboxedExecutor {
     }
 
     /// Terminate process gracefully with escalating signals
-    async fn terminate_process_gracefully(
-        &self,
-        child_handle: &Arc<Mutex<Option<Child>>>,
-    ) -> Result<()> {
-        let pid = {
-            let mut child_guard = child_handle.lock().unwrap();
-            if let Some(ref mut child) = child_guard.as_mut() {
-                #[cfg(unix)]
-                {
-                    // Try SIGTERM first
-                    let pid = child.id();
-                    unsafe {
-                        libc::kill(pid as i32, libc::SIGTERM);
-                    }
-                    Some(pid)
-                }
-                #[cfg(windows)]
-                {
-                    // On Windows, use TerminateProcess
-                    if let Err(e) = child.kill() {
-                        error!("Failed to terminate process: {}", e);
-                    }
-                    None
-                }
-            } else {
-                None
-            }
-        }; // Drop the lock before await
+    #[cfg(unix)]
+    async fn terminate_process_gracefully(&self, child: &ChildSignaller) -> Result<()> {
+        // Try SIGTERM first
+        child.signal(libc::SIGTERM);
 
         // Wait a bit for graceful shutdown
         tokio::time::sleep(Duration::from_secs(2)).await;
 
-        #[cfg(unix)]
-        if let Some(pid) = pid {
-            let mut child_guard = child_handle.lock().unwrap();
-            // Check if process is still running
-            if let Some(ref mut child) = child_guard.as_mut() {
-                match child.try_wait() {
-                    Ok(Some(_)) => {
-                        debug!("Process terminated gracefully");
-                        return Ok(());
-                    }
-                    Ok(None) => {
-                        // Still running, try SIGKILL
-                        warn!("Process did not respond to SIGTERM, sending SIGKILL");
-                        unsafe {
-                            libc::kill(pid as i32, libc::SIGKILL);
-                        }
-                    }
-                    Err(e) => {
-                        error!("Error checking process status: {}", e);
-                    }
-                }
-            }
+        if child.signal(libc::SIGKILL) {
+            warn!("Process did not respond to SIGTERM, sent SIGKILL");
+        } else {
+            debug!("Process terminated gracefully");
         }
 
         Ok(())
     }
 
-    /// Collect resource usage statistics
-    fn collect_resource_usage(&self) -> ResourceUsage {
-        // This would collect actual resource usage statistics
-        // For now, return default values
-        ResourceUsage {
-            peak_memory: 0,
-            cpu_time: Duration::from_secs(0),
-            file_descriptors_used: 0,
-            processes_spawned: 1,
+    /// Terminate the process and everything it started
+    ///
+    /// Windows console tools have no SIGTERM equivalent we can rely on, so
+    /// the whole job is terminated at once.
+    #[cfg(windows)]
+    fn terminate_process_tree(&self, pid: u32, job: Option<&job_object::JobObject>) -> Result<()> {
+        match job {
+            Some(job) => job.terminate(),
+            None => {
+                warn!(
+                    "Process {} is not in a job object and cannot be terminated",
+                    pid
+                );
+                Ok(())
+            }
         }
     }
 
@@ -514,6 +624,10 @@ impl SandboxedExecutor {
             warn!("Execution time exceeded configured timeout");
         }
 
+        for exceeded in result.resource_usage.exceeded_limits(&self.limits) {
+            warn!("Resource limit exceeded: {}", exceeded);
+        }
+
         Ok(())
     }
 }
@@ -528,14 +642,173 @@ pub struct SandboxedOutput {
     pub timeout_occurred: bool,
 }
 
-#[derive(Debug, Clone)]
+/// Resources consumed by a sandboxed command
+///
+/// Fields that cannot be measured on the current platform are left at zero.
+#[derive(Debug, Clone, Default)]
 pub struct ResourceUsage {
+    /// Peak resident set size in bytes
     pub peak_memory: u64,
+
+    /// User plus system CPU time
     pub cpu_time: Duration,
+
     pub file_descriptors_used: u32,
+
+    /// Processes in the command's tree, including the command itself
     pub processes_spawned: u32,
 }
 
+impl ResourceUsage {
+    /// Describe every limit the measured usage went over
+    pub fn exceeded_limits(&self, limits: &ResourceLimits) -> Vec<String> {
+        let mut exceeded = Vec::new();
+
+        if self.peak_memory > limits.max_memory {
+            exceeded.push(format!(
+                "peak memory {} bytes > {} bytes",
+                self.peak_memory, limits.max_memory
+            ));
+        }
+        if self.cpu_time > Duration::from_secs(limits.max_cpu_time) {
+            exceeded.push(format!(
+                "CPU time {:?} > {}s",
+                self.cpu_time, limits.max_cpu_time
+            ));
+        }
+        if self.processes_spawned > limits.max_processes {
+            exceeded.push(format!(
+                "{} processes > {}",
+                self.processes_spawned, limits.max_processes
+            ));
+        }
+
+        exceeded
+    }
+}
+
+/// Signals a spawned child for as long as its PID still belongs to it
+///
+/// Once the child is reaped its PID may be reused by an unrelated process,
+/// so signals are sent under the same lock that reaping takes.
+#[cfg(unix)]
+#[derive(Clone)]
+struct ChildSignaller {
+    pid: libc::pid_t,
+    reaped: std::sync::Arc<std::sync::Mutex<bool>>,
+}
+
+#[cfg(unix)]
+impl ChildSignaller {
+    fn new(child: &Child) -> Self {
+        Self {
+            pid: child.id() as libc::pid_t,
+            reaped: Default::default(),
+        }
+    }
+
+    /// Send `signal` unless the child has been reaped, returning whether it was sent
+    fn signal(&self, signal: libc::c_int) -> bool {
+        let reaped = self.reaped.lock().unwrap_or_else(|e| e.into_inner());
+        !*reaped && unsafe { libc::kill(self.pid, signal) } == 0
+    }
+
+    /// Reap the child once it has exited, so `signal` stops targeting its PID
+    fn reap(&self, status: &mut libc::c_int, rusage: &mut libc::rusage) -> std::io::Result<()> {
+        // Wait without reaping, so the PID stays ours until the lock is held
+        loop {
+            let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+            let flags = libc::WEXITED | libc::WNOWAIT;
+            if unsafe { libc::waitid(libc::P_PID, self.pid as libc::id_t, &mut info, flags) } == 0 {
+                break;
+            }
+            let err = std::io::Error::last_os_error();
+            if err.kind() != std::io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+
+        let mut reaped = self.reaped.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if unsafe { libc::wait4(self.pid, status, 0, rusage) } == self.pid {
+                *reaped = true;
+                return Ok(());
+            }
+            let err = std::io::Error::last_os_error();
+            if err.kind() != std::io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+}
+
+/// Wait for the child, collecting its output and resource usage
+#[cfg(unix)]
+fn wait_with_usage(
+    mut child: Child,
+    signaller: &ChildSignaller,
+) -> std::io::Result<(Output, ResourceUsage)> {
+    use std::io::Read;
+    use std::os::unix::process::ExitStatusExt;
+
+    // Drain the pipes concurrently so a chatty child cannot block on a full pipe
+    fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> std::thread::JoinHandle<Vec<u8>> {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buffer);
+            }
+            buffer
+        })
+    }
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let mut status = 0;
+    let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+    signaller.reap(&mut status, &mut rusage)?;
+
+    let output = Output {
+        status: std::process::ExitStatus::from_raw(status),
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    };
+
+    Ok((output, usage_from_rusage(&rusage)))
+}
+
+#[cfg(unix)]
+fn usage_from_rusage(rusage: &libc::rusage) -> ResourceUsage {
+    let timeval = |tv: libc::timeval| {
+        Duration::from_secs(tv.tv_sec as u64) + Duration::from_micros(tv.tv_usec as u64)
+    };
+
+    // ru_maxrss is in bytes on macOS and kilobytes elsewhere
+    #[cfg(target_os = "macos")]
+    let peak_memory = rusage.ru_maxrss as u64;
+    #[cfg(not(target_os = "macos"))]
+    let peak_memory = rusage.ru_maxrss as u64 * 1024;
+
+    ResourceUsage {
+        peak_memory,
+        cpu_time: timeval(rusage.ru_utime) + timeval(rusage.ru_stime),
+        file_descriptors_used: 0,
+        processes_spawned: 1,
+    }
+}
+
+#[cfg(not(unix))]
+fn wait_with_usage(child: Child) -> std::io::Result<(Output, ResourceUsage)> {
+    let output = child.wait_with_output()?;
+    Ok((
+        output,
+        ResourceUsage {
+            processes_spawned: 1,
+            ..ResourceUsage::default()
+        },
+    ))
+}
+
 impl Default for ResourceLimits {
     fn default() -> Self {
         Self {
@@ -567,3 +840,21 @@ impl Default for SandboxedExecutor {
         Self::new()
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    #[test]
+    fn test_reaped_children_are_not_signalled() {
+        let child = Command::new("sleep").arg("5").spawn().unwrap();
+        let signaller = ChildSignaller::new(&child);
+        assert!(signaller.signal(libc::SIGKILL));
+
+        let (output, _) = wait_with_usage(child, &signaller).unwrap();
+        assert_eq!(output.status.signal(), Some(libc::SIGKILL));
+        // The PID may now belong to another process
+        assert!(!signaller.signal(libc::SIGKILL));
+    }
+}
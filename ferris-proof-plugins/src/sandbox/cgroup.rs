@@ -0,0 +1,87 @@
+use super::ResourceUsage;
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Per-execution cgroup v2 used to account for the whole process tree
+///
+/// Creation fails quietly when cgroup v2 is not mounted or the current
+/// cgroup is not delegated to us; callers fall back to `wait4` figures.
+pub(super) struct CgroupScope {
+    path: PathBuf,
+}
+
+impl CgroupScope {
+    pub fn create() -> Option<Self> {
+        let root = Path::new(CGROUP_ROOT);
+        if !root.join("cgroup.controllers").exists() {
+            return None;
+        }
+
+        let membership = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+        let current = membership.lines().find_map(|l| l.strip_prefix("0::"))?;
+        let path = root
+            .join(current.trim_start_matches('/'))
+            .join(format!("ferris-proof-{}", uuid::Uuid::new_v4()));
+
+        match std::fs::create_dir(&path) {
+            Ok(()) => Some(Self { path }),
+            Err(e) => {
+                debug!("cgroup accounting unavailable: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Move the command into this cgroup between fork and exec
+    pub fn attach_on_exec(&self, cmd: &mut Command) {
+        let Ok(procs) = CString::new(self.path.join("cgroup.procs").as_os_str().as_bytes()) else {
+            return;
+        };
+
+        unsafe {
+            cmd.pre_exec(move || {
+                // Only async-signal-safe calls here; "0" means the writer itself
+                let fd = libc::open(procs.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+                if fd >= 0 {
+                    libc::write(fd, b"0".as_ptr().cast(), 1);
+                    libc::close(fd);
+                }
+                Ok(())
+            });
+        }
+    }
+
+    /// Fold tree-wide peaks into usage measured for the direct child
+    pub fn merge_usage(&self, usage: &mut ResourceUsage) {
+        if let Some(peak) = self.read_counter("memory.peak") {
+            usage.peak_memory = usage.peak_memory.max(peak);
+        }
+        if let Some(peak) = self.read_counter("pids.peak") {
+            usage.processes_spawned = usage.processes_spawned.max(peak as u32);
+        }
+    }
+
+    fn read_counter(&self, name: &str) -> Option<u64> {
+        std::fs::read_to_string(self.path.join(name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+}
+
+impl Drop for CgroupScope {
+    fn drop(&mut self) {
+        // Fails while orphaned descendants are still alive; the kernel keeps
+        // the empty directory until they exit
+        if let Err(e) = std::fs::remove_dir(&self.path) {
+            debug!("Failed to remove cgroup {:?}: {}", self.path, e);
+        }
+    }
+}
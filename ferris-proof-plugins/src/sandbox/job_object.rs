@@ -0,0 +1,125 @@
+use super::{ResourceLimits, ResourceUsage};
+use anyhow::{anyhow, Result};
+use std::os::windows::io::AsRawHandle;
+use std::process::Child;
+use std::time::Duration;
+use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+use windows_sys::Win32::System::JobObjects::{
+    AssignProcessToJobObject, CreateJobObjectW, JobObjectBasicAccountingInformation,
+    JobObjectExtendedLimitInformation, QueryInformationJobObject, SetInformationJobObject,
+    TerminateJobObject, JOBOBJECT_BASIC_ACCOUNTING_INFORMATION,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JOB_OBJECT_LIMIT_ACTIVE_PROCESS,
+    JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+    JOB_OBJECT_LIMIT_PROCESS_TIME,
+};
+
+/// Job Object enforcing `ResourceLimits` on a process tree
+///
+/// Processes started by the child after it is assigned inherit the job, so
+/// terminating or closing the job takes the whole tree down.
+pub(super) struct JobObject {
+    handle: HANDLE,
+}
+
+// The handle is an owned kernel object and may be used from any thread
+unsafe impl Send for JobObject {}
+unsafe impl Sync for JobObject {}
+
+impl JobObject {
+    pub fn create(limits: &ResourceLimits) -> Result<Self> {
+        let handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+        if handle == 0 {
+            return Err(anyhow!(
+                "Failed to create job object: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        let job = Self { handle };
+
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_PROCESS_MEMORY
+            | JOB_OBJECT_LIMIT_PROCESS_TIME
+            | JOB_OBJECT_LIMIT_ACTIVE_PROCESS
+            | JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        info.ProcessMemoryLimit = limits.max_memory as usize;
+        // Expressed in 100ns ticks
+        info.BasicLimitInformation.PerProcessUserTimeLimit =
+            limits.max_cpu_time as i64 * 10_000_000;
+        info.BasicLimitInformation.ActiveProcessLimit = limits.max_processes;
+
+        let ok = unsafe {
+            SetInformationJobObject(
+                job.handle,
+                JobObjectExtendedLimitInformation,
+                &info as *const _ as *const _,
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            )
+        };
+        if ok == 0 {
+            return Err(anyhow!(
+                "Failed to set job object limits: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+
+        Ok(job)
+    }
+
+    /// Place a freshly spawned child in the job
+    pub fn assign(&self, child: &Child) -> Result<()> {
+        let ok = unsafe { AssignProcessToJobObject(self.handle, child.as_raw_handle() as HANDLE) };
+        if ok == 0 {
+            return Err(anyhow!(
+                "Failed to assign process to job object: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Kill every process in the job
+    pub fn terminate(&self) -> Result<()> {
+        if unsafe { TerminateJobObject(self.handle, 1) } == 0 {
+            return Err(anyhow!(
+                "Failed to terminate job object: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Fold job-wide accounting into usage measured for the direct child
+    pub fn merge_usage(&self, usage: &mut ResourceUsage) {
+        let mut limits: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+        if self.query(JobObjectExtendedLimitInformation, &mut limits) {
+            usage.peak_memory = usage.peak_memory.max(limits.PeakJobMemoryUsed as u64);
+        }
+
+        let mut accounting: JOBOBJECT_BASIC_ACCOUNTING_INFORMATION = unsafe { std::mem::zeroed() };
+        if self.query(JobObjectBasicAccountingInformation, &mut accounting) {
+            let ticks = (accounting.TotalUserTime + accounting.TotalKernelTime).max(0) as u64;
+            usage.cpu_time = usage.cpu_time.max(Duration::from_nanos(ticks * 100));
+            usage.processes_spawned = usage.processes_spawned.max(accounting.TotalProcesses);
+        }
+    }
+
+    fn query<T>(&self, class: i32, info: &mut T) -> bool {
+        unsafe {
+            QueryInformationJobObject(
+                self.handle,
+                class,
+                info as *mut T as *mut _,
+                std::mem::size_of::<T>() as u32,
+                std::ptr::null_mut(),
+            ) != 0
+        }
+    }
+}
+
+impl Drop for JobObject {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.handle);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+use std::sync::OnceLock;
+use tracing::debug;
+
+/// Whether this process can give children a private network namespace
+///
+/// Probed once by forking a child that attempts the same `unshare` the
+/// sandbox would perform. Fails on kernels with unprivileged user
+/// namespaces disabled and inside most containers without CAP_SYS_ADMIN.
+pub(super) fn available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+    *AVAILABLE.get_or_init(|| {
+        let available = unsafe {
+            match libc::fork() {
+                -1 => false,
+                0 => libc::_exit(if enter_namespace() { 0 } else { 1 }),
+                pid => {
+                    let mut status = 0;
+                    libc::waitpid(pid, &mut status, 0) == pid
+                        && libc::WIFEXITED(status)
+                        && libc::WEXITSTATUS(status) == 0
+                }
+            }
+        };
+        debug!("Network namespace isolation available: {}", available);
+        available
+    })
+}
+
+/// Run the command in a fresh network namespace with only loopback up
+pub(super) fn isolate_on_exec(cmd: &mut Command) {
+    unsafe {
+        cmd.pre_exec(|| {
+            if !enter_namespace() {
+                return Err(std::io::Error::last_os_error());
+            }
+            bring_up_loopback();
+            Ok(())
+        });
+    }
+}
+
+/// Unshare the network namespace, via a user namespace when unprivileged
+///
+/// Only async-signal-safe calls: this runs between fork and exec.
+fn enter_namespace() -> bool {
+    unsafe {
+        libc::unshare(libc::CLONE_NEWNET) == 0
+            || libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) == 0
+    }
+}
+
+/// New namespaces start with `lo` down; tools talking to themselves over
+/// localhost (e.g. JVM attach) expect it to work
+fn bring_up_loopback() {
+    unsafe {
+        let fd = libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0);
+        if fd < 0 {
+            return;
+        }
+
+        let mut request: libc::ifreq = std::mem::zeroed();
+        for (dst, src) in request.ifr_name.iter_mut().zip(b"lo\0") {
+            *dst = *src as libc::c_char;
+        }
+        if libc::ioctl(fd, libc::SIOCGIFFLAGS as _, &mut request) == 0 {
+            request.ifr_ifru.ifru_flags |= libc::IFF_UP as libc::c_short;
+            libc::ioctl(fd, libc::SIOCSIFFLAGS as _, &request);
+        }
+        libc::close(fd);
+    }
+}
+
+/// Hosts reachable from inside an isolated namespace
+pub(super) fn is_loopback_host(host: &str) -> bool {
+    let host = host.rsplit_once(':').map_or(host, |(name, port)| {
+        if port.chars().all(|c| c.is_ascii_digit()) && !name.ends_with(':') {
+            name
+        } else {
+            host
+        }
+    });
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loopback_hosts() {
+        for host in [
+            "localhost",
+            "127.0.0.1",
+            "127.0.0.1:8080",
+            "::1",
+            "[::1]:443",
+        ] {
+            assert!(is_loopback_host(host), "{} should be loopback", host);
+        }
+        for host in ["example.com", "10.0.0.1", "crates.io:443"] {
+            assert!(!is_loopback_host(host), "{} should not be loopback", host);
+        }
+    }
+}
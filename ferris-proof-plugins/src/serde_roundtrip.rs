@@ -0,0 +1,996 @@
+use anyhow::{anyhow, Context, Result};
+use ferris_proof_core::{
+    cache::module_path_of,
+    plugins::{
+        run_tool, PerformanceMetrics, PluginMetadata, StructuredResult, ToolInfo,
+        VerificationInput, VerificationOutput, VerificationPlugin, VersionRange,
+    },
+    seed,
+    types::*,
+    verification::Target,
+    FerrisProofError,
+};
+use regex::Regex;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Fields, Generics, Item, Token, Visibility};
+use tracing::{debug, info, warn};
+
+/// First line of the generated test file; files without it are never replaced
+pub const GENERATED_HEADER: &str =
+    "// Generated by ferris-proof (`ferris-proof generate serde-roundtrip`). Edits may be overwritten.";
+
+/// Integration test target holding the generated round-trip properties
+pub const TEST_TARGET: &str = "ferris_proof_serde_roundtrip";
+
+/// Serialization formats exercised by the round-trip matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SerdeFormat {
+    Json,
+    Bincode,
+    Toml,
+}
+
+impl SerdeFormat {
+    pub const ALL: [SerdeFormat; 3] = [SerdeFormat::Json, SerdeFormat::Bincode, SerdeFormat::Toml];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SerdeFormat::Json => "json",
+            SerdeFormat::Bincode => "bincode",
+            SerdeFormat::Toml => "toml",
+        }
+    }
+
+    /// Crate the generated tests need as a dev-dependency
+    pub fn crate_name(self) -> &'static str {
+        match self {
+            SerdeFormat::Json => "serde_json",
+            SerdeFormat::Bincode => "bincode",
+            SerdeFormat::Toml => "toml",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Property body encoding `value` and asserting the decoded copy is equal
+    fn property_body(self, ty: &str) -> String {
+        match self {
+            SerdeFormat::Json => format!(
+                "let encoded = serde_json::to_string(&value).map_err(|e| TestCaseError::fail(e.to_string()))?;\n        \
+                 let decoded: {ty} = serde_json::from_str(&encoded).map_err(|e| TestCaseError::fail(e.to_string()))?;\n        \
+                 prop_assert_eq!(decoded, value);"
+            ),
+            SerdeFormat::Bincode => format!(
+                "let encoded = bincode::serialize(&value).map_err(|e| TestCaseError::fail(e.to_string()))?;\n        \
+                 let decoded: {ty} = bincode::deserialize(&encoded).map_err(|e| TestCaseError::fail(e.to_string()))?;\n        \
+                 prop_assert_eq!(decoded, value);"
+            ),
+            // TOML documents must be tables, so wrap the value in one
+            SerdeFormat::Toml => format!(
+                "let encoded = toml::to_string(&TomlRef {{ value: &value }}).map_err(|e| TestCaseError::fail(e.to_string()))?;\n        \
+                 let decoded: TomlOwned<{ty}> = toml::from_str(&encoded).map_err(|e| TestCaseError::fail(e.to_string()))?;\n        \
+                 prop_assert_eq!(decoded.value, value);"
+            ),
+        }
+    }
+}
+
+/// A type deriving both `Serialize` and `Deserialize`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerdeType {
+    pub name: String,
+    /// Module path inside the crate, e.g. `crypto::keys`; empty for the root
+    pub module_path: String,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Why no round-trip property can be generated, if any
+    pub uncovered_reason: Option<String>,
+    /// Configured strategy expression, used instead of `any::<T>()`
+    pub strategy: Option<String>,
+}
+
+impl SerdeType {
+    pub fn path_in_crate(&self, crate_name: &str) -> String {
+        if self.module_path.is_empty() {
+            format!("{}::{}", crate_name, self.name)
+        } else {
+            format!("{}::{}::{}", crate_name, self.module_path, self.name)
+        }
+    }
+
+    /// Strategy generating values in the rendered tests
+    pub fn strategy_in_crate(&self, crate_name: &str) -> String {
+        self.strategy
+            .clone()
+            .unwrap_or_else(|| format!("any::<{}>()", self.path_in_crate(crate_name)))
+    }
+
+    fn test_name(&self, format: SerdeFormat) -> String {
+        let mut name = format!("roundtrip_{}", format.name());
+        for segment in self.module_path.split("::").filter(|s| !s.is_empty()) {
+            name.push_str("__");
+            name.push_str(segment);
+        }
+        name.push_str("__");
+        name.push_str(&self.name);
+        name
+    }
+
+    fn location(&self) -> Location {
+        Location::point(self.file.clone(), self.line as u32, 1)
+    }
+}
+
+/// Round-trip properties to generate for one package
+#[derive(Debug, Clone)]
+pub struct RoundtripPlan {
+    pub crate_name: String,
+    pub types: Vec<SerdeType>,
+    /// Formats whose crate is a dev-dependency of the package
+    pub formats: Vec<SerdeFormat>,
+    /// Requested formats skipped because their crate is missing
+    pub missing_formats: Vec<SerdeFormat>,
+}
+
+impl RoundtripPlan {
+    /// Scan `src/` of the package at `package_root` for serde types
+    ///
+    /// Only types in files accepted by `covered` are planned, so callers can
+    /// restrict the plan to modules with property tests enabled. `strategies`
+    /// maps type paths to strategy expressions, from `[generate.proptest.strategies]`.
+    pub fn build(
+        package_root: &Path,
+        requested: &[SerdeFormat],
+        strategies: &BTreeMap<String, String>,
+        covered: impl Fn(&Path) -> bool,
+    ) -> Result<Self> {
+        let (manifest, crate_name) = package_manifest(package_root)?;
+        let (formats, missing_formats) = requested
+            .iter()
+            .copied()
+            .partition(|format| has_dependency(&manifest, format.crate_name()));
+
+        let mut sources = Vec::new();
+        for file in rust_files(&package_root.join("src")) {
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {:?}", file))?;
+            let ast =
+                syn::parse_file(&content).with_context(|| format!("Failed to parse {:?}", file))?;
+            sources.push((file, content, ast));
+        }
+
+        // `impl Arbitrary` often lives in a different module than the type
+        let mut arbitrary_impls = Vec::new();
+        for (_, _, ast) in &sources {
+            collect_arbitrary_impls(&ast.items, &mut arbitrary_impls);
+        }
+
+        let mut types = Vec::new();
+        for (file, content, ast) in sources.iter().filter(|(file, _, _)| covered(file)) {
+            let relative = file.strip_prefix(package_root).unwrap_or(file);
+            let module_path = module_path_of(&relative.to_string_lossy());
+            let module_path = if module_path == "crate" {
+                ""
+            } else {
+                module_path.as_str()
+            };
+
+            let source = SourceFile {
+                path: file,
+                content,
+                arbitrary_impls: &arbitrary_impls,
+                crate_name: &crate_name,
+                strategies,
+            };
+            source.visit_items(&ast.items, module_path, true, &mut types);
+        }
+
+        Ok(Self {
+            crate_name,
+            types,
+            formats,
+            missing_formats,
+        })
+    }
+
+    pub fn covered_types(&self) -> impl Iterator<Item = &SerdeType> {
+        self.types.iter().filter(|t| t.uncovered_reason.is_none())
+    }
+
+    pub fn test_path(package_root: &Path) -> PathBuf {
+        package_root
+            .join("tests")
+            .join(format!("{}.rs", TEST_TARGET))
+    }
+
+    /// Source of the generated integration test
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(GENERATED_HEADER);
+        out.push_str("\n\nuse proptest::prelude::*;\n");
+
+        if self.formats.contains(&SerdeFormat::Toml) {
+            out.push_str(
+                "\n#[derive(serde::Serialize)]\nstruct TomlRef<'a, T> {\n    value: &'a T,\n}\n\
+                 \n#[derive(serde::Deserialize)]\nstruct TomlOwned<T> {\n    value: T,\n}\n",
+            );
+        }
+
+        out.push_str("\nproptest! {\n");
+        for ty in self.covered_types() {
+            let path = ty.path_in_crate(&self.crate_name);
+            let strategy = ty.strategy_in_crate(&self.crate_name);
+            for format in &self.formats {
+                out.push_str(&format!(
+                    "    #[test]\n    fn {}(value in {}) {{\n        {}\n    }}\n\n",
+                    ty.test_name(*format),
+                    strategy,
+                    format.property_body(&path)
+                ));
+            }
+        }
+        if out.ends_with("\n\n") {
+            out.pop();
+        }
+        out.push_str("}\n");
+
+        out
+    }
+
+    /// Write the generated test, refusing to replace a hand-written file
+    ///
+    /// Returns `false` when an existing file without the generated header was
+    /// left untouched.
+    pub fn write(&self, package_root: &Path) -> Result<bool> {
+        let path = Self::test_path(package_root);
+        if path.exists() {
+            let existing = std::fs::read_to_string(&path)?;
+            if !existing.starts_with(GENERATED_HEADER) {
+                debug!("Keeping hand-written round-trip tests at {:?}", path);
+                return Ok(false);
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, self.render())
+            .map_err(|e| anyhow!("Failed to write round-trip tests {:?}: {}", path, e))?;
+        Ok(true)
+    }
+
+    /// Violations for failing round-trip tests in `cargo test` output
+    pub fn failures(&self, output: &str) -> Vec<Violation> {
+        let Ok(re) = Regex::new(r"(?m)^test (roundtrip_\w+) \.\.\. FAILED") else {
+            return Vec::new();
+        };
+        let mut tests = BTreeMap::new();
+        for ty in self.covered_types() {
+            for format in &self.formats {
+                tests.insert(ty.test_name(*format), (ty, *format));
+            }
+        }
+
+        re.captures_iter(output)
+            .filter_map(|captures| tests.get(&captures[1]))
+            .map(|(ty, format)| Violation {
+                id: "SERDE_ROUNDTRIP_FAILURE".to_string(),
+                severity: Severity::Error,
+                location: ty.location(),
+                message: format!(
+                    "`{}` does not survive a {} round trip",
+                    ty.name,
+                    format.name()
+                ),
+                suggestion: Some(format!(
+                    "Run `cargo test --test {}` to see the minimal failing value",
+                    TEST_TARGET
+                )),
+                rule: "serde_roundtrip".to_string(),
+            })
+            .collect()
+    }
+
+    /// Warnings for serde types and formats the generated tests cannot cover
+    pub fn coverage_gaps(&self) -> Vec<Violation> {
+        let mut violations: Vec<Violation> = self
+            .types
+            .iter()
+            .filter_map(|ty| {
+                let reason = ty.uncovered_reason.as_ref()?;
+                Some(Violation {
+                    id: "SERDE_ROUNDTRIP_UNCOVERED".to_string(),
+                    severity: Severity::Warning,
+                    location: ty.location(),
+                    message: format!("`{}` has no round-trip property: {}", ty.name, reason),
+                    suggestion: Some(
+                        "Derive `PartialEq`, `Debug` and `proptest_derive::Arbitrary` (or \
+                         `ferris_proof_macros::Verified`) on a public type, \
+                         or map it to a strategy in [generate.proptest.strategies]"
+                            .to_string(),
+                    ),
+                    rule: "serde_roundtrip".to_string(),
+                })
+            })
+            .collect();
+
+        for format in &self.missing_formats {
+            violations.push(Violation {
+                id: "SERDE_ROUNDTRIP_UNCOVERED".to_string(),
+                severity: Severity::Warning,
+                location: Location::file(PathBuf::from("Cargo.toml")),
+                message: format!(
+                    "{} round trips skipped: `{}` is not a dev-dependency",
+                    format.name(),
+                    format.crate_name()
+                ),
+                suggestion: Some(format!(
+                    "Add `{}` to [dev-dependencies]",
+                    format.crate_name()
+                )),
+                rule: "serde_roundtrip".to_string(),
+            });
+        }
+
+        violations
+    }
+}
+
+struct SourceFile<'a> {
+    path: &'a Path,
+    content: &'a str,
+    arbitrary_impls: &'a [String],
+    crate_name: &'a str,
+    strategies: &'a BTreeMap<String, String>,
+}
+
+impl SourceFile<'_> {
+    fn visit_items(
+        &self,
+        items: &[Item],
+        module_path: &str,
+        reachable: bool,
+        types: &mut Vec<SerdeType>,
+    ) {
+        for item in items {
+            let (keyword, ident, attrs, vis, generics) = match item {
+                Item::Struct(s) => ("struct", &s.ident, &s.attrs, &s.vis, &s.generics),
+                Item::Enum(e) => ("enum", &e.ident, &e.attrs, &e.vis, &e.generics),
+                Item::Mod(m) => {
+                    if is_cfg_test(&m.attrs) {
+                        continue;
+                    }
+                    if let Some((_, nested)) = &m.content {
+                        let path = if module_path.is_empty() {
+                            m.ident.to_string()
+                        } else {
+                            format!("{}::{}", module_path, m.ident)
+                        };
+                        let reachable = reachable && matches!(m.vis, Visibility::Public(_));
+                        self.visit_items(nested, &path, reachable, types);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let derives = derived_traits(attrs);
+            let has = |name: &str| derives.iter().any(|d| d == name);
+            if !has("Serialize") || !has("Deserialize") {
+                continue;
+            }
+
+            let name = ident.to_string();
+            let strategy =
+                configured_expression(self.strategies, self.crate_name, module_path, &name);
+            let uncovered_reason = if !reachable || !matches!(vis, Visibility::Public(_)) {
+                Some("not public, so integration tests cannot name it".to_string())
+            } else if has_type_parameters(generics) {
+                Some("generic types need a concrete instantiation".to_string())
+            } else if !has("PartialEq") || !has("Debug") {
+                Some("equality checks need `PartialEq` and `Debug`".to_string())
+            } else if !has("Arbitrary")
+                && !has("Verified")
+                && !self.arbitrary_impls.contains(&name)
+                && strategy.is_none()
+            {
+                Some("no `Arbitrary` implementation or configured strategy".to_string())
+            } else if matches!(item, Item::Struct(s) if matches!(s.fields, Fields::Unit)) {
+                Some("unit structs have nothing to round-trip".to_string())
+            } else {
+                None
+            };
+
+            types.push(SerdeType {
+                line: declaration_line(self.content, keyword, &name),
+                name,
+                module_path: module_path.to_string(),
+                file: self.path.to_path_buf(),
+                uncovered_reason,
+                strategy,
+            });
+        }
+    }
+}
+
+/// Configured expression for an item, such as a type's strategy, keyed by
+/// its path or bare name
+///
+/// Paths may start with `crate::` or the crate name, e.g. `crate::msg::Message`,
+/// `wire_types::msg::Message`, `msg::Message` or `Message`.
+pub(crate) fn configured_expression(
+    expressions: &BTreeMap<String, String>,
+    crate_name: &str,
+    module_path: &str,
+    name: &str,
+) -> Option<String> {
+    let path = if module_path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}::{}", module_path, name)
+    };
+
+    expressions.iter().find_map(|(key, expression)| {
+        let key = key.trim();
+        let key = key
+            .strip_prefix("crate::")
+            .or_else(|| key.strip_prefix(&format!("{}::", crate_name)))
+            .unwrap_or(key);
+        (key == path || key == name).then(|| expression.clone())
+    })
+}
+
+/// Parsed `Cargo.toml` of the package at `package_root` and its crate name,
+/// with `-` replaced by `_`
+pub(crate) fn package_manifest(package_root: &Path) -> Result<(toml::Value, String)> {
+    let manifest_path = package_root.join("Cargo.toml");
+    let manifest: toml::Value = toml::from_str(
+        &std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {:?}", manifest_path))?,
+    )
+    .with_context(|| format!("Failed to parse {:?}", manifest_path))?;
+
+    let crate_name = manifest
+        .get("lib")
+        .and_then(|lib| lib.get("name"))
+        .or_else(|| manifest.get("package").and_then(|p| p.get("name")))
+        .and_then(|name| name.as_str())
+        .ok_or_else(|| anyhow!("{:?} has no package name", manifest_path))?
+        .replace('-', "_");
+    Ok((manifest, crate_name))
+}
+
+/// Whether generated tests can use crate `name`, as a dev- or regular dependency
+pub(crate) fn has_dependency(manifest: &toml::Value, name: &str) -> bool {
+    ["dev-dependencies", "dependencies"].iter().any(|table| {
+        manifest.get(table).is_some_and(|deps| {
+            deps.get(name).is_some() || deps.get(name.replace('_', "-")).is_some()
+        })
+    })
+}
+
+/// Names from `#[derive(...)]`, keeping only the last path segment
+pub(crate) fn derived_traits(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter_map(|path| path.segments.last().map(|s| s.ident.to_string()))
+        .collect()
+}
+
+pub(crate) fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg")
+            && attr
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|ident| ident == "test")
+    })
+}
+
+pub(crate) fn has_type_parameters(generics: &Generics) -> bool {
+    generics
+        .params
+        .iter()
+        .any(|p| !matches!(p, syn::GenericParam::Lifetime(_)))
+}
+
+/// Self types of hand-written `impl Arbitrary for T` blocks
+fn collect_arbitrary_impls(items: &[Item], names: &mut Vec<String>) {
+    for item in items {
+        match item {
+            Item::Impl(imp) => {
+                let implements_arbitrary = imp
+                    .trait_
+                    .as_ref()
+                    .and_then(|(_, path, _)| path.segments.last())
+                    .is_some_and(|s| s.ident == "Arbitrary");
+                if let (true, syn::Type::Path(ty)) = (implements_arbitrary, &*imp.self_ty) {
+                    if let Some(segment) = ty.path.segments.last() {
+                        names.push(segment.ident.to_string());
+                    }
+                }
+            }
+            Item::Mod(m) => {
+                if let Some((_, nested)) = &m.content {
+                    collect_arbitrary_impls(nested, names);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 1-based line of `struct Name`/`enum Name`, falling back to the first line
+pub(crate) fn declaration_line(content: &str, keyword: &str, name: &str) -> usize {
+    Regex::new(&format!(r"\b{}\s+{}\b", keyword, regex::escape(name)))
+        .ok()
+        .and_then(|re| re.find(content))
+        .map_or(1, |m| content[..m.start()].matches('\n').count() + 1)
+}
+
+pub(crate) fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    fn visit(dir: &Path, files: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                visit(&path, files);
+            } else if path.extension().and_then(|s| s.to_str()) == Some("rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    visit(dir, &mut files);
+    files.sort();
+    files
+}
+
+/// Property-based layer checking serialize→deserialize is the identity
+pub struct SerdeRoundtripPlugin {
+    formats: Vec<SerdeFormat>,
+    strategies: BTreeMap<String, String>,
+    initialized: bool,
+}
+
+impl SerdeRoundtripPlugin {
+    pub fn new() -> Self {
+        Self {
+            formats: SerdeFormat::ALL.to_vec(),
+            strategies: BTreeMap::new(),
+            initialized: false,
+        }
+    }
+
+    fn run_roundtrips(&self, input: &VerificationInput) -> Result<VerificationOutput> {
+        let start_time = std::time::Instant::now();
+
+        let (anchor, only_file) = match &input.target {
+            Target::RustFile(path) | Target::Item { file: path, .. } => {
+                (path.clone(), Some(path.clone()))
+            }
+            Target::Module(_) => (input.context.working_dir.clone(), None),
+            Target::FormalSpec(_) => {
+                return Err(anyhow!(
+                    "Serde round-trip plugin only supports Rust targets"
+                ))
+            }
+        };
+        let package_root = anchor
+            .ancestors()
+            .find(|dir| dir.join("Cargo.toml").exists())
+            .ok_or_else(|| anyhow!("No Cargo.toml above {:?}", anchor))?
+            .to_path_buf();
+
+        let plan = RoundtripPlan::build(&package_root, &self.formats, &self.strategies, |file| {
+            only_file
+                .as_ref()
+                .is_none_or(|only| only == file || package_root.join(only) == file)
+        })?;
+        let mut violations = plan.coverage_gaps();
+
+        let has_properties = plan.covered_types().next().is_some() && !plan.formats.is_empty();
+        let (stdout, stderr, exit_code) = if has_properties {
+            if !plan.write(&package_root)? {
+                warn!(
+                    "{:?} is hand-written; running it without regenerating",
+                    RoundtripPlan::test_path(&package_root)
+                );
+            }
+
+            let mut cmd = Command::new("cargo");
+            cmd.current_dir(&package_root)
+                .args(["test", "--test", TEST_TARGET]);
+            if let Some(cases) = input
+                .config
+                .tool_config
+                .get("cases")
+                .and_then(|v| v.as_u64())
+            {
+                cmd.env("PROPTEST_CASES", cases.to_string());
+            }
+            cmd.env(seed::PROPTEST_SEED_ENV, input.context.seed.to_string());
+            debug!("Executing command: {:?}", cmd);
+
+            let output = run_tool(&mut cmd, &input.context.cancellation)?;
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            violations.extend(plan.failures(&stdout));
+            (stdout, stderr, output.status.code().unwrap_or(-1))
+        } else {
+            info!(
+                "No serde types with round-trip coverage in {:?}",
+                package_root
+            );
+            (String::new(), String::new(), 0)
+        };
+
+        let status = if violations.iter().any(|v| v.severity == Severity::Error) || exit_code != 0 {
+            Status::Error
+        } else if violations.is_empty() {
+            Status::Success
+        } else {
+            Status::Warning
+        };
+        let execution_time = start_time.elapsed();
+
+        Ok(VerificationOutput {
+            status,
+            violations,
+            artifacts: vec![],
+            tool_output: ToolOutput {
+                tool: "serde-roundtrip".to_string(),
+                stdout,
+                stderr,
+                exit_code,
+                execution_time,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: execution_time,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: (plan.covered_types().count() * plan.formats.len()) as u32,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+        })
+    }
+}
+
+impl VerificationPlugin for SerdeRoundtripPlugin {
+    fn name(&self) -> &str {
+        "serde-roundtrip"
+    }
+
+    fn version(&self) -> &str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn supported_techniques(&self) -> Vec<Technique> {
+        vec![Technique::PropertyTests]
+    }
+
+    fn supported_versions(&self) -> VersionRange {
+        VersionRange {
+            min: Some(semver::Version::new(0, 1, 0)),
+            max: Some(semver::Version::new(1, 0, 0)),
+            requires_exact: None,
+        }
+    }
+
+    fn check_availability(&self) -> ferris_proof_core::Result<ToolInfo> {
+        let output = Command::new("cargo")
+            .arg("--version")
+            .output()
+            .map_err(|e| {
+                FerrisProofError::tool_not_found(
+                    "serde-roundtrip",
+                    format!("Cargo not found: {}", e),
+                )
+            })?;
+        if !output.status.success() {
+            return Err(FerrisProofError::tool_not_found(
+                "serde-roundtrip",
+                "Cargo is not working properly",
+            ));
+        }
+
+        Ok(ToolInfo {
+            name: "serde-roundtrip".to_string(),
+            version: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            path: PathBuf::from("cargo"),
+            available: true,
+            capabilities: self
+                .formats
+                .iter()
+                .map(|f| format!("roundtrip_{}", f.name()))
+                .collect(),
+        })
+    }
+
+    fn verify(&self, input: VerificationInput) -> ferris_proof_core::Result<VerificationOutput> {
+        if !self.initialized {
+            return Err(FerrisProofError::plugin(
+                "serde-roundtrip",
+                "Serde round-trip plugin not initialized",
+            ));
+        }
+
+        self.run_roundtrips(&input)
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
+    }
+
+    fn parse_output(&self, raw_output: &str) -> ferris_proof_core::Result<StructuredResult> {
+        let failures = raw_output.matches("... FAILED").count();
+        Ok(StructuredResult {
+            status: if failures > 0 {
+                Status::Error
+            } else {
+                Status::Success
+            },
+            violations: vec![],
+            statistics: json!({
+                "failures": failures,
+                "successes": raw_output.matches("... ok").count(),
+            }),
+            performance: PerformanceMetrics {
+                execution_time: Duration::from_millis(0),
+                memory_usage: 0,
+                cpu_usage: 0.0,
+                cache_hits: 0,
+            },
+        })
+    }
+
+    fn metadata(&self) -> PluginMetadata {
+        PluginMetadata {
+            name: "serde-roundtrip".to_string(),
+            version: self.version().to_string(),
+            description: "Generated serialize/deserialize round-trip properties for serde types"
+                .to_string(),
+            author: "FerrisProof Team".to_string(),
+            license: "MIT".to_string(),
+            homepage: None,
+            techniques: vec![Technique::PropertyTests],
+            supported_platforms: vec![
+                "linux".to_string(),
+                "macos".to_string(),
+                "windows".to_string(),
+            ],
+            dependencies: vec!["cargo".to_string()],
+            renderers: Vec::new(),
+            error_codes: Vec::new(),
+        }
+    }
+
+    fn initialize(&mut self, config: &serde_json::Value) -> ferris_proof_core::Result<()> {
+        if let Some(formats) = config
+            .get("serde_roundtrip")
+            .and_then(|c| c.get("formats"))
+            .and_then(|f| f.as_array())
+        {
+            self.formats = formats
+                .iter()
+                .filter_map(|f| f.as_str())
+                .map(|name| {
+                    SerdeFormat::parse(name).ok_or_else(|| {
+                        FerrisProofError::plugin(
+                            "serde-roundtrip",
+                            format!("Unknown round-trip format '{}'", name),
+                        )
+                    })
+                })
+                .collect::<ferris_proof_core::Result<_>>()?;
+        }
+
+        if let Some(strategies) = config
+            .get("generate")
+            .and_then(|g| g.get("proptest"))
+            .and_then(|p| p.get("strategies"))
+        {
+            self.strategies = serde_json::from_value(strategies.clone()).map_err(|e| {
+                FerrisProofError::plugin(
+                    "serde-roundtrip",
+                    format!("Invalid [generate.proptest.strategies]: {}", e),
+                )
+            })?;
+        }
+
+        self.initialized = true;
+        info!(
+            "Serde round-trip plugin initialized with formats: {:?}",
+            self.formats
+        );
+        Ok(())
+    }
+
+    fn cleanup(&mut self) -> ferris_proof_core::Result<()> {
+        self.initialized = false;
+        Ok(())
+    }
+}
+
+impl Default for SerdeRoundtripPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_package(root: &Path) {
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"wire-types\"\n\n[dev-dependencies]\nserde_json = \"1\"\nproptest = \"1\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/lib.rs"),
+            r#"pub mod msg;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, proptest_derive::Arbitrary)]
+pub struct Header {
+    pub id: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Opaque(u8);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Private {
+    x: u8,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Verified)]
+pub struct Account {
+    #[refine(range = 1..=120)]
+    pub age: u8,
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/msg.rs"),
+            r#"#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Message {
+    Ping,
+    Data(Vec<u8>),
+}
+
+impl Arbitrary for Message {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+    fn arbitrary_with(_: ()) -> Self::Strategy { unimplemented!() }
+}
+"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_plan_finds_serde_types_and_gaps() {
+        let temp_dir = TempDir::new().unwrap();
+        write_package(temp_dir.path());
+
+        let plan =
+            RoundtripPlan::build(temp_dir.path(), &SerdeFormat::ALL, &BTreeMap::new(), |_| {
+                true
+            })
+            .unwrap();
+        assert_eq!(plan.crate_name, "wire_types");
+        assert_eq!(plan.formats, vec![SerdeFormat::Json]);
+        assert_eq!(
+            plan.missing_formats,
+            vec![SerdeFormat::Bincode, SerdeFormat::Toml]
+        );
+
+        let covered: Vec<String> = plan
+            .covered_types()
+            .map(|t| t.path_in_crate(&plan.crate_name))
+            .collect();
+        assert_eq!(
+            covered,
+            vec![
+                "wire_types::Header",
+                "wire_types::Account",
+                "wire_types::msg::Message"
+            ]
+        );
+
+        let header = plan.types.iter().find(|t| t.name == "Header").unwrap();
+        assert_eq!(header.line, 4);
+
+        // Opaque lacks Arbitrary/PartialEq, Private is not public, plus two missing formats
+        let gaps = plan.coverage_gaps();
+        assert_eq!(gaps.len(), 4);
+        assert!(gaps.iter().all(|v| v.severity == Severity::Warning));
+
+        let rendered = plan.render();
+        assert!(rendered.starts_with(GENERATED_HEADER));
+        assert!(rendered.contains(
+            "fn roundtrip_json__msg__Message(value in any::<wire_types::msg::Message>())"
+        ));
+        assert!(!rendered.contains("bincode::"));
+    }
+
+    #[test]
+    fn test_failures_map_to_types() {
+        let temp_dir = TempDir::new().unwrap();
+        write_package(temp_dir.path());
+        let plan =
+            RoundtripPlan::build(temp_dir.path(), &SerdeFormat::ALL, &BTreeMap::new(), |_| {
+                true
+            })
+            .unwrap();
+
+        let output = "running 2 tests\n\
+                      test roundtrip_json__Header ... ok\n\
+                      test roundtrip_json__msg__Message ... FAILED\n";
+        let failures = plan.failures(output);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].severity, Severity::Error);
+        assert!(failures[0].message.contains("`Message`"));
+        assert!(failures[0].location.file.ends_with("src/msg.rs"));
+    }
+
+    #[test]
+    fn test_configured_strategies_cover_types() {
+        let temp_dir = TempDir::new().unwrap();
+        write_package(temp_dir.path());
+        std::fs::write(
+            temp_dir.path().join("src/lib.rs"),
+            r#"pub mod msg;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Amount(u64);
+"#,
+        )
+        .unwrap();
+
+        let strategies = BTreeMap::from([
+            (
+                "crate::Amount".to_string(),
+                "(0u64..1_000_000).prop_map(wire_types::Amount)".to_string(),
+            ),
+            (
+                "Message".to_string(),
+                "Just(wire_types::msg::Message::Ping)".to_string(),
+            ),
+        ]);
+        let plan = RoundtripPlan::build(temp_dir.path(), &SerdeFormat::ALL, &strategies, |_| true)
+            .unwrap();
+        assert_eq!(plan.covered_types().count(), 2);
+
+        let rendered = plan.render();
+        assert!(rendered.contains(
+            "fn roundtrip_json__Amount(value in (0u64..1_000_000).prop_map(wire_types::Amount))"
+        ));
+        assert!(rendered.contains(
+            "fn roundtrip_json__msg__Message(value in Just(wire_types::msg::Message::Ping))"
+        ));
+        assert!(rendered.contains("let decoded: wire_types::Amount ="));
+    }
+}
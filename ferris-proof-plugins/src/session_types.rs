@@ -0,0 +1,538 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Header written at the top of every generated Rust and graphviz file
+///
+/// Both formats accept `//` line comments, so one header marks either file as
+/// owned by FerrisProof; hand-written files are never overwritten.
+pub const GENERATED_HEADER: &str = "// Generated by ferris-proof. Edits may be overwritten.";
+
+/// Protocol state machine compiled into typestate Rust code
+///
+/// Protocols are read from JSON (`.json`) or from a line-based DSL:
+///
+/// ```text
+/// protocol Handshake
+/// initial Init
+/// state Init
+/// state Connected { peer_id: String }
+/// state Closed
+/// Init -> Connected : connect
+/// Connected -> Closed : close
+/// ```
+///
+/// Each state becomes a struct and each transition a method consuming the
+/// source state. Fields of the target state are carried over from the source
+/// state when it has a field of the same name, otherwise they become method
+/// parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Protocol {
+    pub name: String,
+    /// Defaults to the first declared state
+    #[serde(default)]
+    pub initial: Option<String>,
+    pub states: Vec<State>,
+    #[serde(default)]
+    pub transitions: Vec<Transition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct State {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Transition {
+    pub from: String,
+    pub to: String,
+    /// Method name on the source state
+    pub name: String,
+}
+
+impl Protocol {
+    /// Read a protocol definition, choosing JSON or the DSL by file extension
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read protocol {:?}", path))?;
+        let protocol = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content)
+                .with_context(|| format!("Invalid protocol JSON in {:?}", path))?
+        } else {
+            Self::parse_dsl(&content).with_context(|| format!("Invalid protocol {:?}", path))?
+        };
+        protocol.validate()?;
+        Ok(protocol)
+    }
+
+    /// Parse the line-based protocol DSL; `#` starts a comment
+    pub fn parse_dsl(content: &str) -> Result<Self> {
+        let mut name = None;
+        let mut initial = None;
+        let mut states = Vec::new();
+        let mut transitions = Vec::new();
+
+        for (index, raw_line) in content.lines().enumerate() {
+            let line = raw_line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let line_error = |message: &str| anyhow!("line {}: {}", index + 1, message);
+
+            if let Some(rest) = line.strip_prefix("protocol ") {
+                name = Some(rest.trim().to_string());
+            } else if let Some(rest) = line.strip_prefix("initial ") {
+                initial = Some(rest.trim().to_string());
+            } else if let Some(rest) = line.strip_prefix("state ") {
+                let (state_name, fields) = match rest.split_once('{') {
+                    Some((state_name, fields)) => {
+                        let fields = fields
+                            .strip_suffix('}')
+                            .ok_or_else(|| line_error("expected `}` after state fields"))?;
+                        (
+                            state_name,
+                            parse_fields(fields).map_err(|e| line_error(&e))?,
+                        )
+                    }
+                    None => (rest, Vec::new()),
+                };
+                states.push(State {
+                    name: state_name.trim().to_string(),
+                    fields,
+                });
+            } else if let Some((from, rest)) = line.split_once("->") {
+                let (to, method) = rest
+                    .split_once(':')
+                    .ok_or_else(|| line_error("expected `From -> To : method`"))?;
+                transitions.push(Transition {
+                    from: from.trim().to_string(),
+                    to: to.trim().to_string(),
+                    name: method.trim().to_string(),
+                });
+            } else {
+                return Err(line_error(&format!("unrecognised declaration `{}`", line)));
+            }
+        }
+
+        Ok(Protocol {
+            name: name.ok_or_else(|| anyhow!("missing `protocol <Name>` declaration"))?,
+            initial,
+            states,
+            transitions,
+        })
+    }
+
+    /// Check names are valid identifiers and transitions reference declared states
+    pub fn validate(&self) -> Result<()> {
+        check_ident(&self.name, "protocol name")?;
+        if self.states.is_empty() {
+            bail!("Protocol '{}' declares no states", self.name);
+        }
+
+        let mut state_names = HashSet::new();
+        for state in &self.states {
+            check_ident(&state.name, "state name")?;
+            if !state_names.insert(state.name.as_str()) {
+                bail!("State '{}' is declared twice", state.name);
+            }
+            let mut field_names = HashSet::new();
+            for field in &state.fields {
+                check_ident(&field.name, "field name")?;
+                if !field_names.insert(field.name.as_str()) {
+                    bail!(
+                        "State '{}' declares field '{}' twice",
+                        state.name,
+                        field.name
+                    );
+                }
+            }
+        }
+
+        let initial = self.initial_state();
+        if !state_names.contains(initial) {
+            bail!("Initial state '{}' is not declared", initial);
+        }
+
+        let mut methods = HashSet::new();
+        for transition in &self.transitions {
+            check_ident(&transition.name, "transition name")?;
+            for state in [&transition.from, &transition.to] {
+                if !state_names.contains(state.as_str()) {
+                    bail!(
+                        "Transition '{}' references undeclared state '{}'",
+                        transition.name,
+                        state
+                    );
+                }
+            }
+            if !methods.insert((transition.from.as_str(), transition.name.as_str())) {
+                bail!(
+                    "State '{}' has more than one transition named '{}'",
+                    transition.from,
+                    transition.name
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn initial_state(&self) -> &str {
+        self.initial
+            .as_deref()
+            .unwrap_or_else(|| self.states.first().map_or("", |s| s.name.as_str()))
+    }
+
+    /// States without outgoing transitions
+    pub fn terminal_states(&self) -> BTreeSet<&str> {
+        let sources: HashSet<&str> = self.transitions.iter().map(|t| t.from.as_str()).collect();
+        self.states
+            .iter()
+            .map(|s| s.name.as_str())
+            .filter(|name| !sources.contains(name))
+            .collect()
+    }
+
+    fn state(&self, name: &str) -> Option<&State> {
+        self.states.iter().find(|s| s.name == name)
+    }
+
+    /// File stem for generated files, e.g. `handshake` for `Handshake`
+    pub fn module_name(&self) -> String {
+        let mut module = String::new();
+        for (i, c) in self.name.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                module.push('_');
+            }
+            module.extend(c.to_lowercase());
+        }
+        module
+    }
+
+    /// Render typestate Rust code: one struct per state, consuming transitions
+    pub fn render_rust(&self) -> String {
+        let mut out = String::new();
+        out.push_str(GENERATED_HEADER);
+        out.push('\n');
+
+        let terminal = self.terminal_states();
+        for state in &self.states {
+            out.push_str(&format!(
+                "\n/// `{}` state of the `{}` protocol{}\n",
+                state.name,
+                self.name,
+                if terminal.contains(state.name.as_str()) {
+                    " (terminal)"
+                } else {
+                    ""
+                }
+            ));
+            out.push_str("#[derive(Debug)]\n");
+            if state.fields.is_empty() {
+                out.push_str(&format!("pub struct {};\n", state.name));
+            } else {
+                out.push_str(&format!("pub struct {} {{\n", state.name));
+                for field in &state.fields {
+                    out.push_str(&format!("    pub {}: {},\n", field.name, field.ty));
+                }
+                out.push_str("}\n");
+            }
+        }
+
+        let initial = self.initial_state();
+        if let Some(state) = self.state(initial) {
+            out.push_str(&format!("\nimpl {} {{\n", initial));
+            out.push_str(&format!("    /// Start a new `{}` session\n", self.name));
+            out.push_str(&format!(
+                "    pub fn start({}) -> Self {{\n        {}\n    }}\n}}\n",
+                params(&state.fields),
+                construct(state, |field| field.name.clone())
+            ));
+        }
+
+        for state in &self.states {
+            let outgoing: Vec<&Transition> = self
+                .transitions
+                .iter()
+                .filter(|t| t.from == state.name)
+                .collect();
+            if outgoing.is_empty() {
+                continue;
+            }
+
+            out.push_str(&format!("\nimpl {} {{\n", state.name));
+            for (i, transition) in outgoing.iter().enumerate() {
+                let Some(target) = self.state(&transition.to) else {
+                    continue;
+                };
+                let carried = |field: &Field| state.fields.iter().any(|f| f == field);
+                let new_fields: Vec<Field> = target
+                    .fields
+                    .iter()
+                    .filter(|f| !carried(f))
+                    .cloned()
+                    .collect();
+
+                if i > 0 {
+                    out.push('\n');
+                }
+                out.push_str(&format!(
+                    "    /// `{}` -> `{}`\n",
+                    transition.from, transition.to
+                ));
+                out.push_str(&format!(
+                    "    pub fn {}(self{}) -> {} {{\n        {}\n    }}\n",
+                    transition.name,
+                    if new_fields.is_empty() {
+                        String::new()
+                    } else {
+                        format!(", {}", params(&new_fields))
+                    },
+                    target.name,
+                    construct(target, |field| if carried(field) {
+                        format!("self.{}", field.name)
+                    } else {
+                        field.name.clone()
+                    })
+                ));
+            }
+            out.push_str("}\n");
+        }
+
+        out
+    }
+
+    /// Render the protocol as a graphviz digraph
+    pub fn render_dot(&self) -> String {
+        let mut out = String::new();
+        out.push_str(GENERATED_HEADER);
+        out.push('\n');
+        out.push_str(&format!("digraph {} {{\n", self.name));
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [shape=circle];\n");
+        out.push_str("    __start [shape=point];\n");
+        for state in self.terminal_states() {
+            out.push_str(&format!("    {} [shape=doublecircle];\n", state));
+        }
+        out.push_str(&format!("    __start -> {};\n", self.initial_state()));
+        for transition in &self.transitions {
+            out.push_str(&format!(
+                "    {} -> {} [label=\"{}\"];\n",
+                transition.from, transition.to, transition.name
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Default locations for generated code and diagram
+///
+/// Code goes to `src/<protocol>.rs` and the diagram next to the definition,
+/// unless `output_dir` is given, in which case both are written there.
+pub fn output_paths(
+    protocol: &Protocol,
+    spec_path: &Path,
+    project_root: &Path,
+    output_dir: Option<&Path>,
+) -> (PathBuf, PathBuf) {
+    let module = protocol.module_name();
+    match output_dir {
+        Some(dir) => (
+            dir.join(format!("{}.rs", module)),
+            dir.join(format!("{}.dot", module)),
+        ),
+        None => (
+            project_root.join("src").join(format!("{}.rs", module)),
+            spec_path.with_file_name(format!("{}.dot", module)),
+        ),
+    }
+}
+
+/// Write generated content, refusing to replace hand-written files
+///
+/// Returns `false` when an existing file without the generated header was left
+/// untouched.
+pub fn write_generated(path: &Path, content: &str) -> Result<bool> {
+    if path.exists() {
+        let existing = std::fs::read_to_string(path)?;
+        if !existing.starts_with(GENERATED_HEADER) {
+            debug!("Keeping hand-written file at {:?}", path);
+            return Ok(false);
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content).map_err(|e| anyhow!("Failed to write {:?}: {}", path, e))?;
+    Ok(true)
+}
+
+fn parse_fields(fields: &str) -> std::result::Result<Vec<Field>, String> {
+    // Split on top-level commas only so `HashMap<String, u64>` stays intact
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+    for c in fields.chars() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current);
+
+    parts
+        .iter()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (name, ty) = part
+                .split_once(':')
+                .ok_or_else(|| format!("expected `name: Type`, found `{}`", part))?;
+            Ok(Field {
+                name: name.trim().to_string(),
+                ty: ty.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+fn check_ident(name: &str, what: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        bail!("Invalid {} '{}': expected a Rust identifier", what, name);
+    }
+    Ok(())
+}
+
+fn params(fields: &[Field]) -> String {
+    fields
+        .iter()
+        .map(|f| format!("{}: {}", f.name, f.ty))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn construct(state: &State, value: impl Fn(&Field) -> String) -> String {
+    if state.fields.is_empty() {
+        return state.name.clone();
+    }
+    let fields: Vec<String> = state
+        .fields
+        .iter()
+        .map(|field| {
+            let value = value(field);
+            if value == field.name {
+                value
+            } else {
+                format!("{}: {}", field.name, value)
+            }
+        })
+        .collect();
+    format!("{} {{ {} }}", state.name, fields.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HANDSHAKE: &str = "# Connection handshake
+protocol Handshake
+state Init
+state Connected { peer_id: String }
+state Authenticated { peer_id: String, user_id: u64 }
+state Closed
+Init -> Connected : connect
+Connected -> Authenticated : authenticate
+Connected -> Closed : close
+Authenticated -> Closed : close
+";
+
+    #[test]
+    fn test_dsl_renders_typestate_code_and_diagram() {
+        let protocol = Protocol::parse_dsl(HANDSHAKE).unwrap();
+        protocol.validate().unwrap();
+        assert_eq!(protocol.initial_state(), "Init");
+        assert_eq!(protocol.module_name(), "handshake");
+        assert_eq!(protocol.terminal_states(), BTreeSet::from(["Closed"]));
+
+        let rust = protocol.render_rust();
+        assert!(rust.starts_with(GENERATED_HEADER));
+        assert!(rust.contains("pub struct Init;\n"));
+        assert!(rust.contains("    pub fn start() -> Self {\n        Init\n    }"));
+        assert!(rust.contains(
+            "    pub fn connect(self, peer_id: String) -> Connected {\n        Connected { peer_id }\n    }"
+        ));
+        assert!(rust.contains(
+            "pub fn authenticate(self, user_id: u64) -> Authenticated {\n        Authenticated { peer_id: self.peer_id, user_id }"
+        ));
+        assert!(rust.contains("/// `Closed` state of the `Handshake` protocol (terminal)"));
+        assert!(!rust.contains("impl Closed"));
+
+        let dot = protocol.render_dot();
+        assert!(dot.contains("digraph Handshake {"));
+        assert!(dot.contains("__start -> Init;"));
+        assert!(dot.contains("Closed [shape=doublecircle];"));
+        assert!(dot.contains("Connected -> Authenticated [label=\"authenticate\"];"));
+    }
+
+    #[test]
+    fn test_json_and_invalid_protocols() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("door.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "name": "Door",
+                "initial": "Closed",
+                "states": [{ "name": "Open" }, { "name": "Closed" }],
+                "transitions": [
+                    { "from": "Closed", "to": "Open", "name": "open" },
+                    { "from": "Open", "to": "Closed", "name": "close" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let protocol = Protocol::load(&path).unwrap();
+        assert_eq!(protocol.initial_state(), "Closed");
+        assert!(protocol.terminal_states().is_empty());
+
+        let undeclared = Protocol::parse_dsl("protocol P\nstate A\nA -> B : go\n").unwrap();
+        assert!(undeclared
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("undeclared state 'B'"));
+        assert!(Protocol::parse_dsl("state A\n").is_err());
+        assert!(Protocol::parse_dsl("protocol P\nstate A { x }\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_fields_keeps_generic_types() {
+        let fields = parse_fields("peers: HashMap<String, u64>, id: u32").unwrap();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].ty, "HashMap<String, u64>");
+    }
+}
@@ -0,0 +1,1171 @@
+use crate::serde_roundtrip::{declaration_line, rust_files};
+use anyhow::{anyhow, Context, Result};
+use ferris_proof_core::{
+    plugins::{
+        run_tool, CancellationToken, PerformanceMetrics, PluginMetadata, StructuredResult,
+        ToolInfo, VerificationInput, VerificationOutput, VerificationPlugin, VersionRange,
+    },
+    types::*,
+    verification::Target,
+    FerrisProofError,
+};
+use quote::ToTokens;
+use regex::Regex;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+use syn::{BinOp, Expr, FnArg, ImplItem, Item, Lit, Pat, Stmt, Type, UnOp};
+use tracing::{debug, info};
+
+/// Name the return value goes by in `ensures` clauses
+const RESULT: &str = "result";
+
+/// SMT sort of a contract variable, with the range of its Rust type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sort {
+    Bool,
+    Int {
+        min: Option<i128>,
+        max: Option<i128>,
+    },
+}
+
+impl Sort {
+    /// Sort of a primitive integer or `bool` type, looking through references
+    pub fn of(ty: &Type) -> Option<Self> {
+        let ty = match ty {
+            Type::Reference(reference) => &*reference.elem,
+            Type::Paren(paren) => &*paren.elem,
+            ty => ty,
+        };
+        let Type::Path(path) = ty else {
+            return None;
+        };
+        // `u128::MAX` does not fit in an `i128`, so it is left unbounded above
+        let unsigned = |bits: u32| Sort::Int {
+            min: Some(0),
+            max: (bits < 128).then(|| (1i128 << bits) - 1),
+        };
+        let signed = |bits: u32| Sort::Int {
+            min: Some(i128::MIN >> (128 - bits)),
+            max: Some(i128::MAX >> (128 - bits)),
+        };
+
+        Some(match path.path.get_ident()?.to_string().as_str() {
+            "bool" => Sort::Bool,
+            "u8" => unsigned(8),
+            "u16" => unsigned(16),
+            "u32" => unsigned(32),
+            "u64" | "usize" => unsigned(64),
+            "u128" => unsigned(128),
+            "i8" => signed(8),
+            "i16" => signed(16),
+            "i32" => signed(32),
+            "i64" | "isize" => signed(64),
+            "i128" => signed(128),
+            _ => return None,
+        })
+    }
+
+    fn kind(self) -> Kind {
+        match self {
+            Sort::Bool => Kind::Bool,
+            Sort::Int { .. } => Kind::Int,
+        }
+    }
+
+    /// Assertions keeping `var` within the range of its Rust type
+    fn bounds(self, var: &str) -> Vec<String> {
+        let Sort::Int { min, max } = self else {
+            return Vec::new();
+        };
+        let mut bounds = Vec::new();
+        if let Some(min) = min {
+            bounds.push(format!("(<= {} {})", int_literal(min), symbol(var)));
+        }
+        if let Some(max) = max {
+            bounds.push(format!("(<= {} {})", symbol(var), int_literal(max)));
+        }
+        bounds
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Bool,
+    Int,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Bool => "Bool",
+            Kind::Int => "Int",
+        }
+    }
+}
+
+/// A `requires`/`ensures` predicate with its SMT-LIB translation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clause {
+    /// Rust source of the predicate, for messages
+    pub source: String,
+    /// SMT-LIB term, or why the predicate is outside the supported fragment
+    pub term: Result<String, String>,
+}
+
+/// `#[contract(requires = ..., ensures = ...)]` on a function
+#[derive(Debug, Clone)]
+pub struct FunctionContract {
+    /// Function name, `Type::method` for methods
+    pub function: String,
+    pub location: Location,
+    /// Parameters with an integer or `bool` type
+    pub params: Vec<(String, Sort)>,
+    pub result: Option<(String, Sort)>,
+    pub requires: Vec<Clause>,
+    pub ensures: Vec<Clause>,
+    /// Body as an SMT-LIB term defining `result`
+    pub body: Result<String, String>,
+}
+
+/// Something the solver is asked to prove about a contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Obligation {
+    pub description: String,
+    /// Script asserting the negation; `unsat` means the obligation holds
+    pub script: Result<String, String>,
+}
+
+impl FunctionContract {
+    /// Contracts on the functions of a source file, including methods and inline modules
+    pub fn parse_file(path: &Path, content: &str) -> Result<Vec<Self>> {
+        let ast =
+            syn::parse_file(content).with_context(|| format!("Failed to parse {:?}", path))?;
+        let mut contracts = Vec::new();
+        collect(&ast.items, "", path, content, &mut contracts);
+        Ok(contracts)
+    }
+
+    /// Declarations of the parameters, constrained to their types' ranges
+    fn preamble(&self) -> String {
+        let mut script = String::from("(set-option :produce-models true)\n");
+        for (name, sort) in &self.params {
+            script.push_str(&format!(
+                "(declare-const {} {})\n",
+                symbol(name),
+                sort.kind().name()
+            ));
+            for bound in sort.bounds(name) {
+                script.push_str(&format!("(assert {})\n", bound));
+            }
+        }
+        script
+    }
+
+    /// Script checking the preconditions can hold at all
+    ///
+    /// `None` when a precondition is outside the supported fragment.
+    pub fn precondition_script(&self) -> Option<String> {
+        let mut script = self.preamble();
+        for clause in &self.requires {
+            script.push_str(&format!("(assert {})\n", clause.term.as_ref().ok()?));
+        }
+        script.push_str("(check-sat)\n");
+        Some(script)
+    }
+
+    /// One obligation per `ensures` clause, plus the return value fitting its type
+    pub fn obligations(&self) -> Vec<Obligation> {
+        let mut goals: Vec<(String, Result<String, String>)> = self
+            .ensures
+            .iter()
+            .map(|clause| (format!("`{}`", clause.source), clause.term.clone()))
+            .collect();
+        if let Some((ty, sort)) = &self.result {
+            let bounds = sort.bounds(RESULT);
+            if !bounds.is_empty() && self.body.is_ok() {
+                goals.push((
+                    format!("a result within `{}`", ty),
+                    Ok(format!("(and {})", bounds.join(" "))),
+                ));
+            }
+        }
+
+        goals
+            .into_iter()
+            .map(|(description, goal)| Obligation {
+                description,
+                script: self.negated_goal(goal),
+            })
+            .collect()
+    }
+
+    fn negated_goal(&self, goal: Result<String, String>) -> Result<String, String> {
+        let goal = goal?;
+        let body = self.body.clone()?;
+        let (_, sort) = self
+            .result
+            .as_ref()
+            .ok_or_else(|| "the function returns no value".to_string())?;
+
+        let mut script = self.preamble();
+        for clause in &self.requires {
+            script.push_str(&format!("(assert {})\n", clause.term.clone()?));
+        }
+        script.push_str(&format!(
+            "(declare-const {} {})\n(assert (= {} {}))\n(assert (not {}))\n(check-sat)\n",
+            symbol(RESULT),
+            sort.kind().name(),
+            symbol(RESULT),
+            body,
+            goal
+        ));
+        Ok(script)
+    }
+
+    /// Query for the counterexample values after a `sat` answer
+    pub fn model_query(&self) -> String {
+        let mut names: Vec<String> = self.params.iter().map(|(name, _)| symbol(name)).collect();
+        if self.result.is_some() && self.body.is_ok() {
+            names.push(symbol(RESULT));
+        }
+        if names.is_empty() {
+            String::new()
+        } else {
+            format!("(get-value ({}))\n", names.join(" "))
+        }
+    }
+}
+
+fn collect(
+    items: &[Item],
+    prefix: &str,
+    path: &Path,
+    content: &str,
+    contracts: &mut Vec<FunctionContract>,
+) {
+    for item in items {
+        match item {
+            Item::Fn(function) => {
+                if let Some(contract) = contract_of(
+                    &function.attrs,
+                    &function.sig,
+                    &function.block,
+                    prefix,
+                    path,
+                    content,
+                ) {
+                    contracts.push(contract);
+                }
+            }
+            Item::Impl(imp) => {
+                let Type::Path(ty) = &*imp.self_ty else {
+                    continue;
+                };
+                let Some(segment) = ty.path.segments.last() else {
+                    continue;
+                };
+                let prefix = format!("{}{}::", prefix, segment.ident);
+                for impl_item in &imp.items {
+                    if let ImplItem::Fn(method) = impl_item {
+                        if let Some(contract) = contract_of(
+                            &method.attrs,
+                            &method.sig,
+                            &method.block,
+                            &prefix,
+                            path,
+                            content,
+                        ) {
+                            contracts.push(contract);
+                        }
+                    }
+                }
+            }
+            Item::Mod(module) => {
+                if let Some((_, nested)) = &module.content {
+                    let prefix = format!("{}{}::", prefix, module.ident);
+                    collect(nested, &prefix, path, content, contracts);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn contract_of(
+    attrs: &[syn::Attribute],
+    sig: &syn::Signature,
+    block: &syn::Block,
+    prefix: &str,
+    path: &Path,
+    content: &str,
+) -> Option<FunctionContract> {
+    let mut requires = Vec::new();
+    let mut ensures = Vec::new();
+    for attr in attrs.iter().filter(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "contract")
+    }) {
+        let parsed = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("requires") {
+                requires.push(meta.value()?.parse::<Expr>()?);
+            } else if meta.path.is_ident("ensures") {
+                ensures.push(meta.value()?.parse::<Expr>()?);
+            }
+            Ok(())
+        });
+        if let Err(e) = parsed {
+            debug!("Skipping malformed contract on `{}`: {}", sig.ident, e);
+            return None;
+        }
+    }
+    if requires.is_empty() && ensures.is_empty() {
+        return None;
+    }
+
+    let params: Vec<(String, Sort)> = sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(typed) => match &*typed.pat {
+                Pat::Ident(ident) => Some((ident.ident.to_string(), Sort::of(&typed.ty)?)),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    let result = match &sig.output {
+        syn::ReturnType::Type(_, ty) => {
+            Sort::of(ty).map(|sort| (source(&ty.to_token_stream()), sort))
+        }
+        syn::ReturnType::Default => None,
+    };
+
+    let scope: Vec<(String, Kind)> = params
+        .iter()
+        .map(|(name, sort)| (name.clone(), sort.kind()))
+        .collect();
+    let mut with_result = scope.clone();
+    if let Some((_, sort)) = &result {
+        with_result.push((RESULT.to_string(), sort.kind()));
+    }
+    let clause = |expr: &Expr, scope: &[(String, Kind)]| Clause {
+        source: source(&expr.to_token_stream()),
+        term: predicate(expr, scope),
+    };
+
+    let body = match &result {
+        Some((_, sort)) => block_term(&block.stmts, &scope).and_then(|(term, kind)| {
+            if kind == sort.kind() {
+                Ok(term)
+            } else {
+                Err("the body does not produce the return type".to_string())
+            }
+        }),
+        None => Err("the return type is not an integer or `bool`".to_string()),
+    };
+
+    let name = sig.ident.to_string();
+    Some(FunctionContract {
+        function: format!("{}{}", prefix, name),
+        location: Location::point(
+            path.to_path_buf(),
+            declaration_line(content, "fn", &name) as u32,
+            1,
+        ),
+        params,
+        result,
+        requires: requires.iter().map(|expr| clause(expr, &scope)).collect(),
+        ensures: ensures
+            .iter()
+            .map(|expr| clause(expr, &with_result))
+            .collect(),
+        body,
+    })
+}
+
+fn predicate(expr: &Expr, scope: &[(String, Kind)]) -> Result<String, String> {
+    match term(expr, scope)? {
+        (term, Kind::Bool) => Ok(term),
+        (_, Kind::Int) => Err(format!(
+            "`{}` is not a boolean condition",
+            source(&expr.to_token_stream())
+        )),
+    }
+}
+
+/// SMT-LIB term for a block of `let` bindings ending in an expression
+fn block_term(stmts: &[Stmt], scope: &[(String, Kind)]) -> Result<(String, Kind), String> {
+    let Some((last, init)) = stmts.split_last() else {
+        return Err("the body is empty".to_string());
+    };
+    let mut scope = scope.to_vec();
+    let mut bindings = Vec::new();
+    for stmt in init {
+        match stmt {
+            Stmt::Local(local) if local.init.as_ref().is_some_and(|i| i.diverge.is_none()) => {
+                let pat = match &local.pat {
+                    Pat::Type(typed) => &*typed.pat,
+                    pat => pat,
+                };
+                let Pat::Ident(ident) = pat else {
+                    return Err(unsupported(&local.pat.to_token_stream()));
+                };
+                let init = local.init.as_ref().map(|init| &*init.expr).unwrap();
+                let (value, kind) = term(init, &scope)?;
+                let name = ident.ident.to_string();
+                bindings.push(format!("(let (({} {}))", symbol(&name), value));
+                scope.push((name, kind));
+            }
+            // Assertions do not change the value
+            Stmt::Macro(mac)
+                if mac.mac.path.is_ident("assert") || mac.mac.path.is_ident("debug_assert") => {}
+            stmt => return Err(unsupported(&stmt.to_token_stream())),
+        }
+    }
+    let Stmt::Expr(tail, None) = last else {
+        return Err(unsupported(&last.to_token_stream()));
+    };
+
+    let (mut term, kind) = term(tail, &scope)?;
+    for binding in bindings.iter().rev() {
+        term = format!("{} {})", binding, term);
+    }
+    Ok((term, kind))
+}
+
+/// SMT-LIB term for linear arithmetic and boolean expressions
+fn term(expr: &Expr, scope: &[(String, Kind)]) -> Result<(String, Kind), String> {
+    let expect = |expr: &Expr, kind: Kind| -> Result<String, String> {
+        let (term, actual) = term(expr, scope)?;
+        if actual == kind {
+            Ok(term)
+        } else {
+            Err(format!(
+                "`{}` is not {}",
+                source(&expr.to_token_stream()),
+                if kind == Kind::Bool {
+                    "a boolean"
+                } else {
+                    "an integer"
+                }
+            ))
+        }
+    };
+
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int
+                .base10_parse::<i128>()
+                .map(|n| (int_literal(n), Kind::Int))
+                .map_err(|e| e.to_string()),
+            Lit::Bool(b) => Ok((b.value.to_string(), Kind::Bool)),
+            _ => Err(unsupported(&expr.to_token_stream())),
+        },
+        Expr::Path(path) => {
+            let ident = path
+                .path
+                .get_ident()
+                .ok_or_else(|| unsupported(&expr.to_token_stream()))?
+                .to_string();
+            scope
+                .iter()
+                .rev()
+                .find(|(name, _)| *name == ident)
+                .map(|(name, kind)| (symbol(name), *kind))
+                .ok_or_else(|| {
+                    format!("`{}` is not an integer or `bool` parameter in scope", ident)
+                })
+        }
+        Expr::Paren(paren) => term(&paren.expr, scope),
+        Expr::Group(group) => term(&group.expr, scope),
+        Expr::Block(block) => block_term(&block.block.stmts, scope),
+        Expr::Unary(unary) => match unary.op {
+            UnOp::Neg(_) => Ok((
+                format!("(- {})", expect(&unary.expr, Kind::Int)?),
+                Kind::Int,
+            )),
+            UnOp::Not(_) => Ok((
+                format!("(not {})", expect(&unary.expr, Kind::Bool)?),
+                Kind::Bool,
+            )),
+            UnOp::Deref(_) => term(&unary.expr, scope),
+            _ => Err(unsupported(&expr.to_token_stream())),
+        },
+        Expr::Binary(binary) => {
+            let int = |op: &str| -> Result<(String, Kind), String> {
+                Ok((
+                    format!(
+                        "({} {} {})",
+                        op,
+                        expect(&binary.left, Kind::Int)?,
+                        expect(&binary.right, Kind::Int)?
+                    ),
+                    Kind::Int,
+                ))
+            };
+            let compare = |op: &str| -> Result<(String, Kind), String> {
+                Ok((
+                    format!(
+                        "({} {} {})",
+                        op,
+                        expect(&binary.left, Kind::Int)?,
+                        expect(&binary.right, Kind::Int)?
+                    ),
+                    Kind::Bool,
+                ))
+            };
+            let logic = |op: &str| -> Result<(String, Kind), String> {
+                Ok((
+                    format!(
+                        "({} {} {})",
+                        op,
+                        expect(&binary.left, Kind::Bool)?,
+                        expect(&binary.right, Kind::Bool)?
+                    ),
+                    Kind::Bool,
+                ))
+            };
+
+            match binary.op {
+                BinOp::Add(_) => int("+"),
+                BinOp::Sub(_) => int("-"),
+                BinOp::Mul(_) => int("*"),
+                BinOp::Div(_) | BinOp::Rem(_) => {
+                    let left = expect(&binary.left, Kind::Int)?;
+                    let right = expect(&binary.right, Kind::Int)?;
+                    // Rust rounds towards zero, SMT-LIB `div` towards negative infinity
+                    let quotient = format!(
+                        "(ite (>= {l} 0) (div {l} {r}) (- (div (- {l}) {r})))",
+                        l = left,
+                        r = right
+                    );
+                    Ok(match binary.op {
+                        BinOp::Div(_) => (quotient, Kind::Int),
+                        _ => (
+                            format!("(- {} (* {} {}))", left, right, quotient),
+                            Kind::Int,
+                        ),
+                    })
+                }
+                BinOp::Lt(_) => compare("<"),
+                BinOp::Le(_) => compare("<="),
+                BinOp::Gt(_) => compare(">"),
+                BinOp::Ge(_) => compare(">="),
+                BinOp::Eq(_) | BinOp::Ne(_) => {
+                    let (left, kind) = term(&binary.left, scope)?;
+                    let right = expect(&binary.right, kind)?;
+                    let op = if matches!(binary.op, BinOp::Eq(_)) {
+                        "="
+                    } else {
+                        "distinct"
+                    };
+                    Ok((format!("({} {} {})", op, left, right), Kind::Bool))
+                }
+                BinOp::And(_) | BinOp::BitAnd(_) => logic("and"),
+                BinOp::Or(_) | BinOp::BitOr(_) => logic("or"),
+                _ => Err(unsupported(&expr.to_token_stream())),
+            }
+        }
+        Expr::If(if_expr) => {
+            let Some((_, else_branch)) = &if_expr.else_branch else {
+                return Err(unsupported(&expr.to_token_stream()));
+            };
+            let condition = expect(&if_expr.cond, Kind::Bool)?;
+            let (then, kind) = block_term(&if_expr.then_branch.stmts, scope)?;
+            let otherwise = expect(else_branch, kind)?;
+            Ok((format!("(ite {} {} {})", condition, then, otherwise), kind))
+        }
+        Expr::MethodCall(call) => {
+            let method = call.method.to_string();
+            if !matches!(
+                (method.as_str(), call.args.len()),
+                ("abs", 0) | ("min" | "max", 1)
+            ) {
+                return Err(unsupported(&expr.to_token_stream()));
+            }
+            let receiver = expect(&call.receiver, Kind::Int)?;
+            match (method.as_str(), call.args.len()) {
+                ("abs", 0) => Ok((
+                    format!("(ite (>= {r} 0) {r} (- {r}))", r = receiver),
+                    Kind::Int,
+                )),
+                ("min" | "max", 1) => {
+                    let other = expect(&call.args[0], Kind::Int)?;
+                    let op = if method == "min" { "<=" } else { ">=" };
+                    Ok((
+                        format!("(ite ({} {r} {o}) {r} {o})", op, r = receiver, o = other),
+                        Kind::Int,
+                    ))
+                }
+                _ => Err(unsupported(&expr.to_token_stream())),
+            }
+        }
+        _ => Err(unsupported(&expr.to_token_stream())),
+    }
+}
+
+fn unsupported(tokens: &proc_macro2::TokenStream) -> String {
+    format!("`{}` is outside the supported fragment", source(tokens))
+}
+
+/// Token text with the spacing `quote` adds around punctuation removed
+fn source(tokens: &proc_macro2::TokenStream) -> String {
+    tokens
+        .to_string()
+        .replace(" . ", ".")
+        .replace(" (", "(")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(" :: ", "::")
+}
+
+/// Quoted SMT-LIB symbol, so Rust names never clash with SMT-LIB keywords
+fn symbol(name: &str) -> String {
+    format!("|{}|", name)
+}
+
+fn int_literal(n: i128) -> String {
+    if n < 0 {
+        format!("(- {})", n.unsigned_abs())
+    } else {
+        n.to_string()
+    }
+}
+
+/// Solver answer to a `check-sat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Sat,
+    Unsat,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn parse(stdout: &str) -> Option<Self> {
+        match stdout
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())?
+        {
+            "sat" => Some(Verdict::Sat),
+            "unsat" => Some(Verdict::Unsat),
+            "unknown" | "timeout" => Some(Verdict::Unknown),
+            _ => None,
+        }
+    }
+}
+
+/// `name = value` pairs from a `get-value` response
+pub fn model_values(stdout: &str) -> Vec<(String, String)> {
+    let value =
+        Regex::new(r"\(\|?([A-Za-z_][A-Za-z0-9_]*)\|?\s+(\(-\s*\d+\)|-?\d+|true|false)\)").unwrap();
+    value
+        .captures_iter(stdout)
+        .map(|caps| {
+            let raw = &caps[2];
+            let value = match raw.strip_prefix("(-") {
+                Some(rest) => format!("-{}", rest.trim_end_matches(')').trim()),
+                None => raw.to_string(),
+            };
+            (caps[1].to_string(), value)
+        })
+        .collect()
+}
+
+/// Refinement checking by translating `#[contract]` predicates to SMT queries
+///
+/// Preconditions are checked for satisfiability; postconditions, and the
+/// return value fitting its type, are proved against the function body with
+/// an SMT-LIB solver (`z3` by default). Arithmetic is over unbounded
+/// integers, so only the returned value is range checked.
+pub struct SmtPlugin {
+    solver_path: PathBuf,
+    timeout: Option<u64>,
+    initialized: bool,
+}
+
+impl SmtPlugin {
+    pub fn new() -> Self {
+        Self {
+            solver_path: PathBuf::from("z3"),
+            timeout: None,
+            initialized: false,
+        }
+    }
+
+    fn solve(
+        &self,
+        script: &str,
+        timeout: Option<u64>,
+        cancellation: &CancellationToken,
+    ) -> Result<String> {
+        let mut file = tempfile::Builder::new().suffix(".smt2").tempfile()?;
+        std::io::Write::write_all(&mut file, script.as_bytes())?;
+
+        let mut cmd = Command::new(&self.solver_path);
+        cmd.arg("-smt2");
+        if let Some(timeout) = timeout {
+            cmd.arg(format!("-T:{}", timeout));
+        }
+        cmd.arg(file.path());
+        debug!("Executing command: {:?}", cmd);
+
+        let output = run_tool(&mut cmd, cancellation)
+            .map_err(|e| anyhow!("Failed to execute SMT solver: {}", e))?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn check_contracts(&self, input: &VerificationInput) -> Result<VerificationOutput> {
+        let start_time = std::time::Instant::now();
+
+        let (files, only) = match &input.target {
+            Target::RustFile(path) => (vec![path.clone()], None),
+            Target::Item { file, .. } => (
+                vec![file.clone()],
+                input.target.item_name().map(String::from),
+            ),
+            Target::Module(_) => (rust_files(&input.context.working_dir.join("src")), None),
+            Target::FormalSpec(_) => return Err(anyhow!("SMT plugin only supports Rust targets")),
+        };
+        let timeout = input
+            .config
+            .tool_config
+            .get("timeout")
+            .and_then(|v| v.as_u64())
+            .or(self.timeout);
+        let cancellation = &input.context.cancellation;
+
+        let mut contracts = Vec::new();
+        for file in &files {
+            let content = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read {:?}", file))?;
+            contracts.extend(FunctionContract::parse_file(file, &content)?);
+        }
+        if let Some(only) = &only {
+            contracts.retain(|contract| {
+                contract.function == *only || contract.function.ends_with(&format!("::{}", only))
+            });
+        }
+
+        let mut violations = Vec::new();
+        let mut stdout = String::new();
+        let mut queries = 0;
+        for contract in &contracts {
+            let violation =
+                |id: &str, severity: Severity, message: String, suggestion: &str| Violation {
+                    id: id.to_string(),
+                    severity,
+                    location: contract.location.clone(),
+                    message,
+                    suggestion: Some(suggestion.to_string()),
+                    rule: "smt_refinement".to_string(),
+                };
+
+            if let Some(script) = contract.precondition_script() {
+                let output = self.solve(&script, timeout, cancellation)?;
+                queries += 1;
+                if Verdict::parse(&output) == Some(Verdict::Unsat) {
+                    violations.push(violation(
+                        "SMT_VACUOUS_PRECONDITION",
+                        Severity::Warning,
+                        format!(
+                            "Preconditions of `{}` can never hold, so its postconditions are vacuous",
+                            contract.function
+                        ),
+                        "Relax the `requires` clauses or the parameter types",
+                    ));
+                    continue;
+                }
+            }
+
+            for obligation in contract.obligations() {
+                let script = match &obligation.script {
+                    Ok(script) => script,
+                    Err(reason) => {
+                        violations.push(violation(
+                            "SMT_UNSUPPORTED",
+                            Severity::Info,
+                            format!(
+                                "`{}` cannot be checked to ensure {}: {}",
+                                contract.function, obligation.description, reason
+                            ),
+                            "Keep contracts to linear arithmetic and boolean logic over integer and `bool` parameters",
+                        ));
+                        continue;
+                    }
+                };
+
+                let output = self.solve(script, timeout, cancellation)?;
+                queries += 1;
+                stdout.push_str(&output);
+                match Verdict::parse(&output) {
+                    Some(Verdict::Unsat) => {
+                        debug!("`{}` ensures {}", contract.function, obligation.description)
+                    }
+                    Some(Verdict::Sat) => {
+                        let model = self.solve(
+                            &format!("{}{}", script, contract.model_query()),
+                            timeout,
+                            cancellation,
+                        )?;
+                        let values: Vec<String> = model_values(&model)
+                            .into_iter()
+                            .map(|(name, value)| format!("{} = {}", name, value))
+                            .collect();
+                        violations.push(violation(
+                            "SMT_REFINEMENT_REFUTED",
+                            Severity::Error,
+                            format!(
+                                "`{}` does not ensure {}: counterexample {}",
+                                contract.function,
+                                obligation.description,
+                                if values.is_empty() {
+                                    "found".to_string()
+                                } else {
+                                    values.join(", ")
+                                }
+                            ),
+                            "Strengthen the `requires` clauses or fix the function body",
+                        ));
+                    }
+                    Some(Verdict::Unknown) => violations.push(violation(
+                        "SMT_UNKNOWN",
+                        Severity::Warning,
+                        format!(
+                            "The solver could not decide whether `{}` ensures {}",
+                            contract.function, obligation.description
+                        ),
+                        "Raise [tools.smt] timeout or simplify the contract",
+                    )),
+                    None => {
+                        return Err(anyhow!(
+                            "Unexpected SMT solver output: {}",
+                            output.lines().next().unwrap_or("").trim()
+                        ))
+                    }
+                }
+            }
+        }
+        info!(
+            "Checked {} contract(s) with {} solver queries: {} violation(s)",
+            contracts.len(),
+            queries,
+            violations.len()
+        );
+
+        let status = if violations.iter().any(|v| v.severity == Severity::Error) {
+            Status::Error
+        } else if violations.iter().any(|v| v.severity == Severity::Warning) {
+            Status::Warning
+        } else {
+            Status::Success
+        };
+        let execution_time = start_time.elapsed();
+
+        Ok(VerificationOutput {
+            status,
+            violations,
+            artifacts: vec![],
+            tool_output: ToolOutput {
+                tool: "smt".to_string(),
+                stdout,
+                stderr: String::new(),
+                exit_code: 0,
+                execution_time,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: execution_time,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: queries,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+        })
+    }
+}
+
+impl VerificationPlugin for SmtPlugin {
+    fn name(&self) -> &str {
+        "smt"
+    }
+
+    fn version(&self) -> &str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn supported_techniques(&self) -> Vec<Technique> {
+        vec![Technique::RefinementTypes]
+    }
+
+    fn supported_versions(&self) -> VersionRange {
+        VersionRange {
+            min: Some(semver::Version::new(0, 1, 0)),
+            max: Some(semver::Version::new(1, 0, 0)),
+            requires_exact: None,
+        }
+    }
+
+    fn check_availability(&self) -> ferris_proof_core::Result<ToolInfo> {
+        let output = Command::new(&self.solver_path)
+            .arg("--version")
+            .output()
+            .map_err(|e| {
+                FerrisProofError::tool_not_found(
+                    "smt",
+                    format!("SMT solver not found at {:?}: {}", self.solver_path, e),
+                )
+            })?;
+        if !output.status.success() {
+            return Err(FerrisProofError::tool_not_found(
+                "smt",
+                "SMT solver is not working properly",
+            ));
+        }
+
+        Ok(ToolInfo {
+            name: "smt".to_string(),
+            // e.g. `Z3 version 4.12.2 - 64 bit`
+            version: String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .find(|word| word.chars().next().is_some_and(|c| c.is_ascii_digit()))
+                .unwrap_or("unknown")
+                .to_string(),
+            path: self.solver_path.clone(),
+            available: true,
+            capabilities: vec![
+                "refinement_types".to_string(),
+                "linear_arithmetic".to_string(),
+                "counterexamples".to_string(),
+            ],
+        })
+    }
+
+    fn verify(&self, input: VerificationInput) -> ferris_proof_core::Result<VerificationOutput> {
+        if !self.initialized {
+            return Err(FerrisProofError::plugin(
+                "smt",
+                "SMT plugin not initialized",
+            ));
+        }
+
+        self.check_contracts(&input)
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
+    }
+
+    fn parse_output(&self, raw_output: &str) -> ferris_proof_core::Result<StructuredResult> {
+        let answers: Vec<Verdict> = raw_output.lines().filter_map(Verdict::parse).collect();
+        let refuted = answers.iter().filter(|v| **v == Verdict::Sat).count();
+        let unknown = answers.iter().filter(|v| **v == Verdict::Unknown).count();
+        Ok(StructuredResult {
+            status: if refuted > 0 {
+                Status::Error
+            } else if unknown > 0 {
+                Status::Warning
+            } else {
+                Status::Success
+            },
+            violations: vec![],
+            statistics: json!({
+                "proved": answers.len() - refuted - unknown,
+                "refuted": refuted,
+                "unknown": unknown,
+            }),
+            performance: PerformanceMetrics {
+                execution_time: Duration::from_millis(0),
+                memory_usage: 0,
+                cpu_usage: 0.0,
+                cache_hits: 0,
+            },
+        })
+    }
+
+    fn metadata(&self) -> PluginMetadata {
+        PluginMetadata {
+            name: "smt".to_string(),
+            version: self.version().to_string(),
+            description:
+                "Proves #[contract] refinement predicates at function boundaries with an SMT solver"
+                    .to_string(),
+            author: "FerrisProof Team".to_string(),
+            license: "MIT".to_string(),
+            homepage: Some("https://github.com/Z3Prover/z3".to_string()),
+            techniques: vec![Technique::RefinementTypes],
+            supported_platforms: vec![
+                "linux".to_string(),
+                "macos".to_string(),
+                "windows".to_string(),
+            ],
+            dependencies: vec!["z3".to_string()],
+            renderers: Vec::new(),
+            error_codes: Vec::new(),
+        }
+    }
+
+    fn initialize(&mut self, config: &serde_json::Value) -> ferris_proof_core::Result<()> {
+        if let Some(smt) = config.get("smt") {
+            if let Some(path) = smt.get("solver_path").and_then(|v| v.as_str()) {
+                self.solver_path = PathBuf::from(path);
+            }
+            self.timeout = smt.get("timeout").and_then(|v| v.as_u64());
+        }
+
+        self.check_availability()?;
+        self.initialized = true;
+        info!("SMT plugin initialized with solver: {:?}", self.solver_path);
+        Ok(())
+    }
+
+    fn cleanup(&mut self) -> ferris_proof_core::Result<()> {
+        self.initialized = false;
+        Ok(())
+    }
+}
+
+impl Default for SmtPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"use ferris_proof_macros::contract;
+
+#[contract(requires = x >= 0 && x < 100, ensures = result > x)]
+pub fn bump(x: i32) -> i32 {
+    let step = 1;
+    x + step
+}
+
+pub struct Meter;
+
+impl Meter {
+    #[contract(ensures = result >= 0)]
+    pub fn distance(a: i64, b: i64) -> i64 {
+        if a > b { a - b } else { b - a }
+    }
+
+    #[contract(requires = n > 10 && n < 5)]
+    pub fn impossible(n: u8) -> bool {
+        n.is_power_of_two()
+    }
+}
+
+pub fn plain(x: u32) -> u32 {
+    x
+}
+"#;
+
+    fn contracts() -> Vec<FunctionContract> {
+        FunctionContract::parse_file(Path::new("src/lib.rs"), SOURCE).unwrap()
+    }
+
+    #[test]
+    fn test_parses_contracts_on_functions_and_methods() {
+        let contracts = contracts();
+        let names: Vec<&str> = contracts.iter().map(|c| c.function.as_str()).collect();
+        assert_eq!(names, vec!["bump", "Meter::distance", "Meter::impossible"]);
+
+        let bump = &contracts[0];
+        assert_eq!(bump.location.line, Some(4));
+        assert_eq!(
+            bump.params,
+            vec![(
+                "x".to_string(),
+                Sort::Int {
+                    min: Some(i32::MIN as i128),
+                    max: Some(i32::MAX as i128)
+                }
+            )]
+        );
+        assert_eq!(
+            bump.requires[0].term.as_deref(),
+            Ok("(and (>= |x| 0) (< |x| 100))")
+        );
+        assert_eq!(
+            bump.body.as_deref(),
+            Ok("(let ((|step| 1)) (+ |x| |step|))")
+        );
+
+        assert_eq!(
+            contracts[1].body.as_deref(),
+            Ok("(ite (> |a| |b|) (- |a| |b|) (- |b| |a|))")
+        );
+        assert!(contracts[2]
+            .body
+            .as_ref()
+            .unwrap_err()
+            .contains("n.is_power_of_two()"));
+    }
+
+    #[test]
+    fn test_obligations_negate_each_postcondition() {
+        let bump = &contracts()[0];
+        let obligations = bump.obligations();
+        assert_eq!(
+            obligations
+                .iter()
+                .map(|o| o.description.as_str())
+                .collect::<Vec<_>>(),
+            vec!["`result > x`", "a result within `i32`"]
+        );
+
+        let script = obligations[0].script.as_ref().unwrap();
+        assert!(script.contains("(declare-const |x| Int)\n(assert (<= (- 2147483648) |x|))"));
+        assert!(script.contains("(assert (and (>= |x| 0) (< |x| 100)))"));
+        assert!(script.ends_with("(assert (not (> |result| |x|)))\n(check-sat)\n"));
+        assert_eq!(bump.model_query(), "(get-value (|x| |result|))\n");
+
+        let impossible = &contracts()[2];
+        assert!(impossible.precondition_script().is_some());
+        assert!(impossible.obligations().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_expressions_outside_the_fragment() {
+        let source = r#"
+#[contract(requires = items.len() > 0, ensures = result)]
+fn first(items: Vec<u8>, flag: bool) -> bool { flag }
+"#;
+        let contract = &FunctionContract::parse_file(Path::new("a.rs"), source).unwrap()[0];
+        assert!(contract.requires[0]
+            .term
+            .as_ref()
+            .unwrap_err()
+            .contains("`items.len()`"));
+        assert_eq!(contract.ensures[0].term.as_deref(), Ok("|result|"));
+        assert!(contract.precondition_script().is_none());
+        assert!(contract.obligations()[0].script.is_err());
+    }
+
+    #[test]
+    fn test_registers_with_the_plugin_manager() {
+        let mut manager = ferris_proof_core::PluginManager::new();
+        assert!(manager.register_plugin(Box::new(SmtPlugin::new())).is_ok());
+    }
+
+    #[test]
+    fn test_parses_solver_answers() {
+        assert_eq!(Verdict::parse("unsat\n"), Some(Verdict::Unsat));
+        assert_eq!(Verdict::parse("\nsat\n((|x| 5))"), Some(Verdict::Sat));
+        assert_eq!(Verdict::parse("(error \"x\")"), None);
+        assert_eq!(
+            model_values("sat\n((|x| (- 3))\n (|result| 7)\n (flag true))\n"),
+            vec![
+                ("x".to_string(), "-3".to_string()),
+                ("result".to_string(), "7".to_string()),
+                ("flag".to_string(), "true".to_string())
+            ]
+        );
+    }
+}
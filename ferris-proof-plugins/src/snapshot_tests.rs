@@ -0,0 +1,487 @@
+use crate::serde_roundtrip::{
+    configured_expression, declaration_line, derived_traits, has_dependency, has_type_parameters,
+    is_cfg_test, package_manifest, rust_files,
+};
+use anyhow::{anyhow, Context, Result};
+use ferris_proof_core::{cache::module_path_of, cache::wildcard_match, types::*};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use syn::{Item, ReturnType, Visibility};
+use tracing::debug;
+
+/// First line of the generated test file; files without it are never replaced
+pub const GENERATED_HEADER: &str =
+    "// Generated by ferris-proof (`ferris-proof generate snapshot-tests`). Edits may be overwritten.";
+
+/// Integration test target holding the generated snapshot tests
+pub const TEST_TARGET: &str = "ferris_proof_snapshots";
+
+/// Wrappers looked through to find the serde type a function returns
+const TRANSPARENT_WRAPPERS: [&str; 5] = ["Option", "Vec", "Box", "Rc", "Arc"];
+
+/// A free function returning a type that derives `Serialize`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotFunction {
+    pub name: String,
+    /// Module path inside the crate, e.g. `report::summary`; empty for the root
+    pub module_path: String,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Serde type in the return type, e.g. `Report` for `-> Vec<Report>`
+    pub returns: String,
+    /// Configured argument list, used for functions that take arguments
+    pub inputs: Option<String>,
+    /// Why no snapshot test can be generated, if any
+    pub uncovered_reason: Option<String>,
+}
+
+impl SnapshotFunction {
+    pub fn path_in_crate(&self, crate_name: &str) -> String {
+        if self.module_path.is_empty() {
+            format!("{}::{}", crate_name, self.name)
+        } else {
+            format!("{}::{}::{}", crate_name, self.module_path, self.name)
+        }
+    }
+
+    fn test_name(&self) -> String {
+        let mut name = "snapshot".to_string();
+        for segment in self.module_path.split("::").filter(|s| !s.is_empty()) {
+            name.push_str("__");
+            name.push_str(segment);
+        }
+        name.push_str("__");
+        name.push_str(&self.name);
+        name
+    }
+
+    fn location(&self) -> Location {
+        Location::point(self.file.clone(), self.line as u32, 1)
+    }
+}
+
+/// Snapshot tests to generate for one package
+#[derive(Debug, Clone)]
+pub struct SnapshotPlan {
+    pub crate_name: String,
+    pub functions: Vec<SnapshotFunction>,
+    /// Whether `insta` is a dependency of the package
+    pub has_insta: bool,
+}
+
+impl SnapshotPlan {
+    /// Scan `src/` of the package at `package_root` for functions returning
+    /// serde types
+    ///
+    /// Only modules matching `modules` are planned, all of them when it is
+    /// empty; patterns are module paths like `crate::report` where `*`
+    /// matches any substring. `inputs` maps function paths to the argument
+    /// list they are called with, from `[generate.snapshots.inputs]`.
+    pub fn build(
+        package_root: &Path,
+        modules: &[String],
+        inputs: &BTreeMap<String, String>,
+    ) -> Result<Self> {
+        let (manifest, crate_name) = package_manifest(package_root)?;
+
+        let mut sources = Vec::new();
+        for file in rust_files(&package_root.join("src")) {
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {:?}", file))?;
+            let ast =
+                syn::parse_file(&content).with_context(|| format!("Failed to parse {:?}", file))?;
+            sources.push((file, content, ast));
+        }
+
+        // Functions often return types declared in another module
+        let mut serde_types = Vec::new();
+        for (_, _, ast) in &sources {
+            collect_serialize_types(&ast.items, &mut serde_types);
+        }
+
+        let mut functions = Vec::new();
+        for (file, content, ast) in &sources {
+            let relative = file.strip_prefix(package_root).unwrap_or(file);
+            let module_path = module_path_of(&relative.to_string_lossy());
+            let module_path = if module_path == "crate" {
+                ""
+            } else {
+                module_path.as_str()
+            };
+
+            let source = SourceFile {
+                path: file,
+                content,
+                serde_types: &serde_types,
+                crate_name: &crate_name,
+                modules,
+                inputs,
+            };
+            source.visit_items(&ast.items, module_path, true, &mut functions);
+        }
+
+        Ok(Self {
+            crate_name,
+            functions,
+            has_insta: has_dependency(&manifest, "insta"),
+        })
+    }
+
+    pub fn covered_functions(&self) -> impl Iterator<Item = &SnapshotFunction> {
+        self.functions
+            .iter()
+            .filter(|f| f.uncovered_reason.is_none())
+    }
+
+    pub fn test_path(package_root: &Path) -> PathBuf {
+        package_root
+            .join("tests")
+            .join(format!("{}.rs", TEST_TARGET))
+    }
+
+    /// Source of the generated integration test
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(GENERATED_HEADER);
+        out.push('\n');
+        for function in self.covered_functions() {
+            out.push_str(&format!(
+                "\n#[test]\nfn {}() {{\n    insta::assert_yaml_snapshot!({}({}));\n}}\n",
+                function.test_name(),
+                function.path_in_crate(&self.crate_name),
+                function.inputs.as_deref().unwrap_or_default()
+            ));
+        }
+        out
+    }
+
+    /// Write the generated test, refusing to replace a hand-written file
+    ///
+    /// Returns `false` when an existing file without the generated header was
+    /// left untouched. Snapshots themselves are recorded by `cargo insta`.
+    pub fn write(&self, package_root: &Path) -> Result<bool> {
+        let path = Self::test_path(package_root);
+        if path.exists() {
+            let existing = std::fs::read_to_string(&path)?;
+            if !existing.starts_with(GENERATED_HEADER) {
+                debug!("Keeping hand-written snapshot tests at {:?}", path);
+                return Ok(false);
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, self.render())
+            .map_err(|e| anyhow!("Failed to write snapshot tests {:?}: {}", path, e))?;
+        Ok(true)
+    }
+
+    /// Warnings for functions the generated tests cannot cover and a missing `insta`
+    pub fn coverage_gaps(&self) -> Vec<Violation> {
+        let mut violations: Vec<Violation> = self
+            .functions
+            .iter()
+            .filter_map(|function| {
+                let reason = function.uncovered_reason.as_ref()?;
+                Some(Violation {
+                    id: "SNAPSHOT_UNCOVERED".to_string(),
+                    severity: Severity::Warning,
+                    location: function.location(),
+                    message: format!("`{}` has no snapshot test: {}", function.name, reason),
+                    suggestion: Some(
+                        "Make the function public and non-generic, and map its arguments in \
+                         [generate.snapshots.inputs], e.g. \
+                         \"crate::report::render\" = \"&my_crate::Report::sample()\""
+                            .to_string(),
+                    ),
+                    rule: "snapshot_tests".to_string(),
+                })
+            })
+            .collect();
+
+        if !self.has_insta {
+            violations.push(Violation {
+                id: "SNAPSHOT_UNCOVERED".to_string(),
+                severity: Severity::Warning,
+                location: Location::file(PathBuf::from("Cargo.toml")),
+                message: "snapshot tests skipped: `insta` is not a dev-dependency".to_string(),
+                suggestion: Some(
+                    "Add `insta = { version = \"1\", features = [\"yaml\"] }` to [dev-dependencies]"
+                        .to_string(),
+                ),
+                rule: "snapshot_tests".to_string(),
+            });
+        }
+
+        violations
+    }
+}
+
+struct SourceFile<'a> {
+    path: &'a Path,
+    content: &'a str,
+    serde_types: &'a [String],
+    crate_name: &'a str,
+    modules: &'a [String],
+    inputs: &'a BTreeMap<String, String>,
+}
+
+impl SourceFile<'_> {
+    fn visit_items(
+        &self,
+        items: &[Item],
+        module_path: &str,
+        reachable: bool,
+        functions: &mut Vec<SnapshotFunction>,
+    ) {
+        for item in items {
+            let function = match item {
+                Item::Fn(function) => function,
+                Item::Mod(m) => {
+                    if is_cfg_test(&m.attrs) {
+                        continue;
+                    }
+                    if let Some((_, nested)) = &m.content {
+                        let path = if module_path.is_empty() {
+                            m.ident.to_string()
+                        } else {
+                            format!("{}::{}", module_path, m.ident)
+                        };
+                        let reachable = reachable && matches!(m.vis, Visibility::Public(_));
+                        self.visit_items(nested, &path, reachable, functions);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            if !module_selected(self.modules, module_path)
+                || function
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path().is_ident("test"))
+            {
+                continue;
+            }
+            let ReturnType::Type(_, output) = &function.sig.output else {
+                continue;
+            };
+            let Some(returns) = serde_type_in(output, self.serde_types) else {
+                continue;
+            };
+
+            let name = function.sig.ident.to_string();
+            let inputs = configured_expression(self.inputs, self.crate_name, module_path, &name);
+            let sig = &function.sig;
+            let uncovered_reason = if !reachable || !matches!(function.vis, Visibility::Public(_)) {
+                Some("not public, so integration tests cannot call it".to_string())
+            } else if has_type_parameters(&sig.generics) {
+                Some("generic functions need a concrete instantiation".to_string())
+            } else if sig.asyncness.is_some() || sig.unsafety.is_some() {
+                Some("async and unsafe functions need a hand-written test".to_string())
+            } else if !sig.inputs.is_empty() && inputs.is_none() {
+                Some("its arguments have no configured inputs".to_string())
+            } else {
+                None
+            };
+
+            functions.push(SnapshotFunction {
+                line: declaration_line(self.content, "fn", &name),
+                name,
+                module_path: module_path.to_string(),
+                file: self.path.to_path_buf(),
+                returns,
+                inputs,
+                uncovered_reason,
+            });
+        }
+    }
+}
+
+/// Whether `module_path` (empty for the crate root) matches one of `modules`
+fn module_selected(modules: &[String], module_path: &str) -> bool {
+    modules.is_empty()
+        || modules.iter().any(|pattern| {
+            let pattern = pattern.trim();
+            match pattern.strip_prefix("crate::") {
+                Some(pattern) => wildcard_match(pattern, module_path),
+                None if module_path.is_empty() => pattern == "crate" || pattern == "*",
+                None => wildcard_match(pattern, module_path),
+            }
+        })
+}
+
+/// Serde type named by `ty`, looking through `Option`, `Vec`, references and
+/// other transparent wrappers
+fn serde_type_in(ty: &syn::Type, serde_types: &[String]) -> Option<String> {
+    match ty {
+        syn::Type::Reference(reference) => serde_type_in(&reference.elem, serde_types),
+        syn::Type::Slice(slice) => serde_type_in(&slice.elem, serde_types),
+        syn::Type::Array(array) => serde_type_in(&array.elem, serde_types),
+        syn::Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            let name = segment.ident.to_string();
+            if serde_types.contains(&name) {
+                return Some(name);
+            }
+            if !TRANSPARENT_WRAPPERS.contains(&name.as_str()) {
+                return None;
+            }
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            args.args.iter().find_map(|arg| match arg {
+                syn::GenericArgument::Type(inner) => serde_type_in(inner, serde_types),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Names of structs and enums deriving `Serialize`
+fn collect_serialize_types(items: &[Item], names: &mut Vec<String>) {
+    for item in items {
+        let (ident, attrs) = match item {
+            Item::Struct(s) => (&s.ident, &s.attrs),
+            Item::Enum(e) => (&e.ident, &e.attrs),
+            Item::Mod(m) => {
+                if let Some((_, nested)) = &m.content {
+                    collect_serialize_types(nested, names);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        if derived_traits(attrs).iter().any(|d| d == "Serialize") {
+            names.push(ident.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_package(root: &Path) {
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"shop-report\"\n\n[dev-dependencies]\ninsta = { version = \"1\", features = [\"yaml\"] }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/lib.rs"),
+            r#"pub mod report;
+
+#[derive(Debug, serde::Serialize)]
+pub struct Summary {
+    pub total: u64,
+}
+
+pub fn empty_summary() -> Summary {
+    Summary { total: 0 }
+}
+
+pub fn version() -> String {
+    "1".to_string()
+}
+
+fn hidden() -> Summary {
+    Summary { total: 1 }
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/report.rs"),
+            r#"use crate::Summary;
+
+#[derive(serde::Serialize)]
+pub struct Line {
+    pub sku: String,
+}
+
+pub fn lines() -> Vec<Line> {
+    Vec::new()
+}
+
+pub fn summarize(lines: &[Line]) -> Option<Summary> {
+    None
+}
+
+pub fn render<T>(value: T) -> Summary {
+    unimplemented!()
+}
+"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_plan_finds_serde_returning_functions() {
+        let temp_dir = TempDir::new().unwrap();
+        write_package(temp_dir.path());
+
+        let inputs = BTreeMap::from([(
+            "crate::report::summarize".to_string(),
+            "&shop_report::report::lines()".to_string(),
+        )]);
+        let plan = SnapshotPlan::build(temp_dir.path(), &[], &inputs).unwrap();
+        assert_eq!(plan.crate_name, "shop_report");
+        assert!(plan.has_insta);
+
+        let covered: Vec<String> = plan
+            .covered_functions()
+            .map(|f| f.path_in_crate(&plan.crate_name))
+            .collect();
+        assert_eq!(
+            covered,
+            vec![
+                "shop_report::empty_summary",
+                "shop_report::report::lines",
+                "shop_report::report::summarize",
+            ]
+        );
+        let lines = plan.functions.iter().find(|f| f.name == "lines").unwrap();
+        assert_eq!(lines.returns, "Line");
+        assert_eq!(lines.line, 8);
+
+        // `hidden` is private and `render` is generic
+        let gaps = plan.coverage_gaps();
+        assert_eq!(gaps.len(), 2);
+        assert!(gaps.iter().all(|v| v.severity == Severity::Warning));
+
+        let rendered = plan.render();
+        assert!(rendered.starts_with(GENERATED_HEADER));
+        assert!(rendered.contains(
+            "fn snapshot__report__summarize() {\n    \
+             insta::assert_yaml_snapshot!(shop_report::report::summarize(&shop_report::report::lines()));"
+        ));
+    }
+
+    #[test]
+    fn test_modules_select_functions() {
+        let temp_dir = TempDir::new().unwrap();
+        write_package(temp_dir.path());
+
+        let plan = SnapshotPlan::build(
+            temp_dir.path(),
+            &["crate::report".to_string()],
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        let names: Vec<&str> = plan.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["lines", "summarize", "render"]);
+        assert!(plan.functions[1]
+            .uncovered_reason
+            .as_deref()
+            .is_some_and(|reason| reason.contains("configured inputs")));
+
+        assert!(module_selected(&["crate".to_string()], ""));
+        assert!(!module_selected(&["crate".to_string()], "report"));
+        assert!(module_selected(&["report::*".to_string()], "report::fmt"));
+    }
+}
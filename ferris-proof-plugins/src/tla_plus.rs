@@ -1,19 +1,24 @@
+use crate::apalache::{self, Apalache, Counterexample};
+use crate::tlc_config::{self, TlcModelOverrides};
+use crate::tlc_tuning::{HostResources, TlcTuning};
 use anyhow::Result;
 use ferris_proof_core::{
     plugins::{
-        PerformanceMetrics, PluginMetadata, StructuredResult, ToolInfo, VerificationInput,
-        VerificationOutput, VerificationPlugin, VersionRange,
+        run_tool, PerformanceMetrics, PluginMetadata, StructuredResult, ToolEnv, ToolInfo,
+        VerificationInput, VerificationOutput, VerificationPlugin, VersionRange,
     },
-    types::{Status, Technique, ToolOutput, VerificationMetrics},
+    types::{Location, Severity, Status, Technique, ToolOutput, VerificationMetrics, Violation},
+    verification::Target,
+    FerrisProofError,
 };
 use semver::Version;
 use serde_json::json;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use tracing::info;
+use tracing::{debug, info};
 
 /// Simple replacement for which::which functionality
-fn find_executable(name: &str) -> Option<PathBuf> {
+pub(crate) fn find_executable(name: &str) -> Option<PathBuf> {
     if let Ok(path_env) = std::env::var("PATH") {
         for path in std::env::split_paths(&path_env) {
             let candidate = path.join(name);
@@ -33,18 +38,386 @@ fn find_executable(name: &str) -> Option<PathBuf> {
     None
 }
 
+/// Model checker a spec is run with, from `[tools.tla_plus] backend`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    #[default]
+    Tlc,
+    Apalache,
+}
+
+impl Backend {
+    fn from_tool_config(tool_config: &serde_json::Value) -> Option<Self> {
+        match tool_config.get("backend").and_then(|v| v.as_str())? {
+            "tlc" => Some(Backend::Tlc),
+            "apalache" => Some(Backend::Apalache),
+            _ => None,
+        }
+    }
+}
+
 pub struct TlaPlusPlugin {
+    backend: Backend,
     tlc_path: Option<PathBuf>,
+    apalache_path: Option<PathBuf>,
     initialized: bool,
 }
 
 impl TlaPlusPlugin {
     pub fn new() -> Self {
         Self {
+            backend: Backend::Tlc,
             tlc_path: find_executable("tlc"),
+            apalache_path: Apalache::locate(None),
             initialized: false,
         }
     }
+
+    fn apalache(&self, tool_config: &serde_json::Value) -> Result<Apalache> {
+        let configured = tool_config
+            .get("apalache_path")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from);
+        configured
+            .or_else(|| self.apalache_path.clone())
+            .map(|path| Apalache::new(path).with_env(ToolEnv::from_tool_config(tool_config)))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Apalache (apalache-mc) not found in PATH; install it, set [tools.tla_plus] apalache_path, or use backend = \"tlc\""
+                )
+            })
+    }
+
+    fn apalache_availability(&self) -> Result<ToolInfo> {
+        let apalache = self.apalache(&serde_json::Value::Null)?;
+        let version = apalache.version()?;
+        Ok(ToolInfo {
+            name: "Apalache".to_string(),
+            version,
+            path: apalache.path().to_path_buf(),
+            available: true,
+            capabilities: vec![
+                "model_checking".to_string(),
+                "symbolic_checking".to_string(),
+                "invariant_checking".to_string(),
+                "type_checking".to_string(),
+            ],
+        })
+    }
+
+    /// Bounded symbolic check of `spec_path` against the model in `cfg_path`
+    fn run_apalache(
+        &self,
+        input: &VerificationInput,
+        spec_path: &Path,
+        cfg_path: &Path,
+    ) -> Result<VerificationOutput> {
+        let start_time = std::time::Instant::now();
+        let tool_config = &input.config.tool_config;
+        let apalache = self.apalache(tool_config)?;
+        let length = tool_config
+            .get("length")
+            .and_then(|v| v.as_u64())
+            .map_or(apalache::DEFAULT_LENGTH, |l| l as u32);
+        let run_dir = input
+            .context
+            .cache_dir
+            .join(format!("apalache_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&run_dir)?;
+
+        let output = apalache.check(
+            spec_path,
+            cfg_path,
+            length,
+            &run_dir,
+            &input.context.cancellation,
+        )?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let exit_code = output.status.code().unwrap_or(-1);
+        let combined = format!("{}\n{}", stdout, stderr);
+
+        let spec_dir = spec_path.parent().unwrap_or(Path::new("."));
+        let mut violations: Vec<_> = apalache::type_errors(&combined)
+            .iter()
+            .map(|error| error.violation(spec_dir))
+            .collect();
+        if exit_code == apalache::EXIT_COUNTEREXAMPLE {
+            let invariants =
+                apalache::cfg_invariants(&std::fs::read_to_string(cfg_path).unwrap_or_default());
+            if let Some(counterexample) = Counterexample::parse(&combined, &invariants, &run_dir) {
+                violations.push(counterexample.violation(spec_path));
+            }
+        }
+        if violations.is_empty() {
+            let _ = std::fs::remove_dir_all(&run_dir);
+        }
+
+        let status = match exit_code {
+            0 => Status::Success,
+            apalache::EXIT_COUNTEREXAMPLE | apalache::EXIT_TYPECHECK if !violations.is_empty() => {
+                Status::Error
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "apalache-mc check failed with exit code {}: {}",
+                    exit_code,
+                    combined
+                        .lines()
+                        .rev()
+                        .find(|l| !l.trim().is_empty())
+                        .unwrap_or("")
+                        .trim()
+                ))
+            }
+        };
+        info!(
+            "Apalache checked {:?} up to length {}: {} violation(s)",
+            spec_path,
+            length,
+            violations.len()
+        );
+        let execution_time = start_time.elapsed();
+
+        Ok(VerificationOutput {
+            status,
+            violations,
+            artifacts: Vec::new(),
+            tool_output: ToolOutput {
+                tool: "apalache".to_string(),
+                stdout,
+                stderr,
+                exit_code,
+                execution_time,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: execution_time,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+        })
+    }
+
+    /// Writes the model configuration and runs the selected backend on the input
+    fn run_spec(&self, input: VerificationInput) -> Result<VerificationOutput> {
+        info!("Running TLA+ verification for {:?}", input.target);
+
+        // TLC and Apalache need a model configuration next to the spec, or in the scratch
+        // directory of hermetic runs: the selected named model if any, otherwise a generated
+        // default
+        if let Target::FormalSpec(spec_path) = &input.target {
+            if spec_path.extension().and_then(|s| s.to_str()) == Some("tla") {
+                let tool_config = &input.config.tool_config;
+                let backend = Backend::from_tool_config(tool_config).unwrap_or(self.backend);
+                let cfg_path = match tool_config.get("model").and_then(|v| v.as_str()) {
+                    Some(name) => {
+                        let overrides: TlcModelOverrides = tool_config
+                            .get("models")
+                            .and_then(|models| models.get(name))
+                            .cloned()
+                            .map(serde_json::from_value)
+                            .transpose()?
+                            .ok_or_else(|| anyhow::anyhow!("Unknown TLA+ model '{}'", name))?;
+                        match &input.context.scratch_dir {
+                            Some(dir) => {
+                                tlc_config::write_model_cfg_in(spec_path, name, &overrides, dir)?
+                            }
+                            None => tlc_config::write_model_cfg(spec_path, name, &overrides)?,
+                        }
+                    }
+                    None => match &input.context.scratch_dir {
+                        Some(dir) => tlc_config::ensure_default_cfg_in(spec_path, dir)?,
+                        None => tlc_config::ensure_default_cfg(spec_path)?,
+                    },
+                };
+                debug!("Using TLC config: {:?}", cfg_path);
+
+                return match backend {
+                    Backend::Apalache => self.run_apalache(&input, spec_path, &cfg_path),
+                    Backend::Tlc => self.run_tlc(&input, spec_path, &cfg_path),
+                };
+            }
+        }
+
+        // Only `.tla` specs can be model checked
+        Ok(VerificationOutput {
+            status: Status::Skipped,
+            violations: Vec::new(),
+            artifacts: Vec::new(),
+            tool_output: ToolOutput {
+                tool: "tlc".to_string(),
+                stdout: format!("No TLA+ spec to check in {:?}", input.target),
+                stderr: String::new(),
+                exit_code: 0,
+                execution_time: std::time::Duration::ZERO,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: std::time::Duration::ZERO,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: Default::default(),
+            },
+        })
+    }
+
+    /// Exhaustive TLC check of `spec_path` against the model in `cfg_path`,
+    /// with workers and heap tuned to the host
+    fn run_tlc(
+        &self,
+        input: &VerificationInput,
+        spec_path: &Path,
+        cfg_path: &Path,
+    ) -> Result<VerificationOutput> {
+        let start_time = std::time::Instant::now();
+        let tool_config = &input.config.tool_config;
+        let tlc_path = tool_config
+            .get("tlc_path")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from)
+            .or_else(|| self.tlc_path.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "TLA+ TLC not found in PATH; install it, set [tools.tla_plus] tlc_path, or use backend = \"apalache\""
+                )
+            })?;
+        let tuning = TlcTuning::resolve(
+            tool_config,
+            &HostResources::detect(),
+            input.config.memory_budget,
+        );
+        let run_dir = input
+            .context
+            .cache_dir
+            .join(format!("tlc_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&run_dir)?;
+
+        let mut cmd = Command::new(&tlc_path);
+        if let Some(dir) = spec_path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            cmd.current_dir(dir);
+        }
+        ToolEnv::from_tool_config(tool_config).apply(&mut cmd);
+        tuning.apply(&mut cmd);
+        cmd.arg("-config")
+            .arg(cfg_path)
+            .arg("-metadir")
+            .arg(&run_dir)
+            .arg(spec_path);
+        debug!("Executing command: {:?}", cmd);
+        let output = run_tool(&mut cmd, &input.context.cancellation);
+        let _ = std::fs::remove_dir_all(&run_dir);
+        let output = output?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let exit_code = output.status.code().unwrap_or(-1);
+        let combined = format!("{}\n{}", stdout, stderr);
+        let violations = tlc_violations(&stdout, spec_path);
+        let status = match exit_code {
+            _ if !violations.is_empty() => Status::Error,
+            0 => Status::Success,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "tlc failed with exit code {}: {}",
+                    exit_code,
+                    combined
+                        .lines()
+                        .rev()
+                        .find(|l| !l.trim().is_empty())
+                        .unwrap_or("")
+                        .trim()
+                ))
+            }
+        };
+        info!(
+            "TLC checked {:?} with {} {}: {} violation(s)",
+            spec_path,
+            tuning.java_opts(),
+            tuning.tlc_args().join(" "),
+            violations.len()
+        );
+        let execution_time = start_time.elapsed();
+
+        Ok(VerificationOutput {
+            status,
+            violations,
+            artifacts: Vec::new(),
+            tool_output: ToolOutput {
+                tool: "tlc".to_string(),
+                stdout,
+                stderr,
+                exit_code,
+                execution_time,
+                artifact_id: None,
+                retries: 0,
+            },
+            metrics: VerificationMetrics {
+                total_time: execution_time,
+                cache_hit_rate: 0.0,
+                memory_usage: 0,
+                test_cases_executed: 0,
+                max_verified_scope: None,
+                tool_settings: tuning.settings(),
+            },
+        })
+    }
+}
+
+/// Invariant, property and deadlock errors reported by TLC, located at the
+/// violated definition in `spec_path` where it can be found
+pub fn tlc_violations(stdout: &str, spec_path: &Path) -> Vec<Violation> {
+    let spec = std::fs::read_to_string(spec_path).unwrap_or_default();
+    let states = stdout
+        .lines()
+        .filter(|line| line.starts_with("State "))
+        .count();
+    stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("Error: "))
+        .filter_map(|error| {
+            let (id, rule, name) = if let Some(rest) = error.strip_prefix("Invariant ") {
+                (
+                    "TLC_INVARIANT",
+                    "tla_invariant",
+                    rest.strip_suffix(" is violated."),
+                )
+            } else if let Some(rest) = error.strip_prefix("Action property ") {
+                (
+                    "TLC_PROPERTY",
+                    "tla_property",
+                    rest.strip_suffix(" is violated."),
+                )
+            } else if error.starts_with("Temporal properties were violated") {
+                ("TLC_TEMPORAL", "tla_temporal", None)
+            } else if error.starts_with("Deadlock reached") {
+                ("TLC_DEADLOCK", "tla_deadlock", None)
+            } else {
+                return None;
+            };
+            let location = name
+                .and_then(|name| apalache::definition_line(&spec, name))
+                .map_or_else(
+                    || Location::file(spec_path),
+                    |line| Location::point(spec_path, line, 1),
+                );
+            Some(Violation {
+                id: id.to_string(),
+                severity: Severity::Error,
+                location,
+                message: format!("{} ({}-state trace)", error.trim_end_matches('.'), states),
+                suggestion: Some("Inspect the error trace in the TLC output".to_string()),
+                rule: rule.to_string(),
+            })
+        })
+        .collect()
 }
 
 impl VerificationPlugin for TlaPlusPlugin {
@@ -68,7 +441,13 @@ impl VerificationPlugin for TlaPlusPlugin {
         }
     }
 
-    fn check_availability(&self) -> Result<ToolInfo> {
+    fn check_availability(&self) -> ferris_proof_core::Result<ToolInfo> {
+        if self.backend == Backend::Apalache {
+            return self
+                .apalache_availability()
+                .map_err(|e| FerrisProofError::in_plugin(self.name(), e));
+        }
+
         // First check if TLC is available via which
         let tlc_path = match &self.tlc_path {
             Some(path) => path.clone(),
@@ -93,8 +472,9 @@ impl VerificationPlugin for TlaPlusPlugin {
                 match found_path {
                     Some(path) => path,
                     None => {
-                        return Err(anyhow::anyhow!(
-                            "TLA+ TLC not found in PATH or common locations"
+                        return Err(FerrisProofError::tool_not_found(
+                            "tla-plus",
+                            "TLA+ TLC not found in PATH or common locations",
                         ))
                     }
                 }
@@ -141,46 +521,34 @@ impl VerificationPlugin for TlaPlusPlugin {
             Ok(output) => {
                 // Command failed
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                Err(anyhow::anyhow!("TLC command failed: {}", stderr))
+                Err(FerrisProofError::tool_not_found(
+                    "tla-plus",
+                    format!("TLC command failed: {}", stderr),
+                ))
             }
             Err(e) => {
                 // Could not execute command
-                Err(anyhow::anyhow!("Failed to execute TLC: {}", e))
+                Err(FerrisProofError::tool_not_found(
+                    "tla-plus",
+                    format!("Failed to execute TLC: {}", e),
+                ))
             }
         }
     }
 
-    fn verify(&self, input: VerificationInput) -> Result<VerificationOutput> {
+    fn verify(&self, input: VerificationInput) -> ferris_proof_core::Result<VerificationOutput> {
         if !self.initialized {
-            return Err(anyhow::anyhow!("TLA+ plugin not initialized"));
+            return Err(FerrisProofError::plugin(
+                "tla-plus",
+                "TLA+ plugin not initialized",
+            ));
         }
 
-        info!("Running TLA+ verification for {:?}", input.target);
-
-        // TODO: Implement actual TLA+ verification
-        let tool_output = ToolOutput {
-            tool: "tlc".to_string(),
-            stdout: "Model checking completed successfully".to_string(),
-            stderr: String::new(),
-            exit_code: 0,
-            execution_time: std::time::Duration::from_millis(100),
-        };
-
-        Ok(VerificationOutput {
-            status: Status::Success,
-            violations: Vec::new(),
-            artifacts: Vec::new(),
-            tool_output,
-            metrics: VerificationMetrics {
-                total_time: std::time::Duration::from_millis(100),
-                cache_hit_rate: 0.0,
-                memory_usage: 0,
-                test_cases_executed: 0,
-            },
-        })
+        self.run_spec(input)
+            .map_err(|e| FerrisProofError::in_plugin(self.name(), e))
     }
 
-    fn parse_output(&self, raw_output: &str) -> Result<StructuredResult> {
+    fn parse_output(&self, raw_output: &str) -> ferris_proof_core::Result<StructuredResult> {
         // Parse TLA+ TLC output
         let status = if raw_output.contains("Error:") || raw_output.contains("FAILED") {
             Status::Error
@@ -221,33 +589,48 @@ impl VerificationPlugin for TlaPlusPlugin {
                 "macos".to_string(),
                 "windows".to_string(),
             ],
-            dependencies: vec!["tlc".to_string(), "java".to_string()],
+            dependencies: vec![
+                "tlc".to_string(),
+                "java".to_string(),
+                "apalache-mc".to_string(),
+            ],
+            renderers: Vec::new(),
+            error_codes: Vec::new(),
         }
     }
 
-    fn initialize(&mut self, config: &serde_json::Value) -> Result<()> {
+    fn initialize(&mut self, config: &serde_json::Value) -> ferris_proof_core::Result<()> {
         // Extract TLA+ configuration
         if let Some(tool_config) = config.get("tla_plus") {
             if let Some(path) = tool_config.get("tlc_path").and_then(|v| v.as_str()) {
                 self.tlc_path = Some(PathBuf::from(path));
             }
+            if let Some(path) = tool_config.get("apalache_path").and_then(|v| v.as_str()) {
+                self.apalache_path = Some(PathBuf::from(path));
+            }
+            if let Some(backend) = Backend::from_tool_config(tool_config) {
+                self.backend = backend;
+            }
         }
 
         // Verify tool availability
         let tool_info = self.check_availability()?;
         if !tool_info.available {
-            return Err(anyhow::anyhow!(
-                "TLA+ TLC is not available: {}",
-                tool_info.version
+            return Err(FerrisProofError::plugin(
+                "tla-plus",
+                format!("{} is not available: {}", tool_info.name, tool_info.version),
             ));
         }
 
         self.initialized = true;
-        info!("TLA+ plugin initialized with TLC at: {:?}", self.tlc_path);
+        info!(
+            "TLA+ plugin initialized with {} at: {:?}",
+            tool_info.name, tool_info.path
+        );
         Ok(())
     }
 
-    fn cleanup(&mut self) -> Result<()> {
+    fn cleanup(&mut self) -> ferris_proof_core::Result<()> {
         self.initialized = false;
         info!("TLA+ plugin cleaned up");
         Ok(())
@@ -259,3 +642,50 @@ impl Default for TlaPlusPlugin {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tlc_violations_are_located() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec_path = temp_dir.path().join("Counter.tla");
+        std::fs::write(
+            &spec_path,
+            "---- MODULE Counter ----\nVARIABLE x\n\nBounded == x < 3\n====\n",
+        )
+        .unwrap();
+        let stdout = "\
+Error: Invariant Bounded is violated.
+Error: The behavior up to this point is:
+State 1: <Initial predicate>
+/\\ x = 0
+
+State 2: <Inc line 8, col 9 to line 8, col 20 of module Counter>
+/\\ x = 3
+
+2 states generated, 2 distinct states found, 0 states left on queue.
+";
+
+        let violations = tlc_violations(stdout, &spec_path);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].id, "TLC_INVARIANT");
+        assert_eq!(violations[0].location.line, Some(4));
+        assert_eq!(
+            violations[0].message,
+            "Invariant Bounded is violated (2-state trace)"
+        );
+
+        let deadlock = tlc_violations("Error: Deadlock reached.\n", &spec_path);
+        assert_eq!(deadlock[0].rule, "tla_deadlock");
+        assert_eq!(deadlock[0].location.line, None);
+
+        assert!(tlc_violations(
+            "Model checking completed. No error has been found.\n",
+            &spec_path
+        )
+        .is_empty());
+    }
+}
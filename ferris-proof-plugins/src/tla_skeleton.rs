@@ -0,0 +1,638 @@
+use anyhow::{anyhow, Context, Result};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, FnArg, ImplItem, Item, ItemEnum, ItemImpl, Stmt, Token};
+use tracing::debug;
+
+/// First line of every generated spec; files without it are never replaced
+///
+/// TLA+ ignores everything before the `MODULE` line, so the marker does not
+/// affect parsing.
+pub const GENERATED_HEADER: &str =
+    "\\* Generated by ferris-proof (`ferris-proof generate formal-specs`). Edits may be overwritten.";
+
+/// Rust enum used as a state machine, with the transitions its methods perform
+///
+/// An enum is a state machine when it is annotated with
+/// `#[verification(state_machine)]`, or when at least two methods of an
+/// `impl` block in the same file consume or mutate `self` and construct one
+/// of its variants. Transitions are read from `match self` arms: the variants
+/// an arm matches are the sources and the variants its body constructs are
+/// the targets. Without a `match`, a transition may start in any state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateMachine {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Variants, the first being the initial state
+    pub states: Vec<String>,
+    /// Data carried by the states, as spec variable and Rust type
+    pub variables: Vec<(String, String)>,
+    pub transitions: Vec<Transition>,
+}
+
+/// A method moving a state machine between states
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    pub method: String,
+    pub line: usize,
+    pub arms: Vec<TransitionArm>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionArm {
+    /// States the arm applies to; empty for any state
+    pub from: Vec<String>,
+    pub to: Vec<String>,
+}
+
+impl StateMachine {
+    /// Every state machine in the `src/` tree of the package at `package_root`
+    pub fn discover(package_root: &Path) -> Result<Vec<Self>> {
+        let mut machines = Vec::new();
+        for file in rust_files(&package_root.join("src")) {
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {:?}", file))?;
+            let relative = file.strip_prefix(package_root).unwrap_or(&file);
+            machines.extend(
+                Self::from_source(&content, relative)
+                    .with_context(|| format!("Failed to parse {:?}", file))?,
+            );
+        }
+        Ok(machines)
+    }
+
+    /// State machines declared in one Rust source file
+    pub fn from_source(content: &str, file: &Path) -> Result<Vec<Self>> {
+        let ast = syn::parse_file(content)?;
+        let mut enums = Vec::new();
+        let mut impls = Vec::new();
+        collect_items(&ast.items, &mut enums, &mut impls);
+
+        let mut machines = Vec::new();
+        for item in enums {
+            let annotated = is_state_machine(&item.attrs);
+            let name = item.ident.to_string();
+            let states: Vec<String> = item.variants.iter().map(|v| v.ident.to_string()).collect();
+            let transitions: Vec<Transition> = impls
+                .iter()
+                .filter(|imp| self_type_name(imp).as_deref() == Some(name.as_str()))
+                .flat_map(|imp| transitions(imp, &name, &states, content))
+                .collect();
+
+            if states.is_empty() || (!annotated && transitions.len() < 2) {
+                continue;
+            }
+            debug!(
+                "Found state machine {} with {} transition(s)",
+                name,
+                transitions.len()
+            );
+            machines.push(Self {
+                line: declaration_line(content, &format!(r"enum\s+{}", regex::escape(&name))),
+                variables: variables(item),
+                file: file.to_path_buf(),
+                name,
+                states,
+                transitions,
+            });
+        }
+        Ok(machines)
+    }
+
+    /// Default spec location, `specs/formal/tla/<Name>.tla` or `output_dir/<Name>.tla`
+    pub fn spec_path(&self, project_root: &Path, output_dir: Option<&Path>) -> PathBuf {
+        let dir = match output_dir {
+            Some(dir) => dir.to_path_buf(),
+            None => project_root.join("specs").join("formal").join("tla"),
+        };
+        dir.join(format!("{}.tla", self.name))
+    }
+
+    /// Skeleton TLA+ module with variables, `Init` and `Next` for the machine
+    pub fn render_tla(&self) -> String {
+        let states = |names: &[String]| {
+            names
+                .iter()
+                .map(|s| format!("\"{}\"", s))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let data: Vec<&str> = self.variables.iter().map(|(v, _)| v.as_str()).collect();
+        let vars = std::iter::once("state")
+            .chain(data.iter().copied())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut out = String::new();
+        out.push_str(GENERATED_HEADER);
+        out.push_str(&format!("\n---- MODULE {} ----\n", self.name));
+        out.push_str(&format!(
+            "\\* Skeleton derived from enum {} ({}:{})\n",
+            self.name,
+            self.file.display(),
+            self.line
+        ));
+        out.push_str("EXTENDS Naturals, Sequences, TLC\n\n");
+        if !data.is_empty() {
+            out.push_str("\\* Value of state data the current state does not carry\n");
+            out.push_str("CONSTANT NoValue\n\n");
+        }
+
+        out.push_str(&format!("States == {{{}}}\n\n", states(&self.states)));
+        out.push_str(&format!("VARIABLES state \\* variant of {}\n", self.name));
+        for (variable, ty) in &self.variables {
+            out.push_str(&format!("VARIABLES {} \\* {}\n", variable, ty));
+        }
+        out.push_str(&format!("\nvars == <<{}>>\n\n", vars));
+
+        out.push_str("TypeOK ==\n    /\\ state \\in States\n");
+        for (variable, ty) in &self.variables {
+            out.push_str(&format!(
+                "    \\* TODO: constrain {} to the values of `{}` or NoValue\n",
+                variable, ty
+            ));
+        }
+
+        out.push_str(&format!(
+            "\nInit ==\n    /\\ state = \"{}\"\n",
+            self.states[0]
+        ));
+        for variable in &data {
+            out.push_str(&format!("    /\\ {} = NoValue\n", variable));
+        }
+
+        let mut actions = Vec::new();
+        for transition in &self.transitions {
+            let action = action_name(&transition.method, &actions);
+            out.push_str(&format!(
+                "\n\\* {}::{} ({}:{})\n{} ==\n",
+                self.name,
+                transition.method,
+                self.file.display(),
+                transition.line,
+                action
+            ));
+            let arm_count = transition.arms.len();
+            for arm in &transition.arms {
+                let (bullet, indent) = if arm_count > 1 {
+                    ("    \\/ ", "       ")
+                } else {
+                    ("    ", "    ")
+                };
+                let guard = if arm.from.is_empty() {
+                    "state \\in States".to_string()
+                } else {
+                    format!("state \\in {{{}}}", states(&arm.from))
+                };
+                let next = match arm.to.as_slice() {
+                    [target] => format!("state' = \"{}\"", target),
+                    targets => format!("state' \\in {{{}}}", states(targets)),
+                };
+                out.push_str(&format!(
+                    "{}/\\ {}\n{}/\\ {}\n",
+                    bullet, guard, indent, next
+                ));
+                if !data.is_empty() {
+                    out.push_str(&format!(
+                        "{}/\\ UNCHANGED <<{}>> \\* TODO: update the data {} changes\n",
+                        indent,
+                        data.join(", "),
+                        transition.method
+                    ));
+                }
+            }
+            actions.push(action);
+        }
+
+        out.push_str("\nNext ==\n");
+        if actions.is_empty() {
+            out.push_str("    UNCHANGED vars \\* TODO: no transitions were found\n");
+        }
+        for action in &actions {
+            out.push_str(&format!("    \\/ {}\n", action));
+        }
+
+        out.push_str("\nSpec == Init /\\ [][Next]_vars\n\n");
+        out.push_str("\\* TODO: state the invariants the machine must keep, e.g.\n");
+        out.push_str("\\* Safety == state = \"...\" => ...\n");
+        out.push_str("Invariants == TypeOK\n\n");
+        out.push_str("====\n");
+        out
+    }
+}
+
+/// Write a generated spec, refusing to replace a hand-written one
+///
+/// Returns `false` when an existing file without the generated header was
+/// left untouched.
+pub fn write_generated(path: &Path, content: &str) -> Result<bool> {
+    if path.exists() {
+        let existing = std::fs::read_to_string(path)?;
+        if !existing.starts_with(GENERATED_HEADER) {
+            debug!("Keeping hand-written spec at {:?}", path);
+            return Ok(false);
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content).map_err(|e| anyhow!("Failed to write {:?}: {}", path, e))?;
+    Ok(true)
+}
+
+fn collect_items<'a>(
+    items: &'a [Item],
+    enums: &mut Vec<&'a ItemEnum>,
+    impls: &mut Vec<&'a ItemImpl>,
+) {
+    for item in items {
+        match item {
+            Item::Enum(e) => enums.push(e),
+            Item::Impl(imp) if imp.trait_.is_none() => impls.push(imp),
+            Item::Mod(m) if !is_cfg_test(&m.attrs) => {
+                if let Some((_, nested)) = &m.content {
+                    collect_items(nested, enums, impls);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether `#[verification(...)]` lists `state_machine`
+fn is_state_machine(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("verification"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|meta| meta.path().is_ident("state_machine"))
+}
+
+fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg")
+            && attr
+                .parse_args::<syn::Ident>()
+                .is_ok_and(|ident| ident == "test")
+    })
+}
+
+fn self_type_name(imp: &ItemImpl) -> Option<String> {
+    match imp.self_ty.as_ref() {
+        syn::Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Spec variables for the fields of every variant, named after the field
+///
+/// Fields of tuple variants are named `<variant>_<index>` in snake case.
+fn variables(item: &ItemEnum) -> Vec<(String, String)> {
+    let mut seen = BTreeSet::new();
+    let mut variables = Vec::new();
+    for variant in &item.variants {
+        for (index, field) in variant.fields.iter().enumerate() {
+            let name = match &field.ident {
+                Some(ident) => ident.to_string(),
+                None => format!("{}_{}", snake_case(&variant.ident.to_string()), index),
+            };
+            if seen.insert(name.clone()) {
+                let ty = field.ty.to_token_stream().to_string().replace(' ', "");
+                variables.push((name, ty));
+            }
+        }
+    }
+    variables
+}
+
+/// Methods of `imp` that consume or mutate `self` and construct a state
+fn transitions(
+    imp: &ItemImpl,
+    enum_name: &str,
+    states: &[String],
+    content: &str,
+) -> Vec<Transition> {
+    imp.items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Fn(method) => Some(method),
+            _ => None,
+        })
+        .filter(|method| {
+            matches!(
+                method.sig.inputs.first(),
+                Some(FnArg::Receiver(receiver))
+                    if receiver.reference.is_none() || receiver.mutability.is_some()
+            )
+        })
+        .filter_map(|method| {
+            let arms = match find_self_match(&method.block.stmts) {
+                Some(arms) => match_arms(arms, enum_name, states),
+                None => vec![TransitionArm {
+                    from: Vec::new(),
+                    to: variant_refs(method.block.to_token_stream(), enum_name, states),
+                }],
+            };
+            let arms: Vec<TransitionArm> =
+                arms.into_iter().filter(|arm| !arm.to.is_empty()).collect();
+            if arms.is_empty() {
+                return None;
+            }
+            let name = method.sig.ident.to_string();
+            Some(Transition {
+                line: declaration_line(content, &format!(r"fn\s+{}\b", regex::escape(&name))),
+                method: name,
+                arms,
+            })
+        })
+        .collect()
+}
+
+/// Arms of the first `match self` in a method body
+fn find_self_match(stmts: &[Stmt]) -> Option<&[syn::Arm]> {
+    stmts.iter().find_map(|stmt| match stmt {
+        Stmt::Expr(expr, _) => self_match(expr),
+        Stmt::Local(local) => local.init.as_ref().and_then(|init| self_match(&init.expr)),
+        _ => None,
+    })
+}
+
+fn self_match(expr: &Expr) -> Option<&[syn::Arm]> {
+    match expr {
+        Expr::Match(m) if is_self(&m.expr) => Some(&m.arms),
+        Expr::Assign(assign) => self_match(&assign.right),
+        Expr::Block(block) => find_self_match(&block.block.stmts),
+        Expr::Paren(paren) => self_match(&paren.expr),
+        Expr::Return(ret) => ret.expr.as_deref().and_then(self_match),
+        _ => None,
+    }
+}
+
+/// `self`, `*self`, `&self` or `&mut self`
+fn is_self(expr: &Expr) -> bool {
+    match expr {
+        Expr::Path(path) => path.path.is_ident("self"),
+        Expr::Unary(unary) => is_self(&unary.expr),
+        Expr::Reference(reference) => is_self(&reference.expr),
+        Expr::Paren(paren) => is_self(&paren.expr),
+        _ => false,
+    }
+}
+
+/// Sources and targets of each arm; a catch-all arm covers the states no
+/// earlier arm matched
+fn match_arms(arms: &[syn::Arm], enum_name: &str, states: &[String]) -> Vec<TransitionArm> {
+    let mut matched = BTreeSet::new();
+    arms.iter()
+        .map(|arm| {
+            let mut from = variant_refs(arm.pat.to_token_stream(), enum_name, states);
+            if from.is_empty() {
+                from = states
+                    .iter()
+                    .filter(|s| !matched.contains(*s))
+                    .cloned()
+                    .collect();
+            }
+            matched.extend(from.iter().cloned());
+            TransitionArm {
+                from,
+                to: variant_refs(arm.body.to_token_stream(), enum_name, states),
+            }
+        })
+        .collect()
+}
+
+/// Variants named as `Self::Variant` or `Enum::Variant`, in state order
+fn variant_refs(tokens: TokenStream, enum_name: &str, states: &[String]) -> Vec<String> {
+    fn visit(tokens: TokenStream, enum_name: &str, found: &mut BTreeSet<String>) {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                TokenTree::Group(group) => visit(group.stream(), enum_name, found),
+                TokenTree::Ident(ident) if *ident == "Self" || *ident == enum_name => {
+                    if let [TokenTree::Punct(a), TokenTree::Punct(b), TokenTree::Ident(variant), ..] =
+                        &tokens[i + 1..]
+                    {
+                        if a.as_char() == ':' && b.as_char() == ':' {
+                            found.insert(variant.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut found = BTreeSet::new();
+    visit(tokens, enum_name, &mut found);
+    states
+        .iter()
+        .filter(|s| found.contains(*s))
+        .cloned()
+        .collect()
+}
+
+/// `open_door` -> `OpenDoor`, suffixed when it clashes with a spec definition
+fn action_name(method: &str, taken: &[String]) -> String {
+    let mut name: String = method
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+    let reserved = ["Init", "Next", "Spec", "TypeOK", "Invariants", "States"];
+    while reserved.contains(&name.as_str()) || taken.contains(&name) {
+        name.push('_');
+    }
+    name
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// 1-based line of the first match of `pattern`, falling back to the first line
+fn declaration_line(content: &str, pattern: &str) -> usize {
+    Regex::new(&format!(r"\b{}", pattern))
+        .ok()
+        .and_then(|re| re.find(content))
+        .map_or(1, |m| content[..m.start()].matches('\n').count() + 1)
+}
+
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    fn visit(dir: &Path, files: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                visit(&path, files);
+            } else if path.extension().and_then(|s| s.to_str()) == Some("rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    visit(dir, &mut files);
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOOR: &str = r#"
+#[derive(Debug)]
+#[verification(state_machine)]
+pub enum Door {
+    Closed,
+    Open { opened_by: String },
+    Locked(u32),
+}
+
+impl Door {
+    pub fn open(self, who: String) -> Self {
+        match self {
+            Door::Closed => Door::Open { opened_by: who },
+            other => other,
+        }
+    }
+
+    pub fn close(&mut self) {
+        *self = match self {
+            Self::Open { .. } => Self::Closed,
+            Self::Locked(code) => Self::Locked(*code),
+            Self::Closed => Self::Closed,
+        };
+    }
+
+    pub fn lock(self, code: u32) -> Result<Self, Self> {
+        match self {
+            Door::Closed => Ok(Door::Locked(code)),
+            _ => Err(self),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        matches!(self, Door::Open { .. })
+    }
+}
+
+pub enum Color {
+    Red,
+    Green,
+}
+
+impl Color {
+    pub fn invert(self) -> Self {
+        match self {
+            Color::Red => Color::Green,
+            Color::Green => Color::Red,
+        }
+    }
+}
+"#;
+
+    #[test]
+    fn test_state_machine_from_annotated_enum() {
+        let machines = StateMachine::from_source(DOOR, Path::new("src/door.rs")).unwrap();
+        // Color has a single transition method and no annotation
+        assert_eq!(machines.len(), 1);
+        let door = &machines[0];
+        assert_eq!(door.states, ["Closed", "Open", "Locked"]);
+        assert_eq!(door.line, 4);
+        assert_eq!(
+            door.variables,
+            [
+                ("opened_by".to_string(), "String".to_string()),
+                ("locked_0".to_string(), "u32".to_string())
+            ]
+        );
+
+        let methods: Vec<&str> = door.transitions.iter().map(|t| t.method.as_str()).collect();
+        assert_eq!(methods, ["open", "close", "lock"]);
+        let open = &door.transitions[0];
+        assert_eq!(open.arms.len(), 1);
+        assert_eq!(open.arms[0].from, ["Closed"]);
+        assert_eq!(open.arms[0].to, ["Open"]);
+        let close = &door.transitions[1];
+        assert_eq!(close.arms[0].from, ["Open"]);
+        assert_eq!(close.arms[0].to, ["Closed"]);
+        assert_eq!(close.arms.len(), 3);
+    }
+
+    #[test]
+    fn test_render_tla_skeleton() {
+        let machines = StateMachine::from_source(DOOR, Path::new("src/door.rs")).unwrap();
+        let spec = machines[0].render_tla();
+
+        assert!(spec.starts_with(GENERATED_HEADER));
+        assert!(spec.contains("---- MODULE Door ----"));
+        assert!(spec.contains("States == {\"Closed\", \"Open\", \"Locked\"}"));
+        assert!(spec.contains("VARIABLES opened_by \\* String"));
+        assert!(spec.contains("vars == <<state, opened_by, locked_0>>"));
+        assert!(spec.contains("Init ==\n    /\\ state = \"Closed\"\n    /\\ opened_by = NoValue"));
+        assert!(
+            spec.contains("Open ==\n    /\\ state \\in {\"Closed\"}\n    /\\ state' = \"Open\"")
+        );
+        assert!(spec.contains("\\/ /\\ state \\in {\"Open\"}\n       /\\ state' = \"Closed\""));
+        assert!(spec.contains("Next ==\n    \\/ Open\n    \\/ Close\n    \\/ Lock\n"));
+        assert!(spec.contains("Spec == Init /\\ [][Next]_vars"));
+        assert!(spec.contains("\\* TODO: state the invariants"));
+        assert!(spec.trim_end().ends_with("===="));
+    }
+
+    #[test]
+    fn test_unannotated_enum_needs_two_transitions() {
+        let source = r#"
+pub enum Light { Off, On, Broken }
+
+impl Light {
+    pub fn toggle(self) -> Self {
+        match self {
+            Light::Off => Light::On,
+            Light::On => Light::Off,
+            Light::Broken => Light::Broken,
+        }
+    }
+
+    pub fn smash(&mut self) {
+        *self = Light::Broken;
+    }
+}
+"#;
+        let machines = StateMachine::from_source(source, Path::new("src/light.rs")).unwrap();
+        assert_eq!(machines.len(), 1);
+        let smash = &machines[0].transitions[1];
+        assert!(smash.arms[0].from.is_empty());
+        assert_eq!(smash.arms[0].to, ["Broken"]);
+        assert!(machines[0]
+            .render_tla()
+            .contains("Smash ==\n    /\\ state \\in States\n    /\\ state' = \"Broken\""));
+    }
+}
@@ -0,0 +1,425 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// Header written at the top of every generated `.cfg` file
+///
+/// Files carrying this header are considered owned by FerrisProof and may be
+/// regenerated; hand-written configurations are never overwritten.
+pub const GENERATED_HEADER: &str = "\\* Generated by ferris-proof. Edits may be overwritten.";
+
+/// TLC model description used to render a `.cfg` file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlcModel {
+    pub specification: Option<String>,
+    pub init: Option<String>,
+    pub next: Option<String>,
+    pub constants: BTreeMap<String, String>,
+    pub unassigned_constants: Vec<String>,
+    pub invariants: Vec<String>,
+    pub properties: Vec<String>,
+}
+
+/// Overrides for a model, typically read from `[tools.tla_plus.models]`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TlcModelOverrides {
+    pub specification: Option<String>,
+    pub init: Option<String>,
+    pub next: Option<String>,
+    pub constants: BTreeMap<String, String>,
+    pub invariants: Vec<String>,
+    pub properties: Vec<String>,
+}
+
+impl TlcModel {
+    /// Build a model from a TLA+ module's declarations and annotations
+    ///
+    /// Recognised annotations are line comments of the form:
+    ///
+    /// ```text
+    /// \* @constant Nodes = {n1, n2, n3}
+    /// \* @invariant TypeOK
+    /// \* @property AllNodesComplete
+    /// ```
+    ///
+    /// Without annotations, `Spec` (or `Init`/`Next`) and `TypeOK` definitions
+    /// are picked up by convention.
+    pub fn from_spec(content: &str) -> Self {
+        let mut model = TlcModel::default();
+        let definition_regex = Regex::new(r"^([A-Za-z_][A-Za-z0-9_]*)\s*==").unwrap();
+        let mut definitions = Vec::new();
+        let mut declared_constants = Vec::new();
+        let mut in_constants = false;
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim();
+
+            if let Some(annotation) = line.strip_prefix("\\*").map(str::trim) {
+                if let Some(rest) = annotation.strip_prefix('@') {
+                    model.apply_annotation(rest);
+                }
+                continue;
+            }
+
+            if let Some(captures) = definition_regex.captures(line) {
+                definitions.push(captures[1].to_string());
+                in_constants = false;
+                continue;
+            }
+
+            let declaration = line
+                .strip_prefix("CONSTANTS")
+                .or_else(|| line.strip_prefix("CONSTANT"));
+            if let Some(names) = declaration {
+                in_constants = true;
+                declared_constants.extend(parse_constant_names(names));
+                continue;
+            }
+
+            if in_constants {
+                if line.is_empty() || starts_with_keyword(line) {
+                    in_constants = false;
+                } else {
+                    declared_constants.extend(parse_constant_names(line));
+                }
+            }
+        }
+
+        if model.specification.is_none() && model.init.is_none() {
+            if definitions.iter().any(|d| d == "Spec") {
+                model.specification = Some("Spec".to_string());
+            } else if definitions.iter().any(|d| d == "Init")
+                && definitions.iter().any(|d| d == "Next")
+            {
+                model.init = Some("Init".to_string());
+                model.next = Some("Next".to_string());
+            }
+        }
+
+        if model.invariants.is_empty() && definitions.iter().any(|d| d == "TypeOK") {
+            model.invariants.push("TypeOK".to_string());
+        }
+
+        for name in declared_constants {
+            if !model.constants.contains_key(&name) && !model.unassigned_constants.contains(&name) {
+                model.unassigned_constants.push(name);
+            }
+        }
+
+        model
+    }
+
+    fn apply_annotation(&mut self, annotation: &str) {
+        let (kind, rest) = annotation
+            .split_once(char::is_whitespace)
+            .unwrap_or((annotation, ""));
+        let rest = rest.trim();
+
+        match kind {
+            "constant" => {
+                if let Some((name, value)) = rest.split_once('=') {
+                    self.constants
+                        .insert(name.trim().to_string(), value.trim().to_string());
+                }
+            }
+            "invariant" if !rest.is_empty() => self.invariants.push(rest.to_string()),
+            "property" if !rest.is_empty() => self.properties.push(rest.to_string()),
+            "specification" if !rest.is_empty() => self.specification = Some(rest.to_string()),
+            _ => debug!("Ignoring unknown TLC annotation: @{}", annotation),
+        }
+    }
+
+    /// Apply configuration overrides on top of the annotated model
+    pub fn with_overrides(mut self, overrides: &TlcModelOverrides) -> Self {
+        if overrides.specification.is_some() {
+            self.specification = overrides.specification.clone();
+            self.init = None;
+            self.next = None;
+        }
+        if overrides.init.is_some() || overrides.next.is_some() {
+            self.specification = None;
+            self.init = overrides.init.clone().or(self.init);
+            self.next = overrides.next.clone().or(self.next);
+        }
+
+        for (name, value) in &overrides.constants {
+            self.unassigned_constants.retain(|c| c != name);
+            self.constants.insert(name.clone(), value.clone());
+        }
+
+        if !overrides.invariants.is_empty() {
+            self.invariants = overrides.invariants.clone();
+        }
+        if !overrides.properties.is_empty() {
+            self.properties = overrides.properties.clone();
+        }
+
+        self
+    }
+
+    /// Render the model as TLC `.cfg` content
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(GENERATED_HEADER);
+        out.push_str("\n\n");
+
+        if let Some(specification) = &self.specification {
+            out.push_str(&format!("SPECIFICATION {}\n", specification));
+        } else {
+            if let Some(init) = &self.init {
+                out.push_str(&format!("INIT {}\n", init));
+            }
+            if let Some(next) = &self.next {
+                out.push_str(&format!("NEXT {}\n", next));
+            }
+        }
+
+        if !self.constants.is_empty() || !self.unassigned_constants.is_empty() {
+            out.push_str("\nCONSTANTS\n");
+            for (name, value) in &self.constants {
+                out.push_str(&format!("    {} = {}\n", name, value));
+            }
+            for name in &self.unassigned_constants {
+                out.push_str(&format!(
+                    "    \\* {}: no value annotated; add \"\\* @constant {} = ...\" to the spec\n",
+                    name, name
+                ));
+            }
+        }
+
+        if !self.invariants.is_empty() {
+            out.push('\n');
+            for invariant in &self.invariants {
+                out.push_str(&format!("INVARIANT {}\n", invariant));
+            }
+        }
+
+        if !self.properties.is_empty() {
+            out.push('\n');
+            for property in &self.properties {
+                out.push_str(&format!("PROPERTY {}\n", property));
+            }
+        }
+
+        out
+    }
+}
+
+/// Default `.cfg` location for a spec, as looked up by TLC
+pub fn default_cfg_path(spec_path: &Path) -> PathBuf {
+    spec_path.with_extension("cfg")
+}
+
+/// Write generated `.cfg` content, refusing to replace hand-written files
+///
+/// Returns `false` when an existing file without the generated header was left
+/// untouched.
+pub fn write_cfg(path: &Path, content: &str) -> Result<bool> {
+    if path.exists() {
+        let existing = std::fs::read_to_string(path)?;
+        if !existing.starts_with(GENERATED_HEADER) {
+            debug!("Keeping hand-written TLC config at {:?}", path);
+            return Ok(false);
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)
+        .map_err(|e| anyhow!("Failed to write TLC config {:?}: {}", path, e))?;
+    Ok(true)
+}
+
+/// Render a named model for a spec to `<name>.cfg` next to it
+pub fn write_model_cfg(
+    spec_path: &Path,
+    name: &str,
+    overrides: &TlcModelOverrides,
+) -> Result<PathBuf> {
+    let dir = spec_path.parent().unwrap_or(Path::new(""));
+    write_model_cfg_in(spec_path, name, overrides, dir)
+}
+
+/// Render a named model for a spec to `<name>.cfg` in `dir`
+pub fn write_model_cfg_in(
+    spec_path: &Path,
+    name: &str,
+    overrides: &TlcModelOverrides,
+    dir: &Path,
+) -> Result<PathBuf> {
+    let content = std::fs::read_to_string(spec_path)
+        .map_err(|e| anyhow!("Failed to read TLA+ spec {:?}: {}", spec_path, e))?;
+    let model = TlcModel::from_spec(&content).with_overrides(overrides);
+
+    let cfg_path = dir.join(format!("{}.cfg", name));
+    if write_cfg(&cfg_path, &model.render())? {
+        debug!("Rendered TLC model '{}' to {:?}", name, cfg_path);
+    }
+    Ok(cfg_path)
+}
+
+/// Generate a default `.cfg` next to the spec if none exists
+pub fn ensure_default_cfg(spec_path: &Path) -> Result<PathBuf> {
+    let cfg_path = default_cfg_path(spec_path);
+    if cfg_path.exists() {
+        return Ok(cfg_path);
+    }
+    generate_default_cfg(spec_path, cfg_path)
+}
+
+/// Use the `.cfg` next to the spec if one exists, otherwise generate one in `dir`
+///
+/// For hermetic runs, which must not write next to their inputs.
+pub fn ensure_default_cfg_in(spec_path: &Path, dir: &Path) -> Result<PathBuf> {
+    let existing = default_cfg_path(spec_path);
+    if existing.exists() {
+        return Ok(existing);
+    }
+    let file_name = existing
+        .file_name()
+        .ok_or_else(|| anyhow!("TLA+ spec path {:?} has no file name", spec_path))?;
+    let cfg_path = dir.join(file_name);
+    if cfg_path.exists() {
+        return Ok(cfg_path);
+    }
+    generate_default_cfg(spec_path, cfg_path)
+}
+
+fn generate_default_cfg(spec_path: &Path, cfg_path: PathBuf) -> Result<PathBuf> {
+    let content = std::fs::read_to_string(spec_path)
+        .map_err(|e| anyhow!("Failed to read TLA+ spec {:?}: {}", spec_path, e))?;
+    let model = TlcModel::from_spec(&content);
+    write_cfg(&cfg_path, &model.render())?;
+
+    info!("Generated default TLC config at {:?}", cfg_path);
+    Ok(cfg_path)
+}
+
+fn parse_constant_names(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|name| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+fn starts_with_keyword(line: &str) -> bool {
+    const KEYWORDS: [&str; 8] = [
+        "VARIABLE",
+        "VARIABLES",
+        "ASSUME",
+        "EXTENDS",
+        "INSTANCE",
+        "LOCAL",
+        "THEOREM",
+        "----",
+    ];
+    KEYWORDS.iter().any(|keyword| line.starts_with(keyword)) || line.starts_with("====")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"---- MODULE Example ----
+EXTENDS Naturals
+
+CONSTANTS Nodes,
+          MaxMessages
+
+\* @constant Nodes = {n1, n2}
+\* @property AllNodesComplete
+
+VARIABLES messages
+
+TypeOK == messages \in Nat
+Init == messages = 0
+Next == messages' = messages + 1
+Spec == Init /\ [][Next]_messages
+====
+"#;
+
+    #[test]
+    fn test_model_from_annotations() {
+        let model = TlcModel::from_spec(SPEC);
+
+        assert_eq!(model.specification.as_deref(), Some("Spec"));
+        assert_eq!(model.constants.get("Nodes").unwrap(), "{n1, n2}");
+        assert_eq!(model.unassigned_constants, vec!["MaxMessages"]);
+        assert_eq!(model.invariants, vec!["TypeOK"]);
+        assert_eq!(model.properties, vec!["AllNodesComplete"]);
+    }
+
+    #[test]
+    fn test_overrides_assign_constants() {
+        let mut overrides = TlcModelOverrides::default();
+        overrides
+            .constants
+            .insert("MaxMessages".to_string(), "3".to_string());
+
+        let rendered = TlcModel::from_spec(SPEC)
+            .with_overrides(&overrides)
+            .render();
+
+        assert!(rendered.starts_with(GENERATED_HEADER));
+        assert!(rendered.contains("SPECIFICATION Spec"));
+        assert!(rendered.contains("    MaxMessages = 3"));
+        assert!(rendered.contains("INVARIANT TypeOK"));
+        assert!(!rendered.contains("no value annotated"));
+    }
+
+    #[test]
+    fn test_hand_written_cfg_is_preserved() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cfg_path = temp_dir.path().join("Example.cfg");
+        std::fs::write(&cfg_path, "SPECIFICATION Custom\n").unwrap();
+
+        let written = write_cfg(&cfg_path, &TlcModel::from_spec(SPEC).render()).unwrap();
+
+        assert!(!written);
+        assert_eq!(
+            std::fs::read_to_string(&cfg_path).unwrap(),
+            "SPECIFICATION Custom\n"
+        );
+    }
+
+    #[test]
+    fn test_named_model_written_next_to_spec() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let spec_path = temp_dir.path().join("Example.tla");
+        std::fs::write(&spec_path, SPEC).unwrap();
+
+        let overrides: TlcModelOverrides = serde_json::from_value(serde_json::json!({
+            "spec": "Example.tla",
+            "constants": { "MaxMessages": "2" }
+        }))
+        .unwrap();
+        let cfg_path = write_model_cfg(&spec_path, "small", &overrides).unwrap();
+
+        assert_eq!(cfg_path, temp_dir.path().join("small.cfg"));
+        assert!(std::fs::read_to_string(&cfg_path)
+            .unwrap()
+            .contains("    MaxMessages = 2"));
+    }
+
+    #[test]
+    fn test_default_cfg_generated_in_scratch_dir() {
+        let spec_dir = tempfile::TempDir::new().unwrap();
+        let scratch_dir = tempfile::TempDir::new().unwrap();
+        let spec_path = spec_dir.path().join("Example.tla");
+        std::fs::write(&spec_path, SPEC).unwrap();
+
+        let cfg_path = ensure_default_cfg_in(&spec_path, scratch_dir.path()).unwrap();
+
+        assert_eq!(cfg_path, scratch_dir.path().join("Example.cfg"));
+        assert!(cfg_path.exists());
+        assert!(!default_cfg_path(&spec_path).exists());
+    }
+}
@@ -0,0 +1,175 @@
+//! TLC worker count and JVM heap, chosen from the host and `[tools.tla_plus]`
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::process::Command;
+
+const MIB: u64 = 1024 * 1024;
+
+/// Smallest heap TLC is started with, whatever the host reports
+pub const MIN_HEAP: u64 = 256 * MIB;
+
+/// Heap each TLC worker needs for its share of the state queue
+pub const HEAP_PER_WORKER: u64 = 64 * MIB;
+
+/// Cores and physical memory of the machine running TLC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostResources {
+    pub cores: u32,
+    /// Total memory in bytes, if the platform reports it
+    pub memory: Option<u64>,
+}
+
+impl HostResources {
+    pub fn detect() -> Self {
+        Self {
+            cores: std::thread::available_parallelism()
+                .map(|n| n.get() as u32)
+                .unwrap_or(1),
+            memory: total_memory(),
+        }
+    }
+}
+
+/// `MemTotal` from `/proc/meminfo`
+///
+/// Other platforms report nothing, so the heap falls back to the budget or
+/// [`MIN_HEAP`].
+#[cfg(target_os = "linux")]
+fn total_memory() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|l| l.starts_with("MemTotal:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn total_memory() -> Option<u64> {
+    None
+}
+
+/// TLC `-workers` and JVM `-Xmx` for one run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TlcTuning {
+    pub workers: u32,
+    /// Maximum heap in bytes
+    pub heap: u64,
+}
+
+impl TlcTuning {
+    /// Settings for `host` within `budget`, from `thresholds.max_memory_usage`
+    ///
+    /// The heap is half the host memory, leaving the rest to TLC's off-heap
+    /// fingerprint set and the OS, capped at the budget. Workers use every
+    /// core as long as each gets [`HEAP_PER_WORKER`].
+    pub fn auto(host: &HostResources, budget: Option<u64>) -> Self {
+        let heap = [host.memory.map(|memory| memory / 2), budget]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap_or(MIN_HEAP)
+            .max(MIN_HEAP);
+        Self {
+            workers: Self::workers_for(host, heap),
+            heap,
+        }
+    }
+
+    /// Automatic settings with the `workers` and `heap` of `[tools.tla_plus]`
+    /// taking precedence
+    ///
+    /// An explicit heap still sizes the automatic worker count.
+    pub fn resolve(tool_config: &Value, host: &HostResources, budget: Option<u64>) -> Self {
+        let heap = tool_config
+            .get("heap")
+            .and_then(Value::as_u64)
+            .unwrap_or_else(|| Self::auto(host, budget).heap);
+        let workers = tool_config
+            .get("workers")
+            .and_then(Value::as_u64)
+            .map(|workers| workers.max(1) as u32)
+            .unwrap_or_else(|| Self::workers_for(host, heap));
+        Self { workers, heap }
+    }
+
+    fn workers_for(host: &HostResources, heap: u64) -> u32 {
+        let affordable = u32::try_from(heap / HEAP_PER_WORKER).unwrap_or(u32::MAX);
+        host.cores.min(affordable).max(1)
+    }
+
+    /// TLC arguments, e.g. `-workers 4`
+    pub fn tlc_args(&self) -> Vec<String> {
+        vec!["-workers".to_string(), self.workers.to_string()]
+    }
+
+    /// JVM options, e.g. `-Xmx2048m`
+    pub fn java_opts(&self) -> String {
+        format!("-Xmx{}m", (self.heap / MIB).max(1))
+    }
+
+    /// Pass the settings to a `tlc` launcher, which reads `JAVA_OPTS`
+    pub fn apply(&self, cmd: &mut Command) {
+        cmd.args(self.tlc_args()).env("JAVA_OPTS", self.java_opts());
+    }
+
+    /// Settings as recorded in the run's metrics
+    pub fn settings(&self) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("workers".to_string(), self.workers.to_string()),
+            ("heap".to_string(), self.java_opts()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const GIB: u64 = 1024 * MIB;
+
+    #[test]
+    fn test_auto_tuning_within_budget() {
+        let host = HostResources {
+            cores: 16,
+            memory: Some(32 * GIB),
+        };
+
+        // Half the host memory, capped at the budget, with a worker per core
+        let tuning = TlcTuning::auto(&host, Some(2 * GIB));
+        assert_eq!(tuning.heap, 2 * GIB);
+        assert_eq!(tuning.workers, 16);
+        assert_eq!(tuning.tlc_args(), ["-workers", "16"]);
+        assert_eq!(tuning.java_opts(), "-Xmx2048m");
+
+        // A small heap limits the workers it can feed
+        let small = HostResources {
+            cores: 16,
+            memory: Some(512 * MIB),
+        };
+        let tuning = TlcTuning::auto(&small, None);
+        assert_eq!(tuning.heap, MIN_HEAP);
+        assert_eq!(tuning.workers, 4);
+    }
+
+    #[test]
+    fn test_configured_values_override() {
+        let host = HostResources {
+            cores: 8,
+            memory: Some(16 * GIB),
+        };
+
+        let tuning = TlcTuning::resolve(&json!({ "workers": 3 }), &host, Some(4 * GIB));
+        assert_eq!(
+            tuning,
+            TlcTuning {
+                workers: 3,
+                heap: 4 * GIB
+            }
+        );
+
+        let tuning = TlcTuning::resolve(&json!({ "heap": 128 * MIB }), &host, Some(4 * GIB));
+        assert_eq!(tuning.workers, 2);
+        assert_eq!(tuning.settings()["heap"], "-Xmx128m");
+    }
+}
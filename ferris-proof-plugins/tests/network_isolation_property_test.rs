@@ -1,3 +1,4 @@
+use ferris_proof_core::plugins::ToolEnv;
 use ferris_proof_plugins::sandbox::{NetworkPolicy, ResourceLimits, SandboxedExecutor};
 use proptest::prelude::*;
 use std::collections::HashMap;
@@ -258,4 +259,65 @@ mod network_isolation_tests {
             );
         }
     }
+
+    #[tokio::test]
+    /// With namespace isolation, denied commands only see the loopback interface
+    async fn test_denied_policy_uses_network_namespace() {
+        let executor = SandboxedExecutor::new()
+            .with_network_policy(NetworkPolicy::Denied)
+            .with_timeout(Duration::from_secs(5));
+
+        if !executor.enforces_network_isolation() {
+            // Unprivileged namespaces are unavailable (e.g. non-Linux or locked-down CI)
+            return;
+        }
+
+        let output = executor
+            .execute("cat", &["/proc/net/dev"], HashMap::new(), None)
+            .await
+            .unwrap();
+
+        assert_eq!(output.exit_code, 0, "stderr: {}", output.stderr);
+        let interfaces: Vec<&str> = output
+            .stdout
+            .lines()
+            .filter_map(|line| line.split_once(':').map(|(name, _)| name.trim()))
+            .collect();
+        assert_eq!(interfaces, ["lo"]);
+    }
+
+    #[tokio::test]
+    /// Tools see only the base variables and those their `[tools.*.env]` passes or sets
+    async fn test_environment_is_scrubbed_to_tool_env() {
+        std::env::set_var("FP_SANDBOX_TEST_SECRET", "leaked");
+        std::env::set_var("FP_SANDBOX_TEST_JAVA_HOME", "/opt/java");
+        let tool_env = ToolEnv {
+            pass: vec!["FP_SANDBOX_TEST_JAVA_HOME".to_string()],
+            set: [("TLA_TOOLS".to_string(), "/opt/tla".to_string())].into(),
+        };
+        let executor = SandboxedExecutor::new()
+            .with_network_policy(NetworkPolicy::Denied)
+            .with_timeout(Duration::from_secs(5));
+
+        let output = executor
+            .execute("env", &[], tool_env.vars(), None)
+            .await
+            .unwrap();
+        assert!(output
+            .stdout
+            .contains("FP_SANDBOX_TEST_JAVA_HOME=/opt/java"));
+        assert!(output.stdout.contains("TLA_TOOLS=/opt/tla"));
+        assert!(!output.stdout.contains("FP_SANDBOX_TEST_SECRET"));
+
+        // Proxies contradict a denied network policy
+        let proxied = ToolEnv {
+            set: [("https_proxy".to_string(), "http://proxy:3128".to_string())].into(),
+            ..ToolEnv::default()
+        };
+        let error = executor
+            .execute("env", &[], proxied.vars(), None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("denies network access"));
+    }
 }
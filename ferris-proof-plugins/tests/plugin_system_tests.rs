@@ -1,5 +1,5 @@
 use ferris_proof_core::plugins::{PluginManager, ValidationStatus, VerificationPlugin};
-use ferris_proof_plugins::sandbox::{NetworkPolicy, ResourceLimits};
+use ferris_proof_plugins::sandbox::{NetworkPolicy, ResourceLimits, ResourceUsage};
 use ferris_proof_plugins::{ProptestPlugin, SandboxedExecutor, TlaPlusPlugin};
 use serde_json::json;
 use std::collections::HashMap;
@@ -265,6 +265,13 @@ mod plugin_system_tests {
                     "Output should contain expected text"
                 );
                 assert!(!output.timeout_occurred, "Command should not timeout");
+
+                #[cfg(unix)]
+                assert!(
+                    output.resource_usage.peak_memory > 0,
+                    "Peak memory should be measured on Unix"
+                );
+                assert!(output.resource_usage.processes_spawned >= 1);
             }
             Err(e) => {
                 // Command might not be available in test environment
@@ -280,6 +287,27 @@ mod plugin_system_tests {
         }
     }
 
+    #[test]
+    /// Test resource usage threshold checks
+    fn test_resource_usage_exceeded_limits() {
+        let limits = ResourceLimits {
+            max_memory: 1024,
+            max_cpu_time: 1,
+            ..ResourceLimits::default()
+        };
+        let usage = ResourceUsage {
+            peak_memory: 4096,
+            cpu_time: Duration::from_millis(500),
+            file_descriptors_used: 0,
+            processes_spawned: 1,
+        };
+
+        let exceeded = usage.exceeded_limits(&limits);
+        assert_eq!(exceeded.len(), 1);
+        assert!(exceeded[0].contains("peak memory"));
+        assert!(ResourceUsage::default().exceeded_limits(&limits).is_empty());
+    }
+
     #[tokio::test]
     /// Test sandboxed execution timeout handling
     /// Validates: Requirements 12.4